@@ -1,5 +1,5 @@
 use anyhow::{Context, Error, Result};
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::{
     ffi::OsStr,
     net::SocketAddr,
@@ -38,8 +38,20 @@ impl FromStr for KeyValue {
 
 /// The Wasmi CLI application arguments.
 #[derive(Parser, Debug)]
-#[clap(author, version, about, long_about = None, trailing_var_arg = true)]
+#[clap(
+    author,
+    version,
+    about,
+    long_about = None,
+    trailing_var_arg = true,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true,
+)]
 pub struct Args {
+    /// The optional subcommand to run instead of executing a Wasm module.
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     /// The host directory to pre-open for the `guest` to use.
     #[clap(
         long = "dir",
@@ -67,20 +79,36 @@ pub struct Args {
     envs: Vec<KeyValue>,
 
     /// The file containing the WebAssembly module to execute.
+    ///
+    /// This can either be a binary `.wasm` or a text `.wat` file.
     #[clap(
         value_name = "MODULE",
         value_hint = clap::ValueHint::FilePath,
+        required = true,
     )]
-    wasm_file: PathBuf,
+    wasm_file: Option<PathBuf>,
 
-    /// The function to invoke.
+    /// The function to invoke followed by its arguments.
+    ///
+    /// May be given multiple times to invoke several functions one after
+    /// another on the same instance, e.g. `--invoke add 1 2 --invoke get`.
+    ///
+    /// Integer arguments may be given as decimals or `0x` prefixed hexadecimals,
+    /// float arguments as decimals, `inf`, `nan` or `0x` prefixed bit patterns
+    /// and `v128` arguments as `0x` prefixed hexadecimals or lane-wise, e.g. `i32x4:1,2,3,4`.
     ///
     /// If this argument is missing, Wasmi CLI will try to run `""` or `_start`.
     ///
     /// If neither are exported the Wasmi CLI will display out all exported
     /// functions of the Wasm module and return with an error.
-    #[clap(long = "invoke", value_name = "FUNCTION")]
-    invoke: Option<String>,
+    #[clap(
+        long = "invoke",
+        value_name = "FUNCTION",
+        num_args = 1..,
+        allow_negative_numbers = true,
+        action = clap::ArgAction::Append,
+    )]
+    invoke: Vec<Vec<String>>,
 
     /// Enable lazy Wasm compilation.
     #[clap(long = "compilation-mode", value_enum, default_value_t=CompilationMode::Eager)]
@@ -92,6 +120,31 @@ pub struct Args {
     #[clap(long = "fuel", value_name = "N")]
     fuel: Option<u64>,
 
+    /// Print the fuel consumed by each invoked function upon exit.
+    #[clap(long = "fuel-report", requires = "fuel")]
+    fuel_report: bool,
+
+    /// Write a Wasm coredump to PATH if the execution traps.
+    ///
+    /// The coredump contains the exported linear memories and global variables.
+    #[clap(
+        long = "coredump-on-trap",
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    coredump_on_trap: Option<PathBuf>,
+
+    /// Cache the compiled Wasm module in DIRECTORY to skip its translation on repeated runs.
+    ///
+    /// Cache entries are keyed by the Wasm module and the Wasmi configuration.
+    /// The DIRECTORY must be trusted since cached entries are executed without validation.
+    #[clap(
+        long = "cache-dir",
+        value_name = "DIRECTORY",
+        value_hint = clap::ValueHint::DirPath,
+    )]
+    cache_dir: Option<PathBuf>,
+
     /// Enable informational messages beyond warnings or errors.
     #[clap(long = "verbose")]
     verbose: bool,
@@ -101,6 +154,54 @@ pub struct Args {
     func_args: Vec<String>,
 }
 
+/// The Wasmi CLI subcommands.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Translates a WebAssembly text file into the binary format.
+    Wat2wasm {
+        /// The WebAssembly text file to translate.
+        #[clap(value_name = "INPUT", value_hint = clap::ValueHint::FilePath)]
+        input: PathBuf,
+        /// The output file.
+        ///
+        /// Defaults to the input file with a `.wasm` extension.
+        #[clap(
+            short = 'o',
+            long = "output",
+            value_name = "OUTPUT",
+            value_hint = clap::ValueHint::FilePath,
+        )]
+        output: Option<PathBuf>,
+    },
+    /// Translates a WebAssembly binary file into the text format.
+    Wasm2wat {
+        /// The WebAssembly binary file to translate.
+        #[clap(value_name = "INPUT", value_hint = clap::ValueHint::FilePath)]
+        input: PathBuf,
+        /// The output file.
+        ///
+        /// Defaults to printing to `stdout`.
+        #[clap(
+            short = 'o',
+            long = "output",
+            value_name = "OUTPUT",
+            value_hint = clap::ValueHint::FilePath,
+        )]
+        output: Option<PathBuf>,
+    },
+    /// Prints the translated Wasmi bytecode of a Wasm module for debugging.
+    Dump {
+        /// The Wasm module to dump.
+        ///
+        /// This can either be a binary `.wasm` or a text `.wat` file.
+        #[clap(value_name = "MODULE", value_hint = clap::ValueHint::FilePath)]
+        input: PathBuf,
+        /// Only dump the function with the given export name or function index.
+        #[clap(long = "func", value_name = "NAME|INDEX")]
+        func: Option<String>,
+    },
+}
+
 /// The chosen Wasmi compilation mode.
 #[derive(Debug, Default, Copy, Clone, ValueEnum)]
 enum CompilationMode {
@@ -121,19 +222,28 @@ impl From<CompilationMode> for wasmi::CompilationMode {
 }
 
 impl Args {
-    /// Returns the Wasm file path given to the CLI app.
-    pub fn wasm_file(&self) -> &Path {
-        &self.wasm_file
+    /// Returns the subcommand given to the CLI app if any.
+    pub fn command(&self) -> Option<&Command> {
+        self.command.as_ref()
     }
 
-    /// Returns the name of the invoked function if any.
-    pub fn invoked(&self) -> Option<&str> {
-        self.invoke.as_deref()
+    /// Returns the Wasm file path given to the CLI app.
+    ///
+    /// # Panics
+    ///
+    /// If a subcommand was given to the CLI app.
+    pub fn wasm_file(&self) -> &Path {
+        self.wasm_file
+            .as_deref()
+            .expect("the Wasm file is required without subcommand")
     }
 
-    /// Returns the function arguments given to the CLI app.
-    pub fn func_args(&self) -> &[String] {
-        &self.func_args[..]
+    /// Returns the names and arguments of the invoked functions in invocation order.
+    pub fn invocations(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.invoke
+            .iter()
+            .filter_map(|invocation| invocation.split_first())
+            .map(|(name, args)| (name.as_str(), args))
     }
 
     /// Returns the amount of fuel given to the CLI app if any.
@@ -141,6 +251,21 @@ impl Args {
         self.fuel
     }
 
+    /// Returns `true` if the fuel consumed per invoked function shall be reported.
+    pub fn fuel_report(&self) -> bool {
+        self.fuel_report
+    }
+
+    /// Returns the path to write a Wasm coredump to upon a trap if any.
+    pub fn coredump_on_trap(&self) -> Option<&Path> {
+        self.coredump_on_trap.as_deref()
+    }
+
+    /// Returns the directory to cache compiled Wasm modules in if any.
+    pub fn cache_dir(&self) -> Option<&Path> {
+        self.cache_dir.as_deref()
+    }
+
     /// Returns `true` if lazy Wasm compilation is enabled.
     pub fn compilation_mode(&self) -> wasmi::CompilationMode {
         self.compilation_mode.into()
@@ -196,7 +321,7 @@ impl Args {
         // The WebAssembly filename is expected to be the first argument to WASI.
         // Note that the module name still has it's `.wasm` file extension.
         let module_name = self
-            .wasm_file
+            .wasm_file()
             .file_name()
             .and_then(OsStr::to_str)
             .unwrap_or("")
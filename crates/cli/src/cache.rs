@@ -0,0 +1,53 @@
+use anyhow::{anyhow, Error};
+use std::{
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+};
+use wasmi::{Engine, Module};
+
+/// Creates the [`Module`] for `wasm` reusing the compiled Wasmi bytecode cached in `cache_dir`.
+///
+/// # Note
+///
+/// - Cache entries are keyed by the contents of `wasm` and the [`Config`] of the `engine`.
+/// - Upon a cache miss the `wasm` is compiled and its serialized Wasmi bytecode is stored
+///   in `cache_dir` so that repeated runs of the same module skip its translation.
+/// - Stale or mismatching cache entries are detected via [`Module::hash`] and replaced.
+///
+/// # Errors
+///
+/// - If parsing, validating or compiling the Wasm module failed.
+/// - If the compiled Wasmi bytecode cannot be written to `cache_dir`.
+///
+/// [`Config`]: wasmi::Config
+pub fn module_with_cache(engine: &Engine, wasm: &[u8], cache_dir: &Path) -> Result<Module, Error> {
+    let path = cache_path(engine, wasm, cache_dir);
+    if let Ok(code) = fs::read(&path) {
+        // Safety: the cache directory is trusted to only contain serialized Wasmi bytecode
+        //         written by this function which is checked to belong to `wasm` and to the
+        //         `Config` and build of Wasmi via its `Module::hash`.
+        if let Ok(module) = unsafe { Module::new_with_serialized_code(engine, wasm, &code) } {
+            return Ok(module);
+        }
+    }
+    let module = Module::new(engine, wasm)
+        .map_err(|error| anyhow!("failed to parse and validate Wasm module: {error}"))?;
+    let code = module
+        .serialize_code()
+        .map_err(|error| anyhow!("failed to serialize the compiled Wasm module: {error}"))?;
+    fs::create_dir_all(cache_dir)
+        .map_err(|error| anyhow!("failed to create cache directory {cache_dir:?}: {error}"))?;
+    fs::write(&path, code)
+        .map_err(|error| anyhow!("failed to write cache entry {path:?}: {error}"))?;
+    Ok(module)
+}
+
+/// Returns the path of the cache entry for `wasm` compiled by `engine` within `cache_dir`.
+fn cache_path(engine: &Engine, wasm: &[u8], cache_dir: &Path) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    format!("{:?}", engine.config()).hash(&mut hasher);
+    wasm.hash(&mut hasher);
+    cache_dir.join(format!("{:016x}.wasmi", hasher.finish()))
+}
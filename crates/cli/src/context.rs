@@ -1,8 +1,17 @@
+use crate::cache;
 use anyhow::{anyhow, Error};
 use std::{fs, path::Path};
 use wasmi::{CompilationMode, Config, ExternType, Func, FuncType, Instance, Module, Store};
 use wasmi_wasi::WasiCtx;
 
+/// Returns the base [`Config`] of the Wasmi CLI application.
+pub fn config() -> Config {
+    let mut config = Config::default();
+    config.wasm_custom_page_sizes(true);
+    config.wasm_wide_arithmetic(true);
+    config
+}
+
 /// The [`Context`] for the Wasmi CLI application.
 ///
 /// This simply stores all the necessary data.
@@ -22,25 +31,29 @@ impl Context {
     ///
     /// - If parsing, validating, compiling or instantiating the Wasm module failed.
     /// - If adding WASI definitions to the linker failed.
+    /// - If the compiled Wasm module cannot be written to the `cache_dir`.
     pub fn new(
         wasm_file: &Path,
         wasi_ctx: WasiCtx,
         fuel: Option<u64>,
         compilation_mode: CompilationMode,
+        cache_dir: Option<&Path>,
     ) -> Result<Self, Error> {
-        let mut config = Config::default();
+        let mut config = config();
         if fuel.is_some() {
             config.consume_fuel(true);
         }
         config.compilation_mode(compilation_mode);
-        config.wasm_custom_page_sizes(true);
-        config.wasm_wide_arithmetic(true);
         let engine = wasmi::Engine::new(&config);
         let wasm =
             fs::read(wasm_file).map_err(|_| anyhow!("failed to read Wasm file {wasm_file:?}"))?;
-        let module = wasmi::Module::new(&engine, wasm).map_err(|error| {
-            anyhow!("failed to parse and validate Wasm module {wasm_file:?}: {error}")
-        })?;
+        let module = match cache_dir {
+            Some(cache_dir) => cache::module_with_cache(&engine, &wasm, cache_dir)
+                .map_err(|error| anyhow!("failed to load Wasm module {wasm_file:?}: {error}"))?,
+            None => wasmi::Module::new(&engine, wasm).map_err(|error| {
+                anyhow!("failed to parse and validate Wasm module {wasm_file:?}: {error}")
+            })?,
+        };
         let mut store = wasmi::Store::new(&engine, wasi_ctx);
         if let Some(fuel) = fuel {
             store.set_fuel(fuel).unwrap_or_else(|error| {
@@ -74,6 +87,11 @@ impl Context {
         })
     }
 
+    /// Returns the Wasm module [`Instance`] of the [`Context`].
+    pub fn instance(&self) -> Instance {
+        self.instance
+    }
+
     /// Returns a shared reference to the [`Store`] of the [`Context`].
     pub fn store(&self) -> &Store<WasiCtx> {
         &self.store
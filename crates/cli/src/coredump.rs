@@ -0,0 +1,127 @@
+use crate::context::Context;
+use anyhow::{anyhow, Error};
+use std::{fs, path::Path};
+use wasm_encoder::{
+    ConstExpr,
+    CoreDumpInstancesSection,
+    CoreDumpModulesSection,
+    CoreDumpSection,
+    CoreDumpStackSection,
+    DataSection,
+    GlobalSection,
+    GlobalType,
+    HeapType,
+    MemorySection,
+    MemoryType,
+    Module,
+    RefType,
+    ValType,
+};
+use wasmi::{core::ValType as WasmiValType, Extern, Val};
+
+/// The default `log2` of the page size of Wasm linear memories.
+const DEFAULT_PAGE_SIZE_LOG2: u8 = 16;
+
+/// Writes a Wasm coredump of the trapped [`Context`] to `path`.
+///
+/// The coredump follows the [Wasm coredump] tool conventions and contains
+/// the exported linear memories and global variables of the Wasm instance.
+///
+/// # Note
+///
+/// Wasmi does not expose the call stack of a trapped execution,
+/// therefore the `corestack` section of the coredump has no frames.
+///
+/// # Errors
+///
+/// If the coredump cannot be written to `path`.
+///
+/// [Wasm coredump]: https://github.com/WebAssembly/tool-conventions/blob/main/Coredump.md
+pub fn write(ctx: &Context, wasm_file: &Path, path: &Path) -> Result<(), Error> {
+    let store = ctx.store();
+    let mut memories = MemorySection::new();
+    let mut data = DataSection::new();
+    let mut globals = GlobalSection::new();
+    for export in ctx.instance().exports(store) {
+        match export.into_extern() {
+            Extern::Memory(memory) => {
+                let ty = memory.ty(store);
+                let page_size_log2 = ty.page_size_log2();
+                let index = memories.len();
+                memories.memory(MemoryType {
+                    minimum: memory.size(store),
+                    maximum: ty.maximum(),
+                    memory64: ty.is_64(),
+                    shared: false,
+                    page_size_log2: (page_size_log2 != DEFAULT_PAGE_SIZE_LOG2)
+                        .then_some(u32::from(page_size_log2)),
+                });
+                // Trailing zeros are not stored since linear memory is zero initialized.
+                let bytes = memory.data(store);
+                let len = bytes
+                    .iter()
+                    .rposition(|byte| *byte != 0)
+                    .map_or(0, |n| n + 1);
+                let offset = match ty.is_64() {
+                    true => ConstExpr::i64_const(0),
+                    false => ConstExpr::i32_const(0),
+                };
+                data.active(index, &offset, bytes[..len].iter().copied());
+            }
+            Extern::Global(global) => {
+                let ty = global.ty(store);
+                let global_type = GlobalType {
+                    val_type: encode_val_type(ty.content()),
+                    mutable: ty.mutability().is_mut(),
+                    shared: false,
+                };
+                globals.global(global_type, &encode_val(&global.get(store)));
+            }
+            _ => {}
+        }
+    }
+    let name = wasm_file.display().to_string();
+    let mut modules = CoreDumpModulesSection::new();
+    modules.module(&name);
+    let mut instances = CoreDumpInstancesSection::new();
+    instances.instance(0, 0..memories.len(), 0..globals.len());
+    let mut module = Module::new();
+    module
+        .section(&CoreDumpSection::new(name))
+        .section(&modules)
+        .section(&instances)
+        .section(&CoreDumpStackSection::new("main"))
+        .section(&memories)
+        .section(&globals)
+        .section(&data);
+    fs::write(path, module.finish())
+        .map_err(|error| anyhow!("failed to write coredump to {path:?}: {error}"))
+}
+
+/// Returns the [`ValType`] of the coredump for the Wasmi `ty`.
+fn encode_val_type(ty: WasmiValType) -> ValType {
+    match ty {
+        WasmiValType::I32 => ValType::I32,
+        WasmiValType::I64 => ValType::I64,
+        WasmiValType::F32 => ValType::F32,
+        WasmiValType::F64 => ValType::F64,
+        WasmiValType::V128 => ValType::V128,
+        WasmiValType::FuncRef => ValType::Ref(RefType::FUNCREF),
+        WasmiValType::ExternRef => ValType::Ref(RefType::EXTERNREF),
+    }
+}
+
+/// Returns the constant expression of the coredump for the Wasmi `value`.
+///
+/// Function and external references cannot be represented and are stored as `null`.
+fn encode_val(value: &Val) -> ConstExpr {
+    match value {
+        Val::I32(value) => ConstExpr::i32_const(*value),
+        Val::I64(value) => ConstExpr::i64_const(*value),
+        Val::F32(value) => ConstExpr::f32_const(value.to_float()),
+        Val::F64(value) => ConstExpr::f64_const(value.to_float()),
+        Val::V128(value) => ConstExpr::v128_const(value.as_u128() as i128),
+        Val::FuncRef(_) => ConstExpr::ref_null(HeapType::FUNC),
+        Val::ExternRef(_) => ConstExpr::ref_null(HeapType::EXTERN),
+    }
+}
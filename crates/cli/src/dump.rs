@@ -0,0 +1,68 @@
+use crate::context;
+use anyhow::{anyhow, bail, Error};
+use std::{fs, path::Path};
+use wasmi::{Engine, FuncDisassembly, Module};
+
+/// Prints the translated Wasmi bytecode of the Wasm module at `input`.
+///
+/// Only prints the function with the export name or function index `func` if given.
+/// Prints per-function statistics and a summary for all printed functions.
+///
+/// # Errors
+///
+/// - If `input` cannot be read or fails to validate or translate.
+/// - If `func` does not refer to an internal function of the Wasm module.
+pub fn dump(input: &Path, func: Option<&str>) -> Result<(), Error> {
+    let wasm = fs::read(input).map_err(|_| anyhow!("failed to read Wasm file {input:?}"))?;
+    let engine = Engine::new(&context::config());
+    let module = Module::new(&engine, wasm)
+        .map_err(|error| anyhow!("failed to parse and validate Wasm module {input:?}: {error}"))?;
+    let mut len_funcs = 0;
+    let mut len_instrs = 0;
+    let mut len_consts = 0;
+    for disasm in module.disassemble() {
+        let disasm = disasm?;
+        if !matches_func(&disasm, func) {
+            continue;
+        }
+        print_func(&disasm);
+        len_funcs += 1;
+        len_instrs += disasm.len_instrs();
+        len_consts += disasm.len_consts();
+    }
+    if let Some(func) = func {
+        if len_funcs == 0 {
+            bail!("failed to find internal function {func:?} in Wasm module {input:?}")
+        }
+    }
+    println!("total: {len_funcs} functions, {len_instrs} instructions, {len_consts} constants");
+    Ok(())
+}
+
+/// Returns `true` if `disasm` is selected by `func`.
+///
+/// All functions are selected if `func` is `None`.
+fn matches_func(disasm: &FuncDisassembly, func: Option<&str>) -> bool {
+    let Some(func) = func else {
+        return true;
+    };
+    match func.parse::<u32>() {
+        Ok(index) => disasm.index() == index,
+        Err(_) => disasm.name() == Some(func),
+    }
+}
+
+/// Prints the header, statistics and Wasmi bytecode of `disasm`.
+fn print_func(disasm: &FuncDisassembly) {
+    print!("func[{}]", disasm.index());
+    if let Some(name) = disasm.name() {
+        print!(" {name:?}");
+    }
+    println!(
+        ": {} instructions, {} constants, {} registers",
+        disasm.len_instrs(),
+        disasm.len_consts(),
+        disasm.len_registers(),
+    );
+    println!("{disasm}");
+}
@@ -1,5 +1,5 @@
 use crate::{
-    args::Args,
+    args::{Args, Command},
     display::{DisplayExportedFuncs, DisplayFuncType, DisplaySequence, DisplayValue},
 };
 use anyhow::{anyhow, bail, Error, Result};
@@ -9,8 +9,12 @@ use std::{path::Path, process};
 use wasmi::{Func, FuncType, Val};
 
 mod args;
+mod cache;
 mod context;
+mod coredump;
 mod display;
+mod dump;
+mod translate;
 mod utils;
 
 #[cfg(test)]
@@ -18,56 +22,132 @@ mod tests;
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    match args.command() {
+        Some(Command::Wat2wasm { input, output }) => {
+            return translate::wat2wasm(input, output.as_deref())
+        }
+        Some(Command::Wasm2wat { input, output }) => {
+            return translate::wasm2wat(input, output.as_deref())
+        }
+        Some(Command::Dump { input, func }) => return dump::dump(input, func.as_deref()),
+        None => {}
+    }
     let wasm_file = args.wasm_file();
     let wasi_ctx = args.wasi_context()?;
-    let mut ctx = Context::new(wasm_file, wasi_ctx, args.fuel(), args.compilation_mode())?;
-    let (func_name, func) = get_invoked_func(&args, &ctx)?;
+    let mut ctx = Context::new(
+        wasm_file,
+        wasi_ctx,
+        args.fuel(),
+        args.compilation_mode(),
+        args.cache_dir(),
+    )?;
+    let mut invocations = args
+        .invocations()
+        .map(|(func_name, func_args)| {
+            let func = get_invoked_func(&ctx, func_name)?;
+            Ok((String::from(func_name), func, func_args))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if invocations.is_empty() {
+        let (func_name, func) = get_wasi_entry_point(&ctx)?;
+        invocations.push((func_name, func, &[]));
+    }
+    let mut fuel_report = FuelReport::default();
+    for (func_name, func, func_args) in invocations {
+        let fuel_before = ctx.store().get_fuel().ok();
+        let exit_code = invoke_func(&args, &mut ctx, &func_name, func, func_args)?;
+        if let (Some(before), Ok(after)) = (fuel_before, ctx.store().get_fuel()) {
+            fuel_report.record(&func_name, before.saturating_sub(after));
+        }
+        if let Some(exit_code) = exit_code {
+            // We received an exit code from the WASI program,
+            // therefore we exit with the same exit code after
+            // printing the fuel consumption.
+            print_fuel(&args, &ctx, &fuel_report);
+            process::exit(exit_code)
+        }
+    }
+    print_fuel(&args, &ctx, &fuel_report);
+    Ok(())
+}
+
+/// Invokes `func` named `func_name` with the textual `func_args` and prints its results.
+///
+/// Returns the exit code if the invoked WASI program exited explicitly.
+///
+/// # Errors
+///
+/// - If `func_args` do not match the parameters of `func`.
+/// - If the execution of `func` failed.
+fn invoke_func(
+    args: &Args,
+    ctx: &mut Context,
+    func_name: &str,
+    func: Func,
+    func_args: &[String],
+) -> Result<Option<i32>> {
     let ty = func.ty(ctx.store());
-    let func_args = utils::decode_func_args(&ty, args.func_args())?;
+    typecheck_args(func_name, &ty, func_args)?;
+    let func_args = utils::decode_func_args(&ty, func_args)?;
     let mut func_results = utils::prepare_func_results(&ty);
-    typecheck_args(&func_name, &ty, &func_args)?;
 
     if args.verbose() {
-        print_execution_start(args.wasm_file(), &func_name, &func_args);
-    }
-    if args.invoked().is_some() && ty.params().len() != args.func_args().len() {
-        bail!(
-            "invalid amount of arguments given to function {}. expected {} but received {}",
-            DisplayFuncType::new(&func_name, &ty),
-            ty.params().len(),
-            args.func_args().len()
-        )
+        print_execution_start(args.wasm_file(), func_name, &func_args);
     }
 
     match func.call(ctx.store_mut(), &func_args, &mut func_results) {
         Ok(()) => {
-            print_remaining_fuel(&args, &ctx);
             print_pretty_results(&func_results);
-            Ok(())
+            Ok(None)
         }
         Err(error) => {
             if let Some(exit_code) = error.i32_exit_status() {
-                // We received an exit code from the WASI program,
-                // therefore we exit with the same exit code after
-                // pretty printing the results.
-                print_remaining_fuel(&args, &ctx);
                 print_pretty_results(&func_results);
-                process::exit(exit_code)
+                return Ok(Some(exit_code));
+            }
+            if let Some(path) = args.coredump_on_trap() {
+                coredump::write(ctx, args.wasm_file(), path)?;
             }
             bail!("failed during execution of {func_name}: {error}")
         }
     }
 }
 
-/// Prints the remaining fuel so far if fuel metering was enabled.
-fn print_remaining_fuel(args: &Args, ctx: &Context) {
-    if let Some(given_fuel) = args.fuel() {
-        let remaining = ctx
-            .store()
-            .get_fuel()
-            .unwrap_or_else(|error| panic!("could not get the remaining fuel: {error}"));
-        let consumed = given_fuel.saturating_sub(remaining);
-        println!("fuel consumed: {consumed}, fuel remaining: {remaining}");
+/// The fuel consumed by each invoked function.
+#[derive(Debug, Default)]
+struct FuelReport {
+    /// The function names and their consumed fuel in order of their first invocation.
+    consumed: Vec<(String, u64)>,
+}
+
+impl FuelReport {
+    /// Adds `consumed` fuel to the function named `func_name`.
+    fn record(&mut self, func_name: &str, consumed: u64) {
+        match self.consumed.iter_mut().find(|(name, _)| name == func_name) {
+            Some((_, total)) => *total = total.saturating_add(consumed),
+            None => self.consumed.push((func_name.into(), consumed)),
+        }
+    }
+}
+
+/// Prints the consumed and remaining fuel if fuel metering was enabled.
+///
+/// Also prints the fuel consumed per invoked function if `--fuel-report` was given.
+fn print_fuel(args: &Args, ctx: &Context, report: &FuelReport) {
+    let Some(given_fuel) = args.fuel() else {
+        return;
+    };
+    let remaining = ctx
+        .store()
+        .get_fuel()
+        .unwrap_or_else(|error| panic!("could not get the remaining fuel: {error}"));
+    let consumed = given_fuel.saturating_sub(remaining);
+    println!("fuel consumed: {consumed}, fuel remaining: {remaining}");
+    if args.fuel_report() {
+        println!("fuel consumed per function:");
+        for (func_name, consumed) in &report.consumed {
+            println!(" - {func_name:?}: {consumed}");
+        }
     }
 }
 
@@ -80,7 +160,7 @@ fn print_remaining_fuel(args: &Args, ctx: &Context) {
 /// # Errors
 ///
 /// If too many or too few function arguments were given to the invoked function.
-fn typecheck_args(func_name: &str, func_ty: &FuncType, args: &[Val]) -> Result<(), Error> {
+fn typecheck_args(func_name: &str, func_ty: &FuncType, args: &[String]) -> Result<(), Error> {
     if func_ty.params().len() != args.len() {
         bail!(
             "invalid amount of arguments given to function {}. expected {} but received {}",
@@ -92,35 +172,33 @@ fn typecheck_args(func_name: &str, func_ty: &FuncType, args: &[Val]) -> Result<(
     Ok(())
 }
 
-/// Returns the invoked named function or the WASI entry point to the Wasm module if any.
+/// Returns the exported function named `func_name`.
 ///
 /// # Errors
 ///
-/// - If the function given via `--invoke` could not be found in the Wasm module.
-/// - If `--invoke` was not given and no WASI entry points were exported.
-fn get_invoked_func(args: &Args, ctx: &Context) -> Result<(String, Func), Error> {
-    match args.invoked() {
-        Some(func_name) => {
-            let func = ctx
-                .get_func(func_name)
-                .map_err(|error| anyhow!("{error}\n\n{}", DisplayExportedFuncs::from(ctx)))?;
-            let func_name = func_name.into();
-            Ok((func_name, func))
-        }
-        None => {
-            // No `--invoke` flag was provided so we try to find
-            // the conventional WASI entry points `""` and `"_start"`.
-            if let Ok(func) = ctx.get_func("") {
-                Ok(("".into(), func))
-            } else if let Ok(func) = ctx.get_func("_start") {
-                Ok(("_start".into(), func))
-            } else {
-                bail!(
-                    "did not specify `--invoke` and could not find exported WASI entry point functions\n\n{}",
-                    DisplayExportedFuncs::from(ctx)
-                )
-            }
-        }
+/// If the function given via `--invoke` could not be found in the Wasm module.
+fn get_invoked_func(ctx: &Context, func_name: &str) -> Result<Func, Error> {
+    ctx.get_func(func_name)
+        .map_err(|error| anyhow!("{error}\n\n{}", DisplayExportedFuncs::from(ctx)))
+}
+
+/// Returns the WASI entry point to the Wasm module if any.
+///
+/// # Errors
+///
+/// If `--invoke` was not given and no WASI entry points were exported.
+fn get_wasi_entry_point(ctx: &Context) -> Result<(String, Func), Error> {
+    // No `--invoke` flag was provided so we try to find
+    // the conventional WASI entry points `""` and `"_start"`.
+    if let Ok(func) = ctx.get_func("") {
+        Ok(("".into(), func))
+    } else if let Ok(func) = ctx.get_func("_start") {
+        Ok(("_start".into(), func))
+    } else {
+        bail!(
+            "did not specify `--invoke` and could not find exported WASI entry point functions\n\n{}",
+            DisplayExportedFuncs::from(ctx)
+        )
     }
 }
 
@@ -133,8 +211,15 @@ fn print_execution_start(wasm_file: &Path, func_name: &str, func_args: &[Val]) {
 }
 
 /// Prints the results of the Wasm computation in a human readable form.
+///
+/// Multiple results are printed on a single line as a parenthesized tuple.
 fn print_pretty_results(results: &[Val]) {
-    for result in results {
-        println!("{}", DisplayValue::from(result))
+    match results {
+        [] => {}
+        [result] => println!("{}", DisplayValue::from(result)),
+        results => println!(
+            "({})",
+            DisplaySequence::new(", ", results.iter().map(DisplayValue::from))
+        ),
     }
 }
@@ -0,0 +1,50 @@
+use crate::context;
+use anyhow::{anyhow, Error};
+use std::{fs, path::Path};
+use wasmi::{Engine, Module};
+
+/// Translates the WebAssembly text file `input` into the binary format.
+///
+/// Writes the result to `output` or to `input` with a `.wasm` extension if `output` is `None`.
+///
+/// # Errors
+///
+/// - If `input` cannot be parsed or the resulting Wasm module fails to validate.
+/// - If the output file cannot be written.
+pub fn wat2wasm(input: &Path, output: Option<&Path>) -> Result<(), Error> {
+    let wasm = wat::parse_file(input)
+        .map_err(|error| anyhow!("failed to parse Wasm text file {input:?}: {error}"))?;
+    let engine = Engine::new(&context::config());
+    Module::validate(&engine, &wasm)
+        .map_err(|error| anyhow!("failed to validate Wasm module {input:?}: {error}"))?;
+    let output = match output {
+        Some(output) => output.to_path_buf(),
+        None => input.with_extension("wasm"),
+    };
+    fs::write(&output, wasm).map_err(|error| anyhow!("failed to write {output:?}: {error}"))
+}
+
+/// Translates the WebAssembly binary file `input` into the text format.
+///
+/// Writes the result to `output` or prints it to `stdout` if `output` is `None`.
+///
+/// # Errors
+///
+/// - If `input` cannot be read or fails to validate.
+/// - If the output file cannot be written.
+pub fn wasm2wat(input: &Path, output: Option<&Path>) -> Result<(), Error> {
+    let wasm = fs::read(input).map_err(|_| anyhow!("failed to read Wasm file {input:?}"))?;
+    let engine = Engine::new(&context::config());
+    let wat = Module::new(&engine, wasm)
+        .and_then(|module| module.to_wat())
+        .map_err(|error| anyhow!("failed to print Wasm module {input:?}: {error}"))?;
+    match output {
+        Some(output) => {
+            fs::write(output, wat).map_err(|error| anyhow!("failed to write {output:?}: {error}"))
+        }
+        None => {
+            print!("{wat}");
+            Ok(())
+        }
+    }
+}
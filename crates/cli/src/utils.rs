@@ -32,42 +32,128 @@ pub fn decode_func_args(ty: &FuncType, args: &[String]) -> Result<Box<[Val]>, Er
         .zip(args)
         .enumerate()
         .map(|(n, (param_type, arg))| {
-            macro_rules! make_err {
-                () => {
-                    |_| {
-                        anyhow!(
-                            "failed to parse function argument \
-                            {arg} at index {n} as {}",
-                            DisplayValueType::from(param_type)
-                        )
-                    }
-                };
-            }
-            match param_type {
-                ValType::I32 => arg.parse::<i32>().map(Val::from).map_err(make_err!()),
-                ValType::I64 => arg.parse::<i64>().map(Val::from).map_err(make_err!()),
-                ValType::F32 => arg
-                    .parse::<f32>()
-                    .map(F32::from)
-                    .map(Val::from)
-                    .map_err(make_err!()),
-                ValType::F64 => arg
-                    .parse::<f64>()
-                    .map(F64::from)
-                    .map(Val::from)
-                    .map_err(make_err!()),
-                ValType::V128 => arg
-                    .parse::<u128>()
-                    .map(V128::from)
-                    .map(Val::from)
-                    .map_err(make_err!()),
+            let value = match param_type {
+                ValType::I32 => parse_i32(arg).map(Val::from),
+                ValType::I64 => parse_i64(arg).map(Val::from),
+                ValType::F32 => parse_f32(arg).map(Val::from),
+                ValType::F64 => parse_f64(arg).map(Val::from),
+                ValType::V128 => parse_v128(arg).map(Val::from),
                 ValType::FuncRef => {
                     bail!("the wasmi CLI cannot take arguments of type funcref")
                 }
                 ValType::ExternRef => {
                     bail!("the wasmi CLI cannot take arguments of type externref")
                 }
-            }
+            };
+            value.ok_or_else(|| {
+                anyhow!(
+                    "failed to parse function argument {arg} at index {n} as {}",
+                    DisplayValueType::from(param_type)
+                )
+            })
         })
         .collect::<Result<Box<[_]>, _>>()
 }
+
+/// Splits an optional `0x` prefix off `arg` after removing `_` digit separators.
+///
+/// Returns the remaining digits and `true` if `arg` was prefixed with `0x`.
+fn split_hex(arg: &str) -> (String, bool) {
+    let digits = arg.replace('_', "");
+    match digits.strip_prefix("0x") {
+        Some(hex) => (hex.into(), true),
+        None => (digits, false),
+    }
+}
+
+macro_rules! impl_parse_int {
+    ( $( fn $name:ident -> $signed:ty as $unsigned:ty );* $(;)? ) => {
+        $(
+            /// Parses an integer literal.
+            ///
+            /// Accepts signed and unsigned decimals as well as `0x` prefixed hexadecimal bit patterns.
+            fn $name(arg: &str) -> Option<$signed> {
+                let (digits, is_hex) = split_hex(arg);
+                if is_hex {
+                    return <$unsigned>::from_str_radix(&digits, 16)
+                        .ok()
+                        .map(|bits| bits as $signed);
+                }
+                digits
+                    .parse::<$signed>()
+                    .ok()
+                    .or_else(|| digits.parse::<$unsigned>().ok().map(|bits| bits as $signed))
+            }
+        )*
+    };
+}
+impl_parse_int! {
+    fn parse_i32 -> i32 as u32;
+    fn parse_i64 -> i64 as u64;
+}
+
+macro_rules! impl_parse_float {
+    ( $( fn $name:ident -> $wrapper:ty as $float:ty, $bits:ty );* $(;)? ) => {
+        $(
+            /// Parses a floating point literal.
+            ///
+            /// Accepts decimals, `inf` and `nan` as well as `0x` prefixed hexadecimal bit patterns.
+            fn $name(arg: &str) -> Option<$wrapper> {
+                let (digits, is_hex) = split_hex(arg);
+                if is_hex {
+                    return <$bits>::from_str_radix(&digits, 16)
+                        .ok()
+                        .map(<$wrapper>::from_bits);
+                }
+                digits.parse::<$float>().ok().map(<$wrapper>::from)
+            }
+        )*
+    };
+}
+impl_parse_float! {
+    fn parse_f32 -> F32 as f32, u32;
+    fn parse_f64 -> F64 as f64, u64;
+}
+
+/// Parses a `v128` literal.
+///
+/// Accepts unsigned decimals, `0x` prefixed hexadecimal bit patterns
+/// and lane-wise literals such as `i32x4:1,2,3,4` or `f64x2:1.5,-2.0`
+/// where the first lane is the least significant.
+fn parse_v128(arg: &str) -> Option<V128> {
+    if let Some((shape, lanes)) = arg.split_once(':') {
+        let lanes = lanes.split(',').collect::<Vec<_>>();
+        let mut bytes = Vec::with_capacity(16);
+        macro_rules! encode_lanes {
+            ($len:literal, $parse:expr) => {{
+                if lanes.len() != $len {
+                    return None;
+                }
+                for lane in lanes {
+                    bytes.extend_from_slice(&$parse(lane)?.to_le_bytes());
+                }
+            }};
+        }
+        match shape {
+            "i8x16" => encode_lanes!(16, |lane| parse_i32(lane)
+                .filter(|value| (-0x80..=0xFF).contains(value))
+                .map(|value| value as i8)),
+            "i16x8" => encode_lanes!(8, |lane| parse_i32(lane)
+                .filter(|value| (-0x8000..=0xFFFF).contains(value))
+                .map(|value| value as i16)),
+            "i32x4" => encode_lanes!(4, parse_i32),
+            "i64x2" => encode_lanes!(2, parse_i64),
+            "f32x4" => encode_lanes!(4, |lane| parse_f32(lane).map(F32::to_bits)),
+            "f64x2" => encode_lanes!(2, |lane| parse_f64(lane).map(F64::to_bits)),
+            _ => return None,
+        }
+        let bytes = <[u8; 16]>::try_from(bytes).ok()?;
+        return Some(V128::from(u128::from_le_bytes(bytes)));
+    }
+    let (digits, is_hex) = split_hex(arg);
+    let bits = match is_hex {
+        true => u128::from_str_radix(&digits, 16).ok()?,
+        false => digits.parse::<u128>().ok()?,
+    };
+    Some(V128::from(bits))
+}
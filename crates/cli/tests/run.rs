@@ -39,6 +39,135 @@ fn test_verbose() {
     assert!(contains_slice(stdout, b"proc_exit.wat\")::()"));
 }
 
+#[test]
+fn test_invoke_multiple() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .arg(get_bin_path("arith"))
+        .args(["--invoke", "add", "1", "-2"])
+        .args(["--invoke", "add", "0x10", "1"])
+        .args(["--invoke", "swap", "5", "1.5"])
+        .assert();
+    assert.success().stdout("-1\n17\n(1.5, 5)\n");
+}
+
+#[test]
+fn test_invoke_invalid_arg() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .arg(get_bin_path("arith"))
+        .args(["--invoke", "add", "1", "x"])
+        .assert();
+    assert.failure();
+}
+
+#[test]
+fn test_fuel_report() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .arg(get_bin_path("arith"))
+        .args(["--fuel", "1000", "--fuel-report"])
+        .args(["--invoke", "add", "1", "2"])
+        .args(["--invoke", "swap", "1", "2"])
+        .args(["--invoke", "add", "3", "4"])
+        .assert();
+    let stdout = std::str::from_utf8(&assert.get_output().stdout).unwrap();
+    let report = stdout
+        .split_once("fuel consumed per function:\n")
+        .map(|(_, report)| report)
+        .unwrap();
+    let lines = report.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with(" - \"add\": "));
+    assert!(lines[1].starts_with(" - \"swap\": "));
+    assert.success();
+}
+
+#[test]
+fn test_wat2wasm_wasm2wat() {
+    let wasm_file = std::env::temp_dir().join("wasmi_cli_test_arith.wasm");
+    get_cmd()
+        .arg("wat2wasm")
+        .arg(get_bin_path("arith"))
+        .arg("-o")
+        .arg(&wasm_file)
+        .assert()
+        .success();
+    get_cmd()
+        .arg(&wasm_file)
+        .args(["--invoke", "add", "1", "2"])
+        .assert()
+        .success()
+        .stdout("3\n");
+    let assert = get_cmd().arg("wasm2wat").arg(&wasm_file).assert();
+    let stdout = std::str::from_utf8(&assert.get_output().stdout).unwrap();
+    assert!(stdout.contains("(export \"add\""));
+    assert!(stdout.contains("i32.add"));
+    assert.success();
+}
+
+#[test]
+fn test_dump() {
+    let assert = get_cmd()
+        .arg("dump")
+        .arg(get_bin_path("arith"))
+        .args(["--func", "add"])
+        .assert();
+    let stdout = std::str::from_utf8(&assert.get_output().stdout).unwrap();
+    assert!(stdout.starts_with("func[0] \"add\": "));
+    assert!(stdout.contains("I32Add"));
+    assert!(!stdout.contains("\"swap\""));
+    assert!(stdout.contains("total: 1 functions"));
+    assert.success();
+}
+
+#[test]
+fn test_coredump_on_trap() {
+    let coredump = std::env::temp_dir().join("wasmi_cli_test_trap.coredump");
+    let _ = std::fs::remove_file(&coredump);
+    get_cmd()
+        .arg(get_bin_path("trap"))
+        .args(["--invoke", "trap"])
+        .arg("--coredump-on-trap")
+        .arg(&coredump)
+        .assert()
+        .failure();
+    let bytes = std::fs::read(&coredump).unwrap();
+    assert!(bytes.starts_with(b"\0asm"));
+    assert!(contains_slice(&bytes, b"corestack"));
+}
+
+#[test]
+fn test_cache_dir() {
+    let cache_dir = std::env::temp_dir().join("wasmi_cli_test_cache");
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    let run = || {
+        get_cmd()
+            .arg(get_bin_path("arith"))
+            .arg("--cache-dir")
+            .arg(&cache_dir)
+            .args(["--invoke", "add", "1", "2"])
+            .assert()
+            .success()
+            .stdout("3\n");
+    };
+    // The first run compiles the Wasm module and caches it.
+    run();
+    let entries = std::fs::read_dir(&cache_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    assert_eq!(entries.len(), 1);
+    let cached = std::fs::read(&entries[0]).unwrap();
+    // The second run loads the cached Wasm module.
+    run();
+    assert_eq!(std::fs::read(&entries[0]).unwrap(), cached);
+    // A corrupted cache entry is replaced.
+    std::fs::write(&entries[0], b"corrupted").unwrap();
+    run();
+    assert_eq!(std::fs::read(&entries[0]).unwrap(), cached);
+}
+
 /// gets the path to a wasm binary given it's name
 fn get_bin_path(name: &str) -> PathBuf {
     let mut path = PathBuf::new();
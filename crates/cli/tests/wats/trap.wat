@@ -0,0 +1,9 @@
+(module
+    (memory (export "memory") 1)
+    (global $counter (export "counter") (mut i32) (i32.const 0))
+    (func (export "trap")
+        (i32.store (i32.const 8) (i32.const 42))
+        (global.set $counter (i32.const 1))
+        (unreachable)
+    )
+)
@@ -21,6 +21,15 @@ impl<T> Default for HeadVec<T> {
 }
 
 impl<T> HeadVec<T> {
+    /// Creates a new empty [`HeadVec`] that can hold `capacity` items without reallocating.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            head: None,
+            rest: Vec::with_capacity(capacity.saturating_sub(1)),
+        }
+    }
+
     /// Removes all items from the [`HeadVec`].
     #[inline]
     pub fn clear(&mut self) {
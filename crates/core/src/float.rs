@@ -84,6 +84,26 @@ macro_rules! float {
                 )
             }
         }
+
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+            {
+                <$prim as ::serde::Serialize>::serialize(&self.to_float(), serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                <$prim as ::serde::Deserialize>::deserialize(deserializer).map(Self::from_float)
+            }
+        }
     };
 }
 
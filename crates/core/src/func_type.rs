@@ -1,6 +1,6 @@
 use crate::ValType;
 use alloc::{sync::Arc, vec::Vec};
-use core::{fmt, fmt::Display};
+use core::{fmt, fmt::Display, mem};
 
 /// Errors that can occur upon type checking function signatures.
 #[derive(Debug, Copy, Clone)]
@@ -272,6 +272,34 @@ impl fmt::Debug for FuncType {
     }
 }
 
+/// The `serde` representation of a [`FuncType`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "FuncType")]
+struct FuncTypeRepr<T> {
+    params: T,
+    results: T,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for FuncType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FuncTypeRepr {
+            params: self.params(),
+            results: self.results(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FuncType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = <FuncTypeRepr<Vec<ValType>>>::deserialize(deserializer)?;
+        Self::new(repr.params, repr.results).map_err(serde::de::Error::custom)
+    }
+}
+
 impl FuncType {
     /// Creates a new [`FuncType`].
     ///
@@ -314,6 +342,20 @@ impl FuncType {
         self.inner.params_results()
     }
 
+    /// Returns the number of bytes the function type allocates on the heap.
+    ///
+    /// This is zero if the parameter and result types are stored inline.
+    /// Clones of a function type share their heap allocation.
+    pub fn heap_size(&self) -> usize {
+        match &self.inner {
+            FuncTypeInner::Inline { .. } => 0,
+            FuncTypeInner::Big { params_results, .. } => {
+                // Note: `Arc` allocates its strong and weak reference counts next to its data.
+                2 * mem::size_of::<usize>() + mem::size_of_val(&**params_results)
+            }
+        }
+    }
+
     /// Returns `Ok` if the number and types of items in `params` matches as expected by the [`FuncType`].
     ///
     /// # Errors
@@ -424,6 +466,7 @@ mod tests {
                 assert_eq!(ft.results(), results);
                 assert_eq!(ft.params(), ft.params_results().0);
                 assert_eq!(ft.results(), ft.params_results().1);
+                assert_eq!(ft.heap_size(), 0);
             }
         }
     }
@@ -439,5 +482,6 @@ mod tests {
         assert_eq!(ft.results(), results);
         assert_eq!(ft.params(), ft.params_results().0);
         assert_eq!(ft.results(), ft.params_results().1);
+        assert!(ft.heap_size() > 200 * mem::size_of::<ValType>());
     }
 }
@@ -1,4 +1,5 @@
 use crate::TrapCode;
+use core::mem::size_of;
 
 /// Convert one type to another by wrapping.
 pub trait WrapInto<T> {
@@ -71,56 +72,48 @@ impl_extend_into! {
     impl ExtendInto<i64> for u32;
 }
 
-/// Allows to efficiently load bytes from `memory` into a buffer.
-pub trait LoadInto {
-    /// Loads bytes from `memory` into `self`.
-    ///
-    /// # Errors
-    ///
-    /// Traps if the `memory` access is out of bounds.
-    fn load_into(&mut self, memory: &[u8], address: usize) -> Result<(), TrapCode>;
-}
-
-impl<const N: usize> LoadInto for [u8; N] {
-    #[inline]
-    fn load_into(&mut self, memory: &[u8], address: usize) -> Result<(), TrapCode> {
-        let slice: &Self = memory
-            .get(address..)
-            .and_then(|slice| slice.get(..N))
-            .and_then(|slice| slice.try_into().ok())
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
-        *self = *slice;
-        Ok(())
+/// Returns a pointer to the `len` bytes of `memory` starting at `address`.
+///
+/// # Errors
+///
+/// Traps if the `memory` access is out of bounds.
+#[inline]
+fn access_ptr(memory: &[u8], address: usize, len: usize) -> Result<*const u8, TrapCode> {
+    let Some(remaining) = memory.len().checked_sub(address) else {
+        return Err(TrapCode::MemoryOutOfBounds);
+    };
+    if remaining < len {
+        return Err(TrapCode::MemoryOutOfBounds);
     }
+    // Safety: `address..address + len` is within the bounds of `memory`.
+    Ok(unsafe { memory.as_ptr().add(address) })
 }
 
-/// Allows to efficiently write bytes from a buffer into `memory`.
-pub trait StoreFrom {
-    /// Writes bytes from `self` to `memory`.
-    ///
-    /// # Errors
-    ///
-    /// Traps if the `memory` access is out of bounds.
-    fn store_from(&self, memory: &mut [u8], address: usize) -> Result<(), TrapCode>;
-}
-
-impl<const N: usize> StoreFrom for [u8; N] {
-    #[inline]
-    fn store_from(&self, memory: &mut [u8], address: usize) -> Result<(), TrapCode> {
-        let slice: &mut Self = memory
-            .get_mut(address..)
-            .and_then(|slice| slice.get_mut(..N))
-            .and_then(|slice| slice.try_into().ok())
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
-        *slice = *self;
-        Ok(())
+/// Returns a mutable pointer to the `len` bytes of `memory` starting at `address`.
+///
+/// # Errors
+///
+/// Traps if the `memory` access is out of bounds.
+#[inline]
+fn access_ptr_mut(memory: &mut [u8], address: usize, len: usize) -> Result<*mut u8, TrapCode> {
+    let Some(remaining) = memory.len().checked_sub(address) else {
+        return Err(TrapCode::MemoryOutOfBounds);
+    };
+    if remaining < len {
+        return Err(TrapCode::MemoryOutOfBounds);
     }
+    // Safety: `address..address + len` is within the bounds of `memory`.
+    Ok(unsafe { memory.as_mut_ptr().add(address) })
 }
 
 /// Types that can be converted from and to little endian bytes.
-pub trait LittleEndianConvert {
+///
+/// # Safety
+///
+/// Implementers must guarantee that `Bytes` is a byte array.
+pub unsafe trait LittleEndianConvert {
     /// The little endian bytes representation.
-    type Bytes: Default + LoadInto + StoreFrom;
+    type Bytes;
 
     /// Converts `self` into little endian bytes.
     fn into_le_bytes(self) -> Self::Bytes;
@@ -132,7 +125,8 @@ pub trait LittleEndianConvert {
 macro_rules! impl_little_endian_convert_primitive {
     ( $($primitive:ty),* $(,)? ) => {
         $(
-            impl LittleEndianConvert for $primitive {
+            // Safety: `Bytes` is a byte array.
+            unsafe impl LittleEndianConvert for $primitive {
                 type Bytes = [::core::primitive::u8; ::core::mem::size_of::<$primitive>()];
 
                 #[inline]
@@ -185,9 +179,11 @@ pub fn load_at<T>(memory: &[u8], address: usize) -> Result<T, TrapCode>
 where
     T: LittleEndianConvert,
 {
-    let mut buffer = <<T as LittleEndianConvert>::Bytes as Default>::default();
-    buffer.load_into(memory, address)?;
-    let value: T = <T as LittleEndianConvert>::from_le_bytes(buffer);
+    let src = access_ptr(memory, address, size_of::<T::Bytes>())?;
+    // Safety: `src` points to `size_of::<T::Bytes>()` in-bounds bytes of `memory`
+    //         and `T::Bytes` is a byte array which has no alignment requirements.
+    let bytes = unsafe { src.cast::<T::Bytes>().read_unaligned() };
+    let value: T = <T as LittleEndianConvert>::from_le_bytes(bytes);
     Ok(value)
 }
 
@@ -240,8 +236,11 @@ pub fn store_at<T>(memory: &mut [u8], address: usize, value: T) -> Result<(), Tr
 where
     T: LittleEndianConvert,
 {
-    let buffer = <T as LittleEndianConvert>::into_le_bytes(value);
-    buffer.store_from(memory, address)?;
+    let bytes = <T as LittleEndianConvert>::into_le_bytes(value);
+    let dst = access_ptr_mut(memory, address, size_of::<T::Bytes>())?;
+    // Safety: `dst` points to `size_of::<T::Bytes>()` in-bounds bytes of `memory`
+    //         and `T::Bytes` is a byte array which has no alignment requirements.
+    unsafe { dst.cast::<T::Bytes>().write_unaligned(bytes) };
     Ok(())
 }
 
@@ -272,3 +271,36 @@ where
 {
     store_at::<U>(memory, address, value.wrap_into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unaligned_load_store_works() {
+        let mut memory = [0x00_u8; 16];
+        for address in 0..=8 {
+            let value = 0x0102_0304_0506_0708_u64 << address;
+            store_at::<u64>(&mut memory, address, value).unwrap();
+            assert_eq!(memory[address..address + 8], value.to_le_bytes());
+            assert_eq!(load_at::<u64>(&memory, address), Ok(value));
+        }
+        store_wrap_at::<i64, i16>(&mut memory, 14, -2).unwrap();
+        assert_eq!(load_extend_at::<i64, i16>(&memory, 14), Ok(-2));
+        assert_eq!(load_extend_at::<i64, u16>(&memory, 14), Ok(0xFFFE));
+    }
+
+    #[test]
+    fn out_of_bounds_access_traps() {
+        let mut memory = [0x00_u8; 16];
+        let oob = TrapCode::MemoryOutOfBounds;
+        for address in [9, 16, 17, usize::MAX - 7, usize::MAX] {
+            assert_eq!(load_at::<u64>(&memory, address).unwrap_err(), oob);
+            assert_eq!(store_at::<u64>(&mut memory, address, 0).unwrap_err(), oob);
+        }
+        assert_eq!(load::<u8>(&memory, u64::MAX, 1).unwrap_err(), oob);
+        assert_eq!(store::<u8>(&mut memory, u64::MAX, 1, 0).unwrap_err(), oob);
+        assert_eq!(load_at::<u8>(&[], 0).unwrap_err(), oob);
+        assert_eq!(load_at::<u8>(&memory, 15), Ok(0));
+    }
+}
@@ -13,6 +13,34 @@ use core::{
     ops::{BitAnd, BitOr, BitXor, Neg, Not},
 };
 
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+mod x86_64;
+
+/// Executes the accelerated operation of the target if marked with `#[accelerate]`.
+///
+/// Otherwise or if the target has no accelerated backend, executes the portable `$fallback`.
+///
+/// # Note
+///
+/// With debug assertions enabled the accelerated result is checked against the `$fallback`.
+macro_rules! dispatch {
+    ( #[accelerate] $name:ident($($arg:ident),*) else $fallback:expr ) => {{
+        #[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+        {
+            let result = x86_64::$name($($arg),*);
+            debug_assert_eq!(result, $fallback, "accelerated `{}` diverged", stringify!($name));
+            result
+        }
+        #[cfg(not(all(target_arch = "x86_64", target_feature = "sse2")))]
+        {
+            $fallback
+        }
+    }};
+    ( $name:ident($($arg:ident),*) else $fallback:expr ) => {{
+        $fallback
+    }};
+}
+
 macro_rules! op {
     ($ty:ty, $op:tt) => {{
         |lhs: $ty, rhs: $ty| lhs $op rhs
@@ -82,6 +110,13 @@ impl<const N: u8> TryFrom<u8> for ImmLaneIdx<N> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a, const N: u8> arbitrary::Arbitrary<'a> for ImmLaneIdx<N> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.int_in_range(0..=Self::MASK).map(Self)
+    }
+}
+
 /// A byte with values in the range 0–1 identifying a lane.
 pub type ImmLaneIdx2 = ImmLaneIdx<2>;
 /// A byte with values in the range 0–3 identifying a lane.
@@ -847,62 +882,65 @@ fn pmax<T: PartialOrd>(lhs: T, rhs: T) -> T {
 }
 
 macro_rules! impl_binary_for {
-    ( $( fn $name:ident(lhs: V128, rhs: V128) -> V128 = $lanewise_expr:expr; )* ) => {
+    ( $( $( #[$accelerate:ident] )? fn $name:ident(lhs: V128, rhs: V128) -> V128 = $lanewise_expr:expr; )* ) => {
         $(
             #[doc = concat!("Executes a Wasm `", stringify!($name), "` instruction.")]
             pub fn $name(lhs: V128, rhs: V128) -> V128 {
-                V128::lanewise_binary(lhs, rhs, $lanewise_expr)
+                dispatch!(
+                    $( #[$accelerate] )? $name(lhs, rhs)
+                    else V128::lanewise_binary(lhs, rhs, $lanewise_expr)
+                )
             }
         )*
     };
 }
 impl_binary_for! {
-    fn i64x2_add(lhs: V128, rhs: V128) -> V128 = i64::wrapping_add;
-    fn i32x4_add(lhs: V128, rhs: V128) -> V128 = i32::wrapping_add;
-    fn i16x8_add(lhs: V128, rhs: V128) -> V128 = i16::wrapping_add;
-    fn i8x16_add(lhs: V128, rhs: V128) -> V128 = i8::wrapping_add;
+    #[accelerate] fn i64x2_add(lhs: V128, rhs: V128) -> V128 = i64::wrapping_add;
+    #[accelerate] fn i32x4_add(lhs: V128, rhs: V128) -> V128 = i32::wrapping_add;
+    #[accelerate] fn i16x8_add(lhs: V128, rhs: V128) -> V128 = i16::wrapping_add;
+    #[accelerate] fn i8x16_add(lhs: V128, rhs: V128) -> V128 = i8::wrapping_add;
 
-    fn i64x2_sub(lhs: V128, rhs: V128) -> V128 = i64::wrapping_sub;
-    fn i32x4_sub(lhs: V128, rhs: V128) -> V128 = i32::wrapping_sub;
-    fn i16x8_sub(lhs: V128, rhs: V128) -> V128 = i16::wrapping_sub;
-    fn i8x16_sub(lhs: V128, rhs: V128) -> V128 = i8::wrapping_sub;
+    #[accelerate] fn i64x2_sub(lhs: V128, rhs: V128) -> V128 = i64::wrapping_sub;
+    #[accelerate] fn i32x4_sub(lhs: V128, rhs: V128) -> V128 = i32::wrapping_sub;
+    #[accelerate] fn i16x8_sub(lhs: V128, rhs: V128) -> V128 = i16::wrapping_sub;
+    #[accelerate] fn i8x16_sub(lhs: V128, rhs: V128) -> V128 = i8::wrapping_sub;
 
     fn i64x2_mul(lhs: V128, rhs: V128) -> V128 = i64::wrapping_mul;
     fn i32x4_mul(lhs: V128, rhs: V128) -> V128 = i32::wrapping_mul;
-    fn i16x8_mul(lhs: V128, rhs: V128) -> V128 = i16::wrapping_mul;
+    #[accelerate] fn i16x8_mul(lhs: V128, rhs: V128) -> V128 = i16::wrapping_mul;
     fn i8x16_mul(lhs: V128, rhs: V128) -> V128 = i8::wrapping_mul;
 
-    fn i8x16_add_sat_s(lhs: V128, rhs: V128) -> V128 = i8::saturating_add;
-    fn i8x16_add_sat_u(lhs: V128, rhs: V128) -> V128 = u8::saturating_add;
-    fn i16x8_add_sat_s(lhs: V128, rhs: V128) -> V128 = i16::saturating_add;
-    fn i16x8_add_sat_u(lhs: V128, rhs: V128) -> V128 = u16::saturating_add;
-    fn i8x16_sub_sat_s(lhs: V128, rhs: V128) -> V128 = i8::saturating_sub;
-    fn i8x16_sub_sat_u(lhs: V128, rhs: V128) -> V128 = u8::saturating_sub;
-    fn i16x8_sub_sat_s(lhs: V128, rhs: V128) -> V128 = i16::saturating_sub;
-    fn i16x8_sub_sat_u(lhs: V128, rhs: V128) -> V128 = u16::saturating_sub;
+    #[accelerate] fn i8x16_add_sat_s(lhs: V128, rhs: V128) -> V128 = i8::saturating_add;
+    #[accelerate] fn i8x16_add_sat_u(lhs: V128, rhs: V128) -> V128 = u8::saturating_add;
+    #[accelerate] fn i16x8_add_sat_s(lhs: V128, rhs: V128) -> V128 = i16::saturating_add;
+    #[accelerate] fn i16x8_add_sat_u(lhs: V128, rhs: V128) -> V128 = u16::saturating_add;
+    #[accelerate] fn i8x16_sub_sat_s(lhs: V128, rhs: V128) -> V128 = i8::saturating_sub;
+    #[accelerate] fn i8x16_sub_sat_u(lhs: V128, rhs: V128) -> V128 = u8::saturating_sub;
+    #[accelerate] fn i16x8_sub_sat_s(lhs: V128, rhs: V128) -> V128 = i16::saturating_sub;
+    #[accelerate] fn i16x8_sub_sat_u(lhs: V128, rhs: V128) -> V128 = u16::saturating_sub;
 
     fn i16x8_q15mulr_sat_s(lhs: V128, rhs: V128) -> V128 = i16x8_q15mulr_sat;
 
     fn i8x16_min_s(lhs: V128, rhs: V128) -> V128 = i8::min;
-    fn i8x16_min_u(lhs: V128, rhs: V128) -> V128 = u8::min;
-    fn i16x8_min_s(lhs: V128, rhs: V128) -> V128 = i16::min;
+    #[accelerate] fn i8x16_min_u(lhs: V128, rhs: V128) -> V128 = u8::min;
+    #[accelerate] fn i16x8_min_s(lhs: V128, rhs: V128) -> V128 = i16::min;
     fn i16x8_min_u(lhs: V128, rhs: V128) -> V128 = u16::min;
     fn i32x4_min_s(lhs: V128, rhs: V128) -> V128 = i32::min;
     fn i32x4_min_u(lhs: V128, rhs: V128) -> V128 = u32::min;
     fn i8x16_max_s(lhs: V128, rhs: V128) -> V128 = i8::max;
-    fn i8x16_max_u(lhs: V128, rhs: V128) -> V128 = u8::max;
-    fn i16x8_max_s(lhs: V128, rhs: V128) -> V128 = i16::max;
+    #[accelerate] fn i8x16_max_u(lhs: V128, rhs: V128) -> V128 = u8::max;
+    #[accelerate] fn i16x8_max_s(lhs: V128, rhs: V128) -> V128 = i16::max;
     fn i16x8_max_u(lhs: V128, rhs: V128) -> V128 = u16::max;
     fn i32x4_max_s(lhs: V128, rhs: V128) -> V128 = i32::max;
     fn i32x4_max_u(lhs: V128, rhs: V128) -> V128 = u32::max;
 
-    fn i8x16_avgr_u(lhs: V128, rhs: V128) -> V128 = avgr!(u8 as u16);
-    fn i16x8_avgr_u(lhs: V128, rhs: V128) -> V128 = avgr!(u16 as u32);
+    #[accelerate] fn i8x16_avgr_u(lhs: V128, rhs: V128) -> V128 = avgr!(u8 as u16);
+    #[accelerate] fn i16x8_avgr_u(lhs: V128, rhs: V128) -> V128 = avgr!(u16 as u32);
 
-    fn v128_and(lhs: V128, rhs: V128) -> V128 = <u64 as BitAnd>::bitand;
-    fn v128_or(lhs: V128, rhs: V128) -> V128 = <u64 as BitOr>::bitor;
-    fn v128_xor(lhs: V128, rhs: V128) -> V128 = <u64 as BitXor>::bitxor;
-    fn v128_andnot(lhs: V128, rhs: V128) -> V128 = |a: u64, b: u64| a & !b;
+    #[accelerate] fn v128_and(lhs: V128, rhs: V128) -> V128 = <u64 as BitAnd>::bitand;
+    #[accelerate] fn v128_or(lhs: V128, rhs: V128) -> V128 = <u64 as BitOr>::bitor;
+    #[accelerate] fn v128_xor(lhs: V128, rhs: V128) -> V128 = <u64 as BitXor>::bitxor;
+    #[accelerate] fn v128_andnot(lhs: V128, rhs: V128) -> V128 = |a: u64, b: u64| a & !b;
 
     fn f32x4_min(lhs: V128, rhs: V128) -> V128 = wasm::f32_min;
     fn f64x2_min(lhs: V128, rhs: V128) -> V128 = wasm::f64_min;
@@ -912,14 +950,14 @@ impl_binary_for! {
     fn f64x2_pmin(lhs: V128, rhs: V128) -> V128 = pmin::<f64>;
     fn f32x4_pmax(lhs: V128, rhs: V128) -> V128 = pmax::<f32>;
     fn f64x2_pmax(lhs: V128, rhs: V128) -> V128 = pmax::<f64>;
-    fn f32x4_add(lhs: V128, rhs: V128) -> V128 = op!(f32, +);
-    fn f64x2_add(lhs: V128, rhs: V128) -> V128 = op!(f64, +);
-    fn f32x4_sub(lhs: V128, rhs: V128) -> V128 = op!(f32, -);
-    fn f64x2_sub(lhs: V128, rhs: V128) -> V128 = op!(f64, -);
-    fn f32x4_div(lhs: V128, rhs: V128) -> V128 = op!(f32, /);
-    fn f64x2_div(lhs: V128, rhs: V128) -> V128 = op!(f64, /);
-    fn f32x4_mul(lhs: V128, rhs: V128) -> V128 = op!(f32, *);
-    fn f64x2_mul(lhs: V128, rhs: V128) -> V128 = op!(f64, *);
+    #[accelerate] fn f32x4_add(lhs: V128, rhs: V128) -> V128 = op!(f32, +);
+    #[accelerate] fn f64x2_add(lhs: V128, rhs: V128) -> V128 = op!(f64, +);
+    #[accelerate] fn f32x4_sub(lhs: V128, rhs: V128) -> V128 = op!(f32, -);
+    #[accelerate] fn f64x2_sub(lhs: V128, rhs: V128) -> V128 = op!(f64, -);
+    #[accelerate] fn f32x4_div(lhs: V128, rhs: V128) -> V128 = op!(f32, /);
+    #[accelerate] fn f64x2_div(lhs: V128, rhs: V128) -> V128 = op!(f64, /);
+    #[accelerate] fn f32x4_mul(lhs: V128, rhs: V128) -> V128 = op!(f32, *);
+    #[accelerate] fn f64x2_mul(lhs: V128, rhs: V128) -> V128 = op!(f64, *);
 }
 
 impl_unary_for! {
@@ -961,35 +999,38 @@ impl_unary_cast_for! {
 }
 
 macro_rules! impl_comparison_for {
-    ( $( fn $name:ident(lhs: V128, rhs: V128) -> V128 = $lanewise_expr:expr; )* ) => {
+    ( $( $( #[$accelerate:ident] )? fn $name:ident(lhs: V128, rhs: V128) -> V128 = $lanewise_expr:expr; )* ) => {
         $(
             #[doc = concat!("Executes a Wasm `", stringify!($name), "` instruction.")]
             pub fn $name(lhs: V128, rhs: V128) -> V128 {
-                V128::lanewise_comparison(lhs, rhs, $lanewise_expr)
+                dispatch!(
+                    $( #[$accelerate] )? $name(lhs, rhs)
+                    else V128::lanewise_comparison(lhs, rhs, $lanewise_expr)
+                )
             }
         )*
     };
 }
 impl_comparison_for! {
-    fn i8x16_eq(lhs: V128, rhs: V128) -> V128 = op!(i8, ==);
-    fn i16x8_eq(lhs: V128, rhs: V128) -> V128 = op!(i16, ==);
-    fn i32x4_eq(lhs: V128, rhs: V128) -> V128 = op!(i32, ==);
+    #[accelerate] fn i8x16_eq(lhs: V128, rhs: V128) -> V128 = op!(i8, ==);
+    #[accelerate] fn i16x8_eq(lhs: V128, rhs: V128) -> V128 = op!(i16, ==);
+    #[accelerate] fn i32x4_eq(lhs: V128, rhs: V128) -> V128 = op!(i32, ==);
     fn i64x2_eq(lhs: V128, rhs: V128) -> V128 = op!(i64, ==);
-    fn f32x4_eq(lhs: V128, rhs: V128) -> V128 = op!(f32, ==);
-    fn f64x2_eq(lhs: V128, rhs: V128) -> V128 = op!(f64, ==);
+    #[accelerate] fn f32x4_eq(lhs: V128, rhs: V128) -> V128 = op!(f32, ==);
+    #[accelerate] fn f64x2_eq(lhs: V128, rhs: V128) -> V128 = op!(f64, ==);
 
     fn i8x16_ne(lhs: V128, rhs: V128) -> V128 = op!(i8, !=);
     fn i16x8_ne(lhs: V128, rhs: V128) -> V128 = op!(i16, !=);
     fn i32x4_ne(lhs: V128, rhs: V128) -> V128 = op!(i32, !=);
     fn i64x2_ne(lhs: V128, rhs: V128) -> V128 = op!(i64, !=);
-    fn f32x4_ne(lhs: V128, rhs: V128) -> V128 = op!(f32, !=);
-    fn f64x2_ne(lhs: V128, rhs: V128) -> V128 = op!(f64, !=);
+    #[accelerate] fn f32x4_ne(lhs: V128, rhs: V128) -> V128 = op!(f32, !=);
+    #[accelerate] fn f64x2_ne(lhs: V128, rhs: V128) -> V128 = op!(f64, !=);
 
-    fn i8x16_lt_s(lhs: V128, rhs: V128) -> V128 = op!(i8, <);
+    #[accelerate] fn i8x16_lt_s(lhs: V128, rhs: V128) -> V128 = op!(i8, <);
     fn i8x16_lt_u(lhs: V128, rhs: V128) -> V128 = op!(u8, <);
-    fn i16x8_lt_s(lhs: V128, rhs: V128) -> V128 = op!(i16, <);
+    #[accelerate] fn i16x8_lt_s(lhs: V128, rhs: V128) -> V128 = op!(i16, <);
     fn i16x8_lt_u(lhs: V128, rhs: V128) -> V128 = op!(u16, <);
-    fn i32x4_lt_s(lhs: V128, rhs: V128) -> V128 = op!(i32, <);
+    #[accelerate] fn i32x4_lt_s(lhs: V128, rhs: V128) -> V128 = op!(i32, <);
     fn i32x4_lt_u(lhs: V128, rhs: V128) -> V128 = op!(u32, <);
     fn i64x2_lt_s(lhs: V128, rhs: V128) -> V128 = op!(i64, <);
     fn f32x4_lt(lhs: V128, rhs: V128) -> V128 = op!(f32, <);
@@ -1816,3 +1857,81 @@ fn i8x16_narrow_i16x8_s_works() {
         simd::i8x16_splat(0x7f),
     );
 }
+
+#[test]
+fn accelerated_ops_work() {
+    let inputs = [
+        V128::from(0),
+        V128::from(u128::MAX),
+        V128::from(0x8000_0000_0000_0000_8000_0000_0000_0000),
+        V128::from(0x7F7F_7F7F_7FFF_FFFF_0123_4567_89AB_CDEF),
+        simd::f32x4_splat(f32::NAN),
+        simd::f64x2_splat(-0.0),
+        simd::f64x2_splat(f64::INFINITY),
+        simd::f32x4_splat(1.5),
+    ];
+    let ops: [fn(V128, V128) -> V128; 45] = [
+        simd::i64x2_add,
+        simd::i32x4_add,
+        simd::i16x8_add,
+        simd::i8x16_add,
+        simd::i64x2_sub,
+        simd::i32x4_sub,
+        simd::i16x8_sub,
+        simd::i8x16_sub,
+        simd::i16x8_mul,
+        simd::i8x16_add_sat_s,
+        simd::i8x16_add_sat_u,
+        simd::i16x8_add_sat_s,
+        simd::i16x8_add_sat_u,
+        simd::i8x16_sub_sat_s,
+        simd::i8x16_sub_sat_u,
+        simd::i16x8_sub_sat_s,
+        simd::i16x8_sub_sat_u,
+        simd::i8x16_min_u,
+        simd::i16x8_min_s,
+        simd::i8x16_max_u,
+        simd::i16x8_max_s,
+        simd::i8x16_avgr_u,
+        simd::i16x8_avgr_u,
+        simd::v128_and,
+        simd::v128_or,
+        simd::v128_xor,
+        simd::v128_andnot,
+        simd::f32x4_add,
+        simd::f64x2_add,
+        simd::f32x4_sub,
+        simd::f64x2_sub,
+        simd::f32x4_div,
+        simd::f64x2_div,
+        simd::f32x4_mul,
+        simd::f64x2_mul,
+        simd::i8x16_eq,
+        simd::i16x8_eq,
+        simd::i32x4_eq,
+        simd::f32x4_eq,
+        simd::f64x2_eq,
+        simd::f32x4_ne,
+        simd::f64x2_ne,
+        simd::i8x16_lt_s,
+        simd::i16x8_lt_s,
+        simd::i32x4_lt_s,
+    ];
+    // Note: with debug assertions enabled accelerated operations
+    //       are checked against their portable fallbacks.
+    for op in ops {
+        for lhs in inputs {
+            for rhs in inputs {
+                op(lhs, rhs);
+            }
+        }
+    }
+    assert_eq!(
+        simd::v128_andnot(V128::from(0b1100), V128::from(0b1010)),
+        V128::from(0b0100),
+    );
+    assert_eq!(
+        simd::i8x16_avgr_u(simd::i8x16_splat(-1), simd::i8x16_splat(0)),
+        simd::i8x16_splat(-128),
+    );
+}
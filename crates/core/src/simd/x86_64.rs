@@ -0,0 +1,150 @@
+//! Accelerated Wasm `simd` operations for `x86_64` using SSE2 intrinsics.
+//!
+//! # Note
+//!
+//! SSE2 is part of the `x86_64` baseline and thus always available.
+//! All operations defined here have the exact same semantics as their
+//! portable scalar counterparts in the parent module.
+
+use crate::V128;
+use core::{
+    arch::x86_64::{
+        __m128,
+        __m128d,
+        __m128i,
+        _mm_add_epi16,
+        _mm_add_epi32,
+        _mm_add_epi64,
+        _mm_add_epi8,
+        _mm_add_pd,
+        _mm_add_ps,
+        _mm_adds_epi16,
+        _mm_adds_epi8,
+        _mm_adds_epu16,
+        _mm_adds_epu8,
+        _mm_and_si128,
+        _mm_andnot_si128,
+        _mm_avg_epu16,
+        _mm_avg_epu8,
+        _mm_cmpeq_epi16,
+        _mm_cmpeq_epi32,
+        _mm_cmpeq_epi8,
+        _mm_cmpeq_pd,
+        _mm_cmpeq_ps,
+        _mm_cmplt_epi16,
+        _mm_cmplt_epi32,
+        _mm_cmplt_epi8,
+        _mm_cmpneq_pd,
+        _mm_cmpneq_ps,
+        _mm_div_pd,
+        _mm_div_ps,
+        _mm_max_epi16,
+        _mm_max_epu8,
+        _mm_min_epi16,
+        _mm_min_epu8,
+        _mm_mul_pd,
+        _mm_mul_ps,
+        _mm_mullo_epi16,
+        _mm_or_si128,
+        _mm_sub_epi16,
+        _mm_sub_epi32,
+        _mm_sub_epi64,
+        _mm_sub_epi8,
+        _mm_sub_pd,
+        _mm_sub_ps,
+        _mm_subs_epi16,
+        _mm_subs_epi8,
+        _mm_subs_epu16,
+        _mm_subs_epu8,
+        _mm_xor_si128,
+    },
+    mem::transmute,
+};
+
+macro_rules! impl_binary_for {
+    ( $( fn $name:ident(lhs: V128, rhs: V128) -> V128 = $intrinsic:ident($ty:ty); )* ) => {
+        $(
+            #[doc = concat!("Executes a Wasm `", stringify!($name), "` instruction.")]
+            #[inline]
+            pub fn $name(lhs: V128, rhs: V128) -> V128 {
+                // Safety: `V128` and the SSE2 register types are plain 128-bit values
+                //         and all intrinsics are available on `x86_64` targets.
+                unsafe {
+                    let lhs = transmute::<V128, $ty>(lhs);
+                    let rhs = transmute::<V128, $ty>(rhs);
+                    transmute::<$ty, V128>($intrinsic(lhs, rhs))
+                }
+            }
+        )*
+    };
+}
+impl_binary_for! {
+    fn i64x2_add(lhs: V128, rhs: V128) -> V128 = _mm_add_epi64(__m128i);
+    fn i32x4_add(lhs: V128, rhs: V128) -> V128 = _mm_add_epi32(__m128i);
+    fn i16x8_add(lhs: V128, rhs: V128) -> V128 = _mm_add_epi16(__m128i);
+    fn i8x16_add(lhs: V128, rhs: V128) -> V128 = _mm_add_epi8(__m128i);
+
+    fn i64x2_sub(lhs: V128, rhs: V128) -> V128 = _mm_sub_epi64(__m128i);
+    fn i32x4_sub(lhs: V128, rhs: V128) -> V128 = _mm_sub_epi32(__m128i);
+    fn i16x8_sub(lhs: V128, rhs: V128) -> V128 = _mm_sub_epi16(__m128i);
+    fn i8x16_sub(lhs: V128, rhs: V128) -> V128 = _mm_sub_epi8(__m128i);
+
+    fn i16x8_mul(lhs: V128, rhs: V128) -> V128 = _mm_mullo_epi16(__m128i);
+
+    fn i8x16_add_sat_s(lhs: V128, rhs: V128) -> V128 = _mm_adds_epi8(__m128i);
+    fn i8x16_add_sat_u(lhs: V128, rhs: V128) -> V128 = _mm_adds_epu8(__m128i);
+    fn i16x8_add_sat_s(lhs: V128, rhs: V128) -> V128 = _mm_adds_epi16(__m128i);
+    fn i16x8_add_sat_u(lhs: V128, rhs: V128) -> V128 = _mm_adds_epu16(__m128i);
+    fn i8x16_sub_sat_s(lhs: V128, rhs: V128) -> V128 = _mm_subs_epi8(__m128i);
+    fn i8x16_sub_sat_u(lhs: V128, rhs: V128) -> V128 = _mm_subs_epu8(__m128i);
+    fn i16x8_sub_sat_s(lhs: V128, rhs: V128) -> V128 = _mm_subs_epi16(__m128i);
+    fn i16x8_sub_sat_u(lhs: V128, rhs: V128) -> V128 = _mm_subs_epu16(__m128i);
+
+    fn i8x16_min_u(lhs: V128, rhs: V128) -> V128 = _mm_min_epu8(__m128i);
+    fn i16x8_min_s(lhs: V128, rhs: V128) -> V128 = _mm_min_epi16(__m128i);
+    fn i8x16_max_u(lhs: V128, rhs: V128) -> V128 = _mm_max_epu8(__m128i);
+    fn i16x8_max_s(lhs: V128, rhs: V128) -> V128 = _mm_max_epi16(__m128i);
+
+    fn i8x16_avgr_u(lhs: V128, rhs: V128) -> V128 = _mm_avg_epu8(__m128i);
+    fn i16x8_avgr_u(lhs: V128, rhs: V128) -> V128 = _mm_avg_epu16(__m128i);
+
+    fn v128_and(lhs: V128, rhs: V128) -> V128 = _mm_and_si128(__m128i);
+    fn v128_or(lhs: V128, rhs: V128) -> V128 = _mm_or_si128(__m128i);
+    fn v128_xor(lhs: V128, rhs: V128) -> V128 = _mm_xor_si128(__m128i);
+
+    fn f32x4_add(lhs: V128, rhs: V128) -> V128 = _mm_add_ps(__m128);
+    fn f64x2_add(lhs: V128, rhs: V128) -> V128 = _mm_add_pd(__m128d);
+    fn f32x4_sub(lhs: V128, rhs: V128) -> V128 = _mm_sub_ps(__m128);
+    fn f64x2_sub(lhs: V128, rhs: V128) -> V128 = _mm_sub_pd(__m128d);
+    fn f32x4_div(lhs: V128, rhs: V128) -> V128 = _mm_div_ps(__m128);
+    fn f64x2_div(lhs: V128, rhs: V128) -> V128 = _mm_div_pd(__m128d);
+    fn f32x4_mul(lhs: V128, rhs: V128) -> V128 = _mm_mul_ps(__m128);
+    fn f64x2_mul(lhs: V128, rhs: V128) -> V128 = _mm_mul_pd(__m128d);
+
+    fn i8x16_eq(lhs: V128, rhs: V128) -> V128 = _mm_cmpeq_epi8(__m128i);
+    fn i16x8_eq(lhs: V128, rhs: V128) -> V128 = _mm_cmpeq_epi16(__m128i);
+    fn i32x4_eq(lhs: V128, rhs: V128) -> V128 = _mm_cmpeq_epi32(__m128i);
+    fn f32x4_eq(lhs: V128, rhs: V128) -> V128 = _mm_cmpeq_ps(__m128);
+    fn f64x2_eq(lhs: V128, rhs: V128) -> V128 = _mm_cmpeq_pd(__m128d);
+
+    fn f32x4_ne(lhs: V128, rhs: V128) -> V128 = _mm_cmpneq_ps(__m128);
+    fn f64x2_ne(lhs: V128, rhs: V128) -> V128 = _mm_cmpneq_pd(__m128d);
+
+    fn i8x16_lt_s(lhs: V128, rhs: V128) -> V128 = _mm_cmplt_epi8(__m128i);
+    fn i16x8_lt_s(lhs: V128, rhs: V128) -> V128 = _mm_cmplt_epi16(__m128i);
+    fn i32x4_lt_s(lhs: V128, rhs: V128) -> V128 = _mm_cmplt_epi32(__m128i);
+}
+
+/// Executes a Wasm `v128_andnot` instruction.
+#[inline]
+pub fn v128_andnot(lhs: V128, rhs: V128) -> V128 {
+    // Safety: `V128` and `__m128i` are plain 128-bit values and
+    //         the intrinsic is available on `x86_64` targets.
+    //
+    // Note: `_mm_andnot_si128(a, b)` computes `!a & b` thus the operands are swapped.
+    unsafe {
+        let lhs = transmute::<V128, __m128i>(lhs);
+        let rhs = transmute::<V128, __m128i>(rhs);
+        transmute::<__m128i, V128>(_mm_andnot_si128(rhs, lhs))
+    }
+}
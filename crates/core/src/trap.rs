@@ -291,6 +291,46 @@ pub enum TrapCode {
     /// desire on the part of the embedder to trap the interpreter rather than
     /// merely fail the growth operation.
     GrowthOperationLimited,
+
+    /// This trap is raised when a WebAssembly execution exceeded its instruction limit.
+    ///
+    /// The instruction limit is independent of fuel metering and bounds the number
+    /// of Wasmi bytecode instructions executed per call.
+    InstructionLimitExceeded,
+
+    /// Attempt to write to a read-only range of a linear memory.
+    ///
+    /// Read-only ranges are set up by the host to protect the contents
+    /// of a linear memory from being modified by Wasm code.
+    MemoryReadOnly,
+
+    /// This trap is raised when a WebAssembly execution exceeded its deadline.
+    ///
+    /// Deadlines are set up by the host to bound the wall-clock time of an execution.
+    DeadlineExceeded,
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for TrapCode {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.choose(&[
+            Self::UnreachableCodeReached,
+            Self::MemoryOutOfBounds,
+            Self::TableOutOfBounds,
+            Self::IndirectCallToNull,
+            Self::IntegerDivisionByZero,
+            Self::IntegerOverflow,
+            Self::BadConversionToInteger,
+            Self::StackOverflow,
+            Self::BadSignature,
+            Self::OutOfFuel,
+            Self::GrowthOperationLimited,
+            Self::InstructionLimitExceeded,
+            Self::MemoryReadOnly,
+            Self::DeadlineExceeded,
+        ])
+        .copied()
+    }
 }
 
 impl TrapCode {
@@ -313,6 +353,9 @@ impl TrapCode {
             Self::BadSignature => "indirect call type mismatch",
             Self::OutOfFuel => "all fuel consumed by WebAssembly",
             Self::GrowthOperationLimited => "growth operation limited",
+            Self::InstructionLimitExceeded => "instruction limit exceeded",
+            Self::MemoryReadOnly => "write to read-only memory",
+            Self::DeadlineExceeded => "execution deadline exceeded",
         }
     }
 }
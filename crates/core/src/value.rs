@@ -6,6 +6,7 @@ use crate::{hint::unlikely, TrapCode};
 ///
 /// [`Val`]: enum.Value.html
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ValType {
     /// 32-bit signed or unsigned integer.
     I32,
@@ -509,7 +510,21 @@ impl From<u128> for V128 {
 impl V128 {
     /// Returns the `self` as a 128-bit Rust integer.
     pub fn as_u128(&self) -> u128 {
-        u128::from_ne_bytes(self.0)
+        u128::from_le_bytes(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for V128 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_u128().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for V128 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u128::deserialize(deserializer).map(Self::from)
     }
 }
 
@@ -0,0 +1,328 @@
+//! Implementations of [`Arbitrary`] for Wasmi bytecode for fuzzing purposes.
+//!
+//! - Use the [`Arbitrary`] implementation of [`Instruction`] to generate arbitrary
+//!   instructions that are not necessarily valid, e.g. for fuzzing Wasmi bytecode utilities.
+//! - Use [`InstrSequence`] to generate well-formed sequences of instructions,
+//!   e.g. for fuzzing the Wasmi executor independently of the Wasm frontend.
+
+use crate::{index::*, *};
+use ::arbitrary::{Arbitrary, Error, Result, Unstructured};
+use ::core::num::{NonZeroI16, NonZeroI32, NonZeroI64, NonZeroU16, NonZeroU32, NonZeroU64};
+use alloc::vec::Vec;
+
+macro_rules! impl_arbitrary_from {
+    ( $( $from:ty => $ty:ty ),* $(,)? ) => {
+        $(
+            impl<'a> Arbitrary<'a> for $ty {
+                fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                    <$from as Arbitrary>::arbitrary(u).map(<$ty>::from)
+                }
+            }
+        )*
+    };
+}
+impl_arbitrary_from! {
+    i16 => Reg,
+    u32 => Func,
+    u32 => FuncType,
+    u32 => InternalFunc,
+    u32 => Global,
+    u32 => Memory,
+    u32 => Table,
+    u32 => Data,
+    u32 => Elem,
+    i16 => AnyConst16,
+    u32 => AnyConst32,
+    i16 => Const16<i32>,
+    u16 => Const16<u32>,
+    i16 => Const16<i64>,
+    u16 => Const16<u64>,
+    NonZeroI16 => Const16<NonZeroI32>,
+    NonZeroU16 => Const16<NonZeroU32>,
+    NonZeroI16 => Const16<NonZeroI64>,
+    NonZeroU16 => Const16<NonZeroU64>,
+    i32 => Const32<i32>,
+    u32 => Const32<u32>,
+    i32 => Const32<i64>,
+    u32 => Const32<u64>,
+    f32 => Const32<f64>,
+    i32 => BranchOffset,
+    i16 => BranchOffset16,
+    u32 => BlockFuel,
+}
+
+macro_rules! impl_arbitrary_try_from {
+    ( $( $from:ty => $ty:ty ),* $(,)? ) => {
+        $(
+            impl<'a> Arbitrary<'a> for $ty {
+                fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                    let value = <$from as Arbitrary>::arbitrary(u)?;
+                    <$ty>::try_from(u64::from(value)).map_err(|_| Error::IncorrectFormat)
+                }
+            }
+        )*
+    };
+}
+impl_arbitrary_try_from! {
+    u8 => Offset8,
+    u16 => Offset16,
+}
+
+impl<'a> Arbitrary<'a> for RegSpan {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.arbitrary().map(Self::new)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Offset64Lo {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let (_hi, lo) = Offset64::split(u64::from(u.arbitrary::<u32>()?));
+        Ok(lo)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Offset64Hi {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let (hi, _lo) = Offset64::split(u64::from(u.arbitrary::<u32>()?) << 32);
+        Ok(hi)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Address32 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let address = u64::from(u.arbitrary::<u32>()?);
+        Address::try_from(address)
+            .and_then(Address32::try_from)
+            .map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a, T> Arbitrary<'a> for Sign<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.arbitrary::<bool>()? {
+            true => Ok(Self::pos()),
+            false => Ok(Self::neg()),
+        }
+    }
+}
+
+macro_rules! impl_arbitrary_for_shift_amount {
+    ( $( $ty:ty => $shamt:ty ),* $(,)? ) => {
+        $(
+            impl<'a> Arbitrary<'a> for ShiftAmount<$shamt> {
+                fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                    // Zero shift amounts are not representable and thus skipped.
+                    let bits = <$ty>::BITS as $shamt;
+                    let value = u.int_in_range(1..=(bits - 1))?;
+                    <$ty>::into_shift_amount(value).ok_or(Error::IncorrectFormat)
+                }
+            }
+        )*
+    };
+}
+impl_arbitrary_for_shift_amount! {
+    i32 => i32,
+    i64 => i64,
+    u64 => u32,
+}
+
+impl<'a> Arbitrary<'a> for BoundedRegSpan {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::new(u.arbitrary()?, u.arbitrary()?))
+    }
+}
+
+impl<'a, const N: u16> Arbitrary<'a> for FixedRegSpan<N> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // The span must not overflow the register space.
+        let max = i16::MAX.saturating_sub_unsigned(N);
+        let head = Reg::from(u.int_in_range(i16::MIN..=max)?);
+        Self::new(RegSpan::new(head)).map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+macro_rules! impl_arbitrary_for_instruction {
+    (
+        $(
+            $( #[doc = $doc:literal] )*
+            #[snake_name($snake_name:ident)]
+            $name:ident
+            $(
+                {
+                    $( @ $result_name:ident: $result_ty:ty, )?
+                    $(
+                        $( #[$field_docs:meta] )*
+                        $field_name:ident: $field_ty:ty
+                    ),*
+                    $(,)?
+                }
+            )?
+        ),* $(,)?
+    ) => {
+        impl<'a> Arbitrary<'a> for Instruction {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                let constructors: &[fn(&mut Unstructured<'a>) -> Result<Instruction>] = &[
+                    $(
+                        |_u| Ok(Instruction::$name $( {
+                            $( $result_name: _u.arbitrary()?, )?
+                            $( $field_name: _u.arbitrary()?, )*
+                        } )?),
+                    )*
+                ];
+                u.choose(constructors)?(u)
+            }
+        }
+    };
+}
+for_each_op!(impl_arbitrary_for_instruction);
+
+/// The maximum number of registers used by an [`InstrSequence`].
+const MAX_REGISTERS: u16 = 16;
+
+/// The maximum number of instructions of an [`InstrSequence`] excluding its return.
+const MAX_INSTRS: usize = 64;
+
+/// Returns an arbitrary [`Reg`] within `0..len_registers`.
+fn arbitrary_reg(u: &mut Unstructured, len_registers: u16) -> Result<Reg> {
+    let index = u.int_in_range(0..=(len_registers - 1))?;
+    Reg::try_from(u32::from(index)).map_err(|_| Error::IncorrectFormat)
+}
+
+/// Constructs an arbitrary [`Instruction`] using registers within `0..len_registers`.
+type StraightLineOp = fn(&mut Unstructured, u16) -> Result<Instruction>;
+
+/// Expands to [`StraightLineOp`] constructors for [`Instruction`]s with the same operands.
+///
+/// Operands marked with `imm` are arbitrary immediates and all other operands are registers.
+macro_rules! straight_line_ops {
+    ( $operands:tt => [ $( $name:ident ),* $(,)? ] ) => {
+        &[ $( straight_line_ops!(@op $name $operands) ),* ]
+    };
+    ( @op $name:ident { $( $field:ident $( : $imm:ident )? ),* } ) => {
+        |u, len_registers| {
+            Ok(Instruction::$name {
+                $( $field: straight_line_ops!(@operand u, len_registers $(, $imm)?), )*
+            })
+        }
+    };
+    ( @operand $u:ident, $len_registers:ident ) => {
+        arbitrary_reg($u, $len_registers)?
+    };
+    ( @operand $u:ident, $len_registers:ident, imm ) => {
+        $u.arbitrary()?
+    };
+}
+
+/// Single word [`Instruction`]s that operate on registers only and never branch.
+const STRAIGHT_LINE_OPS: &[&[StraightLineOp]] = &[
+    straight_line_ops!({ result, lhs, rhs } => [
+        I32Eq, I32Ne, I32LtS, I32LtU, I32LeS, I32LeU,
+        I64Eq, I64Ne, I64LtS, I64LtU, I64LeS, I64LeU,
+        I32Add, I32Sub, I32Mul, I32DivS, I32DivU, I32RemS, I32RemU,
+        I32And, I32AndEqz, I32Or, I32OrEqz, I32Xor, I32XorEqz,
+        I32Shl, I32ShrU, I32ShrS, I32Rotl, I32Rotr,
+        I64Add, I64Sub, I64Mul, I64DivS, I64DivU, I64RemS, I64RemU,
+        I64And, I64Or, I64Xor, I64Shl, I64ShrU, I64ShrS, I64Rotl, I64Rotr,
+    ]),
+    straight_line_ops!({ result, input } => [
+        I32Clz, I32Ctz, I32Popcnt, I64Clz, I64Ctz, I64Popcnt,
+        I32WrapI64, I32Extend8S, I32Extend16S, I64Extend8S, I64Extend16S, I64Extend32S,
+    ]),
+    straight_line_ops!({ result, lhs, rhs: imm } => [
+        I32EqImm16, I32NeImm16, I32LtSImm16Rhs, I32LeSImm16Rhs, I32AddImm16, I32MulImm16,
+        I32AndEqzImm16, I32AndImm16, I32OrEqzImm16, I32OrImm16, I32XorEqzImm16, I32XorImm16,
+        I64EqImm16, I64NeImm16, I64LtSImm16Rhs, I64LeSImm16Rhs, I64AddImm16, I64MulImm16,
+        I64AndImm16, I64OrImm16, I64XorImm16,
+        I32LtUImm16Rhs, I32LeUImm16Rhs, I64LtUImm16Rhs, I64LeUImm16Rhs,
+        I32DivSImm16Rhs, I32RemSImm16Rhs, I32DivUImm16Rhs, I32RemUImm16Rhs,
+        I64DivSImm16Rhs, I64RemSImm16Rhs, I64DivUImm16Rhs, I64RemUImm16Rhs,
+        I32ShlBy, I32ShrUBy, I32ShrSBy, I32RotlBy, I32RotrBy,
+        I64ShlBy, I64ShrUBy, I64ShrSBy, I64RotlBy, I64RotrBy,
+    ]),
+    straight_line_ops!({ result, lhs: imm, rhs } => [
+        I32LtSImm16Lhs, I32LeSImm16Lhs, I32SubImm16Lhs, I32DivSImm16Lhs, I32RemSImm16Lhs,
+        I32ShlImm16, I32ShrUImm16, I32ShrSImm16, I32RotlImm16, I32RotrImm16,
+        I64LtSImm16Lhs, I64LeSImm16Lhs, I64SubImm16Lhs, I64DivSImm16Lhs, I64RemSImm16Lhs,
+        I64ShlImm16, I64ShrUImm16, I64ShrSImm16, I64RotlImm16, I64RotrImm16,
+        I32LtUImm16Lhs, I32LeUImm16Lhs, I32DivUImm16Lhs, I32RemUImm16Lhs,
+        I64LtUImm16Lhs, I64LeUImm16Lhs, I64DivUImm16Lhs, I64RemUImm16Lhs,
+    ]),
+    straight_line_ops!({ result, value } => [Copy]),
+    straight_line_ops!({ result, value: imm } => [CopyImm32, CopyI64Imm32]),
+    #[cfg(not(feature = "no-float"))]
+    straight_line_ops!({ result, lhs, rhs } => [
+        F32Eq, F32Ne, F32Lt, F32Le,
+        F64Eq, F64Ne, F64Lt, F64Le,
+        F32Add, F32Sub, F32Mul, F32Div, F32Min, F32Max, F32Copysign,
+        F64Add, F64Sub, F64Mul, F64Div, F64Min, F64Max, F64Copysign,
+    ]),
+    #[cfg(not(feature = "no-float"))]
+    straight_line_ops!({ result, input } => [
+        F32Abs, F32Neg, F32Ceil, F32Floor, F32Trunc, F32Nearest, F32Sqrt,
+        F64Abs, F64Neg, F64Ceil, F64Floor, F64Trunc, F64Nearest, F64Sqrt,
+        I32TruncF32S, I32TruncF32U, I32TruncF64S, I32TruncF64U,
+        I64TruncF32S, I64TruncF32U, I64TruncF64S, I64TruncF64U,
+        I32TruncSatF32S, I32TruncSatF32U, I32TruncSatF64S, I32TruncSatF64U,
+        I64TruncSatF32S, I64TruncSatF32U, I64TruncSatF64S, I64TruncSatF64U,
+        F32DemoteF64, F64PromoteF32,
+        F32ConvertI32S, F32ConvertI32U, F32ConvertI64S, F32ConvertI64U,
+        F64ConvertI32S, F64ConvertI32U, F64ConvertI64S, F64ConvertI64U,
+    ]),
+    #[cfg(not(feature = "no-float"))]
+    straight_line_ops!({ result, lhs, rhs: imm } => [F32CopysignImm, F64CopysignImm]),
+    #[cfg(not(feature = "no-float"))]
+    straight_line_ops!({ result, value: imm } => [CopyF64Imm32]),
+];
+
+/// A well-formed sequence of Wasmi [`Instruction`]s.
+///
+/// The generated sequence is straight-line code that ends with a [`Instruction::ReturnReg`].
+///
+/// - Every instruction is encoded as a single instruction word.
+/// - All instructions only access registers within `0..len_registers`.
+/// - No instruction accesses function local constants, the store or branches.
+///
+/// Execution of the sequence may still trap, e.g. for divisions by zero.
+#[derive(Debug, Clone)]
+pub struct InstrSequence {
+    /// The number of registers used by the instructions.
+    len_registers: u16,
+    /// The instructions of the sequence.
+    instrs: Vec<Instruction>,
+}
+
+impl InstrSequence {
+    /// Returns the number of registers used by the [`InstrSequence`].
+    pub fn len_registers(&self) -> u16 {
+        self.len_registers
+    }
+
+    /// Returns the [`Instruction`]s of the [`InstrSequence`].
+    pub fn instrs(&self) -> &[Instruction] {
+        &self.instrs
+    }
+
+    /// Consumes `self` and returns its [`Instruction`]s.
+    pub fn into_instrs(self) -> Vec<Instruction> {
+        self.instrs
+    }
+}
+
+impl<'a> Arbitrary<'a> for InstrSequence {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let len_registers = u.int_in_range(1..=MAX_REGISTERS)?;
+        let len_instrs = u.int_in_range(0..=MAX_INSTRS)?;
+        let mut instrs = Vec::with_capacity(len_instrs + 1);
+        for _ in 0..len_instrs {
+            let ops = u.choose(STRAIGHT_LINE_OPS)?;
+            let op = u.choose(ops)?;
+            instrs.push(op(u, len_registers)?);
+        }
+        let value = arbitrary_reg(u, len_registers)?;
+        instrs.push(Instruction::return_reg(value));
+        Ok(Self {
+            len_registers,
+            instrs,
+        })
+    }
+}
@@ -0,0 +1,445 @@
+//! Binary encoding and decoding of sequences of Wasmi [`Instruction`]s.
+//!
+//! This allows external tools to store, load and manipulate Wasmi bytecode
+//! independently of the Wasm modules it has been translated from.
+//!
+//! # Format
+//!
+//! Every instruction word is encoded as its 16-bit opcode followed by its fields
+//! in declaration order. All values are encoded in little-endian byte order with
+//! the width of their underlying representation.
+//!
+//! Instruction words are encoded one by one. This includes the parameter words of
+//! multi-word instructions such as [`Instruction::RegisterList`], so that branch
+//! offsets, which are relative to the index of an instruction word, stay valid.
+//!
+//! # Note
+//!
+//! - Opcodes are the indices of the [`Instruction`] variants and thus depend on the
+//!   version of this crate as well as on its enabled `no-float`, `no-bulk-memory` and `simd`
+//!   crate features.
+//! - Decoding validates single instruction words as well as the placement of parameter
+//!   words. It does not validate whether instruction sequences are well-formed otherwise.
+
+use crate::{core::TrapCode, index::*, *};
+use ::core::{
+    fmt,
+    num::{NonZeroI16, NonZeroI32, NonZeroI64, NonZeroU16, NonZeroU32, NonZeroU64},
+};
+use alloc::vec::Vec;
+#[cfg(feature = "simd")]
+use wasmi_core::simd::ImmLaneIdx;
+
+/// An error that may occur upon decoding a sequence of [`Instruction`]s.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    /// The byte offset at which the error occurred.
+    offset: usize,
+    /// The kind of the error.
+    kind: DecodeErrorKind,
+}
+
+/// The kind of a [`DecodeError`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// The encoded bytes ended in the middle of an instruction word.
+    UnexpectedEnd,
+    /// Encountered an opcode that does not belong to any [`Instruction`].
+    UnknownOpCode(u16),
+    /// Encountered an out of bounds value for a field of an [`Instruction`].
+    InvalidField,
+    /// Encountered a parameter word that does not follow the instruction it belongs to.
+    ///
+    /// This is the case for a parameter word at the start of the sequence
+    /// or for an [`Instruction::RegisterList`] that is not followed by one of
+    ///
+    /// - [`Instruction::RegisterList`]
+    /// - [`Instruction::Register`]
+    /// - [`Instruction::Register2`]
+    /// - [`Instruction::Register3`]
+    MisplacedParam,
+}
+
+impl DecodeError {
+    /// Creates a new [`DecodeError`] of `kind` at byte `offset`.
+    fn new(offset: usize, kind: DecodeErrorKind) -> Self {
+        Self { offset, kind }
+    }
+
+    /// Returns the byte offset at which the [`DecodeError`] occurred.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the [`DecodeErrorKind`] of the [`DecodeError`].
+    pub fn kind(&self) -> DecodeErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of instruction sequence"),
+            Self::UnknownOpCode(opcode) => write!(f, "unknown opcode: {opcode}"),
+            Self::InvalidField => write!(f, "invalid instruction field"),
+            Self::MisplacedParam => write!(f, "misplaced instruction parameter"),
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte offset {}", self.kind, self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// Encodes all `instrs` and appends the encoded bytes to `buffer`.
+pub fn encode(instrs: &[Instruction], buffer: &mut Vec<u8>) {
+    for instr in instrs {
+        instr.encode(buffer);
+    }
+}
+
+/// Decodes a sequence of [`Instruction`]s from `bytes`.
+///
+/// # Errors
+///
+/// If `bytes` do not encode a sequence of valid instruction words with properly
+/// placed parameter words.
+pub fn decode(bytes: &[u8]) -> Result<Vec<Instruction>, DecodeError> {
+    let mut decoder = Decoder { bytes, pos: 0 };
+    let mut instrs = Vec::new();
+    while !decoder.is_empty() {
+        let start = decoder.pos;
+        let instr = Instruction::decode(&mut decoder)?;
+        let expects_param = matches!(instrs.last(), Some(Instruction::RegisterList { .. }));
+        let is_misplaced = match instr {
+            Instruction::RegisterList { .. }
+            | Instruction::Register { .. }
+            | Instruction::Register2 { .. }
+            | Instruction::Register3 { .. } => instrs.is_empty(),
+            _ => expects_param || (instrs.is_empty() && is_param(&instr)),
+        };
+        if is_misplaced {
+            return Err(DecodeError::new(start, DecodeErrorKind::MisplacedParam));
+        }
+        instrs.push(instr);
+    }
+    if let Some(Instruction::RegisterList { .. }) = instrs.last() {
+        return Err(DecodeError::new(
+            decoder.pos,
+            DecodeErrorKind::MisplacedParam,
+        ));
+    }
+    Ok(instrs)
+}
+
+/// Returns `true` if `instr` is a parameter word that never starts an instruction.
+fn is_param(instr: &Instruction) -> bool {
+    #[cfg(not(feature = "no-float"))]
+    if let Instruction::F64Const32 { .. } = instr {
+        return true;
+    }
+    matches!(
+        instr,
+        Instruction::TableIndex { .. }
+            | Instruction::MemoryIndex { .. }
+            | Instruction::DataIndex { .. }
+            | Instruction::ElemIndex { .. }
+            | Instruction::Const32 { .. }
+            | Instruction::I64Const32 { .. }
+            | Instruction::BranchTableTarget { .. }
+            | Instruction::BranchTableTargetNonOverlapping { .. }
+            | Instruction::Imm16AndImm32 { .. }
+            | Instruction::RegisterAndImm32 { .. }
+            | Instruction::RegisterSpan { .. }
+            | Instruction::Register { .. }
+            | Instruction::Register2 { .. }
+            | Instruction::Register3 { .. }
+            | Instruction::RegisterList { .. }
+            | Instruction::CallIndirectParams { .. }
+            | Instruction::CallIndirectParamsImm16 { .. }
+    )
+}
+
+/// Reads encoded values from a byte slice.
+#[derive(Debug)]
+struct Decoder<'a> {
+    /// The encoded bytes.
+    bytes: &'a [u8],
+    /// The current byte position within `bytes`.
+    pos: usize,
+}
+
+impl Decoder<'_> {
+    /// Returns `true` if all bytes have been read.
+    fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    /// Reads the next `N` bytes.
+    fn read<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let Some(bytes) = self
+            .bytes
+            .get(self.pos..)
+            .and_then(|rest| rest.first_chunk::<N>())
+        else {
+            return Err(DecodeError::new(self.pos, DecodeErrorKind::UnexpectedEnd));
+        };
+        self.pos += N;
+        Ok(*bytes)
+    }
+}
+
+/// Types that are encoded as fields of instruction words.
+trait Field: Sized {
+    /// Appends the encoding of `self` to `buffer`.
+    fn encode(&self, buffer: &mut Vec<u8>);
+
+    /// Decodes a value of `Self` via `decoder`.
+    fn decode(decoder: &mut Decoder) -> Result<Self, DecodeError>;
+}
+
+macro_rules! impl_field_for_int {
+    ( $($ty:ty),* $(,)? ) => {
+        $(
+            impl Field for $ty {
+                fn encode(&self, buffer: &mut Vec<u8>) {
+                    buffer.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+                    decoder.read().map(<$ty>::from_le_bytes)
+                }
+            }
+        )*
+    };
+}
+impl_field_for_int!(i8, u8, i16, u16, u32);
+
+/// Implements [`Field`] for types encoded via their underlying representation.
+///
+/// The `encode` expression converts `self` into its representation
+/// and the `decode` expression returns `None` for invalid representations.
+macro_rules! impl_field_via {
+    ( $( $ty:ty => $repr:ty { |$this:ident| $encode:expr, |$raw:ident| $decode:expr $(,)? } )* ) => {
+        $(
+            impl Field for $ty {
+                fn encode(&self, buffer: &mut Vec<u8>) {
+                    let $this = *self;
+                    let repr: $repr = $encode;
+                    repr.encode(buffer)
+                }
+
+                fn decode(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+                    let start = decoder.pos;
+                    let $raw = <$repr as Field>::decode(decoder)?;
+                    let value: Option<Self> = $decode;
+                    value.ok_or(DecodeError::new(start, DecodeErrorKind::InvalidField))
+                }
+            }
+        )*
+    };
+}
+impl_field_via! {
+    Reg => i16 { |this| i16::from(this), |raw| Some(Reg::from(raw)) }
+    Func => u32 { |this| u32::from(this), |raw| Some(Func::from(raw)) }
+    FuncType => u32 { |this| u32::from(this), |raw| Some(FuncType::from(raw)) }
+    InternalFunc => u32 { |this| u32::from(this), |raw| Some(InternalFunc::from(raw)) }
+    Global => u32 { |this| u32::from(this), |raw| Some(Global::from(raw)) }
+    Memory => u32 { |this| u32::from(this), |raw| Some(Memory::from(raw)) }
+    Table => u32 { |this| u32::from(this), |raw| Some(Table::from(raw)) }
+    Data => u32 { |this| u32::from(this), |raw| Some(Data::from(raw)) }
+    Elem => u32 { |this| u32::from(this), |raw| Some(Elem::from(raw)) }
+    AnyConst16 => i16 { |this| i16::from(this), |raw| Some(AnyConst16::from(raw)) }
+    AnyConst32 => u32 { |this| u32::from(this), |raw| Some(AnyConst32::from(raw)) }
+    Const16<i32> => i16 { |this| i32::from(this) as i16, |raw| Some(Self::from(raw)) }
+    Const16<u32> => u16 { |this| u32::from(this) as u16, |raw| Some(Self::from(raw)) }
+    Const16<i64> => i16 { |this| i64::from(this) as i16, |raw| Some(Self::from(raw)) }
+    Const16<u64> => u16 { |this| u64::from(this) as u16, |raw| Some(Self::from(raw)) }
+    Const16<NonZeroI32> => i16 {
+        |this| NonZeroI32::from(this).get() as i16,
+        |raw| NonZeroI16::new(raw).map(Self::from),
+    }
+    Const16<NonZeroU32> => u16 {
+        |this| NonZeroU32::from(this).get() as u16,
+        |raw| NonZeroU16::new(raw).map(Self::from),
+    }
+    Const16<NonZeroI64> => i16 {
+        |this| NonZeroI64::from(this).get() as i16,
+        |raw| NonZeroI16::new(raw).map(Self::from),
+    }
+    Const16<NonZeroU64> => u16 {
+        |this| NonZeroU64::from(this).get() as u16,
+        |raw| NonZeroU16::new(raw).map(Self::from),
+    }
+    Const32<i64> => u32 { |this| u32::from(AnyConst32::from(this)), |raw| Some(Self::from(raw as i32)) }
+    Const32<u64> => u32 { |this| u32::from(AnyConst32::from(this)), |raw| Some(Self::from(raw)) }
+    Const32<f64> => u32 {
+        |this| u32::from(AnyConst32::from(this)),
+        |raw| Some(Self::from(f32::from_bits(raw))),
+    }
+    BranchOffset => u32 { |this| this.to_i32() as u32, |raw| Some(Self::from(raw as i32)) }
+    BranchOffset16 => i16 { |this| this.to_i16(), |raw| Some(Self::from(raw)) }
+    BlockFuel => u32 { |this| this.to_u64() as u32, |raw| Some(Self::from(raw)) }
+    Offset8 => u8 {
+        |this| u64::from(Offset64::from(this)) as u8,
+        |raw| Self::try_from(u64::from(raw)).ok(),
+    }
+    Offset16 => u16 {
+        |this| u64::from(Offset64::from(this)) as u16,
+        |raw| Self::try_from(u64::from(raw)).ok(),
+    }
+    Offset64Hi => u32 { |this| this.0, |raw| Some(Self(raw)) }
+    Offset64Lo => u32 { |this| this.0, |raw| Some(Self(raw)) }
+    Address32 => u32 {
+        |this| usize::from(this) as u32,
+        |raw| Address::try_from(u64::from(raw)).and_then(Self::try_from).ok(),
+    }
+    Sign<f32> => u8 { |this| u8::from(f32::from(this) > 0.0), |raw| sign_from_u8(raw) }
+    Sign<f64> => u8 { |this| u8::from(f64::from(this) > 0.0), |raw| sign_from_u8(raw) }
+    ShiftAmount<i32> => i16 {
+        |this| i32::from(this) as i16,
+        |raw| i32::into_shift_amount(i32::from(raw)).filter(|shamt| i32::from(*shamt) == i32::from(raw)),
+    }
+    ShiftAmount<i64> => i16 {
+        |this| i64::from(this) as i16,
+        |raw| i64::into_shift_amount(i64::from(raw)).filter(|shamt| i64::from(*shamt) == i64::from(raw)),
+    }
+    ShiftAmount<u32> => u16 {
+        |this| u32::from(this) as u16,
+        |raw| u64::into_shift_amount(u32::from(raw)).filter(|shamt| u32::from(*shamt) == u32::from(raw)),
+    }
+    RegSpan => i16 { |this| i16::from(this.head()), |raw| Some(Self::new(Reg::from(raw))) }
+    FixedRegSpan<2> => i16 {
+        |this| i16::from(this.span().head()),
+        |raw| Self::new(RegSpan::new(Reg::from(raw))).ok(),
+    }
+    TrapCode => u8 {
+        |this| {
+            let Some(index) = primitive::TRAP_CODES.iter().position(|code| *code == this) else {
+                panic!("missing encoding for trap code: {this:?}")
+            };
+            index as u8
+        },
+        |raw| primitive::TRAP_CODES.get(usize::from(raw)).copied(),
+    }
+}
+
+/// Returns the [`Sign`] encoded as `raw` if valid.
+fn sign_from_u8<T>(raw: u8) -> Option<Sign<T>> {
+    match raw {
+        0 => Some(Sign::neg()),
+        1 => Some(Sign::pos()),
+        _ => None,
+    }
+}
+
+impl Field for BoundedRegSpan {
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        self.span().encode(buffer);
+        self.len().encode(buffer);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        Ok(Self::new(RegSpan::decode(decoder)?, u16::decode(decoder)?))
+    }
+}
+
+impl<const N: usize> Field for [Reg; N] {
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        for reg in self {
+            reg.encode(buffer);
+        }
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        let mut regs = [Reg::from(0); N];
+        for reg in &mut regs {
+            *reg = Reg::decode(decoder)?;
+        }
+        Ok(regs)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<const N: u8> Field for ImmLaneIdx<N> {
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        u8::from(*self).encode(buffer)
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        let start = decoder.pos;
+        Self::try_from(u8::decode(decoder)?)
+            .map_err(|_| DecodeError::new(start, DecodeErrorKind::InvalidField))
+    }
+}
+
+macro_rules! impl_field_for_instruction {
+    (
+        $(
+            $( #[doc = $doc:literal] )*
+            #[snake_name($snake_name:ident)]
+            $name:ident
+            $(
+                {
+                    $( @ $result_name:ident: $result_ty:ty, )?
+                    $(
+                        $( #[$field_docs:meta] )*
+                        $field_name:ident: $field_ty:ty
+                    ),*
+                    $(,)?
+                }
+            )?
+        ),* $(,)?
+    ) => {
+        /// The opcodes of all [`Instruction`]s.
+        #[repr(u16)]
+        enum OpCode {
+            $( $name ),*
+        }
+
+        /// The decoders of all [`Instruction`]s indexed by their opcodes.
+        static DECODERS: &[fn(&mut Decoder) -> Result<Instruction, DecodeError>] = &[
+            $(
+                |_decoder| Ok(Instruction::$name $( {
+                    $( $result_name: Field::decode(_decoder)?, )?
+                    $( $field_name: Field::decode(_decoder)?, )*
+                } )?),
+            )*
+        ];
+
+        impl Field for Instruction {
+            fn encode(&self, buffer: &mut Vec<u8>) {
+                match self {
+                    $(
+                        Self::$name $( {
+                            $( $result_name, )?
+                            $( $field_name, )*
+                        } )? => {
+                            (OpCode::$name as u16).encode(buffer);
+                            $(
+                                $( $result_name.encode(buffer); )?
+                                $( $field_name.encode(buffer); )*
+                            )?
+                        }
+                    )*
+                }
+            }
+
+            fn decode(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+                let start = decoder.pos;
+                let opcode = u16::decode(decoder)?;
+                let Some(decode) = DECODERS.get(usize::from(opcode)) else {
+                    return Err(DecodeError::new(start, DecodeErrorKind::UnknownOpCode(opcode)));
+                };
+                decode(decoder)
+            }
+        }
+    };
+}
+for_each_op!(impl_field_for_instruction);
@@ -85,16 +85,6 @@ macro_rules! for_each_op_grouped {
                 ///
                 /// # Note
                 ///
-                /// Returns a single 32-bit encoded `f64` constant value.
-                #[snake_name(return_f64imm32)]
-                ReturnF64Imm32 {
-                    /// The returned constant value.
-                    value: Const32<f64>,
-                },
-                /// A Wasm `return` instruction.
-                ///
-                /// # Note
-                ///
                 /// Returns values as stored in the bounded [`RegSpan`].
                 #[snake_name(return_span)]
                 ReturnSpan {
@@ -189,19 +179,6 @@ macro_rules! for_each_op_grouped {
                 ///
                 /// # Note
                 ///
-                /// Variant of [`Instruction::ReturnNez`] returning a single
-                /// 32-bit encoded [`f64`] value if the `condition` evaluates to `true`.
-                #[snake_name(return_nez_f64imm32)]
-                ReturnNezF64Imm32 {
-                    /// The register holding the condition to evaluate against zero.
-                    condition: Reg,
-                    /// The returned value.
-                    value: Const32<f64>,
-                },
-                /// A conditional `return` instruction.
-                ///
-                /// # Note
-                ///
                 /// Variant of [`Instruction::ReturnNez`] returning two or more values.
                 #[snake_name(return_nez_span)]
                 ReturnNezSpan {
@@ -715,90 +692,6 @@ macro_rules! for_each_op_grouped {
                     offset: BranchOffset16,
                 },
 
-                /// A fused `f32.eq` and branch instruction.
-                #[snake_name(branch_f32_eq)]
-                BranchF32Eq {
-                    /// The left-hand side operand to the branch conditional.
-                    lhs: Reg,
-                    /// The right-hand side operand to the branch conditional.
-                    rhs: Reg,
-                    /// The 16-bit encoded branch offset.
-                    offset: BranchOffset16,
-                },
-                /// A fused `f32.ne` and branch instruction.
-                #[snake_name(branch_f32_ne)]
-                BranchF32Ne {
-                    /// The left-hand side operand to the branch conditional.
-                    lhs: Reg,
-                    /// The right-hand side operand to the branch conditional.
-                    rhs: Reg,
-                    /// The 16-bit encoded branch offset.
-                    offset: BranchOffset16,
-                },
-
-                /// A fused `f32.lt` and branch instruction.
-                #[snake_name(branch_f32_lt)]
-                BranchF32Lt {
-                    /// The left-hand side operand to the branch conditional.
-                    lhs: Reg,
-                    /// The right-hand side operand to the branch conditional.
-                    rhs: Reg,
-                    /// The 16-bit encoded branch offset.
-                    offset: BranchOffset16,
-                },
-                /// A fused `f32.le` and branch instruction.
-                #[snake_name(branch_f32_le)]
-                BranchF32Le {
-                    /// The left-hand side operand to the branch conditional.
-                    lhs: Reg,
-                    /// The right-hand side operand to the branch conditional.
-                    rhs: Reg,
-                    /// The 16-bit encoded branch offset.
-                    offset: BranchOffset16,
-                },
-
-                /// A fused `f64.eq` and branch instruction.
-                #[snake_name(branch_f64_eq)]
-                BranchF64Eq {
-                    /// The left-hand side operand to the branch conditional.
-                    lhs: Reg,
-                    /// The right-hand side operand to the branch conditional.
-                    rhs: Reg,
-                    /// The 16-bit encoded branch offset.
-                    offset: BranchOffset16,
-                },
-                /// A fused `f64.ne` and branch instruction.
-                #[snake_name(branch_f64_ne)]
-                BranchF64Ne {
-                    /// The left-hand side operand to the branch conditional.
-                    lhs: Reg,
-                    /// The right-hand side operand to the branch conditional.
-                    rhs: Reg,
-                    /// The 16-bit encoded branch offset.
-                    offset: BranchOffset16,
-                },
-
-                /// A fused `f64.lt` and branch instruction.
-                #[snake_name(branch_f64_lt)]
-                BranchF64Lt {
-                    /// The left-hand side operand to the branch conditional.
-                    lhs: Reg,
-                    /// The right-hand side operand to the branch conditional.
-                    rhs: Reg,
-                    /// The 16-bit encoded branch offset.
-                    offset: BranchOffset16,
-                },
-                /// A fused `f64.le` and branch instruction.
-                #[snake_name(branch_f64_le)]
-                BranchF64Le {
-                    /// The left-hand side operand to the branch conditional.
-                    lhs: Reg,
-                    /// The right-hand side operand to the branch conditional.
-                    rhs: Reg,
-                    /// The 16-bit encoded branch offset.
-                    offset: BranchOffset16,
-                },
-
                 /// A Wasm `br_table` equivalent Wasmi instruction.
                 ///
                 /// # Encoding
@@ -961,19 +854,6 @@ macro_rules! for_each_op_grouped {
                     /// The 32-bit encoded `i64` immediate value to copy.
                     value: Const32<i64>,
                 },
-                /// Copies the 32-bit encoded `f64` immediate `value` to `result`.
-                ///
-                /// # Note
-                ///
-                /// - Variant of [`Instruction::Copy`] for 32-bit encodable `f64` immediate values.
-                /// - Upon execution the 32-bit encoded `f32` `value` is promoted to `f64` and copied into `result`.
-                /// - Read [`Instruction::Copy`] for more information about this instruction.
-                #[snake_name(copy_f64imm32)]
-                CopyF64Imm32 {
-                    @result: Reg,
-                    /// The 32-bit encoded `i64` immediate value to copy.
-                    value: Const32<f64>,
-                },
                 /// Copies `len` contiguous `values` [`RegSpan`] into `results` [`RegSpan`].
                 ///
                 /// Copies registers: `registers[results..results+len] <- registers[values..values+len]`
@@ -1057,6 +937,9 @@ macro_rules! for_each_op_grouped {
                 ///     - [`Instruction::Register`]
                 ///     - [`Instruction::Register2`]
                 ///     - [`Instruction::Register3`]
+                ///
+                /// Alternatively followed by a single [`Instruction::RegisterSpan`]
+                /// if all parameters are stored in contiguous registers.
                 #[snake_name(return_call_internal)]
                 ReturnCallInternal {
                     /// The called internal function.
@@ -1088,6 +971,9 @@ macro_rules! for_each_op_grouped {
                 ///     - [`Instruction::Register`]
                 ///     - [`Instruction::Register2`]
                 ///     - [`Instruction::Register3`]
+                ///
+                /// Alternatively followed by a single [`Instruction::RegisterSpan`]
+                /// if all parameters are stored in contiguous registers.
                 #[snake_name(return_call_imported)]
                 ReturnCallImported {
                     /// The called imported function.
@@ -1138,6 +1024,9 @@ macro_rules! for_each_op_grouped {
                 ///     - [`Instruction::Register`]
                 ///     - [`Instruction::Register2`]
                 ///     - [`Instruction::Register3`]
+                ///
+                /// Steps 2. and 3. are replaced by a single [`Instruction::RegisterSpan`]
+                /// if all parameters are stored in contiguous registers.
                 #[snake_name(return_call_indirect)]
                 ReturnCallIndirect {
                     /// The called internal function.
@@ -1159,6 +1048,9 @@ macro_rules! for_each_op_grouped {
                 ///     - [`Instruction::Register`]
                 ///     - [`Instruction::Register2`]
                 ///     - [`Instruction::Register3`]
+                ///
+                /// Steps 2. and 3. are replaced by a single [`Instruction::RegisterSpan`]
+                /// if all parameters are stored in contiguous registers.
                 #[snake_name(return_call_indirect_imm16)]
                 ReturnCallIndirectImm16 {
                     /// The called internal function.
@@ -1191,6 +1083,9 @@ macro_rules! for_each_op_grouped {
                 ///     - [`Instruction::Register`]
                 ///     - [`Instruction::Register2`]
                 ///     - [`Instruction::Register3`]
+                ///
+                /// Alternatively followed by a single [`Instruction::RegisterSpan`]
+                /// if all parameters are stored in contiguous registers.
                 #[snake_name(call_internal)]
                 CallInternal {
                     @results: RegSpan,
@@ -1224,6 +1119,9 @@ macro_rules! for_each_op_grouped {
                 ///     - [`Instruction::Register`]
                 ///     - [`Instruction::Register2`]
                 ///     - [`Instruction::Register3`]
+                ///
+                /// Alternatively followed by a single [`Instruction::RegisterSpan`]
+                /// if all parameters are stored in contiguous registers.
                 #[snake_name(call_imported)]
                 CallImported {
                     @results: RegSpan,
@@ -1277,6 +1175,9 @@ macro_rules! for_each_op_grouped {
                 ///     - [`Instruction::Register`]
                 ///     - [`Instruction::Register2`]
                 ///     - [`Instruction::Register3`]
+                ///
+                /// Steps 2. and 3. are replaced by a single [`Instruction::RegisterSpan`]
+                /// if all parameters are stored in contiguous registers.
                 #[snake_name(call_indirect)]
                 CallIndirect {
                     @results: RegSpan,
@@ -1299,6 +1200,9 @@ macro_rules! for_each_op_grouped {
                 ///     - [`Instruction::Register`]
                 ///     - [`Instruction::Register2`]
                 ///     - [`Instruction::Register3`]
+                ///
+                /// Steps 2. and 3. are replaced by a single [`Instruction::RegisterSpan`]
+                /// if all parameters are stored in contiguous registers.
                 #[snake_name(call_indirect_imm16)]
                 CallIndirectImm16 {
                     @results: RegSpan,
@@ -1383,39 +1287,6 @@ macro_rules! for_each_op_grouped {
                     /// The register holding the left-hand side value.
                     lhs: Const32<i64>,
                 },
-                /// A Wasm `select` equivalent Wasmi instruction with 32-bit encoded `f64` immediate `rhs` value.
-                ///
-                /// # Encoding
-                ///
-                /// Must be followed by [`Instruction::RegisterAndImm32`] to encode `condition` and `rhs`.
-                #[snake_name(select_f64imm32_rhs)]
-                SelectF64Imm32Rhs {
-                    @result: Reg,
-                    /// The register holding the left-hand side value.
-                    lhs: Reg,
-                },
-                /// A Wasm `select` equivalent Wasmi instruction with 32-bit encoded `f64` immediate `lhs` value.
-                ///
-                /// # Encoding
-                ///
-                /// Must be followed by [`Instruction::Register2`] to encode `condition` and `rhs`.
-                #[snake_name(select_f64imm32_lhs)]
-                SelectF64Imm32Lhs {
-                    @result: Reg,
-                    /// The register holding the left-hand side value.
-                    lhs: Const32<f64>,
-                },
-                /// A Wasm `select` equivalent Wasmi instruction with 32-bit encoded `f64` immediate `lhs` and `rhs` value.
-                ///
-                /// # Encoding
-                ///
-                /// Must be followed by [`Instruction::RegisterAndImm32`] to encode `condition` and `rhs`.
-                #[snake_name(select_f64imm32)]
-                SelectF64Imm32 {
-                    @result: Reg,
-                    /// The register holding the left-hand side value.
-                    lhs: Const32<f64>,
-                },
 
                 /// A Wasm `ref.func` equivalent Wasmi instruction.
                 #[snake_name(ref_func)]
@@ -3092,80 +2963,6 @@ macro_rules! for_each_op_grouped {
                     rhs: Const16<u64>,
                 },
 
-                /// Wasm `f32.eq` equivalent Wasmi instruction.
-                #[snake_name(f32_eq)]
-                F32Eq{
-                    @result: Reg,
-                    /// The register holding the left-hand side value.
-                    lhs: Reg,
-                    /// The register holding the right-hand side value.
-                    rhs: Reg,
-                },
-                /// Wasm `f32.ne` equivalent Wasmi instruction.
-                #[snake_name(f32_ne)]
-                F32Ne{
-                    @result: Reg,
-                    /// The register holding the left-hand side value.
-                    lhs: Reg,
-                    /// The register holding the right-hand side value.
-                    rhs: Reg,
-                },
-                /// Wasm `f32.lt` equivalent Wasmi instruction.
-                #[snake_name(f32_lt)]
-                F32Lt{
-                    @result: Reg,
-                    /// The register holding the left-hand side value.
-                    lhs: Reg,
-                    /// The register holding the right-hand side value.
-                    rhs: Reg,
-                },
-                /// Wasm `f32.le` equivalent Wasmi instruction.
-                #[snake_name(f32_le)]
-                F32Le{
-                    @result: Reg,
-                    /// The register holding the left-hand side value.
-                    lhs: Reg,
-                    /// The register holding the right-hand side value.
-                    rhs: Reg,
-                },
-
-                /// Wasm `f64.eq` equivalent Wasmi instruction.
-                #[snake_name(f64_eq)]
-                F64Eq{
-                    @result: Reg,
-                    /// The register holding the left-hand side value.
-                    lhs: Reg,
-                    /// The register holding the right-hand side value.
-                    rhs: Reg,
-                },
-                /// Wasm `f64.ne` equivalent Wasmi instruction.
-                #[snake_name(f64_ne)]
-                F64Ne{
-                    @result: Reg,
-                    /// The register holding the left-hand side value.
-                    lhs: Reg,
-                    /// The register holding the right-hand side value.
-                    rhs: Reg,
-                },
-                /// Wasm `f64.lt` equivalent Wasmi instruction.
-                #[snake_name(f64_lt)]
-                F64Lt{
-                    @result: Reg,
-                    /// The register holding the left-hand side value.
-                    lhs: Reg,
-                    /// The register holding the right-hand side value.
-                    rhs: Reg,
-                },
-                /// Wasm `f64.le` equivalent Wasmi instruction.
-                #[snake_name(f64_le)]
-                F64Le{
-                    @result: Reg,
-                    /// The register holding the left-hand side value.
-                    lhs: Reg,
-                    /// The register holding the right-hand side value.
-                    rhs: Reg,
-                },
-
                 /// `i32` count-leading-zeros (clz) instruction.
                 #[snake_name(i32_clz)]
                 I32Clz {
@@ -4267,19 +4064,596 @@ macro_rules! for_each_op_grouped {
                     input: Reg,
                 },
 
-                /// Wasm `f32.abs` equivalent Wasmi instruction.
-                #[snake_name(f32_abs)]
-                F32Abs {
-                    @result: Reg,
-                    /// The register holding the input of the instruction.
-                    input: Reg,
-                },
-                /// Wasm `f32.neg` equivalent Wasmi instruction.
-                #[snake_name(f32_neg)]
-                F32Neg {
+                /// A Wasm `table.get` instruction: `result = table[index]`
+                ///
+                /// # Encoding
+                ///
+                /// This [`Instruction`] must be followed by an [`Instruction::TableIndex`].
+                #[snake_name(table_get)]
+                TableGet {
                     @result: Reg,
-                    /// The register holding the input of the instruction.
-                    input: Reg,
+                    /// The register storing the index of the table element to get.
+                    index: Reg,
+                },
+                /// Variant of [`Instruction::TableGet`] with constant `index` value.
+                ///
+                /// # Encoding
+                ///
+                /// This [`Instruction`] must be followed by an [`Instruction::TableIndex`].
+                #[snake_name(table_get_imm)]
+                TableGetImm {
+                    @result: Reg,
+                    /// The constant `index` value of the table element to get.
+                    index: Const32<u64>,
+                },
+
+                /// A Wasm `table.size` instruction.
+                #[snake_name(table_size)]
+                TableSize {
+                    @result: Reg,
+                    /// The index identifying the table for the instruction.
+                    table: Table,
+                },
+
+                /// A Wasm `table.set` instruction: `table[index] = value`
+                ///
+                /// # Encoding
+                ///
+                /// This [`Instruction`] must be followed by an [`Instruction::TableIndex`].
+                #[snake_name(table_set)]
+                TableSet {
+                    /// The register holding the `index` of the instruction.
+                    index: Reg,
+                    /// The register holding the `value` of the instruction.
+                    value: Reg,
+                },
+                /// Variant of [`Instruction::TableSet`] with constant `index` value.
+                ///
+                /// # Encoding
+                ///
+                /// This [`Instruction`] must be followed by an [`Instruction::TableIndex`].
+                #[snake_name(table_set_at)]
+                TableSetAt {
+                    /// The register holding the `value` of the instruction.
+                    value: Reg,
+                    /// The constant `index` of the instruction.
+                    index: Const32<u64>,
+                },
+
+                /// Wasm `table.fill <table>` instruction: `table[dst..dst+len] = value`
+                ///
+                /// # Encoding
+                ///
+                /// Followed by [`Instruction::TableIndex`] encoding the Wasm `table` instance.
+                #[snake_name(table_fill)]
+                TableFill {
+                    /// The start index of the table to fill.
+                    dst: Reg,
+                    /// The number of elements to fill.
+                    len: Reg,
+                    /// The value of the filled elements.
+                    value: Reg,
+                },
+                /// Variant of [`Instruction::TableFill`] with 16-bit constant `dst` index.
+                ///
+                /// # Encoding
+                ///
+                /// Followed by [`Instruction::TableIndex`] encoding the Wasm `table` instance.
+                #[snake_name(table_fill_at)]
+                TableFillAt {
+                    /// The start index of the table to fill.
+                    dst: Const16<u64>,
+                    /// The number of elements to fill.
+                    len: Reg,
+                    /// The value of the filled elements.
+                    value: Reg,
+                },
+                /// Variant of [`Instruction::TableFill`] with 16-bit constant `len` index.
+                ///
+                /// # Encoding
+                ///
+                /// Followed by [`Instruction::TableIndex`] encoding the Wasm `table` instance.
+                #[snake_name(table_fill_exact)]
+                TableFillExact {
+                    /// The start index of the table to fill.
+                    dst: Reg,
+                    /// The number of elements to fill.
+                    len: Const16<u64>,
+                    /// The value of the filled elements.
+                    value: Reg,
+                },
+                /// Variant of [`Instruction::TableFill`] with 16-bit constant `dst` and `len` fields.
+                ///
+                /// # Encoding
+                ///
+                /// Followed by [`Instruction::TableIndex`] encoding the Wasm `table` instance.
+                #[snake_name(table_fill_at_exact)]
+                TableFillAtExact {
+                    /// The start index of the table to fill.
+                    dst: Const16<u64>,
+                    /// The number of elements to fill.
+                    len: Const16<u64>,
+                    /// The value of the filled elements.
+                    value: Reg,
+                },
+
+                /// Wasm `table.grow <table>` instruction.
+                ///
+                /// # Encoding
+                ///
+                /// Followed by [`Instruction::TableIndex`] encoding the Wasm `table` instance.
+                #[snake_name(table_grow)]
+                TableGrow {
+                    @result: Reg,
+                    /// The number of elements to add to the table.
+                    delta: Reg,
+                    /// The value that is used to fill up the new cells.
+                    value: Reg,
+                },
+                /// Variant of [`Instruction::TableGrow`] with 16-bit constant `delta`.
+                ///
+                /// # Encoding
+                ///
+                /// Followed by [`Instruction::TableIndex`] encoding the Wasm `table` instance.
+                #[snake_name(table_grow_imm)]
+                TableGrowImm {
+                    @result: Reg,
+                    /// The number of elements to add to the table.
+                    delta: Const16<u64>,
+                    /// The value that is used to fill up the new cells.
+                    value: Reg,
+                },
+
+                /// Wasm `memory.size` instruction.
+                #[snake_name(memory_size)]
+                MemorySize {
+                    @result: Reg,
+                    /// The index identifying the Wasm linear memory for the instruction.
+                    memory: Memory,
+                },
+
+                /// Wasm `memory.grow` instruction.
+                ///
+                /// # Encoding
+                ///
+                /// Followed by [`Instruction::MemoryIndex`] encoding the Wasm `memory` instance.
+                #[snake_name(memory_grow)]
+                MemoryGrow {
+                    @result: Reg,
+                    /// The number of pages to add to the memory.
+                    delta: Reg,
+                },
+                /// Variant of [`Instruction::MemoryGrow`] with 16-bit constant `delta`.
+                ///
+                /// # Encoding
+                ///
+                /// Followed by [`Instruction::MemoryIndex`] encoding the Wasm `memory` instance.
+                #[snake_name(memory_grow_by)]
+                MemoryGrowBy {
+                    @result: Reg,
+                    /// The number of pages to add to the memory.
+                    delta: Const32<u64>,
+                },
+
+                /// A [`Table`] instruction parameter.
+                ///
+                /// # Note
+                ///
+                /// This [`Instruction`] only acts as a parameter to another
+                /// one and will never be executed itself directly.
+                #[snake_name(table_index)]
+                TableIndex {
+                    index: Table,
+                },
+                /// A [`Memory`] instruction parameter.
+                ///
+                /// # Note
+                ///
+                /// This [`Instruction`] only acts as a parameter to another
+                /// one and will never be executed itself directly.
+                #[snake_name(memory_index)]
+                MemoryIndex {
+                    index: Memory,
+                },
+                /// A [`Data`] instruction parameter.
+                ///
+                /// # Note
+                ///
+                /// This [`Instruction`] only acts as a parameter to another
+                /// one and will never be executed itself directly.
+                #[snake_name(data_index)]
+                DataIndex {
+                    index: Data,
+                },
+                /// An [`Elem`] instruction parameter.
+                ///
+                /// # Note
+                ///
+                /// This [`Instruction`] only acts as a parameter to another
+                /// one and will never be executed itself directly.
+                #[snake_name(elem_index)]
+                ElemIndex {
+                    index: Elem,
+                },
+                /// A [`AnyConst32`] instruction parameter.
+                ///
+                /// # Note
+                ///
+                /// This [`Instruction`] only acts as a parameter to another
+                /// one and will never be executed itself directly.
+                #[snake_name(const32)]
+                Const32 {
+                    value: AnyConst32
+                },
+                /// A [`Const32<i64>`] instruction parameter.
+                ///
+                /// # Note
+                ///
+                /// This [`Instruction`] only acts as a parameter to another
+                /// one and will never be executed itself directly.
+                #[snake_name(i64const32)]
+                I64Const32 {
+                    value: Const32<i64>
+                },
+                /// A Wasm `br_table` branching target which copies values before branching.
+                ///
+                /// # Encoding
+                ///
+                /// This always follows
+                ///
+                /// - [`Instruction::BranchTable1`]
+                /// - [`Instruction::BranchTable2`]
+                /// - [`Instruction::BranchTableSpan`]
+                /// - [`Instruction::BranchTableMany`]
+                #[snake_name(branch_table_target)]
+                BranchTableTarget {
+                    /// The registers where the values are going to be copied.
+                    results: RegSpan,
+                    /// The branching offset of the branch table target.
+                    offset: BranchOffset,
+                },
+                /// A Wasm `br_table` branching target which copies overlapping values before branching.
+                ///
+                /// # Encoding
+                ///
+                /// This always follows
+                ///
+                /// - [`Instruction::BranchTableSpan`]
+                /// - [`Instruction::BranchTableMany`]
+                #[snake_name(branch_table_target_non_overlapping)]
+                BranchTableTargetNonOverlapping {
+                    /// The registers where the values are going to be copied.
+                    results: RegSpan,
+                    /// The branching offset of the branch table target.
+                    offset: BranchOffset,
+                },
+                /// An instruction parameter with 16-bit and 32-bit immediate values.
+                #[snake_name(imm16_and_imm32)]
+                Imm16AndImm32 {
+                    /// The 16-bit immediate value.
+                    imm16: AnyConst16,
+                    /// The 32-bit immediate value.
+                    imm32: AnyConst32,
+                },
+                /// An instruction parameter with a [`Reg`] and a 32-bit immediate value.
+                #[snake_name(register_and_imm32)]
+                RegisterAndImm32 {
+                    /// The [`Reg`] parameter value.
+                    reg: Reg,
+                    /// The 32-bit immediate value.
+                    imm: AnyConst32,
+                },
+                /// A bounded [`RegSpan`] instruction parameter.
+                #[snake_name(register_span)]
+                RegisterSpan { span: BoundedRegSpan },
+                /// A [`Reg`] instruction parameter.
+                ///
+                /// # Note
+                ///
+                /// This [`Instruction`] only acts as a parameter to another
+                /// one and will never be executed itself directly.
+                #[snake_name(register)]
+                Register {
+                    reg: Reg
+                },
+                /// Two [`Reg`] instruction parameters.
+                ///
+                /// # Note
+                ///
+                /// This [`Instruction`] only acts as a parameter to another
+                /// one and will never be executed itself directly.
+                #[snake_name(register2)]
+                Register2 {
+                    regs: [Reg; 2]
+                },
+                /// Three [`Reg`] instruction parameters.
+                ///
+                /// # Note
+                ///
+                /// This [`Instruction`] only acts as a parameter to another
+                /// one and will never be executed itself directly.
+                #[snake_name(register3)]
+                Register3 {
+                    regs: [Reg; 3]
+                },
+                /// [`Reg`] slice parameters.
+                ///
+                /// # Note
+                ///
+                /// This [`Instruction`] only acts as a parameter to another
+                /// one and will never be executed itself directly.
+                ///
+                /// # Encoding
+                ///
+                /// This must always be followed by one of
+                ///
+                /// - [`Instruction::Register`]
+                /// - [`Instruction::Register2`]
+                /// - [`Instruction::Register3`]
+                #[snake_name(register_list)]
+                RegisterList {
+                    regs: [Reg; 3]
+                },
+                /// Auxiliary [`Instruction`] to encode table access information for indirect call instructions.
+                #[snake_name(call_indirect_params)]
+                CallIndirectParams {
+                    /// The index of the called function in the table.
+                    index: Reg,
+                    /// The table which holds the called function at the index.
+                    table: Table,
+                },
+                /// Variant of [`Instruction::CallIndirectParams`] for 16-bit constant `index` parameter.
+                #[snake_name(call_indirect_params_imm16)]
+                CallIndirectParamsImm16 {
+                    /// The index of the called function in the table.
+                    index: Const16<u64>,
+                    /// The table which holds the called function at the index.
+                    table: Table,
+                },
+            },
+
+            @float {
+                /// A Wasm `return` instruction.
+                ///
+                /// # Note
+                ///
+                /// Returns a single 32-bit encoded `f64` constant value.
+                #[snake_name(return_f64imm32)]
+                ReturnF64Imm32 {
+                    /// The returned constant value.
+                    value: Const32<f64>,
+                },
+                /// A conditional `return` instruction.
+                ///
+                /// # Note
+                ///
+                /// Variant of [`Instruction::ReturnNez`] returning a single
+                /// 32-bit encoded [`f64`] value if the `condition` evaluates to `true`.
+                #[snake_name(return_nez_f64imm32)]
+                ReturnNezF64Imm32 {
+                    /// The register holding the condition to evaluate against zero.
+                    condition: Reg,
+                    /// The returned value.
+                    value: Const32<f64>,
+                },
+
+                /// A fused `f32.eq` and branch instruction.
+                #[snake_name(branch_f32_eq)]
+                BranchF32Eq {
+                    /// The left-hand side operand to the branch conditional.
+                    lhs: Reg,
+                    /// The right-hand side operand to the branch conditional.
+                    rhs: Reg,
+                    /// The 16-bit encoded branch offset.
+                    offset: BranchOffset16,
+                },
+                /// A fused `f32.ne` and branch instruction.
+                #[snake_name(branch_f32_ne)]
+                BranchF32Ne {
+                    /// The left-hand side operand to the branch conditional.
+                    lhs: Reg,
+                    /// The right-hand side operand to the branch conditional.
+                    rhs: Reg,
+                    /// The 16-bit encoded branch offset.
+                    offset: BranchOffset16,
+                },
+
+                /// A fused `f32.lt` and branch instruction.
+                #[snake_name(branch_f32_lt)]
+                BranchF32Lt {
+                    /// The left-hand side operand to the branch conditional.
+                    lhs: Reg,
+                    /// The right-hand side operand to the branch conditional.
+                    rhs: Reg,
+                    /// The 16-bit encoded branch offset.
+                    offset: BranchOffset16,
+                },
+                /// A fused `f32.le` and branch instruction.
+                #[snake_name(branch_f32_le)]
+                BranchF32Le {
+                    /// The left-hand side operand to the branch conditional.
+                    lhs: Reg,
+                    /// The right-hand side operand to the branch conditional.
+                    rhs: Reg,
+                    /// The 16-bit encoded branch offset.
+                    offset: BranchOffset16,
+                },
+
+                /// A fused `f64.eq` and branch instruction.
+                #[snake_name(branch_f64_eq)]
+                BranchF64Eq {
+                    /// The left-hand side operand to the branch conditional.
+                    lhs: Reg,
+                    /// The right-hand side operand to the branch conditional.
+                    rhs: Reg,
+                    /// The 16-bit encoded branch offset.
+                    offset: BranchOffset16,
+                },
+                /// A fused `f64.ne` and branch instruction.
+                #[snake_name(branch_f64_ne)]
+                BranchF64Ne {
+                    /// The left-hand side operand to the branch conditional.
+                    lhs: Reg,
+                    /// The right-hand side operand to the branch conditional.
+                    rhs: Reg,
+                    /// The 16-bit encoded branch offset.
+                    offset: BranchOffset16,
+                },
+
+                /// A fused `f64.lt` and branch instruction.
+                #[snake_name(branch_f64_lt)]
+                BranchF64Lt {
+                    /// The left-hand side operand to the branch conditional.
+                    lhs: Reg,
+                    /// The right-hand side operand to the branch conditional.
+                    rhs: Reg,
+                    /// The 16-bit encoded branch offset.
+                    offset: BranchOffset16,
+                },
+                /// A fused `f64.le` and branch instruction.
+                #[snake_name(branch_f64_le)]
+                BranchF64Le {
+                    /// The left-hand side operand to the branch conditional.
+                    lhs: Reg,
+                    /// The right-hand side operand to the branch conditional.
+                    rhs: Reg,
+                    /// The 16-bit encoded branch offset.
+                    offset: BranchOffset16,
+                },
+                /// Copies the 32-bit encoded `f64` immediate `value` to `result`.
+                ///
+                /// # Note
+                ///
+                /// - Variant of [`Instruction::Copy`] for 32-bit encodable `f64` immediate values.
+                /// - Upon execution the 32-bit encoded `f32` `value` is promoted to `f64` and copied into `result`.
+                /// - Read [`Instruction::Copy`] for more information about this instruction.
+                #[snake_name(copy_f64imm32)]
+                CopyF64Imm32 {
+                    @result: Reg,
+                    /// The 32-bit encoded `i64` immediate value to copy.
+                    value: Const32<f64>,
+                },
+                /// A Wasm `select` equivalent Wasmi instruction with 32-bit encoded `f64` immediate `rhs` value.
+                ///
+                /// # Encoding
+                ///
+                /// Must be followed by [`Instruction::RegisterAndImm32`] to encode `condition` and `rhs`.
+                #[snake_name(select_f64imm32_rhs)]
+                SelectF64Imm32Rhs {
+                    @result: Reg,
+                    /// The register holding the left-hand side value.
+                    lhs: Reg,
+                },
+                /// A Wasm `select` equivalent Wasmi instruction with 32-bit encoded `f64` immediate `lhs` value.
+                ///
+                /// # Encoding
+                ///
+                /// Must be followed by [`Instruction::Register2`] to encode `condition` and `rhs`.
+                #[snake_name(select_f64imm32_lhs)]
+                SelectF64Imm32Lhs {
+                    @result: Reg,
+                    /// The register holding the left-hand side value.
+                    lhs: Const32<f64>,
+                },
+                /// A Wasm `select` equivalent Wasmi instruction with 32-bit encoded `f64` immediate `lhs` and `rhs` value.
+                ///
+                /// # Encoding
+                ///
+                /// Must be followed by [`Instruction::RegisterAndImm32`] to encode `condition` and `rhs`.
+                #[snake_name(select_f64imm32)]
+                SelectF64Imm32 {
+                    @result: Reg,
+                    /// The register holding the left-hand side value.
+                    lhs: Const32<f64>,
+                },
+
+                /// Wasm `f32.eq` equivalent Wasmi instruction.
+                #[snake_name(f32_eq)]
+                F32Eq{
+                    @result: Reg,
+                    /// The register holding the left-hand side value.
+                    lhs: Reg,
+                    /// The register holding the right-hand side value.
+                    rhs: Reg,
+                },
+                /// Wasm `f32.ne` equivalent Wasmi instruction.
+                #[snake_name(f32_ne)]
+                F32Ne{
+                    @result: Reg,
+                    /// The register holding the left-hand side value.
+                    lhs: Reg,
+                    /// The register holding the right-hand side value.
+                    rhs: Reg,
+                },
+                /// Wasm `f32.lt` equivalent Wasmi instruction.
+                #[snake_name(f32_lt)]
+                F32Lt{
+                    @result: Reg,
+                    /// The register holding the left-hand side value.
+                    lhs: Reg,
+                    /// The register holding the right-hand side value.
+                    rhs: Reg,
+                },
+                /// Wasm `f32.le` equivalent Wasmi instruction.
+                #[snake_name(f32_le)]
+                F32Le{
+                    @result: Reg,
+                    /// The register holding the left-hand side value.
+                    lhs: Reg,
+                    /// The register holding the right-hand side value.
+                    rhs: Reg,
+                },
+
+                /// Wasm `f64.eq` equivalent Wasmi instruction.
+                #[snake_name(f64_eq)]
+                F64Eq{
+                    @result: Reg,
+                    /// The register holding the left-hand side value.
+                    lhs: Reg,
+                    /// The register holding the right-hand side value.
+                    rhs: Reg,
+                },
+                /// Wasm `f64.ne` equivalent Wasmi instruction.
+                #[snake_name(f64_ne)]
+                F64Ne{
+                    @result: Reg,
+                    /// The register holding the left-hand side value.
+                    lhs: Reg,
+                    /// The register holding the right-hand side value.
+                    rhs: Reg,
+                },
+                /// Wasm `f64.lt` equivalent Wasmi instruction.
+                #[snake_name(f64_lt)]
+                F64Lt{
+                    @result: Reg,
+                    /// The register holding the left-hand side value.
+                    lhs: Reg,
+                    /// The register holding the right-hand side value.
+                    rhs: Reg,
+                },
+                /// Wasm `f64.le` equivalent Wasmi instruction.
+                #[snake_name(f64_le)]
+                F64Le{
+                    @result: Reg,
+                    /// The register holding the left-hand side value.
+                    lhs: Reg,
+                    /// The register holding the right-hand side value.
+                    rhs: Reg,
+                },
+
+                /// Wasm `f32.abs` equivalent Wasmi instruction.
+                #[snake_name(f32_abs)]
+                F32Abs {
+                    @result: Reg,
+                    /// The register holding the input of the instruction.
+                    input: Reg,
+                },
+                /// Wasm `f32.neg` equivalent Wasmi instruction.
+                #[snake_name(f32_neg)]
+                F32Neg {
+                    @result: Reg,
+                    /// The register holding the input of the instruction.
+                    input: Reg,
                 },
                 /// Wasm `f32.ceil` equivalent Wasmi instruction.
                 #[snake_name(f32_ceil)]
@@ -4728,63 +5102,19 @@ macro_rules! for_each_op_grouped {
                     /// The register holding the input of the instruction.
                     input: Reg,
                 },
-
-                /// A Wasm `table.get` instruction: `result = table[index]`
-                ///
-                /// # Encoding
-                ///
-                /// This [`Instruction`] must be followed by an [`Instruction::TableIndex`].
-                #[snake_name(table_get)]
-                TableGet {
-                    @result: Reg,
-                    /// The register storing the index of the table element to get.
-                    index: Reg,
-                },
-                /// Variant of [`Instruction::TableGet`] with constant `index` value.
-                ///
-                /// # Encoding
-                ///
-                /// This [`Instruction`] must be followed by an [`Instruction::TableIndex`].
-                #[snake_name(table_get_imm)]
-                TableGetImm {
-                    @result: Reg,
-                    /// The constant `index` value of the table element to get.
-                    index: Const32<u64>,
-                },
-
-                /// A Wasm `table.size` instruction.
-                #[snake_name(table_size)]
-                TableSize {
-                    @result: Reg,
-                    /// The index identifying the table for the instruction.
-                    table: Table,
-                },
-
-                /// A Wasm `table.set` instruction: `table[index] = value`
-                ///
-                /// # Encoding
-                ///
-                /// This [`Instruction`] must be followed by an [`Instruction::TableIndex`].
-                #[snake_name(table_set)]
-                TableSet {
-                    /// The register holding the `index` of the instruction.
-                    index: Reg,
-                    /// The register holding the `value` of the instruction.
-                    value: Reg,
-                },
-                /// Variant of [`Instruction::TableSet`] with constant `index` value.
+                /// A [`Const32<f64>`] instruction parameter.
                 ///
-                /// # Encoding
+                /// # Note
                 ///
-                /// This [`Instruction`] must be followed by an [`Instruction::TableIndex`].
-                #[snake_name(table_set_at)]
-                TableSetAt {
-                    /// The register holding the `value` of the instruction.
-                    value: Reg,
-                    /// The constant `index` of the instruction.
-                    index: Const32<u64>,
+                /// This [`Instruction`] only acts as a parameter to another
+                /// one and will never be executed itself directly.
+                #[snake_name(f64const32)]
+                F64Const32 {
+                    value: Const32<f64>
                 },
+            },
 
+            @bulk_memory {
                 /// Wasm `table.copy <dst> <src>` instruction.
                 ///
                 /// Copies elements from `table<src>[src..src+len]` to `table<dst>[dst..dst+len]`.
@@ -5094,131 +5424,16 @@ macro_rules! for_each_op_grouped {
                     /// The number of copied elements.
                     len: Const16<u32>,
                 },
-
-                /// Wasm `table.fill <table>` instruction: `table[dst..dst+len] = value`
-                ///
-                /// # Encoding
-                ///
-                /// Followed by [`Instruction::TableIndex`] encoding the Wasm `table` instance.
-                #[snake_name(table_fill)]
-                TableFill {
-                    /// The start index of the table to fill.
-                    dst: Reg,
-                    /// The number of elements to fill.
-                    len: Reg,
-                    /// The value of the filled elements.
-                    value: Reg,
-                },
-                /// Variant of [`Instruction::TableFill`] with 16-bit constant `dst` index.
-                ///
-                /// # Encoding
-                ///
-                /// Followed by [`Instruction::TableIndex`] encoding the Wasm `table` instance.
-                #[snake_name(table_fill_at)]
-                TableFillAt {
-                    /// The start index of the table to fill.
-                    dst: Const16<u64>,
-                    /// The number of elements to fill.
-                    len: Reg,
-                    /// The value of the filled elements.
-                    value: Reg,
-                },
-                /// Variant of [`Instruction::TableFill`] with 16-bit constant `len` index.
-                ///
-                /// # Encoding
-                ///
-                /// Followed by [`Instruction::TableIndex`] encoding the Wasm `table` instance.
-                #[snake_name(table_fill_exact)]
-                TableFillExact {
-                    /// The start index of the table to fill.
-                    dst: Reg,
-                    /// The number of elements to fill.
-                    len: Const16<u64>,
-                    /// The value of the filled elements.
-                    value: Reg,
-                },
-                /// Variant of [`Instruction::TableFill`] with 16-bit constant `dst` and `len` fields.
-                ///
-                /// # Encoding
-                ///
-                /// Followed by [`Instruction::TableIndex`] encoding the Wasm `table` instance.
-                #[snake_name(table_fill_at_exact)]
-                TableFillAtExact {
-                    /// The start index of the table to fill.
-                    dst: Const16<u64>,
-                    /// The number of elements to fill.
-                    len: Const16<u64>,
-                    /// The value of the filled elements.
-                    value: Reg,
-                },
-
-                /// Wasm `table.grow <table>` instruction.
-                ///
-                /// # Encoding
-                ///
-                /// Followed by [`Instruction::TableIndex`] encoding the Wasm `table` instance.
-                #[snake_name(table_grow)]
-                TableGrow {
-                    @result: Reg,
-                    /// The number of elements to add to the table.
-                    delta: Reg,
-                    /// The value that is used to fill up the new cells.
-                    value: Reg,
-                },
-                /// Variant of [`Instruction::TableGrow`] with 16-bit constant `delta`.
-                ///
-                /// # Encoding
-                ///
-                /// Followed by [`Instruction::TableIndex`] encoding the Wasm `table` instance.
-                #[snake_name(table_grow_imm)]
-                TableGrowImm {
-                    @result: Reg,
-                    /// The number of elements to add to the table.
-                    delta: Const16<u64>,
-                    /// The value that is used to fill up the new cells.
-                    value: Reg,
-                },
-
-                /// A Wasm `elem.drop` equalivalent Wasmi instruction.
-                #[snake_name(elem_drop)]
-                ElemDrop {
-                    index: Elem,
-                },
-                /// A Wasm `data.drop` equalivalent Wasmi instruction.
-                #[snake_name(data_drop)]
-                DataDrop {
-                    index: Data,
-                },
-
-                /// Wasm `memory.size` instruction.
-                #[snake_name(memory_size)]
-                MemorySize {
-                    @result: Reg,
-                    /// The index identifying the Wasm linear memory for the instruction.
-                    memory: Memory,
-                },
-
-                /// Wasm `memory.grow` instruction.
-                ///
-                /// # Encoding
-                ///
-                /// Followed by [`Instruction::MemoryIndex`] encoding the Wasm `memory` instance.
-                #[snake_name(memory_grow)]
-                MemoryGrow {
-                    @result: Reg,
-                    /// The number of pages to add to the memory.
-                    delta: Reg,
-                },
-                /// Variant of [`Instruction::MemoryGrow`] with 16-bit constant `delta`.
-                ///
-                /// # Encoding
-                ///
-                /// Followed by [`Instruction::MemoryIndex`] encoding the Wasm `memory` instance.
-                #[snake_name(memory_grow_by)]
-                MemoryGrowBy {
-                    @result: Reg,
-                    /// The number of pages to add to the memory.
-                    delta: Const32<u64>,
+
+                /// A Wasm `elem.drop` equalivalent Wasmi instruction.
+                #[snake_name(elem_drop)]
+                ElemDrop {
+                    index: Elem,
+                },
+                /// A Wasm `data.drop` equalivalent Wasmi instruction.
+                #[snake_name(data_drop)]
+                DataDrop {
+                    index: Data,
                 },
 
                 /// Wasm `memory.copy` instruction.
@@ -5629,192 +5844,6 @@ macro_rules! for_each_op_grouped {
                     /// The number of initialized bytes.
                     len: Const16<u32>,
                 },
-
-                /// A [`Table`] instruction parameter.
-                ///
-                /// # Note
-                ///
-                /// This [`Instruction`] only acts as a parameter to another
-                /// one and will never be executed itself directly.
-                #[snake_name(table_index)]
-                TableIndex {
-                    index: Table,
-                },
-                /// A [`Memory`] instruction parameter.
-                ///
-                /// # Note
-                ///
-                /// This [`Instruction`] only acts as a parameter to another
-                /// one and will never be executed itself directly.
-                #[snake_name(memory_index)]
-                MemoryIndex {
-                    index: Memory,
-                },
-                /// A [`Data`] instruction parameter.
-                ///
-                /// # Note
-                ///
-                /// This [`Instruction`] only acts as a parameter to another
-                /// one and will never be executed itself directly.
-                #[snake_name(data_index)]
-                DataIndex {
-                    index: Data,
-                },
-                /// An [`Elem`] instruction parameter.
-                ///
-                /// # Note
-                ///
-                /// This [`Instruction`] only acts as a parameter to another
-                /// one and will never be executed itself directly.
-                #[snake_name(elem_index)]
-                ElemIndex {
-                    index: Elem,
-                },
-                /// A [`AnyConst32`] instruction parameter.
-                ///
-                /// # Note
-                ///
-                /// This [`Instruction`] only acts as a parameter to another
-                /// one and will never be executed itself directly.
-                #[snake_name(const32)]
-                Const32 {
-                    value: AnyConst32
-                },
-                /// A [`Const32<i64>`] instruction parameter.
-                ///
-                /// # Note
-                ///
-                /// This [`Instruction`] only acts as a parameter to another
-                /// one and will never be executed itself directly.
-                #[snake_name(i64const32)]
-                I64Const32 {
-                    value: Const32<i64>
-                },
-                /// A [`Const32<f64>`] instruction parameter.
-                ///
-                /// # Note
-                ///
-                /// This [`Instruction`] only acts as a parameter to another
-                /// one and will never be executed itself directly.
-                #[snake_name(f64const32)]
-                F64Const32 {
-                    value: Const32<f64>
-                },
-                /// A Wasm `br_table` branching target which copies values before branching.
-                ///
-                /// # Encoding
-                ///
-                /// This always follows
-                ///
-                /// - [`Instruction::BranchTable1`]
-                /// - [`Instruction::BranchTable2`]
-                /// - [`Instruction::BranchTableSpan`]
-                /// - [`Instruction::BranchTableMany`]
-                #[snake_name(branch_table_target)]
-                BranchTableTarget {
-                    /// The registers where the values are going to be copied.
-                    results: RegSpan,
-                    /// The branching offset of the branch table target.
-                    offset: BranchOffset,
-                },
-                /// A Wasm `br_table` branching target which copies overlapping values before branching.
-                ///
-                /// # Encoding
-                ///
-                /// This always follows
-                ///
-                /// - [`Instruction::BranchTableSpan`]
-                /// - [`Instruction::BranchTableMany`]
-                #[snake_name(branch_table_target_non_overlapping)]
-                BranchTableTargetNonOverlapping {
-                    /// The registers where the values are going to be copied.
-                    results: RegSpan,
-                    /// The branching offset of the branch table target.
-                    offset: BranchOffset,
-                },
-                /// An instruction parameter with 16-bit and 32-bit immediate values.
-                #[snake_name(imm16_and_imm32)]
-                Imm16AndImm32 {
-                    /// The 16-bit immediate value.
-                    imm16: AnyConst16,
-                    /// The 32-bit immediate value.
-                    imm32: AnyConst32,
-                },
-                /// An instruction parameter with a [`Reg`] and a 32-bit immediate value.
-                #[snake_name(register_and_imm32)]
-                RegisterAndImm32 {
-                    /// The [`Reg`] parameter value.
-                    reg: Reg,
-                    /// The 32-bit immediate value.
-                    imm: AnyConst32,
-                },
-                /// A bounded [`RegSpan`] instruction parameter.
-                #[snake_name(register_span)]
-                RegisterSpan { span: BoundedRegSpan },
-                /// A [`Reg`] instruction parameter.
-                ///
-                /// # Note
-                ///
-                /// This [`Instruction`] only acts as a parameter to another
-                /// one and will never be executed itself directly.
-                #[snake_name(register)]
-                Register {
-                    reg: Reg
-                },
-                /// Two [`Reg`] instruction parameters.
-                ///
-                /// # Note
-                ///
-                /// This [`Instruction`] only acts as a parameter to another
-                /// one and will never be executed itself directly.
-                #[snake_name(register2)]
-                Register2 {
-                    regs: [Reg; 2]
-                },
-                /// Three [`Reg`] instruction parameters.
-                ///
-                /// # Note
-                ///
-                /// This [`Instruction`] only acts as a parameter to another
-                /// one and will never be executed itself directly.
-                #[snake_name(register3)]
-                Register3 {
-                    regs: [Reg; 3]
-                },
-                /// [`Reg`] slice parameters.
-                ///
-                /// # Note
-                ///
-                /// This [`Instruction`] only acts as a parameter to another
-                /// one and will never be executed itself directly.
-                ///
-                /// # Encoding
-                ///
-                /// This must always be followed by one of
-                ///
-                /// - [`Instruction::Register`]
-                /// - [`Instruction::Register2`]
-                /// - [`Instruction::Register3`]
-                #[snake_name(register_list)]
-                RegisterList {
-                    regs: [Reg; 3]
-                },
-                /// Auxiliary [`Instruction`] to encode table access information for indirect call instructions.
-                #[snake_name(call_indirect_params)]
-                CallIndirectParams {
-                    /// The index of the called function in the table.
-                    index: Reg,
-                    /// The table which holds the called function at the index.
-                    table: Table,
-                },
-                /// Variant of [`Instruction::CallIndirectParams`] for 16-bit constant `index` parameter.
-                #[snake_name(call_indirect_params_imm16)]
-                CallIndirectParamsImm16 {
-                    /// The index of the called function in the table.
-                    index: Const16<u64>,
-                    /// The table which holds the called function at the index.
-                    table: Table,
-                },
             },
 
             @simd {
@@ -8704,29 +8733,47 @@ macro_rules! for_each_op_grouped {
     };
 }
 
-#[cfg(not(feature = "simd"))]
+/// Generates the `for_each_op` macro from the instruction groups of `for_each_op_grouped`.
+///
+/// Instruction groups of disabled crate features are filtered out.
 macro_rules! generate_for_each_op {
+    ( $( @$group:ident { $($instrs:tt)* } ),* $(,)? ) => {
+        generate_for_each_op! { @filter [] $( @$group { $($instrs)* } )* }
+    };
+    ( @filter [$($acc:tt)*] @default { $($instrs:tt)* } $($rest:tt)* ) => {
+        generate_for_each_op! { @filter [$($acc)* $($instrs)*] $($rest)* }
+    };
+    ( @filter [$($acc:tt)*] @float { $($instrs:tt)* } $($rest:tt)* ) => {
+        filter_float_group! { [$($acc)*] [$($instrs)*] $($rest)* }
+    };
+    ( @filter [$($acc:tt)*] @bulk_memory { $($instrs:tt)* } $($rest:tt)* ) => {
+        filter_bulk_memory_group! { [$($acc)*] [$($instrs)*] $($rest)* }
+    };
+    ( @filter [$($acc:tt)*] @simd { $($instrs:tt)* } $($rest:tt)* ) => {
+        filter_simd_group! { [$($acc)*] [$($instrs)*] $($rest)* }
+    };
+    ( @filter [$($acc:tt)*] ) => {
+        generate_for_each_op! { @define $($acc)* }
+    };
     (
-        @default {
+        @define
+        $(
+            $( #[doc = $doc:literal] )*
+            #[snake_name($snake_name:ident)]
+            $name:ident
             $(
-                $( #[doc = $doc:literal] )*
-                #[snake_name($snake_name:ident)]
-                $name:ident
-                $(
-                    {
-                        $(
-                            @ $result_name:ident: $result_ty:ty,
-                        )?
-                        $(
-                            $( #[$field_docs:meta] )*
-                            $field_name:ident: $field_ty:ty
-                        ),*
-                        $(,)?
-                    }
-                )?
-            ),* $(,)?
-        },
-        @simd $rest:tt
+                {
+                    $(
+                        @ $result_name:ident: $result_ty:ty,
+                    )?
+                    $(
+                        $( #[$field_docs:meta] )*
+                        $field_name:ident: $field_ty:ty
+                    ),*
+                    $(,)?
+                }
+            )?
+        ),* $(,)?
     ) => {
         #[macro_export]
         macro_rules! for_each_op {
@@ -8754,59 +8801,46 @@ macro_rules! generate_for_each_op {
     };
 }
 
+#[cfg(not(feature = "no-float"))]
+macro_rules! filter_float_group {
+    ( [$($acc:tt)*] [$($instrs:tt)*] $($rest:tt)* ) => {
+        generate_for_each_op! { @filter [$($acc)* $($instrs)*] $($rest)* }
+    };
+}
+
+#[cfg(feature = "no-float")]
+macro_rules! filter_float_group {
+    ( [$($acc:tt)*] [$($instrs:tt)*] $($rest:tt)* ) => {
+        generate_for_each_op! { @filter [$($acc)*] $($rest)* }
+    };
+}
+
+#[cfg(not(feature = "no-bulk-memory"))]
+macro_rules! filter_bulk_memory_group {
+    ( [$($acc:tt)*] [$($instrs:tt)*] $($rest:tt)* ) => {
+        generate_for_each_op! { @filter [$($acc)* $($instrs)*] $($rest)* }
+    };
+}
+
+#[cfg(feature = "no-bulk-memory")]
+macro_rules! filter_bulk_memory_group {
+    ( [$($acc:tt)*] [$($instrs:tt)*] $($rest:tt)* ) => {
+        generate_for_each_op! { @filter [$($acc)*] $($rest)* }
+    };
+}
+
 #[cfg(feature = "simd")]
-macro_rules! generate_for_each_op {
-    (
-        $(
-            @$group:ident {
-                $(
-                    $( #[doc = $doc:literal] )*
-                    #[snake_name($snake_name:ident)]
-                    $name:ident
-                    $(
-                        {
-                            $(
-                                @ $result_name:ident: $result_ty:ty,
-                            )?
-                            $(
-                                $( #[$field_docs:meta] )*
-                                $field_name:ident: $field_ty:ty
-                            ),*
-                            $(,)?
-                        }
-                    )?
-                ),* $(,)?
-            }
-        ),* $(,)?
-    ) => {
-        #[macro_export]
-        macro_rules! for_each_op {
-            ($mac:ident) => {
-                $mac! {
-                    $(
-                        // for each group
-                        $(
-                            // for each instruction in group
-                            $( #[doc = $doc] )*
-                            #[snake_name($snake_name)]
-                            $name
-                            $(
-                                {
-                                    $(
-                                        @ $result_name: $result_ty,
-                                    )?
-                                    $(
-                                        $( #[$field_docs] )*
-                                        $field_name: $field_ty,
-                                    )*
-                                }
-                            )?,
-                        )*
-                    )*
-                }
-            };
-        }
-    }
+macro_rules! filter_simd_group {
+    ( [$($acc:tt)*] [$($instrs:tt)*] $($rest:tt)* ) => {
+        generate_for_each_op! { @filter [$($acc)* $($instrs)*] $($rest)* }
+    };
+}
+
+#[cfg(not(feature = "simd"))]
+macro_rules! filter_simd_group {
+    ( [$($acc:tt)*] [$($instrs:tt)*] $($rest:tt)* ) => {
+        generate_for_each_op! { @filter [$($acc)*] $($rest)* }
+    };
 }
 
 for_each_op_grouped!(generate_for_each_op);
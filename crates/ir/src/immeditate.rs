@@ -10,7 +10,6 @@ use core::{
 pub struct OutOfBoundsConst;
 
 /// A typed 16-bit encoded constant value.
-#[derive(Debug)]
 pub struct Const16<T> {
     /// The underlying untyped value.
     inner: AnyConst16,
@@ -42,6 +41,17 @@ impl<T> Const16<T> {
     }
 }
 
+impl<T> Debug for Const16<T>
+where
+    Self: Into<T>,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let inner: T = (*self).into();
+        inner.fmt(f)
+    }
+}
+
 impl<T> Clone for Const16<T> {
     fn clone(&self) -> Self {
         *self
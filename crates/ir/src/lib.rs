@@ -4,6 +4,15 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(all(feature = "simd", feature = "no-float"))]
+compile_error!("the `simd` and `no-float` crate features cannot be enabled at the same time");
+
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+#[cfg(feature = "codec")]
+pub mod codec;
+#[cfg(feature = "text")]
+pub mod text;
 #[macro_use]
 mod for_each_op;
 mod r#enum;
@@ -12,6 +21,7 @@ mod immeditate;
 pub mod index;
 mod primitive;
 mod span;
+mod visit_instr;
 mod visit_regs;
 
 #[cfg(test)]
@@ -43,5 +53,6 @@ pub use self::{
     },
     r#enum::Instruction,
     span::{BoundedRegSpan, FixedRegSpan, RegSpan, RegSpanIter},
+    visit_instr::VisitInstruction,
     visit_regs::VisitRegs,
 };
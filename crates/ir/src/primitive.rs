@@ -1,8 +1,9 @@
+#[cfg(any(feature = "codec", feature = "text"))]
+use crate::core::TrapCode;
 use crate::{core::UntypedVal, immeditate::OutOfBoundsConst, Const16, Error};
-use core::marker::PhantomData;
+use core::{fmt, marker::PhantomData};
 
 /// The sign of a value.
-#[derive(Debug)]
 pub struct Sign<T> {
     /// Whether the sign value is positive.
     is_positive: bool,
@@ -10,6 +11,17 @@ pub struct Sign<T> {
     marker: PhantomData<fn() -> T>,
 }
 
+impl<T> fmt::Debug for Sign<T>
+where
+    Self: Into<T>,
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value: T = (*self).into();
+        value.fmt(f)
+    }
+}
+
 impl<T> Clone for Sign<T> {
     fn clone(&self) -> Self {
         *self
@@ -350,12 +362,21 @@ impl From<ComparatorAndOffset> for UntypedVal {
 }
 
 /// A typed shift amount for shift and rotate instructions.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub struct ShiftAmount<T> {
     /// The underlying wrapped shift amount.
     value: Const16<T>,
 }
 
+impl<T> fmt::Debug for ShiftAmount<T>
+where
+    Const16<T>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
 macro_rules! impl_from_shift_amount_for {
     ( $($ty:ty),* $(,)? ) => {
         $(
@@ -568,3 +589,22 @@ impl From<Address32> for usize {
         address.0 as usize
     }
 }
+
+/// All [`TrapCode`]s in the order of their encoding.
+#[cfg(any(feature = "codec", feature = "text"))]
+pub(crate) const TRAP_CODES: [TrapCode; 14] = [
+    TrapCode::UnreachableCodeReached,
+    TrapCode::MemoryOutOfBounds,
+    TrapCode::TableOutOfBounds,
+    TrapCode::IndirectCallToNull,
+    TrapCode::IntegerDivisionByZero,
+    TrapCode::IntegerOverflow,
+    TrapCode::BadConversionToInteger,
+    TrapCode::StackOverflow,
+    TrapCode::BadSignature,
+    TrapCode::OutOfFuel,
+    TrapCode::GrowthOperationLimited,
+    TrapCode::InstructionLimitExceeded,
+    TrapCode::MemoryReadOnly,
+    TrapCode::DeadlineExceeded,
+];
@@ -38,3 +38,361 @@ fn has_overlapping_copy_spans_works() {
     assert!(has_overlapping_copy_spans(span(4), span(1), 4));
     assert!(has_overlapping_copy_spans(span(4), span(0), 5));
 }
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_instr_sequence_works() {
+    use crate::{arbitrary::InstrSequence, Instruction, VisitRegs};
+    use ::arbitrary::{Arbitrary, Unstructured};
+    use alloc::vec::Vec;
+
+    /// Asserts that all visited [`Reg`]s are within `0..len_registers`.
+    struct AssertRegsInBounds {
+        len_registers: u16,
+    }
+
+    impl AssertRegsInBounds {
+        fn assert_reg(&self, reg: &Reg) {
+            let index = i16::from(*reg);
+            assert!(0 <= index && index < self.len_registers as i16);
+        }
+    }
+
+    impl VisitRegs for AssertRegsInBounds {
+        fn visit_result_reg(&mut self, reg: &mut Reg) {
+            self.assert_reg(reg)
+        }
+
+        fn visit_result_regs(&mut self, _regs: &mut RegSpan, _len: Option<u16>) {
+            panic!("unexpected register span")
+        }
+
+        fn visit_input_reg(&mut self, reg: &mut Reg) {
+            self.assert_reg(reg)
+        }
+
+        fn visit_input_regs(&mut self, _regs: &mut RegSpan, _len: Option<u16>) {
+            panic!("unexpected register span")
+        }
+    }
+
+    let bytes: Vec<u8> = (0..4096_u32)
+        .map(|n| (n.wrapping_mul(2654435761) >> 13) as u8)
+        .collect();
+    let mut u = Unstructured::new(&bytes);
+    for _ in 0..16 {
+        let seq = InstrSequence::arbitrary(&mut u).unwrap();
+        let len_registers = seq.len_registers();
+        let mut instrs = seq.into_instrs();
+        assert!(matches!(instrs.last(), Some(Instruction::ReturnReg { .. })));
+        let mut visitor = AssertRegsInBounds { len_registers };
+        for instr in &mut instrs {
+            instr.visit_regs(&mut visitor);
+        }
+    }
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn codec_roundtrip_works() {
+    use crate::{
+        codec::{decode, encode},
+        core::TrapCode,
+        index::{Global, InternalFunc},
+        BranchOffset,
+        Instruction,
+    };
+    use alloc::vec::Vec;
+
+    let reg = Reg::from;
+    let instrs = [
+        Instruction::i32_add(reg(0), reg(1), reg(2)),
+        Instruction::copy_many(RegSpan::new(reg(3)), [reg(4), reg(5)]),
+        Instruction::register_list([reg(6), reg(7), reg(8)]),
+        Instruction::register_list([reg(9), reg(-1), reg(-2)]),
+        Instruction::register2([reg(10), reg(11)]),
+        Instruction::call_internal(RegSpan::new(reg(12)), InternalFunc::from(42)),
+        Instruction::register3([reg(13), reg(14), reg(15)]),
+        Instruction::global_get(reg(16), Global::from(u32::MAX)),
+        Instruction::branch(BranchOffset::from(-7)),
+        Instruction::trap(TrapCode::OutOfFuel),
+        Instruction::r#return(),
+    ];
+    let mut bytes = Vec::new();
+    encode(&instrs, &mut bytes);
+    // Note: `i32.add` is encoded as its 16-bit opcode followed by its 3 registers.
+    assert_eq!(bytes[2..8], [0, 0, 1, 0, 2, 0]);
+    assert_eq!(decode(&bytes).unwrap(), instrs);
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn codec_decode_errors_work() {
+    use crate::{
+        codec::{decode, encode, DecodeError, DecodeErrorKind},
+        core::TrapCode,
+        Instruction,
+    };
+    use alloc::vec::Vec;
+
+    fn decode_err(instrs: &[Instruction], tail: &[u8]) -> (usize, DecodeErrorKind) {
+        let mut bytes = Vec::new();
+        encode(instrs, &mut bytes);
+        bytes.extend_from_slice(tail);
+        let error: DecodeError = decode(&bytes).unwrap_err();
+        (error.offset(), error.kind())
+    }
+
+    let reg = Reg::from;
+    let add = Instruction::i32_add(reg(0), reg(1), reg(2));
+    let list = Instruction::register_list([reg(0), reg(1), reg(2)]);
+    assert!(decode(&[]).unwrap().is_empty());
+    // Truncated instruction words.
+    assert_eq!(
+        decode_err(&[add], &[0]),
+        (8, DecodeErrorKind::UnexpectedEnd)
+    );
+    assert_eq!(
+        decode_err(&[add], &[0, 0]),
+        (10, DecodeErrorKind::UnexpectedEnd)
+    );
+    // Unknown opcodes and invalid fields.
+    assert_eq!(
+        decode_err(&[add], &[0xFF, 0xFF]),
+        (8, DecodeErrorKind::UnknownOpCode(u16::MAX)),
+    );
+    let mut trap = Vec::new();
+    encode(&[Instruction::trap(TrapCode::OutOfFuel)], &mut trap);
+    *trap.last_mut().unwrap() = u8::MAX;
+    assert_eq!(
+        decode_err(&[add], &trap),
+        (10, DecodeErrorKind::InvalidField),
+    );
+    // Misplaced parameter words.
+    let mut register = Vec::new();
+    encode(&[Instruction::register(reg(0))], &mut register);
+    assert_eq!(
+        decode_err(&[], &register),
+        (0, DecodeErrorKind::MisplacedParam),
+    );
+    assert_eq!(
+        decode_err(&[add, list], &[]),
+        (16, DecodeErrorKind::MisplacedParam),
+    );
+    assert_eq!(
+        decode_err(&[add, list, add], &[]),
+        (16, DecodeErrorKind::MisplacedParam),
+    );
+    let mut bytes = Vec::new();
+    encode(&[add, list, Instruction::register(reg(3))], &mut bytes);
+    assert_eq!(decode(&bytes).unwrap().len(), 3);
+}
+
+#[cfg(all(feature = "codec", feature = "arbitrary"))]
+#[test]
+fn codec_roundtrip_arbitrary_instrs_works() {
+    use crate::{
+        arbitrary::InstrSequence,
+        codec::{decode, encode},
+        Instruction,
+    };
+    use ::arbitrary::{Arbitrary, Unstructured};
+    use alloc::vec::Vec;
+
+    let bytes: Vec<u8> = (0..4096_u32)
+        .map(|n| (n.wrapping_mul(2654435761) >> 7) as u8)
+        .collect();
+    let mut u = Unstructured::new(&bytes);
+    for _ in 0..16 {
+        let instrs = InstrSequence::arbitrary(&mut u).unwrap().into_instrs();
+        let mut encoded = Vec::new();
+        encode(&instrs, &mut encoded);
+        assert_eq!(decode(&encoded).unwrap(), instrs);
+    }
+    // Note: arbitrary instruction words are enclosed to place parameter words properly.
+    let (reg0, reg1) = (Reg::from(0), Reg::from(1));
+    for _ in 0..256 {
+        let instrs = [
+            Instruction::i32_add(reg0, reg0, reg1),
+            Instruction::arbitrary(&mut u).unwrap(),
+            Instruction::register(reg1),
+        ];
+        let mut encoded = Vec::new();
+        encode(&instrs, &mut encoded);
+        assert_eq!(decode(&encoded).unwrap(), instrs);
+    }
+}
+
+#[cfg(feature = "text")]
+#[test]
+fn text_parse_instrs_works() {
+    use crate::{
+        core::TrapCode,
+        index::Global,
+        text::parse_instrs,
+        BranchOffset,
+        Const16,
+        Instruction,
+        Sign,
+    };
+    use alloc::{format, string::String};
+
+    let reg = Reg::from;
+    let instrs = [
+        Instruction::i32_add_imm16(reg(0), reg(1), <Const16<i32>>::from(-5_i16)),
+        Instruction::f32_copysign_imm(reg(0), reg(1), <Sign<f32>>::neg()),
+        Instruction::copy_many(RegSpan::new(reg(3)), [reg(4), reg(5)]),
+        Instruction::register3([reg(6), reg(7), reg(-1)]),
+        Instruction::global_get(reg(2), Global::from(7)),
+        Instruction::branch(BranchOffset::from(-4)),
+        Instruction::trap(TrapCode::OutOfFuel),
+        Instruction::r#return(),
+    ];
+    for instr in instrs {
+        assert_eq!(format!("{instr:?}").parse::<Instruction>().unwrap(), instr);
+    }
+    // Note: this is the form of the disassembler of the `wasmi` crate.
+    let mut listing = String::from("consts:\n  Reg(-1): 0x000000000000002a\ninstrs:\n");
+    for (n, instr) in instrs.iter().enumerate() {
+        listing += &format!("  {n:04}: {instr:?}\n");
+    }
+    assert_eq!(parse_instrs(&listing).unwrap(), instrs);
+    let text = "
+        ;; Hand-written instructions.
+        I32AddImm16 { result: Reg(0), lhs: Reg(1), rhs: -5 }
+        F32CopysignImm{result:Reg(0),lhs:Reg(1),rhs:-1.0,}
+
+        Return ;; trailing comment
+    ";
+    assert_eq!(
+        parse_instrs(text).unwrap(),
+        [instrs[0], instrs[1], Instruction::r#return()],
+    );
+}
+
+#[cfg(feature = "text")]
+#[test]
+fn text_parse_errors_work() {
+    use crate::{
+        text::{parse_instrs, ParseError, ParseErrorKind},
+        Instruction,
+    };
+
+    fn parse_err(text: &str) -> (usize, usize, ParseErrorKind) {
+        let error: ParseError = parse_instrs(text).unwrap_err();
+        (error.line(), error.column(), error.kind())
+    }
+
+    assert_eq!(
+        parse_err("Return\n  I32Foo { result: Reg(0) }"),
+        (2, 3, ParseErrorKind::UnknownInstruction),
+    );
+    assert_eq!(
+        parse_err("I32Add { result: Reg(0), rhs: Reg(1), lhs: Reg(2) }"),
+        (1, 26, ParseErrorKind::Expected("lhs")),
+    );
+    assert_eq!(
+        parse_err("I32Add { result: Reg(0) lhs: Reg(1), rhs: Reg(2) }"),
+        (1, 25, ParseErrorKind::Expected(",")),
+    );
+    assert_eq!(
+        parse_err("I32Add { result: Reg(40000), lhs: Reg(1), rhs: Reg(2) }"),
+        (1, 22, ParseErrorKind::InvalidValue),
+    );
+    assert_eq!(
+        parse_err("I32AddImm16 { result: Reg(0), lhs: Reg(1), rhs: 40000 }"),
+        (1, 49, ParseErrorKind::InvalidValue),
+    );
+    assert_eq!(
+        parse_err("Return Return"),
+        (1, 8, ParseErrorKind::Expected("end of line")),
+    );
+    assert_eq!(parse_err("I32Add"), (1, 7, ParseErrorKind::Expected("{")),);
+    assert!("Return".parse::<Instruction>().is_ok());
+    assert!("Return {}".parse::<Instruction>().is_err());
+}
+
+#[cfg(all(feature = "text", feature = "arbitrary"))]
+#[test]
+fn text_parse_arbitrary_instrs_works() {
+    use crate::Instruction;
+    use ::arbitrary::{Arbitrary, Unstructured};
+    use alloc::{format, vec::Vec};
+
+    let bytes: Vec<u8> = (0..65536_u32)
+        .map(|n| (n.wrapping_mul(2654435761) >> 11) as u8)
+        .collect();
+    let mut u = Unstructured::new(&bytes);
+    for _ in 0..4096 {
+        let instr = Instruction::arbitrary(&mut u).unwrap();
+        let text = format!("{instr:?}");
+        // Note: we compare textual forms since `NaN` payloads are not part of them.
+        let parsed = text
+            .parse::<Instruction>()
+            .unwrap_or_else(|error| panic!("failed to parse {text}: {error}"));
+        assert_eq!(format!("{parsed:?}"), text);
+    }
+}
+
+#[test]
+fn visit_instruction_works() {
+    use crate::{Instruction, VisitInstruction};
+
+    /// Returns the result of `i32.add` and the value of `return_reg` instructions.
+    struct VisitSome;
+
+    impl VisitInstruction for VisitSome {
+        type Output = Option<Reg>;
+
+        fn visit_fallback(&mut self, _instr: Instruction) -> Self::Output {
+            None
+        }
+
+        fn i32_add(&mut self, result: Reg, _lhs: Reg, _rhs: Reg) -> Self::Output {
+            Some(result)
+        }
+
+        fn return_reg(&mut self, value: Reg) -> Self::Output {
+            Some(value)
+        }
+    }
+
+    let reg = Reg::from;
+    let mut visitor = VisitSome;
+    let mut visit = |instr: Instruction| instr.visit(&mut visitor);
+    assert_eq!(
+        visit(Instruction::i32_add(reg(1), reg(2), reg(3))),
+        Some(reg(1))
+    );
+    assert_eq!(visit(Instruction::i32_sub(reg(1), reg(2), reg(3))), None);
+    assert_eq!(visit(Instruction::return_reg(reg(4))), Some(reg(4)));
+    assert_eq!(visit(Instruction::r#return()), None);
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn visit_instruction_arbitrary_instrs_works() {
+    use crate::{Instruction, VisitInstruction};
+    use ::arbitrary::{Arbitrary, Unstructured};
+    use alloc::vec::Vec;
+
+    /// Reassembles all visited instructions via the default variant methods.
+    struct Reassemble;
+
+    impl VisitInstruction for Reassemble {
+        type Output = Instruction;
+
+        fn visit_fallback(&mut self, instr: Instruction) -> Self::Output {
+            instr
+        }
+    }
+
+    let bytes: Vec<u8> = (0..65536_u32)
+        .map(|n| (n.wrapping_mul(2654435761) >> 9) as u8)
+        .collect();
+    let mut u = Unstructured::new(&bytes);
+    for _ in 0..4096 {
+        let instr = Instruction::arbitrary(&mut u).unwrap();
+        assert_eq!(instr.visit(&mut Reassemble), instr);
+    }
+}
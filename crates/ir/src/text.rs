@@ -0,0 +1,487 @@
+//! Parsing of the textual form of Wasmi [`Instruction`]s.
+//!
+//! This allows writing Wasmi bytecode by hand, for example for executor test cases
+//! or for human-editable golden files of the Wasm to Wasmi bytecode translation.
+//!
+//! # Format
+//!
+//! The textual form of an [`Instruction`] is its [`Debug`] representation, for example:
+//!
+//! ```text
+//! I32AddImm16 { result: Reg(0), lhs: Reg(1), rhs: 5 }
+//! ```
+//!
+//! A sequence of [`Instruction`]s has one [`Instruction`] per line and each line may
+//! start with the index of its [`Instruction`] as in `0003: Return`.
+//! This is the same form used by the disassembler of the `wasmi` crate.
+//!
+//! # Note
+//!
+//! - Instruction indices are ignored and not required to match.
+//! - Empty lines and comments starting with `;;` are ignored.
+//! - Section headers such as `instrs:` are ignored as well as the lines
+//!   of a `consts:` section listing function local constant values.
+//!
+//! [`Debug`]: ::core::fmt::Debug
+
+use crate::{core::TrapCode, index::*, *};
+use ::core::{
+    fmt,
+    mem,
+    num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64},
+    str::FromStr,
+};
+use alloc::{format, vec::Vec};
+#[cfg(feature = "simd")]
+use wasmi_core::simd::ImmLaneIdx;
+
+/// An error that may occur upon parsing the textual form of [`Instruction`]s.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line at which the error occurred starting at 1.
+    line: usize,
+    /// The column at which the error occurred starting at 1.
+    column: usize,
+    /// The kind of the error.
+    kind: ParseErrorKind,
+}
+
+/// The kind of a [`ParseError`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// Expected the described token.
+    Expected(&'static str),
+    /// Encountered a name that does not belong to any [`Instruction`].
+    UnknownInstruction,
+    /// Encountered an out of bounds value for a field of an [`Instruction`].
+    InvalidValue,
+}
+
+impl ParseError {
+    /// Returns the line at which the [`ParseError`] occurred starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column at which the [`ParseError`] occurred starting at 1.
+    ///
+    /// Columns are counted in bytes.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the [`ParseErrorKind`] of the [`ParseError`].
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Expected(expected) => write!(f, "expected {expected}"),
+            Self::UnknownInstruction => write!(f, "unknown instruction"),
+            Self::InvalidValue => write!(f, "invalid instruction field value"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}:{}", self.kind, self.line, self.column)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(text, 1);
+        let instr = Instruction::parse(&mut parser)?;
+        parser.expect_end()?;
+        Ok(instr)
+    }
+}
+
+/// Parses the sequence of [`Instruction`]s in `text`.
+///
+/// # Errors
+///
+/// If a line of `text` is not the textual form of an [`Instruction`].
+pub fn parse_instrs(text: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut instrs = Vec::new();
+    let mut in_consts = false;
+    for (n, line) in text.lines().enumerate() {
+        let line = match line.find(";;") {
+            Some(comment) => &line[..comment],
+            None => line,
+        };
+        match line.trim() {
+            "" => continue,
+            "consts:" => in_consts = true,
+            "instrs:" => in_consts = false,
+            _ if in_consts => continue,
+            _ => {
+                let mut parser = Parser::new(line, n + 1);
+                parser.skip_index();
+                instrs.push(Instruction::parse(&mut parser)?);
+                parser.expect_end()?;
+            }
+        }
+    }
+    Ok(instrs)
+}
+
+/// Reads tokens of the textual form of [`Instruction`]s from a single line.
+#[derive(Debug)]
+struct Parser<'a> {
+    /// The line of text.
+    text: &'a str,
+    /// The current byte position within `text`.
+    pos: usize,
+    /// The line number of `text` starting at 1.
+    line: usize,
+    /// Is `true` while parsing the fields of an [`Instruction`] within braces.
+    in_fields: bool,
+}
+
+impl<'a> Parser<'a> {
+    /// Creates a new [`Parser`] for `text` at `line`.
+    fn new(text: &'a str, line: usize) -> Self {
+        Self {
+            text,
+            pos: 0,
+            line,
+            in_fields: false,
+        }
+    }
+
+    /// Returns a [`ParseError`] of `kind` at byte position `pos`.
+    fn error(&self, pos: usize, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: pos + 1,
+            kind,
+        }
+    }
+
+    /// Returns the remaining text after skipping whitespace.
+    fn rest(&mut self) -> &'a str {
+        let rest = &self.text[self.pos..];
+        let trimmed = rest.trim_start();
+        self.pos += rest.len() - trimmed.len();
+        trimmed
+    }
+
+    /// Skips whitespace and returns the byte position of the next token.
+    fn token_start(&mut self) -> usize {
+        self.rest();
+        self.pos
+    }
+
+    /// Skips the index of an [`Instruction`] such as `0003:` if any.
+    fn skip_index(&mut self) {
+        let rest = self.rest();
+        let len_digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if len_digits != 0 && rest[len_digits..].starts_with(':') {
+            self.pos += len_digits + 1;
+        }
+    }
+
+    /// Consumes `token` and returns `true` if the remaining text starts with it.
+    fn eat(&mut self, token: &str) -> bool {
+        if !self.rest().starts_with(token) {
+            return false;
+        }
+        self.pos += token.len();
+        true
+    }
+
+    /// Consumes `token` or returns an error if the remaining text does not start with it.
+    fn expect(&mut self, token: &'static str) -> Result<(), ParseError> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(self.error(self.pos, ParseErrorKind::Expected(token))),
+        }
+    }
+
+    /// Returns an error if there is remaining text other than whitespace.
+    fn expect_end(&mut self) -> Result<(), ParseError> {
+        match self.rest().is_empty() {
+            true => Ok(()),
+            false => Err(self.error(self.pos, ParseErrorKind::Expected("end of line"))),
+        }
+    }
+
+    /// Consumes the longest prefix whose characters satisfy `f`.
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Consumes an identifier.
+    fn ident(&mut self, expected: &'static str) -> Result<&'a str, ParseError> {
+        let start = self.token_start();
+        let ident = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+        if ident.is_empty() {
+            return Err(self.error(start, ParseErrorKind::Expected(expected)));
+        }
+        Ok(ident)
+    }
+
+    /// Consumes a number literal and parses it as `T`.
+    fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = self.token_start();
+        let literal =
+            self.take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-'));
+        if literal.is_empty() {
+            return Err(self.error(start, ParseErrorKind::Expected("number")));
+        }
+        literal
+            .parse()
+            .map_err(|_| self.error(start, ParseErrorKind::InvalidValue))
+    }
+
+    /// Consumes the field called `name` of an [`Instruction`] and parses its value.
+    ///
+    /// This also consumes the opening brace before the first field.
+    fn field<T: Parse>(&mut self, name: &'static str) -> Result<T, ParseError> {
+        match self.in_fields {
+            true => self.expect(",")?,
+            false => self.expect("{")?,
+        }
+        self.in_fields = true;
+        let start = self.token_start();
+        if self.ident("field name")? != name {
+            return Err(self.error(start, ParseErrorKind::Expected(name)));
+        }
+        self.expect(":")?;
+        T::parse(self)
+    }
+
+    /// Parses a value of `T` via `f` which consumes its fields via [`Parser::field`] if any.
+    ///
+    /// This consumes the closing brace after the fields if any.
+    fn fields<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let outer = mem::replace(&mut self.in_fields, false);
+        let value = f(self)?;
+        if self.in_fields {
+            self.eat(",");
+            self.expect("}")?;
+        }
+        self.in_fields = outer;
+        Ok(value)
+    }
+
+    /// Parses a value of `T` via `f` and returns an error if `f` returns `None`.
+    fn parse_with<T, R: Parse>(&mut self, f: impl FnOnce(R) -> Option<T>) -> Result<T, ParseError> {
+        let start = self.token_start();
+        let raw = R::parse(self)?;
+        f(raw).ok_or(self.error(start, ParseErrorKind::InvalidValue))
+    }
+}
+
+/// Types that are parsed as fields of [`Instruction`]s.
+trait Parse: Sized {
+    /// Parses a value of `Self` via `parser`.
+    fn parse(parser: &mut Parser) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_parse_for_number {
+    ( $($ty:ty),* $(,)? ) => {
+        $(
+            impl Parse for $ty {
+                fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
+                    parser.number()
+                }
+            }
+        )*
+    };
+}
+impl_parse_for_number!(
+    i8, u8, i16, u16, i32, u32, i64, u64, f32, f64, NonZeroI32, NonZeroU32, NonZeroI64, NonZeroU64,
+);
+
+/// Implements [`Parse`] for types printed as `Name(value)` such as `Reg(0)`.
+///
+/// The expression converts the parsed `value` and returns `None` for invalid values.
+macro_rules! impl_parse_for_tuple {
+    ( $( $ty:ty => $name:literal($repr:ty) { |$raw:ident| $convert:expr } )* ) => {
+        $(
+            impl Parse for $ty {
+                fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
+                    parser.expect($name)?;
+                    parser.expect("(")?;
+                    let value = parser.parse_with(|$raw: $repr| -> Option<Self> { $convert })?;
+                    parser.expect(")")?;
+                    Ok(value)
+                }
+            }
+        )*
+    };
+}
+impl_parse_for_tuple! {
+    Reg => "Reg"(i16) { |raw| Some(Self::from(raw)) }
+    Func => "Func"(u32) { |raw| Some(Self::from(raw)) }
+    FuncType => "FuncType"(u32) { |raw| Some(Self::from(raw)) }
+    InternalFunc => "InternalFunc"(u32) { |raw| Some(Self::from(raw)) }
+    Global => "Global"(u32) { |raw| Some(Self::from(raw)) }
+    Memory => "Memory"(u32) { |raw| Some(Self::from(raw)) }
+    Table => "Table"(u32) { |raw| Some(Self::from(raw)) }
+    Data => "Data"(u32) { |raw| Some(Self::from(raw)) }
+    Elem => "Elem"(u32) { |raw| Some(Self::from(raw)) }
+    AnyConst16 => "AnyConst16"(i16) { |raw| Some(Self::from(raw)) }
+    AnyConst32 => "AnyConst32"(u32) { |raw| Some(Self::from(raw)) }
+    BranchOffset => "BranchOffset"(i32) { |raw| Some(Self::from(raw)) }
+    BranchOffset16 => "BranchOffset16"(i16) { |raw| Some(Self::from(raw)) }
+    BlockFuel => "BlockFuel"(u32) { |raw| Some(Self::from(raw)) }
+    Offset8 => "Offset8"(u64) { |raw| Self::try_from(raw).ok() }
+    Offset16 => "Offset16"(u64) { |raw| Self::try_from(raw).ok() }
+    Offset64Hi => "Offset64Hi"(u32) { |raw| Some(Self(raw)) }
+    Offset64Lo => "Offset64Lo"(u32) { |raw| Some(Self(raw)) }
+    Address32 => "Address32"(u64) { |raw| Address::try_from(raw).and_then(Self::try_from).ok() }
+    RegSpan => "RegSpan"(Reg) { |raw| Some(Self::new(raw)) }
+}
+
+/// Implements [`Parse`] for types printed as their typed value such as `Const16<i32>`.
+///
+/// The expression converts the parsed value and returns `None` for invalid values.
+macro_rules! impl_parse_for_value {
+    ( $( $ty:ty => $repr:ty { |$raw:ident| $convert:expr } )* ) => {
+        $(
+            impl Parse for $ty {
+                fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
+                    parser.parse_with(|$raw: $repr| -> Option<Self> { $convert })
+                }
+            }
+        )*
+    };
+}
+impl_parse_for_value! {
+    Const16<i32> => i32 { |raw| Self::try_from(raw).ok() }
+    Const16<u32> => u32 { |raw| Self::try_from(raw).ok() }
+    Const16<i64> => i64 { |raw| Self::try_from(raw).ok() }
+    Const16<u64> => u64 { |raw| Self::try_from(raw).ok() }
+    Const16<NonZeroI32> => NonZeroI32 { |raw| Self::try_from(raw).ok() }
+    Const16<NonZeroU32> => NonZeroU32 { |raw| Self::try_from(raw).ok() }
+    Const16<NonZeroI64> => NonZeroI64 { |raw| Self::try_from(raw).ok() }
+    Const16<NonZeroU64> => NonZeroU64 { |raw| Self::try_from(raw).ok() }
+    Const32<i64> => i64 { |raw| Self::try_from(raw).ok() }
+    Const32<u64> => u64 { |raw| Self::try_from(raw).ok() }
+    Const32<f64> => f64 {
+        |raw| match raw.is_nan() {
+            true => Some(Self::from(raw as f32)),
+            false => Self::try_from(raw).ok(),
+        }
+    }
+    Sign<f32> => f32 { |raw| (raw.abs() == 1.0).then(|| Self::from(raw)) }
+    Sign<f64> => f64 { |raw| (raw.abs() == 1.0).then(|| Self::from(raw)) }
+    ShiftAmount<i32> => i32 {
+        |raw| i32::into_shift_amount(raw).filter(|shamt| i32::from(*shamt) == raw)
+    }
+    ShiftAmount<i64> => i64 {
+        |raw| i64::into_shift_amount(raw).filter(|shamt| i64::from(*shamt) == raw)
+    }
+    ShiftAmount<u32> => u32 {
+        |raw| u64::into_shift_amount(raw).filter(|shamt| u32::from(*shamt) == raw)
+    }
+}
+
+impl Parse for FixedRegSpan<2> {
+    fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
+        let start = parser.token_start();
+        parser.expect("FixedRegSpan")?;
+        let span = parser.fields(|parser| parser.field("span"))?;
+        Self::new(span).map_err(|_| parser.error(start, ParseErrorKind::InvalidValue))
+    }
+}
+
+impl Parse for BoundedRegSpan {
+    fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
+        parser.expect("BoundedRegSpan")?;
+        parser.fields(|parser| Ok(Self::new(parser.field("span")?, parser.field("len")?)))
+    }
+}
+
+impl<const N: usize> Parse for [Reg; N] {
+    fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
+        parser.expect("[")?;
+        let mut regs = [Reg::from(0); N];
+        for (n, reg) in regs.iter_mut().enumerate() {
+            if n != 0 {
+                parser.expect(",")?;
+            }
+            *reg = Reg::parse(parser)?;
+        }
+        parser.expect("]")?;
+        Ok(regs)
+    }
+}
+
+impl Parse for TrapCode {
+    fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
+        let start = parser.token_start();
+        let name = parser.ident("trap code")?;
+        primitive::TRAP_CODES
+            .into_iter()
+            .find(|code| format!("{code:?}") == name)
+            .ok_or(parser.error(start, ParseErrorKind::InvalidValue))
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<const N: u8> Parse for ImmLaneIdx<N> {
+    fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
+        parser.expect("ImmLaneIdx")?;
+        parser.expect("(")?;
+        let lane = parser.parse_with(|lane: u8| Self::try_from(lane).ok())?;
+        parser.expect(")")?;
+        Ok(lane)
+    }
+}
+
+macro_rules! impl_parse_for_instruction {
+    (
+        $(
+            $( #[doc = $doc:literal] )*
+            #[snake_name($snake_name:ident)]
+            $name:ident
+            $(
+                {
+                    $( @ $result_name:ident: $result_ty:ty, )?
+                    $(
+                        $( #[$field_docs:meta] )*
+                        $field_name:ident: $field_ty:ty
+                    ),*
+                    $(,)?
+                }
+            )?
+        ),* $(,)?
+    ) => {
+        impl Parse for Instruction {
+            fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
+                let start = parser.token_start();
+                match parser.ident("instruction name")? {
+                    $(
+                        stringify!($name) => {
+                            parser.fields(|_parser| Ok(Self::$name $( {
+                                $( $result_name: _parser.field(stringify!($result_name))?, )?
+                                $( $field_name: _parser.field(stringify!($field_name))?, )*
+                            } )?))
+                        }
+                    )*
+                    _ => Err(parser.error(start, ParseErrorKind::UnknownInstruction)),
+                }
+            }
+        }
+    };
+}
+for_each_op!(impl_parse_for_instruction);
@@ -0,0 +1,109 @@
+#[cfg(feature = "simd")]
+use crate::core::simd::{ImmLaneIdx16, ImmLaneIdx2, ImmLaneIdx4, ImmLaneIdx8};
+use crate::{core::TrapCode, index::*, *};
+use ::core::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
+
+impl Instruction {
+    /// Dispatches `self` to the respective method of the `visitor`.
+    ///
+    /// This is a shorthand for [`VisitInstruction::visit_instruction`].
+    pub fn visit<V: VisitInstruction>(self, visitor: &mut V) -> V::Output {
+        visitor.visit_instruction(self)
+    }
+}
+
+macro_rules! define_visit_instruction {
+    (
+        $(
+            $( #[doc = $doc:literal] )*
+            #[snake_name($snake_name:ident)]
+            $name:ident
+            $(
+                {
+                    $( @ $result_name:ident: $result_ty:ty, )?
+                    $(
+                        $( #[$field_docs:meta] )*
+                        $field_name:ident: $field_ty:ty
+                    ),*
+                    $(,)?
+                }
+            )?
+        ),* $(,)?
+    ) => {
+        /// Implemented by visitors of [`Instruction`]s with one method per [`Instruction`] variant.
+        ///
+        /// [`VisitInstruction::visit_instruction`] dispatches an [`Instruction`] to the method of its variant.
+        /// All variant methods forward to [`VisitInstruction::visit_fallback`] by default,
+        /// so visitors only need to implement the methods of the variants they are interested in.
+        ///
+        /// The variant methods are named after the respective [`Instruction`] constructors.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use wasmi_ir::{Instruction, Reg, VisitInstruction};
+        ///
+        /// /// Counts the `i32.add` instructions of a sequence.
+        /// struct CountI32Add(usize);
+        ///
+        /// impl VisitInstruction for CountI32Add {
+        ///     type Output = ();
+        ///
+        ///     fn visit_fallback(&mut self, _instr: Instruction) {}
+        ///
+        ///     fn i32_add(&mut self, _result: Reg, _lhs: Reg, _rhs: Reg) {
+        ///         self.0 += 1;
+        ///     }
+        /// }
+        ///
+        /// let instrs = [
+        ///     Instruction::i32_add(Reg::from(2), Reg::from(0), Reg::from(1)),
+        ///     Instruction::i32_mul(Reg::from(2), Reg::from(2), Reg::from(2)),
+        ///     Instruction::i32_add(Reg::from(2), Reg::from(2), Reg::from(0)),
+        ///     Instruction::return_reg(Reg::from(2)),
+        /// ];
+        /// let mut counter = CountI32Add(0);
+        /// for instr in instrs {
+        ///     instr.visit(&mut counter);
+        /// }
+        /// assert_eq!(counter.0, 2);
+        /// ```
+        pub trait VisitInstruction {
+            /// The type returned by all visit methods.
+            type Output;
+
+            /// Visits an [`Instruction`] whose variant method is not implemented by the visitor.
+            fn visit_fallback(&mut self, instr: Instruction) -> Self::Output;
+
+            /// Visits `instr` by dispatching to the method of its variant.
+            fn visit_instruction(&mut self, instr: Instruction) -> Self::Output {
+                match instr {
+                    $(
+                        Instruction::$name { $( $( $result_name, )? $( $field_name, )* )? } => {
+                            self.$snake_name($( $( $result_name, )? $( $field_name, )* )?)
+                        }
+                    )*
+                }
+            }
+
+            $(
+                #[doc = concat!("Visits an [`Instruction::", stringify!($name), "`].")]
+                fn $snake_name(
+                    &mut self,
+                    $(
+                        $( $result_name: $result_ty, )?
+                        $( $field_name: $field_ty, )*
+                    )?
+                ) -> Self::Output {
+                    self.visit_fallback(Instruction::$name {
+                        $(
+                            $( $result_name, )?
+                            $( $field_name, )*
+                        )?
+                    })
+                }
+            )*
+        }
+    };
+}
+for_each_op!(define_visit_instruction);
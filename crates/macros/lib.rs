@@ -0,0 +1,336 @@
+//! Procedural macros to define host APIs for the `wasmi` interpreter.
+//!
+//! Hand-writing a [`Linker::func_wrap`] closure for every host function is
+//! the most boilerplate heavy part of embedding `wasmi`. The [`host_api`] macro
+//! generates this glue code from a plain Rust trait instead.
+//!
+//! The [`bindgen!`] macro generates the same kind of glue code from a
+//! WebAssembly Interface Type (WIT) interface definition instead.
+//!
+//! The [`wat!`] macro embeds Wasm modules written in the WebAssembly text format
+//! by parsing them at compile time.
+//!
+//! [`Linker::func_wrap`]: https://docs.rs/wasmi/latest/wasmi/struct.Linker.html#method.func_wrap
+
+mod wit;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input,
+    parse_quote,
+    spanned::Spanned,
+    FnArg,
+    ItemTrait,
+    LitByteStr,
+    LitStr,
+    ReturnType,
+    TraitItem,
+    TraitItemFn,
+    Type,
+};
+
+/// Generates [`Linker`] registration glue for the host functions of a trait.
+///
+/// Adds the provided `add_to_linker` method to the annotated trait which defines
+/// every trait method as a host function in a [`Linker`] under the given module name.
+///
+/// # Host Functions
+///
+/// - Every trait method must take `&mut self` as its receiver.
+/// - The optional first parameter after the receiver may be of type `&mut [u8]`
+///   in which case it receives the bytes of the linear memory exported as `"memory"`
+///   by the calling Wasm instance.
+/// - All other parameter types must implement `WasmTy`.
+/// - The return type must implement `WasmRet`, e.g. `i32`, `(i64, f32)` or `Result<i32, Error>`.
+///
+/// # Example
+///
+/// ```
+/// use wasmi::{Engine, Linker};
+///
+/// #[wasmi_macros::host_api]
+/// trait Env {
+///     fn add(&mut self, lhs: i32, rhs: i32) -> i32;
+///     fn print(&mut self, memory: &mut [u8], ptr: u32, len: u32);
+/// }
+///
+/// struct Host;
+///
+/// impl Env for Host {
+///     fn add(&mut self, lhs: i32, rhs: i32) -> i32 {
+///         lhs.wrapping_add(rhs)
+///     }
+///
+///     fn print(&mut self, memory: &mut [u8], ptr: u32, len: u32) {
+///         let (ptr, len) = (ptr as usize, len as usize);
+///         println!("{}", String::from_utf8_lossy(&memory[ptr..ptr + len]));
+///     }
+/// }
+///
+/// let engine = Engine::default();
+/// let mut linker = <Linker<Host>>::new(&engine);
+/// Host::add_to_linker(&mut linker, "env", |host| host).unwrap();
+/// ```
+///
+/// [`Linker`]: https://docs.rs/wasmi/latest/wasmi/struct.Linker.html
+#[proc_macro_attribute]
+pub fn host_api(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            Span::call_site(),
+            "the `host_api` attribute does not take arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let item = parse_macro_input!(item as ItemTrait);
+    expand_host_api(item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Expands the [`host_api`] attribute macro applied to `item`.
+fn expand_host_api(mut item: ItemTrait) -> syn::Result<TokenStream> {
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            item.generics.span(),
+            "`host_api` traits must not be generic",
+        ));
+    }
+    let trait_ident = &item.ident;
+    let definitions = item
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            TraitItem::Fn(func) => Some(expand_host_func(trait_ident, func)),
+            _ => None,
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let add_to_linker: TraitItem = parse_quote! {
+        /// Defines all host functions of this trait in the `linker` under `module`.
+        ///
+        /// The `get` function projects the `T` data of the `Store` to the trait implementation.
+        ///
+        /// # Errors
+        ///
+        /// If there already is a definition for any of the host functions in the `linker`.
+        fn add_to_linker<T>(
+            linker: &mut ::wasmi::Linker<T>,
+            module: &str,
+            get: fn(&mut T) -> &mut Self,
+        ) -> ::core::result::Result<(), ::wasmi::errors::LinkerError>
+        where
+            Self: ::core::marker::Sized + 'static,
+            T: 'static,
+        {
+            #( #definitions )*
+            ::core::result::Result::Ok(())
+        }
+    };
+    item.items.push(add_to_linker);
+    Ok(quote! { #item })
+}
+
+/// Expands the definition of the host function for the trait method `func`.
+fn expand_host_func(trait_ident: &syn::Ident, func: &TraitItemFn) -> syn::Result<TokenStream> {
+    let sig = &func.sig;
+    let method = &sig.ident;
+    let name = method.to_string();
+    if !sig.generics.params.is_empty() || sig.asyncness.is_some() {
+        return Err(syn::Error::new(
+            sig.span(),
+            "`host_api` methods must be neither generic nor async",
+        ));
+    }
+    let mut inputs = sig.inputs.iter();
+    match inputs.next() {
+        Some(FnArg::Receiver(receiver))
+            if receiver.reference.is_some() && receiver.mutability.is_some() => {}
+        _ => {
+            return Err(syn::Error::new(
+                sig.span(),
+                "`host_api` methods must take `&mut self` as receiver",
+            ))
+        }
+    }
+    let mut inputs = inputs.peekable();
+    let has_memory = inputs.peek().is_some_and(|input| is_memory_param(input));
+    if has_memory {
+        inputs.next();
+    }
+    let (params, param_types) = inputs
+        .enumerate()
+        .map(|(n, input)| match input {
+            FnArg::Typed(param) => Ok((format_ident!("arg{n}"), &*param.ty)),
+            FnArg::Receiver(receiver) => Err(syn::Error::new(
+                receiver.span(),
+                "unexpected receiver parameter",
+            )),
+        })
+        .collect::<syn::Result<(Vec<_>, Vec<_>)>>()?;
+    let ret: Type = match &sig.output {
+        ReturnType::Default => parse_quote!(()),
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+    let body = if has_memory {
+        quote! {
+            let memory = caller
+                .get_export("memory")
+                .and_then(::wasmi::Extern::into_memory)
+                .ok_or_else(|| ::wasmi::Error::new("missing exported linear memory: `memory`"))?;
+            let (memory, data) = memory.data_and_store_mut(&mut caller);
+            ::wasmi::WasmRet::into_fallible(
+                <Self as #trait_ident>::#method(get(data), memory, #( #params ),*)
+            )
+        }
+    } else {
+        quote! {
+            ::wasmi::WasmRet::into_fallible(
+                <Self as #trait_ident>::#method(get(caller.data_mut()), #( #params ),*)
+            )
+        }
+    };
+    Ok(quote! {
+        linker.func_wrap(
+            module,
+            #name,
+            move |
+                mut caller: ::wasmi::Caller<'_, T>,
+                #( #params: #param_types ),*
+            | -> ::core::result::Result<
+                <#ret as ::wasmi::WasmRet>::Ok,
+                ::wasmi::Error,
+            > {
+                #body
+            },
+        )?;
+    })
+}
+
+/// Returns `true` if `input` is a `&mut [u8]` linear memory parameter.
+fn is_memory_param(input: &FnArg) -> bool {
+    let FnArg::Typed(param) = input else {
+        return false;
+    };
+    let Type::Reference(reference) = &*param.ty else {
+        return false;
+    };
+    let Type::Slice(slice) = &*reference.elem else {
+        return false;
+    };
+    reference.mutability.is_some()
+        && matches!(&*slice.elem, Type::Path(path) if path.path.is_ident("u8"))
+}
+
+/// Generates host traits and [`Linker`] glue from a WebAssembly Interface Type (WIT) source.
+///
+/// Takes a single string literal with the WIT source and generates a Rust trait for every
+/// `interface` in it. Every trait has a method per WIT function and a provided
+/// `add_to_linker` method which defines all of them as host functions in a [`Linker`].
+///
+/// The host functions follow the canonical ABI for core Wasm modules, as used by guests
+/// generated with `wit-bindgen`, and are defined under the module name
+/// `"<package>/<interface>"`, or just `"<interface>"` if the WIT source has no package.
+///
+/// # Supported WIT
+///
+/// - An optional `package` declaration followed by `interface` items with functions.
+/// - The types `bool`, `s8`, `u8`, `s16`, `u16`, `s32`, `u32`, `s64`, `u64`, `f32`, `f64`,
+///   `char`, `string` and `list<u8>`.
+/// - Parameters of type `string` and `list<u8>` are passed to the trait methods as
+///   `&str` and `&[u8]` borrowing the linear memory exported as `"memory"` by the guest.
+/// - Results of type `string` and `list<u8>` are returned as `String` and `Vec<u8>`
+///   and copied into a guest buffer allocated via the exported `cabi_realloc` function.
+///
+/// # Example
+///
+/// ```
+/// use wasmi::{Engine, Linker};
+///
+/// wasmi_macros::bindgen!(r#"
+///     package example:host;
+///
+///     interface logging {
+///         /// Logs the `message` at the given `level`.
+///         log: func(level: u8, message: string);
+///         /// Returns the name of the host.
+///         host-name: func() -> string;
+///     }
+/// "#);
+///
+/// struct Host;
+///
+/// impl Logging for Host {
+///     fn log(&mut self, level: u8, message: &str) {
+///         println!("[{level}] {message}");
+///     }
+///
+///     fn host_name(&mut self) -> String {
+///         "wasmi".into()
+///     }
+/// }
+///
+/// let engine = Engine::default();
+/// let mut linker = <Linker<Host>>::new(&engine);
+/// Host::add_to_linker(&mut linker, |host| host).unwrap();
+/// ```
+///
+/// [`Linker`]: https://docs.rs/wasmi/latest/wasmi/struct.Linker.html
+#[proc_macro]
+pub fn bindgen(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let source = parse_macro_input!(input as LitStr);
+    expand_bindgen(&source)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Expands the [`bindgen!`] macro for the WIT `source`.
+fn expand_bindgen(source: &LitStr) -> syn::Result<TokenStream> {
+    let document = wit::parse(&source.value())
+        .map_err(|error| syn::Error::new(source.span(), format!("invalid WIT: {error}")))?;
+    Ok(document.expand())
+}
+
+/// Parses the WebAssembly text format (Wat) at compile time into Wasm bytes.
+///
+/// Takes a single string literal with the Wat source and expands to a `&'static [u8; N]`
+/// byte string with the encoded Wasm. Syntax errors of the Wat source are reported as
+/// compile errors. This allows to embed small Wasm modules without a build step or
+/// a runtime dependency on a Wat parser.
+///
+/// # Example
+///
+/// ```
+/// use wasmi::{Engine, Module};
+///
+/// let wasm = wasmi_macros::wat!(r#"
+///     (module
+///         (func (export "add") (param i32 i32) (result i32)
+///             (i32.add (local.get 0) (local.get 1))
+///         )
+///     )
+/// "#);
+/// assert_eq!(&wasm[..4], b"\0asm");
+/// let engine = Engine::default();
+/// let module = Module::new(&engine, wasm).unwrap();
+/// assert!(module.get_export("add").is_some());
+/// ```
+#[proc_macro]
+pub fn wat(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let source = parse_macro_input!(input as LitStr);
+    expand_wat(&source)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Expands the [`wat!`] macro for the Wat `source`.
+fn expand_wat(source: &LitStr) -> syn::Result<TokenStream> {
+    let wasm = wat::parse_str(source.value())
+        .map_err(|error| syn::Error::new(source.span(), format!("invalid Wat: {error}")))?;
+    let wasm = LitByteStr::new(&wasm, source.span());
+    Ok(quote! { #wasm })
+}
@@ -0,0 +1,155 @@
+//! Tests to check if the `bindgen` macro generates working host bindings.
+
+use wasmi::{Engine, Instance, Linker, Module, Store};
+use wasmi_macros::bindgen;
+
+bindgen!(
+    r#"
+    package test:host@0.1.0;
+
+    /// Host functions exercising all supported WIT types.
+    interface env {
+        add: func(lhs: s32, rhs: s32) -> s32;
+        negate: func(value: bool) -> bool;
+        widen: func(byte: u8, small: s16, big: u64) -> f64;
+        /* Block comments are ignored. */
+        next-char: func(c: char) -> char;
+        length: func(text: string, bytes: list<u8>) -> u32;
+        // Line comments are ignored as well.
+        greet: func(name: string) -> string;
+        record: func(value: f32);
+    }
+"#
+);
+
+#[derive(Default)]
+struct Host {
+    recorded: Vec<f32>,
+}
+
+impl Env for Host {
+    fn add(&mut self, lhs: i32, rhs: i32) -> i32 {
+        lhs.wrapping_add(rhs)
+    }
+
+    fn negate(&mut self, value: bool) -> bool {
+        !value
+    }
+
+    fn widen(&mut self, byte: u8, small: i16, big: u64) -> f64 {
+        f64::from(byte) + f64::from(small) + big as f64
+    }
+
+    fn next_char(&mut self, c: char) -> char {
+        char::from_u32(u32::from(c) + 1).unwrap_or(c)
+    }
+
+    fn length(&mut self, text: &str, bytes: &[u8]) -> u32 {
+        (text.chars().count() + bytes.len()) as u32
+    }
+
+    fn greet(&mut self, name: &str) -> String {
+        format!("hello, {name}!")
+    }
+
+    fn record(&mut self, value: f32) {
+        self.recorded.push(value);
+    }
+}
+
+fn test_setup() -> (Store<Host>, Instance) {
+    let wasm = r#"
+        (module
+            (import "test:host/env@0.1.0" "add" (func (param i32 i32) (result i32)))
+            (import "test:host/env@0.1.0" "negate" (func (param i32) (result i32)))
+            (import "test:host/env@0.1.0" "widen" (func (param i32 i32 i64) (result f64)))
+            (import "test:host/env@0.1.0" "next-char" (func (param i32) (result i32)))
+            (import "test:host/env@0.1.0" "length" (func $length (param i32 i32 i32 i32) (result i32)))
+            (import "test:host/env@0.1.0" "greet" (func $greet (param i32 i32 i32)))
+            (import "test:host/env@0.1.0" "record" (func (param f32)))
+            (memory (export "memory") 1)
+            (global $next (mut i32) (i32.const 1024))
+            (data (i32.const 0) "w\C3\A4smi\00\01\FF")
+            (func (export "cabi_realloc") (param i32 i32 i32 i32) (result i32)
+                (global.get $next)
+                (global.set $next (i32.add (global.get $next) (local.get 3)))
+            )
+            (export "add" (func 0))
+            (export "negate" (func 1))
+            (export "widen" (func 2))
+            (export "next-char" (func 3))
+            (func (export "length") (param i32 i32 i32 i32) (result i32)
+                (call $length (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+            )
+            (func (export "greet") (param i32 i32 i32)
+                (call $greet (local.get 0) (local.get 1) (local.get 2))
+            )
+            (export "record" (func 6))
+        )
+    "#;
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, Host::default());
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut linker = <Linker<Host>>::new(&engine);
+    Host::add_to_linker(&mut linker, |host| host).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn bindgen_scalars_work() {
+    let (mut store, instance) = test_setup();
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+    let negate = instance
+        .get_typed_func::<i32, i32>(&store, "negate")
+        .unwrap();
+    assert_eq!(negate.call(&mut store, 2).unwrap(), 0);
+    assert_eq!(negate.call(&mut store, 0).unwrap(), 1);
+    let widen = instance
+        .get_typed_func::<(i32, i32, i64), f64>(&store, "widen")
+        .unwrap();
+    assert_eq!(
+        widen.call(&mut store, (0x1FF, -1, -1)).unwrap(),
+        254.0 + u64::MAX as f64
+    );
+    let next_char = instance
+        .get_typed_func::<i32, i32>(&store, "next-char")
+        .unwrap();
+    assert_eq!(next_char.call(&mut store, 'a' as i32).unwrap(), 'b' as i32);
+    assert!(next_char.call(&mut store, 0xD800).is_err());
+    let record = instance
+        .get_typed_func::<f32, ()>(&store, "record")
+        .unwrap();
+    record.call(&mut store, 1.5).unwrap();
+    assert_eq!(store.data().recorded, [1.5]);
+}
+
+#[test]
+fn bindgen_strings_and_lists_work() {
+    let (mut store, instance) = test_setup();
+    let length = instance
+        .get_typed_func::<(i32, i32, i32, i32), i32>(&store, "length")
+        .unwrap();
+    assert_eq!(length.call(&mut store, (0, 6, 6, 3)).unwrap(), 8);
+    assert!(length.call(&mut store, (6, 3, 0, 0)).is_err());
+    assert!(length.call(&mut store, (0, 0, 0x1_0000, 1)).is_err());
+    let greet = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "greet")
+        .unwrap();
+    greet.call(&mut store, (0, 6, 512)).unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let ptr = memory.read_scalar::<u32>(&store, 512).unwrap();
+    let len = memory.read_scalar::<u32>(&store, 516).unwrap();
+    assert_eq!(ptr, 1024);
+    assert_eq!(
+        memory.read_utf8(&store, ptr.into(), len.into()).unwrap(),
+        "hello, wäsmi!"
+    );
+}
@@ -0,0 +1,104 @@
+//! Tests to check if the `host_api` macro generates working `Linker` glue.
+
+use wasmi::{Engine, Error, Instance, Linker, Module, Store};
+use wasmi_macros::host_api;
+
+#[host_api]
+trait Env {
+    fn add(&mut self, lhs: i32, rhs: i32) -> i32;
+    fn store(&mut self, memory: &mut [u8], ptr: u32, value: i32);
+    fn checked_div(&mut self, lhs: i32, rhs: i32) -> Result<i32, Error>;
+}
+
+#[derive(Default)]
+struct Host {
+    calls: usize,
+}
+
+impl Env for Host {
+    fn add(&mut self, lhs: i32, rhs: i32) -> i32 {
+        self.calls += 1;
+        lhs.wrapping_add(rhs)
+    }
+
+    fn store(&mut self, memory: &mut [u8], ptr: u32, value: i32) {
+        self.calls += 1;
+        let ptr = ptr as usize;
+        memory[ptr..ptr + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn checked_div(&mut self, lhs: i32, rhs: i32) -> Result<i32, Error> {
+        self.calls += 1;
+        lhs.checked_div(rhs)
+            .ok_or_else(|| Error::new("division by zero"))
+    }
+}
+
+#[derive(Default)]
+struct State {
+    host: Host,
+}
+
+fn test_setup() -> (Store<State>, Instance) {
+    let wasm = r#"
+        (module
+            (import "env" "add" (func $add (param i32 i32) (result i32)))
+            (import "env" "store" (func $store (param i32 i32)))
+            (import "env" "checked_div" (func $checked_div (param i32 i32) (result i32)))
+            (memory (export "memory") 1)
+            (func (export "add") (param i32 i32) (result i32)
+                (call $add (local.get 0) (local.get 1))
+            )
+            (func (export "store") (param i32 i32)
+                (call $store (local.get 0) (local.get 1))
+            )
+            (func (export "checked_div") (param i32 i32) (result i32)
+                (call $checked_div (local.get 0) (local.get 1))
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, State::default());
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut linker = <Linker<State>>::new(&engine);
+    Host::add_to_linker(&mut linker, "env", |state| &mut state.host).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn host_api_works() {
+    let (mut store, instance) = test_setup();
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+    let store_fn = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "store")
+        .unwrap();
+    store_fn.call(&mut store, (8, 42)).unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let mut bytes = [0x00_u8; 4];
+    memory.read(&store, 8, &mut bytes).unwrap();
+    assert_eq!(i32::from_le_bytes(bytes), 42);
+    let checked_div = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "checked_div")
+        .unwrap();
+    assert_eq!(checked_div.call(&mut store, (10, 2)).unwrap(), 5);
+    let error = checked_div.call(&mut store, (1, 0)).unwrap_err();
+    assert_eq!(error.to_string(), "division by zero");
+    assert_eq!(store.data().host.calls, 4);
+}
+
+#[test]
+fn duplicate_definitions_fail() {
+    let engine = Engine::default();
+    let mut linker = <Linker<State>>::new(&engine);
+    Host::add_to_linker(&mut linker, "env", |state| &mut state.host).unwrap();
+    Host::add_to_linker(&mut linker, "env", |state| &mut state.host).unwrap_err();
+    Host::add_to_linker(&mut linker, "env2", |state| &mut state.host).unwrap();
+}
@@ -0,0 +1,37 @@
+//! Tests to check if the `wat!` macro parses Wat at compile time.
+
+use wasmi::{Engine, Linker, Module, Store};
+use wasmi_macros::wat;
+
+#[test]
+fn wat_macro_works() {
+    let wasm = wat!(
+        r#"
+        (module
+            (func (export "answer") (result i32)
+                (i32.const 42)
+            )
+        )
+    "#
+    );
+    assert_eq!(&wasm[..4], b"\0asm");
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let answer = instance
+        .get_typed_func::<(), i32>(&store, "answer")
+        .unwrap();
+    assert_eq!(answer.call(&mut store, ()).unwrap(), 42);
+}
+
+#[test]
+fn wat_macro_matches_runtime_parser() {
+    const WAT: &str = "(module (memory 1) (data (i32.const 0) \"wasmi\"))";
+    let wasm = wat!("(module (memory 1) (data (i32.const 0) \"wasmi\"))");
+    assert_eq!(&wasm[..], &wat::parse_str(WAT).unwrap()[..]);
+}
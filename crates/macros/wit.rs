@@ -0,0 +1,603 @@
+//! Parsing and expansion of the WebAssembly Interface Type (WIT) subset of [`bindgen!`].
+//!
+//! [`bindgen!`]: crate::bindgen!
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::Ident;
+
+/// A parsed WIT document.
+#[derive(Debug)]
+pub struct Document {
+    /// The package name of the WIT document if any, e.g. `example:host@1.0.0`.
+    package: Option<String>,
+    /// The interfaces of the WIT document.
+    interfaces: Vec<Interface>,
+}
+
+/// A WIT `interface` item.
+#[derive(Debug)]
+struct Interface {
+    /// The kebab-case name of the interface.
+    name: String,
+    /// The doc comments of the interface.
+    docs: Vec<String>,
+    /// The functions of the interface.
+    funcs: Vec<Function>,
+}
+
+/// A WIT function of an `interface`.
+#[derive(Debug)]
+struct Function {
+    /// The kebab-case name of the function.
+    name: String,
+    /// The doc comments of the function.
+    docs: Vec<String>,
+    /// The named parameters of the function.
+    params: Vec<(String, WitType)>,
+    /// The result of the function if any.
+    result: Option<WitType>,
+}
+
+/// A WIT type supported by [`bindgen!`](crate::bindgen!).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum WitType {
+    Bool,
+    S8,
+    U8,
+    S16,
+    U16,
+    S32,
+    U32,
+    S64,
+    U64,
+    F32,
+    F64,
+    Char,
+    String,
+    ListU8,
+}
+
+/// A token of the WIT source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// A (possibly kebab-case) identifier, keyword or number.
+    Id(String),
+    /// A `///` doc comment without its leading slashes.
+    Doc(String),
+    /// The `->` arrow.
+    Arrow,
+    /// A single punctuation character.
+    Punct(char),
+}
+
+/// Splits the WIT `source` into [`Token`]s with their line numbers.
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    let mut line = 1;
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            '/' if source[start..].starts_with("///") => {
+                let end = source[start..]
+                    .find('\n')
+                    .map_or(source.len(), |n| start + n);
+                tokens.push((Token::Doc(source[start + 3..end].trim().to_string()), line));
+                while chars.next_if(|&(n, _)| n < end).is_some() {}
+            }
+            '/' if source[start..].starts_with("//") => {
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+            }
+            '/' if source[start..].starts_with("/*") => {
+                let len = source[start + 2..]
+                    .find("*/")
+                    .ok_or_else(|| format!("line {line}: unterminated block comment"))?;
+                let end = start + 2 + len + 2;
+                line += source[start..end].matches('\n').count();
+                while chars.next_if(|&(n, _)| n < end).is_some() {}
+            }
+            '-' if chars.next_if(|&(_, c)| c == '>').is_some() => tokens.push((Token::Arrow, line)),
+            c if c.is_ascii_alphanumeric() || c == '%' => {
+                let mut end = start + c.len_utf8();
+                while let Some((n, c)) =
+                    chars.next_if(|&(_, c)| c.is_ascii_alphanumeric() || c == '-')
+                {
+                    end = n + c.len_utf8();
+                }
+                tokens.push((Token::Id(source[start..end].to_string()), line));
+            }
+            '{' | '}' | '(' | ')' | '<' | '>' | ':' | ';' | ',' | '@' | '/' | '.' => {
+                tokens.push((Token::Punct(c), line))
+            }
+            c => return Err(format!("line {line}: unexpected character `{c}`")),
+        }
+    }
+    Ok(tokens)
+}
+
+/// A parser for the tokens of a WIT document.
+struct Parser {
+    /// The remaining tokens in reverse order.
+    tokens: Vec<(Token, usize)>,
+    /// The line of the most recently consumed token.
+    line: usize,
+}
+
+impl Parser {
+    /// Returns the next token if any.
+    fn next(&mut self) -> Option<Token> {
+        let (token, line) = self.tokens.pop()?;
+        self.line = line;
+        Some(token)
+    }
+
+    /// Returns the next token if it equals `expected`.
+    fn eat(&mut self, expected: &Token) -> bool {
+        if self
+            .tokens
+            .last()
+            .is_some_and(|(token, _)| token == expected)
+        {
+            self.next();
+            return true;
+        }
+        false
+    }
+
+    /// Returns an error message for the current line.
+    fn error(&self, message: impl core::fmt::Display) -> String {
+        format!("line {}: {message}", self.line)
+    }
+
+    /// Consumes the next token which must equal `expected`.
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            token => Err(self.error(format!("expected {expected:?} but found {token:?}"))),
+        }
+    }
+
+    /// Consumes the next identifier token.
+    fn ident(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Id(id)) => Ok(id),
+            token => Err(self.error(format!("expected an identifier but found {token:?}"))),
+        }
+    }
+
+    /// Consumes all doc comment tokens.
+    fn docs(&mut self) -> Vec<String> {
+        let mut docs = Vec::new();
+        while let Some((Token::Doc(_), _)) = self.tokens.last() {
+            if let Some(Token::Doc(doc)) = self.next() {
+                docs.push(doc);
+            }
+        }
+        docs
+    }
+
+    /// Parses a WIT document.
+    fn document(&mut self) -> Result<Document, String> {
+        self.docs();
+        let mut package = None;
+        if self.eat(&Token::Id("package".into())) {
+            let mut name = String::new();
+            loop {
+                match self.next() {
+                    Some(Token::Punct(';')) => break,
+                    Some(Token::Id(id)) => name.push_str(&id),
+                    Some(Token::Punct(c @ (':' | '@' | '.' | '/'))) => name.push(c),
+                    token => return Err(self.error(format!("unexpected {token:?} in package"))),
+                }
+            }
+            package = Some(name);
+        }
+        let mut interfaces = Vec::new();
+        loop {
+            let docs = self.docs();
+            match self.next() {
+                None => break,
+                Some(Token::Id(keyword)) if keyword == "interface" => {
+                    interfaces.push(self.interface(docs)?)
+                }
+                token => {
+                    return Err(self.error(format!("expected `interface` but found {token:?}")))
+                }
+            }
+        }
+        Ok(Document {
+            package,
+            interfaces,
+        })
+    }
+
+    /// Parses the remainder of an `interface` item with its `docs`.
+    fn interface(&mut self, docs: Vec<String>) -> Result<Interface, String> {
+        let name = self.ident()?;
+        self.expect(Token::Punct('{'))?;
+        let mut funcs = Vec::new();
+        loop {
+            let docs = self.docs();
+            if self.eat(&Token::Punct('}')) {
+                break;
+            }
+            funcs.push(self.function(docs)?);
+        }
+        Ok(Interface { name, docs, funcs })
+    }
+
+    /// Parses a function of an `interface` with its `docs`.
+    fn function(&mut self, docs: Vec<String>) -> Result<Function, String> {
+        let name = self.ident()?;
+        self.expect(Token::Punct(':'))?;
+        match self.ident()?.as_str() {
+            "func" => {}
+            item => return Err(self.error(format!("unsupported interface item: `{item}`"))),
+        }
+        self.expect(Token::Punct('('))?;
+        let mut params = Vec::new();
+        while !self.eat(&Token::Punct(')')) {
+            let param = self.ident()?;
+            self.expect(Token::Punct(':'))?;
+            params.push((param, self.ty()?));
+            if !self.eat(&Token::Punct(',')) {
+                self.expect(Token::Punct(')'))?;
+                break;
+            }
+        }
+        let result = match self.eat(&Token::Arrow) {
+            true => Some(self.ty()?),
+            false => None,
+        };
+        self.expect(Token::Punct(';'))?;
+        Ok(Function {
+            name,
+            docs,
+            params,
+            result,
+        })
+    }
+
+    /// Parses a WIT type.
+    fn ty(&mut self) -> Result<WitType, String> {
+        let ty = match self.ident()?.as_str() {
+            "bool" => WitType::Bool,
+            "s8" => WitType::S8,
+            "u8" => WitType::U8,
+            "s16" => WitType::S16,
+            "u16" => WitType::U16,
+            "s32" => WitType::S32,
+            "u32" => WitType::U32,
+            "s64" => WitType::S64,
+            "u64" => WitType::U64,
+            "f32" | "float32" => WitType::F32,
+            "f64" | "float64" => WitType::F64,
+            "char" => WitType::Char,
+            "string" => WitType::String,
+            "list" => {
+                self.expect(Token::Punct('<'))?;
+                let elem = self.ident()?;
+                self.expect(Token::Punct('>'))?;
+                if elem != "u8" {
+                    return Err(self.error(format!("unsupported type: `list<{elem}>`")));
+                }
+                WitType::ListU8
+            }
+            ty => return Err(self.error(format!("unsupported type: `{ty}`"))),
+        };
+        Ok(ty)
+    }
+}
+
+/// Parses the WIT `source` into a [`Document`].
+///
+/// # Errors
+///
+/// If `source` is not valid or uses WIT features unsupported by [`bindgen!`](crate::bindgen!).
+pub fn parse(source: &str) -> Result<Document, String> {
+    let mut tokens = tokenize(source)?;
+    tokens.reverse();
+    Parser { tokens, line: 1 }.document()
+}
+
+impl WitType {
+    /// Returns the Rust type of a parameter of the [`WitType`].
+    fn param_type(self) -> TokenStream {
+        match self {
+            Self::String => quote!(&str),
+            Self::ListU8 => quote!(&[u8]),
+            _ => self.result_type(),
+        }
+    }
+
+    /// Returns the Rust type of a result of the [`WitType`].
+    fn result_type(self) -> TokenStream {
+        match self {
+            Self::Bool => quote!(bool),
+            Self::S8 => quote!(i8),
+            Self::U8 => quote!(u8),
+            Self::S16 => quote!(i16),
+            Self::U16 => quote!(u16),
+            Self::S32 => quote!(i32),
+            Self::U32 => quote!(u32),
+            Self::S64 => quote!(i64),
+            Self::U64 => quote!(u64),
+            Self::F32 => quote!(f32),
+            Self::F64 => quote!(f64),
+            Self::Char => quote!(char),
+            Self::String => quote!(String),
+            Self::ListU8 => quote!(Vec<u8>),
+        }
+    }
+
+    /// Returns `true` if the [`WitType`] is passed via pointer and length in linear memory.
+    fn is_indirect(self) -> bool {
+        matches!(self, Self::String | Self::ListU8)
+    }
+
+    /// Returns the core Wasm type of the [`WitType`] if it is passed directly.
+    fn core_type(self) -> TokenStream {
+        match self {
+            Self::S64 | Self::U64 => quote!(i64),
+            Self::F32 => quote!(f32),
+            Self::F64 => quote!(f64),
+            _ => quote!(i32),
+        }
+    }
+
+    /// Returns the expression lifting the core Wasm value `value` to the Rust type.
+    ///
+    /// Returns `None` if the core Wasm value already is of the Rust type.
+    fn lift(self, value: &Ident) -> Option<TokenStream> {
+        let lift = match self {
+            Self::Bool => quote!(#value != 0),
+            Self::S8 => quote!(#value as i8),
+            Self::U8 => quote!(#value as u8),
+            Self::S16 => quote!(#value as i16),
+            Self::U16 => quote!(#value as u16),
+            Self::U32 => quote!(#value as u32),
+            Self::U64 => quote!(#value as u64),
+            Self::Char => quote! {
+                ::core::primitive::char::from_u32(#value as u32)
+                    .ok_or_else(|| ::wasmi::Error::new("invalid `char` parameter"))?
+            },
+            _ => return None,
+        };
+        Some(lift)
+    }
+
+    /// Returns the expression lowering the Rust value `value` to its core Wasm type.
+    fn lower(self, value: &Ident) -> TokenStream {
+        match self {
+            Self::Bool | Self::S8 | Self::U8 | Self::S16 | Self::U16 => {
+                quote!(::core::primitive::i32::from(#value))
+            }
+            Self::U32 => quote!(#value as i32),
+            Self::U64 => quote!(#value as i64),
+            Self::Char => quote!(::core::primitive::u32::from(#value) as i32),
+            _ => quote!(#value),
+        }
+    }
+}
+
+/// Converts the kebab-case WIT `name` into a Rust identifier using `case`.
+fn ident(name: &str, case: fn(&str) -> String) -> Ident {
+    let name = case(name.trim_start_matches('%'));
+    syn::parse_str::<Ident>(&name).unwrap_or_else(|_| Ident::new_raw(&name, Span::call_site()))
+}
+
+/// Converts the kebab-case `name` to `snake_case`.
+fn snake_case(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+/// Converts the kebab-case `name` to `UpperCamelCase`.
+fn upper_camel_case(name: &str) -> String {
+    name.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Returns the `#[doc]` attributes for `docs`.
+fn doc_attrs(docs: &[String]) -> TokenStream {
+    quote! { #( #[doc = #docs] )* }
+}
+
+impl Document {
+    /// Expands the host bindings of all interfaces of the [`Document`].
+    pub fn expand(&self) -> TokenStream {
+        let interfaces = self
+            .interfaces
+            .iter()
+            .map(|interface| interface.expand(&self.module_name(&interface.name)));
+        quote! { #( #interfaces )* }
+    }
+
+    /// Returns the module name under which the `interface` is imported by Wasm guests.
+    ///
+    /// # Note
+    ///
+    /// The version of the package, if any, is appended to the interface name,
+    /// e.g. `example:host/logging@1.0.0` for the `logging` interface.
+    fn module_name(&self, interface: &str) -> String {
+        let Some(package) = &self.package else {
+            return interface.to_string();
+        };
+        match package.split_once('@') {
+            Some((package, version)) => format!("{package}/{interface}@{version}"),
+            None => format!("{package}/{interface}"),
+        }
+    }
+}
+
+impl Interface {
+    /// Expands the host trait of the [`Interface`] imported from `module`.
+    fn expand(&self, module: &str) -> TokenStream {
+        let trait_ident = ident(&self.name, upper_camel_case);
+        let docs = doc_attrs(&self.docs);
+        let methods = self.funcs.iter().map(Function::expand_method);
+        let definitions = self
+            .funcs
+            .iter()
+            .map(|func| func.expand_definition(&trait_ident));
+        let add_to_linker_doc = format!(
+            "Defines all functions of the `{}` interface in the `linker` under `\"{module}\"`.",
+            self.name
+        );
+        quote! {
+            #docs
+            pub trait #trait_ident {
+                #( #methods )*
+
+                #[doc = #add_to_linker_doc]
+                ///
+                /// The `get` function projects the `T` data of the `Store` to the trait implementation.
+                ///
+                /// # Errors
+                ///
+                /// If there already is a definition for any of the functions in the `linker`.
+                fn add_to_linker<T>(
+                    linker: &mut ::wasmi::Linker<T>,
+                    get: fn(&mut T) -> &mut Self,
+                ) -> ::core::result::Result<(), ::wasmi::errors::LinkerError>
+                where
+                    Self: ::core::marker::Sized + 'static,
+                    T: 'static,
+                {
+                    let module = #module;
+                    #( #definitions )*
+                    ::core::result::Result::Ok(())
+                }
+            }
+        }
+    }
+}
+
+impl Function {
+    /// Expands the trait method declaration of the [`Function`].
+    fn expand_method(&self) -> TokenStream {
+        let docs = doc_attrs(&self.docs);
+        let method = ident(&self.name, snake_case);
+        let params = self.params.iter().map(|(name, ty)| {
+            let name = ident(name, snake_case);
+            let ty = ty.param_type();
+            quote!(#name: #ty)
+        });
+        let result = self.result.map(|ty| {
+            let ty = ty.result_type();
+            quote!(-> #ty)
+        });
+        quote! {
+            #docs
+            fn #method(&mut self, #( #params ),*) #result;
+        }
+    }
+
+    /// Expands the [`Linker`] definition of the [`Function`] for the trait `trait_ident`.
+    ///
+    /// [`Linker`]: https://docs.rs/wasmi/latest/wasmi/struct.Linker.html
+    fn expand_definition(&self, trait_ident: &Ident) -> TokenStream {
+        let name = &self.name;
+        let method = ident(&self.name, snake_case);
+        let mut core_params = Vec::new();
+        let mut lifts = Vec::new();
+        let mut args = Vec::new();
+        for (n, (_, ty)) in self.params.iter().enumerate() {
+            let arg = format_ident!("arg{n}");
+            if ty.is_indirect() {
+                let (ptr, len) = (format_ident!("arg{n}_ptr"), format_ident!("arg{n}_len"));
+                core_params.push(quote!(#ptr: i32));
+                core_params.push(quote!(#len: i32));
+                let bytes = quote! {
+                    bytes
+                        .get(#ptr as u32 as usize..)
+                        .and_then(|bytes| bytes.get(..#len as u32 as usize))
+                        .ok_or(::wasmi::errors::MemoryError::OutOfBoundsAccess)?
+                };
+                lifts.push(match ty {
+                    WitType::String => quote! {
+                        let #arg = ::core::str::from_utf8(#bytes)
+                            .map_err(::wasmi::errors::MemoryError::InvalidUtf8)?;
+                    },
+                    _ => quote! { let #arg = #bytes; },
+                });
+            } else {
+                let core_ty = ty.core_type();
+                core_params.push(quote!(#arg: #core_ty));
+                if let Some(lift) = ty.lift(&arg) {
+                    lifts.push(quote! { let #arg = #lift; });
+                }
+            }
+            args.push(arg);
+        }
+        let result = format_ident!("result");
+        let indirect_result = self.result.is_some_and(WitType::is_indirect);
+        let uses_memory = indirect_result || self.params.iter().any(|(_, ty)| ty.is_indirect());
+        if indirect_result {
+            core_params.push(quote!(ret_ptr: i32));
+        }
+        let binding = self.result.map(|_| quote!(let #result =));
+        let call = if uses_memory {
+            quote! {
+                let memory = caller
+                    .get_export("memory")
+                    .and_then(::wasmi::Extern::into_memory)
+                    .ok_or_else(|| ::wasmi::Error::new("missing exported linear memory: `memory`"))?;
+                #binding {
+                    let (bytes, data) = memory.data_and_store_mut(&mut caller);
+                    let bytes: &[u8] = bytes;
+                    #( #lifts )*
+                    <Self as #trait_ident>::#method(get(data), #( #args ),*)
+                };
+            }
+        } else {
+            quote! {
+                #( #lifts )*
+                #binding <Self as #trait_ident>::#method(get(caller.data_mut()), #( #args ),*);
+            }
+        };
+        let (core_result, ret) = match self.result {
+            None => (quote!(()), quote!(::core::result::Result::Ok(()))),
+            Some(ty) if ty.is_indirect() => (
+                quote!(()),
+                quote! {
+                    let bytes: &[u8] = ::core::convert::AsRef::as_ref(&#result);
+                    let len = i32::try_from(bytes.len())
+                        .map_err(|_| ::wasmi::errors::MemoryError::GuestAllocationFailed)?;
+                    let ptr = caller
+                        .get_typed_func::<(i32, i32, i32, i32), i32>("cabi_realloc")?
+                        .call(&mut caller, (0, 0, 1, len))?;
+                    memory.write64(&mut caller, u64::from(ptr as u32), bytes)?;
+                    memory.write_scalar(&mut caller, u64::from(ret_ptr as u32), ptr)?;
+                    memory.write_scalar(&mut caller, u64::from(ret_ptr as u32) + 4, len)?;
+                    ::core::result::Result::Ok(())
+                },
+            ),
+            Some(ty) => {
+                let lower = ty.lower(&result);
+                (ty.core_type(), quote!(::core::result::Result::Ok(#lower)))
+            }
+        };
+        quote! {
+            linker.func_wrap(
+                module,
+                #name,
+                move |
+                    mut caller: ::wasmi::Caller<'_, T>,
+                    #( #core_params ),*
+                | -> ::core::result::Result<#core_result, ::wasmi::Error> {
+                    #call
+                    #ret
+                },
+            )?;
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Deterministic clocks and randomness for reproducible WASI guest executions.
+//!
+//! By default the WASI context reads the host wall-clock and draws randomness from the
+//! operating system so that two runs of the same guest may observe different values.
+//! Use [`deterministic_ctx`] to create a [`WasiCtx`] whose clocks and random number
+//! generator are fully determined by its configuration instead, e.g. for testing or
+//! consensus use cases.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::{Duration, UNIX_EPOCH};
+//! use wasmi_wasi::deterministic::{deterministic_ctx, DeterministicClock};
+//!
+//! let clock = DeterministicClock::stepped(UNIX_EPOCH, Duration::from_millis(1));
+//! let wasi = deterministic_ctx(clock, 42);
+//! wasi.push_arg("guest.wasm").unwrap();
+//! ```
+
+use crate::WasiCtx;
+use cap_rand::{rngs::StdRng, SeedableRng};
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::{Duration, Instant, SystemTime},
+};
+use wasi_common::{
+    clocks::{WasiClocks, WasiMonotonicClock, WasiSystemClock},
+    sync::sched_ctx,
+    table::Table,
+};
+
+/// A clock that starts at a fixed point in time and advances by a fixed step on every reading.
+///
+/// The same [`DeterministicClock`] serves as both the realtime and the monotonic WASI clock.
+#[derive(Debug)]
+pub struct DeterministicClock {
+    /// The realtime reported by the first reading.
+    start: SystemTime,
+    /// The anchor for the monotonic readings.
+    anchor: Instant,
+    /// The duration the clock advances on every reading.
+    step: Duration,
+    /// The number of readings so far.
+    ticks: AtomicU32,
+}
+
+impl Clone for DeterministicClock {
+    fn clone(&self) -> Self {
+        Self {
+            start: self.start,
+            anchor: self.anchor,
+            step: self.step,
+            ticks: AtomicU32::new(self.ticks.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl DeterministicClock {
+    /// Creates a [`DeterministicClock`] that always reports the `start` time.
+    pub fn fixed(start: SystemTime) -> Self {
+        Self::stepped(start, Duration::ZERO)
+    }
+
+    /// Creates a [`DeterministicClock`] starting at `start` that advances by `step` on every reading.
+    pub fn stepped(start: SystemTime, step: Duration) -> Self {
+        Self {
+            start,
+            anchor: Instant::now(),
+            step,
+            ticks: AtomicU32::new(0),
+        }
+    }
+
+    /// Returns the elapsed time since `start` and advances the clock.
+    fn tick(&self) -> Duration {
+        let ticks = self.ticks.fetch_add(1, Ordering::Relaxed);
+        self.step.saturating_mul(ticks)
+    }
+}
+
+impl WasiSystemClock for DeterministicClock {
+    fn resolution(&self) -> Duration {
+        Duration::from_nanos(1)
+    }
+
+    fn now(&self, _precision: Duration) -> cap_std::time::SystemTime {
+        cap_std::time::SystemTime::from_std(self.start + self.tick())
+    }
+}
+
+impl WasiMonotonicClock for DeterministicClock {
+    fn resolution(&self) -> Duration {
+        Duration::from_nanos(1)
+    }
+
+    fn now(&self, _precision: Duration) -> cap_std::time::Instant {
+        cap_std::time::Instant::from_std(self.anchor + self.tick())
+    }
+}
+
+/// Creates a new [`WasiCtx`] with deterministic clocks and randomness.
+///
+/// - The realtime and monotonic clocks are driven by independent copies of `clock`.
+/// - The random number generator is seeded with `seed`.
+///
+/// # Note
+///
+/// The returned [`WasiCtx`] has no arguments, environment variables, stdio or
+/// preopened directories. Use the methods of [`WasiCtx`] to configure those.
+pub fn deterministic_ctx(clock: DeterministicClock, seed: u64) -> WasiCtx {
+    let clocks = WasiClocks::new()
+        .with_system(clock.clone())
+        .with_monotonic(clock);
+    WasiCtx::new(
+        Box::new(StdRng::seed_from_u64(seed)),
+        clocks,
+        sched_ctx(),
+        Table::new(),
+    )
+}
@@ -1,8 +1,48 @@
 //! This crate provides support for WASI `preview1` for the Wasmi interpreter.
 //!
 //! Use [`add_to_linker`] to add all supported WASI definitions to the Wasmi linker.
+//!
+//! # Sockets
+//!
+//! The WASI `sock_accept`, `sock_recv`, `sock_send` and `sock_shutdown` functions
+//! operate on sockets that the host preopened for the guest since WASI `preview1`
+//! provides no means to create sockets from within the guest.
+//! Use [`WasiCtxBuilder::preopened_socket`] to preopen a listening [`TcpListener`]
+//! under a file descriptor that is known to the guest, e.g. for networked guests
+//! such as HTTP servers compiled to `wasm32-wasi`.
+//!
+//! ```no_run
+//! use wasmi_wasi::{TcpListener, WasiCtxBuilder};
+//!
+//! let listener = std::net::TcpListener::bind("127.0.0.1:8080").unwrap();
+//! let wasi = WasiCtxBuilder::new()
+//!     .inherit_stdio()
+//!     .preopened_socket(3, TcpListener::from_std(listener))
+//!     .unwrap()
+//!     .build();
+//! ```
+//!
+//! # Virtual Filesystem
+//!
+//! Use a [`vfs::VirtualDir`] as preopened directory to provide guests with an
+//! in-memory filesystem that has no access to the host disk.
+//!
+//! # Determinism
+//!
+//! Use [`deterministic::deterministic_ctx`] to create a WASI context with fixed or
+//! stepped clocks and a seeded random number generator for reproducible guest runs.
+//!
+//! # wasi-nn
+//!
+//! With the `nn` crate feature enabled the [`nn`] module provides the
+//! `wasi_ephemeral_nn` API for machine learning inference backed by a
+//! user provided [`nn::Backend`].
 
+pub mod deterministic;
+#[cfg(feature = "nn")]
+pub mod nn;
 pub mod sync;
+pub mod vfs;
 
 pub use wasi_common::{Error, WasiCtx, WasiDir, WasiFile};
 pub use wiggle::GuestMemory as WasmiGuestMemory;
@@ -0,0 +1,477 @@
+//! Host implementation of the [wasi-nn] API for machine learning inference.
+//!
+//! The actual inference is performed by a user provided [`Backend`] so that
+//! embedders can plug in whatever framework is available on the target device.
+//!
+//! Use [`add_to_linker`] to add the `wasi_ephemeral_nn` definitions to the Wasmi linker.
+//!
+//! [wasi-nn]: https://github.com/WebAssembly/wasi-nn
+
+use core::fmt::{self, Display};
+use wasmi::{Caller, Error, Extern, Linker};
+
+/// The Wasm module name under which the wasi-nn functions are defined.
+const MODULE: &str = "wasi_ephemeral_nn";
+
+/// The size of a `tensor` record in the guest linear memory.
+const TENSOR_SIZE: u32 = 20;
+
+/// Errors that a wasi-nn [`Backend`] may report to the guest.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum BackendError {
+    /// Caller module passed an invalid argument.
+    InvalidArgument = 1,
+    /// Invalid encoding.
+    InvalidEncoding = 2,
+    /// The operation failed due to missing memory.
+    MissingMemory = 3,
+    /// Device or resource busy.
+    Busy = 4,
+    /// Runtime error.
+    RuntimeError = 5,
+    /// Unsupported operation.
+    UnsupportedOperation = 6,
+    /// Graph is too large.
+    TooLarge = 7,
+    /// Graph not found.
+    NotFound = 8,
+}
+
+impl std::error::Error for BackendError {}
+
+impl Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidArgument => write!(f, "invalid argument"),
+            Self::InvalidEncoding => write!(f, "invalid encoding"),
+            Self::MissingMemory => write!(f, "missing memory"),
+            Self::Busy => write!(f, "device or resource busy"),
+            Self::RuntimeError => write!(f, "runtime error"),
+            Self::UnsupportedOperation => write!(f, "unsupported operation"),
+            Self::TooLarge => write!(f, "graph is too large"),
+            Self::NotFound => write!(f, "graph not found"),
+        }
+    }
+}
+
+/// The encoding of a graph passed to [`Backend::load`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GraphEncoding {
+    Openvino,
+    Onnx,
+    Tensorflow,
+    Pytorch,
+    TensorflowLite,
+    Ggml,
+    Autodetect,
+}
+
+impl TryFrom<i32> for GraphEncoding {
+    type Error = BackendError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        let encoding = match value {
+            0 => Self::Openvino,
+            1 => Self::Onnx,
+            2 => Self::Tensorflow,
+            3 => Self::Pytorch,
+            4 => Self::TensorflowLite,
+            5 => Self::Ggml,
+            6 => Self::Autodetect,
+            _ => return Err(BackendError::InvalidEncoding),
+        };
+        Ok(encoding)
+    }
+}
+
+/// The device on which a graph passed to [`Backend::load`] is executed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExecutionTarget {
+    Cpu,
+    Gpu,
+    Tpu,
+}
+
+impl TryFrom<i32> for ExecutionTarget {
+    type Error = BackendError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        let target = match value {
+            0 => Self::Cpu,
+            1 => Self::Gpu,
+            2 => Self::Tpu,
+            _ => return Err(BackendError::InvalidArgument),
+        };
+        Ok(target)
+    }
+}
+
+/// The element type of a [`Tensor`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TensorType {
+    F16,
+    F32,
+    F64,
+    U8,
+    I32,
+    I64,
+}
+
+impl TryFrom<u8> for TensorType {
+    type Error = BackendError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        let ty = match value {
+            0 => Self::F16,
+            1 => Self::F32,
+            2 => Self::F64,
+            3 => Self::U8,
+            4 => Self::I32,
+            5 => Self::I64,
+            _ => return Err(BackendError::InvalidArgument),
+        };
+        Ok(ty)
+    }
+}
+
+/// A tensor passed by the guest as input of an inference.
+#[derive(Debug)]
+pub struct Tensor<'a> {
+    /// The dimensions of the tensor.
+    pub dimensions: Vec<u32>,
+    /// The element type of the tensor.
+    pub ty: TensorType,
+    /// The little-endian encoded elements of the tensor.
+    pub data: &'a [u8],
+}
+
+/// A pluggable wasi-nn backend performing the actual inference.
+pub trait Backend: Send + Sync {
+    /// Loads a graph from its serialized `builders` parts.
+    ///
+    /// # Errors
+    ///
+    /// If the backend fails to load the graph.
+    fn load(
+        &mut self,
+        builders: &[&[u8]],
+        encoding: GraphEncoding,
+        target: ExecutionTarget,
+    ) -> Result<Box<dyn Graph>, BackendError>;
+
+    /// Loads a graph that is known to the backend by `name`.
+    ///
+    /// By default no named graphs are known to the backend.
+    ///
+    /// # Errors
+    ///
+    /// If the backend does not know a graph named `name` or fails to load it.
+    fn load_by_name(&mut self, _name: &str) -> Result<Box<dyn Graph>, BackendError> {
+        Err(BackendError::NotFound)
+    }
+}
+
+/// A graph loaded by a [`Backend`].
+pub trait Graph: Send + Sync {
+    /// Creates a new [`ExecutionContext`] to perform inferences with the graph.
+    ///
+    /// # Errors
+    ///
+    /// If the backend fails to create the execution context.
+    fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext>, BackendError>;
+}
+
+/// The state of a single inference of a [`Graph`].
+pub trait ExecutionContext: Send + Sync {
+    /// Sets the input `tensor` at `index` of the inference.
+    ///
+    /// # Errors
+    ///
+    /// If `index` or `tensor` are invalid for the graph.
+    fn set_input(&mut self, index: u32, tensor: &Tensor) -> Result<(), BackendError>;
+
+    /// Performs the inference.
+    ///
+    /// # Errors
+    ///
+    /// If the inference fails.
+    fn compute(&mut self) -> Result<(), BackendError>;
+
+    /// Writes the output tensor at `index` into `buffer` and returns the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// If `index` is invalid or `buffer` is too small to hold the output tensor.
+    fn get_output(&mut self, index: u32, buffer: &mut [u8]) -> Result<u32, BackendError>;
+}
+
+/// The wasi-nn state of a [`Store`](wasmi::Store).
+pub struct WasiNnCtx {
+    /// The backend performing the inference.
+    backend: Box<dyn Backend>,
+    /// The graphs loaded by the guest, indexed by their handle.
+    graphs: Vec<Box<dyn Graph>>,
+    /// The execution contexts created by the guest, indexed by their handle.
+    contexts: Vec<Box<dyn ExecutionContext>>,
+}
+
+impl fmt::Debug for WasiNnCtx {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasiNnCtx")
+            .field("graphs", &self.graphs.len())
+            .field("contexts", &self.contexts.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl WasiNnCtx {
+    /// Creates a new [`WasiNnCtx`] performing inference via `backend`.
+    pub fn new(backend: impl Backend + 'static) -> Self {
+        Self {
+            backend: Box::new(backend),
+            graphs: Vec::new(),
+            contexts: Vec::new(),
+        }
+    }
+
+    /// Registers `graph` and returns its handle.
+    fn push_graph(&mut self, graph: Box<dyn Graph>) -> Result<u32, BackendError> {
+        let handle = u32::try_from(self.graphs.len()).map_err(|_| BackendError::TooLarge)?;
+        self.graphs.push(graph);
+        Ok(handle)
+    }
+
+    /// Returns the graph with the `handle`.
+    fn graph(&self, handle: i32) -> Result<&dyn Graph, BackendError> {
+        let index = usize::try_from(handle).map_err(|_| BackendError::InvalidArgument)?;
+        self.graphs
+            .get(index)
+            .map(|graph| &**graph)
+            .ok_or(BackendError::InvalidArgument)
+    }
+
+    /// Registers `context` and returns its handle.
+    fn push_context(&mut self, context: Box<dyn ExecutionContext>) -> Result<u32, BackendError> {
+        let handle = u32::try_from(self.contexts.len()).map_err(|_| BackendError::TooLarge)?;
+        self.contexts.push(context);
+        Ok(handle)
+    }
+
+    /// Returns the execution context with the `handle`.
+    fn context(&mut self, handle: i32) -> Result<&mut dyn ExecutionContext, BackendError> {
+        let index = usize::try_from(handle).map_err(|_| BackendError::InvalidArgument)?;
+        match self.contexts.get_mut(index) {
+            Some(context) => Ok(&mut **context),
+            None => Err(BackendError::InvalidArgument),
+        }
+    }
+}
+
+/// Adds the wasi-nn API to the Wasmi [`Linker`].
+///
+/// The `wasi_nn_ctx` function projects the `T` data of the [`Store`](wasmi::Store)
+/// to its [`WasiNnCtx`].
+///
+/// # Note
+///
+/// The wasi-nn functions access the linear memory exported as `"memory"` by the guest.
+///
+/// # Errors
+///
+/// If there already are wasi-nn definitions in the `linker`.
+pub fn add_to_linker<T: 'static>(
+    linker: &mut Linker<T>,
+    wasi_nn_ctx: impl Fn(&mut T) -> &mut WasiNnCtx + Send + Sync + Copy + 'static,
+) -> Result<(), Error> {
+    linker.func_wrap(
+        MODULE,
+        "load",
+        move |mut caller: Caller<'_, T>,
+              builder: i32,
+              builder_len: i32,
+              encoding: i32,
+              target: i32,
+              graph_out: i32|
+              -> Result<i32, Error> {
+            with_memory(&mut caller, wasi_nn_ctx, |memory, ctx| {
+                let encoding = GraphEncoding::try_from(encoding)?;
+                let target = ExecutionTarget::try_from(target)?;
+                let builders = (0..builder_len)
+                    .map(|n| {
+                        let offset = builder.wrapping_add(n.wrapping_mul(8));
+                        let ptr = read_u32(memory, offset)?;
+                        let len = read_u32(memory, offset.wrapping_add(4))?;
+                        slice(memory, ptr, len)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let graph = ctx.backend.load(&builders, encoding, target)?;
+                let handle = ctx.push_graph(graph)?;
+                write_u32(memory, graph_out, handle)
+            })
+        },
+    )?;
+    linker.func_wrap(
+        MODULE,
+        "load_by_name",
+        move |mut caller: Caller<'_, T>,
+              name: i32,
+              name_len: i32,
+              graph_out: i32|
+              -> Result<i32, Error> {
+            with_memory(&mut caller, wasi_nn_ctx, |memory, ctx| {
+                let name = slice(memory, name as u32, name_len as u32)?;
+                let name = core::str::from_utf8(name).map_err(|_| BackendError::InvalidArgument)?;
+                let graph = ctx.backend.load_by_name(name)?;
+                let handle = ctx.push_graph(graph)?;
+                write_u32(memory, graph_out, handle)
+            })
+        },
+    )?;
+    linker.func_wrap(
+        MODULE,
+        "init_execution_context",
+        move |mut caller: Caller<'_, T>, graph: i32, context_out: i32| -> Result<i32, Error> {
+            with_memory(&mut caller, wasi_nn_ctx, |memory, ctx| {
+                let context = ctx.graph(graph)?.init_execution_context()?;
+                let handle = ctx.push_context(context)?;
+                write_u32(memory, context_out, handle)
+            })
+        },
+    )?;
+    linker.func_wrap(
+        MODULE,
+        "set_input",
+        move |mut caller: Caller<'_, T>,
+              context: i32,
+              index: i32,
+              tensor: i32|
+              -> Result<i32, Error> {
+            with_memory(&mut caller, wasi_nn_ctx, |memory, ctx| {
+                let tensor = read_tensor(memory, tensor)?;
+                ctx.context(context)?.set_input(index as u32, &tensor)
+            })
+        },
+    )?;
+    linker.func_wrap(
+        MODULE,
+        "compute",
+        move |mut caller: Caller<'_, T>, context: i32| -> Result<i32, Error> {
+            with_memory(&mut caller, wasi_nn_ctx, |_memory, ctx| {
+                ctx.context(context)?.compute()
+            })
+        },
+    )?;
+    linker.func_wrap(
+        MODULE,
+        "get_output",
+        move |mut caller: Caller<'_, T>,
+              context: i32,
+              index: i32,
+              out_buffer: i32,
+              out_buffer_max_size: i32,
+              bytes_written_out: i32|
+              -> Result<i32, Error> {
+            with_memory(&mut caller, wasi_nn_ctx, |memory, ctx| {
+                let buffer = slice_mut(memory, out_buffer as u32, out_buffer_max_size as u32)?;
+                let bytes_written = ctx.context(context)?.get_output(index as u32, buffer)?;
+                write_u32(memory, bytes_written_out, bytes_written)
+            })
+        },
+    )?;
+    Ok(())
+}
+
+/// Calls `f` with the exported linear memory of the guest and the [`WasiNnCtx`].
+///
+/// Returns the wasi-nn error code of the result of `f`.
+///
+/// # Errors
+///
+/// If the guest does not export its linear memory as `"memory"`.
+fn with_memory<T>(
+    caller: &mut Caller<'_, T>,
+    wasi_nn_ctx: impl Fn(&mut T) -> &mut WasiNnCtx,
+    f: impl FnOnce(&mut [u8], &mut WasiNnCtx) -> Result<(), BackendError>,
+) -> Result<i32, Error> {
+    let Some(Extern::Memory(memory)) = caller.get_export("memory") else {
+        return Err(Error::new("missing required wasi-nn memory export"));
+    };
+    let (memory, data) = memory.data_and_store_mut(caller);
+    match f(memory, wasi_nn_ctx(data)) {
+        Ok(()) => Ok(0),
+        Err(error) => Ok(error as i32),
+    }
+}
+
+/// Returns the `len` bytes at `ptr` of the linear `memory`.
+fn slice(memory: &[u8], ptr: u32, len: u32) -> Result<&[u8], BackendError> {
+    let start = ptr as usize;
+    let end = start
+        .checked_add(len as usize)
+        .ok_or(BackendError::InvalidArgument)?;
+    memory.get(start..end).ok_or(BackendError::InvalidArgument)
+}
+
+/// Returns the `len` bytes at `ptr` of the linear `memory`.
+fn slice_mut(memory: &mut [u8], ptr: u32, len: u32) -> Result<&mut [u8], BackendError> {
+    let start = ptr as usize;
+    let end = start
+        .checked_add(len as usize)
+        .ok_or(BackendError::InvalidArgument)?;
+    memory
+        .get_mut(start..end)
+        .ok_or(BackendError::InvalidArgument)
+}
+
+/// Reads the little-endian `u32` at `ptr` of the linear `memory`.
+fn read_u32(memory: &[u8], ptr: i32) -> Result<u32, BackendError> {
+    let bytes = slice(memory, ptr as u32, 4)?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Writes `value` as little-endian `u32` at `ptr` of the linear `memory`.
+fn write_u32(memory: &mut [u8], ptr: i32, value: u32) -> Result<(), BackendError> {
+    slice_mut(memory, ptr as u32, 4)?.copy_from_slice(&value.to_le_bytes());
+    Ok(())
+}
+
+/// Reads the `tensor` record at `ptr` of the linear `memory`.
+///
+/// # Note
+///
+/// The `tensor` record is laid out as follows:
+///
+/// - offset 0: pointer to the `u32` dimensions
+/// - offset 4: number of dimensions
+/// - offset 8: the `u8` tensor type
+/// - offset 12: pointer to the tensor data
+/// - offset 16: length of the tensor data in bytes
+fn read_tensor(memory: &[u8], ptr: i32) -> Result<Tensor<'_>, BackendError> {
+    let record = slice(memory, ptr as u32, TENSOR_SIZE)?;
+    let field = |offset: usize| {
+        u32::from_le_bytes([
+            record[offset],
+            record[offset + 1],
+            record[offset + 2],
+            record[offset + 3],
+        ])
+    };
+    let dimensions = slice(
+        memory,
+        field(0),
+        field(4)
+            .checked_mul(4)
+            .ok_or(BackendError::InvalidArgument)?,
+    )?
+    .chunks_exact(4)
+    .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    .collect();
+    let ty = TensorType::try_from(record[8])?;
+    let data = slice(memory, field(12), field(16))?;
+    Ok(Tensor {
+        dimensions,
+        ty,
+        data,
+    })
+}
@@ -0,0 +1,539 @@
+//! A virtual in-memory filesystem for WASI guests.
+//!
+//! A [`VirtualDir`] can be preopened for a guest instead of a host directory so that
+//! untrusted guests can be given a filesystem without any access to the real disk.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use wasmi_wasi::{vfs::VirtualDir, WasiCtxBuilder};
+//!
+//! let assets = VirtualDir::new()
+//!     .with_file("index.html", "<h1>Hello, World!</h1>")
+//!     .read_only();
+//! let root = VirtualDir::new().with_dir("srv/assets", assets);
+//! let wasi = WasiCtxBuilder::new().build();
+//! wasi.push_preopened_dir(Box::new(root), "/").unwrap();
+//! ```
+
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    io::{IoSlice, IoSliceMut, SeekFrom},
+    sync::{Arc, Mutex, RwLock},
+};
+use wasi_common::{
+    dir::{OpenResult, ReaddirCursor, ReaddirEntity, WasiDir},
+    file::{FdFlags, FileType, Filestat, OFlags, WasiFile},
+    Error,
+    ErrorExt,
+};
+
+/// A node of the virtual filesystem.
+#[derive(Debug, Clone)]
+enum Node {
+    File(VirtualFileData),
+    Dir(VirtualDir),
+}
+
+impl Node {
+    /// Returns the [`Filestat`] of the [`Node`].
+    fn filestat(&self) -> Filestat {
+        let (filetype, inode, size) = match self {
+            Self::File(file) => (FileType::RegularFile, file.inode(), file.len()),
+            Self::Dir(dir) => (FileType::Directory, dir.inode(), 0),
+        };
+        Filestat {
+            device_id: 0,
+            inode,
+            filetype,
+            nlink: 1,
+            size,
+            atim: None,
+            mtim: None,
+            ctim: None,
+        }
+    }
+
+    /// Returns the [`Node`] as seen through a parent that may be read-only.
+    fn inherit_read_only(self, read_only: bool) -> Self {
+        match self {
+            Self::File(mut file) => {
+                file.read_only |= read_only;
+                Self::File(file)
+            }
+            Self::Dir(mut dir) => {
+                dir.read_only |= read_only;
+                Self::Dir(dir)
+            }
+        }
+    }
+}
+
+/// The shared contents of a file of the virtual filesystem.
+#[derive(Debug, Clone, Default)]
+struct VirtualFileData {
+    /// The bytes of the file.
+    contents: Arc<RwLock<Vec<u8>>>,
+    /// Whether the file may be modified through this handle.
+    read_only: bool,
+}
+
+impl VirtualFileData {
+    /// Returns a unique inode number for the file.
+    fn inode(&self) -> u64 {
+        Arc::as_ptr(&self.contents) as usize as u64
+    }
+
+    /// Returns the length of the file in bytes.
+    fn len(&self) -> u64 {
+        self.contents.read().unwrap().len() as u64
+    }
+}
+
+/// A directory of the virtual in-memory filesystem.
+///
+/// Cloning a [`VirtualDir`] yields another handle to the same directory.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualDir {
+    /// The entries of the directory.
+    entries: Arc<RwLock<BTreeMap<String, Node>>>,
+    /// Whether the directory and its descendants may be modified through this handle.
+    read_only: bool,
+}
+
+impl VirtualDir {
+    /// Creates a new empty and writable [`VirtualDir`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes the [`VirtualDir`] and all of its descendants read-only for the guest.
+    ///
+    /// # Note
+    ///
+    /// Other handles to the same directory that were cloned before keep their access.
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Adds a file with `contents` at `path` relative to the [`VirtualDir`].
+    ///
+    /// Missing parent directories are created.
+    ///
+    /// # Panics
+    ///
+    /// If `path` is empty or traverses a file.
+    pub fn with_file(self, path: &str, contents: impl Into<Vec<u8>>) -> Self {
+        let file = VirtualFileData {
+            contents: Arc::new(RwLock::new(contents.into())),
+            read_only: false,
+        };
+        self.insert(path, Node::File(file));
+        self
+    }
+
+    /// Mounts `dir` at `path` relative to the [`VirtualDir`].
+    ///
+    /// Missing parent directories are created.
+    /// This allows to remap directories to arbitrary guest paths.
+    ///
+    /// # Panics
+    ///
+    /// If `path` is empty or traverses a file.
+    pub fn with_dir(self, path: &str, dir: VirtualDir) -> Self {
+        self.insert(path, Node::Dir(dir));
+        self
+    }
+
+    /// Returns the contents of the file at `path` relative to the [`VirtualDir`] if any.
+    pub fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        let components = components(path).ok()?;
+        match self.lookup(&components).ok()? {
+            Node::File(file) => Some(file.contents.read().unwrap().clone()),
+            Node::Dir(_) => None,
+        }
+    }
+
+    /// Inserts `node` at `path` creating missing parent directories.
+    fn insert(&self, path: &str, node: Node) {
+        let mut components = components(path).expect("path must not escape the directory");
+        let name = components.pop().expect("path must not be empty");
+        let mut dir = self.clone();
+        for component in components {
+            let child = dir
+                .entries
+                .write()
+                .unwrap()
+                .entry(component.into())
+                .or_insert_with(|| Node::Dir(VirtualDir::new()))
+                .clone();
+            dir = match child {
+                Node::Dir(child) => child,
+                Node::File(_) => panic!("path must not traverse file: {component}"),
+            };
+        }
+        dir.entries.write().unwrap().insert(name.into(), node);
+    }
+
+    /// Returns a unique inode number for the directory.
+    fn inode(&self) -> u64 {
+        Arc::as_ptr(&self.entries) as usize as u64
+    }
+
+    /// Returns the [`Node`] at the path `components`.
+    fn lookup(&self, components: &[&str]) -> Result<Node, Error> {
+        let mut node = Node::Dir(self.clone());
+        for component in components {
+            let Node::Dir(dir) = node else {
+                return Err(Error::not_dir());
+            };
+            node = dir
+                .entries
+                .read()
+                .unwrap()
+                .get(*component)
+                .cloned()
+                .ok_or_else(Error::not_found)?
+                .inherit_read_only(dir.read_only);
+        }
+        Ok(node)
+    }
+
+    /// Returns the parent directory of `path` and the name of its last component.
+    fn parent<'a>(&self, path: &'a str) -> Result<(VirtualDir, &'a str), Error> {
+        let mut components = components(path)?;
+        let name = components.pop().ok_or_else(Error::invalid_argument)?;
+        match self.lookup(&components)? {
+            Node::Dir(dir) => Ok((dir, name)),
+            Node::File(_) => Err(Error::not_dir()),
+        }
+    }
+
+    /// Returns the writable parent directory of `path` and the name of its last component.
+    fn parent_mut<'a>(&self, path: &'a str) -> Result<(VirtualDir, &'a str), Error> {
+        let (dir, name) = self.parent(path)?;
+        if dir.read_only {
+            return Err(Error::perm());
+        }
+        Ok((dir, name))
+    }
+}
+
+/// Returns the normalized components of the relative `path`.
+///
+/// # Errors
+///
+/// If `path` escapes the directory it is relative to.
+fn components(path: &str) -> Result<Vec<&str>, Error> {
+    let mut components = Vec::new();
+    for component in path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop().ok_or_else(Error::perm)?;
+            }
+            component => components.push(component),
+        }
+    }
+    Ok(components)
+}
+
+#[wiggle::async_trait]
+impl WasiDir for VirtualDir {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn open_file(
+        &self,
+        _symlink_follow: bool,
+        path: &str,
+        oflags: OFlags,
+        _read: bool,
+        write: bool,
+        fdflags: FdFlags,
+    ) -> Result<OpenResult, Error> {
+        if components(path)?.is_empty() {
+            if oflags.contains(OFlags::CREATE) || write {
+                return Err(Error::perm());
+            }
+            return Ok(OpenResult::Dir(Box::new(self.clone())));
+        }
+        let (dir, name) = self.parent(path)?;
+        let mut entries = dir.entries.write().unwrap();
+        let node = match entries.get(name) {
+            Some(_) if oflags.contains(OFlags::CREATE | OFlags::EXCLUSIVE) => {
+                return Err(Error::exist())
+            }
+            Some(node) => node.clone().inherit_read_only(dir.read_only),
+            None if !oflags.contains(OFlags::CREATE) => return Err(Error::not_found()),
+            None if dir.read_only => return Err(Error::perm()),
+            None if oflags.contains(OFlags::DIRECTORY) => return Err(Error::invalid_argument()),
+            None => {
+                let node = Node::File(VirtualFileData::default());
+                entries.insert(name.into(), node.clone());
+                node
+            }
+        };
+        match node {
+            Node::Dir(_) if write => Err(Error::perm()),
+            Node::Dir(dir) => Ok(OpenResult::Dir(Box::new(dir))),
+            Node::File(_) if oflags.contains(OFlags::DIRECTORY) => Err(Error::not_dir()),
+            Node::File(file) => {
+                let truncate = oflags.contains(OFlags::TRUNCATE);
+                if file.read_only && (write || truncate) {
+                    return Err(Error::perm());
+                }
+                if truncate {
+                    file.contents.write().unwrap().clear();
+                }
+                Ok(OpenResult::File(Box::new(VirtualFile::new(file, fdflags))))
+            }
+        }
+    }
+
+    async fn create_dir(&self, path: &str) -> Result<(), Error> {
+        let (dir, name) = self.parent_mut(path)?;
+        let mut entries = dir.entries.write().unwrap();
+        if entries.contains_key(name) {
+            return Err(Error::exist());
+        }
+        entries.insert(name.into(), Node::Dir(VirtualDir::new()));
+        Ok(())
+    }
+
+    async fn readdir(
+        &self,
+        cursor: ReaddirCursor,
+    ) -> Result<Box<dyn Iterator<Item = Result<ReaddirEntity, Error>> + Send>, Error> {
+        let entries = self.entries.read().unwrap();
+        let dots = [
+            (".", self.inode(), FileType::Directory),
+            ("..", 0, FileType::Directory),
+        ];
+        let children = entries.iter().map(|(name, node)| {
+            let stat = node.filestat();
+            (name.as_str(), stat.inode, stat.filetype)
+        });
+        let entities = dots
+            .into_iter()
+            .chain(children)
+            .enumerate()
+            .map(|(n, (name, inode, filetype))| {
+                Ok(ReaddirEntity {
+                    next: ReaddirCursor::from(n as u64 + 1),
+                    inode,
+                    name: name.into(),
+                    filetype,
+                })
+            })
+            .skip(u64::from(cursor) as usize)
+            .collect::<Vec<_>>();
+        Ok(Box::new(entities.into_iter()))
+    }
+
+    async fn remove_dir(&self, path: &str) -> Result<(), Error> {
+        let (dir, name) = self.parent_mut(path)?;
+        let mut entries = dir.entries.write().unwrap();
+        match entries.get(name) {
+            None => return Err(Error::not_found()),
+            Some(Node::File(_)) => return Err(Error::not_dir()),
+            Some(Node::Dir(child)) if !child.entries.read().unwrap().is_empty() => {
+                return Err(Error::exist())
+            }
+            Some(Node::Dir(_)) => {}
+        }
+        entries.remove(name);
+        Ok(())
+    }
+
+    async fn unlink_file(&self, path: &str) -> Result<(), Error> {
+        let (dir, name) = self.parent_mut(path)?;
+        let mut entries = dir.entries.write().unwrap();
+        match entries.get(name) {
+            None => return Err(Error::not_found()),
+            Some(Node::Dir(_)) => return Err(Error::perm()),
+            Some(Node::File(_)) => {}
+        }
+        entries.remove(name);
+        Ok(())
+    }
+
+    async fn get_filestat(&self) -> Result<Filestat, Error> {
+        Ok(Node::Dir(self.clone()).filestat())
+    }
+
+    async fn get_path_filestat(
+        &self,
+        path: &str,
+        _follow_symlinks: bool,
+    ) -> Result<Filestat, Error> {
+        let components = components(path)?;
+        Ok(self.lookup(&components)?.filestat())
+    }
+
+    async fn rename(
+        &self,
+        path: &str,
+        dest_dir: &dyn WasiDir,
+        dest_path: &str,
+    ) -> Result<(), Error> {
+        let dest_dir = dest_dir
+            .as_any()
+            .downcast_ref::<VirtualDir>()
+            .ok_or_else(Error::not_supported)?;
+        let (src, src_name) = self.parent_mut(path)?;
+        let (dest, dest_name) = dest_dir.parent_mut(dest_path)?;
+        let node = src
+            .entries
+            .write()
+            .unwrap()
+            .remove(src_name)
+            .ok_or_else(Error::not_found)?;
+        dest.entries.write().unwrap().insert(dest_name.into(), node);
+        Ok(())
+    }
+}
+
+/// An open file of the virtual in-memory filesystem.
+#[derive(Debug)]
+struct VirtualFile {
+    /// The shared contents of the file.
+    data: VirtualFileData,
+    /// The current read and write position within the file.
+    position: Mutex<u64>,
+    /// The flags of the file descriptor.
+    fdflags: Mutex<FdFlags>,
+}
+
+impl VirtualFile {
+    /// Creates a new [`VirtualFile`] for `data` positioned at its start.
+    fn new(data: VirtualFileData, fdflags: FdFlags) -> Self {
+        Self {
+            data,
+            position: Mutex::new(0),
+            fdflags: Mutex::new(fdflags),
+        }
+    }
+
+    /// Reads from the file at `offset` into `bufs` and returns the number of bytes read.
+    fn read_at(&self, bufs: &mut [IoSliceMut<'_>], offset: u64) -> u64 {
+        let contents = self.data.contents.read().unwrap();
+        let mut position = usize::try_from(offset).unwrap_or(usize::MAX);
+        let mut read = 0;
+        for buf in bufs {
+            let Some(src) = contents.get(position..) else {
+                break;
+            };
+            let len = buf.len().min(src.len());
+            buf[..len].copy_from_slice(&src[..len]);
+            position += len;
+            read += len as u64;
+        }
+        read
+    }
+
+    /// Writes `bufs` to the file at `offset` and returns the number of bytes written.
+    ///
+    /// Writes to the end of the file if `offset` is `None`.
+    fn write_at(&self, bufs: &[IoSlice<'_>], offset: Option<u64>) -> Result<(u64, u64), Error> {
+        if self.data.read_only {
+            return Err(Error::perm());
+        }
+        let mut contents = self.data.contents.write().unwrap();
+        let start = match offset {
+            Some(offset) => usize::try_from(offset).map_err(|_| Error::too_big())?,
+            None => contents.len(),
+        };
+        let mut position = start;
+        for buf in bufs {
+            let end = position.checked_add(buf.len()).ok_or_else(Error::too_big)?;
+            if contents.len() < end {
+                contents.resize(end, 0);
+            }
+            contents[position..end].copy_from_slice(buf);
+            position = end;
+        }
+        Ok((start as u64, (position - start) as u64))
+    }
+}
+
+#[wiggle::async_trait]
+impl WasiFile for VirtualFile {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn get_filetype(&self) -> Result<FileType, Error> {
+        Ok(FileType::RegularFile)
+    }
+
+    async fn get_fdflags(&self) -> Result<FdFlags, Error> {
+        Ok(*self.fdflags.lock().unwrap())
+    }
+
+    async fn set_fdflags(&mut self, fdflags: FdFlags) -> Result<(), Error> {
+        *self.fdflags.get_mut().unwrap() = fdflags;
+        Ok(())
+    }
+
+    async fn get_filestat(&self) -> Result<Filestat, Error> {
+        Ok(Node::File(self.data.clone()).filestat())
+    }
+
+    async fn set_filestat_size(&self, size: u64) -> Result<(), Error> {
+        if self.data.read_only {
+            return Err(Error::perm());
+        }
+        let size = usize::try_from(size).map_err(|_| Error::too_big())?;
+        self.data.contents.write().unwrap().resize(size, 0);
+        Ok(())
+    }
+
+    async fn read_vectored<'a>(&self, bufs: &mut [IoSliceMut<'a>]) -> Result<u64, Error> {
+        let mut position = self.position.lock().unwrap();
+        let read = self.read_at(bufs, *position);
+        *position += read;
+        Ok(read)
+    }
+
+    async fn read_vectored_at<'a>(
+        &self,
+        bufs: &mut [IoSliceMut<'a>],
+        offset: u64,
+    ) -> Result<u64, Error> {
+        Ok(self.read_at(bufs, offset))
+    }
+
+    async fn write_vectored<'a>(&self, bufs: &[IoSlice<'a>]) -> Result<u64, Error> {
+        let mut position = self.position.lock().unwrap();
+        let append = self.fdflags.lock().unwrap().contains(FdFlags::APPEND);
+        let offset = if append { None } else { Some(*position) };
+        let (start, written) = self.write_at(bufs, offset)?;
+        *position = start + written;
+        Ok(written)
+    }
+
+    async fn write_vectored_at<'a>(&self, bufs: &[IoSlice<'a>], offset: u64) -> Result<u64, Error> {
+        let (_, written) = self.write_at(bufs, Some(offset))?;
+        Ok(written)
+    }
+
+    async fn seek(&self, pos: SeekFrom) -> Result<u64, Error> {
+        let mut position = self.position.lock().unwrap();
+        let new_position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => position.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.data.len().checked_add_signed(offset),
+        };
+        *position = new_position.ok_or_else(Error::invalid_argument)?;
+        Ok(*position)
+    }
+
+    fn num_ready_bytes(&self) -> Result<u64, Error> {
+        let position = *self.position.lock().unwrap();
+        Ok(self.data.len().saturating_sub(position))
+    }
+}
@@ -1 +1,5 @@
+mod wasi_deterministic;
+#[cfg(feature = "nn")]
+mod wasi_nn;
+mod wasi_vfs;
 mod wasi_wat;
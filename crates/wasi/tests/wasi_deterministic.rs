@@ -0,0 +1,53 @@
+use std::time::{Duration, UNIX_EPOCH};
+use wasmi::{Engine, Linker, Module, Store};
+use wasmi_wasi::{
+    add_to_linker,
+    deterministic::{deterministic_ctx, DeterministicClock},
+    WasiCtx,
+};
+
+/// Runs the guest and returns its two realtime clock readings and random bytes.
+fn run(clock: DeterministicClock, seed: u64) -> ([u64; 2], [u8; 16]) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, include_bytes!("wat/deterministic.wat")).unwrap();
+    let mut linker = <Linker<WasiCtx>>::new(&engine);
+    add_to_linker(&mut linker, |ctx| ctx).unwrap();
+    let mut store = Store::new(&engine, deterministic_ctx(clock, seed));
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<(), i32>(&store, "run").unwrap();
+    assert_eq!(run.call(&mut store, ()).unwrap(), 0);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let mut bytes = [0x00_u8; 32];
+    memory.read(&store, 0, &mut bytes).unwrap();
+    let time = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+    ([time(0), time(8)], bytes[16..].try_into().unwrap())
+}
+
+#[test]
+fn test_fixed_clock() {
+    let start = UNIX_EPOCH + Duration::from_secs(1_000);
+    let (times, _) = run(DeterministicClock::fixed(start), 0);
+    assert_eq!(times, [1_000_000_000_000; 2]);
+}
+
+#[test]
+fn test_stepped_clock() {
+    let start = UNIX_EPOCH + Duration::from_secs(1_000);
+    let clock = DeterministicClock::stepped(start, Duration::from_millis(1));
+    let (times, _) = run(clock, 0);
+    assert_eq!(times, [1_000_000_000_000, 1_000_001_000_000]);
+}
+
+#[test]
+fn test_seeded_random() {
+    let clock = || DeterministicClock::fixed(UNIX_EPOCH);
+    let (_, random_a) = run(clock(), 42);
+    let (_, random_b) = run(clock(), 42);
+    let (_, random_c) = run(clock(), 43);
+    assert_eq!(random_a, random_b);
+    assert_ne!(random_a, random_c);
+}
@@ -0,0 +1,117 @@
+use wasmi::{Engine, Instance, Linker, Module, Store};
+use wasmi_wasi::nn::{
+    add_to_linker,
+    Backend,
+    BackendError,
+    ExecutionContext,
+    ExecutionTarget,
+    Graph,
+    GraphEncoding,
+    Tensor,
+    TensorType,
+    WasiNnCtx,
+};
+
+/// A [`Backend`] that only knows the `"double"` graph which doubles its `u8` input.
+struct DoubleBackend;
+
+impl Backend for DoubleBackend {
+    fn load(
+        &mut self,
+        builders: &[&[u8]],
+        _encoding: GraphEncoding,
+        _target: ExecutionTarget,
+    ) -> Result<Box<dyn Graph>, BackendError> {
+        match builders {
+            [b"double"] => Ok(Box::new(DoubleGraph)),
+            _ => Err(BackendError::InvalidEncoding),
+        }
+    }
+}
+
+struct DoubleGraph;
+
+impl Graph for DoubleGraph {
+    fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext>, BackendError> {
+        Ok(Box::new(DoubleContext::default()))
+    }
+}
+
+#[derive(Default)]
+struct DoubleContext {
+    input: Vec<u8>,
+    output: Vec<u8>,
+}
+
+impl ExecutionContext for DoubleContext {
+    fn set_input(&mut self, index: u32, tensor: &Tensor) -> Result<(), BackendError> {
+        if index != 0 || tensor.ty != TensorType::U8 || tensor.dimensions.len() != 1 {
+            return Err(BackendError::InvalidArgument);
+        }
+        self.input = tensor.data.to_vec();
+        Ok(())
+    }
+
+    fn compute(&mut self) -> Result<(), BackendError> {
+        self.output = self.input.iter().map(|x| x.wrapping_mul(2)).collect();
+        Ok(())
+    }
+
+    fn get_output(&mut self, index: u32, buffer: &mut [u8]) -> Result<u32, BackendError> {
+        if index != 0 {
+            return Err(BackendError::InvalidArgument);
+        }
+        let Some(buffer) = buffer.get_mut(..self.output.len()) else {
+            return Err(BackendError::TooLarge);
+        };
+        buffer.copy_from_slice(&self.output);
+        Ok(self.output.len() as u32)
+    }
+}
+
+fn load_instance() -> (Store<WasiNnCtx>, Instance) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, include_bytes!("wat/nn_double.wat")).unwrap();
+    let mut linker = <Linker<WasiNnCtx>>::new(&engine);
+    add_to_linker(&mut linker, |ctx| ctx).unwrap();
+    let mut store = Store::new(&engine, WasiNnCtx::new(DoubleBackend));
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn test_nn_inference() {
+    let (mut store, instance) = load_instance();
+    let run = instance.get_typed_func::<(), i32>(&store, "run").unwrap();
+    assert_eq!(run.call(&mut store, ()).unwrap(), 0);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let mut output = [0x00_u8; 4];
+    memory.read(&store, 500, &mut output).unwrap();
+    assert_eq!(output, [2, 4, 6, 8]);
+    let mut bytes_written = [0x00_u8; 4];
+    memory.read(&store, 600, &mut bytes_written).unwrap();
+    assert_eq!(u32::from_le_bytes(bytes_written), 4);
+}
+
+#[test]
+fn test_nn_errors() {
+    let (mut store, instance) = load_instance();
+    let compute = instance
+        .get_typed_func::<i32, i32>(&store, "compute")
+        .unwrap();
+    assert_eq!(
+        compute.call(&mut store, 0).unwrap(),
+        BackendError::InvalidArgument as i32
+    );
+    let load_by_name = instance
+        .get_typed_func::<(), i32>(&store, "load_by_name")
+        .unwrap();
+    assert_eq!(
+        load_by_name.call(&mut store, ()).unwrap(),
+        BackendError::NotFound as i32
+    );
+}
@@ -0,0 +1,46 @@
+use wasi_common::sync::WasiCtxBuilder;
+use wasmi::{Engine, Instance, Linker, Module, Store};
+use wasmi_wasi::{add_to_linker, vfs::VirtualDir, WasiCtx};
+
+/// The WASI error code for operations that are not permitted.
+const ERRNO_PERM: i32 = 63;
+
+fn load_instance(root: VirtualDir) -> (Store<WasiCtx>, Instance) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, include_bytes!("wat/vfs_write.wat")).unwrap();
+    let mut linker = <Linker<WasiCtx>>::new(&engine);
+    add_to_linker(&mut linker, |ctx| ctx).unwrap();
+    let wasi = WasiCtxBuilder::new().build();
+    wasi.push_preopened_dir(Box::new(root), "/").unwrap();
+    let mut store = Store::new(&engine, wasi);
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn test_vfs_write() {
+    let root = VirtualDir::new();
+    let (mut store, instance) = load_instance(root.clone());
+    let create = instance
+        .get_typed_func::<(), i32>(&store, "create_writable")
+        .unwrap();
+    assert_eq!(create.call(&mut store, ()).unwrap(), 0);
+    assert_eq!(root.read_file("hello.txt").unwrap(), b"Hello");
+}
+
+#[test]
+fn test_vfs_read_only() {
+    let assets = VirtualDir::new().with_file("index.html", "<h1>Hello</h1>");
+    let root = VirtualDir::new().with_dir("assets", assets.clone().read_only());
+    let (mut store, instance) = load_instance(root);
+    let create = instance
+        .get_typed_func::<(), i32>(&store, "create_read_only")
+        .unwrap();
+    assert_eq!(create.call(&mut store, ()).unwrap(), ERRNO_PERM);
+    assert!(assets.read_file("new.txt").is_none());
+    assert_eq!(assets.read_file("index.html").unwrap(), b"<h1>Hello</h1>");
+}
@@ -1,4 +1,8 @@
-use wasi_common::sync::WasiCtxBuilder;
+use std::{
+    io::Read,
+    net::{TcpListener as StdTcpListener, TcpStream},
+};
+use wasi_common::sync::{TcpListener, WasiCtxBuilder};
 use wasmi::{Config, Engine, Extern, Instance, Linker, Module, Store};
 use wasmi_wasi::{add_to_linker, WasiCtx};
 
@@ -39,3 +43,28 @@ fn test_hello_world() {
     let mut result = [];
     f.call(&mut store, &[], &mut result).unwrap();
 }
+
+#[test]
+fn test_preopened_socket() {
+    let listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+    let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, include_bytes!("wat/sock_send.wat")).unwrap();
+    let mut linker = <Linker<WasiCtx>>::new(&engine);
+    let wasi = WasiCtxBuilder::new()
+        .preopened_socket(3, TcpListener::from_std(listener))
+        .unwrap()
+        .build();
+    let mut store = Store::new(&engine, wasi);
+    add_to_linker(&mut linker, |ctx| ctx).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let serve = instance.get_typed_func::<(), i32>(&store, "serve").unwrap();
+    assert_eq!(serve.call(&mut store, ()).unwrap(), 0);
+    let mut received = String::new();
+    client.read_to_string(&mut received).unwrap();
+    assert_eq!(received, "hello");
+}
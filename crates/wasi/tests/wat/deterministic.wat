@@ -0,0 +1,23 @@
+(module
+    (import "wasi_snapshot_preview1" "clock_time_get"
+        (func $clock_time_get (param i32 i64 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "random_get"
+        (func $random_get (param i32 i32) (result i32)))
+    (memory (export "memory") 1)
+    ;; Reads the realtime clock twice into offsets 0 and 8
+    ;; and 16 random bytes into offset 16.
+    ;;
+    ;; Returns the WASI error code of the first failing call.
+    (func (export "run") (result i32)
+        (local $errno i32)
+        (block $fail
+            (br_if $fail (local.tee $errno
+                (call $clock_time_get (i32.const 0) (i64.const 1) (i32.const 0))))
+            (br_if $fail (local.tee $errno
+                (call $clock_time_get (i32.const 0) (i64.const 1) (i32.const 8))))
+            (local.set $errno
+                (call $random_get (i32.const 16) (i32.const 16)))
+        )
+        (local.get $errno)
+    )
+)
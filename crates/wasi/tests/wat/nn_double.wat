@@ -0,0 +1,43 @@
+(module
+    (import "wasi_ephemeral_nn" "load" (func $load (param i32 i32 i32 i32 i32) (result i32)))
+    (import "wasi_ephemeral_nn" "load_by_name" (func $load_by_name (param i32 i32 i32) (result i32)))
+    (import "wasi_ephemeral_nn" "init_execution_context" (func $init_execution_context (param i32 i32) (result i32)))
+    (import "wasi_ephemeral_nn" "set_input" (func $set_input (param i32 i32 i32) (result i32)))
+    (import "wasi_ephemeral_nn" "compute" (func $compute (param i32) (result i32)))
+    (import "wasi_ephemeral_nn" "get_output" (func $get_output (param i32 i32 i32 i32 i32) (result i32)))
+    (memory (export "memory") 1)
+    ;; The graph builder array with a single builder: "double"
+    (data (i32.const 0) "\64\00\00\00\06\00\00\00")
+    (data (i32.const 100) "double")
+    ;; The tensor dimensions: [4]
+    (data (i32.const 200) "\04\00\00\00")
+    ;; The tensor data: [1, 2, 3, 4]
+    (data (i32.const 300) "\01\02\03\04")
+    ;; The tensor record of type `u8`.
+    (data (i32.const 400) "\c8\00\00\00\01\00\00\00\03\00\00\00\2c\01\00\00\04\00\00\00")
+    ;; Infers the doubled tensor data into offset 500 and its length into offset 600.
+    ;;
+    ;; Returns the wasi-nn error code of the first failing call.
+    (func (export "run") (result i32)
+        (local $errno i32)
+        (block $fail
+            (br_if $fail (local.tee $errno
+                (call $load (i32.const 0) (i32.const 1) (i32.const 0) (i32.const 0) (i32.const 700))))
+            (br_if $fail (local.tee $errno
+                (call $init_execution_context (i32.load (i32.const 700)) (i32.const 704))))
+            (br_if $fail (local.tee $errno
+                (call $set_input (i32.load (i32.const 704)) (i32.const 0) (i32.const 400))))
+            (br_if $fail (local.tee $errno
+                (call $compute (i32.load (i32.const 704)))))
+            (local.set $errno
+                (call $get_output (i32.load (i32.const 704)) (i32.const 0) (i32.const 500) (i32.const 4) (i32.const 600)))
+        )
+        (local.get $errno)
+    )
+    (func (export "compute") (param $context i32) (result i32)
+        (call $compute (local.get $context))
+    )
+    (func (export "load_by_name") (result i32)
+        (call $load_by_name (i32.const 100) (i32.const 6) (i32.const 700))
+    )
+)
@@ -0,0 +1,38 @@
+;; Accepts a connection on the preopened listening socket and sends 'hello' over it.
+
+(module
+    (import "wasi_snapshot_preview1" "sock_accept" (func $sock_accept (param i32 i32 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "sock_send" (func $sock_send (param i32 i32 i32 i32 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "sock_shutdown" (func $sock_shutdown (param i32 i32) (result i32)))
+
+    (memory 1)
+    (export "memory" (memory 0))
+
+    ;; The io vector pointing to the 'hello' string at an offset of 16 bytes
+    (data (i32.const 8) "\10\00\00\00\05\00\00\00")
+    (data (i32.const 16) "hello")
+
+    ;; Returns the WASI errno of the first failing socket operation or 0 upon success.
+    (func $serve (export "serve") (result i32)
+        (local $errno i32)
+        ;; Accept a new connection on the preopened socket with file descriptor 3.
+        ;; The file descriptor of the new connection is stored at memory location 0.
+        (local.set $errno
+            (call $sock_accept (i32.const 3) (i32.const 0) (i32.const 0))
+        )
+        (if (local.get $errno) (then (return (local.get $errno))))
+        ;; Send the 'hello' string and store the number of bytes sent at memory location 4.
+        (local.set $errno
+            (call $sock_send
+                (i32.load (i32.const 0)) ;; file_descriptor - the accepted connection
+                (i32.const 8) ;; *ciovs - The pointer to the io vector array
+                (i32.const 1) ;; ciovs_len - We're sending 1 string stored in an io vector
+                (i32.const 0) ;; si_flags - No flags
+                (i32.const 4) ;; nsent - A place in memory to store the number of bytes sent
+            )
+        )
+        (if (local.get $errno) (then (return (local.get $errno))))
+        ;; Shut down the sending channel of the connection so that the peer observes EOF.
+        (call $sock_shutdown (i32.load (i32.const 0)) (i32.const 2))
+    )
+)
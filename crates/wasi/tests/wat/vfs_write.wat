@@ -0,0 +1,42 @@
+(module
+    (import "wasi_snapshot_preview1" "path_open"
+        (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "fd_write"
+        (func $fd_write (param i32 i32 i32 i32) (result i32)))
+    (memory (export "memory") 1)
+    (data (i32.const 0) "hello.txt")
+    (data (i32.const 16) "assets/new.txt")
+    ;; The single I/O vector pointing to the written bytes.
+    (data (i32.const 200) "\2c\01\00\00\05\00\00\00")
+    (data (i32.const 300) "Hello")
+    ;; Creates `path` in the preopened directory and writes "Hello" to it.
+    ;;
+    ;; Returns the WASI error code of the first failing call.
+    (func $create (param $path i32) (param $path_len i32) (result i32)
+        (local $errno i32)
+        (block $fail
+            (br_if $fail (local.tee $errno
+                (call $path_open
+                    (i32.const 3) ;; preopened directory
+                    (i32.const 0) ;; lookup flags
+                    (local.get $path)
+                    (local.get $path_len)
+                    (i32.const 1) ;; O_CREAT
+                    (i64.const 64) ;; FD_WRITE
+                    (i64.const 0)
+                    (i32.const 0) ;; fd flags
+                    (i32.const 100) ;; opened fd
+                )
+            ))
+            (local.set $errno
+                (call $fd_write (i32.load (i32.const 100)) (i32.const 200) (i32.const 1) (i32.const 104)))
+        )
+        (local.get $errno)
+    )
+    (func (export "create_writable") (result i32)
+        (call $create (i32.const 0) (i32.const 9))
+    )
+    (func (export "create_read_only") (result i32)
+        (call $create (i32.const 16) (i32.const 14))
+    )
+)
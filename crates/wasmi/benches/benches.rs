@@ -124,6 +124,7 @@ criterion_group! {
         bench_execute_recursive_is_even,
         bench_execute_memory_sum,
         bench_execute_memory_fill,
+        bench_execute_memory_store_unaligned,
         bench_execute_vec_add,
         bench_execute_bulk_ops,
 }
@@ -1358,6 +1359,28 @@ fn bench_execute_memory_fill(c: &mut Criterion) {
     });
 }
 
+fn bench_execute_memory_store_unaligned(c: &mut Criterion) {
+    c.bench_function("execute/memory/store_unaligned", |b| {
+        let (mut store, instance) = load_instance_from_wat(include_bytes!("wat/memory-store.wat"));
+        let run = instance
+            .get_typed_func::<(i32, i32), ()>(&store, "store_unaligned")
+            .unwrap();
+        let ptr = 0x100;
+        let len = 10_000;
+        let mem = instance.get_memory(&store, "mem").unwrap();
+        mem.grow(&mut store, 1).unwrap();
+        b.iter(|| {
+            run.call(&mut store, (ptr as i32, len as i32)).unwrap();
+        });
+        let data = mem.data(&store);
+        assert!((0..len).all(|n| {
+            let offset = ptr + 8 * n + 1;
+            let bytes = data[offset..(offset + 8)].try_into().unwrap();
+            u64::from_le_bytes(bytes) == n as u64
+        }));
+    });
+}
+
 fn bench_execute_vec_add(c: &mut Criterion) {
     fn test_for<A, B>(
         b: &mut Bencher,
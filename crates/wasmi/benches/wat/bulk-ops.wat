@@ -1,6 +1,9 @@
 (module
     (memory 8 8)
 
+    ;; Passive data segment used as source for `memory.init`.
+    (data $data "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")
+
     ;; The maximum amount of bytes to process per iteration.
     (global $MAX_N i64 (i64.const 250000))
 
@@ -30,6 +33,12 @@
                     (i32.const 0) ;; src
                     (local.get $n) ;; len
                 )
+                ;; mem[i..i+64].copy(data[0..64])
+                (memory.init $data
+                    (local.get $i) ;; dst
+                    (i32.const 0) ;; src
+                    (i32.const 64) ;; len
+                )
                 ;; i += 1
                 (local.set $i (i32.add (local.get $i) (i32.const 1)))
                 (br $continue)
@@ -0,0 +1,36 @@
+;; Exports a function `store_unaligned` that stores `len` many `i64`
+;; values to the linear memory at unaligned addresses.
+;;
+;; # Note
+;;
+;; The `ptr` and `len` parameters tell where to store
+;; contents within the linear memory.
+(module
+    (memory (export "mem") 1)
+    (func (export "store_unaligned") (param $ptr i32) (param $len i32)
+        (local $n i32)
+        (block $exit
+            (loop $loop
+                (br_if ;; exit loop if $n == $len
+                    $exit
+                    (i32.eq
+                        (local.get $n)
+                        (local.get $len)
+                    )
+                )
+                (i64.store offset=1 ;; store $n at mem[ptr+8*n+1]
+                    (i32.add
+                        (local.get $ptr)
+                        (i32.shl (local.get $n) (i32.const 3))
+                    )
+                    (i64.extend_i32_u (local.get $n))
+                )
+                (local.set $n ;; increment n
+                    (i32.add (local.get $n) (i32.const 1))
+                 )
+                (br $loop) ;; continue loop
+            )
+        )
+        (return)
+    )
+)
@@ -0,0 +1,614 @@
+//! Helpers to lift and lower values according to the canonical ABI of the component model.
+//!
+//! Guests generated by `wit-bindgen` pass strings, lists, records and variants
+//! through their default linear memory and allocate buffers via their exported
+//! `cabi_realloc` function. The [`Lift`] and [`Lower`] traits describe how Rust
+//! values are read from and written to linear memory following the memory layout
+//! of the canonical ABI while [`LiftContext`] and [`LowerContext`] drive them.
+//!
+//! | Rust | WIT |
+//! |:--|:--|
+//! | `bool` | `bool` |
+//! | `i8`, `u8`, ..., `i64`, `u64` | `s8`, `u8`, ..., `s64`, `u64` |
+//! | `f32`, `f64` | `f32`, `f64` |
+//! | `char` | `char` |
+//! | `String`, `str` | `string` |
+//! | `Vec<T>`, `[T]` | `list<T>` |
+//! | `()`, `(T1, T2, ...)` | `tuple<T1, T2, ...>` and `record` |
+//! | `Option<T>` | `option<T>` |
+//! | `Result<T, E>` | `result<T, E>` |
+
+use crate::{
+    memory::MemoryError,
+    AsContext,
+    AsContextMut,
+    Error,
+    GuestAllocator,
+    Memory,
+    MemoryScalar,
+    StoreContext,
+    StoreContextMut,
+};
+use alloc::{string::String, vec::Vec};
+use core::str;
+
+/// Returns `offset` rounded up to the next multiple of `align`.
+///
+/// # Note
+///
+/// The `align` must be a power of two.
+pub const fn align_to(offset: u32, align: u32) -> u32 {
+    (offset + align - 1) & !(align - 1)
+}
+
+/// Returns the maximum of `a` and `b` in `const` contexts.
+const fn max(a: u32, b: u32) -> u32 {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/// Types with a memory layout defined by the canonical ABI.
+pub trait CanonicalType {
+    /// The size of the type in linear memory in bytes.
+    const SIZE: u32;
+    /// The alignment of the type in linear memory in bytes.
+    const ALIGN: u32;
+}
+
+/// Types that can be lifted from linear memory according to the canonical ABI.
+///
+/// # Note
+///
+/// User defined WIT `record` types are lifted field by field in declaration order
+/// which is the same memory layout as a Rust tuple of the same fields.
+pub trait Lift: CanonicalType + Sized {
+    /// Lifts a value from the linear memory of `cx` at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// - If the lifted value is out of bounds of the linear memory.
+    /// - If the lifted value is invalid for the type.
+    fn lift(cx: &LiftContext<'_>, offset: u32) -> Result<Self, Error>;
+}
+
+/// Types that can be lowered into linear memory according to the canonical ABI.
+///
+/// # Note
+///
+/// User defined WIT `record` types are lowered field by field in declaration order
+/// which is the same memory layout as a Rust tuple of the same fields.
+pub trait Lower: CanonicalType {
+    /// Lowers `self` into the linear memory of `cx` at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// - If the lowered value is out of bounds of the linear memory.
+    /// - If the guest allocator traps or fails to allocate a buffer.
+    fn lower<T>(&self, cx: &mut LowerContext<'_, T>, offset: u32) -> Result<(), Error>;
+}
+
+/// Lifts values from the default linear memory of a Wasm guest.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{Engine, LiftContext, Linker, Module, Store};
+/// # let engine = Engine::default();
+/// # let wasm = r#"
+/// #     (module
+/// #         (memory (export "memory") 1)
+/// #         (data (i32.const 16) "hello")
+/// #         (data (i32.const 32) "\10\00\00\00\05\00\00\00\2a\00\00\00")
+/// #     )
+/// # "#;
+/// # let module = Module::new(&engine, wasm).unwrap();
+/// # let mut store = Store::new(&engine, ());
+/// # let instance = <Linker<()>>::new(&engine)
+/// #     .instantiate(&mut store, &module).unwrap()
+/// #     .start(&mut store).unwrap();
+/// let memory = instance.get_memory(&store, "memory").unwrap();
+/// let cx = LiftContext::new(&store, memory);
+/// // Lifts the WIT `record { name: string, id: u32 }` at address 32.
+/// let (name, id) = cx.lift::<(String, u32)>(32).unwrap();
+/// assert_eq!((name.as_str(), id), ("hello", 42));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct LiftContext<'a> {
+    /// The bytes of the linear memory.
+    bytes: &'a [u8],
+}
+
+impl<'a> LiftContext<'a> {
+    /// Creates a new [`LiftContext`] for the bytes of `memory`.
+    pub fn new<T: 'a>(ctx: impl Into<StoreContext<'a, T>>, memory: Memory) -> Self {
+        Self {
+            bytes: memory.data(ctx),
+        }
+    }
+
+    /// Returns `len` bytes of the linear memory starting at `ptr`.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes are out of bounds of the linear memory.
+    pub fn bytes(&self, ptr: u32, len: u32) -> Result<&'a [u8], MemoryError> {
+        let start = ptr as usize;
+        start
+            .checked_add(len as usize)
+            .and_then(|end| self.bytes.get(start..end))
+            .ok_or(MemoryError::OutOfBoundsAccess)
+    }
+
+    /// Reads the little-endian scalar `S` at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If the scalar is out of bounds of the linear memory.
+    pub fn read_scalar<S: MemoryScalar>(&self, offset: u32) -> Result<S, MemoryError> {
+        let mut buffer = S::Bytes::default();
+        let len = buffer.as_ref().len() as u32;
+        buffer.as_mut().copy_from_slice(self.bytes(offset, len)?);
+        Ok(S::from_le_bytes(buffer))
+    }
+
+    /// Lifts the value `V` stored at `ptr`.
+    ///
+    /// # Errors
+    ///
+    /// - If `ptr` is not aligned for `V`.
+    /// - If the value is out of bounds of the linear memory or invalid for `V`.
+    pub fn lift<V: Lift>(&self, ptr: u32) -> Result<V, Error> {
+        if ptr % V::ALIGN != 0 {
+            return Err(Error::from(MemoryError::UnalignedAccess));
+        }
+        V::lift(self, ptr)
+    }
+
+    /// Lifts the UTF-8 encoded `string` of `len` bytes at `ptr`.
+    ///
+    /// # Errors
+    ///
+    /// - If the string is out of bounds of the linear memory.
+    /// - If the string is not valid UTF-8.
+    pub fn lift_string(&self, ptr: u32, len: u32) -> Result<String, Error> {
+        let bytes = self.bytes(ptr, len)?;
+        let string = str::from_utf8(bytes).map_err(MemoryError::InvalidUtf8)?;
+        Ok(String::from(string))
+    }
+
+    /// Lifts the `list<V>` of `len` elements at `ptr`.
+    ///
+    /// # Errors
+    ///
+    /// - If `ptr` is not aligned for `V`.
+    /// - If the list is out of bounds of the linear memory.
+    /// - If any of the elements is invalid for `V`.
+    pub fn lift_list<V: Lift>(&self, ptr: u32, len: u32) -> Result<Vec<V>, Error> {
+        if ptr % V::ALIGN != 0 {
+            return Err(Error::from(MemoryError::UnalignedAccess));
+        }
+        let len_bytes = len
+            .checked_mul(V::SIZE)
+            .ok_or(MemoryError::OutOfBoundsAccess)?;
+        self.bytes(ptr, len_bytes)?;
+        (0..len).map(|n| V::lift(self, ptr + n * V::SIZE)).collect()
+    }
+}
+
+/// Lowers values into the default linear memory of a Wasm guest.
+///
+/// Buffers for the lowered values are allocated via the [`GuestAllocator`] of the guest
+/// and are owned by the guest afterwards.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{Engine, GuestAllocator, LowerContext, Linker, Module, Store};
+/// # let engine = Engine::default();
+/// # let wasm = r#"
+/// #     (module
+/// #         (memory (export "memory") 1)
+/// #         (global $next (mut i32) (i32.const 16))
+/// #         (func (export "cabi_realloc") (param i32 i32 i32 i32) (result i32)
+/// #             (local $ptr i32)
+/// #             (local.set $ptr (global.get $next))
+/// #             (global.set $next (i32.add (local.get $ptr) (local.get 3)))
+/// #             (local.get $ptr)
+/// #         )
+/// #         (func (export "len") (param i32 i32) (result i32)
+/// #             (local.get 1)
+/// #         )
+/// #     )
+/// # "#;
+/// # let module = Module::new(&engine, wasm).unwrap();
+/// # let mut store = Store::new(&engine, ());
+/// # let instance = <Linker<()>>::new(&engine)
+/// #     .instantiate(&mut store, &module).unwrap()
+/// #     .start(&mut store).unwrap();
+/// let len = instance.get_typed_func::<(i32, i32), i32>(&store, "len").unwrap();
+/// let allocator = GuestAllocator::new(&store, instance).unwrap();
+/// let mut cx = LowerContext::new(&mut store, allocator);
+/// let (ptr, n) = cx.lower_list(&[1_u16, 2, 3]).unwrap();
+/// assert_eq!(len.call(&mut store, (ptr as i32, n as i32)).unwrap(), 3);
+/// ```
+#[derive(Debug)]
+pub struct LowerContext<'a, T> {
+    /// The store context of the guest.
+    ctx: StoreContextMut<'a, T>,
+    /// The allocator of the guest.
+    allocator: GuestAllocator,
+}
+
+impl<'a, T> LowerContext<'a, T> {
+    /// Creates a new [`LowerContext`] allocating buffers via `allocator`.
+    pub fn new(ctx: impl Into<StoreContextMut<'a, T>>, allocator: GuestAllocator) -> Self {
+        Self {
+            ctx: ctx.into(),
+            allocator,
+        }
+    }
+
+    /// Allocates a guest buffer of `size` bytes aligned to `align` bytes and returns its address.
+    ///
+    /// # Errors
+    ///
+    /// If the guest allocator traps or fails to allocate the buffer.
+    pub fn realloc(&mut self, size: u32, align: u32) -> Result<u32, Error> {
+        let guest = self
+            .allocator
+            .alloc(self.ctx.as_context_mut(), size, align)?;
+        Ok(guest.into_raw())
+    }
+
+    /// Writes `bytes` to the linear memory at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes are out of bounds of the linear memory.
+    pub fn write_bytes(&mut self, offset: u32, bytes: &[u8]) -> Result<(), MemoryError> {
+        self.allocator
+            .memory()
+            .write(&mut self.ctx, offset as usize, bytes)
+    }
+
+    /// Writes the scalar `value` to the linear memory at `offset` in little-endian byte order.
+    ///
+    /// # Errors
+    ///
+    /// If the scalar is out of bounds of the linear memory.
+    pub fn write_scalar<S: MemoryScalar>(
+        &mut self,
+        offset: u32,
+        value: S,
+    ) -> Result<(), MemoryError> {
+        self.allocator
+            .memory()
+            .write_scalar(&mut self.ctx, u64::from(offset), value)
+    }
+
+    /// Lowers `value` into a newly allocated guest buffer and returns its address.
+    ///
+    /// # Errors
+    ///
+    /// If the guest allocator traps or fails to allocate a buffer.
+    pub fn lower<V: Lower + ?Sized>(&mut self, value: &V) -> Result<u32, Error> {
+        let ptr = self.realloc(V::SIZE, V::ALIGN)?;
+        value.lower(self, ptr)?;
+        Ok(ptr)
+    }
+
+    /// Lowers the UTF-8 encoded `string` into a newly allocated guest buffer.
+    ///
+    /// Returns the address and length in bytes of the guest buffer.
+    ///
+    /// # Errors
+    ///
+    /// - If `string` does not fit into a 32-bit linear memory.
+    /// - If the guest allocator traps or fails to allocate the buffer.
+    pub fn lower_str(&mut self, string: &str) -> Result<(u32, u32), Error> {
+        let len = u32::try_from(string.len()).map_err(|_| MemoryError::GuestAllocationFailed)?;
+        let ptr = self.realloc(len, 1)?;
+        self.write_bytes(ptr, string.as_bytes())?;
+        Ok((ptr, len))
+    }
+
+    /// Lowers the `list<V>` of `values` into a newly allocated guest buffer.
+    ///
+    /// Returns the address of the guest buffer and the number of its elements.
+    ///
+    /// # Errors
+    ///
+    /// - If `values` does not fit into a 32-bit linear memory.
+    /// - If the guest allocator traps or fails to allocate a buffer.
+    pub fn lower_list<V: Lower>(&mut self, values: &[V]) -> Result<(u32, u32), Error> {
+        let len = u32::try_from(values.len())
+            .ok()
+            .filter(|len| len.checked_mul(V::SIZE).is_some())
+            .ok_or(MemoryError::GuestAllocationFailed)?;
+        let ptr = self.realloc(len * V::SIZE, V::ALIGN)?;
+        for (n, value) in (0..len).zip(values) {
+            value.lower(self, ptr + n * V::SIZE)?;
+        }
+        Ok((ptr, len))
+    }
+}
+
+impl<T> AsContextMut for LowerContext<'_, T> {
+    #[inline]
+    fn as_context_mut(&mut self) -> StoreContextMut<'_, Self::Data> {
+        self.ctx.as_context_mut()
+    }
+}
+
+impl<T> AsContext for LowerContext<'_, T> {
+    type Data = T;
+
+    #[inline]
+    fn as_context(&self) -> StoreContext<'_, Self::Data> {
+        self.ctx.as_context()
+    }
+}
+
+macro_rules! impl_scalar {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl CanonicalType for $ty {
+                const SIZE: u32 = core::mem::size_of::<$ty>() as u32;
+                const ALIGN: u32 = core::mem::size_of::<$ty>() as u32;
+            }
+
+            impl Lift for $ty {
+                fn lift(cx: &LiftContext<'_>, offset: u32) -> Result<Self, Error> {
+                    Ok(cx.read_scalar::<$ty>(offset)?)
+                }
+            }
+
+            impl Lower for $ty {
+                fn lower<T>(&self, cx: &mut LowerContext<'_, T>, offset: u32) -> Result<(), Error> {
+                    Ok(cx.write_scalar(offset, *self)?)
+                }
+            }
+        )*
+    };
+}
+impl_scalar!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+impl CanonicalType for bool {
+    const SIZE: u32 = 1;
+    const ALIGN: u32 = 1;
+}
+
+impl Lift for bool {
+    fn lift(cx: &LiftContext<'_>, offset: u32) -> Result<Self, Error> {
+        Ok(cx.read_scalar::<u8>(offset)? != 0)
+    }
+}
+
+impl Lower for bool {
+    fn lower<T>(&self, cx: &mut LowerContext<'_, T>, offset: u32) -> Result<(), Error> {
+        Ok(cx.write_scalar(offset, u8::from(*self))?)
+    }
+}
+
+impl CanonicalType for char {
+    const SIZE: u32 = 4;
+    const ALIGN: u32 = 4;
+}
+
+impl Lift for char {
+    fn lift(cx: &LiftContext<'_>, offset: u32) -> Result<Self, Error> {
+        let value = cx.read_scalar::<u32>(offset)?;
+        char::from_u32(value).ok_or_else(|| Error::from(MemoryError::InvalidChar(value)))
+    }
+}
+
+impl Lower for char {
+    fn lower<T>(&self, cx: &mut LowerContext<'_, T>, offset: u32) -> Result<(), Error> {
+        Ok(cx.write_scalar(offset, u32::from(*self))?)
+    }
+}
+
+impl CanonicalType for str {
+    const SIZE: u32 = 8;
+    const ALIGN: u32 = 4;
+}
+
+impl Lower for str {
+    fn lower<T>(&self, cx: &mut LowerContext<'_, T>, offset: u32) -> Result<(), Error> {
+        let (ptr, len) = cx.lower_str(self)?;
+        cx.write_scalar(offset, ptr)?;
+        cx.write_scalar(offset + 4, len)?;
+        Ok(())
+    }
+}
+
+impl CanonicalType for String {
+    const SIZE: u32 = <str as CanonicalType>::SIZE;
+    const ALIGN: u32 = <str as CanonicalType>::ALIGN;
+}
+
+impl Lift for String {
+    fn lift(cx: &LiftContext<'_>, offset: u32) -> Result<Self, Error> {
+        let ptr = cx.read_scalar::<u32>(offset)?;
+        let len = cx.read_scalar::<u32>(offset + 4)?;
+        cx.lift_string(ptr, len)
+    }
+}
+
+impl Lower for String {
+    fn lower<T>(&self, cx: &mut LowerContext<'_, T>, offset: u32) -> Result<(), Error> {
+        self.as_str().lower(cx, offset)
+    }
+}
+
+impl<V> CanonicalType for [V] {
+    const SIZE: u32 = 8;
+    const ALIGN: u32 = 4;
+}
+
+impl<V: Lower> Lower for [V] {
+    fn lower<T>(&self, cx: &mut LowerContext<'_, T>, offset: u32) -> Result<(), Error> {
+        let (ptr, len) = cx.lower_list(self)?;
+        cx.write_scalar(offset, ptr)?;
+        cx.write_scalar(offset + 4, len)?;
+        Ok(())
+    }
+}
+
+impl<V> CanonicalType for Vec<V> {
+    const SIZE: u32 = <[V] as CanonicalType>::SIZE;
+    const ALIGN: u32 = <[V] as CanonicalType>::ALIGN;
+}
+
+impl<V: Lift> Lift for Vec<V> {
+    fn lift(cx: &LiftContext<'_>, offset: u32) -> Result<Self, Error> {
+        let ptr = cx.read_scalar::<u32>(offset)?;
+        let len = cx.read_scalar::<u32>(offset + 4)?;
+        cx.lift_list(ptr, len)
+    }
+}
+
+impl<V: Lower> Lower for Vec<V> {
+    fn lower<T>(&self, cx: &mut LowerContext<'_, T>, offset: u32) -> Result<(), Error> {
+        self.as_slice().lower(cx, offset)
+    }
+}
+
+impl<V: CanonicalType + ?Sized> CanonicalType for &V {
+    const SIZE: u32 = V::SIZE;
+    const ALIGN: u32 = V::ALIGN;
+}
+
+impl<V: Lower + ?Sized> Lower for &V {
+    fn lower<T>(&self, cx: &mut LowerContext<'_, T>, offset: u32) -> Result<(), Error> {
+        (**self).lower(cx, offset)
+    }
+}
+
+macro_rules! impl_tuple {
+    ( $n:literal $( $ty:ident )* ) => {
+        impl<$($ty: CanonicalType),*> CanonicalType for ($($ty,)*) {
+            const ALIGN: u32 = {
+                let align = 1;
+                $( let align = max(align, $ty::ALIGN); )*
+                align
+            };
+            const SIZE: u32 = {
+                let size = 0;
+                $( let size = align_to(size, $ty::ALIGN) + $ty::SIZE; )*
+                align_to(size, Self::ALIGN)
+            };
+        }
+
+        impl<$($ty: Lift),*> Lift for ($($ty,)*) {
+            #[allow(unused_mut, unused_variables, unused_assignments)]
+            fn lift(cx: &LiftContext<'_>, offset: u32) -> Result<Self, Error> {
+                let mut field = 0;
+                Ok(($(
+                    {
+                        let start = align_to(field, $ty::ALIGN);
+                        field = start + $ty::SIZE;
+                        $ty::lift(cx, offset + start)?
+                    },
+                )*))
+            }
+        }
+
+        impl<$($ty: Lower),*> Lower for ($($ty,)*) {
+            #[allow(non_snake_case, unused_mut, unused_variables, unused_assignments)]
+            fn lower<T>(&self, cx: &mut LowerContext<'_, T>, offset: u32) -> Result<(), Error> {
+                let ($($ty,)*) = self;
+                let mut field = 0;
+                $(
+                    let start = align_to(field, <$ty as CanonicalType>::ALIGN);
+                    field = start + <$ty as CanonicalType>::SIZE;
+                    $ty.lower(cx, offset + start)?;
+                )*
+                Ok(())
+            }
+        }
+    };
+}
+for_each_tuple!(impl_tuple);
+
+/// Returns the offset of the payload of a variant with a `u8` discriminant.
+const fn payload_offset(payload_align: u32) -> u32 {
+    align_to(1, payload_align)
+}
+
+/// Reads the `u8` discriminant of a variant with `cases` cases at `offset`.
+///
+/// # Errors
+///
+/// - If the discriminant is out of bounds of the linear memory.
+/// - If the discriminant does not refer to any of the `cases`.
+fn lift_discriminant(cx: &LiftContext<'_>, offset: u32, cases: u8) -> Result<u8, Error> {
+    let discriminant = cx.read_scalar::<u8>(offset)?;
+    if discriminant >= cases {
+        return Err(Error::from(MemoryError::InvalidDiscriminant(discriminant)));
+    }
+    Ok(discriminant)
+}
+
+impl<V: CanonicalType> CanonicalType for Option<V> {
+    const ALIGN: u32 = max(1, V::ALIGN);
+    const SIZE: u32 = align_to(payload_offset(V::ALIGN) + V::SIZE, Self::ALIGN);
+}
+
+impl<V: Lift> Lift for Option<V> {
+    fn lift(cx: &LiftContext<'_>, offset: u32) -> Result<Self, Error> {
+        match lift_discriminant(cx, offset, 2)? {
+            0 => Ok(None),
+            _ => V::lift(cx, offset + payload_offset(V::ALIGN)).map(Some),
+        }
+    }
+}
+
+impl<V: Lower> Lower for Option<V> {
+    fn lower<T>(&self, cx: &mut LowerContext<'_, T>, offset: u32) -> Result<(), Error> {
+        match self {
+            None => Ok(cx.write_scalar(offset, 0_u8)?),
+            Some(value) => {
+                cx.write_scalar(offset, 1_u8)?;
+                value.lower(cx, offset + payload_offset(V::ALIGN))
+            }
+        }
+    }
+}
+
+impl<V: CanonicalType, E: CanonicalType> CanonicalType for Result<V, E> {
+    const ALIGN: u32 = max(1, max(V::ALIGN, E::ALIGN));
+    const SIZE: u32 = align_to(
+        payload_offset(max(V::ALIGN, E::ALIGN)) + max(V::SIZE, E::SIZE),
+        Self::ALIGN,
+    );
+}
+
+impl<V: Lift, E: Lift> Lift for Result<V, E> {
+    fn lift(cx: &LiftContext<'_>, offset: u32) -> Result<Self, Error> {
+        let payload = offset + payload_offset(max(V::ALIGN, E::ALIGN));
+        match lift_discriminant(cx, offset, 2)? {
+            0 => V::lift(cx, payload).map(Ok),
+            _ => E::lift(cx, payload).map(Err),
+        }
+    }
+}
+
+impl<V: Lower, E: Lower> Lower for Result<V, E> {
+    fn lower<T>(&self, cx: &mut LowerContext<'_, T>, offset: u32) -> Result<(), Error> {
+        let payload = offset + payload_offset(max(V::ALIGN, E::ALIGN));
+        match self {
+            Ok(value) => {
+                cx.write_scalar(offset, 0_u8)?;
+                value.lower(cx, payload)
+            }
+            Err(error) => {
+                cx.write_scalar(offset, 1_u8)?;
+                error.lower(cx, payload)
+            }
+        }
+    }
+}
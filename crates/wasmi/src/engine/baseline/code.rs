@@ -0,0 +1,244 @@
+use crate::{
+    engine::{BlockType, EngineFunc},
+    module::{FuncIdx, ModuleHeader},
+    Engine,
+};
+use alloc::{sync::Arc, vec::Vec};
+use core::mem;
+use wasmparser::{BinaryReader, BinaryReaderError, FunctionBody, Operator};
+
+/// The structured control flow information of a Wasm `block`, `loop` or `if`.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct BlockInfo {
+    /// The index of the `else` operator of a Wasm `if` or of the matching `end` operator.
+    pub else_or_end: u32,
+    /// The index of the matching `end` operator.
+    pub end: u32,
+    /// The number of parameters of the block type.
+    pub len_params: u16,
+    /// The number of results of the block type.
+    pub len_results: u16,
+}
+
+/// The decoded Wasm operators of a Wasm function ready for interpretation.
+///
+/// # Note
+///
+/// A Wasm function is decoded once upon its first call by the baseline tier.
+/// All of its calls share the same [`BaselineCode`] until it tiers up.
+#[derive(Debug)]
+pub struct BaselineCode {
+    /// The engine function of the decoded Wasm function.
+    func: EngineFunc,
+    /// The number of results of the function.
+    len_results: usize,
+    /// The number of local variables excluding the function parameters.
+    len_locals: usize,
+    /// The decoded Wasm operators of the function body.
+    ///
+    /// # Note
+    ///
+    /// The operators borrow from `bytes` and thus must be dropped before `bytes`.
+    ops: Vec<Operator<'static>>,
+    /// The [`BlockInfo`] of every structured control operator indexed by operator.
+    ///
+    /// Entries of all other operators are unused.
+    blocks: Vec<BlockInfo>,
+    /// The validated Wasm function body.
+    bytes: Arc<[u8]>,
+}
+
+impl BaselineCode {
+    /// Decodes the validated Wasm function body `bytes` of `func` for interpretation.
+    ///
+    /// The function is at `func_index` of the Wasm `module`.
+    ///
+    /// Returns `None` if the function body uses operators unsupported by the baseline tier.
+    pub fn new(
+        func: EngineFunc,
+        func_index: FuncIdx,
+        bytes: Arc<[u8]>,
+        module: &ModuleHeader,
+        engine: &Engine,
+    ) -> Option<Self> {
+        // Safety: the decoded operators borrow from the heap allocated `bytes` which are
+        //         never mutated. Both are owned by `Self` and `ops` is dropped before `bytes`.
+        //         Operators are only handed out with lifetimes bound to `Self`.
+        let body = unsafe { mem::transmute::<&[u8], &'static [u8]>(&bytes[..]) };
+        let decoded = decode(body, func_index, module, engine).ok().flatten()?;
+        Some(Self {
+            func,
+            len_results: decoded.len_results,
+            len_locals: decoded.len_locals,
+            ops: decoded.ops,
+            blocks: decoded.blocks,
+            bytes,
+        })
+    }
+
+    /// Returns the number of heap allocated bytes held by the [`BaselineCode`].
+    pub fn heap_size(&self) -> usize {
+        self.bytes.len()
+            + self.ops.capacity() * mem::size_of::<Operator>()
+            + self.blocks.capacity() * mem::size_of::<BlockInfo>()
+    }
+
+    /// Returns the engine function of the decoded Wasm function.
+    pub fn func(&self) -> EngineFunc {
+        self.func
+    }
+
+    /// Returns the number of results of the function.
+    pub fn len_results(&self) -> usize {
+        self.len_results
+    }
+
+    /// Returns the number of local variables excluding the function parameters.
+    pub fn len_locals(&self) -> usize {
+        self.len_locals
+    }
+
+    /// Returns the decoded Wasm operators of the function body.
+    pub fn ops(&self) -> &[Operator<'_>] {
+        &self.ops
+    }
+
+    /// Returns the [`BlockInfo`] of the structured control operator at `index`.
+    pub fn block(&self, index: usize) -> BlockInfo {
+        self.blocks[index]
+    }
+}
+
+/// A Wasm function body decoded by [`decode`].
+struct DecodedBody<'a> {
+    /// The number of results of the function.
+    len_results: usize,
+    /// The number of local variables excluding the function parameters.
+    len_locals: usize,
+    /// The decoded Wasm operators of the function body.
+    ops: Vec<Operator<'a>>,
+    /// The [`BlockInfo`] of every structured control operator indexed by operator.
+    blocks: Vec<BlockInfo>,
+}
+
+/// Decodes the Wasm function body `bytes` of the function at `func_index` of `module`.
+///
+/// Returns `None` if the function body uses operators unsupported by the baseline tier.
+fn decode<'a>(
+    bytes: &'a [u8],
+    func_index: FuncIdx,
+    module: &ModuleHeader,
+    engine: &Engine,
+) -> Result<Option<DecodedBody<'a>>, BinaryReaderError> {
+    let func_type = module.get_type_of_func(func_index);
+    let len_results = engine.resolve_func_type(func_type, |ty| ty.results().len());
+    let features = engine.config().wasm_features();
+    let reader = BinaryReader::new_features(bytes, 0, features);
+    let body = FunctionBody::new(reader);
+    let mut len_locals = 0_usize;
+    let mut locals = body.get_locals_reader()?;
+    for _ in 0..locals.get_count() {
+        let (amount, _ty) = locals.read()?;
+        len_locals += amount as usize;
+    }
+    let mut reader = body.get_operators_reader()?;
+    let mut ops = Vec::new();
+    let mut blocks = Vec::new();
+    // The indices of the currently open structured control operators.
+    let mut open = Vec::new();
+    while !reader.eof() {
+        let op = reader.read()?;
+        if !is_supported(&op) {
+            return Ok(None);
+        }
+        let index = ops.len() as u32;
+        let mut info = BlockInfo::default();
+        match op {
+            Operator::Block { blockty } | Operator::Loop { blockty } | Operator::If { blockty } => {
+                let block_type = BlockType::new(blockty, module);
+                info.len_params = block_type.len_params(engine);
+                info.len_results = block_type.len_results(engine);
+                open.push(index);
+            }
+            Operator::Else => {
+                if let Some(&start) = open.last() {
+                    blocks[start as usize] = BlockInfo {
+                        else_or_end: index,
+                        ..blocks[start as usize]
+                    };
+                }
+            }
+            Operator::End => {
+                if let Some(start) = open.pop() {
+                    let start = &mut blocks[start as usize];
+                    if start.else_or_end == 0 {
+                        start.else_or_end = index;
+                    }
+                    start.end = index;
+                }
+            }
+            _ => {}
+        }
+        ops.push(op);
+        blocks.push(info);
+    }
+    Ok(Some(DecodedBody {
+        len_results,
+        len_locals,
+        ops,
+        blocks,
+    }))
+}
+
+/// Returns `true` if the baseline tier supports interpreting `op`.
+#[rustfmt::skip]
+fn is_supported(op: &Operator) -> bool {
+    use Operator as Op;
+    matches!(
+        op,
+        Op::Unreachable | Op::Nop | Op::Block { .. } | Op::Loop { .. } | Op::If { .. } |
+        Op::Else | Op::End | Op::Br { .. } | Op::BrIf { .. } | Op::BrTable { .. } |
+        Op::Return | Op::Call { .. } | Op::CallIndirect { .. } | Op::Drop | Op::Select |
+        Op::TypedSelect { .. } | Op::LocalGet { .. } | Op::LocalSet { .. } |
+        Op::LocalTee { .. } | Op::GlobalGet { .. } | Op::GlobalSet { .. } |
+        Op::I32Load { .. } | Op::I64Load { .. } | Op::F32Load { .. } | Op::F64Load { .. } |
+        Op::I32Load8S { .. } | Op::I32Load8U { .. } | Op::I32Load16S { .. } |
+        Op::I32Load16U { .. } | Op::I64Load8S { .. } | Op::I64Load8U { .. } |
+        Op::I64Load16S { .. } | Op::I64Load16U { .. } | Op::I64Load32S { .. } |
+        Op::I64Load32U { .. } | Op::I32Store { .. } | Op::I64Store { .. } |
+        Op::F32Store { .. } | Op::F64Store { .. } | Op::I32Store8 { .. } |
+        Op::I32Store16 { .. } | Op::I64Store8 { .. } | Op::I64Store16 { .. } |
+        Op::I64Store32 { .. } | Op::MemorySize { .. } | Op::MemoryGrow { .. } |
+        Op::I32Const { .. } | Op::I64Const { .. } | Op::F32Const { .. } |
+        Op::F64Const { .. } | Op::RefNull { .. } | Op::RefIsNull | Op::RefFunc { .. } |
+        Op::I32Eqz | Op::I32Eq | Op::I32Ne | Op::I32LtS | Op::I32LtU | Op::I32GtS |
+        Op::I32GtU | Op::I32LeS | Op::I32LeU | Op::I32GeS | Op::I32GeU |
+        Op::I64Eqz | Op::I64Eq | Op::I64Ne | Op::I64LtS | Op::I64LtU | Op::I64GtS |
+        Op::I64GtU | Op::I64LeS | Op::I64LeU | Op::I64GeS | Op::I64GeU |
+        Op::F32Eq | Op::F32Ne | Op::F32Lt | Op::F32Gt | Op::F32Le | Op::F32Ge |
+        Op::F64Eq | Op::F64Ne | Op::F64Lt | Op::F64Gt | Op::F64Le | Op::F64Ge |
+        Op::I32Clz | Op::I32Ctz | Op::I32Popcnt | Op::I32Add | Op::I32Sub | Op::I32Mul |
+        Op::I32DivS | Op::I32DivU | Op::I32RemS | Op::I32RemU | Op::I32And | Op::I32Or |
+        Op::I32Xor | Op::I32Shl | Op::I32ShrS | Op::I32ShrU | Op::I32Rotl | Op::I32Rotr |
+        Op::I64Clz | Op::I64Ctz | Op::I64Popcnt | Op::I64Add | Op::I64Sub | Op::I64Mul |
+        Op::I64DivS | Op::I64DivU | Op::I64RemS | Op::I64RemU | Op::I64And | Op::I64Or |
+        Op::I64Xor | Op::I64Shl | Op::I64ShrS | Op::I64ShrU | Op::I64Rotl | Op::I64Rotr |
+        Op::F32Abs | Op::F32Neg | Op::F32Ceil | Op::F32Floor | Op::F32Trunc |
+        Op::F32Nearest | Op::F32Sqrt | Op::F32Add | Op::F32Sub | Op::F32Mul | Op::F32Div |
+        Op::F32Min | Op::F32Max | Op::F32Copysign |
+        Op::F64Abs | Op::F64Neg | Op::F64Ceil | Op::F64Floor | Op::F64Trunc |
+        Op::F64Nearest | Op::F64Sqrt | Op::F64Add | Op::F64Sub | Op::F64Mul | Op::F64Div |
+        Op::F64Min | Op::F64Max | Op::F64Copysign |
+        Op::I32WrapI64 | Op::I32TruncF32S | Op::I32TruncF32U | Op::I32TruncF64S |
+        Op::I32TruncF64U | Op::I64ExtendI32S | Op::I64ExtendI32U | Op::I64TruncF32S |
+        Op::I64TruncF32U | Op::I64TruncF64S | Op::I64TruncF64U | Op::F32ConvertI32S |
+        Op::F32ConvertI32U | Op::F32ConvertI64S | Op::F32ConvertI64U | Op::F32DemoteF64 |
+        Op::F64ConvertI32S | Op::F64ConvertI32U | Op::F64ConvertI64S |
+        Op::F64ConvertI64U | Op::F64PromoteF32 | Op::I32ReinterpretF32 |
+        Op::I64ReinterpretF64 | Op::F32ReinterpretI32 | Op::F64ReinterpretI64 |
+        Op::I32Extend8S | Op::I32Extend16S | Op::I64Extend8S | Op::I64Extend16S |
+        Op::I64Extend32S | Op::I32TruncSatF32S | Op::I32TruncSatF32U |
+        Op::I32TruncSatF64S | Op::I32TruncSatF64U | Op::I64TruncSatF32S |
+        Op::I64TruncSatF32U | Op::I64TruncSatF64S | Op::I64TruncSatF64U
+    )
+}
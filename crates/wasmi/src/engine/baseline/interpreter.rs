@@ -0,0 +1,640 @@
+use super::{BaselineCode, BaselineParams, BaselineResults, MAX_DEPTH};
+use crate::{
+    core::{wasm, TrapCode, UntypedVal},
+    error::EntityGrowError,
+    Error,
+    Func,
+    FuncRef,
+    Instance,
+    Memory,
+    Store,
+    StoreContextMut,
+};
+use alloc::{format, vec, vec::Vec};
+use wasmparser::{BrTable, MemArg, Operator};
+
+/// A Wasm control frame of the baseline tier.
+#[derive(Debug, Copy, Clone)]
+struct ControlFrame {
+    /// Is `true` if the control frame is a Wasm `loop`.
+    is_loop: bool,
+    /// The index of the operator that started the control frame.
+    start: usize,
+    /// The index of the matching `end` operator.
+    end: usize,
+    /// The height of the value stack below the parameters of the control frame.
+    height: usize,
+    /// The number of values carried by branches to the control frame.
+    len_branch_values: usize,
+}
+
+/// Interprets a [`BaselineCode`] for a single call.
+#[derive(Debug)]
+struct Interpreter<'a> {
+    /// The interpreted Wasm function.
+    code: &'a BaselineCode,
+    /// The [`Instance`] of the interpreted Wasm function.
+    instance: Instance,
+    /// The nesting depth of interpreted Wasm functions.
+    depth: u32,
+    /// The parameters and local variables of the interpreted Wasm function.
+    locals: Vec<UntypedVal>,
+    /// The Wasm value stack.
+    stack: Vec<UntypedVal>,
+    /// The Wasm control stack.
+    frames: Vec<ControlFrame>,
+    /// The index of the next operator to execute.
+    pc: usize,
+    /// The number of loop iterations run so far.
+    len_iterations: u32,
+}
+
+impl BaselineCode {
+    /// Executes the [`BaselineCode`] of a Wasm function of `instance` with `params`.
+    ///
+    /// Returns the results of the call.
+    ///
+    /// # Errors
+    ///
+    /// If the execution traps.
+    pub fn execute<T>(
+        &self,
+        store: &mut Store<T>,
+        instance: Instance,
+        params: impl IntoIterator<Item = UntypedVal>,
+        depth: u32,
+    ) -> Result<Vec<UntypedVal>, Error> {
+        let mut locals: Vec<UntypedVal> = params.into_iter().collect();
+        locals.resize(locals.len() + self.len_locals(), UntypedVal::default());
+        // Note: the function body itself is the outermost Wasm block.
+        let body = ControlFrame {
+            is_loop: false,
+            start: 0,
+            end: self.ops().len() - 1,
+            height: 0,
+            len_branch_values: self.len_results(),
+        };
+        let mut interpreter = Interpreter {
+            code: self,
+            instance,
+            depth,
+            locals,
+            stack: Vec::new(),
+            frames: vec![body],
+            pc: 0,
+            len_iterations: 0,
+        };
+        let result = interpreter.run(store);
+        // Note: loop iterations count towards tier-up even if the execution trapped.
+        store
+            .engine()
+            .inner
+            .code_map
+            .add_hotness(self.func(), interpreter.len_iterations);
+        result?;
+        Ok(interpreter.stack)
+    }
+}
+
+impl Interpreter<'_> {
+    /// Runs the interpreted Wasm function until it returns or traps.
+    fn run<T>(&mut self, store: &mut Store<T>) -> Result<(), Error> {
+        use Operator as Op;
+        let code = self.code;
+        while let Some(op) = code.ops().get(self.pc) {
+            self.pc += 1;
+            match op {
+                Op::Unreachable => return Err(Error::from(TrapCode::UnreachableCodeReached)),
+                Op::Nop => {}
+                Op::Block { .. } => self.enter_block(false),
+                Op::Loop { .. } => self.enter_block(true),
+                Op::If { .. } => {
+                    let condition = self.pop();
+                    let info = code.block(self.pc - 1);
+                    self.enter_block(false);
+                    if u32::from(condition) == 0 {
+                        // Note: continues with the `else` branch or the `end` of the `if`.
+                        self.pc = info.else_or_end as usize;
+                        if info.else_or_end != info.end {
+                            self.pc += 1;
+                        }
+                    }
+                }
+                Op::Else => {
+                    // Note: the `then` branch of the `if` finished.
+                    let frame = self.pop_frame();
+                    self.pc = frame.end + 1;
+                }
+                Op::End => {
+                    self.pop_frame();
+                }
+                Op::Br { relative_depth } => self.branch(*relative_depth),
+                Op::BrIf { relative_depth } => {
+                    if u32::from(self.pop()) != 0 {
+                        self.branch(*relative_depth)
+                    }
+                }
+                Op::BrTable { targets } => {
+                    let index = u32::from(self.pop());
+                    self.branch(branch_table_target(targets, index));
+                }
+                Op::Return => self.branch(self.frames.len() as u32 - 1),
+                Op::Call { function_index } => {
+                    let func = self.func(store, *function_index);
+                    self.call(store, func)?;
+                }
+                Op::CallIndirect {
+                    type_index,
+                    table_index,
+                } => {
+                    let func = self.indirect_func(store, *type_index, *table_index)?;
+                    self.call(store, func)?;
+                }
+                Op::Drop => {
+                    self.pop();
+                }
+                Op::Select | Op::TypedSelect { .. } => {
+                    let condition = self.pop();
+                    let if_false = self.pop();
+                    let if_true = self.pop();
+                    match u32::from(condition) != 0 {
+                        true => self.push(if_true),
+                        false => self.push(if_false),
+                    }
+                }
+                Op::LocalGet { local_index } => {
+                    let value = self.locals[*local_index as usize];
+                    self.push(value);
+                }
+                Op::LocalSet { local_index } => {
+                    let value = self.pop();
+                    self.locals[*local_index as usize] = value;
+                }
+                Op::LocalTee { local_index } => {
+                    let value = self.peek();
+                    self.locals[*local_index as usize] = value;
+                }
+                Op::GlobalGet { global_index } => {
+                    let global = self.instance_entity(store).get_global(*global_index);
+                    let global = global.unwrap_or_else(|| panic!("missing global: {global_index}"));
+                    let value = store.inner.resolve_global(&global).get_untyped();
+                    self.push(value);
+                }
+                Op::GlobalSet { global_index } => {
+                    let global = self.instance_entity(store).get_global(*global_index);
+                    let global = global.unwrap_or_else(|| panic!("missing global: {global_index}"));
+                    let value = self.pop();
+                    store.inner.resolve_global_mut(&global).set_untyped(value);
+                }
+                Op::I32Load { memarg } | Op::F32Load { memarg } => {
+                    self.load(store, memarg, wasm::load32)?
+                }
+                Op::I64Load { memarg } | Op::F64Load { memarg } => {
+                    self.load(store, memarg, wasm::load64)?
+                }
+                Op::I32Load8S { memarg } => self.load(store, memarg, wasm::i32_load8_s)?,
+                Op::I32Load8U { memarg } => self.load(store, memarg, wasm::i32_load8_u)?,
+                Op::I32Load16S { memarg } => self.load(store, memarg, wasm::i32_load16_s)?,
+                Op::I32Load16U { memarg } => self.load(store, memarg, wasm::i32_load16_u)?,
+                Op::I64Load8S { memarg } => self.load(store, memarg, wasm::i64_load8_s)?,
+                Op::I64Load8U { memarg } => self.load(store, memarg, wasm::i64_load8_u)?,
+                Op::I64Load16S { memarg } => self.load(store, memarg, wasm::i64_load16_s)?,
+                Op::I64Load16U { memarg } => self.load(store, memarg, wasm::i64_load16_u)?,
+                Op::I64Load32S { memarg } => self.load(store, memarg, wasm::i64_load32_s)?,
+                Op::I64Load32U { memarg } => self.load(store, memarg, wasm::i64_load32_u)?,
+                Op::I32Store { memarg } | Op::F32Store { memarg } => {
+                    self.store(store, memarg, wasm::store32)?
+                }
+                Op::I64Store { memarg } | Op::F64Store { memarg } => {
+                    self.store(store, memarg, wasm::store64)?
+                }
+                Op::I32Store8 { memarg } => self.store(store, memarg, wasm::i32_store8)?,
+                Op::I32Store16 { memarg } => self.store(store, memarg, wasm::i32_store16)?,
+                Op::I64Store8 { memarg } => self.store(store, memarg, wasm::i64_store8)?,
+                Op::I64Store16 { memarg } => self.store(store, memarg, wasm::i64_store16)?,
+                Op::I64Store32 { memarg } => self.store(store, memarg, wasm::i64_store32)?,
+                Op::MemorySize { mem } => {
+                    let memory = self.memory(store, *mem);
+                    let size = store.inner.resolve_memory(&memory).size();
+                    self.push(size);
+                }
+                Op::MemoryGrow { mem } => self.memory_grow(store, *mem)?,
+                Op::I32Const { value } => self.push(*value),
+                Op::I64Const { value } => self.push(*value),
+                Op::F32Const { value } => self.push(value.bits()),
+                Op::F64Const { value } => self.push(value.bits()),
+                Op::RefNull { .. } => self.push(FuncRef::null()),
+                Op::RefIsNull => self.unary(|value: u64| value == 0),
+                Op::RefFunc { function_index } => {
+                    let func = self.func(store, *function_index);
+                    self.push(FuncRef::new(func));
+                }
+                Op::I32Eqz => self.unary(wasm::i32_eqz),
+                Op::I32Eq => self.binary(wasm::i32_eq),
+                Op::I32Ne => self.binary(wasm::i32_ne),
+                Op::I32LtS => self.binary(wasm::i32_lt_s),
+                Op::I32LtU => self.binary(wasm::i32_lt_u),
+                Op::I32GtS => self.binary(wasm::i32_gt_s),
+                Op::I32GtU => self.binary(wasm::i32_gt_u),
+                Op::I32LeS => self.binary(wasm::i32_le_s),
+                Op::I32LeU => self.binary(wasm::i32_le_u),
+                Op::I32GeS => self.binary(wasm::i32_ge_s),
+                Op::I32GeU => self.binary(wasm::i32_ge_u),
+                Op::I64Eqz => self.unary(wasm::i64_eqz),
+                Op::I64Eq => self.binary(wasm::i64_eq),
+                Op::I64Ne => self.binary(wasm::i64_ne),
+                Op::I64LtS => self.binary(wasm::i64_lt_s),
+                Op::I64LtU => self.binary(wasm::i64_lt_u),
+                Op::I64GtS => self.binary(wasm::i64_gt_s),
+                Op::I64GtU => self.binary(wasm::i64_gt_u),
+                Op::I64LeS => self.binary(wasm::i64_le_s),
+                Op::I64LeU => self.binary(wasm::i64_le_u),
+                Op::I64GeS => self.binary(wasm::i64_ge_s),
+                Op::I64GeU => self.binary(wasm::i64_ge_u),
+                Op::F32Eq => self.binary(wasm::f32_eq),
+                Op::F32Ne => self.binary(wasm::f32_ne),
+                Op::F32Lt => self.binary(wasm::f32_lt),
+                Op::F32Gt => self.binary(wasm::f32_gt),
+                Op::F32Le => self.binary(wasm::f32_le),
+                Op::F32Ge => self.binary(wasm::f32_ge),
+                Op::F64Eq => self.binary(wasm::f64_eq),
+                Op::F64Ne => self.binary(wasm::f64_ne),
+                Op::F64Lt => self.binary(wasm::f64_lt),
+                Op::F64Gt => self.binary(wasm::f64_gt),
+                Op::F64Le => self.binary(wasm::f64_le),
+                Op::F64Ge => self.binary(wasm::f64_ge),
+                Op::I32Clz => self.unary(wasm::i32_clz),
+                Op::I32Ctz => self.unary(wasm::i32_ctz),
+                Op::I32Popcnt => self.unary(wasm::i32_popcnt),
+                Op::I32Add => self.binary(wasm::i32_add),
+                Op::I32Sub => self.binary(wasm::i32_sub),
+                Op::I32Mul => self.binary(wasm::i32_mul),
+                Op::I32DivS => self.try_binary(wasm::i32_div_s)?,
+                Op::I32DivU => self.try_binary(wasm::i32_div_u)?,
+                Op::I32RemS => self.try_binary(wasm::i32_rem_s)?,
+                Op::I32RemU => self.try_binary(wasm::i32_rem_u)?,
+                Op::I32And => self.binary(wasm::i32_and),
+                Op::I32Or => self.binary(wasm::i32_or),
+                Op::I32Xor => self.binary(wasm::i32_xor),
+                Op::I32Shl => self.binary(wasm::i32_shl),
+                Op::I32ShrS => self.binary(wasm::i32_shr_s),
+                Op::I32ShrU => self.binary(wasm::i32_shr_u),
+                Op::I32Rotl => self.binary(wasm::i32_rotl),
+                Op::I32Rotr => self.binary(wasm::i32_rotr),
+                Op::I64Clz => self.unary(wasm::i64_clz),
+                Op::I64Ctz => self.unary(wasm::i64_ctz),
+                Op::I64Popcnt => self.unary(wasm::i64_popcnt),
+                Op::I64Add => self.binary(wasm::i64_add),
+                Op::I64Sub => self.binary(wasm::i64_sub),
+                Op::I64Mul => self.binary(wasm::i64_mul),
+                Op::I64DivS => self.try_binary(wasm::i64_div_s)?,
+                Op::I64DivU => self.try_binary(wasm::i64_div_u)?,
+                Op::I64RemS => self.try_binary(wasm::i64_rem_s)?,
+                Op::I64RemU => self.try_binary(wasm::i64_rem_u)?,
+                Op::I64And => self.binary(wasm::i64_and),
+                Op::I64Or => self.binary(wasm::i64_or),
+                Op::I64Xor => self.binary(wasm::i64_xor),
+                Op::I64Shl => self.binary(wasm::i64_shl),
+                Op::I64ShrS => self.binary(wasm::i64_shr_s),
+                Op::I64ShrU => self.binary(wasm::i64_shr_u),
+                Op::I64Rotl => self.binary(wasm::i64_rotl),
+                Op::I64Rotr => self.binary(wasm::i64_rotr),
+                Op::F32Abs => self.unary(wasm::f32_abs),
+                Op::F32Neg => self.unary(wasm::f32_neg),
+                Op::F32Ceil => self.unary(wasm::f32_ceil),
+                Op::F32Floor => self.unary(wasm::f32_floor),
+                Op::F32Trunc => self.unary(wasm::f32_trunc),
+                Op::F32Nearest => self.unary(wasm::f32_nearest),
+                Op::F32Sqrt => self.unary(wasm::f32_sqrt),
+                Op::F32Add => self.binary(wasm::f32_add),
+                Op::F32Sub => self.binary(wasm::f32_sub),
+                Op::F32Mul => self.binary(wasm::f32_mul),
+                Op::F32Div => self.binary(wasm::f32_div),
+                Op::F32Min => self.binary(wasm::f32_min),
+                Op::F32Max => self.binary(wasm::f32_max),
+                Op::F32Copysign => self.binary(wasm::f32_copysign),
+                Op::F64Abs => self.unary(wasm::f64_abs),
+                Op::F64Neg => self.unary(wasm::f64_neg),
+                Op::F64Ceil => self.unary(wasm::f64_ceil),
+                Op::F64Floor => self.unary(wasm::f64_floor),
+                Op::F64Trunc => self.unary(wasm::f64_trunc),
+                Op::F64Nearest => self.unary(wasm::f64_nearest),
+                Op::F64Sqrt => self.unary(wasm::f64_sqrt),
+                Op::F64Add => self.binary(wasm::f64_add),
+                Op::F64Sub => self.binary(wasm::f64_sub),
+                Op::F64Mul => self.binary(wasm::f64_mul),
+                Op::F64Div => self.binary(wasm::f64_div),
+                Op::F64Min => self.binary(wasm::f64_min),
+                Op::F64Max => self.binary(wasm::f64_max),
+                Op::F64Copysign => self.binary(wasm::f64_copysign),
+                Op::I32WrapI64 => self.unary(wasm::i32_wrap_i64),
+                Op::I32TruncF32S => self.try_unary(wasm::i32_trunc_f32_s)?,
+                Op::I32TruncF32U => self.try_unary(wasm::i32_trunc_f32_u)?,
+                Op::I32TruncF64S => self.try_unary(wasm::i32_trunc_f64_s)?,
+                Op::I32TruncF64U => self.try_unary(wasm::i32_trunc_f64_u)?,
+                Op::I64ExtendI32S => self.unary(wasm::i64_extend_i32_s),
+                Op::I64ExtendI32U => self.unary(<u64 as From<u32>>::from),
+                Op::I64TruncF32S => self.try_unary(wasm::i64_trunc_f32_s)?,
+                Op::I64TruncF32U => self.try_unary(wasm::i64_trunc_f32_u)?,
+                Op::I64TruncF64S => self.try_unary(wasm::i64_trunc_f64_s)?,
+                Op::I64TruncF64U => self.try_unary(wasm::i64_trunc_f64_u)?,
+                Op::F32ConvertI32S => self.unary(wasm::f32_convert_i32_s),
+                Op::F32ConvertI32U => self.unary(wasm::f32_convert_i32_u),
+                Op::F32ConvertI64S => self.unary(wasm::f32_convert_i64_s),
+                Op::F32ConvertI64U => self.unary(wasm::f32_convert_i64_u),
+                Op::F32DemoteF64 => self.unary(wasm::f32_demote_f64),
+                Op::F64ConvertI32S => self.unary(wasm::f64_convert_i32_s),
+                Op::F64ConvertI32U => self.unary(wasm::f64_convert_i32_u),
+                Op::F64ConvertI64S => self.unary(wasm::f64_convert_i64_s),
+                Op::F64ConvertI64U => self.unary(wasm::f64_convert_i64_u),
+                Op::F64PromoteF32 => self.unary(wasm::f64_promote_f32),
+                Op::I32ReinterpretF32
+                | Op::I64ReinterpretF64
+                | Op::F32ReinterpretI32
+                | Op::F64ReinterpretI64 => {
+                    // Note: reinterpretations do not change the bits of untyped values.
+                }
+                Op::I32Extend8S => self.unary(wasm::i32_extend8_s),
+                Op::I32Extend16S => self.unary(wasm::i32_extend16_s),
+                Op::I64Extend8S => self.unary(wasm::i64_extend8_s),
+                Op::I64Extend16S => self.unary(wasm::i64_extend16_s),
+                Op::I64Extend32S => self.unary(wasm::i64_extend32_s),
+                Op::I32TruncSatF32S => self.unary(wasm::i32_trunc_sat_f32_s),
+                Op::I32TruncSatF32U => self.unary(wasm::i32_trunc_sat_f32_u),
+                Op::I32TruncSatF64S => self.unary(wasm::i32_trunc_sat_f64_s),
+                Op::I32TruncSatF64U => self.unary(wasm::i32_trunc_sat_f64_u),
+                Op::I64TruncSatF32S => self.unary(wasm::i64_trunc_sat_f32_s),
+                Op::I64TruncSatF32U => self.unary(wasm::i64_trunc_sat_f32_u),
+                Op::I64TruncSatF64S => self.unary(wasm::i64_trunc_sat_f64_s),
+                Op::I64TruncSatF64U => self.unary(wasm::i64_trunc_sat_f64_u),
+                op => {
+                    // Note: function bodies with unsupported operators are rejected up front
+                    //       by `BaselineCode::new` and never reach the baseline tier.
+                    //       Still, this must not panic the host.
+                    return Err(Error::new(format!(
+                        "unsupported operator in baseline tier: {op:?}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Pushes `value` onto the value stack.
+    fn push(&mut self, value: impl Into<UntypedVal>) {
+        self.stack.push(value.into());
+    }
+
+    /// Pops the top-most value from the value stack.
+    fn pop(&mut self) -> UntypedVal {
+        self.stack
+            .pop()
+            .unwrap_or_else(|| panic!("value stack underflow in validated Wasm"))
+    }
+
+    /// Returns the top-most value of the value stack.
+    fn peek(&self) -> UntypedVal {
+        self.stack
+            .last()
+            .copied()
+            .unwrap_or_else(|| panic!("value stack underflow in validated Wasm"))
+    }
+
+    /// Executes the unary Wasm operator `f`.
+    fn unary<P, R>(&mut self, f: impl FnOnce(P) -> R)
+    where
+        P: From<UntypedVal>,
+        R: Into<UntypedVal>,
+    {
+        let value = self.pop();
+        self.push(f(P::from(value)));
+    }
+
+    /// Executes the fallible unary Wasm operator `f`.
+    fn try_unary<P, R>(&mut self, f: impl FnOnce(P) -> Result<R, TrapCode>) -> Result<(), Error>
+    where
+        P: From<UntypedVal>,
+        R: Into<UntypedVal>,
+    {
+        let value = self.pop();
+        self.push(f(P::from(value))?);
+        Ok(())
+    }
+
+    /// Executes the binary Wasm operator `f`.
+    fn binary<L, R, O>(&mut self, f: impl FnOnce(L, R) -> O)
+    where
+        L: From<UntypedVal>,
+        R: From<UntypedVal>,
+        O: Into<UntypedVal>,
+    {
+        let rhs = self.pop();
+        let lhs = self.pop();
+        self.push(f(L::from(lhs), R::from(rhs)));
+    }
+
+    /// Executes the fallible binary Wasm operator `f`.
+    fn try_binary<L, R, O>(
+        &mut self,
+        f: impl FnOnce(L, R) -> Result<O, TrapCode>,
+    ) -> Result<(), Error>
+    where
+        L: From<UntypedVal>,
+        R: From<UntypedVal>,
+        O: Into<UntypedVal>,
+    {
+        let rhs = self.pop();
+        let lhs = self.pop();
+        self.push(f(L::from(lhs), R::from(rhs))?);
+        Ok(())
+    }
+
+    /// Enters the Wasm `block`, `loop` or `if` of the previous operator.
+    fn enter_block(&mut self, is_loop: bool) {
+        let start = self.pc - 1;
+        let info = self.code.block(start);
+        let len_branch_values = match is_loop {
+            true => info.len_params,
+            false => info.len_results,
+        };
+        self.frames.push(ControlFrame {
+            is_loop,
+            start,
+            end: info.end as usize,
+            height: self.stack.len() - usize::from(info.len_params),
+            len_branch_values: usize::from(len_branch_values),
+        });
+    }
+
+    /// Pops the top-most [`ControlFrame`] from the control stack.
+    fn pop_frame(&mut self) -> ControlFrame {
+        self.frames
+            .pop()
+            .unwrap_or_else(|| panic!("control stack underflow in validated Wasm"))
+    }
+
+    /// Branches to the control frame at `depth`.
+    fn branch(&mut self, depth: u32) {
+        let index = self.frames.len() - 1 - depth as usize;
+        let frame = self.frames[index];
+        let len_values = self.stack.len();
+        self.stack
+            .drain(frame.height..len_values - frame.len_branch_values);
+        match frame.is_loop {
+            true => {
+                self.len_iterations = self.len_iterations.saturating_add(1);
+                self.frames.truncate(index + 1);
+                self.pc = frame.start + 1;
+            }
+            false => {
+                self.frames.truncate(index);
+                self.pc = frame.end + 1;
+            }
+        }
+    }
+
+    /// Returns the [`InstanceEntity`] of the interpreted Wasm function.
+    ///
+    /// [`InstanceEntity`]: crate::instance::InstanceEntity
+    fn instance_entity<'a, T>(&self, store: &'a Store<T>) -> &'a crate::instance::InstanceEntity {
+        store.inner.resolve_instance(&self.instance)
+    }
+
+    /// Returns the [`Func`] at `index` of the [`Instance`].
+    fn func<T>(&self, store: &Store<T>, index: u32) -> Func {
+        self.instance_entity(store)
+            .get_func(index)
+            .unwrap_or_else(|| panic!("missing function: {index}"))
+    }
+
+    /// Returns the [`Memory`] at `index` of the [`Instance`].
+    fn memory<T>(&self, store: &Store<T>, index: u32) -> Memory {
+        self.instance_entity(store)
+            .get_memory(index)
+            .unwrap_or_else(|| panic!("missing linear memory: {index}"))
+    }
+
+    /// Executes a Wasm load operator using `load`.
+    fn load<T, V>(
+        &mut self,
+        store: &Store<T>,
+        memarg: &MemArg,
+        load: impl FnOnce(&[u8], u64, u64) -> Result<V, TrapCode>,
+    ) -> Result<(), Error>
+    where
+        V: Into<UntypedVal>,
+    {
+        let memory = self.memory(store, memarg.memory);
+        let ptr = u64::from(self.pop());
+        let value = load(
+            store.inner.resolve_memory(&memory).data(),
+            ptr,
+            memarg.offset,
+        )?;
+        self.push(value);
+        Ok(())
+    }
+
+    /// Executes a Wasm store operator using `store`.
+    fn store<T, V>(
+        &mut self,
+        store: &mut Store<T>,
+        memarg: &MemArg,
+        f: impl FnOnce(&mut [u8], u64, u64, V) -> Result<(), TrapCode>,
+    ) -> Result<(), Error>
+    where
+        V: From<UntypedVal>,
+    {
+        let memory = self.memory(store, memarg.memory);
+        let value = V::from(self.pop());
+        let ptr = u64::from(self.pop());
+        let bytes = store.inner.resolve_memory_mut(&memory).data_mut();
+        f(bytes, ptr, memarg.offset, value)?;
+        Ok(())
+    }
+
+    /// Executes a Wasm `memory.grow` operator on the linear memory at `index`.
+    fn memory_grow<T>(&mut self, store: &mut Store<T>, index: u32) -> Result<(), Error> {
+        let memory = self.memory(store, index);
+        let delta = u64::from(self.pop());
+        let (inner, mut resource_limiter) = store.store_inner_and_resource_limiter_ref();
+        let (memory, fuel) = inner.resolve_memory_and_fuel_mut(&memory);
+        let result = match memory.grow(delta, Some(fuel), &mut resource_limiter) {
+            Ok(result) => result,
+            Err(EntityGrowError::InvalidGrow) => match memory.ty().is_64() {
+                true => EntityGrowError::ERROR_CODE_64,
+                false => EntityGrowError::ERROR_CODE_32,
+            },
+            Err(EntityGrowError::TrapCode(trap_code)) => return Err(Error::from(trap_code)),
+        };
+        self.push(result);
+        Ok(())
+    }
+
+    /// Returns the [`Func`] called by a Wasm `call_indirect` operator.
+    fn indirect_func<T>(
+        &mut self,
+        store: &mut Store<T>,
+        type_index: u32,
+        table_index: u32,
+    ) -> Result<Func, Error> {
+        let index = u64::from(self.pop());
+        let instance = self.instance_entity(store);
+        let table = instance
+            .get_table(table_index)
+            .unwrap_or_else(|| panic!("missing table: {table_index}"));
+        let expected_signature = *instance
+            .get_signature(type_index)
+            .unwrap_or_else(|| panic!("missing function type: {type_index}"));
+        let funcref = store
+            .inner
+            .resolve_table_mut(&table)
+            .get_untyped_materialized(index)
+            .map(FuncRef::from)
+            .ok_or(TrapCode::TableOutOfBounds)?;
+        let func = *funcref.func().ok_or(TrapCode::IndirectCallToNull)?;
+        let actual_signature = store.inner.resolve_func(&func).ty_dedup();
+        if *actual_signature != expected_signature {
+            return Err(Error::from(TrapCode::BadSignature));
+        }
+        Ok(func)
+    }
+
+    /// Calls `func` with parameters from the value stack and pushes its results.
+    ///
+    /// # Note
+    ///
+    /// Interprets `func` if it is eligible for the baseline tier, otherwise executes it
+    /// as usual which compiles `func` if it has not yet been compiled.
+    fn call<T>(&mut self, store: &mut Store<T>, func: Func) -> Result<(), Error> {
+        let engine = store.engine().clone();
+        let func_type = store.inner.resolve_func(&func).ty_dedup();
+        let (len_params, len_results) =
+            engine.resolve_func_type(func_type, |ty| (ty.params().len(), ty.results().len()));
+        let params = self.stack.split_off(self.stack.len() - len_params);
+        if self.depth < MAX_DEPTH {
+            if let Some((instance, code)) = engine.inner.take_baseline(store, &func) {
+                let results = code.execute(store, instance, params, self.depth + 1)?;
+                self.stack.extend(results);
+                return Ok(());
+            }
+        }
+        let mut results = vec![UntypedVal::default(); len_results];
+        engine.inner.execute_func_compiled(
+            StoreContextMut { store },
+            &func,
+            BaselineParams(&params),
+            BaselineResults(&mut results),
+        )?;
+        self.stack.extend(results);
+        Ok(())
+    }
+}
+
+/// Returns the branch depth of the Wasm `br_table` `targets` for `index`.
+fn branch_table_target(targets: &BrTable, index: u32) -> u32 {
+    match targets.targets().nth(index as usize) {
+        Some(Ok(depth)) => depth,
+        _ => targets.default(),
+    }
+}
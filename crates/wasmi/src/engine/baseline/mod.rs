@@ -0,0 +1,106 @@
+//! The baseline tier that interprets validated Wasm bytecode without translation.
+//!
+//! # Note
+//!
+//! Translating a Wasm function to Wasmi bytecode pays off only if the Wasm function
+//! is executed often enough. The baseline tier instead interprets calls of a Wasm
+//! function directly from its Wasm bytecode which is slower to execute but does not
+//! require any translation. This speeds up workloads that call most of their Wasm
+//! functions just once, for example initialization routines.
+//!
+//! The baseline tier tracks the hotness of every interpreted Wasm function, that is
+//! the number of its interpreted calls and loop iterations. Once a Wasm function
+//! becomes hot it tiers up: its next call translates it to Wasmi bytecode which
+//! is then used by all of its subsequent calls.
+
+mod code;
+mod interpreter;
+
+pub use self::code::BaselineCode;
+use super::{CallParams, CallResults, EngineInner};
+use crate::{core::UntypedVal, Func, FuncEntity, Instance, Store};
+use alloc::sync::Arc;
+use core::{iter, slice};
+
+/// The maximum nesting depth of Wasm functions interpreted by the baseline tier.
+///
+/// Calls from interpreted Wasm functions beyond this depth translate their callee
+/// so that the baseline tier does not exhaust the native stack.
+const MAX_DEPTH: u32 = 16;
+
+/// Returns `true` if executions within `store` may use the baseline tier.
+///
+/// # Note
+///
+/// Fuel metering, code coverage, branch profiling, call hooks, instruction limits and
+/// callbacks, deadlines, trace hashing, global hooks and memory protection all require
+/// Wasmi bytecode.
+/// Stackless execution forbids the recursive calls of the baseline tier.
+fn is_enabled<T>(store: &Store<T>) -> bool {
+    let config = store.engine().config();
+    let inner = &store.inner;
+    config.get_baseline_tier()
+        && !config.get_stackless()
+        && !config.get_consume_fuel()
+        && !cfg!(feature = "branch-profile")
+        && !inner.is_covering()
+        && !inner.is_tracing()
+        && !inner.has_watched_globals()
+        && !inner.has_protected_memories()
+        && inner.instr_limit().is_none()
+        && inner.instr_callback_interval().is_none()
+        && inner.deadline_interval().is_none()
+        && !store.has_call_hook()
+}
+
+impl EngineInner {
+    /// Takes the [`BaselineCode`] of `func` if its next call shall be run by the baseline tier.
+    ///
+    /// Returns the [`Instance`] of `func` alongside its [`BaselineCode`].
+    pub(super) fn take_baseline<T>(
+        &self,
+        store: &Store<T>,
+        func: &Func,
+    ) -> Option<(Instance, Arc<BaselineCode>)> {
+        if !is_enabled(store) {
+            return None;
+        }
+        let FuncEntity::Wasm(func) = store.inner.resolve_func(func) else {
+            return None;
+        };
+        let engine = store.engine();
+        let threshold = engine.config().get_tier_up_threshold();
+        let code = self
+            .code_map
+            .take_baseline(func.func_body(), threshold, engine)?;
+        Some((*func.instance(), code))
+    }
+}
+
+/// The untyped parameters of a call from the baseline tier.
+#[derive(Debug)]
+struct BaselineParams<'a>(&'a [UntypedVal]);
+
+impl<'a> CallParams for BaselineParams<'a> {
+    type Params = iter::Copied<slice::Iter<'a, UntypedVal>>;
+
+    fn call_params(self) -> Self::Params {
+        self.0.iter().copied()
+    }
+}
+
+/// The untyped results of a call from the baseline tier.
+#[derive(Debug)]
+struct BaselineResults<'a>(&'a mut [UntypedVal]);
+
+impl CallResults for BaselineResults<'_> {
+    type Results = ();
+
+    fn len_results(&self) -> usize {
+        self.0.len()
+    }
+
+    fn call_results(self, results: &[UntypedVal]) -> Self::Results {
+        self.0.copy_from_slice(results);
+    }
+}
@@ -0,0 +1,237 @@
+use super::code_map::CompiledFuncRef;
+use crate::ir::Instruction;
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use core::{fmt, fmt::Display};
+
+/// A conditional branch instruction within the Wasmi bytecode of a compiled function.
+///
+/// # Note
+///
+/// Wasmi translates some Wasm conditional branches into Wasmi branches with negated
+/// conditions. For example the Wasmi branch of an `if` is taken if its Wasm condition
+/// is zero and branches to the `else` block.
+#[derive(Debug, Copy, Clone)]
+pub struct BranchSite {
+    /// The index of the conditional branch instruction within its compiled function.
+    instr: u32,
+    /// The offset of the Wasm operator of the conditional branch.
+    ///
+    /// This is relative to the start of the Wasm function body.
+    offset: u32,
+    /// Is `true` if the Wasmi branch is taken if the Wasm branch is not taken.
+    negated: bool,
+}
+
+impl BranchSite {
+    /// Creates a new [`BranchSite`] for the conditional branch instruction at `instr`.
+    pub fn new(instr: u32, offset: u32, negated: bool) -> Self {
+        Self {
+            instr,
+            offset,
+            negated,
+        }
+    }
+}
+
+/// The taken and not-taken counts of executed Wasmi conditional branch instructions.
+#[derive(Debug, Default)]
+pub struct BranchCounter {
+    /// The not-taken and taken counts keyed by the addresses of the executed [`Instruction`]s.
+    counts: BTreeMap<usize, [u64; 2]>,
+}
+
+impl BranchCounter {
+    /// Records that the conditional branch `instr` has been executed and whether it was `taken`.
+    pub fn record(&mut self, instr: &Instruction, taken: bool) {
+        let counts = self
+            .counts
+            .entry(instr as *const Instruction as usize)
+            .or_default();
+        counts[usize::from(taken)] += 1;
+    }
+
+    /// Adds all counts of `other` to `self`.
+    pub fn merge(&mut self, other: BranchCounter) {
+        if self.counts.is_empty() {
+            self.counts = other.counts;
+            return;
+        }
+        for (instr, [not_taken, taken]) in other.counts {
+            let counts = self.counts.entry(instr).or_default();
+            counts[0] += not_taken;
+            counts[1] += taken;
+        }
+    }
+
+    /// Returns the not-taken and taken counts of `site` within `func`.
+    fn get(&self, func: CompiledFuncRef, site: &BranchSite) -> [u64; 2] {
+        let instr = func.instrs().as_ptr().wrapping_add(site.instr as usize);
+        self.counts
+            .get(&(instr as usize))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// The taken and not-taken counts of a conditional branch within a Wasm module.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BranchCounts {
+    /// The index of the function within its Wasm module.
+    func: u32,
+    /// The offset of the Wasm operator of the conditional branch.
+    offset: u32,
+    /// The number of times the branch has been taken.
+    taken: u64,
+    /// The number of times the branch has not been taken.
+    not_taken: u64,
+}
+
+impl BranchCounts {
+    /// Returns the index of the function of the conditional branch within its Wasm module.
+    pub fn func(&self) -> u32 {
+        self.func
+    }
+
+    /// Returns the byte offset of the Wasm operator of the conditional branch.
+    ///
+    /// The offset is relative to the start of the Wasm function body,
+    /// which begins with the declaration of the function's local variables.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns the number of times the conditional branch has been taken.
+    ///
+    /// A `br_if` is taken if it branches to its label and an `if` is taken if it enters its `then` block.
+    pub fn taken(&self) -> u64 {
+        self.taken
+    }
+
+    /// Returns the number of times the conditional branch has not been taken.
+    pub fn not_taken(&self) -> u64 {
+        self.not_taken
+    }
+}
+
+/// The branch profile of the internal functions of a [`Module`] recorded by a [`Store`].
+///
+/// Computed via [`Store::branch_profile`].
+///
+/// # Note
+///
+/// - Profiled conditional branches are Wasm `if` and `br_if` operators.
+/// - Conditional branches with constant conditions are resolved during translation
+///   and are not part of the branch profile.
+/// - The [`Display`] implementation prints one conditional branch per line as
+///   its function index, its hexadecimal offset, its taken and its not-taken count.
+///
+/// [`Module`]: crate::Module
+/// [`Store`]: crate::Store
+/// [`Store::branch_profile`]: crate::Store::branch_profile
+#[derive(Debug, Clone)]
+pub struct BranchProfile {
+    /// The index of the first internal function within its Wasm module.
+    first_func: u32,
+    /// The number of internal functions.
+    len_funcs: u32,
+    /// The counts of all conditional branches ordered by function index and offset.
+    branches: Box<[BranchCounts]>,
+}
+
+impl BranchProfile {
+    /// Returns the index of the first internal function within its Wasm module.
+    ///
+    /// This is the number of imported functions.
+    pub fn first_func(&self) -> u32 {
+        self.first_func
+    }
+
+    /// Returns the number of internal functions.
+    pub fn len_funcs(&self) -> u32 {
+        self.len_funcs
+    }
+
+    /// Returns the counts of all conditional branches of the internal functions.
+    ///
+    /// Conditional branches are ordered by function index and offset.
+    pub fn branches(&self) -> &[BranchCounts] {
+        &self.branches
+    }
+
+    /// Returns the counts of the conditional branch of the function at `func_index` at `offset`.
+    ///
+    /// Returns `None` if there is no profiled conditional branch at this location.
+    pub fn get(&self, func_index: u32, offset: u32) -> Option<&BranchCounts> {
+        let index = self
+            .branches
+            .binary_search_by_key(&(func_index, offset), |branch| (branch.func, branch.offset))
+            .ok()?;
+        Some(&self.branches[index])
+    }
+}
+
+impl Display for BranchProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for branch in self.branches() {
+            writeln!(
+                f,
+                "{} {:#x} {} {}",
+                branch.func, branch.offset, branch.taken, branch.not_taken
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Incrementally builds the [`BranchProfile`] of the internal functions of a Wasm module.
+#[derive(Debug)]
+pub struct BranchProfileBuilder<'a> {
+    /// The recorded counts of the executed conditional branches.
+    counter: &'a BranchCounter,
+    /// The index of the first internal function within its Wasm module.
+    first_func: u32,
+    /// The number of functions pushed so far.
+    len_funcs: u32,
+    /// The counts of the conditional branches pushed so far.
+    branches: Vec<BranchCounts>,
+}
+
+impl<'a> BranchProfileBuilder<'a> {
+    /// Creates a new [`BranchProfileBuilder`] evaluating the counts recorded by `counter`.
+    pub fn new(counter: &'a BranchCounter, first_func: u32) -> Self {
+        Self {
+            counter,
+            first_func,
+            len_funcs: 0,
+            branches: Vec::new(),
+        }
+    }
+
+    /// Pushes the compiled function at `func_index` within its Wasm module.
+    pub fn push_func(&mut self, func_index: u32, func: CompiledFuncRef) {
+        debug_assert_eq!(func_index, self.first_func + self.len_funcs);
+        for site in func.branch_sites() {
+            let [not_taken, taken] = self.counter.get(func, site);
+            let (taken, not_taken) = match site.negated {
+                true => (not_taken, taken),
+                false => (taken, not_taken),
+            };
+            self.branches.push(BranchCounts {
+                func: func_index,
+                offset: site.offset,
+                taken,
+                not_taken,
+            });
+        }
+        self.len_funcs += 1;
+    }
+
+    /// Finishes building the [`BranchProfile`].
+    pub fn finish(self) -> BranchProfile {
+        BranchProfile {
+            first_func: self.first_func,
+            len_funcs: self.len_funcs,
+            branches: self.branches.into(),
+        }
+    }
+}
@@ -6,6 +6,10 @@
 //! register machine based bytecode functions.
 
 use super::{
+    baseline::BaselineCode,
+    metrics::FuncCompilationMetrics,
+    CoverageProbe,
+    EngineMemoryUsage,
     FuelCosts,
     FuncTranslationDriver,
     FuncTranslator,
@@ -20,19 +24,25 @@ use crate::{
     module::{FuncIdx, ModuleHeader},
     store::{Fuel, FuelError},
     Config,
+    Engine,
     Error,
+    FuelSchedule,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc};
 use core::{
     fmt,
     mem::{self, MaybeUninit},
     ops::{self, Range},
     pin::Pin,
     slice,
+    time::Duration,
 };
 use spin::Mutex;
 use wasmparser::{FuncToValidate, ValidatorResources, WasmFeatures};
 
+#[cfg(feature = "branch-profile")]
+use super::BranchSite;
+
 /// A reference to a compiled function stored in the [`CodeMap`] of an [`Engine`](crate::Engine).
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct EngineFunc(u32);
@@ -78,6 +88,10 @@ impl ArenaIndex for EngineFunc {
 #[derive(Debug)]
 pub struct CodeMap {
     funcs: Mutex<Arena<EngineFunc, FuncEntity>>,
+    /// The Wasm function bodies kept to compile functions for per-call [`FuelSchedule`]s.
+    sources: Mutex<BTreeMap<EngineFunc, FuncSource>>,
+    /// The functions compiled for per-call [`FuelSchedule`]s keyed by schedule version.
+    scheduled: Mutex<BTreeMap<(u32, EngineFunc), CompiledFuncEntity>>,
     features: WasmFeatures,
 }
 
@@ -220,6 +234,8 @@ impl CodeMap {
     pub fn new(config: &Config) -> Self {
         Self {
             funcs: Mutex::new(Arena::default()),
+            sources: Mutex::new(BTreeMap::new()),
+            scheduled: Mutex::new(BTreeMap::new()),
             features: config.wasm_features(),
         }
     }
@@ -261,6 +277,7 @@ impl CodeMap {
         &self,
         func: EngineFunc,
         func_idx: FuncIdx,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
@@ -271,14 +288,33 @@ impl CodeMap {
         };
         func.init_uncompiled(UncompiledFuncEntity::new(
             func_idx,
+            offset,
             bytes,
             module.clone(),
             func_to_validate,
         ));
     }
 
+    /// Keeps the Wasm function body of `func` to compile it for per-call [`FuelSchedule`]s.
+    pub fn init_func_source(
+        &self,
+        func: EngineFunc,
+        func_idx: FuncIdx,
+        bytes: &[u8],
+        module: &ModuleHeader,
+    ) {
+        let source = FuncSource {
+            func_index: func_idx,
+            bytes: bytes.into(),
+            module: module.clone(),
+        };
+        self.sources.lock().insert(func, source);
+    }
+
     /// Returns the [`FuncEntity`] of the [`EngineFunc`].
     ///
+    /// Returns `func` compiled for the [`FuelSchedule`] selected for the current call if any.
+    ///
     /// # Errors
     ///
     /// - If translation or Wasm validation of `func` failed.
@@ -290,12 +326,55 @@ impl CodeMap {
         fuel: Option<&mut Fuel>,
         func: EngineFunc,
     ) -> Result<CompiledFuncRef<'a>, Error> {
+        if let Some(schedule) = fuel.as_deref().and_then(Fuel::schedule) {
+            return self.get_scheduled(fuel, func, schedule);
+        }
         match self.get_compiled(func) {
             Some(cref) => Ok(cref),
             None => self.compile_or_wait(fuel, func),
         }
     }
 
+    /// Returns `func` compiled for the `schedule` and compiles it if necessary.
+    ///
+    /// # Note
+    ///
+    /// This first compiles `func` as usual in order to validate it if necessary.
+    ///
+    /// # Errors
+    ///
+    /// - If translation or Wasm validation of `func` failed.
+    /// - If `ctx` ran out of fuel in case fuel consumption is enabled.
+    /// - If the Wasm function body of `func` has not been kept.
+    #[cold]
+    #[inline(never)]
+    fn get_scheduled<'a>(
+        &'a self,
+        fuel: Option<&mut Fuel>,
+        func: EngineFunc,
+        schedule: FuelSchedule,
+    ) -> Result<CompiledFuncRef<'a>, Error> {
+        let key = (schedule.version(), func);
+        if let Some(entity) = self.scheduled.lock().get(&key) {
+            return Ok(self.adjust_cref_lifetime(CompiledFuncRef::from(entity)));
+        }
+        if self.get_compiled(func).is_none() {
+            self.compile_or_wait(fuel, func)?;
+        }
+        let Some(source) = self.sources.lock().get(&func).cloned() else {
+            return Err(Error::from(FuelError::ScheduleUnavailable));
+        };
+        // Note: it is important that compilation happens without locking the `CodeMap`
+        //       since compilation can take a prolonged time.
+        let func_index = source.func_index.into_u32();
+        let compiled_func = source
+            .compile(&schedule)
+            .map_err(|error| error.with_func_index(func_index))?;
+        let mut scheduled = self.scheduled.lock();
+        let entity = scheduled.entry(key).or_insert(compiled_func);
+        Ok(self.adjust_cref_lifetime(CompiledFuncRef::from(&*entity)))
+    }
+
     /// Compile `func` or wait for result if another process already started compilation.
     ///
     /// # Errors
@@ -315,6 +394,115 @@ impl CodeMap {
         }
     }
 
+    /// Adds the number of bytes held by the functions of the [`CodeMap`] to `usage`.
+    pub fn memory_usage(&self, usage: &mut EngineMemoryUsage) {
+        let funcs = self.funcs.lock();
+        for (_, func) in funcs.iter() {
+            usage.compiled_code += mem::size_of::<FuncEntity>();
+            match func {
+                FuncEntity::Compiled(func) => {
+                    usage.compiled_code += func.code_size();
+                    usage.const_pools += mem::size_of_val(&*func.consts);
+                }
+                FuncEntity::Uncompiled(func) => {
+                    usage.uncompiled_code += func.bytes.heap_size();
+                    if let BaselineState::Decoded(code) = &func.baseline {
+                        usage.uncompiled_code += code.heap_size();
+                    }
+                }
+                FuncEntity::Uninit | FuncEntity::Compiling | FuncEntity::FailedToCompile => {}
+            }
+        }
+        for source in self.sources.lock().values() {
+            usage.uncompiled_code += source.bytes.len();
+        }
+        for func in self.scheduled.lock().values() {
+            usage.compiled_code += func.code_size();
+            usage.const_pools += mem::size_of_val(&*func.consts);
+        }
+    }
+
+    /// Returns the [`FuncCompilationMetrics`] of `func` at `func_idx` if it has been compiled.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`CodeMap::get`] this never compiles `func`.
+    pub fn get_metrics(&self, func_idx: u32, func: EngineFunc) -> Option<FuncCompilationMetrics> {
+        let funcs = self.funcs.lock();
+        match funcs.get(func)? {
+            FuncEntity::Compiled(entity) => Some(FuncCompilationMetrics::new(
+                func_idx,
+                entity.into(),
+                entity.translation_time,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`BaselineCode`] of `func` if it shall be run by the baseline tier.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` if `func` has already been compiled, if it still requires Wasm
+    /// validation, if it uses operators unsupported by the baseline tier or if its
+    /// hotness reached `threshold` so that it is compiled instead.
+    /// Otherwise counts the call towards the hotness of `func`.
+    ///
+    /// The [`BaselineCode`] of `func` is decoded upon its first call and then shared.
+    pub fn take_baseline(
+        &self,
+        func: EngineFunc,
+        threshold: u32,
+        engine: &Engine,
+    ) -> Option<Arc<BaselineCode>> {
+        let (func_index, bytes, module) = {
+            let mut funcs = self.funcs.lock();
+            let FuncEntity::Uncompiled(entity) = funcs.get_mut(func)? else {
+                return None;
+            };
+            if entity.hotness >= threshold || entity.validation.is_some() {
+                return None;
+            }
+            match &entity.baseline {
+                BaselineState::Decoded(code) => {
+                    entity.hotness += 1;
+                    return Some(code.clone());
+                }
+                BaselineState::Unsupported => return None,
+                BaselineState::Undecoded => (
+                    entity.func_index,
+                    Arc::<[u8]>::from(entity.bytes.as_slice()),
+                    entity.module.clone(),
+                ),
+            }
+        };
+        // Note: decoding happens without holding the lock since it may take a while.
+        let code = BaselineCode::new(func, func_index, bytes, &module, engine).map(Arc::new);
+        let mut funcs = self.funcs.lock();
+        let FuncEntity::Uncompiled(entity) = funcs.get_mut(func)? else {
+            return None;
+        };
+        if let BaselineState::Undecoded = entity.baseline {
+            entity.baseline = match code {
+                Some(code) => BaselineState::Decoded(code),
+                None => BaselineState::Unsupported,
+            };
+        }
+        let BaselineState::Decoded(code) = &entity.baseline else {
+            return None;
+        };
+        entity.hotness += 1;
+        Some(code.clone())
+    }
+
+    /// Adds `delta` to the hotness of `func` if it has not yet been compiled.
+    pub fn add_hotness(&self, func: EngineFunc, delta: u32) {
+        let mut funcs = self.funcs.lock();
+        if let Some(FuncEntity::Uncompiled(entity)) = funcs.get_mut(func) {
+            entity.hotness = entity.hotness.saturating_add(delta);
+        }
+    }
+
     /// Returns the [`CompiledFuncRef`] of `func` if possible, otherwise returns `None`.
     #[inline]
     fn get_compiled(&self, func: EngineFunc) -> Option<CompiledFuncRef> {
@@ -376,7 +564,10 @@ impl CodeMap {
     ) -> Result<CompiledFuncRef<'a>, Error> {
         // Note: it is important that compilation happens without locking the `CodeMap`
         //       since compilation can take a prolonged time.
-        let compiled_func = entity.compile(fuel, &self.features);
+        let func_index = entity.func_index.into_u32();
+        let compiled_func = entity
+            .compile(fuel, &self.features)
+            .map_err(|error| error.with_func_index(func_index));
         let mut funcs = self.funcs.lock();
         let Some(entity) = funcs.get_mut(func) else {
             panic!("encountered invalid internal function: {func:?}")
@@ -546,6 +737,10 @@ pub struct TypeIndex(u32);
 pub struct UncompiledFuncEntity {
     /// The index of the function within the Wasm module.
     func_index: FuncIdx,
+    /// The position of the Wasm function body within the Wasm binary.
+    ///
+    /// This is used to report errors at their absolute offsets within the Wasm binary.
+    offset: usize,
     /// The Wasm binary bytes.
     bytes: SmallByteSlice,
     /// The Wasm module of the Wasm function.
@@ -557,12 +752,28 @@ pub struct UncompiledFuncEntity {
     ///
     /// This is `Some` if the [`UncompiledFuncEntity`] is to be validated upon compilation.
     validation: Option<(TypeIndex, ValidatorResources)>,
+    /// The number of calls and loop iterations run by the baseline tier so far.
+    hotness: u32,
+    /// The decoded Wasm function body if it has been run by the baseline tier.
+    baseline: BaselineState,
+}
+
+/// The state of the baseline tier of an [`UncompiledFuncEntity`].
+#[derive(Debug)]
+enum BaselineState {
+    /// The Wasm function body has not yet been decoded for the baseline tier.
+    Undecoded,
+    /// The Wasm function body has been decoded for the baseline tier.
+    Decoded(Arc<BaselineCode>),
+    /// The Wasm function body uses operators unsupported by the baseline tier.
+    Unsupported,
 }
 
 impl UncompiledFuncEntity {
     /// Creates a new [`UncompiledFuncEntity`].
     pub fn new(
         func_index: FuncIdx,
+        offset: usize,
         bytes: &[u8],
         module: ModuleHeader,
         func_to_validate: impl Into<Option<FuncToValidate<ValidatorResources>>>,
@@ -580,9 +791,12 @@ impl UncompiledFuncEntity {
         let bytes = bytes.into();
         Self {
             func_index,
+            offset,
             bytes,
             module,
             validation,
+            hotness: 0,
+            baseline: BaselineState::Undecoded,
         }
     }
 
@@ -636,7 +850,7 @@ impl UncompiledFuncEntity {
         if let Some(fuel) = fuel {
             match fuel.consume_fuel(compilation_fuel) {
                 Err(FuelError::OutOfFuel) => return Err(Error::from(TrapCode::OutOfFuel)),
-                Ok(_) | Err(FuelError::FuelMeteringDisabled) => {}
+                Ok(_) | Err(FuelError::FuelMeteringDisabled | FuelError::ScheduleUnavailable) => {}
             }
         }
         let module = self.module.clone();
@@ -659,21 +873,19 @@ impl UncompiledFuncEntity {
                 };
                 let validator = func_to_validate.into_validator(allocs.1);
                 let translator = ValidatingFuncTranslator::new(validator, translator)?;
-                let allocs = FuncTranslationDriver::new(0, &bytes[..], translator)?.translate(
-                    |compiled_func| {
+                let allocs = FuncTranslationDriver::new(self.offset, &bytes[..], translator)?
+                    .translate(|compiled_func| {
                         result.write(compiled_func);
-                    },
-                )?;
+                    })?;
                 engine.recycle_allocs(allocs.translation, allocs.validation);
             }
             None => {
                 let allocs = engine.get_translation_allocs();
                 let translator = FuncTranslator::new(func_idx, module, allocs)?;
-                let allocs = FuncTranslationDriver::new(0, &bytes[..], translator)?.translate(
-                    |compiled_func| {
+                let allocs = FuncTranslationDriver::new(self.offset, &bytes[..], translator)?
+                    .translate(|compiled_func| {
                         result.write(compiled_func);
-                    },
-                )?;
+                    })?;
                 engine.recycle_translation_allocs(allocs);
             }
         };
@@ -681,13 +893,59 @@ impl UncompiledFuncEntity {
     }
 }
 
+/// The Wasm function body of an internal function kept for per-call [`FuelSchedule`]s.
+#[derive(Debug, Clone)]
+struct FuncSource {
+    /// The index of the function within the Wasm module.
+    func_index: FuncIdx,
+    /// The Wasm binary bytes of the function body.
+    bytes: Box<[u8]>,
+    /// The Wasm module of the Wasm function.
+    module: ModuleHeader,
+}
+
+impl FuncSource {
+    /// Compiles the already validated [`FuncSource`] for the `schedule`.
+    ///
+    /// # Errors
+    ///
+    /// If function translation failed.
+    fn compile(self, schedule: &FuelSchedule) -> Result<CompiledFuncEntity, Error> {
+        let Some(engine) = self.module.engine().upgrade() else {
+            panic!(
+                "cannot compile function since engine does no longer exist: {:?}",
+                self.module.engine()
+            )
+        };
+        let fuel_costs = engine.config().fuel_costs().with_schedule(schedule);
+        let allocs = engine.get_translation_allocs();
+        let translator = FuncTranslator::with_fuel_costs(
+            self.func_index,
+            self.module,
+            allocs,
+            Some(fuel_costs),
+        )?;
+        let mut result = None;
+        let allocs = FuncTranslationDriver::new(0, &self.bytes[..], translator)?.translate(
+            |compiled_func| {
+                result = Some(compiled_func);
+            },
+        )?;
+        engine.recycle_translation_allocs(allocs);
+        Ok(result.expect("translated function must be compiled"))
+    }
+}
+
 impl fmt::Debug for UncompiledFuncEntity {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("UncompiledFuncEntity")
             .field("func_idx", &self.func_index)
+            .field("offset", &self.offset)
             .field("bytes", &self.bytes)
             .field("module", &self.module)
             .field("validate", &self.validation.is_some())
+            .field("hotness", &self.hotness)
+            .field("baseline", &self.baseline)
             .finish()
     }
 }
@@ -724,6 +982,14 @@ impl SmallByteSlice {
     /// amount of bytes stored inline with minimal `size_of`.
     const MAX_INLINE_SIZE: usize = 22;
 
+    /// Returns the number of bytes the [`SmallByteSlice`] allocates on the heap.
+    pub fn heap_size(&self) -> usize {
+        match self {
+            SmallByteSlice::Small { .. } => 0,
+            SmallByteSlice::Big(bytes) => bytes.len(),
+        }
+    }
+
     /// Returns the underlying slice of bytes.
     #[inline]
     pub fn as_slice(&self) -> &[u8] {
@@ -772,6 +1038,15 @@ pub struct CompiledFuncEntity {
     /// This includes registers to store the function local constant values,
     /// function parameters, function locals and dynamically used registers.
     len_registers: u16,
+    /// The coverage probes of the basic blocks of the [`EngineFunc`] in order.
+    ///
+    /// This is empty unless code coverage is enabled.
+    probes: Pin<Box<[CoverageProbe]>>,
+    /// The conditional branches of the [`EngineFunc`] in order of their Wasm operators.
+    #[cfg(feature = "branch-profile")]
+    branch_sites: Pin<Box<[BranchSite]>>,
+    /// The time spent on validating and translating the [`EngineFunc`] if measured.
+    translation_time: Option<Duration>,
 }
 
 impl CompiledFuncEntity {
@@ -781,13 +1056,14 @@ impl CompiledFuncEntity {
     ///
     /// - If `instrs` is empty.
     /// - If `instrs` contains more than `i32::MAX` instructions.
-    pub fn new<I, C>(len_registers: u16, instrs: I, consts: C) -> Self
+    pub fn new<I, C>(len_registers: u16, instrs: I, consts: C, probes: Box<[CoverageProbe]>) -> Self
     where
         I: IntoIterator<Item = Instruction>,
         C: IntoIterator<Item = UntypedVal>,
     {
         let instrs: Pin<Box<[Instruction]>> = Pin::new(instrs.into_iter().collect());
         let consts: Pin<Box<[UntypedVal]>> = Pin::new(consts.into_iter().collect());
+        let probes: Pin<Box<[CoverageProbe]>> = Pin::new(probes);
         assert!(
             !instrs.is_empty(),
             "compiled functions must have at least one instruction"
@@ -805,8 +1081,35 @@ impl CompiledFuncEntity {
             instrs,
             consts,
             len_registers,
+            probes,
+            #[cfg(feature = "branch-profile")]
+            branch_sites: Pin::new(Box::default()),
+            translation_time: None,
         }
     }
+
+    /// Returns the number of bytes held by the Wasmi bytecode and its metadata.
+    ///
+    /// This excludes the function local constant values.
+    fn code_size(&self) -> usize {
+        let size = mem::size_of_val(&*self.instrs) + mem::size_of_val(&*self.probes);
+        #[cfg(feature = "branch-profile")]
+        let size = size + mem::size_of_val(&*self.branch_sites);
+        size
+    }
+
+    /// Returns the [`CompiledFuncEntity`] with its conditional branches recorded for the branch profile.
+    #[cfg(feature = "branch-profile")]
+    pub fn with_branch_sites(mut self, branch_sites: Box<[BranchSite]>) -> Self {
+        self.branch_sites = Pin::new(branch_sites);
+        self
+    }
+
+    /// Returns the [`CompiledFuncEntity`] with the time spent on its validation and translation.
+    pub fn with_translation_time(mut self, translation_time: Option<Duration>) -> Self {
+        self.translation_time = translation_time;
+        self
+    }
 }
 
 /// A shared reference to the data of a [`EngineFunc`].
@@ -818,6 +1121,11 @@ pub struct CompiledFuncRef<'a> {
     consts: Pin<&'a [UntypedVal]>,
     /// The number of registers used by the [`EngineFunc`] in total.
     len_registers: u16,
+    /// The coverage probes of the basic blocks of the [`EngineFunc`] in order.
+    probes: Pin<&'a [CoverageProbe]>,
+    /// The conditional branches of the [`EngineFunc`] in order of their Wasm operators.
+    #[cfg(feature = "branch-profile")]
+    branch_sites: Pin<&'a [BranchSite]>,
 }
 
 impl<'a> From<&'a CompiledFuncEntity> for CompiledFuncRef<'a> {
@@ -827,6 +1135,9 @@ impl<'a> From<&'a CompiledFuncEntity> for CompiledFuncRef<'a> {
             instrs: func.instrs.as_ref(),
             consts: func.consts.as_ref(),
             len_registers: func.len_registers,
+            probes: func.probes.as_ref(),
+            #[cfg(feature = "branch-profile")]
+            branch_sites: func.branch_sites.as_ref(),
         }
     }
 }
@@ -849,4 +1160,19 @@ impl<'a> CompiledFuncRef<'a> {
     pub fn consts(&self) -> &'a [UntypedVal] {
         self.consts.get_ref()
     }
+
+    /// Returns the coverage probes of the basic blocks of the [`EngineFunc`].
+    ///
+    /// This is empty unless code coverage is enabled.
+    #[inline]
+    pub fn probes(&self) -> &'a [CoverageProbe] {
+        self.probes.get_ref()
+    }
+
+    /// Returns the conditional branches of the [`EngineFunc`] recorded for the branch profile.
+    #[cfg(feature = "branch-profile")]
+    #[inline]
+    pub fn branch_sites(&self) -> &'a [BranchSite] {
+        self.branch_sites.get_ref()
+    }
 }
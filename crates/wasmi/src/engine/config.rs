@@ -1,11 +1,14 @@
 use super::{EnforcedLimits, StackLimits};
-use crate::core::UntypedVal;
+use crate::{core::UntypedVal, module::StableHasher};
 use core::{mem::size_of, num::NonZeroU64};
 use wasmparser::WasmFeatures;
 
 /// The default amount of stacks kept in the cache at most.
 const DEFAULT_CACHED_STACKS: usize = 2;
 
+/// The default hotness at which Wasm functions tier up from the baseline tier.
+const DEFAULT_TIER_UP_THRESHOLD: u32 = 1_000;
+
 /// Configuration for an [`Engine`].
 ///
 /// [`Engine`]: [`crate::Engine`]
@@ -23,14 +26,32 @@ pub struct Config {
     ignore_custom_sections: bool,
     /// The configured fuel costs of all Wasmi bytecode instructions.
     fuel_costs: FuelCosts,
+    /// The versioned fuel schedule from which `fuel_costs` originate if any.
+    fuel_schedule: Option<FuelSchedule>,
     /// The mode of Wasm to Wasmi bytecode compilation.
     compilation_mode: CompilationMode,
     /// Enforced limits for Wasm module parsing and compilation.
     limits: EnforcedLimits,
+    /// Is `true` if Wasmi records which basic blocks of Wasm functions are executed.
+    code_coverage: bool,
+    /// Is `true` if Wasmi hoists loop-invariant computations out of Wasm loops.
+    hoist_loop_invariants: bool,
+    /// Is `true` if Wasmi interprets Wasm functions directly until they become hot.
+    baseline_tier: bool,
+    /// The hotness at which Wasm functions tier up from the baseline tier.
+    tier_up_threshold: u32,
+    /// The width of the indices used by the Wasmi bytecode of compiled Wasm functions.
+    index_width: IndexWidth,
+    /// Is `true` if the Wasm stacks are allocated up front so that executions never heap allocate.
+    static_allocation: bool,
+    /// Is `true` if executions use a constant amount of native stack space.
+    stackless: bool,
+    /// Is `true` if the fuel schedule can be selected per call.
+    per_call_fuel_schedules: bool,
 }
 
 /// Type storing all kinds of fuel costs of instructions.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct FuelCosts {
     /// The base fuel costs for all instructions.
     base: u64,
@@ -38,6 +59,10 @@ pub struct FuelCosts {
     copies_per_fuel: NonZeroU64,
     /// The bytes that can be copied per unit of fuel.
     bytes_per_fuel: NonZeroU64,
+    /// The fuel charged per page of linear memory grown.
+    memory_page: u64,
+    /// The fuel charged per table element grown.
+    table_element: u64,
 }
 
 impl FuelCosts {
@@ -113,6 +138,38 @@ impl FuelCosts {
         Self::costs_per(len_bytes, self.bytes_per_fuel())
     }
 
+    /// Returns the fuel costs for growing a linear memory by `len_pages` pages.
+    ///
+    /// # Note
+    ///
+    /// This is charged by `memory.grow` in addition to [`FuelCosts::fuel_for_bytes`].
+    pub fn fuel_for_memory_pages(&self, len_pages: u64) -> u64 {
+        len_pages.saturating_mul(self.memory_page)
+    }
+
+    /// Returns the fuel costs for growing a table by `len_elements` elements.
+    ///
+    /// # Note
+    ///
+    /// This is charged by `table.grow` in addition to [`FuelCosts::fuel_for_copies`].
+    pub fn fuel_for_table_elements(&self, len_elements: u64) -> u64 {
+        len_elements.saturating_mul(self.table_element)
+    }
+
+    /// Returns the [`FuelCosts`] of `schedule` that keep the grow costs of `self`.
+    pub(crate) fn with_schedule(&self, schedule: &FuelSchedule) -> Self {
+        self.with_instr_costs(schedule.costs())
+    }
+
+    /// Returns the instruction fuel costs of `costs` that keep the grow costs of `self`.
+    fn with_instr_costs(&self, costs: &FuelCosts) -> Self {
+        Self {
+            memory_page: self.memory_page,
+            table_element: self.table_element,
+            ..*costs
+        }
+    }
+
     /// Returns the fuel consumption of the amount of items with costs per items.
     fn costs_per(len_items: u64, items_per_fuel: NonZeroU64) -> u64 {
         len_items / items_per_fuel
@@ -130,10 +187,71 @@ impl Default for FuelCosts {
                 .unwrap_or_else(|| panic!("invalid zero value for copies_per_fuel value")),
             bytes_per_fuel: NonZeroU64::new(bytes_per_fuel)
                 .unwrap_or_else(|| panic!("invalid zero value for copies_per_fuel value")),
+            memory_page: 0,
+            table_element: 0,
         }
     }
 }
 
+/// A named and versioned schedule of fuel costs for Wasmi bytecode instructions.
+///
+/// Unlike the default fuel costs which may be adjusted between Wasmi releases,
+/// the fuel costs of a [`FuelSchedule`] are frozen once it has been released.
+/// Changes to fuel costs are introduced as new schedule versions instead.
+/// This is required by applications that must deterministically replay
+/// executions across Wasmi upgrades, e.g. blockchains.
+///
+/// Use [`Config::fuel_schedule`] to select a [`FuelSchedule`] for an [`Engine`].
+///
+/// # Note
+///
+/// A [`FuelSchedule`] freezes the fuel costs of the kinds of Wasmi bytecode instructions.
+/// Wasmi's test suite pins the fuel consumed by reference programs under every schedule
+/// so that changes to the translation of Wasm into Wasmi bytecode affecting fuel are caught.
+///
+/// [`Engine`]: crate::Engine
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FuelSchedule {
+    /// The version of the schedule.
+    version: u32,
+    /// The fuel costs of the schedule.
+    costs: FuelCosts,
+}
+
+impl FuelSchedule {
+    /// Returns the first version of Wasmi's fuel schedules.
+    ///
+    /// - All instructions consume 1 unit of fuel.
+    /// - Copying 8 registers consumes 1 unit of fuel.
+    /// - Copying 64 bytes of linear memory consumes 1 unit of fuel.
+    ///
+    /// # Note
+    ///
+    /// Equal to the default fuel costs of Wasmi without the `simd` crate feature.
+    pub const fn v1() -> Self {
+        Self {
+            version: 1,
+            costs: FuelCosts {
+                base: 1,
+                copies_per_fuel: NonZeroU64::new(8).unwrap(),
+                bytes_per_fuel: NonZeroU64::new(64).unwrap(),
+                memory_page: 0,
+                table_element: 0,
+            },
+        }
+    }
+
+    /// Returns the version of the [`FuelSchedule`].
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the [`FuelCosts`] of the [`FuelSchedule`].
+    pub(crate) fn costs(&self) -> &FuelCosts {
+        &self.costs
+    }
+}
+
 /// The chosen mode of Wasm to Wasmi bytecode compilation.
 #[derive(Debug, Default, Copy, Clone)]
 pub enum CompilationMode {
@@ -151,6 +269,55 @@ pub enum CompilationMode {
     Lazy,
 }
 
+/// The width of the indices used by the Wasmi bytecode of compiled Wasm functions.
+///
+/// Narrow indices cap the number of registers, function local constants and
+/// instructions of every compiled Wasm function. This bounds the memory used by
+/// compiled Wasm functions and their call frames which is useful on targets with
+/// little memory, such as microcontrollers, that only run small Wasm modules.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum IndexWidth {
+    /// Registers and function local constants use 16-bit indices and branch offsets use 32 bits.
+    #[default]
+    Wide,
+    /// Registers and function local constants use 8-bit indices and branch offsets use 16 bits.
+    ///
+    /// Compiled Wasm functions are limited to:
+    ///
+    /// - 127 registers for parameters, locals and temporary values,
+    /// - 128 function local constants,
+    /// - 32767 instructions.
+    ///
+    /// Translating Wasm functions that exceed these limits fails.
+    Narrow,
+}
+
+impl IndexWidth {
+    /// Returns the maximum number of registers of a compiled Wasm function if limited.
+    pub(crate) fn max_registers(self) -> Option<u16> {
+        match self {
+            Self::Wide => None,
+            Self::Narrow => Some(i8::MAX as u16),
+        }
+    }
+
+    /// Returns the maximum number of function local constants of a compiled Wasm function if limited.
+    pub(crate) fn max_consts(self) -> Option<u16> {
+        match self {
+            Self::Wide => None,
+            Self::Narrow => Some(i8::MIN.unsigned_abs().into()),
+        }
+    }
+
+    /// Returns the maximum number of instructions of a compiled Wasm function if limited.
+    pub(crate) fn max_instrs(self) -> Option<usize> {
+        match self {
+            Self::Wide => None,
+            Self::Narrow => Some(i16::MAX as usize),
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -160,8 +327,17 @@ impl Default for Config {
             consume_fuel: false,
             ignore_custom_sections: false,
             fuel_costs: FuelCosts::default(),
+            fuel_schedule: None,
             compilation_mode: CompilationMode::default(),
             limits: EnforcedLimits::default(),
+            code_coverage: false,
+            hoist_loop_invariants: false,
+            baseline_tier: false,
+            tier_up_threshold: DEFAULT_TIER_UP_THRESHOLD,
+            index_width: IndexWidth::Wide,
+            static_allocation: false,
+            stackless: false,
+            per_call_fuel_schedules: false,
         }
     }
 }
@@ -175,12 +351,12 @@ impl Config {
         features.set(WasmFeatures::MULTI_MEMORY, true);
         features.set(WasmFeatures::SATURATING_FLOAT_TO_INT, true);
         features.set(WasmFeatures::SIGN_EXTENSION, true);
-        features.set(WasmFeatures::BULK_MEMORY, true);
+        features.set(WasmFeatures::BULK_MEMORY, !cfg!(feature = "no-bulk-memory"));
         features.set(WasmFeatures::REFERENCE_TYPES, true);
         features.set(WasmFeatures::GC_TYPES, true); // required by reference-types
         features.set(WasmFeatures::TAIL_CALL, true);
         features.set(WasmFeatures::EXTENDED_CONST, true);
-        features.set(WasmFeatures::FLOATS, true);
+        features.set(WasmFeatures::FLOATS, !cfg!(feature = "no-float"));
         features.set(WasmFeatures::CUSTOM_PAGE_SIZES, false);
         features.set(WasmFeatures::MEMORY64, true);
         features.set(WasmFeatures::WIDE_ARITHMETIC, false);
@@ -189,6 +365,78 @@ impl Config {
         features
     }
 
+    /// Returns a [`Config`] for devices with little memory, such as embedded devices.
+    ///
+    /// - Limits the value stack to 64 KiB and the call stack to 256 nested calls.
+    /// - Does not cache stacks for reuse between executions.
+    /// - Translates Wasm functions lazily via [`CompilationMode::LazyTranslation`]
+    ///   so that only executed Wasm functions occupy memory as Wasmi bytecode.
+    /// - Disables the `multi-memory` and `memory64` Wasm proposals.
+    /// - Enforces [`EnforcedLimits::strict`] to bound the resources spent on compilation.
+    pub fn embedded() -> Self {
+        const MAX_VALUE_STACK_BYTES: usize = 64 * 1024;
+        let register_len = size_of::<UntypedVal>();
+        let mut config = Self::default();
+        config
+            .set_stack_limits(StackLimits {
+                initial_value_stack_height: 1024 / register_len,
+                maximum_value_stack_height: MAX_VALUE_STACK_BYTES / register_len,
+                maximum_recursion_depth: 256,
+            })
+            .set_cached_stacks(0)
+            .compilation_mode(CompilationMode::LazyTranslation)
+            .wasm_multi_memory(false)
+            .wasm_memory64(false)
+            .enforced_limits(EnforcedLimits::strict());
+        config
+    }
+
+    /// Returns a [`Config`] for servers executing many untrusted Wasm modules.
+    ///
+    /// - Enables fuel metering via [`Config::consume_fuel`] so that executions can be halted.
+    /// - Translates Wasm functions lazily via [`CompilationMode::LazyTranslation`]
+    ///   to speed up startup while still rejecting invalid Wasm modules upfront.
+    /// - Caches up to 16 stacks for reuse by concurrent executions.
+    /// - Enforces [`EnforcedLimits::strict`] to protect against malicious Wasm modules.
+    ///
+    /// # Note
+    ///
+    /// Fuel must be added to each [`Store`] via [`Store::set_fuel`] before executing Wasm.
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::set_fuel`]: crate::Store::set_fuel
+    pub fn server() -> Self {
+        let mut config = Self::default();
+        config
+            .consume_fuel(true)
+            .compilation_mode(CompilationMode::LazyTranslation)
+            .set_cached_stacks(16)
+            .enforced_limits(EnforcedLimits::strict());
+        config
+    }
+
+    /// Returns a [`Config`] for executions that must be reproducible, such as on blockchains.
+    ///
+    /// - Enables fuel metering via [`Config::consume_fuel`] with the frozen [`FuelSchedule::v1`]
+    ///   so that fuel consumption does not change between Wasmi releases.
+    /// - Compiles Wasm eagerly via [`CompilationMode::Eager`].
+    /// - Disables the `relaxed-simd` Wasm proposal since its results are implementation defined.
+    ///
+    /// # Note
+    ///
+    /// Wasm floating point operations may produce different `NaN` bit patterns amongst
+    /// Wasm implementations. Disable them via [`Config::floats`] if results must match
+    /// other Wasm implementations as well.
+    pub fn deterministic() -> Self {
+        let mut config = Self::default();
+        config
+            .consume_fuel(true)
+            .fuel_schedule(FuelSchedule::v1())
+            .compilation_mode(CompilationMode::Eager);
+        config.features.set(WasmFeatures::RELAXED_SIMD, false);
+        config
+    }
+
     /// Sets the [`StackLimits`] for the [`Config`].
     pub fn set_stack_limits(&mut self, stack_limits: StackLimits) -> &mut Self {
         self.stack_limits = stack_limits;
@@ -284,6 +532,7 @@ impl Config {
     /// Enabled by default.
     ///
     /// [`bulk-memory`]: https://github.com/WebAssembly/bulk-memory-operations
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn wasm_bulk_memory(&mut self, enable: bool) -> &mut Self {
         self.features.set(WasmFeatures::BULK_MEMORY, enable);
         self
@@ -385,6 +634,7 @@ impl Config {
     /// Enable or disable Wasm floating point (`f32` and `f64`) instructions and types.
     ///
     /// Enabled by default.
+    #[cfg(not(feature = "no-float"))]
     pub fn floats(&mut self, enable: bool) -> &mut Self {
         self.features.set(WasmFeatures::FLOATS, enable);
         self
@@ -432,6 +682,98 @@ impl Config {
         self.ignore_custom_sections
     }
 
+    /// Sets the [`FuelSchedule`] used to charge fuel for executed instructions.
+    ///
+    /// # Note
+    ///
+    /// - This only has an effect if fuel metering is enabled via [`Config::consume_fuel`].
+    /// - Fuel costs are compiled into the Wasmi bytecode of an [`Engine`]. Therefore all
+    ///   [`Store`]s of the same [`Engine`] share its [`FuelSchedule`]. Use one [`Engine`]
+    ///   per [`FuelSchedule`] to execute [`Store`]s with different schedules.
+    ///
+    /// By default Wasmi's unversioned fuel costs are used which may change between releases.
+    ///
+    /// The fuel costs set via [`Config::memory_page_fuel`] and [`Config::table_element_fuel`]
+    /// are kept and charged in addition to the fuel costs of the [`FuelSchedule`].
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Engine`]: crate::Engine
+    pub fn fuel_schedule(&mut self, schedule: FuelSchedule) -> &mut Self {
+        self.fuel_costs = self.fuel_costs.with_schedule(&schedule);
+        self.fuel_schedule = Some(schedule);
+        self
+    }
+
+    /// Enables or disables reproducible translation of Wasm into Wasmi bytecode.
+    ///
+    /// When enabled, identical Wasm inputs are compiled into byte-identical Wasmi bytecode
+    /// by every build of Wasmi with the same crate features regardless of the target platform.
+    /// Use [`Module::code_hash`] to obtain a stable hash of the compiled Wasmi bytecode.
+    ///
+    /// # Note
+    ///
+    /// - Wasmi's translation never depends on memory addresses or hash seeds. However, Wasmi's unversioned fuel costs depend
+    ///   on the size of registers which differs between crate features and may change between
+    ///   releases. Enabling this option therefore replaces the unversioned fuel costs with the
+    ///   frozen costs of [`FuelSchedule::v1`] unless a [`FuelSchedule`] has been set via
+    ///   [`Config::fuel_schedule`].
+    /// - The fuel costs set via [`Config::memory_page_fuel`] and [`Config::table_element_fuel`]
+    ///   are not compiled into Wasmi bytecode and thus kept as is.
+    ///
+    /// Default value: `false`
+    ///
+    /// [`Module::code_hash`]: crate::Module::code_hash
+    pub fn reproducible_translation(&mut self, enable: bool) -> &mut Self {
+        if self.fuel_schedule.is_none() {
+            let costs = match enable {
+                true => *FuelSchedule::v1().costs(),
+                false => FuelCosts::default(),
+            };
+            self.fuel_costs = self.fuel_costs.with_instr_costs(&costs);
+        }
+        self
+    }
+
+    /// Sets the fuel charged per page of linear memory grown by `memory.grow`.
+    ///
+    /// # Note
+    ///
+    /// - This only has an effect if fuel metering is enabled via [`Config::consume_fuel`].
+    /// - The fuel is charged in addition to the fuel for the grown bytes and before
+    ///   the linear memory is grown. Growing a linear memory without enough fuel
+    ///   traps with [`TrapCode::OutOfFuel`] and leaves the linear memory as is.
+    /// - This allows metered environments to price the allocations caused by `memory.grow`
+    ///   independently of the cost of the instruction itself.
+    ///
+    /// Default value: `0`
+    ///
+    /// [`TrapCode::OutOfFuel`]: crate::core::TrapCode::OutOfFuel
+    pub fn memory_page_fuel(&mut self, fuel: u64) -> &mut Self {
+        self.fuel_costs.memory_page = fuel;
+        self
+    }
+
+    /// Sets the fuel charged per table element grown by `table.grow`.
+    ///
+    /// # Note
+    ///
+    /// - This only has an effect if fuel metering is enabled via [`Config::consume_fuel`].
+    /// - The fuel is charged in addition to the fuel for the copied elements and before the
+    ///   table is grown. Growing a table without enough fuel fails and leaves the table as is.
+    ///
+    /// Default value: `0`
+    pub fn table_element_fuel(&mut self, fuel: u64) -> &mut Self {
+        self.fuel_costs.table_element = fuel;
+        self
+    }
+
+    /// Returns the [`FuelSchedule`] of the [`Config`] if any.
+    ///
+    /// Returns `None` if Wasmi's unversioned default fuel costs are used.
+    pub fn get_fuel_schedule(&self) -> Option<FuelSchedule> {
+        self.fuel_schedule
+    }
+
     /// Returns the configured [`FuelCosts`].
     pub(crate) fn fuel_costs(&self) -> &FuelCosts {
         &self.fuel_costs
@@ -471,8 +813,282 @@ impl Config {
         &self.limits
     }
 
+    /// Enables or disables code coverage instrumentation.
+    ///
+    /// If enabled, Wasmi probes the basic blocks of compiled Wasm functions so that
+    /// [`Store`]s record which of them are executed. Use [`Store::coverage`] to
+    /// query the code coverage of a [`Module`] afterwards.
+    ///
+    /// # Note
+    ///
+    /// - Probes are part of the compiled Wasmi bytecode and count towards the
+    ///   instruction limit and the interval of the instruction callback of a [`Store`].
+    /// - Code coverage slows down execution since every branch and call is recorded.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::coverage`]: crate::Store::coverage
+    /// [`Module`]: crate::Module
+    pub fn code_coverage(&mut self, enable: bool) -> &mut Self {
+        self.code_coverage = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables code coverage instrumentation.
+    pub(crate) fn get_code_coverage(&self) -> bool {
+        self.code_coverage
+    }
+
+    /// Enables or disables hoisting of loop-invariant computations out of Wasm loops.
+    ///
+    /// If enabled, Wasmi computes `local.get x` plus constant additions, such as base
+    /// addresses of memory accesses, once upon entering a Wasm `loop` instead of once
+    /// per loop iteration if `x` is never written within the `loop`.
+    ///
+    /// # Note
+    ///
+    /// - At most a few computations are hoisted per Wasm `loop` in order to keep
+    ///   the register pressure of compiled Wasm functions low.
+    /// - Wasm constants do not need to be hoisted since Wasmi already stores them in
+    ///   function local constant registers that are set up once per function call.
+    /// - Hoisted computations no longer consume fuel per loop iteration.
+    ///
+    /// Disabled by default.
+    pub fn hoist_loop_invariants(&mut self, enable: bool) -> &mut Self {
+        self.hoist_loop_invariants = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables hoisting of loop-invariant computations.
+    pub(crate) fn get_hoist_loop_invariants(&self) -> bool {
+        self.hoist_loop_invariants
+    }
+
+    /// Enables or disables the baseline tier that interprets Wasm functions without translation.
+    ///
+    /// If enabled, calls of a Wasm function that has not yet been translated to Wasmi
+    /// bytecode interpret its validated Wasm bytecode directly instead until the
+    /// Wasm function becomes hot, see [`Config::tier_up_threshold`].
+    /// Only then the Wasm function is translated to Wasmi bytecode which all of
+    /// its subsequent calls execute.
+    /// This avoids translation costs for Wasm functions that are rarely executed,
+    /// such as initialization routines, at the cost of slower execution of those calls.
+    ///
+    /// # Note
+    ///
+    /// - This only takes effect with [`CompilationMode::LazyTranslation`] since Wasm
+    ///   functions are translated eagerly with [`CompilationMode::Eager`] and are
+    ///   not validated ahead of their first call with [`CompilationMode::Lazy`].
+    /// - Wasm functions using operators unsupported by the baseline tier, such as
+    ///   bulk-memory, table or SIMD operators, are always translated.
+    /// - The baseline tier is bypassed for executions that require Wasmi bytecode,
+    ///   e.g. with fuel metering, code coverage, call hooks, instruction limits or
+    ///   callbacks, trace hashing, watched global variables or protected memories.
+    /// - Only calls from the host and calls from interpreted Wasm functions are
+    ///   interpreted while calls from Wasmi bytecode always translate their callee.
+    ///
+    /// Disabled by default.
+    pub fn baseline_tier(&mut self, enable: bool) -> &mut Self {
+        self.baseline_tier = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables the baseline tier.
+    pub(crate) fn get_baseline_tier(&self) -> bool {
+        self.baseline_tier
+    }
+
+    /// Sets the hotness at which Wasm functions tier up from the baseline tier.
+    ///
+    /// The hotness of a Wasm function is the number of its calls plus the number of
+    /// loop iterations run by the baseline tier. Once it reaches `threshold` the next
+    /// call of the Wasm function translates it to Wasmi bytecode.
+    ///
+    /// # Note
+    ///
+    /// - A `threshold` of 1 interprets only the first call of every Wasm function.
+    /// - A `threshold` of 0 translates every Wasm function upon its first call
+    ///   which effectively disables the baseline tier.
+    /// - Interpreted calls are never switched to Wasmi bytecode while they run,
+    ///   so a single long running loop is interpreted in its entirety.
+    /// - This only takes effect if the baseline tier is enabled via [`Config::baseline_tier`].
+    ///
+    /// Defaults to 1000.
+    pub fn tier_up_threshold(&mut self, threshold: u32) -> &mut Self {
+        self.tier_up_threshold = threshold;
+        self
+    }
+
+    /// Returns the hotness at which Wasm functions tier up from the baseline tier.
+    pub(crate) fn get_tier_up_threshold(&self) -> u32 {
+        self.tier_up_threshold
+    }
+
+    /// Sets the width of the indices used by the Wasmi bytecode of compiled Wasm functions.
+    ///
+    /// Use [`IndexWidth::Narrow`] to cap the registers, function local constants and
+    /// instructions of every compiled Wasm function, which bounds the memory used per
+    /// compiled Wasm function and per call frame on targets with little memory.
+    ///
+    /// # Note
+    ///
+    /// - Wasm functions exceeding the caps of [`IndexWidth::Narrow`] fail to translate.
+    ///   With lazy compilation this error is only reported upon their first call.
+    /// - Pair this with small [`StackLimits`] to bound the total memory of Wasm executions.
+    ///
+    /// Defaults to [`IndexWidth::Wide`].
+    pub fn index_width(&mut self, width: IndexWidth) -> &mut Self {
+        self.index_width = width;
+        self
+    }
+
+    /// Returns the [`IndexWidth`] of the [`Config`].
+    pub(crate) fn get_index_width(&self) -> IndexWidth {
+        self.index_width
+    }
+
+    /// Enables or disables static allocation of the Wasm stacks.
+    ///
+    /// If enabled, the [`Engine`] allocates a Wasm stack with the maximum heights of
+    /// its [`StackLimits`] upon creation and reuses it without ever shrinking it.
+    /// Successful executions of Wasm and host functions then never heap allocate which is
+    /// required in safety-critical and bare-metal environments where all heap memory
+    /// is handed out by an arena during setup.
+    ///
+    /// # Note
+    ///
+    /// Heap allocations are only avoided during executions if also:
+    ///
+    /// - [`CompilationMode::Eager`] is used since lazily compiled Wasm functions
+    ///   are translated upon their first call,
+    /// - linear memories are backed by static buffers via [`Memory::new_static`]
+    ///   or are never grown,
+    /// - tables are never grown,
+    /// - at most [`Config::set_cached_stacks`] executions are active at the same time,
+    ///   e.g. via resumable calls.
+    ///
+    /// Failed executions only heap allocate the returned [`Error`].
+    /// Since the stacks are allocated with their maximum heights small [`StackLimits`]
+    /// should be configured.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Error`]: crate::Error
+    /// [`Memory::new_static`]: crate::Memory::new_static
+    pub fn static_allocation(&mut self, enable: bool) -> &mut Self {
+        self.static_allocation = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables static allocation of the Wasm stacks.
+    pub(crate) fn get_static_allocation(&self) -> bool {
+        self.static_allocation
+    }
+
+    /// Enables or disables stackless execution.
+    ///
+    /// If enabled, the native stack usage of executions is constant regardless of the call
+    /// depth of the Wasm guest. All call frames of Wasm functions are kept on the Wasm stacks
+    /// managed by the [`Engine`] which are bounded by its [`StackLimits`] instead.
+    /// This is required on embedded devices where deeply recursive Wasm guests could
+    /// otherwise overflow the tiny native stacks of their RTOS tasks.
+    ///
+    /// # Note
+    ///
+    /// - Host functions must not call back into Wasm via nested calls, e.g. via [`Func::call`].
+    ///   Those calls fail with [`FuncError::NestedCall`]. Instead host functions return the
+    ///   results of a Wasm function via [`Caller::return_call`] which is executed as
+    ///   continuation of the host function by the same execution.
+    /// - The baseline tier is bypassed since it interprets calls recursively,
+    ///   see [`Config::baseline_tier`].
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Func::call`]: crate::Func::call
+    /// [`FuncError::NestedCall`]: crate::errors::FuncError::NestedCall
+    /// [`Caller::return_call`]: crate::Caller::return_call
+    pub fn stackless(&mut self, enable: bool) -> &mut Self {
+        self.stackless = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables stackless execution.
+    pub(crate) fn get_stackless(&self) -> bool {
+        self.stackless
+    }
+
+    /// Enables selecting the [`FuelSchedule`] per call via [`TypedFunc::call_with_schedule`].
+    ///
+    /// # Note
+    ///
+    /// - This only has an effect if fuel metering is enabled via [`Config::consume_fuel`].
+    /// - Fuel costs are compiled into the Wasmi bytecode. Therefore the [`Engine`] keeps the
+    ///   Wasm function bodies of all compiled Wasm modules in order to compile them again
+    ///   for every other [`FuelSchedule`] they are called with. Each Wasm function is compiled
+    ///   at most once per [`FuelSchedule`] upon its first call with that [`FuelSchedule`].
+    /// - This allows a single long-lived [`Engine`] to execute Wasm modules pinned
+    ///   to different historical [`FuelSchedule`]s.
+    /// - Wasm modules created via [`Module::new_with_serialized_code`] cannot be called
+    ///   with other [`FuelSchedule`]s since their Wasm function bodies are not compiled.
+    ///
+    /// Default value: `false`
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`TypedFunc::call_with_schedule`]: crate::TypedFunc::call_with_schedule
+    /// [`Module::new_with_serialized_code`]: crate::Module::new_with_serialized_code
+    pub fn per_call_fuel_schedules(&mut self, enable: bool) -> &mut Self {
+        self.per_call_fuel_schedules = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables selecting the [`FuelSchedule`] per call.
+    pub(crate) fn get_per_call_fuel_schedules(&self) -> bool {
+        self.per_call_fuel_schedules && self.consume_fuel
+    }
+
     /// Returns the [`WasmFeatures`] represented by the [`Config`].
     pub(crate) fn wasm_features(&self) -> WasmFeatures {
         self.features
     }
+
+    /// Hashes all parts of the [`Config`] that affect Wasm validation and translation.
+    ///
+    /// # Note
+    ///
+    /// This also hashes the Wasmi version since Wasmi bytecode may change between versions.
+    pub(crate) fn hash_into(&self, hasher: &mut StableHasher) {
+        hasher.write_bytes(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.write_bool(!cfg!(feature = "no-float"));
+        hasher.write_bool(!cfg!(feature = "no-bulk-memory"));
+        hasher.write_bool(cfg!(feature = "simd"));
+        hasher.write_u32(self.features.bits());
+        hasher.write_bool(self.consume_fuel);
+        hasher.write_bool(self.ignore_custom_sections);
+        hasher.write_bool(self.code_coverage);
+        hasher.write_bool(self.hoist_loop_invariants);
+        hasher.write_bool(matches!(self.index_width, IndexWidth::Narrow));
+        hasher.write_u64(self.fuel_costs.base);
+        hasher.write_u64(self.fuel_costs.copies_per_fuel.get());
+        hasher.write_u64(self.fuel_costs.bytes_per_fuel.get());
+        hasher.write_u32(match self.compilation_mode {
+            CompilationMode::Eager => 0,
+            CompilationMode::LazyTranslation => 1,
+            CompilationMode::Lazy => 2,
+        });
+        let limits = &self.limits;
+        hasher.write_opt_u32(limits.max_globals);
+        hasher.write_opt_u32(limits.max_functions);
+        hasher.write_opt_u32(limits.max_tables);
+        hasher.write_opt_u32(limits.max_element_segments);
+        hasher.write_opt_u32(limits.max_memories);
+        hasher.write_opt_u32(limits.max_data_segments);
+        hasher.write_opt_u32(limits.max_params.map(|limit| limit as u32));
+        hasher.write_opt_u32(limits.max_results.map(|limit| limit as u32));
+        let min_avg = limits.min_avg_bytes_per_function;
+        hasher.write_opt_u32(min_avg.map(|limit| limit.req_funcs_bytes));
+        hasher.write_opt_u32(min_avg.map(|limit| limit.min_avg_bytes_per_function));
+    }
 }
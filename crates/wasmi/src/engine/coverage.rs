@@ -0,0 +1,251 @@
+use super::code_map::CompiledFuncRef;
+use crate::ir::Instruction;
+use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
+
+/// A probe of a basic block within the Wasmi bytecode of a compiled function.
+///
+/// # Note
+///
+/// Probes are only encoded if code coverage is enabled via [`Config::code_coverage`].
+/// A probe is an unconditional branch to its next instruction. Therefore executing it
+/// reaches a safepoint at which the execution records that the basic block was entered.
+///
+/// [`Config::code_coverage`]: crate::Config::code_coverage
+#[derive(Debug, Copy, Clone)]
+pub struct CoverageProbe {
+    /// The index of the probe instruction within its compiled function.
+    instr: u32,
+    /// The offset of the first Wasm operator of the probed basic block.
+    ///
+    /// This is relative to the start of the Wasm function body.
+    offset: u32,
+}
+
+impl CoverageProbe {
+    /// Creates a new [`CoverageProbe`] for the probe instruction at `instr`.
+    ///
+    /// The Wasm `offset` of the probed basic block is located later via [`CoverageProbe::locate`].
+    pub fn new(instr: u32) -> Self {
+        Self { instr, offset: 0 }
+    }
+
+    /// Locates the probed basic block at `offset` relative to the start of the Wasm function body.
+    pub fn locate(&mut self, offset: u32) {
+        self.offset = offset;
+    }
+}
+
+/// The Wasmi bytecode positions at which executions with code coverage reached a safepoint.
+#[derive(Debug, Default)]
+pub struct CoverageRecorder {
+    /// The addresses of the reached [`Instruction`]s.
+    reached: BTreeSet<usize>,
+}
+
+impl CoverageRecorder {
+    /// Records that the execution reached `instr` at a safepoint.
+    pub fn record(&mut self, instr: &Instruction) {
+        self.reached.insert(instr as *const Instruction as usize);
+    }
+
+    /// Returns `true` if the basic block of `probe` within `func` has been entered.
+    fn is_entered(&self, func: CompiledFuncRef, probe: &CoverageProbe) -> bool {
+        // Note: probes branch to their next instruction which is reached afterwards.
+        let target = func
+            .instrs()
+            .as_ptr()
+            .wrapping_add(probe.instr as usize + 1);
+        self.reached.contains(&(target as usize))
+    }
+}
+
+/// The location of a basic block within a Wasm module.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BlockLocation {
+    /// The index of the function within its Wasm module.
+    func: u32,
+    /// The offset of the first Wasm operator of the basic block.
+    offset: u32,
+}
+
+impl BlockLocation {
+    /// Returns the index of the function of the basic block within its Wasm module.
+    pub fn func(&self) -> u32 {
+        self.func
+    }
+
+    /// Returns the byte offset of the first Wasm operator of the basic block.
+    ///
+    /// The offset is relative to the start of the Wasm function body,
+    /// which begins with the declaration of the function's local variables.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+}
+
+/// The code coverage of the internal functions of a [`Module`] recorded by a [`Store`].
+///
+/// Computed via [`Store::coverage`].
+///
+/// # Note
+///
+/// - Functions and basic blocks are represented as bitmaps where the `n`-th bit
+///   is stored at bit `n % 8` of byte `n / 8`. A set bit marks an executed item.
+/// - A function has been executed if it has been entered at least once.
+/// - A basic block has been executed if it has been entered at least once.
+///   Basic blocks start at the beginning of function bodies, `loop`, `if` and `else` bodies
+///   as well as after `end` and `br_if` operators.
+///
+/// [`Module`]: crate::Module
+/// [`Store`]: crate::Store
+/// [`Store::coverage`]: crate::Store::coverage
+#[derive(Debug, Clone)]
+pub struct Coverage {
+    /// The index of the first internal function within its Wasm module.
+    first_func: u32,
+    /// The number of internal functions.
+    len_funcs: u32,
+    /// Bitmap of the executed internal functions.
+    funcs: Box<[u8]>,
+    /// Bitmap of the executed basic blocks.
+    blocks: Box<[u8]>,
+    /// The locations of all basic blocks in the order of the `blocks` bitmap.
+    locations: Box<[BlockLocation]>,
+}
+
+impl Coverage {
+    /// Returns the index of the first internal function within its Wasm module.
+    ///
+    /// This is the number of imported functions.
+    pub fn first_func(&self) -> u32 {
+        self.first_func
+    }
+
+    /// Returns the number of internal functions.
+    pub fn len_funcs(&self) -> u32 {
+        self.len_funcs
+    }
+
+    /// Returns the bitmap of executed internal functions.
+    ///
+    /// The `n`-th bit refers to the function at index `first_func + n` within its Wasm module.
+    pub fn funcs_bitmap(&self) -> &[u8] {
+        &self.funcs
+    }
+
+    /// Returns the bitmap of executed basic blocks.
+    ///
+    /// The `n`-th bit refers to the basic block at the `n`-th [`BlockLocation`] of [`Coverage::blocks`].
+    pub fn blocks_bitmap(&self) -> &[u8] {
+        &self.blocks
+    }
+
+    /// Returns the locations of all basic blocks of the internal functions.
+    ///
+    /// Basic blocks are ordered by function index and offset.
+    pub fn blocks(&self) -> &[BlockLocation] {
+        &self.locations
+    }
+
+    /// Returns `true` if the function at `func_index` within its Wasm module has been executed.
+    ///
+    /// Returns `false` for imported or out of bounds functions.
+    pub fn is_func_executed(&self, func_index: u32) -> bool {
+        let Some(n) = func_index.checked_sub(self.first_func) else {
+            return false;
+        };
+        get_bit(&self.funcs, n as usize)
+    }
+
+    /// Returns `true` if the `n`-th basic block of [`Coverage::blocks`] has been executed.
+    ///
+    /// Returns `false` if `n` is out of bounds.
+    pub fn is_block_executed(&self, n: usize) -> bool {
+        get_bit(&self.blocks, n)
+    }
+
+    /// Returns an iterator over the locations of all executed basic blocks.
+    pub fn executed_blocks(&self) -> impl Iterator<Item = BlockLocation> + '_ {
+        self.locations
+            .iter()
+            .enumerate()
+            .filter(|(n, _)| self.is_block_executed(*n))
+            .map(|(_, location)| *location)
+    }
+}
+
+/// Returns `true` if the `n`-th bit of `bitmap` is set.
+fn get_bit(bitmap: &[u8], n: usize) -> bool {
+    bitmap
+        .get(n / 8)
+        .is_some_and(|byte| byte & (1 << (n % 8)) != 0)
+}
+
+/// Pushes the `n`-th bit with `value` to `bitmap`.
+fn push_bit(bitmap: &mut Vec<u8>, n: usize, value: bool) {
+    if n % 8 == 0 {
+        bitmap.push(0);
+    }
+    if value {
+        bitmap[n / 8] |= 1 << (n % 8);
+    }
+}
+
+/// Incrementally builds the [`Coverage`] of the internal functions of a Wasm module.
+#[derive(Debug)]
+pub struct CoverageBuilder<'a> {
+    /// The recorded safepoints of the executions.
+    recorder: &'a CoverageRecorder,
+    /// The index of the first internal function within its Wasm module.
+    first_func: u32,
+    /// The number of functions pushed so far.
+    len_funcs: u32,
+    /// Bitmap of the executed functions pushed so far.
+    funcs: Vec<u8>,
+    /// Bitmap of the executed basic blocks pushed so far.
+    blocks: Vec<u8>,
+    /// The locations of the basic blocks pushed so far.
+    locations: Vec<BlockLocation>,
+}
+
+impl<'a> CoverageBuilder<'a> {
+    /// Creates a new [`CoverageBuilder`] evaluating the safepoints recorded by `recorder`.
+    pub fn new(recorder: &'a CoverageRecorder, first_func: u32) -> Self {
+        Self {
+            recorder,
+            first_func,
+            len_funcs: 0,
+            funcs: Vec::new(),
+            blocks: Vec::new(),
+            locations: Vec::new(),
+        }
+    }
+
+    /// Pushes the compiled function at `func_index` within its Wasm module.
+    pub fn push_func(&mut self, func_index: u32, func: CompiledFuncRef) {
+        debug_assert_eq!(func_index, self.first_func + self.len_funcs);
+        let mut executed = false;
+        for probe in func.probes() {
+            let entered = self.recorder.is_entered(func, probe);
+            executed |= entered;
+            push_bit(&mut self.blocks, self.locations.len(), entered);
+            self.locations.push(BlockLocation {
+                func: func_index,
+                offset: probe.offset,
+            });
+        }
+        push_bit(&mut self.funcs, self.len_funcs as usize, executed);
+        self.len_funcs += 1;
+    }
+
+    /// Finishes building the [`Coverage`].
+    pub fn finish(self) -> Coverage {
+        Coverage {
+            first_func: self.first_func,
+            len_funcs: self.len_funcs,
+            funcs: self.funcs.into(),
+            blocks: self.blocks.into(),
+            locations: self.locations.into(),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use super::{code_map::CompiledFuncRef, Instr};
+use crate::{core::UntypedVal, ir::Instruction};
+use alloc::boxed::Box;
+use core::fmt;
+
+/// The disassembled Wasmi bytecode of a compiled Wasm function.
+///
+/// # Note
+///
+/// This is intended for debugging Wasm to Wasmi bytecode translation and code size issues.
+/// The textual representation of the Wasmi bytecode is not stable and may change between versions.
+///
+/// The instructions of the textual representation can be parsed back via
+/// `wasmi_ir::text::parse_instrs` with the `text` crate feature of `wasmi_ir` enabled.
+#[derive(Debug, Clone)]
+pub struct FuncDisassembly {
+    /// The index of the function within its Wasm module.
+    index: u32,
+    /// The name under which the function is exported if any.
+    name: Option<Box<str>>,
+    /// The sequence of [`Instruction`] of the function.
+    instrs: Box<[Instruction]>,
+    /// The function local constant values in the order of their registers.
+    consts: Box<[UntypedVal]>,
+    /// The number of registers used by the function in total.
+    len_registers: u16,
+}
+
+impl FuncDisassembly {
+    /// Creates a new [`FuncDisassembly`] for the function at `index` from its compiled `func`.
+    pub(super) fn new(index: u32, name: Option<&str>, func: CompiledFuncRef) -> Self {
+        // Function local constants are stored in reverse order of their registers.
+        let consts = func.consts().iter().rev().copied().collect();
+        Self {
+            index,
+            name: name.map(Box::from),
+            instrs: func.instrs().into(),
+            consts,
+            len_registers: func.len_registers(),
+        }
+    }
+
+    /// Returns the index of the disassembled function within its Wasm module.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the name under which the disassembled function is exported if any.
+    ///
+    /// If the function is exported multiple times this returns one of its export names.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the number of Wasmi bytecode instructions of the function.
+    pub fn len_instrs(&self) -> usize {
+        self.instrs.len()
+    }
+
+    /// Returns an iterator over the Wasmi bytecode instructions of the function.
+    ///
+    /// Yields each [`Instruction`] together with its [`Instr`] reference,
+    /// which is the index of the [`Instruction`] within the function.
+    pub fn instrs(&self) -> impl ExactSizeIterator<Item = (Instr, Instruction)> + '_ {
+        self.instrs
+            .iter()
+            .enumerate()
+            .map(|(index, instr)| (Instr::from_usize(index), *instr))
+    }
+
+    /// Returns the number of function local constant values of the function.
+    pub fn len_consts(&self) -> usize {
+        self.consts.len()
+    }
+
+    /// Returns the function local constant values of the function.
+    ///
+    /// The constant value at index `n` is stored in the register `Reg(-1 - n)`.
+    pub fn consts(&self) -> &[UntypedVal] {
+        &self.consts
+    }
+
+    /// Returns the number of registers used by the function in total.
+    ///
+    /// This includes registers for function local constants, parameters and locals.
+    pub fn len_registers(&self) -> u16 {
+        self.len_registers
+    }
+}
+
+impl fmt::Display for FuncDisassembly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.consts.is_empty() {
+            writeln!(f, "consts:")?;
+            for (n, value) in self.consts.iter().enumerate() {
+                let bits = u64::from(*value);
+                writeln!(f, "  Reg(-{}): {bits:#018x}", n + 1)?;
+            }
+        }
+        writeln!(f, "instrs:")?;
+        for (n, instr) in self.instrs.iter().enumerate() {
+            writeln!(f, "  {n:04}: {instr:?}")?;
+        }
+        Ok(())
+    }
+}
@@ -3,10 +3,8 @@ use crate::{
     engine::DedupFuncType,
     instance::InstanceEntity,
     ir::index,
-    memory::DataSegment,
     module::DEFAULT_MEMORY_INDEX,
     store::StoreInner,
-    table::ElementSegment,
     Func,
     Global,
     Instance,
@@ -15,6 +13,9 @@ use crate::{
 };
 use core::ptr::{self, NonNull};
 
+#[cfg(not(feature = "no-bulk-memory"))]
+use crate::{memory::DataSegment, table::ElementSegment};
+
 /// Cached WebAssembly instance.
 #[derive(Debug)]
 pub struct CachedInstance {
@@ -153,6 +154,7 @@ impl CachedInstance {
     /// # Safety
     ///
     /// It is the callers responsibility to use this method only when the caches are fresh.
+    #[cfg(not(feature = "no-bulk-memory"))]
     #[inline]
     pub unsafe fn get_data_segment(&self, index: index::Data) -> Option<DataSegment> {
         let instance = unsafe { self.as_ref() };
@@ -164,6 +166,7 @@ impl CachedInstance {
     /// # Safety
     ///
     /// It is the callers responsibility to use this method only when the caches are fresh.
+    #[cfg(not(feature = "no-bulk-memory"))]
     #[inline]
     pub unsafe fn get_element_segment(&self, index: index::Elem) -> Option<ElementSegment> {
         let instance = unsafe { self.as_ref() };
@@ -1,4 +1,4 @@
-pub use self::call::{dispatch_host_func, ResumableHostError};
+pub use self::call::{dispatch_host_func, resolve_return_call, ResumableHostError};
 use super::{cache::CachedInstance, InstructionPtr, Stack};
 use crate::{
     core::{hint, wasm, ReadAs, TrapCode, UntypedVal, WriteAs},
@@ -8,11 +8,10 @@ use crate::{
         utils::unreachable_unchecked,
         DedupFuncType,
         EngineFunc,
+        ExecutionTrace,
     },
     ir::{index, BlockFuel, Const16, Instruction, Offset64Hi, Reg, ShiftAmount},
-    memory::DataSegment,
     store::{PrunedStore, StoreInner},
-    table::ElementSegment,
     Error,
     Func,
     FuncRef,
@@ -20,6 +19,13 @@ use crate::{
     Memory,
     Table,
 };
+use core::cmp;
+
+#[cfg(not(feature = "no-bulk-memory"))]
+use crate::{memory::DataSegment, table::ElementSegment};
+
+#[cfg(feature = "branch-profile")]
+use crate::engine::BranchCounter;
 
 #[cfg(doc)]
 use crate::Instance;
@@ -69,8 +75,41 @@ pub fn execute_instrs<'engine>(
     code_map: &'engine CodeMap,
 ) -> Result<(), Error> {
     let instance = stack.calls.instance_expect();
+    let instr_limit = store.inner().instr_limit();
+    let callback_interval = store.inner().instr_callback_interval();
+    let deadline_interval = store.inner().deadline_interval();
+    let tracing = store.inner().is_tracing();
+    let covering = store.inner().is_covering();
     let cache = CachedInstance::new(store.inner_mut(), instance);
-    Executor::new(stack, code_map, cache).execute(store)
+    let mut executor = Executor::new(
+        stack,
+        code_map,
+        cache,
+        instr_limit,
+        callback_interval,
+        deadline_interval,
+        tracing,
+        covering,
+    );
+    let result = if executor.is_counting() {
+        executor.execute::<true>(store)
+    } else {
+        executor.execute::<false>(store)
+    };
+    #[cfg(feature = "branch-profile")]
+    store
+        .inner_mut()
+        .branch_counter_mut()
+        .merge(executor.branches);
+    result
+}
+
+/// Folds an effect into the execution trace of the `store` via `f` if trace hashing is enabled.
+#[cold]
+fn trace_effect(store: &mut StoreInner, f: impl FnOnce(&mut ExecutionTrace)) {
+    if let Some(trace) = store.trace_mut() {
+        f(trace)
+    }
 }
 
 /// An execution context for executing a Wasmi function frame.
@@ -88,15 +127,60 @@ struct Executor<'engine> {
     ///
     /// [`Engine`]: crate::Engine
     code_map: &'engine CodeMap,
+    /// The number of instructions executed so far.
+    ///
+    /// This is only counted if any safepoint can become due, see [`Executor::is_counting`].
+    executed: u64,
+    /// The maximum number of instructions to execute.
+    ///
+    /// This is [`u64::MAX`] if no instruction limit is set.
+    instr_limit: u64,
+    /// The number of instructions executed between calls to the instruction callback.
+    ///
+    /// This is [`u64::MAX`] if no instruction callback is set.
+    callback_interval: u64,
+    /// The number of executed instructions at which the instruction callback is due next.
+    next_callback: u64,
+    /// The number of instructions executed between checks of the deadline.
+    ///
+    /// This is [`u64::MAX`] if no deadline is set.
+    deadline_interval: u64,
+    /// The number of executed instructions at which the deadline check is due next.
+    next_deadline_check: u64,
+    /// The number of executed instructions at which the next safepoint check is due.
+    ///
+    /// This is the minimum of `instr_limit`, `next_callback` and `next_deadline_check` so that
+    /// safepoints only require a single comparison in the common case.
+    /// If `tracing` or `covering` is enabled this is 0 so that every safepoint is handled.
+    next_safepoint: u64,
+    /// Is `true` if a safepoint is due before executing the instruction at `ip`.
+    safepoint_due: bool,
+    /// Is `true` if the control flow and effects of the execution are folded into its trace.
+    tracing: bool,
+    /// Is `true` if the safepoints reached by the execution are recorded for code coverage.
+    covering: bool,
+    /// The counts of the conditional branches executed by the [`Executor`].
+    ///
+    /// These are merged into the branch profile of the [`Store`] once the execution returns.
+    ///
+    /// [`Store`]: crate::Store
+    #[cfg(feature = "branch-profile")]
+    branches: BranchCounter,
 }
 
 impl<'engine> Executor<'engine> {
     /// Creates a new [`Executor`] for executing a Wasmi function frame.
     #[inline(always)]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         stack: &'engine mut Stack,
         code_map: &'engine CodeMap,
         cache: CachedInstance,
+        instr_limit: Option<u64>,
+        callback_interval: Option<u64>,
+        deadline_interval: Option<u64>,
+        tracing: bool,
+        covering: bool,
     ) -> Self {
         let frame = stack
             .calls
@@ -107,20 +191,87 @@ impl<'engine> Executor<'engine> {
         //         valid for all register indices used by the associated function body.
         let sp = unsafe { stack.values.stack_ptr_at(frame.base_offset()) };
         let ip = frame.instr_ptr();
-        Self {
+        let instr_limit = instr_limit.unwrap_or(u64::MAX);
+        let callback_interval = callback_interval.unwrap_or(u64::MAX);
+        let deadline_interval = deadline_interval.unwrap_or(u64::MAX);
+        let mut executor = Self {
             sp,
             ip,
             cache,
             stack,
             code_map,
+            executed: 0,
+            instr_limit,
+            callback_interval,
+            next_callback: callback_interval,
+            deadline_interval,
+            next_deadline_check: deadline_interval,
+            next_safepoint: 0,
+            // Note: the first executed instruction is traced, too.
+            safepoint_due: tracing,
+            tracing,
+            covering,
+            #[cfg(feature = "branch-profile")]
+            branches: BranchCounter::default(),
+        };
+        executor.next_safepoint = executor.next_safepoint();
+        executor
+    }
+
+    /// Returns the number of executed instructions at which the next safepoint check is due.
+    fn next_safepoint(&self) -> u64 {
+        if self.tracing || self.covering {
+            return 0;
         }
+        cmp::min(
+            self.instr_limit,
+            cmp::min(self.next_callback, self.next_deadline_check),
+        )
+    }
+
+    /// Returns `true` if the [`Executor`] needs to count its executed instructions.
+    ///
+    /// This is the case if the instruction limit, callback, deadline, tracing or coverage
+    /// is enabled. Otherwise no safepoint can ever become due and the instruction counting
+    /// is compiled out of the execution loop.
+    fn is_counting(&self) -> bool {
+        self.next_safepoint != u64::MAX
+    }
+
+    /// Checks if the [`Executor`] reached its next safepoint.
+    ///
+    /// # Note
+    ///
+    /// This is called at safepoints, such as branches and calls, after the
+    /// instruction pointer has been updated. A due safepoint is executed by the
+    /// execution loop before the instruction at the updated instruction pointer
+    /// since only the execution loop has access to the [`PrunedStore`].
+    #[inline(always)]
+    fn check_safepoint(&mut self) {
+        if hint::unlikely(self.executed > self.next_safepoint) {
+            self.safepoint_due = true;
+        }
+    }
+
+    /// Records that the conditional branch at the current instruction pointer was `taken` or not.
+    #[cfg(feature = "branch-profile")]
+    fn profile_branch(&mut self, taken: bool) {
+        self.branches.record(self.ip.get(), taken);
     }
 
     /// Executes the function frame until it returns or traps.
+    ///
+    /// Counts the executed instructions if `COUNTING` is `true`.
     #[inline(always)]
-    fn execute(mut self, store: &mut PrunedStore) -> Result<(), Error> {
+    fn execute<const COUNTING: bool>(&mut self, store: &mut PrunedStore) -> Result<(), Error> {
         use Instruction as Instr;
         loop {
+            if COUNTING {
+                if hint::unlikely(self.safepoint_due) {
+                    self.execute_safepoint(store)?;
+                }
+                self.executed = self.executed.wrapping_add(1);
+            }
             match *self.ip.get() {
                 Instr::Trap { trap_code } => self.execute_trap(trap_code)?,
                 Instr::ConsumeFuel { block_fuel } => {
@@ -144,6 +295,7 @@ impl<'engine> Executor<'engine> {
                 Instr::ReturnI64Imm32 { value } => {
                     forward_return!(self.execute_return_i64imm32(store.inner_mut(), value))
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::ReturnF64Imm32 { value } => {
                     forward_return!(self.execute_return_f64imm32(store.inner_mut(), value))
                 }
@@ -184,6 +336,7 @@ impl<'engine> Executor<'engine> {
                         value
                     ))
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::ReturnNezF64Imm32 { condition, value } => {
                     forward_return!(self.execute_return_nez_f64imm32(
                         store.inner_mut(),
@@ -359,27 +512,35 @@ impl<'engine> Executor<'engine> {
                 Instr::BranchI64LeUImm16Rhs { lhs, rhs, offset } => {
                     self.execute_branch_i64_le_u_imm16_rhs(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::BranchF32Eq { lhs, rhs, offset } => {
                     self.execute_branch_f32_eq(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::BranchF32Ne { lhs, rhs, offset } => {
                     self.execute_branch_f32_ne(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::BranchF32Lt { lhs, rhs, offset } => {
                     self.execute_branch_f32_lt(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::BranchF32Le { lhs, rhs, offset } => {
                     self.execute_branch_f32_le(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::BranchF64Eq { lhs, rhs, offset } => {
                     self.execute_branch_f64_eq(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::BranchF64Ne { lhs, rhs, offset } => {
                     self.execute_branch_f64_ne(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::BranchF64Lt { lhs, rhs, offset } => {
                     self.execute_branch_f64_lt(lhs, rhs, offset)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::BranchF64Le { lhs, rhs, offset } => {
                     self.execute_branch_f64_le(lhs, rhs, offset)
                 }
@@ -387,6 +548,7 @@ impl<'engine> Executor<'engine> {
                 Instr::Copy2 { results, values } => self.execute_copy_2(results, values),
                 Instr::CopyImm32 { result, value } => self.execute_copy_imm32(result, value),
                 Instr::CopyI64Imm32 { result, value } => self.execute_copy_i64imm32(result, value),
+                #[cfg(not(feature = "no-float"))]
                 Instr::CopyF64Imm32 { result, value } => self.execute_copy_f64imm32(result, value),
                 Instr::CopySpan {
                     results,
@@ -463,25 +625,28 @@ impl<'engine> Executor<'engine> {
                     self.execute_select_i64imm32_lhs(result, lhs)
                 }
                 Instr::SelectI64Imm32 { result, lhs } => self.execute_select_i64imm32(result, lhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::SelectF64Imm32Rhs { result, lhs } => {
                     self.execute_select_f64imm32_rhs(result, lhs)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::SelectF64Imm32Lhs { result, lhs } => {
                     self.execute_select_f64imm32_lhs(result, lhs)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::SelectF64Imm32 { result, lhs } => self.execute_select_f64imm32(result, lhs),
                 Instr::RefFunc { result, func } => self.execute_ref_func(result, func),
                 Instr::GlobalGet { result, global } => {
                     self.execute_global_get(store.inner(), result, global)
                 }
                 Instr::GlobalSet { global, input } => {
-                    self.execute_global_set(store.inner_mut(), global, input)
+                    self.execute_global_set(store, global, input)?
                 }
                 Instr::GlobalSetI32Imm16 { global, input } => {
-                    self.execute_global_set_i32imm16(store.inner_mut(), global, input)
+                    self.execute_global_set_i32imm16(store, global, input)?
                 }
                 Instr::GlobalSetI64Imm16 { global, input } => {
-                    self.execute_global_set_i64imm16(store.inner_mut(), global, input)
+                    self.execute_global_set_i64imm16(store, global, input)?
                 }
                 Instr::Load32 { result, offset_lo } => {
                     self.execute_load32(store.inner(), result, offset_lo)?
@@ -619,7 +784,7 @@ impl<'engine> Executor<'engine> {
                     self.execute_store32(store.inner_mut(), ptr, offset_lo)?
                 }
                 Instr::Store32Offset16 { ptr, offset, value } => {
-                    self.execute_store32_offset16(ptr, offset, value)?
+                    self.execute_store32_offset16(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::Store32At { address, value } => {
                     self.execute_store32_at(store.inner_mut(), address, value)?
@@ -628,7 +793,7 @@ impl<'engine> Executor<'engine> {
                     self.execute_store64(store.inner_mut(), ptr, offset_lo)?
                 }
                 Instr::Store64Offset16 { ptr, offset, value } => {
-                    self.execute_store64_offset16(ptr, offset, value)?
+                    self.execute_store64_offset16(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::Store64At { address, value } => {
                     self.execute_store64_at(store.inner_mut(), address, value)?
@@ -637,7 +802,7 @@ impl<'engine> Executor<'engine> {
                     self.execute_i32_store_imm16(store.inner_mut(), ptr, offset_lo)?
                 }
                 Instr::I32StoreOffset16Imm16 { ptr, offset, value } => {
-                    self.execute_i32_store_offset16_imm16(ptr, offset, value)?
+                    self.execute_i32_store_offset16_imm16(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::I32StoreAtImm16 { address, value } => {
                     self.execute_i32_store_at_imm16(store.inner_mut(), address, value)?
@@ -649,10 +814,10 @@ impl<'engine> Executor<'engine> {
                     self.execute_i32_store8_imm(store.inner_mut(), ptr, offset_lo)?
                 }
                 Instr::I32Store8Offset16 { ptr, offset, value } => {
-                    self.execute_i32_store8_offset16(ptr, offset, value)?
+                    self.execute_i32_store8_offset16(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::I32Store8Offset16Imm { ptr, offset, value } => {
-                    self.execute_i32_store8_offset16_imm(ptr, offset, value)?
+                    self.execute_i32_store8_offset16_imm(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::I32Store8At { address, value } => {
                     self.execute_i32_store8_at(store.inner_mut(), address, value)?
@@ -667,10 +832,10 @@ impl<'engine> Executor<'engine> {
                     self.execute_i32_store16_imm(store.inner_mut(), ptr, offset_lo)?
                 }
                 Instr::I32Store16Offset16 { ptr, offset, value } => {
-                    self.execute_i32_store16_offset16(ptr, offset, value)?
+                    self.execute_i32_store16_offset16(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::I32Store16Offset16Imm { ptr, offset, value } => {
-                    self.execute_i32_store16_offset16_imm(ptr, offset, value)?
+                    self.execute_i32_store16_offset16_imm(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::I32Store16At { address, value } => {
                     self.execute_i32_store16_at(store.inner_mut(), address, value)?
@@ -682,7 +847,7 @@ impl<'engine> Executor<'engine> {
                     self.execute_i64_store_imm16(store.inner_mut(), ptr, offset_lo)?
                 }
                 Instr::I64StoreOffset16Imm16 { ptr, offset, value } => {
-                    self.execute_i64_store_offset16_imm16(ptr, offset, value)?
+                    self.execute_i64_store_offset16_imm16(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::I64StoreAtImm16 { address, value } => {
                     self.execute_i64_store_at_imm16(store.inner_mut(), address, value)?
@@ -694,10 +859,10 @@ impl<'engine> Executor<'engine> {
                     self.execute_i64_store8_imm(store.inner_mut(), ptr, offset_lo)?
                 }
                 Instr::I64Store8Offset16 { ptr, offset, value } => {
-                    self.execute_i64_store8_offset16(ptr, offset, value)?
+                    self.execute_i64_store8_offset16(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::I64Store8Offset16Imm { ptr, offset, value } => {
-                    self.execute_i64_store8_offset16_imm(ptr, offset, value)?
+                    self.execute_i64_store8_offset16_imm(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::I64Store8At { address, value } => {
                     self.execute_i64_store8_at(store.inner_mut(), address, value)?
@@ -712,10 +877,10 @@ impl<'engine> Executor<'engine> {
                     self.execute_i64_store16_imm(store.inner_mut(), ptr, offset_lo)?
                 }
                 Instr::I64Store16Offset16 { ptr, offset, value } => {
-                    self.execute_i64_store16_offset16(ptr, offset, value)?
+                    self.execute_i64_store16_offset16(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::I64Store16Offset16Imm { ptr, offset, value } => {
-                    self.execute_i64_store16_offset16_imm(ptr, offset, value)?
+                    self.execute_i64_store16_offset16_imm(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::I64Store16At { address, value } => {
                     self.execute_i64_store16_at(store.inner_mut(), address, value)?
@@ -730,10 +895,10 @@ impl<'engine> Executor<'engine> {
                     self.execute_i64_store32_imm16(store.inner_mut(), ptr, offset_lo)?
                 }
                 Instr::I64Store32Offset16 { ptr, offset, value } => {
-                    self.execute_i64_store32_offset16(ptr, offset, value)?
+                    self.execute_i64_store32_offset16(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::I64Store32Offset16Imm16 { ptr, offset, value } => {
-                    self.execute_i64_store32_offset16_imm16(ptr, offset, value)?
+                    self.execute_i64_store32_offset16_imm16(store.inner_mut(), ptr, offset, value)?
                 }
                 Instr::I64Store32At { address, value } => {
                     self.execute_i64_store32_at(store.inner_mut(), address, value)?
@@ -813,13 +978,21 @@ impl<'engine> Executor<'engine> {
                 Instr::I64LeUImm16Rhs { result, lhs, rhs } => {
                     self.execute_i64_le_u_imm16_rhs(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Eq { result, lhs, rhs } => self.execute_f32_eq(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Ne { result, lhs, rhs } => self.execute_f32_ne(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Lt { result, lhs, rhs } => self.execute_f32_lt(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Le { result, lhs, rhs } => self.execute_f32_le(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Eq { result, lhs, rhs } => self.execute_f64_eq(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Ne { result, lhs, rhs } => self.execute_f64_ne(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Lt { result, lhs, rhs } => self.execute_f64_lt(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Le { result, lhs, rhs } => self.execute_f64_le(result, lhs, rhs),
                 Instr::I32Clz { result, input } => self.execute_i32_clz(result, input),
                 Instr::I32Ctz { result, input } => self.execute_i32_ctz(result, input),
@@ -1015,117 +1188,173 @@ impl<'engine> Executor<'engine> {
                 Instr::I64Extend8S { result, input } => self.execute_i64_extend8_s(result, input),
                 Instr::I64Extend16S { result, input } => self.execute_i64_extend16_s(result, input),
                 Instr::I64Extend32S { result, input } => self.execute_i64_extend32_s(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Abs { result, input } => self.execute_f32_abs(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Neg { result, input } => self.execute_f32_neg(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Ceil { result, input } => self.execute_f32_ceil(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Floor { result, input } => self.execute_f32_floor(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Trunc { result, input } => self.execute_f32_trunc(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Nearest { result, input } => self.execute_f32_nearest(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Sqrt { result, input } => self.execute_f32_sqrt(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Add { result, lhs, rhs } => self.execute_f32_add(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Sub { result, lhs, rhs } => self.execute_f32_sub(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Mul { result, lhs, rhs } => self.execute_f32_mul(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Div { result, lhs, rhs } => self.execute_f32_div(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Min { result, lhs, rhs } => self.execute_f32_min(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Max { result, lhs, rhs } => self.execute_f32_max(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32Copysign { result, lhs, rhs } => {
                     self.execute_f32_copysign(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32CopysignImm { result, lhs, rhs } => {
                     self.execute_f32_copysign_imm(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Abs { result, input } => self.execute_f64_abs(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Neg { result, input } => self.execute_f64_neg(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Ceil { result, input } => self.execute_f64_ceil(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Floor { result, input } => self.execute_f64_floor(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Trunc { result, input } => self.execute_f64_trunc(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Nearest { result, input } => self.execute_f64_nearest(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Sqrt { result, input } => self.execute_f64_sqrt(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Add { result, lhs, rhs } => self.execute_f64_add(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Sub { result, lhs, rhs } => self.execute_f64_sub(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Mul { result, lhs, rhs } => self.execute_f64_mul(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Div { result, lhs, rhs } => self.execute_f64_div(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Min { result, lhs, rhs } => self.execute_f64_min(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Max { result, lhs, rhs } => self.execute_f64_max(result, lhs, rhs),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64Copysign { result, lhs, rhs } => {
                     self.execute_f64_copysign(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64CopysignImm { result, lhs, rhs } => {
                     self.execute_f64_copysign_imm(result, lhs, rhs)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I32TruncF32S { result, input } => {
                     self.execute_i32_trunc_f32_s(result, input)?
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I32TruncF32U { result, input } => {
                     self.execute_i32_trunc_f32_u(result, input)?
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I32TruncF64S { result, input } => {
                     self.execute_i32_trunc_f64_s(result, input)?
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I32TruncF64U { result, input } => {
                     self.execute_i32_trunc_f64_u(result, input)?
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I64TruncF32S { result, input } => {
                     self.execute_i64_trunc_f32_s(result, input)?
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I64TruncF32U { result, input } => {
                     self.execute_i64_trunc_f32_u(result, input)?
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I64TruncF64S { result, input } => {
                     self.execute_i64_trunc_f64_s(result, input)?
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I64TruncF64U { result, input } => {
                     self.execute_i64_trunc_f64_u(result, input)?
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I32TruncSatF32S { result, input } => {
                     self.execute_i32_trunc_sat_f32_s(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I32TruncSatF32U { result, input } => {
                     self.execute_i32_trunc_sat_f32_u(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I32TruncSatF64S { result, input } => {
                     self.execute_i32_trunc_sat_f64_s(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I32TruncSatF64U { result, input } => {
                     self.execute_i32_trunc_sat_f64_u(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I64TruncSatF32S { result, input } => {
                     self.execute_i64_trunc_sat_f32_s(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I64TruncSatF32U { result, input } => {
                     self.execute_i64_trunc_sat_f32_u(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I64TruncSatF64S { result, input } => {
                     self.execute_i64_trunc_sat_f64_s(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::I64TruncSatF64U { result, input } => {
                     self.execute_i64_trunc_sat_f64_u(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32DemoteF64 { result, input } => self.execute_f32_demote_f64(result, input),
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64PromoteF32 { result, input } => {
                     self.execute_f64_promote_f32(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32ConvertI32S { result, input } => {
                     self.execute_f32_convert_i32_s(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32ConvertI32U { result, input } => {
                     self.execute_f32_convert_i32_u(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32ConvertI64S { result, input } => {
                     self.execute_f32_convert_i64_s(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F32ConvertI64U { result, input } => {
                     self.execute_f32_convert_i64_u(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64ConvertI32S { result, input } => {
                     self.execute_f64_convert_i32_s(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64ConvertI32U { result, input } => {
                     self.execute_f64_convert_i32_u(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64ConvertI64S { result, input } => {
                     self.execute_f64_convert_i64_s(result, input)
                 }
+                #[cfg(not(feature = "no-float"))]
                 Instr::F64ConvertI64U { result, input } => {
                     self.execute_f64_convert_i64_u(result, input)
                 }
@@ -1144,51 +1373,67 @@ impl<'engine> Executor<'engine> {
                 Instr::TableSetAt { index, value } => {
                     self.execute_table_set_at(store.inner_mut(), index, value)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableCopy { dst, src, len } => {
                     self.execute_table_copy(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableCopyTo { dst, src, len } => {
                     self.execute_table_copy_to(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableCopyFrom { dst, src, len } => {
                     self.execute_table_copy_from(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableCopyFromTo { dst, src, len } => {
                     self.execute_table_copy_from_to(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableCopyExact { dst, src, len } => {
                     self.execute_table_copy_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableCopyToExact { dst, src, len } => {
                     self.execute_table_copy_to_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableCopyFromExact { dst, src, len } => {
                     self.execute_table_copy_from_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableCopyFromToExact { dst, src, len } => {
                     self.execute_table_copy_from_to_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableInit { dst, src, len } => {
                     self.execute_table_init(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableInitTo { dst, src, len } => {
                     self.execute_table_init_to(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableInitFrom { dst, src, len } => {
                     self.execute_table_init_from(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableInitFromTo { dst, src, len } => {
                     self.execute_table_init_from_to(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableInitExact { dst, src, len } => {
                     self.execute_table_init_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableInitToExact { dst, src, len } => {
                     self.execute_table_init_to_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableInitFromExact { dst, src, len } => {
                     self.execute_table_init_from_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::TableInitFromToExact { dst, src, len } => {
                     self.execute_table_init_from_to_exact(store.inner_mut(), dst, src, len)?
                 }
@@ -1214,7 +1459,9 @@ impl<'engine> Executor<'engine> {
                     delta,
                     value,
                 } => self.execute_table_grow_imm(store, result, delta, value)?,
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::ElemDrop { index } => self.execute_element_drop(store.inner_mut(), index),
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::DataDrop { index } => self.execute_data_drop(store.inner_mut(), index),
                 Instr::MemorySize { result, memory } => {
                     self.execute_memory_size(store.inner(), result, memory)
@@ -1225,75 +1472,99 @@ impl<'engine> Executor<'engine> {
                 Instr::MemoryGrowBy { result, delta } => {
                     self.execute_memory_grow_by(store, result, delta)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryCopy { dst, src, len } => {
                     self.execute_memory_copy(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryCopyTo { dst, src, len } => {
                     self.execute_memory_copy_to(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryCopyFrom { dst, src, len } => {
                     self.execute_memory_copy_from(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryCopyFromTo { dst, src, len } => {
                     self.execute_memory_copy_from_to(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryCopyExact { dst, src, len } => {
                     self.execute_memory_copy_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryCopyToExact { dst, src, len } => {
                     self.execute_memory_copy_to_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryCopyFromExact { dst, src, len } => {
                     self.execute_memory_copy_from_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryCopyFromToExact { dst, src, len } => {
                     self.execute_memory_copy_from_to_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryFill { dst, value, len } => {
                     self.execute_memory_fill(store.inner_mut(), dst, value, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryFillAt { dst, value, len } => {
                     self.execute_memory_fill_at(store.inner_mut(), dst, value, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryFillImm { dst, value, len } => {
                     self.execute_memory_fill_imm(store.inner_mut(), dst, value, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryFillExact { dst, value, len } => {
                     self.execute_memory_fill_exact(store.inner_mut(), dst, value, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryFillAtImm { dst, value, len } => {
                     self.execute_memory_fill_at_imm(store.inner_mut(), dst, value, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryFillAtExact { dst, value, len } => {
                     self.execute_memory_fill_at_exact(store.inner_mut(), dst, value, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryFillImmExact { dst, value, len } => {
                     self.execute_memory_fill_imm_exact(store.inner_mut(), dst, value, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryFillAtImmExact { dst, value, len } => {
                     self.execute_memory_fill_at_imm_exact(store.inner_mut(), dst, value, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryInit { dst, src, len } => {
                     self.execute_memory_init(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryInitTo { dst, src, len } => {
                     self.execute_memory_init_to(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryInitFrom { dst, src, len } => {
                     self.execute_memory_init_from(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryInitFromTo { dst, src, len } => {
                     self.execute_memory_init_from_to(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryInitExact { dst, src, len } => {
                     self.execute_memory_init_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryInitToExact { dst, src, len } => {
                     self.execute_memory_init_to_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryInitFromExact { dst, src, len } => {
                     self.execute_memory_init_from_exact(store.inner_mut(), dst, src, len)?
                 }
+                #[cfg(not(feature = "no-bulk-memory"))]
                 Instr::MemoryInitFromToExact { dst, src, len } => {
                     self.execute_memory_init_from_to_exact(store.inner_mut(), dst, src, len)?
                 }
@@ -1303,7 +1574,6 @@ impl<'engine> Executor<'engine> {
                 | Instr::ElemIndex { .. }
                 | Instr::Const32 { .. }
                 | Instr::I64Const32 { .. }
-                | Instr::F64Const32 { .. }
                 | Instr::BranchTableTarget { .. }
                 | Instr::BranchTableTargetNonOverlapping { .. }
                 | Instr::Register { .. }
@@ -1315,6 +1585,8 @@ impl<'engine> Executor<'engine> {
                 | Instr::RegisterList { .. }
                 | Instr::CallIndirectParams { .. }
                 | Instr::CallIndirectParamsImm16 { .. } => self.invalid_instruction_word()?,
+                #[cfg(not(feature = "no-float"))]
+                Instr::F64Const32 { .. } => self.invalid_instruction_word()?,
                 #[cfg(feature = "simd")]
                 Instr::I8x16Splat { result, value } => self.execute_i8x16_splat(result, value),
                 #[cfg(feature = "simd")]
@@ -1970,7 +2242,7 @@ impl<'engine> Executor<'engine> {
                 }
                 #[cfg(feature = "simd")]
                 Instr::V128StoreOffset16 { ptr, value, offset } => {
-                    self.execute_v128_store_offset16(ptr, offset, value)?
+                    self.execute_v128_store_offset16(store.inner_mut(), ptr, offset, value)?
                 }
                 #[cfg(feature = "simd")]
                 Instr::V128StoreAt { value, address } => {
@@ -1986,7 +2258,13 @@ impl<'engine> Executor<'engine> {
                     value,
                     offset,
                     lane,
-                } => self.execute_v128_store8_lane_offset8(ptr, value, offset, lane)?,
+                } => self.execute_v128_store8_lane_offset8(
+                    store.inner_mut(),
+                    ptr,
+                    value,
+                    offset,
+                    lane,
+                )?,
                 #[cfg(feature = "simd")]
                 Instr::V128Store8LaneAt { value, address } => {
                     self.execute_v128_store8_lane_at(store.inner_mut(), value, address)?
@@ -2001,7 +2279,13 @@ impl<'engine> Executor<'engine> {
                     value,
                     offset,
                     lane,
-                } => self.execute_v128_store16_lane_offset8(ptr, value, offset, lane)?,
+                } => self.execute_v128_store16_lane_offset8(
+                    store.inner_mut(),
+                    ptr,
+                    value,
+                    offset,
+                    lane,
+                )?,
                 #[cfg(feature = "simd")]
                 Instr::V128Store16LaneAt { value, address } => {
                     self.execute_v128_store16_lane_at(store.inner_mut(), value, address)?
@@ -2016,7 +2300,13 @@ impl<'engine> Executor<'engine> {
                     value,
                     offset,
                     lane,
-                } => self.execute_v128_store32_lane_offset8(ptr, value, offset, lane)?,
+                } => self.execute_v128_store32_lane_offset8(
+                    store.inner_mut(),
+                    ptr,
+                    value,
+                    offset,
+                    lane,
+                )?,
                 #[cfg(feature = "simd")]
                 Instr::V128Store32LaneAt { value, address } => {
                     self.execute_v128_store32_lane_at(store.inner_mut(), value, address)?
@@ -2031,7 +2321,13 @@ impl<'engine> Executor<'engine> {
                     value,
                     offset,
                     lane,
-                } => self.execute_v128_store64_lane_offset8(ptr, value, offset, lane)?,
+                } => self.execute_v128_store64_lane_offset8(
+                    store.inner_mut(),
+                    ptr,
+                    value,
+                    offset,
+                    lane,
+                )?,
                 #[cfg(feature = "simd")]
                 Instr::V128Store64LaneAt { value, address } => {
                     self.execute_v128_store64_lane_at(store.inner_mut(), value, address)?
@@ -2283,10 +2579,12 @@ impl<'engine> Executor<'engine> {
 macro_rules! get_entity {
     (
         $(
+            $( #[$attr:meta] )*
             fn $name:ident(&self, index: $index_ty:ty) -> $id_ty:ty;
         )*
     ) => {
         $(
+            $( #[$attr] )*
             #[doc = ::core::concat!(
                 "Returns the [`",
                 ::core::stringify!($id_ty),
@@ -2322,7 +2620,9 @@ impl Executor<'_> {
         fn get_memory(&self, index: index::Memory) -> Memory;
         fn get_table(&self, index: index::Table) -> Table;
         fn get_global(&self, index: index::Global) -> Global;
+        #[cfg(not(feature = "no-bulk-memory"))]
         fn get_data_segment(&self, index: index::Data) -> DataSegment;
+        #[cfg(not(feature = "no-bulk-memory"))]
         fn get_element_segment(&self, index: index::Elem) -> ElementSegment;
     }
 
@@ -2430,7 +2730,8 @@ impl Executor<'_> {
     ///
     /// The initialization of the [`Executor`] allows for efficient execution.
     fn init_call_frame(&mut self, frame: &CallFrame) {
-        Self::init_call_frame_impl(&mut self.stack.values, &mut self.sp, &mut self.ip, frame)
+        Self::init_call_frame_impl(&mut self.stack.values, &mut self.sp, &mut self.ip, frame);
+        self.check_safepoint();
     }
 
     /// Initializes the [`Executor`] state for the [`CallFrame`].
@@ -2460,6 +2761,7 @@ impl Executor<'_> {
     }
 
     /// Executes a fallible generic unary [`Instruction`].
+    #[cfg(not(feature = "no-float"))]
     #[inline(always)]
     fn try_execute_unary_t<P, R>(
         &mut self,
@@ -2692,6 +2994,42 @@ impl Executor<'_> {
         Err(Error::from(trap_code))
     }
 
+    /// Executes a due safepoint before the instruction at the current instruction pointer.
+    ///
+    /// - Traps with [`TrapCode::InstructionLimitExceeded`] if the instruction limit has been exceeded.
+    /// - Folds the control flow into the execution trace if `tracing` is enabled.
+    /// - Records the reached instruction for code coverage if `covering` is enabled.
+    /// - Checks the deadline of the execution if it is due.
+    /// - Invokes the instruction callback if it is due.
+    #[cold]
+    fn execute_safepoint(&mut self, store: &mut PrunedStore) -> Result<(), Error> {
+        self.safepoint_due = false;
+        if self.executed > self.instr_limit {
+            return Err(Error::from(TrapCode::InstructionLimitExceeded));
+        }
+        if self.tracing {
+            let executed = self.executed;
+            let instr = self.ip.get();
+            trace_effect(store.inner_mut(), |trace| {
+                trace.fold_control(executed, instr)
+            });
+        }
+        if self.covering {
+            store.inner_mut().coverage_mut().record(self.ip.get());
+        }
+        if self.executed > self.next_deadline_check {
+            self.next_deadline_check = self.executed.saturating_add(self.deadline_interval);
+            self.next_safepoint = self.next_safepoint();
+            store.inner().check_deadline()?;
+        }
+        if self.executed > self.next_callback {
+            self.next_callback = self.executed.saturating_add(self.callback_interval);
+            self.next_safepoint = self.next_safepoint();
+            store.invoke_instr_callback()?;
+        }
+        Ok(())
+    }
+
     /// Executes an [`Instruction::ConsumeFuel`].
     fn execute_consume_fuel(
         &mut self,
@@ -1,11 +1,14 @@
 use super::{Executor, UntypedValueExt};
 use crate::{
     core::{wasm, TrapCode},
-    ir::{Const16, Reg, ShiftAmount, Sign},
+    ir::{Const16, Reg, ShiftAmount},
     Error,
 };
 use core::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
 
+#[cfg(not(feature = "no-float"))]
+use crate::ir::Sign;
+
 #[cfg(doc)]
 use crate::ir::Instruction;
 
@@ -39,22 +42,6 @@ impl Executor<'_> {
         (Instruction::I64ShrS, execute_i64_shr_s, wasm::i64_shr_s),
         (Instruction::I64Rotl, execute_i64_rotl, wasm::i64_rotl),
         (Instruction::I64Rotr, execute_i64_rotr, wasm::i64_rotr),
-
-        (Instruction::F32Add, execute_f32_add, wasm::f32_add),
-        (Instruction::F32Sub, execute_f32_sub, wasm::f32_sub),
-        (Instruction::F32Mul, execute_f32_mul, wasm::f32_mul),
-        (Instruction::F32Div, execute_f32_div, wasm::f32_div),
-        (Instruction::F32Min, execute_f32_min, wasm::f32_min),
-        (Instruction::F32Max, execute_f32_max, wasm::f32_max),
-        (Instruction::F32Copysign, execute_f32_copysign, wasm::f32_copysign),
-
-        (Instruction::F64Add, execute_f64_add, wasm::f64_add),
-        (Instruction::F64Sub, execute_f64_sub, wasm::f64_sub),
-        (Instruction::F64Mul, execute_f64_mul, wasm::f64_mul),
-        (Instruction::F64Div, execute_f64_div, wasm::f64_div),
-        (Instruction::F64Min, execute_f64_min, wasm::f64_min),
-        (Instruction::F64Max, execute_f64_max, wasm::f64_max),
-        (Instruction::F64Copysign, execute_f64_copysign, wasm::f64_copysign),
     }
 }
 
@@ -293,7 +280,26 @@ impl Executor<'_> {
     }
 }
 
+#[cfg(not(feature = "no-float"))]
 impl Executor<'_> {
+    impl_binary_executors! {
+        (Instruction::F32Add, execute_f32_add, wasm::f32_add),
+        (Instruction::F32Sub, execute_f32_sub, wasm::f32_sub),
+        (Instruction::F32Mul, execute_f32_mul, wasm::f32_mul),
+        (Instruction::F32Div, execute_f32_div, wasm::f32_div),
+        (Instruction::F32Min, execute_f32_min, wasm::f32_min),
+        (Instruction::F32Max, execute_f32_max, wasm::f32_max),
+        (Instruction::F32Copysign, execute_f32_copysign, wasm::f32_copysign),
+
+        (Instruction::F64Add, execute_f64_add, wasm::f64_add),
+        (Instruction::F64Sub, execute_f64_sub, wasm::f64_sub),
+        (Instruction::F64Mul, execute_f64_mul, wasm::f64_mul),
+        (Instruction::F64Div, execute_f64_div, wasm::f64_div),
+        (Instruction::F64Min, execute_f64_min, wasm::f64_min),
+        (Instruction::F64Max, execute_f64_max, wasm::f64_max),
+        (Instruction::F64Copysign, execute_f64_copysign, wasm::f64_copysign),
+    }
+
     /// Executes an [`Instruction::F32CopysignImm`].
     pub fn execute_f32_copysign_imm(&mut self, result: Reg, lhs: Reg, rhs: Sign<f32>) {
         let lhs = self.get_register_as::<f32>(lhs);
@@ -21,7 +21,8 @@ impl Executor<'_> {
     ///
     /// Offsets the instruction pointer using the given [`BranchOffset`].
     fn branch_to(&mut self, offset: BranchOffset) {
-        self.ip.offset(offset.to_i32() as isize)
+        self.ip.offset(offset.to_i32() as isize);
+        self.check_safepoint();
     }
 
     /// Branches and adjusts the value stack.
@@ -30,7 +31,8 @@ impl Executor<'_> {
     ///
     /// Offsets the instruction pointer using the given [`BranchOffset`].
     fn branch_to16(&mut self, offset: BranchOffset16) {
-        self.ip.offset(offset.to_i16() as isize)
+        self.ip.offset(offset.to_i16() as isize);
+        self.check_safepoint();
     }
 
     pub fn execute_branch(&mut self, offset: BranchOffset) {
@@ -49,6 +51,12 @@ impl Executor<'_> {
     pub fn execute_branch_table_0(&mut self, index: Reg, len_targets: u32) {
         let offset = self.fetch_branch_table_offset(index, len_targets);
         self.ip.add(offset);
+        if let Instruction::Branch { offset } = *self.ip.get() {
+            // Note: we jump to the branch target directly instead of dispatching `Instruction::Branch`
+            //       so that the branch table acts as a jump table with a single dispatch.
+            //       Branch table returns are executed as the next conventional instruction in the pipeline.
+            self.execute_branch(offset)
+        }
     }
 
     pub fn execute_branch_table_1(&mut self, index: Reg, len_targets: u32) {
@@ -58,6 +66,7 @@ impl Executor<'_> {
             Instruction::Register { reg } => self.get_register(reg),
             Instruction::Const32 { value } => UntypedVal::from(u32::from(value)),
             Instruction::I64Const32 { value } => UntypedVal::from(i64::from(value)),
+            #[cfg(not(feature = "no-float"))]
             Instruction::F64Const32 { value } => UntypedVal::from(f64::from(value)),
             unexpected => {
                 // Safety: one of the above instruction parameters is guaranteed to exist by the Wasmi translation.
@@ -208,7 +217,10 @@ impl Executor<'_> {
     {
         let lhs: T = self.get_register_as(lhs);
         let rhs: T = self.get_register_as(rhs);
-        if f(lhs, rhs) {
+        let taken = f(lhs, rhs);
+        #[cfg(feature = "branch-profile")]
+        self.profile_branch(taken);
+        if taken {
             return self.branch_to(offset.into());
         }
         self.next_instr()
@@ -227,7 +239,10 @@ impl Executor<'_> {
     {
         let lhs: T = self.get_register_as(lhs);
         let rhs = T::from(rhs);
-        if f(lhs, rhs) {
+        let taken = f(lhs, rhs);
+        #[cfg(feature = "branch-profile")]
+        self.profile_branch(taken);
+        if taken {
             return self.branch_to16(offset);
         }
         self.next_instr()
@@ -246,7 +261,10 @@ impl Executor<'_> {
     {
         let lhs = T::from(lhs);
         let rhs: T = self.get_register_as(rhs);
-        if f(lhs, rhs) {
+        let taken = f(lhs, rhs);
+        #[cfg(feature = "branch-profile")]
+        self.profile_branch(taken);
+        if taken {
             return self.branch_to16(offset);
         }
         self.next_instr()
@@ -338,7 +356,10 @@ impl_execute_branch_binop! {
     (u64, Instruction::BranchI64LtU, execute_branch_i64_lt_u, cmp_lt),
     (i64, Instruction::BranchI64LeS, execute_branch_i64_le_s, cmp_le),
     (u64, Instruction::BranchI64LeU, execute_branch_i64_le_u, cmp_le),
+}
 
+#[cfg(not(feature = "no-float"))]
+impl_execute_branch_binop! {
     (f32, Instruction::BranchF32Eq, execute_branch_f32_eq, cmp_eq),
     (f32, Instruction::BranchF32Ne, execute_branch_f32_ne, cmp_ne),
     (f32, Instruction::BranchF32Lt, execute_branch_f32_lt, cmp_lt),
@@ -1,6 +1,6 @@
 use super::{ControlFlow, Executor, InstructionPtr};
 use crate::{
-    core::TrapCode,
+    core::{TrapCode, UntypedVal},
     engine::{
         code_map::CompiledFuncRef,
         executor::stack::{CallFrame, FrameParams, ValueStack},
@@ -8,8 +8,8 @@ use crate::{
         EngineFunc,
         FuncInOut,
     },
-    func::{FuncEntity, HostFuncEntity},
-    ir::{index, Instruction, Reg, RegSpan},
+    func::{FuncEntity, FuncError, HostFuncEntity, HostReturnCall, WasmFuncEntity},
+    ir::{index, BoundedRegSpan, Instruction, Reg, RegSpan},
     store::{CallHooks, PrunedStore, StoreInner},
     Error,
     Func,
@@ -50,10 +50,34 @@ pub fn dispatch_host_func(
             //       need to clean up the temporary buffer values here.
             //       This is required for resumable calls to work properly.
             value_stack.drop(usize::from(max_inout));
+            // Note: a tail call of a failed host function is never executed.
+            store.inner_mut().take_return_call();
         })?;
     Ok((len_params, len_results))
 }
 
+/// Resolves the Wasm function tail called by `host_func` via `return_call`.
+///
+/// # Errors
+///
+/// - If the tail called function is a host function.
+/// - If the results of the tail called function do not match the results of `host_func`.
+pub fn resolve_return_call<'a>(
+    store: &'a StoreInner,
+    host_func: &HostFuncEntity,
+    return_call: &HostReturnCall,
+) -> Result<&'a WasmFuncEntity, Error> {
+    let FuncEntity::Wasm(func) = store.resolve_func(&return_call.func) else {
+        return Err(Error::from(FuncError::ReturnCallToHost));
+    };
+    let expected = store.resolve_func_type(host_func.ty_dedup());
+    let actual = store.resolve_func_type(func.ty_dedup());
+    if expected.results() != actual.results() {
+        return Err(Error::from(FuncError::MismatchingResultType));
+    }
+    Ok(func)
+}
+
 /// The kind of a function call.
 #[derive(Debug, Copy, Clone)]
 pub enum CallKind {
@@ -260,6 +284,9 @@ impl Executor<'_> {
             self.copy_call_params_list(uninit_params);
         }
         match self.ip.get() {
+            Instruction::RegisterSpan { span } => {
+                self.copy_call_params_span(uninit_params, *span);
+            }
             Instruction::Register { reg } => {
                 self.copy_regs(uninit_params, array::from_ref(reg));
             }
@@ -292,6 +319,15 @@ impl Executor<'_> {
         }
     }
 
+    /// Copies the contiguous `span` of [`Reg`] to the `dst` [`Reg`] span.
+    fn copy_call_params_span(&self, uninit_params: &mut FrameParams, span: BoundedRegSpan) {
+        for value in span.iter() {
+            let value = self.get_register(value);
+            // Safety: see `copy_regs` for why this access is safe.
+            unsafe { uninit_params.init_next(value) }
+        }
+    }
+
     /// Copies a list of [`Instruction::RegisterList`] to the `dst` [`Reg`] span.
     /// Copies the parameters from `src` for the called [`CallFrame`].
     ///
@@ -539,6 +575,16 @@ impl Executor<'_> {
                 false => ResumableHostError::new(error, *func, results).into(),
             })?;
         self.cache.update(store.inner_mut(), &instance);
+        if let Some(return_call) = store.inner_mut().take_return_call() {
+            return self.execute_host_return_call::<C>(
+                store.inner_mut(),
+                &host_func,
+                &return_call,
+                &caller,
+                results,
+                max_inout,
+            );
+        }
         let results = results.iter(len_results);
         match <C as CallContext>::KIND {
             CallKind::Nested => {
@@ -599,6 +645,46 @@ impl Executor<'_> {
         }
     }
 
+    /// Executes the Wasm function tail called by the returned `host_func` via `return_call`.
+    ///
+    /// The called Wasm function replaces the call frame of the host function and
+    /// writes its results into the `results` of the host function.
+    #[cold]
+    fn execute_host_return_call<C: CallContext>(
+        &mut self,
+        store: &mut StoreInner,
+        host_func: &HostFuncEntity,
+        return_call: &HostReturnCall,
+        caller: &CallFrame,
+        results: RegSpan,
+        max_inout: usize,
+    ) -> Result<ControlFlow, Error> {
+        let func = resolve_return_call(store, host_func, return_call)?;
+        let instance = *func.instance();
+        let func_body = func.func_body();
+        self.stack.values.drop(max_inout);
+        if matches!(<C as CallContext>::KIND, CallKind::Tail) {
+            self.stack.values.truncate(caller.frame_offset());
+        }
+        let func = self.code_map.get(Some(store.fuel_mut()), func_body)?;
+        let (mut uninit_params, offsets) = self.stack.values.alloc_call_frame(func, |_| {})?;
+        for param in &return_call.params[..] {
+            // Safety: the parameters have been checked against the
+            //         function type upon `Caller::return_call`.
+            unsafe { uninit_params.init_next(UntypedVal::from(param.clone())) };
+        }
+        uninit_params.init_zeroes();
+        let frame = CallFrame::new(
+            InstructionPtr::new(func.instrs().as_ptr()),
+            offsets,
+            results,
+        );
+        self.init_call_frame(&frame);
+        self.stack.calls.push(frame, Some(instance))?;
+        self.cache.update(store, &instance);
+        Ok(ControlFlow::Continue(()))
+    }
+
     /// Convenience forwarder to [`dispatch_host_func`].
     fn dispatch_host_func(
         &mut self,
@@ -738,9 +824,9 @@ impl Executor<'_> {
     ) -> Result<ControlFlow, Error> {
         let table = self.get_table(table);
         let funcref = store
-            .inner()
-            .resolve_table(&table)
-            .get_untyped(index)
+            .inner_mut()
+            .resolve_table_mut(&table)
+            .get_untyped_materialized(index)
             .map(FuncRef::from)
             .ok_or(TrapCode::TableOutOfBounds)?;
         let func = funcref.func().ok_or(TrapCode::IndirectCallToNull)?;
@@ -22,7 +22,12 @@ impl Executor<'_> {
         (Instruction::I64LtU, execute_i64_lt_u, wasm::i64_lt_u),
         (Instruction::I64LeS, execute_i64_le_s, wasm::i64_le_s),
         (Instruction::I64LeU, execute_i64_le_u, wasm::i64_le_u),
+    }
+}
 
+#[cfg(not(feature = "no-float"))]
+impl Executor<'_> {
+    impl_binary_executors! {
         (Instruction::F32Eq, execute_f32_eq, wasm::f32_eq),
         (Instruction::F32Ne, execute_f32_ne, wasm::f32_ne),
         (Instruction::F32Lt, execute_f32_lt, wasm::f32_lt),
@@ -1,9 +1,13 @@
 use super::Executor;
-use crate::{core::wasm, ir::Reg, Error};
+use crate::{core::wasm, ir::Reg};
+
+#[cfg(not(feature = "no-float"))]
+use crate::Error;
 
 #[cfg(doc)]
 use crate::ir::Instruction;
 
+#[cfg(not(feature = "no-float"))]
 macro_rules! impl_fallible_conversion_impls {
     ( $( (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
@@ -19,6 +23,17 @@ impl Executor<'_> {
     impl_unary_executors! {
         (Instruction::I32WrapI64, execute_i32_wrap_i64, wasm::i32_wrap_i64),
 
+        (Instruction::I32Extend8S, execute_i32_extend8_s, wasm::i32_extend8_s),
+        (Instruction::I32Extend16S, execute_i32_extend16_s, wasm::i32_extend16_s),
+        (Instruction::I64Extend8S, execute_i64_extend8_s, wasm::i64_extend8_s),
+        (Instruction::I64Extend16S, execute_i64_extend16_s, wasm::i64_extend16_s),
+        (Instruction::I64Extend32S, execute_i64_extend32_s, wasm::i64_extend32_s),
+    }
+}
+
+#[cfg(not(feature = "no-float"))]
+impl Executor<'_> {
+    impl_unary_executors! {
         (Instruction::I32TruncSatF32S, execute_i32_trunc_sat_f32_s, wasm::i32_trunc_sat_f32_s),
         (Instruction::I32TruncSatF32U, execute_i32_trunc_sat_f32_u, wasm::i32_trunc_sat_f32_u),
         (Instruction::I32TruncSatF64S, execute_i32_trunc_sat_f64_s, wasm::i32_trunc_sat_f64_s),
@@ -28,12 +43,6 @@ impl Executor<'_> {
         (Instruction::I64TruncSatF64S, execute_i64_trunc_sat_f64_s, wasm::i64_trunc_sat_f64_s),
         (Instruction::I64TruncSatF64U, execute_i64_trunc_sat_f64_u, wasm::i64_trunc_sat_f64_u),
 
-        (Instruction::I32Extend8S, execute_i32_extend8_s, wasm::i32_extend8_s),
-        (Instruction::I32Extend16S, execute_i32_extend16_s, wasm::i32_extend16_s),
-        (Instruction::I64Extend8S, execute_i64_extend8_s, wasm::i64_extend8_s),
-        (Instruction::I64Extend16S, execute_i64_extend16_s, wasm::i64_extend16_s),
-        (Instruction::I64Extend32S, execute_i64_extend32_s, wasm::i64_extend32_s),
-
         (Instruction::F32DemoteF64, execute_f32_demote_f64, wasm::f32_demote_f64),
         (Instruction::F64PromoteF32, execute_f64_promote_f32, wasm::f64_promote_f32),
 
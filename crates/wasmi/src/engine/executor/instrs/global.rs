@@ -1,8 +1,9 @@
-use super::Executor;
+use super::{trace_effect, Executor};
 use crate::{
     core::{hint, UntypedVal},
     ir::{index, Const16, Reg},
-    store::StoreInner,
+    store::{PrunedStore, StoreInner},
+    Error,
 };
 
 #[cfg(doc)]
@@ -26,10 +27,10 @@ impl Executor<'_> {
     /// Executes an [`Instruction::GlobalSet`].
     pub fn execute_global_set(
         &mut self,
-        store: &mut StoreInner,
+        store: &mut PrunedStore,
         global: index::Global,
         input: Reg,
-    ) {
+    ) -> Result<(), Error> {
         let input = self.get_register(input);
         self.execute_global_set_impl(store, global, input)
     }
@@ -37,10 +38,10 @@ impl Executor<'_> {
     /// Executes an [`Instruction::GlobalSetI32Imm16`].
     pub fn execute_global_set_i32imm16(
         &mut self,
-        store: &mut StoreInner,
+        store: &mut PrunedStore,
         global: index::Global,
         input: Const16<i32>,
-    ) {
+    ) -> Result<(), Error> {
         let input = i32::from(input).into();
         self.execute_global_set_impl(store, global, input)
     }
@@ -48,10 +49,10 @@ impl Executor<'_> {
     /// Executes an [`Instruction::GlobalSetI64Imm16`].
     pub fn execute_global_set_i64imm16(
         &mut self,
-        store: &mut StoreInner,
+        store: &mut PrunedStore,
         global: index::Global,
         input: Const16<i64>,
-    ) {
+    ) -> Result<(), Error> {
         let input = i64::from(input).into();
         self.execute_global_set_impl(store, global, input)
     }
@@ -59,18 +60,46 @@ impl Executor<'_> {
     /// Executes a generic `global.set` instruction.
     fn execute_global_set_impl(
         &mut self,
-        store: &mut StoreInner,
+        store: &mut PrunedStore,
         global: index::Global,
         new_value: UntypedVal,
-    ) {
+    ) -> Result<(), Error> {
         match u32::from(global) {
             0 => unsafe { self.cache.global.set(new_value) },
             _ => {
                 hint::cold();
                 let global = self.get_global(global);
-                store.resolve_global_mut(&global).set_untyped(new_value)
+                store
+                    .inner_mut()
+                    .resolve_global_mut(&global)
+                    .set_untyped(new_value)
             }
         };
-        self.next_instr()
+        if hint::unlikely(self.tracing) {
+            trace_effect(store.inner_mut(), |trace| {
+                trace.fold_global_set(u32::from(global), new_value)
+            });
+        }
+        if hint::unlikely(store.inner().has_watched_globals()) {
+            self.notify_global_set(store, global)?;
+        }
+        self.next_instr();
+        Ok(())
+    }
+
+    /// Invokes the [`Store::global_hook`] if the written global variable is watched.
+    ///
+    /// [`Store::global_hook`]: crate::Store::global_hook
+    #[cold]
+    fn notify_global_set(
+        &mut self,
+        store: &mut PrunedStore,
+        global: index::Global,
+    ) -> Result<(), Error> {
+        let global = self.get_global(global);
+        if !store.inner().resolve_global(&global).is_watched() {
+            return Ok(());
+        }
+        store.invoke_global_hook(global)
     }
 }
@@ -1,19 +1,52 @@
-use super::{Executor, InstructionPtr};
+use super::{trace_effect, Executor, InstructionPtr};
 use crate::{
-    core::TrapCode,
+    core::hint,
     engine::utils::unreachable_unchecked,
     error::EntityGrowError,
-    ir::{
-        index::{Data, Memory},
-        Const16,
-        Const32,
-        Instruction,
-        Reg,
-    },
+    ir::{index::Memory, Const32, Instruction, Reg},
     store::{PrunedStore, ResourceLimiterRef, StoreInner},
     Error,
 };
 
+#[cfg(not(feature = "no-bulk-memory"))]
+use crate::{
+    core::TrapCode,
+    ir::{index::Data, Const16},
+};
+
+/// Returns the `len` bytes of `bytes` starting at `index`.
+///
+/// # Note
+///
+/// This performs the bounds checks required by the Wasm spec for bulk-memory operations.
+/// Afterwards, bulk-memory operations are performed via `memmove`, `memcpy` or `memset`.
+///
+/// # Errors
+///
+/// Returns [`TrapCode::MemoryOutOfBounds`] if the range is out of bounds of `bytes`.
+#[inline]
+#[cfg(not(feature = "no-bulk-memory"))]
+fn memory_slice(bytes: &[u8], index: usize, len: usize) -> Result<&[u8], TrapCode> {
+    bytes
+        .get(index..)
+        .and_then(|bytes| bytes.get(..len))
+        .ok_or(TrapCode::MemoryOutOfBounds)
+}
+
+/// Returns the `len` bytes of `bytes` starting at `index` mutably.
+///
+/// # Errors
+///
+/// Returns [`TrapCode::MemoryOutOfBounds`] if the range is out of bounds of `bytes`.
+#[inline]
+#[cfg(not(feature = "no-bulk-memory"))]
+fn memory_slice_mut(bytes: &mut [u8], index: usize, len: usize) -> Result<&mut [u8], TrapCode> {
+    bytes
+        .get_mut(index..)
+        .and_then(|bytes| bytes.get_mut(..len))
+        .ok_or(TrapCode::MemoryOutOfBounds)
+}
+
 impl Executor<'_> {
     /// Returns the [`Instruction::MemoryIndex`] parameter for an [`Instruction`].
     fn fetch_memory_index(&self, offset: usize) -> Memory {
@@ -33,6 +66,7 @@ impl Executor<'_> {
     }
 
     /// Returns the [`Instruction::DataIndex`] parameter for an [`Instruction`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn fetch_data_segment_index(&self, offset: usize) -> Data {
         let mut addr: InstructionPtr = self.ip;
         addr.add(offset);
@@ -50,6 +84,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::DataDrop`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_data_drop(&mut self, store: &mut StoreInner, segment_index: Data) {
         let segment = self.get_data_segment(segment_index);
         store.resolve_data_segment_mut(&segment).drop_bytes();
@@ -108,6 +143,7 @@ impl Executor<'_> {
             self.execute_memory_size_impl(store, result, memory);
             return self.try_next_instr_at(2);
         }
+        let memory_index = memory;
         let memory = self.get_memory(memory);
         let (memory, fuel) = store.resolve_memory_and_fuel_mut(&memory);
         let return_value = memory.grow(delta, Some(fuel), resource_limiter);
@@ -127,11 +163,17 @@ impl Executor<'_> {
             },
             Err(EntityGrowError::TrapCode(trap_code)) => return Err(Error::from(trap_code)),
         };
+        if hint::unlikely(self.tracing) {
+            trace_effect(store, |trace| {
+                trace.fold_memory_grow(u32::from(memory_index), delta, return_value)
+            });
+        }
         self.set_register(result, return_value);
         self.try_next_instr_at(2)
     }
 
     /// Executes an [`Instruction::MemoryCopy`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_copy(
         &mut self,
         store: &mut StoreInner,
@@ -146,6 +188,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryCopyTo`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_copy_to(
         &mut self,
         store: &mut StoreInner,
@@ -160,6 +203,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryCopyFrom`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_copy_from(
         &mut self,
         store: &mut StoreInner,
@@ -174,6 +218,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryCopyFromTo`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_copy_from_to(
         &mut self,
         store: &mut StoreInner,
@@ -188,6 +233,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryCopyExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_copy_exact(
         &mut self,
         store: &mut StoreInner,
@@ -202,6 +248,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryCopyToExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_copy_to_exact(
         &mut self,
         store: &mut StoreInner,
@@ -216,6 +263,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryCopyFromExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_copy_from_exact(
         &mut self,
         store: &mut StoreInner,
@@ -230,6 +278,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryCopyFromToExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_copy_from_to_exact(
         &mut self,
         store: &mut StoreInner,
@@ -245,6 +294,7 @@ impl Executor<'_> {
 
     /// Executes a generic `memory.copy` instruction.
     #[inline(never)]
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn execute_memory_copy_impl(
         &mut self,
         store: &mut StoreInner,
@@ -264,30 +314,30 @@ impl Executor<'_> {
         let dst_memory = self.fetch_memory_index(1);
         let src_memory = self.fetch_memory_index(2);
         if src_memory == dst_memory {
-            return self
-                .execute_memory_copy_within_impl(store, src_memory, dst_index, src_index, len);
+            self.execute_memory_copy_within_impl(store, src_memory, dst_index, src_index, len)?;
+        } else {
+            let (src, dst, fuel) = store.resolve_memory_pair_and_fuel(
+                &self.get_memory(src_memory),
+                &self.get_memory(dst_memory),
+            );
+            dst.check_writable(dst_index, len)?;
+            let src_bytes = memory_slice(src.data(), src_index, len)?;
+            let dst_bytes = memory_slice_mut(dst.data_mut(), dst_index, len)?;
+            fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
+            dst_bytes.copy_from_slice(src_bytes);
+        }
+        if hint::unlikely(self.tracing) {
+            trace_effect(store, |trace| {
+                let (dst_memory, src_memory) = (u32::from(dst_memory), u32::from(src_memory));
+                let (dst, src, len) = (dst_index as u64, src_index as u64, len as u64);
+                trace.fold_memory_copy(dst_memory, src_memory, dst, src, len)
+            });
         }
-        let (src_memory, dst_memory, fuel) = store.resolve_memory_pair_and_fuel(
-            &self.get_memory(src_memory),
-            &self.get_memory(dst_memory),
-        );
-        // These accesses just perform the bounds checks required by the Wasm spec.
-        let src_bytes = src_memory
-            .data()
-            .get(src_index..)
-            .and_then(|memory| memory.get(..len))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
-        let dst_bytes = dst_memory
-            .data_mut()
-            .get_mut(dst_index..)
-            .and_then(|memory| memory.get_mut(..len))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
-        fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
-        dst_bytes.copy_from_slice(src_bytes);
         self.try_next_instr_at(3)
     }
 
     /// Executes a generic `memory.copy` instruction.
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn execute_memory_copy_within_impl(
         &mut self,
         store: &mut StoreInner,
@@ -295,25 +345,21 @@ impl Executor<'_> {
         dst_index: usize,
         src_index: usize,
         len: usize,
-    ) -> Result<(), Error> {
+    ) -> Result<(), TrapCode> {
         let memory = self.get_memory(memory);
         let (memory, fuel) = store.resolve_memory_and_fuel_mut(&memory);
+        memory.check_writable(dst_index, len)?;
         let bytes = memory.data_mut();
         // These accesses just perform the bounds checks required by the Wasm spec.
-        bytes
-            .get(src_index..)
-            .and_then(|memory| memory.get(..len))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
-        bytes
-            .get(dst_index..)
-            .and_then(|memory| memory.get(..len))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
+        memory_slice(bytes, src_index, len)?;
+        memory_slice(bytes, dst_index, len)?;
         fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
         bytes.copy_within(src_index..src_index.wrapping_add(len), dst_index);
-        self.try_next_instr_at(3)
+        Ok(())
     }
 
     /// Executes an [`Instruction::MemoryFill`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_fill(
         &mut self,
         store: &mut StoreInner,
@@ -328,6 +374,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryFillAt`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_fill_at(
         &mut self,
         store: &mut StoreInner,
@@ -342,6 +389,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryFillImm`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_fill_imm(
         &mut self,
         store: &mut StoreInner,
@@ -355,6 +403,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryFillAtImm`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_fill_at_imm(
         &mut self,
         store: &mut StoreInner,
@@ -368,6 +417,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryFillExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_fill_exact(
         &mut self,
         store: &mut StoreInner,
@@ -382,6 +432,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryFillAtExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_fill_at_exact(
         &mut self,
         store: &mut StoreInner,
@@ -396,6 +447,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryFillImmExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_fill_imm_exact(
         &mut self,
         store: &mut StoreInner,
@@ -409,6 +461,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryFillAtImmExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_fill_at_imm_exact(
         &mut self,
         store: &mut StoreInner,
@@ -423,6 +476,7 @@ impl Executor<'_> {
 
     /// Executes a generic `memory.fill` instruction.
     #[inline(never)]
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn execute_memory_fill_impl(
         &mut self,
         store: &mut StoreInner,
@@ -436,20 +490,24 @@ impl Executor<'_> {
         let Ok(len) = usize::try_from(len) else {
             return Err(Error::from(TrapCode::MemoryOutOfBounds));
         };
-        let memory = self.fetch_memory_index(1);
-        let memory = self.get_memory(memory);
+        let memory_index = self.fetch_memory_index(1);
+        let memory = self.get_memory(memory_index);
         let (memory, fuel) = store.resolve_memory_and_fuel_mut(&memory);
-        let slice = memory
-            .data_mut()
-            .get_mut(dst..)
-            .and_then(|memory| memory.get_mut(..len))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
+        memory.check_writable(dst, len)?;
+        let slice = memory_slice_mut(memory.data_mut(), dst, len)?;
         fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
         slice.fill(value);
+        if hint::unlikely(self.tracing) {
+            trace_effect(store, |trace| {
+                let memory = u32::from(memory_index);
+                trace.fold_memory_fill(memory, dst as u64, value, len as u64)
+            });
+        }
         self.try_next_instr_at(2)
     }
 
     /// Executes an [`Instruction::MemoryInit`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_init(
         &mut self,
         store: &mut StoreInner,
@@ -464,6 +522,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryInitTo`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_init_to(
         &mut self,
         store: &mut StoreInner,
@@ -478,6 +537,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryInitFrom`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_init_from(
         &mut self,
         store: &mut StoreInner,
@@ -492,6 +552,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryInitFromTo`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_init_from_to(
         &mut self,
         store: &mut StoreInner,
@@ -506,6 +567,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryInitExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_init_exact(
         &mut self,
         store: &mut StoreInner,
@@ -520,6 +582,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryInitToExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_init_to_exact(
         &mut self,
         store: &mut StoreInner,
@@ -534,6 +597,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryInitFromExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_init_from_exact(
         &mut self,
         store: &mut StoreInner,
@@ -548,6 +612,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::MemoryInitFromToExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_memory_init_from_to_exact(
         &mut self,
         store: &mut StoreInner,
@@ -563,6 +628,7 @@ impl Executor<'_> {
 
     /// Executes a generic `memory.init` instruction.
     #[inline(never)]
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn execute_memory_init_impl(
         &mut self,
         store: &mut StoreInner,
@@ -585,18 +651,17 @@ impl Executor<'_> {
             &self.get_memory(memory_index),
             &self.get_data_segment(data_index),
         );
-        let memory = memory
-            .data_mut()
-            .get_mut(dst_index..)
-            .and_then(|memory| memory.get_mut(..len))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
-        let data = data
-            .bytes()
-            .get(src_index..)
-            .and_then(|data| data.get(..len))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
+        memory.check_writable(dst_index, len)?;
+        let memory = memory_slice_mut(memory.data_mut(), dst_index, len)?;
+        let data = memory_slice(data.bytes(), src_index, len)?;
         fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
         memory.copy_from_slice(data);
+        if hint::unlikely(self.tracing) {
+            trace_effect(store, |trace| {
+                let (memory, data) = (u32::from(memory_index), u32::from(data_index));
+                trace.fold_memory_init(memory, data, dst, src, len as u32)
+            });
+        }
         self.try_next_instr_at(3)
     }
 }
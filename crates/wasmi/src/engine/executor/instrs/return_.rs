@@ -146,6 +146,7 @@ impl Executor<'_> {
     }
 
     /// Execute an [`Instruction::ReturnF64Imm32`] returning a single 32-bit encoded `f64` value.
+    #[cfg(not(feature = "no-float"))]
     pub fn execute_return_f64imm32(
         &mut self,
         store: &mut StoreInner,
@@ -233,8 +234,10 @@ impl Executor<'_> {
         value: T,
         f: fn(&mut Self, &mut StoreInner, T) -> ControlFlow,
     ) -> ControlFlow {
-        let condition = self.get_register(condition);
-        match bool::from(condition) {
+        let condition = bool::from(self.get_register(condition));
+        #[cfg(feature = "branch-profile")]
+        self.profile_branch(condition);
+        match condition {
             true => f(self, store, value),
             false => {
                 self.next_instr();
@@ -291,6 +294,7 @@ impl Executor<'_> {
     }
 
     /// Execute an [`Instruction::ReturnNezF64Imm32`] returning a single 32-bit encoded immediate `f64` value.
+    #[cfg(not(feature = "no-float"))]
     pub fn execute_return_nez_f64imm32(
         &mut self,
         store: &mut StoreInner,
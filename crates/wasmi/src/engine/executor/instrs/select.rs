@@ -131,6 +131,7 @@ impl<'engine> Executor<'engine> {
     }
 
     /// Executes an [`Instruction::SelectF64Imm32Rhs`].
+    #[cfg(not(feature = "no-float"))]
     pub fn execute_select_f64imm32_rhs(&mut self, result: Reg, lhs: Reg) {
         let (condition, rhs) = self.fetch_register_and_imm32::<f32>();
         self.execute_select_impl(
@@ -142,6 +143,7 @@ impl<'engine> Executor<'engine> {
     }
 
     /// Executes an [`Instruction::SelectF64Imm32Lhs`].
+    #[cfg(not(feature = "no-float"))]
     pub fn execute_select_f64imm32_lhs(&mut self, result: Reg, lhs: Const32<f64>) {
         let (condition, rhs) = self.fetch_register_2();
         self.execute_select_impl(
@@ -153,6 +155,7 @@ impl<'engine> Executor<'engine> {
     }
 
     /// Executes an [`Instruction::SelectF64Imm32`].
+    #[cfg(not(feature = "no-float"))]
     pub fn execute_select_f64imm32(&mut self, result: Reg, lhs: Const32<f64>) {
         let (condition, rhs) = self.fetch_register_and_imm32::<f32>();
         self.execute_select_impl(result, condition, |_| f64::from(lhs), |_| f64::from(rhs))
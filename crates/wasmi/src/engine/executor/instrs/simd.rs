@@ -1,6 +1,7 @@
-use super::Executor;
+use super::{trace_effect, Executor};
 use crate::{
     core::{
+        hint,
         simd::{
             self,
             ImmLaneIdx16,
@@ -30,6 +31,7 @@ use crate::{
     store::StoreInner,
     Error,
 };
+use core::mem;
 
 #[cfg(doc)]
 use crate::ir::Offset64Hi;
@@ -716,12 +718,13 @@ macro_rules! impl_execute_v128_store_lane_offset16 {
             #[doc = concat!("Executes an [`Instruction::", stringify!($op), "`] instruction.")]
             pub fn $exec(
                 &mut self,
+                store: &mut StoreInner,
                 ptr: Reg,
                 value: Reg,
                 offset: Offset8,
                 lane: <$ty as IntoLaneIdx>::LaneIdx,
             ) -> Result<(), Error> {
-                self.execute_v128_store_lane_offset8::<$ty>(ptr, value, offset, lane, $eval)
+                self.execute_v128_store_lane_offset8::<$ty>(store, ptr, value, offset, lane, $eval)
             }
         )*
     };
@@ -757,20 +760,43 @@ type V128StoreLaneAt<LaneType> =
     fn(memory: &mut [u8], address: usize, value: V128, lane: LaneType) -> Result<(), TrapCode>;
 
 impl Executor<'_> {
+    /// Folds a store of the `lane` of `value` to `memory` at `address` into the execution trace if enabled.
+    #[inline(always)]
+    fn trace_store_lane(
+        &self,
+        store: &mut StoreInner,
+        memory: index::Memory,
+        address: u64,
+        value: V128,
+        lane: u8,
+    ) {
+        if hint::unlikely(self.tracing) {
+            trace_effect(store, |trace| {
+                trace.fold_store_lane(u32::from(memory), address, value.into(), lane)
+            });
+        }
+    }
+
     fn execute_v128_store_lane<T: IntoLaneIdx>(
         &mut self,
         store: &mut StoreInner,
         ptr: Reg,
         offset_lo: Offset64Lo,
         eval: V128StoreLane<T::LaneIdx>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error>
+    where
+        T::LaneIdx: TryFrom<u8> + Into<u8>,
+    {
         let (value, offset_hi) = self.fetch_value_and_offset_hi();
-        let (lane, memory) = self.fetch_lane_and_memory(2);
-        let offset = Offset64::combine(offset_hi, offset_lo);
+        let (lane, memory) = self.fetch_lane_and_memory::<T::LaneIdx>(2);
+        let offset = u64::from(Offset64::combine(offset_hi, offset_lo));
         let ptr = self.get_register_as::<u64>(ptr);
         let v128 = self.get_register_as::<V128>(value);
-        let memory = self.fetch_memory_bytes_mut(memory, store);
-        eval(memory, ptr, u64::from(offset), v128, lane)?;
+        self.check_memory_writable(store, memory, ptr, offset, mem::size_of::<T>())?;
+        let bytes = self.fetch_memory_bytes_mut(memory, store);
+        eval(bytes, ptr, offset, v128, lane)?;
+        // Note: the store succeeded and thus `ptr + offset` does not overflow.
+        self.trace_store_lane(store, memory, ptr.wrapping_add(offset), v128, lane.into());
         self.try_next_instr_at(3)
     }
 
@@ -783,17 +809,26 @@ impl Executor<'_> {
 
     fn execute_v128_store_lane_offset8<T: IntoLaneIdx>(
         &mut self,
+        store: &mut StoreInner,
         ptr: Reg,
         value: Reg,
         offset: Offset8,
         lane: T::LaneIdx,
         eval: V128StoreLane<T::LaneIdx>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error>
+    where
+        T::LaneIdx: Into<u8>,
+    {
         let ptr = self.get_register_as::<u64>(ptr);
         let offset = u64::from(Offset64::from(offset));
         let v128 = self.get_register_as::<V128>(value);
+        let len = mem::size_of::<T>();
+        self.check_memory_writable(store, index::Memory::from(0), ptr, offset, len)?;
         let memory = self.fetch_default_memory_bytes_mut();
         eval(memory, ptr, offset, v128, lane)?;
+        // Note: the store succeeded and thus `ptr + offset` does not overflow.
+        let address = ptr.wrapping_add(offset);
+        self.trace_store_lane(store, index::Memory::from(0), address, v128, lane.into());
         self.try_next_instr()
     }
 
@@ -816,8 +851,12 @@ impl Executor<'_> {
     {
         let (lane, memory) = self.fetch_lane_and_memory::<T::LaneIdx>(1);
         let v128 = self.get_register_as::<V128>(value);
-        let memory = self.fetch_memory_bytes_mut(memory, store);
-        eval(memory, usize::from(address), v128, lane)?;
+        let len = mem::size_of::<T>();
+        self.check_memory_writable(store, memory, usize::from(address) as u64, 0, len)?;
+        let bytes = self.fetch_memory_bytes_mut(memory, store);
+        eval(bytes, usize::from(address), v128, lane)?;
+        let address = usize::from(address) as u64;
+        self.trace_store_lane(store, memory, address, v128, lane.into());
         self.try_next_instr_at(2)
     }
 
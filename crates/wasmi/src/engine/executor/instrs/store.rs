@@ -1,6 +1,6 @@
-use super::{Executor, InstructionPtr};
+use super::{trace_effect, Executor, InstructionPtr};
 use crate::{
-    core::{wasm, ReadAs, TrapCode, UntypedVal},
+    core::{hint, wasm, ReadAs, TrapCode, UntypedVal},
     engine::utils::unreachable_unchecked,
     ir::{
         index::Memory,
@@ -16,6 +16,7 @@ use crate::{
     store::StoreInner,
     Error,
 };
+use core::mem;
 
 #[cfg(feature = "simd")]
 use crate::core::{simd, V128};
@@ -48,6 +49,65 @@ impl Executor<'_> {
         }
     }
 
+    /// Folds a store of `value` to `memory` at `address` into the execution trace if enabled.
+    #[inline(always)]
+    pub(super) fn trace_store<T>(
+        &self,
+        store: &mut StoreInner,
+        memory: Memory,
+        address: u64,
+        value: T,
+    ) where
+        T: Into<UntypedVal>,
+    {
+        if hint::unlikely(self.tracing) {
+            trace_effect(store, |trace| {
+                trace.fold_store(u32::from(memory), address, value.into())
+            });
+        }
+    }
+
+    /// Checks if Wasm code may write `len` bytes to `memory` at `address + offset`.
+    ///
+    /// # Note
+    ///
+    /// This is a no-op unless the [`StoreInner`] has linear memories with read-only bytes.
+    /// Out of bounds writes are left to the store operation itself to trap.
+    #[inline(always)]
+    pub(super) fn check_memory_writable(
+        &self,
+        store: &StoreInner,
+        memory: Memory,
+        address: u64,
+        offset: u64,
+        len: usize,
+    ) -> Result<(), TrapCode> {
+        if hint::likely(!store.has_protected_memories()) {
+            return Ok(());
+        }
+        self.check_memory_writable_cold(store, memory, address, offset, len)
+    }
+
+    /// Checks if Wasm code may write `len` bytes to `memory` at `address + offset`.
+    #[cold]
+    fn check_memory_writable_cold(
+        &self,
+        store: &StoreInner,
+        memory: Memory,
+        address: u64,
+        offset: u64,
+        len: usize,
+    ) -> Result<(), TrapCode> {
+        let Some(address) = address.checked_add(offset) else {
+            return Ok(());
+        };
+        let Ok(address) = usize::try_from(address) else {
+            return Ok(());
+        };
+        let memory = self.get_memory(memory);
+        store.resolve_memory(&memory).check_writable(address, len)
+    }
+
     /// Executes a generic Wasm `store[N]` operation.
     ///
     /// # Note
@@ -58,6 +118,7 @@ impl Executor<'_> {
     /// - `{i32, i64}.store8`
     /// - `{i32, i64}.store16`
     /// - `i64.store32`
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn execute_store_wrap<T>(
         &mut self,
         store: &mut StoreInner,
@@ -66,12 +127,18 @@ impl Executor<'_> {
         offset: Offset64,
         value: T,
         store_wrap: WasmStoreOp<T>,
+        len: usize,
     ) -> Result<(), Error>
     where
+        T: Copy + Into<UntypedVal>,
         UntypedVal: ReadAs<T>,
     {
-        let memory = self.fetch_memory_bytes_mut(memory, store);
-        store_wrap(memory, address, u64::from(offset), value)?;
+        let offset = u64::from(offset);
+        self.check_memory_writable(store, memory, address, offset, len)?;
+        let bytes = self.fetch_memory_bytes_mut(memory, store);
+        store_wrap(bytes, address, offset, value)?;
+        // Note: the store succeeded and thus `address + offset` does not overflow.
+        self.trace_store(store, memory, address.wrapping_add(offset), value);
         Ok(())
     }
 
@@ -92,9 +159,15 @@ impl Executor<'_> {
         address: Address32,
         value: T,
         store_wrap_at: WasmStoreAtOp<T>,
-    ) -> Result<(), Error> {
-        let memory = self.fetch_memory_bytes_mut(memory, store);
-        store_wrap_at(memory, usize::from(address), value)?;
+        len: usize,
+    ) -> Result<(), Error>
+    where
+        T: Copy + Into<UntypedVal>,
+    {
+        self.check_memory_writable(store, memory, usize::from(address) as u64, 0, len)?;
+        let bytes = self.fetch_memory_bytes_mut(memory, store);
+        store_wrap_at(bytes, usize::from(address), value)?;
+        self.trace_store(store, memory, usize::from(address) as u64, value);
         Ok(())
     }
 
@@ -110,16 +183,23 @@ impl Executor<'_> {
     /// - `i64.store32`
     fn execute_store_wrap_mem0<T>(
         &mut self,
+        store: &mut StoreInner,
         address: u64,
         offset: Offset64,
         value: T,
         store_wrap: WasmStoreOp<T>,
+        len: usize,
     ) -> Result<(), Error>
     where
+        T: Copy + Into<UntypedVal>,
         UntypedVal: ReadAs<T>,
     {
-        let memory = self.fetch_default_memory_bytes_mut();
-        store_wrap(memory, address, u64::from(offset), value)?;
+        let offset = u64::from(offset);
+        self.check_memory_writable(store, Memory::from(0), address, offset, len)?;
+        let bytes = self.fetch_default_memory_bytes_mut();
+        store_wrap(bytes, address, offset, value)?;
+        // Note: the store succeeded and thus `address + offset` does not overflow.
+        self.trace_store(store, Memory::from(0), address.wrapping_add(offset), value);
         Ok(())
     }
 
@@ -129,8 +209,10 @@ impl Executor<'_> {
         ptr: Reg,
         offset_lo: Offset64Lo,
         store_op: WasmStoreOp<T>,
+        len: usize,
     ) -> Result<(), Error>
     where
+        T: Copy + Into<UntypedVal>,
         UntypedVal: ReadAs<T>,
     {
         let (value, offset_hi) = self.fetch_value_and_offset_hi();
@@ -138,10 +220,11 @@ impl Executor<'_> {
         let offset = Offset64::combine(offset_hi, offset_lo);
         let ptr = self.get_register_as::<u64>(ptr);
         let value = self.get_register_as::<T>(value);
-        self.execute_store_wrap::<T>(store, memory, ptr, offset, value, store_op)?;
+        self.execute_store_wrap::<T>(store, memory, ptr, offset, value, store_op, len)?;
         self.try_next_instr_at(2)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn execute_store_imm<T>(
         &mut self,
         store: &mut StoreInner,
@@ -150,45 +233,67 @@ impl Executor<'_> {
         offset_hi: Offset64Hi,
         value: T,
         store_op: WasmStoreOp<T>,
+        len: usize,
     ) -> Result<(), Error>
     where
+        T: Copy + Into<UntypedVal>,
         UntypedVal: ReadAs<T>,
     {
         let memory = self.fetch_optional_memory(2);
         let offset = Offset64::combine(offset_hi, offset_lo);
         let ptr = self.get_register_as::<u64>(ptr);
-        self.execute_store_wrap::<T>(store, memory, ptr, offset, value, store_op)?;
+        self.execute_store_wrap::<T>(store, memory, ptr, offset, value, store_op, len)?;
         self.try_next_instr_at(2)
     }
 
     fn execute_store_offset16<T>(
         &mut self,
+        store: &mut StoreInner,
         ptr: Reg,
         offset: Offset16,
         value: Reg,
         store_op: WasmStoreOp<T>,
+        len: usize,
     ) -> Result<(), Error>
     where
+        T: Copy + Into<UntypedVal>,
         UntypedVal: ReadAs<T>,
     {
         let ptr = self.get_register_as::<u64>(ptr);
         let value = self.get_register_as::<T>(value);
-        self.execute_store_wrap_mem0::<T>(ptr, Offset64::from(offset), value, store_op)?;
+        self.execute_store_wrap_mem0::<T>(
+            store,
+            ptr,
+            Offset64::from(offset),
+            value,
+            store_op,
+            len,
+        )?;
         self.try_next_instr()
     }
 
     fn execute_store_offset16_imm16<T>(
         &mut self,
+        store: &mut StoreInner,
         ptr: Reg,
         offset: Offset16,
         value: T,
         store_op: WasmStoreOp<T>,
+        len: usize,
     ) -> Result<(), Error>
     where
+        T: Copy + Into<UntypedVal>,
         UntypedVal: ReadAs<T>,
     {
         let ptr = self.get_register_as::<u64>(ptr);
-        self.execute_store_wrap_mem0::<T>(ptr, Offset64::from(offset), value, store_op)?;
+        self.execute_store_wrap_mem0::<T>(
+            store,
+            ptr,
+            Offset64::from(offset),
+            value,
+            store_op,
+            len,
+        )?;
         self.try_next_instr()
     }
 
@@ -198,8 +303,10 @@ impl Executor<'_> {
         address: Address32,
         value: Reg,
         store_at_op: WasmStoreAtOp<T>,
+        len: usize,
     ) -> Result<(), Error>
     where
+        T: Copy + Into<UntypedVal>,
         UntypedVal: ReadAs<T>,
     {
         let memory = self.fetch_optional_memory(1);
@@ -209,6 +316,7 @@ impl Executor<'_> {
             address,
             self.get_register_as::<T>(value),
             store_at_op,
+            len,
         )?;
         self.try_next_instr()
     }
@@ -219,12 +327,14 @@ impl Executor<'_> {
         address: Address32,
         value: T,
         store_at_op: WasmStoreAtOp<T>,
+        len: usize,
     ) -> Result<(), Error>
     where
+        T: Copy + Into<UntypedVal>,
         UntypedVal: ReadAs<T>,
     {
         let memory = self.fetch_optional_memory(1);
-        self.execute_store_wrap_at::<T>(store, memory, address, value, store_at_op)?;
+        self.execute_store_wrap_at::<T>(store, memory, address, value, store_at_op, len)?;
         self.try_next_instr()
     }
 }
@@ -246,18 +356,19 @@ macro_rules! impl_execute_istore {
             #[allow(clippy::cast_lossless)]
             pub fn $fn_store_imm(&mut self, store: &mut StoreInner, ptr: Reg, offset_lo: Offset64Lo) -> Result<(), Error> {
                 let (value, offset_hi) = self.fetch_value_and_offset_imm::<$to_ty>();
-                self.execute_store_imm::<$ty>(store, ptr, offset_lo, offset_hi, value as $ty, $store_fn)
+                self.execute_store_imm::<$ty>(store, ptr, offset_lo, offset_hi, value as $ty, $store_fn, mem::size_of::<$to_ty>())
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16_imm16), "`].")]
             #[allow(clippy::cast_lossless)]
             pub fn $fn_store_off16_imm16(
                 &mut self,
+                store: &mut StoreInner,
                 ptr: Reg,
                 offset: Offset16,
                 value: $from_ty,
             ) -> Result<(), Error> {
-                self.execute_store_offset16_imm16::<$ty>(ptr, offset, <$to_ty>::from(value) as _, $store_fn)
+                self.execute_store_offset16_imm16::<$ty>(store, ptr, offset, <$to_ty>::from(value) as _, $store_fn, mem::size_of::<$to_ty>())
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at_imm16), "`].")]
@@ -269,7 +380,7 @@ macro_rules! impl_execute_istore {
                 value: $from_ty,
             ) -> Result<(), Error> {
                 #[allow(clippy::cast_lossless)]
-                self.execute_store_at_imm16::<$ty>(store, address, <$to_ty>::from(value) as _, $store_at_fn)
+                self.execute_store_at_imm16::<$ty>(store, address, <$to_ty>::from(value) as _, $store_at_fn, mem::size_of::<$to_ty>())
             }
         )*
     };
@@ -327,22 +438,23 @@ macro_rules! impl_execute_istore_trunc {
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store), "`].")]
             pub fn $fn_store(&mut self, store: &mut StoreInner, ptr: Reg, offset_lo: Offset64Lo) -> Result<(), Error> {
-                self.execute_store::<$ty>(store, ptr, offset_lo, $store_fn)
+                self.execute_store::<$ty>(store, ptr, offset_lo, $store_fn, mem::size_of::<$to_ty>())
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16), "`].")]
             pub fn $fn_store_off16(
                 &mut self,
+                store: &mut StoreInner,
                 ptr: Reg,
                 offset: Offset16,
                 value: Reg,
             ) -> Result<(), Error> {
-                self.execute_store_offset16::<$ty>(ptr, offset, value, $store_fn)
+                self.execute_store_offset16::<$ty>(store, ptr, offset, value, $store_fn, mem::size_of::<$to_ty>())
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at), "`].")]
             pub fn $fn_store_at(&mut self, store: &mut StoreInner, address: Address32, value: Reg) -> Result<(), Error> {
-                self.execute_store_at::<$ty>(store, address, value, $store_at_fn)
+                self.execute_store_at::<$ty>(store, address, value, $store_at_fn, mem::size_of::<$to_ty>())
             }
         )*
     };
@@ -426,22 +538,23 @@ macro_rules! impl_execute_store {
         $(
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store), "`].")]
             pub fn $fn_store(&mut self, store: &mut StoreInner, ptr: Reg, offset_lo: Offset64Lo) -> Result<(), Error> {
-                self.execute_store::<$ty>(store, ptr, offset_lo, $store_fn)
+                self.execute_store::<$ty>(store, ptr, offset_lo, $store_fn, mem::size_of::<$ty>())
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16), "`].")]
             pub fn $fn_store_off16(
                 &mut self,
+                store: &mut StoreInner,
                 ptr: Reg,
                 offset: Offset16,
                 value: Reg,
             ) -> Result<(), Error> {
-                self.execute_store_offset16::<$ty>(ptr, offset, value, $store_fn)
+                self.execute_store_offset16::<$ty>(store, ptr, offset, value, $store_fn, mem::size_of::<$ty>())
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at), "`].")]
             pub fn $fn_store_at(&mut self, store: &mut StoreInner, address: Address32, value: Reg) -> Result<(), Error> {
-                self.execute_store_at::<$ty>(store, address, value, $store_at_fn)
+                self.execute_store_at::<$ty>(store, address, value, $store_at_fn, mem::size_of::<$ty>())
             }
         )*
     }
@@ -3,18 +3,14 @@ use crate::{
     core::TrapCode,
     engine::utils::unreachable_unchecked,
     error::EntityGrowError,
-    ir::{
-        index::{Elem, Table},
-        Const16,
-        Const32,
-        Instruction,
-        Reg,
-    },
+    ir::{index::Table, Const16, Const32, Instruction, Reg},
     store::{PrunedStore, ResourceLimiterRef, StoreInner},
-    table::TableEntity,
     Error,
 };
 
+#[cfg(not(feature = "no-bulk-memory"))]
+use crate::{ir::index::Elem, table::TableEntity};
+
 impl Executor<'_> {
     /// Returns the [`Instruction::TableIndex`] parameter for an [`Instruction`].
     fn fetch_table_index(&self, offset: usize) -> Table {
@@ -34,6 +30,7 @@ impl Executor<'_> {
     }
 
     /// Returns the [`Instruction::ElemIndex`] parameter for an [`Instruction`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn fetch_element_segment_index(&self, offset: usize) -> Elem {
         let mut addr: InstructionPtr = self.ip;
         addr.add(offset);
@@ -142,6 +139,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableCopy`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_copy(
         &mut self,
         store: &mut StoreInner,
@@ -156,6 +154,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableCopyTo`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_copy_to(
         &mut self,
         store: &mut StoreInner,
@@ -170,6 +169,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableCopyFrom`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_copy_from(
         &mut self,
         store: &mut StoreInner,
@@ -184,6 +184,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableCopyFromTo`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_copy_from_to(
         &mut self,
         store: &mut StoreInner,
@@ -198,6 +199,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableCopyExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_copy_exact(
         &mut self,
         store: &mut StoreInner,
@@ -212,6 +214,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableCopyToExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_copy_to_exact(
         &mut self,
         store: &mut StoreInner,
@@ -226,6 +229,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableCopyFromExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_copy_from_exact(
         &mut self,
         store: &mut StoreInner,
@@ -240,6 +244,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableCopyFromToExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_copy_from_to_exact(
         &mut self,
         store: &mut StoreInner,
@@ -255,6 +260,7 @@ impl Executor<'_> {
 
     /// Executes a generic `table.copy` instruction.
     #[inline(never)]
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn execute_table_copy_impl(
         &mut self,
         store: &mut StoreInner,
@@ -282,6 +288,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableInit`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_init(
         &mut self,
         store: &mut StoreInner,
@@ -296,6 +303,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableInitTo`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_init_to(
         &mut self,
         store: &mut StoreInner,
@@ -310,6 +318,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableInitFrom`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_init_from(
         &mut self,
         store: &mut StoreInner,
@@ -324,6 +333,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableInitFromTo`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_init_from_to(
         &mut self,
         store: &mut StoreInner,
@@ -338,6 +348,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableInitExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_init_exact(
         &mut self,
         store: &mut StoreInner,
@@ -352,6 +363,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableInitToExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_init_to_exact(
         &mut self,
         store: &mut StoreInner,
@@ -366,6 +378,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableInitFromExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_init_from_exact(
         &mut self,
         store: &mut StoreInner,
@@ -380,6 +393,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::TableInitFromToExact`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_table_init_from_to_exact(
         &mut self,
         store: &mut StoreInner,
@@ -395,6 +409,7 @@ impl Executor<'_> {
 
     /// Executes a generic `table.init` instruction.
     #[inline(never)]
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn execute_table_init_impl(
         &mut self,
         store: &mut StoreInner,
@@ -540,6 +555,7 @@ impl Executor<'_> {
     }
 
     /// Executes an [`Instruction::ElemDrop`].
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn execute_element_drop(&mut self, store: &mut StoreInner, segment_index: Elem) {
         let segment = self.get_element_segment(segment_index);
         store.resolve_element_segment_mut(&segment).drop_items();
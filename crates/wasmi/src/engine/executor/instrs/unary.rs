@@ -13,7 +13,12 @@ impl Executor<'_> {
         (Instruction::I64Clz, execute_i64_clz, wasm::i64_clz),
         (Instruction::I64Ctz, execute_i64_ctz, wasm::i64_ctz),
         (Instruction::I64Popcnt, execute_i64_popcnt, wasm::i64_popcnt),
+    }
+}
 
+#[cfg(not(feature = "no-float"))]
+impl Executor<'_> {
+    impl_unary_executors! {
         (Instruction::F32Abs, execute_f32_abs, wasm::f32_abs),
         (Instruction::F32Neg, execute_f32_neg, wasm::f32_neg),
         (Instruction::F32Ceil, execute_f32_ceil, wasm::f32_ceil),
@@ -2,7 +2,7 @@ pub use self::instrs::ResumableHostError;
 pub(crate) use self::stack::Stack;
 use self::{
     instr_ptr::InstructionPtr,
-    instrs::{dispatch_host_func, execute_instrs},
+    instrs::{dispatch_host_func, execute_instrs, resolve_return_call},
     stack::CallFrame,
 };
 use crate::{
@@ -43,16 +43,42 @@ impl EngineInner {
         params: impl CallParams,
         results: Results,
     ) -> Result<<Results as CallResults>::Results, Error>
+    where
+        Results: CallResults,
+    {
+        if let Some((instance, code)) = self.take_baseline(ctx.store, func) {
+            let values = code.execute(ctx.store, instance, params.call_params(), 0)?;
+            return Ok(results.call_results(&values));
+        }
+        self.execute_func_compiled(ctx, func, params, results)
+    }
+
+    /// Executes the given [`Func`] with the given `params` and returns the `results`.
+    ///
+    /// Unlike [`EngineInner::execute_func`] this never uses the baseline tier.
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm execution traps or runs out of resources.
+    pub fn execute_func_compiled<T, Results>(
+        &self,
+        ctx: StoreContextMut<T>,
+        func: &Func,
+        params: impl CallParams,
+        results: Results,
+    ) -> Result<<Results as CallResults>::Results, Error>
     where
         Results: CallResults,
     {
         let mut stack = self.stacks.lock().reuse_or_new();
-        let results = EngineExecutor::new(&self.code_map, &mut stack)
-            .execute_root_func(ctx.store, func, params, results)
-            .map_err(|error| match error.into_resumable() {
-                Ok(error) => error.into_error(),
-                Err(error) => error,
-            });
+        let results = with_stackless(ctx.store, |store| {
+            EngineExecutor::new(&self.code_map, &mut stack)
+                .execute_root_func(store, func, params, results)
+        })
+        .map_err(|error| match error.into_resumable() {
+            Ok(error) => error.into_error(),
+            Err(error) => error,
+        });
         self.stacks.lock().recycle(stack);
         results
     }
@@ -76,8 +102,10 @@ impl EngineInner {
     {
         let store = ctx.store;
         let mut stack = self.stacks.lock().reuse_or_new();
-        let results = EngineExecutor::new(&self.code_map, &mut stack)
-            .execute_root_func(store, func, params, results);
+        let results = with_stackless(store, |store| {
+            EngineExecutor::new(&self.code_map, &mut stack)
+                .execute_root_func(store, func, params, results)
+        });
         match results {
             Ok(results) => {
                 self.stacks.lock().recycle(stack);
@@ -124,13 +152,15 @@ impl EngineInner {
     {
         let host_func = invocation.host_func();
         let caller_results = invocation.caller_results();
-        let results = EngineExecutor::new(&self.code_map, &mut invocation.stack).resume_func(
-            ctx.store,
-            host_func,
-            params,
-            caller_results,
-            results,
-        );
+        let results = with_stackless(ctx.store, |store| {
+            EngineExecutor::new(&self.code_map, &mut invocation.stack).resume_func(
+                store,
+                host_func,
+                params,
+                caller_results,
+                results,
+            )
+        });
         match results {
             Ok(results) => {
                 self.stacks.lock().recycle(invocation.take_stack());
@@ -152,6 +182,29 @@ impl EngineInner {
     }
 }
 
+/// Runs the execution `f` within `store`.
+///
+/// With [`Config::stackless`] enabled this forbids executions nested into `f`
+/// which would otherwise require additional native stack space.
+///
+/// # Errors
+///
+/// If [`Config::stackless`] is enabled and `store` is already executing.
+///
+/// [`Config::stackless`]: crate::Config::stackless
+fn with_stackless<T, R>(
+    store: &mut Store<T>,
+    f: impl FnOnce(&mut Store<T>) -> Result<R, Error>,
+) -> Result<R, Error> {
+    if !store.engine().config().get_stackless() {
+        return f(store);
+    }
+    store.inner.enter_stackless()?;
+    let result = f(store);
+    store.inner.exit_stackless();
+    result
+}
+
 /// The internal state of the Wasmi engine.
 #[derive(Debug)]
 pub struct EngineExecutor<'engine> {
@@ -238,6 +291,15 @@ impl<'engine> EngineExecutor<'engine> {
                 }
                 let host_func = *host_func;
                 self.dispatch_host_func(store, host_func)?;
+                if let Some(return_call) = store.inner.take_return_call() {
+                    resolve_return_call(&store.inner, &host_func, &return_call)?;
+                    return self.execute_root_func(
+                        store,
+                        &return_call.func,
+                        &return_call.params[..],
+                        results,
+                    );
+                }
             }
         };
         let results = self.write_results_back(results);
@@ -287,7 +349,9 @@ impl<'engine> EngineExecutor<'engine> {
     /// When encountering a Wasm or host trap during execution.
     #[inline(always)]
     fn execute_func<T>(&mut self, store: &mut Store<T>) -> Result<(), Error> {
-        execute_instrs(store.prune(), self.stack, self.code_map)
+        let outcome = execute_instrs(store.prune(), self.stack, self.code_map);
+        let reported = store.invoke_fuel_consumed_hook();
+        outcome.and(reported)
     }
 
     /// Convenience forwarder to [`dispatch_host_func`].
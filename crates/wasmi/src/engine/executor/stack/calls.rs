@@ -1,15 +1,11 @@
 use super::{err_stack_overflow, BaseValueStackOffset, FrameValueStackOffset};
-use crate::{
-    collections::HeadVec,
-    core::TrapCode,
-    engine::executor::InstructionPtr,
-    ir::RegSpan,
-    Instance,
-};
+use crate::{collections::HeadVec, engine::executor::InstructionPtr, ir::RegSpan, Error, Instance};
 use alloc::vec::Vec;
+use core::mem;
 
 #[cfg(doc)]
 use crate::{
+    core::TrapCode,
     engine::executor::stack::ValueStack,
     engine::EngineFunc,
     ir::Instruction,
@@ -35,6 +31,9 @@ pub struct CallStack {
 }
 
 impl CallStack {
+    /// The number of [`CallFrame`]s up to which [`CallStack::shrink`] retains heap memory.
+    const MAX_RETAINED_FRAMES: usize = 256;
+
     /// Creates a new [`CallStack`] using the given recursion limit.
     pub fn new(recursion_limit: usize) -> Self {
         Self {
@@ -44,6 +43,15 @@ impl CallStack {
         }
     }
 
+    /// Creates a new [`CallStack`] that can hold `recursion_limit` [`CallFrame`]s without reallocating.
+    pub fn preallocated(recursion_limit: usize) -> Self {
+        Self {
+            frames: Vec::with_capacity(recursion_limit),
+            instances: HeadVec::with_capacity(recursion_limit),
+            recursion_limit,
+        }
+    }
+
     /// Clears the [`CallStack`] entirely.
     ///
     /// # Note
@@ -58,6 +66,22 @@ impl CallStack {
         self.instances.clear();
     }
 
+    /// Shrinks the [`CallStack`] if it grew beyond its retained number of [`CallFrame`]s.
+    ///
+    /// # Note
+    ///
+    /// This releases the heap memory a [`CallStack`] acquired during deep recursions.
+    pub fn shrink(&mut self) {
+        if self.frames.capacity() > Self::MAX_RETAINED_FRAMES {
+            self.frames.shrink_to(Self::MAX_RETAINED_FRAMES);
+        }
+    }
+
+    /// Returns the number of bytes allocated for the [`CallFrame`]s of the [`CallStack`].
+    pub fn memory_usage(&self) -> usize {
+        self.frames.capacity() * mem::size_of::<CallFrame>()
+    }
+
     /// Returns the number of [`CallFrame`]s on the [`CallStack`].
     #[inline(always)]
     fn len(&self) -> usize {
@@ -93,16 +117,14 @@ impl CallStack {
     ///
     /// # Errors
     ///
-    /// If the recursion limit has been reached.
+    /// - If the recursion limit has been reached.
+    /// - If the system ran out of memory to grow the [`CallStack`].
     #[inline(always)]
-    pub fn push(
-        &mut self,
-        mut call: CallFrame,
-        instance: Option<Instance>,
-    ) -> Result<(), TrapCode> {
+    pub fn push(&mut self, mut call: CallFrame, instance: Option<Instance>) -> Result<(), Error> {
         if self.len() == self.recursion_limit {
-            return Err(err_stack_overflow());
+            return Err(err_stack_overflow().into());
         }
+        self.frames.try_reserve(1)?;
         if let Some(instance) = instance {
             call.changed_instance = self.push_instance(instance);
         }
@@ -1,6 +1,9 @@
 mod calls;
 mod values;
 
+#[cfg(test)]
+mod tests;
+
 pub use self::{
     calls::{CallFrame, CallStack, StackOffsets},
     values::{
@@ -11,7 +14,12 @@ pub use self::{
         ValueStack,
     },
 };
-use crate::{core::TrapCode, Instance, StackLimits};
+use crate::{
+    core::{TrapCode, UntypedVal},
+    Instance,
+    StackLimits,
+};
+use core::mem;
 
 /// Returns a [`TrapCode`] signalling a stack overflow.
 #[cold]
@@ -41,6 +49,17 @@ impl Stack {
         Self { calls, values }
     }
 
+    /// Creates a new [`Stack`] with all the memory its `limits` allow allocated up front.
+    ///
+    /// # Note
+    ///
+    /// Executions using this [`Stack`] never grow it and thus never heap allocate.
+    pub fn preallocated(limits: StackLimits) -> Self {
+        let calls = CallStack::preallocated(limits.maximum_recursion_depth);
+        let values = ValueStack::preallocated(limits.maximum_value_stack_height);
+        Self { calls, values }
+    }
+
     /// Resets the [`Stack`] for clean reuse.
     pub fn reset(&mut self) {
         self.calls.reset();
@@ -59,11 +78,28 @@ impl Stack {
         }
     }
 
+    /// Shrinks the [`Stack`] if deep recursions grew it beyond its retained size.
+    ///
+    /// # Note
+    ///
+    /// The [`ValueStack`] must be contiguous since [`FrameRegisters`] point into it.
+    /// Therefore the [`Stack`] is not segmented but grows on demand up to its `limits`
+    /// and releases the excess heap memory via this method before it is reused.
+    pub fn shrink(&mut self, limits: StackLimits) {
+        self.values.shrink(limits.initial_value_stack_height);
+        self.calls.shrink();
+    }
+
     /// Returns the capacity of the [`Stack`].
     pub fn capacity(&self) -> usize {
         self.values.capacity()
     }
 
+    /// Returns the number of bytes allocated by the [`Stack`].
+    pub fn memory_usage(&self) -> usize {
+        self.values.capacity() * mem::size_of::<UntypedVal>() + self.calls.memory_usage()
+    }
+
     /// Merge the two top-most [`CallFrame`] with respect to a tail call.
     ///
     /// # Panics (Debug)
@@ -0,0 +1,20 @@
+use super::*;
+
+#[test]
+fn shrink_releases_excess_memory() {
+    let limits = StackLimits::default();
+    let mut stack = Stack::new(limits);
+    // Moderate growth is retained to avoid reallocations of subsequent executions.
+    stack.values.extend_by(1_000, |_| {}).unwrap();
+    let capacity = stack.capacity();
+    stack.reset();
+    stack.shrink(limits);
+    assert_eq!(stack.capacity(), capacity);
+    // Growth from deep recursions is released.
+    let deep = limits.maximum_value_stack_height / 2;
+    stack.values.extend_by(deep, |_| {}).unwrap();
+    assert!(stack.capacity() >= deep);
+    stack.reset();
+    stack.shrink(limits);
+    assert!(stack.capacity() < capacity);
+}
@@ -1,8 +1,9 @@
 use super::{err_stack_overflow, StackOffsets};
 use crate::{
-    core::{ReadAs, TrapCode, UntypedVal, WriteAs},
+    core::{ReadAs, UntypedVal, WriteAs},
     engine::code_map::CompiledFuncRef,
     ir::Reg,
+    Error,
 };
 use alloc::vec::Vec;
 use core::{
@@ -31,6 +32,9 @@ impl ValueStack {
 
     /// Default value for maximum value stack height in bytes.
     pub const DEFAULT_MAX_HEIGHT: usize = 1024 * Self::DEFAULT_MIN_HEIGHT;
+
+    /// The value stack height in bytes up to which [`ValueStack::shrink`] retains heap memory.
+    pub const MAX_RETAINED_HEIGHT: usize = 64 * Self::DEFAULT_MIN_HEIGHT;
 }
 
 impl Debug for ValueStack {
@@ -84,6 +88,15 @@ impl ValueStack {
         }
     }
 
+    /// Creates a new empty [`ValueStack`] that can hold `maximum_len` cells without reallocating.
+    ///
+    /// # Panics
+    ///
+    /// If the `maximum_len` is zero.
+    pub fn preallocated(maximum_len: usize) -> Self {
+        Self::new(maximum_len, maximum_len)
+    }
+
     /// Creates an empty [`ValueStack`] that does not allocate heap memory.
     ///
     /// # Note
@@ -109,6 +122,20 @@ impl ValueStack {
         self.values.clear();
     }
 
+    /// Shrinks the [`ValueStack`] to `initial_len` if it grew beyond its retained height.
+    ///
+    /// # Note
+    ///
+    /// This releases the heap memory a [`ValueStack`] acquired during deep recursions
+    /// while retaining enough capacity for common executions to not reallocate.
+    pub fn shrink(&mut self, initial_len: usize) {
+        let retained_len =
+            initial_len.max(Self::MAX_RETAINED_HEIGHT / mem::size_of::<UntypedVal>());
+        if self.capacity() > retained_len {
+            self.values.shrink_to(initial_len);
+        }
+    }
+
     /// Returns the root [`FrameRegisters`] pointing to the first value on the [`ValueStack`].
     pub fn root_stack_ptr(&mut self) -> FrameRegisters {
         FrameRegisters::new(self.values.as_mut_ptr())
@@ -132,18 +159,19 @@ impl ValueStack {
     ///
     /// # Errors
     ///
-    /// When trying to grow the [`ValueStack`] over its maximum size limit.
+    /// - When trying to grow the [`ValueStack`] over its maximum size limit.
+    /// - When the system ran out of memory to grow the [`ValueStack`].
     #[inline(always)]
     pub fn extend_by(
         &mut self,
         additional: usize,
         on_resize: impl FnOnce(&mut Self),
-    ) -> Result<&mut [MaybeUninit<UntypedVal>], TrapCode> {
+    ) -> Result<&mut [MaybeUninit<UntypedVal>], Error> {
         if additional >= self.max_len() - self.len() {
-            return Err(err_stack_overflow());
+            return Err(err_stack_overflow().into());
         }
         let prev_capacity = self.capacity();
-        self.values.reserve(additional);
+        self.values.try_reserve(additional)?;
         if prev_capacity != self.capacity() {
             on_resize(self);
         }
@@ -216,12 +244,13 @@ impl ValueStack {
     ///
     /// # Errors
     ///
-    /// When trying to grow the [`ValueStack`] over its maximum size limit.
+    /// - When trying to grow the [`ValueStack`] over its maximum size limit.
+    /// - When the system ran out of memory to grow the [`ValueStack`].
     pub fn alloc_call_frame(
         &mut self,
         func: CompiledFuncRef,
         on_resize: impl FnMut(&mut Self),
-    ) -> Result<(FrameParams, StackOffsets), TrapCode> {
+    ) -> Result<(FrameParams, StackOffsets), Error> {
         let len_registers = func.len_registers();
         let len_consts = func.consts().len();
         let len = self.len();
@@ -3,6 +3,7 @@ use crate::{
     collections::arena::{ArenaIndex, DedupArena, GuardedEntity},
     FuncType,
 };
+use core::mem;
 
 /// A raw index to a function signature entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -113,6 +114,19 @@ impl FuncTypeRegistry {
         ))
     }
 
+    /// Returns the number of bytes held by the [`FuncTypeRegistry`].
+    ///
+    /// # Note
+    ///
+    /// Each function type is stored twice for deduplication and both copies share their heap allocation.
+    pub(crate) fn memory_usage(&self) -> usize {
+        let entry_size = 2 * mem::size_of::<FuncType>() + mem::size_of::<DedupFuncTypeIdx>();
+        self.func_types
+            .iter()
+            .map(|(_, func_type)| entry_size + func_type.heap_size())
+            .sum()
+    }
+
     /// Resolves a deduplicated function type into a [`FuncType`] entity.
     ///
     /// # Panics
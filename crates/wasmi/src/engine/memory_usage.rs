@@ -0,0 +1,84 @@
+use super::EngineInner;
+
+/// The heap memory held by an [`Engine`] in bytes.
+///
+/// Computed via [`Engine::memory_usage`].
+///
+/// # Note
+///
+/// - The reported numbers are estimates that do not include allocator overhead.
+/// - Memory shared with [`Module`]s, such as their Wasm module headers,
+///   is not attributed to the [`Engine`].
+/// - Reusable allocations for Wasm validation and translation are not included.
+///
+/// [`Engine`]: crate::Engine
+/// [`Engine::memory_usage`]: crate::Engine::memory_usage
+/// [`Module`]: crate::Module
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct EngineMemoryUsage {
+    /// The bytes held by compiled Wasmi bytecode and the bookkeeping of all functions.
+    pub(crate) compiled_code: usize,
+    /// The bytes held by Wasm function bodies awaiting lazy compilation.
+    pub(crate) uncompiled_code: usize,
+    /// The bytes held by function local constant value pools.
+    pub(crate) const_pools: usize,
+    /// The bytes held by the registry of deduplicated function types.
+    pub(crate) type_registry: usize,
+    /// The bytes held by cached execution stacks.
+    pub(crate) caches: usize,
+}
+
+impl EngineMemoryUsage {
+    /// Returns the bytes held by compiled Wasmi bytecode and the bookkeeping of all functions.
+    pub fn compiled_code(&self) -> usize {
+        self.compiled_code
+    }
+
+    /// Returns the bytes held by Wasm function bodies awaiting lazy compilation.
+    ///
+    /// This is zero unless lazy compilation is enabled via [`Config::compilation_mode`].
+    ///
+    /// [`Config::compilation_mode`]: crate::Config::compilation_mode
+    pub fn uncompiled_code(&self) -> usize {
+        self.uncompiled_code
+    }
+
+    /// Returns the bytes held by the constant value pools of compiled functions.
+    pub fn const_pools(&self) -> usize {
+        self.const_pools
+    }
+
+    /// Returns the bytes held by the registry of deduplicated function types.
+    pub fn type_registry(&self) -> usize {
+        self.type_registry
+    }
+
+    /// Returns the bytes held by internal caches, such as execution stacks kept for reuse.
+    ///
+    /// The amount of cached execution stacks is configured via [`Config::set_cached_stacks`].
+    ///
+    /// [`Config::set_cached_stacks`]: crate::Config::set_cached_stacks
+    pub fn caches(&self) -> usize {
+        self.caches
+    }
+
+    /// Returns the total bytes held by the [`Engine`](crate::Engine).
+    pub fn total(&self) -> usize {
+        self.compiled_code
+            + self.uncompiled_code
+            + self.const_pools
+            + self.type_registry
+            + self.caches
+    }
+}
+
+impl EngineInner {
+    /// Returns the [`EngineMemoryUsage`] of the [`EngineInner`].
+    pub fn memory_usage(&self) -> EngineMemoryUsage {
+        let mut usage = EngineMemoryUsage::default();
+        self.code_map.memory_usage(&mut usage);
+        usage.type_registry = self.func_types.read().memory_usage();
+        usage.caches = self.stacks.lock().memory_usage();
+        usage
+    }
+}
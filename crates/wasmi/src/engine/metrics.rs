@@ -0,0 +1,241 @@
+use super::code_map::CompiledFuncRef;
+use crate::ir::Instruction;
+use alloc::boxed::Box;
+use core::time::Duration;
+
+/// Measures the time spent on translating a Wasm function if the platform supports it.
+///
+/// # Note
+///
+/// Time is only measured with the `std` crate feature enabled and not on
+/// `wasm32-unknown-unknown` since querying the time panics on that platform.
+#[derive(Debug, Copy, Clone)]
+pub struct TranslationTimer {
+    /// The point in time at which the translation started.
+    #[cfg(all(
+        feature = "std",
+        not(all(target_family = "wasm", target_os = "unknown"))
+    ))]
+    start: std::time::Instant,
+}
+
+impl TranslationTimer {
+    /// Starts a new [`TranslationTimer`].
+    pub fn start() -> Self {
+        Self {
+            #[cfg(all(
+                feature = "std",
+                not(all(target_family = "wasm", target_os = "unknown"))
+            ))]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Returns the time elapsed since the [`TranslationTimer`] was started if measured.
+    pub fn elapsed(&self) -> Option<Duration> {
+        #[cfg(all(
+            feature = "std",
+            not(all(target_family = "wasm", target_os = "unknown"))
+        ))]
+        {
+            Some(self.start.elapsed())
+        }
+        #[cfg(not(all(
+            feature = "std",
+            not(all(target_family = "wasm", target_os = "unknown"))
+        )))]
+        {
+            None
+        }
+    }
+}
+
+/// The compilation metrics of a compiled Wasm function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FuncCompilationMetrics {
+    /// The index of the function within its Wasm module.
+    index: u32,
+    /// The time spent on validating and translating the function if measured.
+    translation_time: Option<Duration>,
+    /// The number of Wasmi bytecode instructions of the function.
+    len_instrs: u32,
+    /// The number of function local constant values of the function.
+    len_consts: u32,
+    /// The number of registers used by the function in total.
+    len_registers: u16,
+    /// The number of basic blocks of the function that consume fuel.
+    len_fuel_blocks: u32,
+}
+
+impl FuncCompilationMetrics {
+    /// Creates new [`FuncCompilationMetrics`] for the function at `index` from its compiled `func`.
+    pub(super) fn new(
+        index: u32,
+        func: CompiledFuncRef,
+        translation_time: Option<Duration>,
+    ) -> Self {
+        let instrs = func.instrs();
+        let len_fuel_blocks = instrs
+            .iter()
+            .filter(|instr| matches!(instr, Instruction::ConsumeFuel { .. }))
+            .count();
+        Self {
+            index,
+            translation_time,
+            len_instrs: instrs.len() as u32,
+            len_consts: func.consts().len() as u32,
+            len_registers: func.len_registers(),
+            len_fuel_blocks: len_fuel_blocks as u32,
+        }
+    }
+
+    /// Returns the index of the function within its Wasm module.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the time spent on validating and translating the function.
+    ///
+    /// Returns `None` if the time has not been measured, e.g. without the `std` crate feature.
+    pub fn translation_time(&self) -> Option<Duration> {
+        self.translation_time
+    }
+
+    /// Returns the number of Wasmi bytecode instructions emitted for the function.
+    pub fn len_instrs(&self) -> u32 {
+        self.len_instrs
+    }
+
+    /// Returns the number of function local constant values occupying const-pool slots.
+    pub fn len_consts(&self) -> u32 {
+        self.len_consts
+    }
+
+    /// Returns the number of registers used by the function in total.
+    ///
+    /// This includes registers for function local constants, parameters and locals.
+    pub fn len_registers(&self) -> u16 {
+        self.len_registers
+    }
+
+    /// Returns the number of basic blocks of the function that consume fuel.
+    ///
+    /// This is always 0 unless fuel metering is enabled via [`Config::consume_fuel`].
+    ///
+    /// [`Config::consume_fuel`]: crate::Config::consume_fuel
+    pub fn len_fuel_blocks(&self) -> u32 {
+        self.len_fuel_blocks
+    }
+}
+
+/// The compilation metrics of the compiled internal functions of a [`Module`].
+///
+/// Computed via [`Module::compilation_metrics`].
+///
+/// # Note
+///
+/// Functions that have not yet been compiled, e.g. due to lazy compilation via
+/// [`CompilationMode::Lazy`], are not part of the [`CompilationMetrics`].
+///
+/// [`Module`]: crate::Module
+/// [`Module::compilation_metrics`]: crate::Module::compilation_metrics
+/// [`CompilationMode::Lazy`]: crate::CompilationMode::Lazy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompilationMetrics {
+    /// The index of the first internal function within its Wasm module.
+    first_func: u32,
+    /// The number of internal functions.
+    len_funcs: u32,
+    /// The metrics of all compiled internal functions ordered by function index.
+    funcs: Box<[FuncCompilationMetrics]>,
+}
+
+impl CompilationMetrics {
+    /// Creates new [`CompilationMetrics`] from the metrics of the compiled `funcs`.
+    pub(crate) fn new(
+        first_func: u32,
+        len_funcs: u32,
+        funcs: Box<[FuncCompilationMetrics]>,
+    ) -> Self {
+        Self {
+            first_func,
+            len_funcs,
+            funcs,
+        }
+    }
+
+    /// Returns the index of the first internal function within its Wasm module.
+    ///
+    /// This is the number of imported functions.
+    pub fn first_func(&self) -> u32 {
+        self.first_func
+    }
+
+    /// Returns the number of internal functions including those not yet compiled.
+    pub fn len_funcs(&self) -> u32 {
+        self.len_funcs
+    }
+
+    /// Returns the number of compiled internal functions.
+    pub fn len_compiled_funcs(&self) -> u32 {
+        self.funcs.len() as u32
+    }
+
+    /// Returns the [`FuncCompilationMetrics`] of all compiled internal functions ordered by index.
+    pub fn funcs(&self) -> &[FuncCompilationMetrics] {
+        &self.funcs
+    }
+
+    /// Returns the [`FuncCompilationMetrics`] of the function at `index` if it has been compiled.
+    pub fn get(&self, index: u32) -> Option<&FuncCompilationMetrics> {
+        let position = self
+            .funcs
+            .binary_search_by_key(&index, |func| func.index)
+            .ok()?;
+        Some(&self.funcs[position])
+    }
+
+    /// Returns the total time spent on validating and translating all compiled functions.
+    ///
+    /// Returns `None` if the time has not been measured, e.g. without the `std` crate feature.
+    pub fn translation_time(&self) -> Option<Duration> {
+        self.funcs
+            .iter()
+            .map(FuncCompilationMetrics::translation_time)
+            .try_fold(Duration::ZERO, |total, time| Some(total + time?))
+    }
+
+    /// Returns the total number of Wasmi bytecode instructions of all compiled functions.
+    pub fn len_instrs(&self) -> u64 {
+        self.sum(|func| u64::from(func.len_instrs))
+    }
+
+    /// Returns the total number of const-pool slots of all compiled functions.
+    pub fn len_consts(&self) -> u64 {
+        self.sum(|func| u64::from(func.len_consts))
+    }
+
+    /// Returns the total number of registers used by all compiled functions.
+    pub fn len_registers(&self) -> u64 {
+        self.sum(|func| u64::from(func.len_registers))
+    }
+
+    /// Returns the maximum number of registers used by any compiled function.
+    pub fn max_registers(&self) -> u16 {
+        self.funcs
+            .iter()
+            .map(|func| func.len_registers)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the total number of basic blocks of all compiled functions that consume fuel.
+    pub fn len_fuel_blocks(&self) -> u64 {
+        self.sum(|func| u64::from(func.len_fuel_blocks))
+    }
+
+    /// Returns the sum of `f` applied to all [`FuncCompilationMetrics`].
+    fn sum(&self, f: impl Fn(&FuncCompilationMetrics) -> u64) -> u64 {
+        self.funcs.iter().map(f).sum()
+    }
+}
@@ -1,12 +1,22 @@
 //! The Wasmi interpreter.
 
+mod baseline;
 mod block_type;
+#[cfg(feature = "branch-profile")]
+mod branch_profile;
 mod code_map;
 mod config;
+mod coverage;
+mod disasm;
 mod executor;
 mod func_types;
 mod limits;
+mod memory_usage;
+mod metrics;
+mod precompile;
 mod resumable;
+mod serialize;
+mod trace;
 mod traits;
 mod translator;
 mod utils;
@@ -14,11 +24,18 @@ mod utils;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "branch-profile")]
+pub(crate) use self::branch_profile::{BranchCounter, BranchProfileBuilder, BranchSite};
+#[cfg(feature = "branch-profile")]
+pub use self::branch_profile::{BranchCounts, BranchProfile};
 pub(crate) use self::{
     block_type::BlockType,
     config::FuelCosts,
+    coverage::{CoverageBuilder, CoverageProbe, CoverageRecorder},
     executor::Stack,
     func_types::DedupFuncType,
+    serialize::SerializedCode,
+    trace::ExecutionTrace,
     translator::{
         FuncTranslationDriver,
         FuncTranslator,
@@ -35,17 +52,25 @@ use self::{
 };
 pub use self::{
     code_map::{EngineFunc, EngineFuncSpan, EngineFuncSpanIter},
-    config::{CompilationMode, Config},
+    config::{CompilationMode, Config, FuelSchedule, IndexWidth},
+    coverage::{BlockLocation, Coverage},
+    disasm::FuncDisassembly,
     executor::ResumableHostError,
     limits::{EnforcedLimits, EnforcedLimitsError, StackLimits},
+    memory_usage::EngineMemoryUsage,
+    metrics::{CompilationMetrics, FuncCompilationMetrics},
+    precompile::PrecompileProgress,
     resumable::{ResumableCall, ResumableInvocation, TypedResumableCall, TypedResumableInvocation},
+    serialize::SerializedCodeError,
+    trace::TraceHash,
     traits::{CallParams, CallResults},
     translator::{Instr, TranslationError},
 };
 use crate::{
     collections::arena::{ArenaIndex, GuardedEntity},
     func::FuncInOut,
-    module::{FuncIdx, ModuleHeader},
+    ir::index::InternalFunc,
+    module::{FuncIdx, ModuleHash, ModuleHeader},
     Error,
     Func,
     FuncType,
@@ -55,7 +80,10 @@ use alloc::{
     sync::{Arc, Weak},
     vec::Vec,
 };
-use core::sync::atomic::{AtomicU32, Ordering};
+use core::{
+    iter::Peekable,
+    sync::atomic::{AtomicU32, Ordering},
+};
 use spin::{Mutex, RwLock};
 use wasmparser::{FuncToValidate, FuncValidatorAllocations, ValidatorResources};
 
@@ -154,6 +182,16 @@ impl Engine {
         self.inner.config()
     }
 
+    /// Returns the heap memory currently held by the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// This allows long-running hosts to monitor the memory growth of the [`Engine`],
+    /// for example caused by compiling many Wasm modules over time.
+    pub fn memory_usage(&self) -> EngineMemoryUsage {
+        self.inner.memory_usage()
+    }
+
     /// Returns `true` if both [`Engine`] references `a` and `b` refer to the same [`Engine`].
     pub fn same(a: &Engine, b: &Engine) -> bool {
         Arc::ptr_eq(&a.inner, &b.inner)
@@ -221,6 +259,156 @@ impl Engine {
         )
     }
 
+    /// Disassembles the Wasmi bytecode of `func` at `func_idx` of its Wasm module.
+    ///
+    /// The `name` is the name under which `func` is exported if any.
+    ///
+    /// # Note
+    ///
+    /// This compiles `func` without charging fuel if it has not yet been compiled.
+    ///
+    /// # Errors
+    ///
+    /// If `func` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    pub(crate) fn disassemble_func(
+        &self,
+        func_idx: FuncIdx,
+        name: Option<&str>,
+        func: EngineFunc,
+    ) -> Result<FuncDisassembly, Error> {
+        let func = self.inner.code_map.get(None, func)?;
+        Ok(FuncDisassembly::new(func_idx.into_u32(), name, func))
+    }
+
+    /// Serializes the compiled Wasmi bytecode of the contiguous `funcs` of the Wasm module with `hash`.
+    ///
+    /// # Note
+    ///
+    /// This compiles all `funcs` without charging fuel if they have not yet been compiled.
+    ///
+    /// # Errors
+    ///
+    /// If any of the `funcs` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    pub(crate) fn serialize_funcs(
+        &self,
+        hash: ModuleHash,
+        funcs: impl ExactSizeIterator<Item = EngineFunc>,
+    ) -> Result<Vec<u8>, Error> {
+        let mut funcs = funcs.peekable();
+        let base = Self::base_func(&mut funcs);
+        let funcs = funcs
+            .map(|func| self.inner.code_map.get(None, func))
+            .collect::<Result<Vec<_>, Error>>()?;
+        serialize::serialize_funcs(hash, base, funcs.into_iter())
+    }
+
+    /// Returns the stable hash of the compiled Wasmi bytecode of the contiguous `funcs`.
+    ///
+    /// # Note
+    ///
+    /// This compiles all `funcs` without charging fuel if they have not yet been compiled.
+    ///
+    /// # Errors
+    ///
+    /// If any of the `funcs` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    pub(crate) fn code_hash(
+        &self,
+        funcs: impl ExactSizeIterator<Item = EngineFunc>,
+    ) -> Result<ModuleHash, Error> {
+        let mut funcs = funcs.peekable();
+        let base = Self::base_func(&mut funcs);
+        let funcs = funcs
+            .map(|func| self.inner.code_map.get(None, func))
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(serialize::hash_funcs(base, funcs.into_iter()))
+    }
+
+    /// Returns the first of the contiguous `funcs` as [`InternalFunc`] or zero if `funcs` is empty.
+    fn base_func(funcs: &mut Peekable<impl Iterator<Item = EngineFunc>>) -> InternalFunc {
+        funcs
+            .peek()
+            .map_or(InternalFunc::from(0), |func| InternalFunc::from(*func))
+    }
+
+    /// Initializes the uninitialized `funcs` with the compiled Wasmi bytecode decoded from `code`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `code` has been serialized for the Wasm module of `funcs`
+    /// by an [`Engine`] with the same configuration using the same build of Wasmi.
+    ///
+    /// # Errors
+    ///
+    /// If `code` is malformed or does not contain exactly one function per `funcs`.
+    pub(crate) unsafe fn init_serialized_funcs(
+        &self,
+        mut code: SerializedCode<'_>,
+        funcs: impl ExactSizeIterator<Item = EngineFunc>,
+    ) -> Result<(), Error> {
+        if code.len_funcs() as usize != funcs.len() {
+            return Err(Error::from(SerializedCodeError::ModuleMismatch));
+        }
+        let mut funcs = funcs.peekable();
+        let base = Self::base_func(&mut funcs);
+        for func in funcs {
+            let entity = unsafe { code.next_func(base)? };
+            self.inner.code_map.init_func_as_compiled(func, entity);
+        }
+        Ok(())
+    }
+
+    /// Returns the [`FuncCompilationMetrics`] of `func` at `func_idx` of its Wasm module.
+    ///
+    /// Returns `None` if `func` has not yet been compiled.
+    pub(crate) fn func_compilation_metrics(
+        &self,
+        func_idx: FuncIdx,
+        func: EngineFunc,
+    ) -> Option<FuncCompilationMetrics> {
+        self.inner.code_map.get_metrics(func_idx.into_u32(), func)
+    }
+
+    /// Pushes the code coverage of the compiled `func` at `func_idx` to `builder`.
+    ///
+    /// # Note
+    ///
+    /// This compiles `func` without charging fuel if it has not yet been compiled.
+    ///
+    /// # Errors
+    ///
+    /// If `func` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    pub(crate) fn cover_func(
+        &self,
+        builder: &mut CoverageBuilder,
+        func_idx: FuncIdx,
+        func: EngineFunc,
+    ) -> Result<(), Error> {
+        let func = self.inner.code_map.get(None, func)?;
+        builder.push_func(func_idx.into_u32(), func);
+        Ok(())
+    }
+
+    /// Pushes the branch profile of the compiled `func` at `func_idx` to `builder`.
+    ///
+    /// # Note
+    ///
+    /// This compiles `func` without charging fuel if it has not yet been compiled.
+    ///
+    /// # Errors
+    ///
+    /// If `func` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    #[cfg(feature = "branch-profile")]
+    pub(crate) fn profile_func(
+        &self,
+        builder: &mut BranchProfileBuilder,
+        func_idx: FuncIdx,
+        func: EngineFunc,
+    ) -> Result<(), Error> {
+        let func = self.inner.code_map.get(None, func)?;
+        builder.push_func(func_idx.into_u32(), func);
+        Ok(())
+    }
+
     /// Returns reusable [`FuncTranslatorAllocations`] from the [`Engine`].
     pub(crate) fn get_translation_allocs(&self) -> FuncTranslatorAllocations {
         self.inner.get_translation_allocs()
@@ -252,6 +440,8 @@ impl Engine {
     /// The initialized function will not be compiled after this call and instead
     /// be prepared to be compiled on the fly when it is called the first time.
     ///
+    /// The `offset` is the position of the function body `bytes` within the Wasm binary.
+    ///
     /// # Panics
     ///
     /// - If `func` is an invalid [`EngineFunc`] reference for this [`CodeMap`].
@@ -260,12 +450,13 @@ impl Engine {
         &self,
         func_idx: FuncIdx,
         func: EngineFunc,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) {
         self.inner
-            .init_lazy_func(func_idx, func, bytes, module, func_to_validate)
+            .init_lazy_func(func_idx, func, offset, bytes, module, func_to_validate)
     }
 
     /// Executes the given [`Func`] with parameters `params`.
@@ -477,15 +668,29 @@ pub struct EngineStacks {
     limits: StackLimits,
     /// How many stacks should be kept for reuse at most.
     keep: usize,
+    /// Is `true` if stacks are allocated up front and never shrunk.
+    preallocate: bool,
 }
 
 impl EngineStacks {
     /// Creates new [`EngineStacks`] with the given [`StackLimits`].
+    ///
+    /// Allocates the first [`Stack`] up front if [`Config::static_allocation`] is enabled.
     pub fn new(config: &Config) -> Self {
+        let limits = config.stack_limits();
+        let preallocate = config.get_static_allocation();
+        let mut stacks = Vec::new();
+        let mut keep = config.cached_stacks();
+        if preallocate {
+            keep = keep.max(1);
+            stacks.reserve_exact(keep);
+            stacks.push(Stack::preallocated(limits));
+        }
         Self {
-            stacks: Vec::new(),
-            limits: config.stack_limits(),
-            keep: config.cached_stacks(),
+            stacks,
+            limits,
+            keep,
+            preallocate,
         }
     }
 
@@ -493,13 +698,24 @@ impl EngineStacks {
     pub fn reuse_or_new(&mut self) -> Stack {
         match self.stacks.pop() {
             Some(stack) => stack,
+            None if self.preallocate => Stack::preallocated(self.limits),
             None => Stack::new(self.limits),
         }
     }
 
+    /// Returns the number of bytes held by the [`Stack`]s kept for reuse.
+    pub fn memory_usage(&self) -> usize {
+        self.stacks.iter().map(Stack::memory_usage).sum()
+    }
+
     /// Disose and recycle the `stack`.
-    pub fn recycle(&mut self, stack: Stack) {
+    ///
+    /// Recycled stacks are shrunk so that deep recursions do not pin their memory.
+    pub fn recycle(&mut self, mut stack: Stack) {
         if stack.capacity() > 0 && self.stacks.len() < self.keep {
+            if !self.preallocate {
+                stack.shrink(self.limits);
+            }
             self.stacks.push(stack);
         }
     }
@@ -559,8 +775,33 @@ impl EngineInner {
         bytes: &[u8],
         module: ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
+    ) -> Result<(), Error> {
+        self.translate_func_impl(
+            func_index,
+            engine_func,
+            offset,
+            bytes,
+            module,
+            func_to_validate,
+        )
+        .map_err(|error| error.with_func_index(func_index.into_u32()))
+    }
+
+    /// Implementation of [`EngineInner::translate_func`] without additional error context.
+    fn translate_func_impl(
+        &self,
+        func_index: FuncIdx,
+        engine_func: EngineFunc,
+        offset: usize,
+        bytes: &[u8],
+        module: ModuleHeader,
+        func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) -> Result<(), Error> {
         let features = self.config().wasm_features();
+        if self.config.get_per_call_fuel_schedules() {
+            self.code_map
+                .init_func_source(engine_func, func_index, bytes, &module);
+        }
         match (self.config.get_compilation_mode(), func_to_validate) {
             (CompilationMode::Eager, Some(func_to_validate)) => {
                 let (translation_allocs, validation_allocs) = self.get_allocs();
@@ -677,6 +918,8 @@ impl EngineInner {
     /// The initialized function will not be compiled after this call and instead
     /// be prepared to be compiled on the fly when it is called the first time.
     ///
+    /// The `offset` is the position of the function body `bytes` within the Wasm binary.
+    ///
     /// # Panics
     ///
     /// - If `func` is an invalid [`EngineFunc`] reference for this [`CodeMap`].
@@ -685,12 +928,19 @@ impl EngineInner {
         &self,
         func_idx: FuncIdx,
         func: EngineFunc,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) {
-        self.code_map
-            .init_func_as_uncompiled(func, func_idx, bytes, module, func_to_validate)
+        self.code_map.init_func_as_uncompiled(
+            func,
+            func_idx,
+            offset,
+            bytes,
+            module,
+            func_to_validate,
+        )
     }
 
     /// Recycles the given [`Stack`].
@@ -0,0 +1,162 @@
+use crate::{Engine, Error, Module};
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use core::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+#[cfg(feature = "std")]
+use std::{sync::mpsc, thread};
+
+/// The progress of [`Engine::precompile_modules`] reported after each compiled Wasm module.
+///
+/// [`Engine::precompile_modules`]: crate::Engine::precompile_modules
+#[derive(Debug)]
+pub struct PrecompileProgress {
+    /// The index of the Wasm module in the input sequence.
+    index: usize,
+    /// The number of Wasm modules compiled so far, including this one.
+    completed: usize,
+    /// The total number of Wasm modules to compile.
+    total: usize,
+    /// The compiled [`Module`] or the error that occurred during its compilation.
+    result: Result<Module, Error>,
+}
+
+impl PrecompileProgress {
+    /// Returns the index of the Wasm module in the input sequence.
+    ///
+    /// # Note
+    ///
+    /// Wasm modules compiled in parallel are reported in order of completion,
+    /// thus the index is required to relate the progress to its input.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the number of Wasm modules compiled so far, including this one.
+    pub fn completed(&self) -> usize {
+        self.completed
+    }
+
+    /// Returns the total number of Wasm modules to compile.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns a shared reference to the compilation result of the Wasm module.
+    pub fn result(&self) -> &Result<Module, Error> {
+        &self.result
+    }
+
+    /// Consumes `self` and returns the compiled [`Module`].
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm module failed to compile.
+    pub fn into_result(self) -> Result<Module, Error> {
+        self.result
+    }
+}
+
+impl Engine {
+    /// Compiles all `wasms` into [`Module`]s of the [`Engine`] one after another.
+    ///
+    /// Calls `on_progress` with the [`PrecompileProgress`] after each compiled Wasm module
+    /// in order of the `wasms`. Compilation continues for the remaining Wasm modules if
+    /// a Wasm module fails to compile.
+    ///
+    /// # Note
+    ///
+    /// This is useful for hosts that warm up the [`Engine`] with many Wasm modules at startup.
+    /// Use [`Engine::precompile_modules_parallel`] to compile on multiple threads.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmi::Engine;
+    /// let engine = Engine::default();
+    /// let wasms = ["(module)", "(module (func (export \"f\")))", "(module"];
+    /// let mut modules = Vec::new();
+    /// engine.precompile_modules(wasms, |progress| {
+    ///     assert_eq!(progress.total(), 3);
+    ///     modules.push(progress.into_result());
+    /// });
+    /// assert!(modules[0].is_ok());
+    /// assert!(modules[1].is_ok());
+    /// assert!(modules[2].is_err());
+    /// ```
+    pub fn precompile_modules<I, F>(&self, wasms: I, mut on_progress: F)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+        F: FnMut(PrecompileProgress),
+    {
+        let wasms: Vec<I::Item> = wasms.into_iter().collect();
+        let total = wasms.len();
+        for (index, wasm) in wasms.iter().enumerate() {
+            on_progress(PrecompileProgress {
+                index,
+                completed: index + 1,
+                total,
+                result: Module::new(self, wasm),
+            });
+        }
+    }
+
+    /// Compiles all `wasms` into [`Module`]s of the [`Engine`] on up to `threads` threads.
+    ///
+    /// Calls `on_progress` on the calling thread with the [`PrecompileProgress`]
+    /// after each compiled Wasm module in order of completion.
+    /// Compilation continues for the remaining Wasm modules if a Wasm module fails to compile.
+    ///
+    /// # Note
+    ///
+    /// Returns after all Wasm modules have been compiled.
+    #[cfg(feature = "std")]
+    pub fn precompile_modules_parallel<I, F>(
+        &self,
+        wasms: I,
+        threads: NonZeroUsize,
+        mut on_progress: F,
+    ) where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]> + Sync,
+        F: FnMut(PrecompileProgress),
+    {
+        let wasms: Vec<I::Item> = wasms.into_iter().collect();
+        let total = wasms.len();
+        let threads = threads.get().min(total);
+        if threads <= 1 {
+            return self.precompile_modules(wasms, on_progress);
+        }
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                let sender = sender.clone();
+                let (wasms, next) = (&wasms, &next);
+                scope.spawn(move || loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(wasm) = wasms.get(index) else {
+                        return;
+                    };
+                    let result = Module::new(self, wasm);
+                    if sender.send((index, result)).is_err() {
+                        return;
+                    }
+                });
+            }
+            drop(sender);
+            for (completed, (index, result)) in receiver.iter().enumerate() {
+                on_progress(PrecompileProgress {
+                    index,
+                    completed: completed + 1,
+                    total,
+                    result,
+                });
+            }
+        });
+    }
+}
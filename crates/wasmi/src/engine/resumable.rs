@@ -1,11 +1,14 @@
 use super::Func;
 use crate::{
+    core::TrapCode,
     engine::Stack,
     func::CallResultsTuple,
     ir::RegSpan,
+    AsContext,
     AsContextMut,
     Engine,
     Error,
+    ExternRef,
     Val,
     WasmResults,
 };
@@ -183,6 +186,35 @@ impl ResumableInvocation {
         &self.host_error
     }
 
+    /// Consumes `self` and returns the host error that caused the function invocation to break.
+    pub(crate) fn into_host_error(mut self) -> Error {
+        // Note: `self` is dropped right after so the placeholder is never observed.
+        replace(
+            &mut self.host_error,
+            Error::from(TrapCode::UnreachableCodeReached),
+        )
+    }
+
+    /// Calls `f` for every non-null [`ExternRef`] held by the registers of the suspended call.
+    ///
+    /// # Note
+    ///
+    /// - Registers are untyped and thus scanned conservatively: every register value that
+    ///   refers to an [`ExternRef`] of the [`Store`] is reported even if it is not used as such.
+    /// - The same [`ExternRef`] may be reported multiple times.
+    /// - Use [`Store::trace_externrefs`] to enumerate the remaining [`ExternRef`]s of the [`Store`].
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::trace_externrefs`]: crate::Store::trace_externrefs
+    pub fn trace_externrefs(&self, ctx: impl AsContext, mut f: impl FnMut(ExternRef)) {
+        let store = &ctx.as_context().store.inner;
+        for value in self.stack.values.as_slice() {
+            if let Some(externref) = store.as_externref(*value) {
+                f(externref)
+            }
+        }
+    }
+
     /// Returns the caller results [`RegSpan`].
     ///
     /// # Note
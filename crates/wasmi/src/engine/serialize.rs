@@ -0,0 +1,338 @@
+//! Serialization of compiled Wasmi bytecode.
+//!
+//! # Format
+//!
+//! Serialized code starts with a header of 32 bytes:
+//!
+//! - The [`MAGIC`] bytes.
+//! - The format version and the [`flags`] of the Wasmi build as one byte each.
+//! - Two reserved zero bytes.
+//! - The number of serialized functions as `u32`.
+//! - The [`ModuleHash`] of the serialized Wasm module.
+//!
+//! The header is followed by one record per internal function of the Wasm module:
+//!
+//! - The number of registers as `u16` followed by two reserved zero bytes.
+//! - The number of function local constant values as `u32`.
+//! - The number of encoded instruction bytes as `u32` followed by four reserved zero bytes.
+//! - The function local constant values as `u128` each.
+//! - The instruction words encoded via [`codec`].
+//!
+//! Called internal functions of instruction words are encoded relative to the first
+//! internal function of the Wasm module since their indices depend on the functions
+//! compiled by the [`Engine`](crate::Engine) before the Wasm module.
+//!
+//! All integers are encoded in little-endian byte order.
+
+use super::code_map::{CompiledFuncEntity, CompiledFuncRef};
+use crate::{
+    core::UntypedVal,
+    ir::{codec, index::InternalFunc, Instruction},
+    module::{ModuleHash, StableHasher},
+    Error,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    fmt::{self, Display},
+    mem,
+    slice,
+};
+
+#[cfg(feature = "simd")]
+use crate::core::V128;
+
+/// The magic bytes at the start of serialized code.
+const MAGIC: [u8; 8] = *b"\0wasmibc";
+
+/// The version of the serialized code format.
+const VERSION: u8 = 2;
+
+/// The size of the header of serialized code in bytes.
+const HEADER_SIZE: usize = 32;
+
+/// The size of the header of a serialized function in bytes.
+const FUNC_HEADER_SIZE: usize = 16;
+
+/// The size of a serialized function local constant value in bytes.
+const CONST_SIZE: usize = mem::size_of::<u128>();
+
+/// Returns the flags of the Wasmi build that affect the encoding of instruction words.
+fn flags() -> u8 {
+    u8::from(!cfg!(feature = "no-float"))
+        | u8::from(!cfg!(feature = "no-bulk-memory")) << 1
+        | u8::from(cfg!(feature = "simd")) << 2
+}
+
+/// An error that may occur upon loading serialized Wasmi bytecode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SerializedCodeError {
+    /// The serialized code is malformed.
+    Malformed,
+    /// The serialized code has been produced by an incompatible build of Wasmi.
+    Incompatible,
+    /// The serialized code does not belong to the Wasm module or the engine configuration.
+    ModuleMismatch,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SerializedCodeError {}
+
+impl Display for SerializedCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::Malformed => "malformed serialized code",
+            Self::Incompatible => {
+                "serialized code has been produced by an incompatible Wasmi build"
+            }
+            Self::ModuleMismatch => {
+                "serialized code does not belong to the Wasm module or engine configuration"
+            }
+        };
+        f.write_str(message)
+    }
+}
+
+/// Returns an exclusive reference to the called internal function of `instr` if any.
+fn internal_callee(instr: &mut Instruction) -> Option<&mut InternalFunc> {
+    match instr {
+        Instruction::CallInternal0 { func, .. }
+        | Instruction::CallInternal { func, .. }
+        | Instruction::ReturnCallInternal0 { func, .. }
+        | Instruction::ReturnCallInternal { func, .. } => Some(func),
+        _ => None,
+    }
+}
+
+/// Returns `instr` with its called internal function rebased by `rebase` if any.
+///
+/// Returns `None` if `rebase` returns `None`.
+fn rebase_callee(
+    mut instr: Instruction,
+    rebase: impl FnOnce(u32) -> Option<u32>,
+) -> Option<Instruction> {
+    if let Some(func) = internal_callee(&mut instr) {
+        *func = InternalFunc::from(rebase(u32::from(*func))?);
+    }
+    Some(instr)
+}
+
+/// Serializes the compiled `funcs` of the Wasm module with `hash` starting at `base`.
+pub fn serialize_funcs<'a>(
+    hash: ModuleHash,
+    base: InternalFunc,
+    funcs: impl ExactSizeIterator<Item = CompiledFuncRef<'a>>,
+) -> Result<Vec<u8>, Error> {
+    let Ok(len_funcs) = u32::try_from(funcs.len()) else {
+        return Err(Error::from(SerializedCodeError::Malformed));
+    };
+    let mut buffer = Vec::new();
+    let mut instrs = Vec::new();
+    buffer.extend_from_slice(&MAGIC);
+    buffer.extend_from_slice(&[VERSION, flags(), 0, 0]);
+    buffer.extend_from_slice(&len_funcs.to_le_bytes());
+    buffer.extend_from_slice(&hash.to_bytes());
+    for func in funcs {
+        let consts = func.consts();
+        instrs.clear();
+        encode_instrs(base, func.instrs(), &mut instrs);
+        let Ok(len_instrs) = u32::try_from(instrs.len()) else {
+            return Err(Error::from(SerializedCodeError::Malformed));
+        };
+        buffer.try_reserve(FUNC_HEADER_SIZE + consts.len() * CONST_SIZE + instrs.len())?;
+        buffer.extend_from_slice(&func.len_registers().to_le_bytes());
+        buffer.extend_from_slice(&[0, 0]);
+        buffer.extend_from_slice(&(consts.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(&len_instrs.to_le_bytes());
+        buffer.extend_from_slice(&[0, 0, 0, 0]);
+        for value in consts {
+            buffer.extend_from_slice(&const_to_bits(*value).to_le_bytes());
+        }
+        buffer.extend_from_slice(&instrs);
+    }
+    Ok(buffer)
+}
+
+/// Encodes the `instrs` of a function of the Wasm module starting at `base` into `buffer`.
+///
+/// Called internal functions are encoded relative to `base`.
+fn encode_instrs(base: InternalFunc, instrs: &[Instruction], buffer: &mut Vec<u8>) {
+    let base = u32::from(base);
+    for instr in instrs {
+        let mut instr = *instr;
+        if let Some(func) = internal_callee(&mut instr) {
+            *func = InternalFunc::from(u32::from(*func).wrapping_sub(base));
+        }
+        codec::encode(slice::from_ref(&instr), buffer);
+    }
+}
+
+/// Returns the stable hash of the compiled `funcs` of a Wasm module starting at `base`.
+///
+/// This hashes the same encoding of the instruction words as [`serialize_funcs`] and thus
+/// does not depend on the target platform. Called internal functions are hashed relative
+/// to `base` since their indices depend on the functions compiled by the
+/// [`Engine`](crate::Engine) before the Wasm module.
+pub fn hash_funcs<'a>(
+    base: InternalFunc,
+    funcs: impl ExactSizeIterator<Item = CompiledFuncRef<'a>>,
+) -> ModuleHash {
+    let mut hasher = StableHasher::new();
+    let mut buffer = Vec::new();
+    hasher.write_u64(funcs.len() as u64);
+    for func in funcs {
+        let consts = func.consts();
+        hasher.write_u32(u32::from(func.len_registers()));
+        hasher.write_u64(consts.len() as u64);
+        for value in consts {
+            let bits = const_to_bits(*value);
+            hasher.write_u64(bits as u64);
+            hasher.write_u64((bits >> 64) as u64);
+        }
+        buffer.clear();
+        encode_instrs(base, func.instrs(), &mut buffer);
+        hasher.write_bytes(&buffer);
+    }
+    hasher.finish()
+}
+
+/// Returns the bits of the function local constant `value`.
+fn const_to_bits(value: UntypedVal) -> u128 {
+    #[cfg(feature = "simd")]
+    return V128::from(value).as_u128();
+    #[cfg(not(feature = "simd"))]
+    return u128::from(value.to_bits64());
+}
+
+/// Returns the function local constant value from its `bits`.
+fn const_from_bits(bits: u128) -> UntypedVal {
+    #[cfg(feature = "simd")]
+    return UntypedVal::from(V128::from(bits));
+    #[cfg(not(feature = "simd"))]
+    return UntypedVal::from_bits64(bits as u64);
+}
+
+/// Serialized Wasmi bytecode of the internal functions of a Wasm module.
+#[derive(Debug)]
+pub struct SerializedCode<'a> {
+    /// The [`ModuleHash`] bytes of the serialized Wasm module.
+    hash: [u8; 16],
+    /// The number of serialized functions.
+    len_funcs: u32,
+    /// The serialized functions that have not yet been loaded.
+    funcs: &'a [u8],
+}
+
+impl<'a> SerializedCode<'a> {
+    /// Creates a new [`SerializedCode`] from `bytes` after checking its header.
+    ///
+    /// # Errors
+    ///
+    /// - If `bytes` do not start with a valid header.
+    /// - If `bytes` have been produced by an incompatible build of Wasmi.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, SerializedCodeError> {
+        let Some((header, funcs)) = bytes.split_first_chunk::<HEADER_SIZE>() else {
+            return Err(SerializedCodeError::Malformed);
+        };
+        if header[..8] != MAGIC {
+            return Err(SerializedCodeError::Malformed);
+        }
+        if header[8] != VERSION || header[9] != flags() {
+            return Err(SerializedCodeError::Incompatible);
+        }
+        let len_funcs = u32::from_le_bytes(read_array(header, 12));
+        let hash = read_array(header, 16);
+        Ok(Self {
+            hash,
+            len_funcs,
+            funcs,
+        })
+    }
+
+    /// Returns the [`ModuleHash`] bytes of the serialized Wasm module.
+    pub fn hash(&self) -> [u8; 16] {
+        self.hash
+    }
+
+    /// Returns the number of serialized functions.
+    pub fn len_funcs(&self) -> u32 {
+        self.len_funcs
+    }
+
+    /// Loads the next serialized function of the Wasm module starting at `base`.
+    ///
+    /// # Note
+    ///
+    /// Decoding validates the opcodes and fields of all instruction words as well as
+    /// the called internal functions. It does not validate whether the instruction words
+    /// form valid Wasmi bytecode, e.g. whether their registers and branch offsets are in bounds.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the serialized code has been produced by [`serialize_funcs`]
+    /// for the same Wasm module and engine configuration using the same build of Wasmi.
+    ///
+    /// # Errors
+    ///
+    /// - If the serialized code is malformed.
+    /// - If an instruction word fails to decode.
+    /// - If a called internal function is out of bounds for the serialized Wasm module.
+    pub unsafe fn next_func(
+        &mut self,
+        base: InternalFunc,
+    ) -> Result<CompiledFuncEntity, SerializedCodeError> {
+        let Some((header, rest)) = self.funcs.split_first_chunk::<FUNC_HEADER_SIZE>() else {
+            return Err(SerializedCodeError::Malformed);
+        };
+        let len_registers = u16::from_le_bytes(read_array(header, 0));
+        let len_consts = u32::from_le_bytes(read_array(header, 4)) as usize;
+        let len_instrs = u32::from_le_bytes(read_array(header, 8)) as usize;
+        let size_consts = len_consts
+            .checked_mul(CONST_SIZE)
+            .ok_or(SerializedCodeError::Malformed)?;
+        let Some((consts, rest)) = rest.split_at_checked(size_consts) else {
+            return Err(SerializedCodeError::Malformed);
+        };
+        let Some((instrs, rest)) = rest.split_at_checked(len_instrs) else {
+            return Err(SerializedCodeError::Malformed);
+        };
+        let consts = consts
+            .chunks_exact(CONST_SIZE)
+            .map(|bits| const_from_bits(u128::from_le_bytes(read_array(bits, 0))));
+        let instrs = codec::decode(instrs).map_err(|_| SerializedCodeError::Malformed)?;
+        if instrs.is_empty() || instrs.len() > i32::MAX as usize {
+            return Err(SerializedCodeError::Malformed);
+        }
+        let base = u32::from(base);
+        let len_funcs = self.len_funcs;
+        let rebase = |func: u32| -> Option<u32> {
+            if func >= len_funcs {
+                return None;
+            }
+            func.checked_add(base)
+        };
+        let instrs = instrs
+            .into_iter()
+            .map(|instr| rebase_callee(instr, rebase))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(SerializedCodeError::Malformed)?;
+        self.funcs = rest;
+        Ok(CompiledFuncEntity::new(
+            len_registers,
+            instrs,
+            consts,
+            Box::default(),
+        ))
+    }
+}
+
+/// Reads an array of `N` bytes from `bytes` at `offset`.
+///
+/// # Panics
+///
+/// If `bytes` is too short.
+fn read_array<const N: usize>(bytes: &[u8], offset: usize) -> [u8; N] {
+    let mut array = [0x00_u8; N];
+    array.copy_from_slice(&bytes[offset..][..N]);
+    array
+}
@@ -36,3 +36,98 @@ fn many_results() {
         assert!(result as usize == i % 10);
     }
 }
+
+#[test]
+fn call_span_params() {
+    // Note: the parameters of all calls are in contiguous registers and thus
+    //       encoded as a single `Instruction::RegisterSpan` parameter.
+    let wasm = r#"
+        (module
+            (import "env" "host" (func $host (param i32 i32 i32 i32 i32) (result i32)))
+            (func $wasm (param i32 i32 i32 i32 i32) (result i32)
+                (call $host
+                    (local.get 0) (local.get 1) (local.get 2) (local.get 3) (local.get 4)
+                )
+            )
+            (func $tail (param i32 i32 i32 i32 i32) (result i32)
+                (return_call $wasm
+                    (i32.add (local.get 0) (i32.const 1))
+                    (i32.add (local.get 1) (i32.const 1))
+                    (i32.add (local.get 2) (i32.const 1))
+                    (i32.add (local.get 3) (i32.const 1))
+                    (i32.add (local.get 4) (i32.const 1))
+                )
+            )
+            (func (export "test") (param i32 i32 i32 i32 i32) (result i32)
+                (call $tail
+                    (local.get 0) (local.get 1) (local.get 2) (local.get 3) (local.get 4)
+                )
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let mut store = <Store<()>>::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "host", |a: i32, b: i32, c: i32, d: i32, e: i32| {
+            a + 10 * b + 100 * c + 1_000 * d + 10_000 * e
+        })
+        .unwrap();
+    let module = Module::new(&engine, wasm).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .ensure_no_start(&mut store)
+        .unwrap();
+    let func = instance
+        .get_typed_func::<(i32, i32, i32, i32, i32), i32>(&store, "test")
+        .unwrap();
+    assert_eq!(func.call(&mut store, (0, 1, 2, 3, 4)).unwrap(), 54_321);
+}
+
+#[test]
+fn call_results_to_branch_params() {
+    // Note: the results of all calls are written directly into the branch parameters of the blocks.
+    let wasm = r#"
+        (module
+            (import "env" "host" (func $host (param i32) (result i32 i32 i32)))
+            (func $wasm (param i32) (result i32 i32 i32)
+                (local.get 0)
+                (i32.add (local.get 0) (i32.const 1))
+                (i32.add (local.get 0) (i32.const 2))
+            )
+            (func (export "test") (param i32) (result i32 i32 i32 i32)
+                (i32.const 100)
+                (if (result i32 i32 i32) (local.get 0)
+                    (then
+                        (i32.add (local.get 0) (i32.const 10))
+                        (call $wasm (local.get 0))
+                        (br 0)
+                    )
+                    (else
+                        (i32.add (local.get 0) (i32.const 20))
+                        (call $host (local.get 0))
+                        (br 0)
+                    )
+                )
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let mut store = <Store<()>>::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "host", |a: i32| (a + 3, a + 4, a + 5))
+        .unwrap();
+    let module = Module::new(&engine, wasm).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .ensure_no_start(&mut store)
+        .unwrap();
+    let func = instance
+        .get_typed_func::<i32, (i32, i32, i32, i32)>(&store, "test")
+        .unwrap();
+    assert_eq!(func.call(&mut store, 1).unwrap(), (100, 1, 2, 3));
+    assert_eq!(func.call(&mut store, 0).unwrap(), (100, 3, 4, 5));
+}
@@ -0,0 +1,172 @@
+use crate::{core::UntypedVal, ir::Instruction, module::StableHasher};
+use core::{fmt, fmt::Display};
+
+#[cfg(feature = "simd")]
+use crate::core::V128;
+
+/// A stable 128-bit digest of the execution trace of a [`Store`].
+///
+/// Computed via [`Store::trace_hash`].
+///
+/// # Note
+///
+/// - Two executions with equal inputs on the same Wasmi version and [`Config`]
+///   produce the same [`TraceHash`] independent of platform.
+/// - The hash is not cryptographically secure.
+///
+/// [`Store`]: crate::Store
+/// [`Store::trace_hash`]: crate::Store::trace_hash
+/// [`Config`]: crate::Config
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TraceHash([u8; 16]);
+
+impl TraceHash {
+    /// Returns the little-endian bytes of the [`TraceHash`].
+    pub fn to_bytes(self) -> [u8; 16] {
+        self.0
+    }
+
+    /// Creates a [`TraceHash`] from its little-endian bytes.
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl Display for TraceHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0.iter().rev() {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The kinds of events folded into an [`ExecutionTrace`].
+///
+/// # Note
+///
+/// Each event is prefixed with its kind so that different sequences
+/// of events never fold the same sequence of words.
+#[derive(Debug, Copy, Clone)]
+#[repr(u64)]
+enum Event {
+    Control = 0,
+    Store = 1,
+    StoreLane = 2,
+    MemoryGrow = 3,
+    MemoryFill = 4,
+    MemoryCopy = 5,
+    MemoryInit = 6,
+    GlobalSet = 7,
+}
+
+/// A running hash over the executed Wasmi bytecode and its key effects.
+///
+/// # Note
+///
+/// The following events are folded into the hash in execution order:
+///
+/// - The opcode of the first instruction executed upon entering Wasm and of every
+///   instruction at which execution continues after a branch or call, together
+///   with the number of instructions executed so far. Since execution in between
+///   is straight-line this identifies the sequence of all executed instructions.
+/// - Writes to linear memories by stores and bulk-memory instructions.
+/// - Linear memory growth.
+/// - Writes to global variables.
+///
+/// Effects of host functions are not part of the execution trace.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ExecutionTrace {
+    hasher: StableHasher,
+}
+
+impl ExecutionTrace {
+    /// Returns the [`TraceHash`] of all events folded so far.
+    pub fn digest(&self) -> TraceHash {
+        TraceHash(self.hasher.finish().to_bytes())
+    }
+
+    /// Folds an `event` with its parameter `words` into the [`ExecutionTrace`].
+    fn fold(&mut self, event: Event, words: &[u64]) {
+        self.hasher.write_u64(event as u64);
+        for &word in words {
+            self.hasher.write_u64(word);
+        }
+    }
+
+    /// Folds continuing execution at `instr` after `executed` instructions into the [`ExecutionTrace`].
+    pub fn fold_control(&mut self, executed: u64, instr: &Instruction) {
+        // Safety: `Instruction` is `#[repr(u16)]` and thus starts with its `u16` discriminant.
+        let opcode = unsafe { *<*const Instruction>::cast::<u16>(instr) };
+        self.fold(Event::Control, &[executed, u64::from(opcode)]);
+    }
+
+    /// Folds a store of `value` to `memory` at `address` into the [`ExecutionTrace`].
+    pub fn fold_store(&mut self, memory: u32, address: u64, value: UntypedVal) {
+        let [lo, hi] = untyped_words(value);
+        self.fold(Event::Store, &[u64::from(memory), address, lo, hi]);
+    }
+
+    /// Folds a store of the `lane` of `value` to `memory` at `address` into the [`ExecutionTrace`].
+    pub fn fold_store_lane(&mut self, memory: u32, address: u64, value: UntypedVal, lane: u8) {
+        let [lo, hi] = untyped_words(value);
+        let words = [u64::from(memory), address, lo, hi, u64::from(lane)];
+        self.fold(Event::StoreLane, &words);
+    }
+
+    /// Folds growing `memory` by `delta` pages with `result` into the [`ExecutionTrace`].
+    pub fn fold_memory_grow(&mut self, memory: u32, delta: u64, result: u64) {
+        self.fold(Event::MemoryGrow, &[u64::from(memory), delta, result]);
+    }
+
+    /// Folds filling `len` bytes of `memory` at `dst` with `value` into the [`ExecutionTrace`].
+    pub fn fold_memory_fill(&mut self, memory: u32, dst: u64, value: u8, len: u64) {
+        let words = [u64::from(memory), dst, u64::from(value), len];
+        self.fold(Event::MemoryFill, &words);
+    }
+
+    /// Folds copying `len` bytes from `src_memory` at `src` to `dst_memory` at `dst`
+    /// into the [`ExecutionTrace`].
+    pub fn fold_memory_copy(
+        &mut self,
+        dst_memory: u32,
+        src_memory: u32,
+        dst: u64,
+        src: u64,
+        len: u64,
+    ) {
+        let words = [u64::from(dst_memory), u64::from(src_memory), dst, src, len];
+        self.fold(Event::MemoryCopy, &words);
+    }
+
+    /// Folds copying `len` bytes from `data` at `src` to `memory` at `dst` into the [`ExecutionTrace`].
+    pub fn fold_memory_init(&mut self, memory: u32, data: u32, dst: u64, src: u32, len: u32) {
+        let words = [
+            u64::from(memory),
+            u64::from(data),
+            dst,
+            u64::from(src),
+            u64::from(len),
+        ];
+        self.fold(Event::MemoryInit, &words);
+    }
+
+    /// Folds setting `global` to `value` into the [`ExecutionTrace`].
+    pub fn fold_global_set(&mut self, global: u32, value: UntypedVal) {
+        let [lo, hi] = untyped_words(value);
+        self.fold(Event::GlobalSet, &[u64::from(global), lo, hi]);
+    }
+}
+
+/// Returns the low and high 64-bit words of `value`.
+fn untyped_words(value: UntypedVal) -> [u64; 2] {
+    #[cfg(feature = "simd")]
+    {
+        let bits = V128::from(value).as_u128();
+        [bits as u64, (bits >> 64) as u64]
+    }
+    #[cfg(not(feature = "simd"))]
+    {
+        [value.to_bits64(), 0]
+    }
+}
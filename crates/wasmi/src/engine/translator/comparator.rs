@@ -70,13 +70,17 @@ impl NegateCmpInstr for Instruction {
             //
             // Note: due to NaN values always comparing as `false` we unfortunately
             //       cannot negate `f32.{lt,le}` comparison instructions.
+            #[cfg(not(feature = "no-float"))]
             I::F32Eq { result, lhs, rhs } => I::f32_ne(result, lhs, rhs),
+            #[cfg(not(feature = "no-float"))]
             I::F32Ne { result, lhs, rhs } => I::f32_eq(result, lhs, rhs),
             // f64
             //
             // Note: due to NaN values always comparing as `false` we unfortunately
             //       cannot negate `f64.{lt,le}` comparison instructions.
+            #[cfg(not(feature = "no-float"))]
             I::F64Eq { result, lhs, rhs } => I::f64_ne(result, lhs, rhs),
+            #[cfg(not(feature = "no-float"))]
             I::F64Ne { result, lhs, rhs } => I::f64_eq(result, lhs, rhs),
             _ => return None,
         };
@@ -152,14 +156,22 @@ impl TryIntoCmpBranchInstr for Instruction {
             I::I64LtSImm16Rhs { lhs, rhs, .. } => I::branch_i64_lt_s_imm16_rhs(lhs, rhs, offset),
             I::I64LtUImm16Rhs { lhs, rhs, .. } => I::branch_i64_lt_u_imm16_rhs(lhs, rhs, offset),
             // f32
+            #[cfg(not(feature = "no-float"))]
             I::F32Eq { lhs, rhs, .. } => I::branch_f32_eq(lhs, rhs, offset),
+            #[cfg(not(feature = "no-float"))]
             I::F32Ne { lhs, rhs, .. } => I::branch_f32_ne(lhs, rhs, offset),
+            #[cfg(not(feature = "no-float"))]
             I::F32Lt { lhs, rhs, .. } => I::branch_f32_lt(lhs, rhs, offset),
+            #[cfg(not(feature = "no-float"))]
             I::F32Le { lhs, rhs, .. } => I::branch_f32_le(lhs, rhs, offset),
             // f64
+            #[cfg(not(feature = "no-float"))]
             I::F64Eq { lhs, rhs, .. } => I::branch_f64_eq(lhs, rhs, offset),
+            #[cfg(not(feature = "no-float"))]
             I::F64Ne { lhs, rhs, .. } => I::branch_f64_ne(lhs, rhs, offset),
+            #[cfg(not(feature = "no-float"))]
             I::F64Lt { lhs, rhs, .. } => I::branch_f64_lt(lhs, rhs, offset),
+            #[cfg(not(feature = "no-float"))]
             I::F64Le { lhs, rhs, .. } => I::branch_f64_le(lhs, rhs, offset),
             _ => return Ok(None),
         };
@@ -205,7 +217,8 @@ impl TryIntoCmpBranchFallbackInstr for Instruction {
             | I::BranchI64LtS { lhs, rhs, .. }
             | I::BranchI64LtU { lhs, rhs, .. }
             | I::BranchI64LeS { lhs, rhs, .. }
-            | I::BranchI64LeU { lhs, rhs, .. }
+            | I::BranchI64LeU { lhs, rhs, .. } => (lhs, rhs),
+            #[cfg(not(feature = "no-float"))]
             | I::BranchF32Eq { lhs, rhs, .. }
             | I::BranchF32Ne { lhs, rhs, .. }
             | I::BranchF32Lt { lhs, rhs, .. }
@@ -313,14 +326,22 @@ fn try_into_cmp_br_comparator(instr: &Instruction) -> Option<Comparator> {
         | I::BranchI64LeUImm16Lhs { .. }
         | I::BranchI64LeUImm16Rhs { .. } => Comparator::I64LeU,
         // f32
+        #[cfg(not(feature = "no-float"))]
         | I::BranchF32Eq { .. } => Comparator::F32Eq,
+        #[cfg(not(feature = "no-float"))]
         | I::BranchF32Ne { .. } => Comparator::F32Ne,
+        #[cfg(not(feature = "no-float"))]
         | I::BranchF32Lt { .. } => Comparator::F32Lt,
+        #[cfg(not(feature = "no-float"))]
         | I::BranchF32Le { .. } => Comparator::F32Le,
         // f64
+        #[cfg(not(feature = "no-float"))]
         | I::BranchF64Eq { .. } => Comparator::F64Eq,
+        #[cfg(not(feature = "no-float"))]
         | I::BranchF64Ne { .. } => Comparator::F64Ne,
+        #[cfg(not(feature = "no-float"))]
         | I::BranchF64Lt { .. } => Comparator::F64Lt,
+        #[cfg(not(feature = "no-float"))]
         | I::BranchF64Le { .. } => Comparator::F64Le,
         _ => return None,
     };
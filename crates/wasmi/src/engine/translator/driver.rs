@@ -1,5 +1,5 @@
 use crate::{
-    engine::{code_map::CompiledFuncEntity, WasmTranslator},
+    engine::{code_map::CompiledFuncEntity, metrics::TranslationTimer, WasmTranslator},
     Error,
 };
 use wasmparser::{BinaryReader, FunctionBody};
@@ -40,7 +40,11 @@ where
         mut self,
         finalize: impl FnOnce(CompiledFuncEntity),
     ) -> Result<T::Allocations, Error> {
-        if self.translator.setup(self.bytes)? {
+        let timer = TranslationTimer::start();
+        let finalize =
+            |entity: CompiledFuncEntity| finalize(entity.with_translation_time(timer.elapsed()));
+        let offset = self.func_body.range().start;
+        if self.translator.setup(offset, self.bytes)? {
             let allocations = self.translator.finish(finalize)?;
             return Ok(allocations);
         }
@@ -82,7 +86,11 @@ where
         while !reader.eof() {
             let pos = reader.original_position();
             self.translator.update_pos(pos);
-            reader.visit_operator(&mut self.translator)??;
+            reader
+                .visit_operator(&mut self.translator)?
+                .map_err(|error| {
+                    error.with_operator_from(self.bytes, self.func_body.range().start)
+                })?;
         }
         reader.ensure_end()?;
         Ok(reader.original_position())
@@ -16,6 +16,7 @@ use crate::{
     core::{UntypedVal, ValType},
     engine::{
         translator::{stack::RegisterSpace, ValueStack},
+        CoverageProbe,
         FuelCosts,
     },
     ir::{
@@ -32,11 +33,18 @@ use crate::{
     module::ModuleHeader,
     Error,
 };
-use alloc::vec::{Drain, Vec};
+use alloc::{
+    boxed::Box,
+    vec::{Drain, Vec},
+};
 use core::mem;
 
-/// A reference to an instruction of the partially
-/// constructed function body of the [`InstrEncoder`].
+#[cfg(feature = "branch-profile")]
+use crate::engine::BranchSite;
+
+/// A reference to an [`Instruction`] within the Wasmi bytecode of a function.
+///
+/// This is the index of the [`Instruction`] within the sequence of its function.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Instr(u32);
 
@@ -99,6 +107,13 @@ pub struct InstrEncoder {
     /// defragmentation of the register space due to `local.set` register
     /// preservations.
     notified_preservation: Option<Instr>,
+    /// The coverage probes encoded via [`InstrEncoder::encode_coverage_probe`].
+    probes: Vec<CoverageProbe>,
+    /// The number of trailing `probes` whose basic blocks have not yet been located.
+    unlocated_probes: usize,
+    /// The conditional branches recorded via [`InstrEncoder::record_branch_site`].
+    #[cfg(feature = "branch-profile")]
+    branch_sites: Vec<BranchSite>,
 }
 
 /// The sequence of encoded [`Instruction`].
@@ -123,9 +138,11 @@ impl InstrSequence {
     ///
     /// # Errors
     ///
-    /// If there are too many instructions in the instruction sequence.
+    /// - If there are too many instructions in the instruction sequence.
+    /// - If the system ran out of memory to grow the instruction sequence.
     fn push(&mut self, instruction: Instruction) -> Result<Instr, Error> {
         let instr = self.next_instr();
+        self.instrs.try_reserve(1)?;
         self.instrs.push(instruction);
         Ok(instr)
     }
@@ -144,8 +161,10 @@ impl InstrSequence {
     ///
     /// # Errors
     ///
-    /// If there are too many instructions in the instruction sequence.
+    /// - If there are too many instructions in the instruction sequence.
+    /// - If the system ran out of memory to grow the instruction sequence.
     fn push_before(&mut self, instr: Instr, instruction: Instruction) -> Result<Instr, Error> {
+        self.instrs.try_reserve(1)?;
         self.instrs.insert(instr.into_usize(), instruction);
         let shifted_instr = instr
             .into_u32()
@@ -208,6 +227,10 @@ impl InstrEncoder {
         self.labels.reset();
         self.reset_last_instr();
         self.notified_preservation = None;
+        self.probes.clear();
+        self.unlocated_probes = 0;
+        #[cfg(feature = "branch-profile")]
+        self.branch_sites.clear();
     }
 
     /// Resets the [`Instr`] last created via [`InstrEncoder::push_instr`].
@@ -225,6 +248,11 @@ impl InstrEncoder {
         self.last_instr = None;
     }
 
+    /// Returns the number of encoded [`Instruction`] words.
+    pub fn len_instrs(&self) -> usize {
+        self.instrs.instrs.len()
+    }
+
     /// Return an iterator over the sequence of generated [`Instruction`].
     ///
     /// # Note
@@ -234,6 +262,69 @@ impl InstrEncoder {
         self.instrs.drain()
     }
 
+    /// Encodes a coverage probe for the basic block starting at the current position.
+    ///
+    /// # Note
+    ///
+    /// The probe is an unconditional branch to the next instruction so that its execution
+    /// reaches a safepoint. The basic block is located by the next call to
+    /// [`InstrEncoder::locate_coverage_probes`] which happens for its first Wasm operator.
+    pub fn encode_coverage_probe(&mut self) -> Result<(), Error> {
+        let instr = self.push_instr(Instruction::branch(BranchOffset::from(1)))?;
+        self.probes.try_reserve(1)?;
+        self.probes.push(CoverageProbe::new(instr.into_u32()));
+        self.unlocated_probes += 1;
+        Ok(())
+    }
+
+    /// Locates the basic blocks of all not yet located coverage probes at the Wasm `offset`.
+    pub fn locate_coverage_probes(&mut self, offset: u32) {
+        let len_probes = self.probes.len();
+        for probe in &mut self.probes[len_probes - self.unlocated_probes..] {
+            probe.locate(offset);
+        }
+        self.unlocated_probes = 0;
+    }
+
+    /// Returns the encoded coverage probes.
+    ///
+    /// # Note
+    ///
+    /// The coverage probes of the [`InstrEncoder`] will be empty after this operation.
+    pub fn take_coverage_probes(&mut self) -> Box<[CoverageProbe]> {
+        self.unlocated_probes = 0;
+        self.probes.drain(..).collect()
+    }
+
+    /// Records the last encoded conditional branch for the Wasm operator at `offset`.
+    ///
+    /// `negated` is `true` if the encoded branch is taken if the Wasm branch is not taken.
+    ///
+    /// # Note
+    ///
+    /// Fused compare and branch instructions replace the last instruction so that
+    /// the last instruction is the conditional branch in all cases.
+    #[cfg(feature = "branch-profile")]
+    pub fn record_branch_site(&mut self, offset: u32, negated: bool) -> Result<(), Error> {
+        let Some(instr) = self.last_instr else {
+            panic!("expected a conditional branch instruction to be encoded")
+        };
+        self.branch_sites.try_reserve(1)?;
+        self.branch_sites
+            .push(BranchSite::new(instr.into_u32(), offset, negated));
+        Ok(())
+    }
+
+    /// Returns the recorded conditional branches.
+    ///
+    /// # Note
+    ///
+    /// The recorded conditional branches of the [`InstrEncoder`] will be empty after this operation.
+    #[cfg(feature = "branch-profile")]
+    pub fn take_branch_sites(&mut self) -> Box<[BranchSite]> {
+        self.branch_sites.drain(..).collect()
+    }
+
     /// Creates a new unresolved label and returns its [`LabelRef`].
     pub fn new_label(&mut self) -> LabelRef {
         self.labels.new_label()
@@ -429,10 +520,13 @@ impl InstrEncoder {
                     Some(value) => Instruction::copy_i64imm32(result, value),
                     None => copy_imm(stack, result, value)?,
                 },
+                #[cfg(not(feature = "no-float"))]
                 ValType::F64 => match <Const32<f64>>::try_from(f64::from(value)).ok() {
                     Some(value) => Instruction::copy_f64imm32(result, value),
                     None => copy_imm(stack, result, value)?,
                 },
+                #[cfg(feature = "no-float")]
+                ValType::F64 => copy_imm(stack, result, value)?,
                 ValType::V128 | ValType::FuncRef | ValType::ExternRef => {
                     copy_imm(stack, result, value)?
                 }
@@ -599,10 +693,13 @@ impl InstrEncoder {
                     None => Instruction::return_reg(stack.alloc_const(*value)?),
                 },
                 ValType::F32 => Instruction::return_imm32(f32::from(*value)),
+                #[cfg(not(feature = "no-float"))]
                 ValType::F64 => match <Const32<f64>>::try_from(f64::from(*value)).ok() {
                     Some(value) => Instruction::return_f64imm32(value),
                     None => Instruction::return_reg(stack.alloc_const(*value)?),
                 },
+                #[cfg(feature = "no-float")]
+                ValType::F64 => Instruction::return_reg(stack.alloc_const(*value)?),
                 ValType::V128 | ValType::FuncRef | ValType::ExternRef => {
                     Instruction::return_reg(stack.alloc_const(*value)?)
                 }
@@ -667,10 +764,13 @@ impl InstrEncoder {
                     None => Instruction::return_nez_reg(condition, stack.alloc_const(*value)?),
                 },
                 ValType::F32 => Instruction::return_nez_imm32(condition, f32::from(*value)),
+                #[cfg(not(feature = "no-float"))]
                 ValType::F64 => match <Const32<f64>>::try_from(f64::from(*value)).ok() {
                     Some(value) => Instruction::return_nez_f64imm32(condition, value),
                     None => Instruction::return_nez_reg(condition, stack.alloc_const(*value)?),
                 },
+                #[cfg(feature = "no-float")]
+                ValType::F64 => Instruction::return_nez_reg(condition, stack.alloc_const(*value)?),
                 ValType::V128 | ValType::FuncRef | ValType::ExternRef => {
                     Instruction::return_nez_reg(condition, stack.alloc_const(*value)?)
                 }
@@ -757,6 +857,37 @@ impl InstrEncoder {
         Ok(())
     }
 
+    /// Encode the given slice of [`TypedProvider`] as the parameters of a call instruction.
+    ///
+    /// # Note
+    ///
+    /// Parameters that are stored in contiguous registers are encoded as a single
+    /// [`Instruction::RegisterSpan`] instead of a [`Instruction::RegisterList`] sequence.
+    /// This is the common case for calls since their parameters are usually computed
+    /// into consecutive dynamic registers.
+    ///
+    /// This is used for the following n-ary instructions:
+    ///
+    /// - [`Instruction::CallInternal`]
+    /// - [`Instruction::CallImported`]
+    /// - [`Instruction::CallIndirect`]
+    /// - [`Instruction::ReturnCallInternal`]
+    /// - [`Instruction::ReturnCallImported`]
+    /// - [`Instruction::ReturnCallIndirect`]
+    pub fn encode_call_params(
+        &mut self,
+        stack: &mut ValueStack,
+        params: &[TypedProvider],
+    ) -> Result<(), Error> {
+        if params.len() > 3 {
+            if let Some(span) = BoundedRegSpan::from_providers(params) {
+                self.instrs.push(Instruction::register_span(span))?;
+                return Ok(());
+            }
+        }
+        self.encode_register_list(stack, params)
+    }
+
     /// Encode a `local.set` or `local.tee` instruction.
     ///
     /// This also applies an optimization in that the previous instruction
@@ -861,8 +992,91 @@ impl InstrEncoder {
         Ok(())
     }
 
+    /// Tries to relink the results of the last encoded call instruction to `results`.
+    ///
+    /// Returns `true` if the results have been relinked so that copying `values` to
+    /// `results` is no longer necessary.
+    ///
+    /// # Note
+    ///
+    /// This allows calls returning multiple values to write their results directly into
+    /// the registers expected by the branch target instead of copying them afterwards.
+    /// The `values` must be the results of the last encoded call instruction and must be
+    /// consumed by the copy, e.g. by an unconditional branch or the `end` of a block.
+    pub fn try_relink_call_results(
+        &mut self,
+        stack: &ValueStack,
+        res: &ModuleHeader,
+        results: BoundedRegSpan,
+        values: &[TypedProvider],
+    ) -> Result<bool, Error> {
+        if values.len() < 2 {
+            // Note: single results are relinked via `try_relink_result`.
+            return Ok(false);
+        }
+        let Some(values) = BoundedRegSpan::from_providers(values) else {
+            return Ok(false);
+        };
+        if values.span() == results.span() {
+            // Case: the values are already where they are expected, no copy is required.
+            return Ok(false);
+        }
+        let is_dynamic = values
+            .iter()
+            .all(|value| matches!(stack.get_register_space(value), RegisterSpace::Dynamic));
+        if !is_dynamic {
+            // Can only relink results of calls which are always written into dynamic registers.
+            return Ok(false);
+        }
+        let Some(last_instr) = self.last_instr else {
+            return Ok(false);
+        };
+        self.instrs
+            .get_mut(last_instr)
+            .relink_call_results(res, results.span(), values)
+    }
+
+    /// Tries to relink the result of the last encoded instruction to `result`.
+    ///
+    /// Returns `true` if the result has been relinked so that copying `value` to
+    /// `result` is no longer necessary.
+    ///
+    /// # Note
+    ///
+    /// This allows the last instruction of a block to write its result directly into
+    /// the block result register instead of copying it there afterwards.
+    /// The `value` must be consumed by the copy, e.g. by an unconditional branch or
+    /// the `end` of a block.
+    pub fn try_relink_result(
+        &mut self,
+        stack: &ValueStack,
+        res: &ModuleHeader,
+        result: Reg,
+        value: TypedProvider,
+    ) -> Result<bool, Error> {
+        let TypedProvider::Register(value) = value else {
+            return Ok(false);
+        };
+        if value == result {
+            // Case: the value is already where it is expected, no copy is required.
+            return Ok(false);
+        }
+        if !matches!(stack.get_register_space(value), RegisterSpace::Dynamic) {
+            // Note: local and preserved registers are not produced by the last instruction.
+            return Ok(false);
+        }
+        let Some(last_instr) = self.last_instr else {
+            return Ok(false);
+        };
+        self.instrs
+            .get_mut(last_instr)
+            .relink_result(res, result, value)
+    }
+
     /// Notifies the [`InstrEncoder`] that a local variable has been preserved.
     ///
+    /// This is also used for loop-invariant computations hoisted into preserved registers.
+    ///
     /// # Note
     ///
     /// This is an optimization that we perform to avoid or minimize the work
@@ -883,8 +1097,12 @@ impl InstrEncoder {
                         | Instruction::Copy2 { .. }
                         | Instruction::CopySpanNonOverlapping { .. }
                         | Instruction::CopyManyNonOverlapping { .. }
+                        | Instruction::I32Add { .. }
+                        | Instruction::I32AddImm16 { .. }
+                        | Instruction::I64Add { .. }
+                        | Instruction::I64AddImm16 { .. }
                 ),
-                "a preserve instruction is always a register copy instruction but found: {:?}",
+                "a preserve instruction is always a register copy or hoisted add instruction but found: {:?}",
                 preserved,
             );
         }
@@ -1050,7 +1268,8 @@ impl InstrEncoder {
             | I::I64LtS { result, .. } | I::I64LtSImm16Lhs { result, .. } | I::I64LtSImm16Rhs { result, .. }
             | I::I64LtU { result, .. } | I::I64LtUImm16Lhs { result, .. } | I::I64LtUImm16Rhs { result, .. }
             | I::I64LeS { result, .. } | I::I64LeSImm16Lhs { result, .. } | I::I64LeSImm16Rhs { result, .. }
-            | I::I64LeU { result, .. } | I::I64LeUImm16Lhs { result, .. } | I::I64LeUImm16Rhs { result, .. }
+            | I::I64LeU { result, .. } | I::I64LeUImm16Lhs { result, .. } | I::I64LeUImm16Rhs { result, .. } => result,
+            #[cfg(not(feature = "no-float"))]
             | I::F32Eq { result, .. }
             | I::F32Ne { result, .. }
             | I::F32Lt { result, .. }
@@ -1157,14 +1376,6 @@ impl UpdateBranchOffset for Instruction {
             I::BranchI64LtU { offset, .. } |
             I::BranchI64LeS { offset, .. } |
             I::BranchI64LeU { offset, .. } |
-            I::BranchF32Eq { offset, .. } |
-            I::BranchF32Ne { offset, .. } |
-            I::BranchF32Lt { offset, .. } |
-            I::BranchF32Le { offset, .. } |
-            I::BranchF64Eq { offset, .. } |
-            I::BranchF64Ne { offset, .. } |
-            I::BranchF64Lt { offset, .. } |
-            I::BranchF64Le { offset, .. } |
             I::BranchI32AndImm16 { offset, .. } |
             I::BranchI32OrImm16 { offset, .. } |
             I::BranchI32XorImm16 { offset, .. } |
@@ -1193,6 +1404,17 @@ impl UpdateBranchOffset for Instruction {
             I::BranchI64LeUImm16Rhs { offset, .. } => {
                 offset.init(new_offset)
             }
+            #[cfg(not(feature = "no-float"))]
+            I::BranchF32Eq { offset, .. } |
+            I::BranchF32Ne { offset, .. } |
+            I::BranchF32Lt { offset, .. } |
+            I::BranchF32Le { offset, .. } |
+            I::BranchF64Eq { offset, .. } |
+            I::BranchF64Ne { offset, .. } |
+            I::BranchF64Lt { offset, .. } |
+            I::BranchF64Le { offset, .. } => {
+                offset.init(new_offset)
+            }
             unexpected => {
                 panic!("expected a Wasmi branch+cmp instruction but found: {unexpected:?}")
             }
@@ -0,0 +1,294 @@
+use super::LabelRef;
+use crate::{
+    core::{TypedVal, ValType},
+    ir::Reg,
+};
+use alloc::vec::Vec;
+use wasmparser::{BinaryReader, BinaryReaderError, FunctionBody, Operator, WasmFeatures};
+
+/// The maximum number of computations hoisted out of a single Wasm `loop`.
+///
+/// Every hoisted computation occupies a register for the entire Wasm `loop`.
+const MAX_HOISTS_PER_LOOP: usize = 4;
+
+/// The maximum number of computations hoisted out of all enclosing Wasm `loop`s.
+///
+/// This bounds the register pressure of deeply nested Wasm `loop`s.
+const MAX_HOISTS: usize = 16;
+
+/// A `local.get x` plus constant addition that is invariant within a Wasm `loop`.
+#[derive(Debug, Copy, Clone)]
+pub struct LoopInvariant {
+    /// The position of the Wasm `loop` relative to the start of the function body.
+    pos: u32,
+    /// The local variable that is never written within the Wasm `loop`.
+    local: Reg,
+    /// The constant `i32` or `i64` value that is added to `local`.
+    value: TypedVal,
+}
+
+impl LoopInvariant {
+    /// Returns the local variable [`Reg`] of the [`LoopInvariant`].
+    pub fn local(&self) -> Reg {
+        self.local
+    }
+
+    /// Returns the constant value that is added to the local variable.
+    pub fn value(&self) -> TypedVal {
+        self.value
+    }
+}
+
+/// A [`LoopInvariant`] that has been hoisted into its own register.
+#[derive(Debug, Copy, Clone)]
+struct HoistedInvariant {
+    /// The header of the Wasm `loop` out of which the computation got hoisted.
+    loop_header: LabelRef,
+    /// The local variable of the hoisted computation.
+    local: Reg,
+    /// The constant value of the hoisted computation.
+    value: TypedVal,
+    /// The register holding the result of the hoisted computation.
+    result: Reg,
+}
+
+/// A Wasm control frame visited by [`LoopInvariants::scan`].
+#[derive(Debug, Copy, Clone)]
+struct ScanFrame {
+    /// Is `true` if the frame is a Wasm `loop`.
+    is_loop: bool,
+    /// The position of the innermost enclosing Wasm `loop` if any.
+    loop_pos: Option<u32>,
+    /// The index of the first candidate of the innermost enclosing Wasm `loop`.
+    first_candidate: usize,
+}
+
+/// Finds and tracks hoisted loop-invariant computations of a Wasm function.
+///
+/// # Note
+///
+/// Wasm constants themselves are never recomputed since they are stored in
+/// function local constant registers. However, `local.get x` plus constant
+/// additions, such as base addresses of memory accesses, are recomputed in
+/// every iteration of a Wasm `loop` unless they are hoisted out of it.
+#[derive(Debug, Default)]
+pub struct LoopInvariants {
+    /// The [`LoopInvariant`]s of the scanned Wasm function sorted by `loop` position.
+    found: Vec<LoopInvariant>,
+    /// The index of the next [`LoopInvariant`] in `found` for the translation.
+    cursor: usize,
+    /// The currently hoisted [`LoopInvariant`]s of all enclosing Wasm `loop`s.
+    hoisted: Vec<HoistedInvariant>,
+    /// The Wasm control frames during the scan.
+    frames: Vec<ScanFrame>,
+    /// The [`LoopInvariant`] candidates of all Wasm `loop`s during the scan.
+    candidates: Vec<LoopInvariant>,
+    /// The position of the last write to each local variable indexed by the local.
+    ///
+    /// A position of 0 indicates that the local has not been written, yet.
+    /// This is unambiguous since the first Wasm operator of a function body
+    /// is always preceded by its local variable declarations.
+    last_write: Vec<u32>,
+}
+
+impl LoopInvariants {
+    /// Resets the [`LoopInvariants`].
+    pub fn reset(&mut self) {
+        self.found.clear();
+        self.cursor = 0;
+        self.hoisted.clear();
+        self.frames.clear();
+        self.candidates.clear();
+        self.last_write.clear();
+    }
+
+    /// Scans the Wasm function body `bytes` starting at `offset` for [`LoopInvariant`]s.
+    ///
+    /// # Note
+    ///
+    /// Scanning stops without [`LoopInvariant`]s for malformed Wasm function bodies
+    /// since their errors are reported by Wasm validation during translation.
+    pub fn scan(&mut self, offset: usize, bytes: &[u8], features: WasmFeatures) {
+        self.reset();
+        if self.scan_operators(offset, bytes, features).is_err() {
+            self.found.clear();
+        }
+        self.found.sort_by_key(|invariant| invariant.pos);
+    }
+
+    /// Scans the operators of the Wasm function body for [`LoopInvariant`]s.
+    fn scan_operators(
+        &mut self,
+        offset: usize,
+        bytes: &[u8],
+        features: WasmFeatures,
+    ) -> Result<(), BinaryReaderError> {
+        let reader = BinaryReader::new_features(bytes, offset, features);
+        let mut reader = FunctionBody::new(reader).get_operators_reader()?;
+        self.push_frame(false, 0);
+        // The local of the directly preceding `local.get`.
+        let mut local_get: Option<u32> = None;
+        // The local and constant of the directly preceding `local.get` and `{i32,i64}.const`.
+        let mut local_const: Option<(u32, TypedVal)> = None;
+        while !reader.eof() {
+            let pos = (reader.original_position() - offset) as u32;
+            let op = reader.read()?;
+            let (local_get_before, local_const_before) = (local_get.take(), local_const.take());
+            match op {
+                Operator::LocalGet { local_index } => local_get = Some(local_index),
+                Operator::I32Const { value } if value != 0 => {
+                    local_const = local_get_before.map(|local| (local, TypedVal::from(value)));
+                }
+                Operator::I64Const { value } if value != 0 => {
+                    local_const = local_get_before.map(|local| (local, TypedVal::from(value)));
+                }
+                Operator::I32Add => self.push_candidate(local_const_before, ValType::I32),
+                Operator::I64Add => self.push_candidate(local_const_before, ValType::I64),
+                Operator::LocalSet { local_index } | Operator::LocalTee { local_index } => {
+                    self.record_write(local_index, pos);
+                }
+                Operator::Loop { .. } => self.push_frame(true, pos),
+                Operator::Block { .. } | Operator::If { .. } | Operator::TryTable { .. } => {
+                    self.push_frame(false, pos)
+                }
+                Operator::End => match self.frames.pop() {
+                    Some(frame) if frame.is_loop => self.finish_loop(frame),
+                    Some(_) => {}
+                    None => break,
+                },
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Pushes a new [`ScanFrame`] at `pos`.
+    fn push_frame(&mut self, is_loop: bool, pos: u32) {
+        let frame = match is_loop {
+            true => ScanFrame {
+                is_loop,
+                loop_pos: Some(pos),
+                first_candidate: self.candidates.len(),
+            },
+            false => match self.frames.last() {
+                Some(parent) => ScanFrame { is_loop, ..*parent },
+                None => ScanFrame {
+                    is_loop,
+                    loop_pos: None,
+                    first_candidate: 0,
+                },
+            },
+        };
+        self.frames.push(frame);
+    }
+
+    /// Records a `local.get x` plus constant addition of type `ty` for the innermost Wasm `loop`.
+    fn push_candidate(&mut self, local_const: Option<(u32, TypedVal)>, ty: ValType) {
+        let Some((local, value)) = local_const else {
+            return;
+        };
+        let Some(frame) = self.frames.last() else {
+            return;
+        };
+        let Some(pos) = frame.loop_pos else {
+            return;
+        };
+        let Ok(local) = i16::try_from(local).map(Reg::from) else {
+            return;
+        };
+        if value.ty() != ty {
+            return;
+        }
+        let candidates = &self.candidates[frame.first_candidate..];
+        if candidates.len() >= MAX_HOISTS_PER_LOOP
+            || candidates
+                .iter()
+                .any(|c| c.local == local && c.value == value)
+        {
+            return;
+        }
+        self.candidates.push(LoopInvariant { pos, local, value });
+    }
+
+    /// Records a write to the local variable `local` at `pos`.
+    fn record_write(&mut self, local: u32, pos: u32) {
+        let Ok(local) = i16::try_from(local) else {
+            // Note: such locals are never part of any candidate.
+            return;
+        };
+        let index = local as usize;
+        if index >= self.last_write.len() {
+            self.last_write.resize(index + 1, 0);
+        }
+        self.last_write[index] = pos;
+    }
+
+    /// Moves all candidates of the Wasm `loop` of `frame` not written within the `loop`.
+    fn finish_loop(&mut self, frame: ScanFrame) {
+        let Some(pos) = frame.loop_pos else {
+            return;
+        };
+        let Self {
+            found,
+            candidates,
+            last_write,
+            ..
+        } = self;
+        found.extend(candidates.drain(frame.first_candidate..).filter(|c| {
+            let index = i16::from(c.local) as usize;
+            last_write.get(index).copied().unwrap_or(0) <= pos
+        }));
+    }
+
+    /// Returns the next [`LoopInvariant`] of the Wasm `loop` at `pos` if any.
+    ///
+    /// # Note
+    ///
+    /// Wasm `loop`s must be queried in the order of their positions.
+    /// [`LoopInvariant`]s of skipped Wasm `loop`s, e.g. due to unreachable code, are dropped.
+    pub fn next(&mut self, pos: u32) -> Option<LoopInvariant> {
+        while let Some(invariant) = self.found.get(self.cursor).copied() {
+            if invariant.pos > pos {
+                return None;
+            }
+            self.cursor += 1;
+            if invariant.pos == pos {
+                return Some(invariant);
+            }
+        }
+        None
+    }
+
+    /// Returns `true` if no more computations may be hoisted for the enclosing Wasm `loop`s.
+    pub fn is_full(&self) -> bool {
+        self.hoisted.len() >= MAX_HOISTS
+    }
+
+    /// Registers the `invariant` of the Wasm `loop` at `loop_header` as hoisted into `result`.
+    pub fn push_hoisted(&mut self, loop_header: LabelRef, invariant: LoopInvariant, result: Reg) {
+        self.hoisted.push(HoistedInvariant {
+            loop_header,
+            local: invariant.local,
+            value: invariant.value,
+            result,
+        });
+    }
+
+    /// Returns the register holding the hoisted `local` plus `value` computation if any.
+    pub fn get(&self, local: Reg, value: TypedVal) -> Option<Reg> {
+        self.hoisted
+            .iter()
+            .rev()
+            .find(|hoisted| hoisted.local == local && hoisted.value == value)
+            .map(|hoisted| hoisted.result)
+    }
+
+    /// Pops the next register of a computation hoisted out of the Wasm `loop` at `loop_header`.
+    pub fn pop_hoisted(&mut self, loop_header: LabelRef) -> Option<Reg> {
+        let hoisted = self.hoisted.last()?;
+        if hoisted.loop_header != loop_header {
+            return None;
+        }
+        self.hoisted.pop().map(|hoisted| hoisted.result)
+    }
+}
@@ -7,6 +7,7 @@ mod driver;
 mod error;
 mod instr_encoder;
 mod labels;
+mod loop_invariant;
 mod provider;
 mod relink_result;
 mod stack;
@@ -32,9 +33,10 @@ use self::{
     },
     control_stack::AcquiredTarget,
     labels::{LabelRef, LabelRegistry},
+    loop_invariant::LoopInvariants,
     provider::{Provider, ProviderSliceStack, UntypedProvider},
     stack::ValueStack,
-    utils::{FromProviders as _, WasmFloat, WasmInteger},
+    utils::{FromProviders as _, WasmInteger},
 };
 pub use self::{
     control_frame::{ControlFrame, ControlFrameKind},
@@ -47,7 +49,7 @@ pub use self::{
 use super::code_map::CompiledFuncEntity;
 use crate::{
     core::{TrapCode, Typed, TypedVal, UntypedVal, ValType},
-    engine::{config::FuelCosts, BlockType, EngineFunc},
+    engine::{config::FuelCosts, BlockType, EngineFunc, IndexWidth},
     ir::{
         index,
         Address,
@@ -65,7 +67,6 @@ use crate::{
         Offset64Lo,
         Reg,
         RegSpan,
-        Sign,
     },
     module::{FuncIdx, FuncTypeIdx, MemoryIdx, ModuleHeader, TableIdx},
     Engine,
@@ -88,6 +89,11 @@ use wasmparser::{
     WasmFeatures,
 };
 
+#[cfg(not(feature = "no-float"))]
+use self::utils::WasmFloat;
+#[cfg(not(feature = "no-float"))]
+use crate::ir::Sign;
+
 macro_rules! impl_typed_for {
     ( $( $ty:ident ),* $(,)? ) => {
         $(
@@ -126,6 +132,8 @@ pub struct FuncTranslatorAllocations {
     control_stack: ControlStack,
     /// Some reusable buffers for translation purposes.
     buffer: TranslationBuffers,
+    /// The loop-invariant computations of the translated function.
+    loop_invariants: LoopInvariants,
 }
 
 /// Reusable allocations for utility buffers.
@@ -169,6 +177,7 @@ impl FuncTranslatorAllocations {
         self.instr_encoder.reset();
         self.control_stack.reset();
         self.buffer.reset();
+        self.loop_invariants.reset();
     }
 }
 
@@ -237,9 +246,10 @@ pub trait WasmTranslator<'parser>:
     ///
     /// - This method requires `bytes` to be the slice of bytes that make up the entire
     ///   Wasm function body (including local variables).
+    /// - The `offset` is the position of `bytes` within the translated Wasm binary.
     /// - Also `module` must be a reference to the Wasm module header that is going to be
     ///   used for translation of the Wasm function body.
-    fn setup(&mut self, bytes: &[u8]) -> Result<bool, Error>;
+    fn setup(&mut self, offset: usize, bytes: &[u8]) -> Result<bool, Error>;
 
     /// Returns a reference to the [`WasmFeatures`] used by the [`WasmTranslator`].
     fn features(&self) -> WasmFeatures;
@@ -320,8 +330,8 @@ where
 {
     type Allocations = ReusableAllocations<T::Allocations>;
 
-    fn setup(&mut self, bytes: &[u8]) -> Result<bool, Error> {
-        self.translator.setup(bytes)?;
+    fn setup(&mut self, offset: usize, bytes: &[u8]) -> Result<bool, Error> {
+        self.translator.setup(offset, bytes)?;
         // Note: Wasm validation always need to be driven, therefore returning `Ok(false)`
         //       even if the underlying Wasm translator does not need a translation driver.
         Ok(false)
@@ -349,6 +359,7 @@ where
 
     fn update_pos(&mut self, pos: usize) {
         self.pos = pos;
+        self.translator.update_pos(pos);
     }
 
     fn finish(
@@ -555,7 +566,7 @@ impl LazyFuncTranslator {
 impl WasmTranslator<'_> for LazyFuncTranslator {
     type Allocations = ();
 
-    fn setup(&mut self, bytes: &[u8]) -> Result<bool, Error> {
+    fn setup(&mut self, offset: usize, bytes: &[u8]) -> Result<bool, Error> {
         self.module
             .engine()
             .upgrade()
@@ -568,6 +579,7 @@ impl WasmTranslator<'_> for LazyFuncTranslator {
             .init_lazy_func(
                 self.func_idx,
                 self.engine_func,
+                offset,
                 bytes,
                 &self.module,
                 self.validation.take_func_to_validate(),
@@ -663,6 +675,16 @@ pub struct FuncTranslator {
     ///
     /// `None` if fuel metering is disabled.
     fuel_costs: Option<FuelCosts>,
+    /// Is `true` if basic blocks are probed for code coverage.
+    coverage: bool,
+    /// Is `true` if loop-invariant computations are hoisted out of Wasm loops.
+    hoist_loop_invariants: bool,
+    /// The width of the indices used by the compiled function.
+    index_width: IndexWidth,
+    /// The position of the translated Wasm function body within the Wasm binary.
+    body_offset: usize,
+    /// The offset of the currently translated Wasm operator relative to `body_offset`.
+    pos: u32,
     /// The reusable data structures of the [`FuncTranslator`].
     alloc: FuncTranslatorAllocations,
 }
@@ -670,7 +692,12 @@ pub struct FuncTranslator {
 impl WasmTranslator<'_> for FuncTranslator {
     type Allocations = FuncTranslatorAllocations;
 
-    fn setup(&mut self, _bytes: &[u8]) -> Result<bool, Error> {
+    fn setup(&mut self, offset: usize, bytes: &[u8]) -> Result<bool, Error> {
+        self.body_offset = offset;
+        if self.hoist_loop_invariants {
+            let features = self.engine.config().wasm_features();
+            self.alloc.loop_invariants.scan(offset, bytes, features);
+        }
         Ok(false)
     }
 
@@ -689,10 +716,17 @@ impl WasmTranslator<'_> for FuncTranslator {
 
     fn finish_translate_locals(&mut self) -> Result<(), Error> {
         self.alloc.stack.finish_register_locals();
+        self.encode_coverage_probe()?;
         Ok(())
     }
 
-    fn update_pos(&mut self, _pos: usize) {}
+    #[inline]
+    fn update_pos(&mut self, pos: usize) {
+        self.pos = (pos - self.body_offset) as u32;
+        if self.coverage {
+            self.alloc.instr_encoder.locate_coverage_probes(self.pos);
+        }
+    }
 
     fn finish(
         mut self,
@@ -720,9 +754,16 @@ impl WasmTranslator<'_> for FuncTranslator {
                     costs.fuel_for_copies(u64::from(len_registers))
                 })?;
         }
+        self.check_index_width(len_registers)?;
         let func_consts = self.alloc.stack.func_local_consts();
+        let probes = self.alloc.instr_encoder.take_coverage_probes();
+        #[cfg(feature = "branch-profile")]
+        let branch_sites = self.alloc.instr_encoder.take_branch_sites();
         let instrs = self.alloc.instr_encoder.drain_instrs();
-        finalize(CompiledFuncEntity::new(len_registers, instrs, func_consts));
+        let entity = CompiledFuncEntity::new(len_registers, instrs, func_consts, probes);
+        #[cfg(feature = "branch-profile")]
+        let entity = entity.with_branch_sites(branch_sites);
+        finalize(entity);
         Ok(self.into_allocations())
     }
 }
@@ -754,6 +795,21 @@ impl FuncTranslator {
         func: FuncIdx,
         res: ModuleHeader,
         alloc: FuncTranslatorAllocations,
+    ) -> Result<Self, Error> {
+        Self::with_fuel_costs(func, res, alloc, None)
+    }
+
+    /// Creates a new [`FuncTranslator`] charging fuel according to `fuel_costs`.
+    ///
+    /// Uses the fuel costs of the [`Engine`]'s [`Config`] if `fuel_costs` is `None`.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Config`]: crate::Config
+    pub fn with_fuel_costs(
+        func: FuncIdx,
+        res: ModuleHeader,
+        alloc: FuncTranslatorAllocations,
+        fuel_costs: Option<FuelCosts>,
     ) -> Result<Self, Error> {
         let Some(engine) = res.engine().upgrade() else {
             panic!(
@@ -764,19 +820,57 @@ impl FuncTranslator {
         let config = engine.config();
         let fuel_costs = config
             .get_consume_fuel()
-            .then(|| config.fuel_costs())
-            .copied();
+            .then(|| fuel_costs.unwrap_or(*config.fuel_costs()));
+        let coverage = config.get_code_coverage();
+        let hoist_loop_invariants = config.get_hoist_loop_invariants();
+        let index_width = config.get_index_width();
         Self {
             func,
             engine,
             module: res,
             reachable: true,
             fuel_costs,
+            coverage,
+            hoist_loop_invariants,
+            index_width,
+            body_offset: 0,
+            pos: 0,
             alloc,
         }
         .init()
     }
 
+    /// Checks that the compiled function fits into the configured [`IndexWidth`].
+    ///
+    /// # Errors
+    ///
+    /// If the compiled function uses too many registers, function local constants
+    /// or instructions for the [`IndexWidth`].
+    fn check_index_width(&self, len_registers: u16) -> Result<(), Error> {
+        let width = self.index_width;
+        let len_consts = self.alloc.stack.len_consts();
+        let len_instrs = self.alloc.instr_encoder.len_instrs();
+        if width
+            .max_consts()
+            .is_some_and(|max_consts| len_consts > max_consts)
+        {
+            return Err(Error::from(TranslationError::TooManyFuncLocalConstValues));
+        }
+        if width
+            .max_registers()
+            .is_some_and(|max_registers| len_registers - len_consts > max_registers)
+        {
+            return Err(Error::from(TranslationError::AllocatedTooManyRegisters));
+        }
+        if width
+            .max_instrs()
+            .is_some_and(|max_instrs| len_instrs > max_instrs)
+        {
+            return Err(Error::from(TranslationError::BranchOffsetOutOfBounds));
+        }
+        Ok(())
+    }
+
     /// Returns the [`Engine`] for which the function is compiled.
     fn engine(&self) -> &Engine {
         &self.engine
@@ -922,12 +1016,116 @@ impl FuncTranslator {
         self.alloc.instr_encoder.push_instr(instr)
     }
 
+    /// Hoists the loop-invariant computations of the Wasm `loop` at the current position.
+    ///
+    /// # Note
+    ///
+    /// This must be called before the `loop_header` is pinned so that the hoisted
+    /// computations are executed once upon entering the Wasm `loop` instead of once
+    /// per loop iteration.
+    fn hoist_loop_invariants(&mut self, loop_header: LabelRef) -> Result<(), Error> {
+        if !self.hoist_loop_invariants {
+            return Ok(());
+        }
+        while let Some(invariant) = self.alloc.loop_invariants.next(self.pos) {
+            let (local, value) = (invariant.local(), invariant.value());
+            if self.alloc.loop_invariants.is_full()
+                || self.alloc.loop_invariants.get(local, value).is_some()
+            {
+                // Note: either the register budget for hoisting is exhausted or
+                //       an enclosing `loop` already hoisted the same computation.
+                continue;
+            }
+            let result = self.alloc.stack.alloc_preserved()?;
+            let instr = match value.ty() {
+                ValType::I32 => self.make_add_imm_instr(
+                    result,
+                    local,
+                    i32::from(value),
+                    Instruction::i32_add,
+                    Instruction::i32_add_imm16,
+                )?,
+                ValType::I64 => self.make_add_imm_instr(
+                    result,
+                    local,
+                    i64::from(value),
+                    Instruction::i64_add,
+                    Instruction::i64_add_imm16,
+                )?,
+                ty => panic!("encountered invalid loop invariant type: {ty:?}"),
+            };
+            let instr = self.push_fueled_instr(instr, FuelCosts::base)?;
+            self.alloc.instr_encoder.notify_preserved_register(instr);
+            self.alloc
+                .loop_invariants
+                .push_hoisted(loop_header, invariant, result);
+        }
+        Ok(())
+    }
+
+    /// Creates an `add` [`Instruction`] computing `lhs + rhs` into `result`.
+    fn make_add_imm_instr<T>(
+        &mut self,
+        result: Reg,
+        lhs: Reg,
+        rhs: T,
+        make_instr: fn(result: Reg, lhs: Reg, rhs: Reg) -> Instruction,
+        make_instr_imm16: fn(result: Reg, lhs: Reg, rhs: Const16<T>) -> Instruction,
+    ) -> Result<Instruction, Error>
+    where
+        T: Copy + TryInto<Const16<T>> + Into<UntypedVal>,
+    {
+        if let Ok(rhs) = rhs.try_into() {
+            return Ok(make_instr_imm16(result, lhs, rhs));
+        }
+        let rhs = self.alloc.stack.alloc_const(rhs)?;
+        Ok(make_instr(result, lhs, rhs))
+    }
+
+    /// Releases the registers of all computations hoisted out of the Wasm `loop` at `loop_header`.
+    fn release_loop_invariants(&mut self, loop_header: LabelRef) {
+        while let Some(hoisted) = self.alloc.loop_invariants.pop_hoisted(loop_header) {
+            self.alloc.stack.dec_register_usage(hoisted);
+        }
+    }
+
+    /// Pushes the register of the hoisted `reg + value` computation if any.
+    ///
+    /// Returns `true` if such a hoisted loop-invariant computation exists.
+    fn try_push_loop_invariant(&mut self, reg: Reg, value: TypedVal) -> Result<bool, Error> {
+        let Some(hoisted) = self.alloc.loop_invariants.get(reg, value) else {
+            return Ok(false);
+        };
+        self.alloc.stack.push_register(hoisted)?;
+        Ok(true)
+    }
+
     /// Convenience method for appending an [`Instruction`] parameter.
     fn append_instr(&mut self, instr: Instruction) -> Result<(), Error> {
         self.alloc.instr_encoder.append_instr(instr)?;
         Ok(())
     }
 
+    /// Records the last encoded conditional branch for the branch profile.
+    ///
+    /// `negated` is `true` if the encoded branch is taken if the Wasm branch is not taken.
+    #[cfg(feature = "branch-profile")]
+    fn record_branch_site(&mut self, negated: bool) -> Result<(), Error> {
+        self.alloc
+            .instr_encoder
+            .record_branch_site(self.pos, negated)
+    }
+
+    /// Encodes a coverage probe for the basic block starting at the current position.
+    ///
+    /// Does nothing if code coverage is disabled or the current position is unreachable.
+    fn encode_coverage_probe(&mut self) -> Result<(), Error> {
+        if !self.coverage || !self.is_reachable() {
+            return Ok(());
+        }
+        self.alloc.instr_encoder.encode_coverage_probe()
+    }
+
     /// Utility function for pushing a new [`Instruction`] with basic fuel costs.
     ///
     /// # Note
@@ -1021,6 +1219,26 @@ impl FuncTranslator {
         self.alloc
             .stack
             .pop_n(usize::from(branch_params.len()), params);
+        if self.alloc.instr_encoder.try_relink_call_results(
+            &self.alloc.stack,
+            &self.module,
+            branch_params,
+            &self.alloc.buffer.providers[..],
+        )? {
+            // Case: the results of the preceding call are written directly into the branch params.
+            return Ok(());
+        }
+        if let [value] = self.alloc.buffer.providers[..] {
+            if self.alloc.instr_encoder.try_relink_result(
+                &self.alloc.stack,
+                &self.module,
+                branch_params.span().head(),
+                value,
+            )? {
+                // Case: the result of the preceding instruction is written directly into the branch param.
+                return Ok(());
+            }
+        }
         self.alloc.instr_encoder.encode_copies(
             &mut self.alloc.stack,
             branch_params,
@@ -1079,11 +1297,12 @@ impl FuncTranslator {
     }
 
     /// Translates the `end` of a Wasm `loop` control frame.
-    fn translate_end_loop(&mut self, _frame: LoopControlFrame) -> Result<(), Error> {
+    fn translate_end_loop(&mut self, frame: LoopControlFrame) -> Result<(), Error> {
         debug_assert!(
             !self.alloc.control_stack.is_empty(),
             "control stack must not be empty since its first element is always a `block`"
         );
+        self.release_loop_invariants(frame.branch_destination());
         // # Note
         //
         // There is no need to copy the top of the stack over
@@ -1585,6 +1804,7 @@ impl FuncTranslator {
     ///
     /// - `{f32, f64}.{sub, div}`
     #[allow(clippy::too_many_arguments)]
+    #[cfg(not(feature = "no-float"))]
     fn translate_fbinary<T, R>(
         &mut self,
         make_instr: fn(result: Reg, lhs: Reg, rhs: Reg) -> Instruction,
@@ -1642,6 +1862,7 @@ impl FuncTranslator {
     ///
     /// - This applies several optimization that are valid for copysign instructions.
     /// - Applies constant evaluation if both operands are constant values.
+    #[cfg(not(feature = "no-float"))]
     fn translate_fcopysign<T>(
         &mut self,
         make_instr: fn(result: Reg, lhs: Reg, rhs: Reg) -> Instruction,
@@ -1757,6 +1978,7 @@ impl FuncTranslator {
     ///
     /// - `{f32, f64}.{add, mul, min, max}`
     #[allow(clippy::too_many_arguments)]
+    #[cfg(not(feature = "no-float"))]
     fn translate_fbinary_commutative<T, R>(
         &mut self,
         make_instr: fn(result: Reg, lhs: Reg, rhs: Reg) -> Instruction,
@@ -1970,6 +2192,7 @@ impl FuncTranslator {
     }
 
     /// Translates a fallible unary Wasm instruction to Wasmi bytecode.
+    #[cfg(not(feature = "no-float"))]
     fn translate_unary_fallible<T, R>(
         &mut self,
         make_instr: fn(result: Reg, input: Reg) -> Instruction,
@@ -2353,6 +2576,7 @@ impl FuncTranslator {
     /// Used for translating the following Wasm operators to Wasmi bytecode:
     ///
     /// - `{f32, f64, v128}.store`
+    #[cfg(not(feature = "no-float"))]
     fn translate_store(
         &mut self,
         memarg: MemArg,
@@ -2401,6 +2625,7 @@ impl FuncTranslator {
     /// # Note
     ///
     /// This is used in cases where the `ptr` is a known constant value.
+    #[cfg(not(feature = "no-float"))]
     fn translate_fstore_at(
         &mut self,
         memory: index::Memory,
@@ -2493,7 +2718,10 @@ impl FuncTranslator {
         match type_infer {
             ValType::I32 | ValType::F32 => self.translate_select_32(result, condition, lhs, rhs),
             ValType::I64 => self.translate_select_i64(result, condition, lhs, rhs),
+            #[cfg(not(feature = "no-float"))]
             ValType::F64 => self.translate_select_f64(result, condition, lhs, rhs),
+            #[cfg(feature = "no-float")]
+            ValType::F64 => self.translate_select_generic(result, condition, lhs, rhs),
             ValType::V128 | ValType::FuncRef | ValType::ExternRef => {
                 self.translate_select_generic(result, condition, lhs, rhs)
             }
@@ -2597,6 +2825,7 @@ impl FuncTranslator {
         Ok(())
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn translate_select_f64(
         &mut self,
         result: Reg,
@@ -2661,6 +2890,7 @@ impl FuncTranslator {
     }
 
     /// Translates a Wasm `reinterpret` instruction.
+    #[cfg(not(feature = "no-float"))]
     fn translate_reinterpret(&mut self, ty: ValType) -> Result<(), Error> {
         bail_unreachable!(self);
         if let TypedProvider::Register(_) = self.alloc.stack.peek() {
@@ -2728,7 +2958,10 @@ impl FuncTranslator {
             condition,
             values,
             fuel_info,
-        )
+        )?;
+        #[cfg(feature = "branch-profile")]
+        self.record_branch_site(false)?;
+        Ok(())
     }
 
     /// Create either [`Instruction::CallIndirectParams`] or [`Instruction::CallIndirectParamsImm16`] depending on the inputs.
@@ -2764,6 +2997,93 @@ impl FuncTranslator {
         }
     }
 
+    /// Translates a Wasm `br_if` instruction with its `relative_depth`.
+    fn translate_br_if(&mut self, relative_depth: u32) -> Result<(), Error> {
+        let engine = self.engine().clone();
+        let condition = match self.alloc.stack.pop() {
+            Provider::Const(condition) => {
+                if i32::from(condition) != 0 {
+                    // Case: `condition != 0` so the branch is always taken.
+                    // Therefore we can simplify the `br_if` to a `br` instruction.
+                    self.translate_br(relative_depth)?;
+                }
+                return Ok(());
+            }
+            Provider::Register(condition) => condition,
+        };
+        let fuel_info = self.fuel_info();
+        let frame = match self.alloc.control_stack.acquire_target(relative_depth) {
+            AcquiredTarget::Return(_frame) => return self.translate_return_if(condition),
+            AcquiredTarget::Branch(frame) => frame,
+        };
+        frame.bump_branches();
+        let branch_dst = frame.branch_destination();
+        let branch_params = frame.branch_params(&engine);
+        if branch_params.is_empty() {
+            // Case: no values need to be copied so we can directly
+            //       encode the `br_if` as efficient `branch_nez`.
+            self.alloc.instr_encoder.encode_branch_nez(
+                &mut self.alloc.stack,
+                condition,
+                branch_dst,
+            )?;
+            #[cfg(feature = "branch-profile")]
+            self.record_branch_site(false)?;
+            return Ok(());
+        }
+        self.alloc.stack.peek_n(
+            usize::from(branch_params.len()),
+            &mut self.alloc.buffer.providers,
+        );
+        if self
+            .alloc
+            .buffer
+            .providers
+            .iter()
+            .copied()
+            .eq(branch_params.iter().map(TypedProvider::Register))
+        {
+            // Case: the providers on the stack are already as
+            //       expected by the branch params and therefore
+            //       no copies are required.
+            //
+            // This means we can encode the `br_if` as efficient `branch_nez`.
+            self.alloc.instr_encoder.encode_branch_nez(
+                &mut self.alloc.stack,
+                condition,
+                branch_dst,
+            )?;
+            #[cfg(feature = "branch-profile")]
+            self.record_branch_site(false)?;
+            return Ok(());
+        }
+        // Case: We need to copy the branch inputs to where the
+        //       control frame expects them before actually branching
+        //       to it.
+        //       We do this by performing a negated `br_eqz` and skip
+        //       the copy process with it in cases where no branch is
+        //       needed.
+        //       Otherwise we copy the values to their expected locations
+        //       and finally perform the actual branch to the target
+        //       control frame.
+        let skip_label = self.alloc.instr_encoder.new_label();
+        self.alloc
+            .instr_encoder
+            .encode_branch_eqz(&mut self.alloc.stack, condition, skip_label)?;
+        #[cfg(feature = "branch-profile")]
+        self.record_branch_site(true)?;
+        self.alloc.instr_encoder.encode_copies(
+            &mut self.alloc.stack,
+            branch_params,
+            &self.alloc.buffer.providers[..],
+            fuel_info,
+        )?;
+        let branch_offset = self.alloc.instr_encoder.try_resolve_label(branch_dst)?;
+        self.push_base_instr(Instruction::branch(branch_offset))?;
+        self.alloc.instr_encoder.pin_label(skip_label);
+        Ok(())
+    }
+
     /// Populate the `buffer` with the `table` targets including the `table` default target.
     ///
     /// Returns a shared slice to the `buffer` after it has been filled.
@@ -2920,6 +3240,7 @@ impl FuncTranslator {
                         Instruction::register(register)
                     }
                 },
+                #[cfg(not(feature = "no-float"))]
                 ValType::F64 => match <Const32<f64>>::try_from(f64::from(immediate)) {
                     Ok(value) => Instruction::f64const32(value),
                     Err(_) => {
@@ -2927,6 +3248,11 @@ impl FuncTranslator {
                         Instruction::register(register)
                     }
                 },
+                #[cfg(feature = "no-float")]
+                ValType::F64 => {
+                    let register = self.alloc.stack.provider2reg(&value)?;
+                    Instruction::register(register)
+                }
                 ValType::V128 | ValType::ExternRef | ValType::FuncRef => {
                     let register = self.alloc.stack.provider2reg(&value)?;
                     Instruction::register(register)
@@ -1,6 +1,6 @@
 use crate::{
     engine::EngineFunc,
-    ir::{index, Instruction, Reg, RegSpan, VisitRegs},
+    ir::{index, BoundedRegSpan, Instruction, Reg, RegSpan, VisitRegs},
     module::ModuleHeader,
     Engine,
     Error,
@@ -22,6 +22,19 @@ pub trait RelinkResult {
         new_result: Reg,
         old_result: Reg,
     ) -> Result<bool, Error>;
+
+    /// Relinks the `results` of a call instruction `self` to `new_results` if they are equal to `old_results`.
+    ///
+    /// # Note
+    ///
+    /// - Only relinks if the called function returns exactly `old_results.len()` values.
+    /// - Returns `false` if `self` is not a call instruction.
+    fn relink_call_results(
+        &mut self,
+        module: &ModuleHeader,
+        new_results: RegSpan,
+        old_results: BoundedRegSpan,
+    ) -> Result<bool, Error>;
 }
 
 /// Visitor to implement [`RelinkResult`] for [`Instruction`].
@@ -102,6 +115,36 @@ impl RelinkResult for Instruction {
             }
         }
     }
+
+    fn relink_call_results(
+        &mut self,
+        module: &ModuleHeader,
+        new_results: RegSpan,
+        old_results: BoundedRegSpan,
+    ) -> Result<bool, Error> {
+        let (results, len_results) = match self {
+            Self::CallInternal0 { results, func } | Self::CallInternal { results, func } => {
+                let len_results = len_results_internal(EngineFunc::from(*func), module);
+                (results, len_results)
+            }
+            Self::CallImported0 { results, func } | Self::CallImported { results, func } => {
+                (results, len_results_imported(*func, module))
+            }
+            Self::CallIndirect0 { results, func_type }
+            | Self::CallIndirect0Imm16 { results, func_type }
+            | Self::CallIndirect { results, func_type }
+            | Self::CallIndirectImm16 { results, func_type } => {
+                (results, len_results_indirect(*func_type, module))
+            }
+            _ => return Ok(false),
+        };
+        if *results != old_results.span() || len_results != usize::from(old_results.len()) {
+            // Note: This is a safeguard to prevent miscompilations.
+            return Ok(false);
+        }
+        *results = new_results;
+        Ok(true)
+    }
 }
 
 fn relink_simple(result: &mut Reg, new_result: Reg, old_result: Reg) -> Result<bool, Error> {
@@ -130,13 +173,7 @@ fn relink_call_internal(
     new_result: Reg,
     old_result: Reg,
 ) -> Result<bool, Error> {
-    let Some(module_func) = module.get_func_index(func) else {
-        panic!("missing module func for compiled func: {func:?}")
-    };
-    let engine = get_engine(module);
-    let func_type = module.get_type_of_func(module_func);
-    let len_results = engine.resolve_func_type(func_type, FuncType::len_results);
-    if len_results != 1 {
+    if len_results_internal(func, module) != 1 {
         return Ok(false);
     }
     relink_simple(results.head_mut(), new_result, old_result)
@@ -149,11 +186,7 @@ fn relink_call_imported(
     new_result: Reg,
     old_result: Reg,
 ) -> Result<bool, Error> {
-    let engine = get_engine(module);
-    let func_idx = u32::from(func).into();
-    let func_type = module.get_type_of_func(func_idx);
-    let len_results = engine.resolve_func_type(func_type, |func_type| func_type.results().len());
-    if len_results != 1 {
+    if len_results_imported(func, module) != 1 {
         return Ok(false);
     }
     relink_simple(results.head_mut(), new_result, old_result)
@@ -166,12 +199,34 @@ fn relink_call_indirect(
     new_result: Reg,
     old_result: Reg,
 ) -> Result<bool, Error> {
-    let engine = get_engine(module);
-    let func_type_idx = u32::from(func_type).into();
-    let func_type = module.get_func_type(func_type_idx);
-    let len_results = engine.resolve_func_type(func_type, |func_type| func_type.results().len());
-    if len_results != 1 {
+    if len_results_indirect(func_type, module) != 1 {
         return Ok(false);
     }
     relink_simple(results.head_mut(), new_result, old_result)
 }
+
+/// Returns the number of results of the internal `func`.
+fn len_results_internal(func: EngineFunc, module: &ModuleHeader) -> usize {
+    let Some(module_func) = module.get_func_index(func) else {
+        panic!("missing module func for compiled func: {func:?}")
+    };
+    let engine = get_engine(module);
+    let func_type = module.get_type_of_func(module_func);
+    usize::from(engine.resolve_func_type(func_type, FuncType::len_results))
+}
+
+/// Returns the number of results of the imported `func`.
+fn len_results_imported(func: index::Func, module: &ModuleHeader) -> usize {
+    let engine = get_engine(module);
+    let func_idx = u32::from(func).into();
+    let func_type = module.get_type_of_func(func_idx);
+    engine.resolve_func_type(func_type, |func_type| func_type.results().len())
+}
+
+/// Returns the number of results of functions with `func_type`.
+fn len_results_indirect(func_type: index::FuncType, module: &ModuleHeader) -> usize {
+    let engine = get_engine(module);
+    let func_type_idx = u32::from(func_type).into();
+    let func_type = module.get_func_type(func_type_idx);
+    engine.resolve_func_type(func_type, |func_type| func_type.results().len())
+}
@@ -68,12 +68,12 @@ impl FuncLocalConsts {
     ///
     /// If too many constant values have been allocated for this [`FuncLocalConsts`].
     pub fn alloc(&mut self, value: UntypedVal) -> Result<Reg, Error> {
-        if self.next_idx == Self::last_index() {
-            return Err(Error::from(TranslationError::TooManyFuncLocalConstValues));
-        }
         match self.const2idx.entry(value) {
             btree_map::Entry::Occupied(entry) => Ok(*entry.get()),
             btree_map::Entry::Vacant(entry) => {
+                if self.next_idx == Self::last_index() {
+                    return Err(Error::from(TranslationError::TooManyFuncLocalConstValues));
+                }
                 let register = Reg::from(self.next_idx);
                 self.next_idx -= 1;
                 entry.insert(register);
@@ -110,6 +110,19 @@ impl ValueStack {
         self.reg_alloc.gc_preservations()
     }
 
+    /// Allocates a new [`Reg`] on the preservation register space.
+    ///
+    /// # Note
+    ///
+    /// The returned [`Reg`] must be released via [`ValueStack::dec_register_usage`].
+    ///
+    /// # Errors
+    ///
+    /// If too many registers have been registered.
+    pub fn alloc_preserved(&mut self) -> Result<Reg, Error> {
+        self.reg_alloc.push_preserved()
+    }
+
     /// Returns the number of [`Provider`] on the [`ValueStack`].
     ///
     /// # Note
@@ -125,6 +138,11 @@ impl ValueStack {
         self.consts.len_consts() + self.reg_alloc.len_registers()
     }
 
+    /// Returns the number of function local constant values of the [`ValueStack`].
+    pub fn len_consts(&self) -> u16 {
+        self.consts.len_consts()
+    }
+
     /// Registers an `amount` of function inputs or local variables.
     ///
     /// # Errors
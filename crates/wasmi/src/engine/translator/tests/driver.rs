@@ -188,6 +188,11 @@ impl TranslationTest {
         &self.config
     }
 
+    /// Returns an exclusive reference to the [`Config`] used for the test case.
+    pub fn config_mut(&mut self) -> &mut Config {
+        &mut self.config
+    }
+
     /// Returns the WebAssembly bytes used for the test case.
     fn wasm(&self) -> &[u8] {
         &self.wasm
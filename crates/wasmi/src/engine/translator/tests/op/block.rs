@@ -1,6 +1,6 @@
 use super::*;
 use crate::{
-    engine::translator::tests::wasm_type::WasmTy,
+    engine::{translator::tests::wasm_type::WasmTy, EngineFunc},
     ir::{BranchOffset, BranchOffset16, RegSpan},
 };
 use std::fmt::Display;
@@ -317,6 +317,61 @@ fn branched_block_2() {
         .run()
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branched_block_call_results() {
+    let wasm = r"
+        (module
+            (func $f (param i32) (result i32 i32 i32)
+                (local.get 0)
+                (local.get 0)
+                (local.get 0)
+            )
+            (func (param i32) (result i32 i32 i32 i32)
+                (i32.add (local.get 0) (i32.const 1))
+                (block (result i32 i32 i32)
+                    (i32.add (local.get 0) (i32.const 2))
+                    (call $f (local.get 0))
+                    (br 0)
+                )
+            )
+        )";
+    TranslationTest::new(wasm)
+        .expect_func_instrs([Instruction::return_reg3_ext(0, 0, 0)])
+        .expect_func_instrs([
+            Instruction::i32_add_imm16(Reg::from(1), Reg::from(0), 1),
+            Instruction::i32_add_imm16(Reg::from(2), Reg::from(0), 2),
+            Instruction::call_internal(RegSpan::new(Reg::from(2)), EngineFunc::from_u32(0)),
+            Instruction::register(0),
+            Instruction::branch(BranchOffset::from(1)),
+            Instruction::return_span(bspan(1, 4)),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branched_block_result() {
+    let wasm = r"
+        (module
+            (func (param i32 i32) (result i32)
+                (block (result i32)
+                    (i32.popcnt (local.get 0))
+                    (i32.add (local.get 0) (local.get 1))
+                    (br 0)
+                )
+            )
+        )";
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_popcnt(Reg::from(2), Reg::from(0)),
+            Instruction::i32_add(Reg::from(2), Reg::from(0), Reg::from(1)),
+            Instruction::branch(BranchOffset::from(1)),
+            Instruction::return_reg(Reg::from(2)),
+        ])
+        .run()
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn branch_if_block_0() {
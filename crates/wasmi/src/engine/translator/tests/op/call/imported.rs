@@ -211,9 +211,7 @@ fn params7_reg() {
     TranslationTest::new(wasm)
         .expect_func_instrs([
             Instruction::call_imported(RegSpan::new(Reg::from(7)), Func::from(0)),
-            Instruction::register_list_ext(0, 1, 2),
-            Instruction::register_list_ext(3, 4, 5),
-            Instruction::register(6),
+            Instruction::register_span(bspan(0, 7)),
             Instruction::return_span(bspan(7, 7)),
         ])
         .run();
@@ -305,9 +303,7 @@ fn params8_reg() {
     TranslationTest::new(wasm)
         .expect_func_instrs([
             Instruction::call_imported(RegSpan::new(Reg::from(8)), Func::from(0)),
-            Instruction::register_list_ext(0, 1, 2),
-            Instruction::register_list_ext(3, 4, 5),
-            Instruction::register2_ext(6, 7),
+            Instruction::register_span(bspan(0, 8)),
             Instruction::return_span(bspan(8, 8)),
         ])
         .run();
@@ -402,9 +398,7 @@ fn params9_reg() {
     TranslationTest::new(wasm)
         .expect_func_instrs([
             Instruction::call_imported(RegSpan::new(Reg::from(9)), Func::from(0)),
-            Instruction::register_list_ext(0, 1, 2),
-            Instruction::register_list_ext(3, 4, 5),
-            Instruction::register3_ext(6, 7, 8),
+            Instruction::register_span(bspan(0, 9)),
             Instruction::return_span(bspan(9, 9)),
         ])
         .run();
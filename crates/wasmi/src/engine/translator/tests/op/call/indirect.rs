@@ -603,9 +603,7 @@ fn params7_reg_index_local() {
         .expect_func_instrs([
             Instruction::call_indirect(RegSpan::new(Reg::from(8)), FuncType::from(0)),
             Instruction::call_indirect_params(Reg::from(0), Table::from(0)),
-            Instruction::register_list_ext(1, 2, 3),
-            Instruction::register_list_ext(4, 5, 6),
-            Instruction::register(7),
+            Instruction::register_span(bspan(1, 7)),
             Instruction::return_span(bspan(8, 7)),
         ])
         .run();
@@ -671,9 +669,7 @@ fn params8_reg_index_local() {
         .expect_func_instrs([
             Instruction::call_indirect(RegSpan::new(Reg::from(9)), FuncType::from(0)),
             Instruction::call_indirect_params(Reg::from(0), Table::from(0)),
-            Instruction::register_list_ext(1, 2, 3),
-            Instruction::register_list_ext(4, 5, 6),
-            Instruction::register2_ext(7, 8),
+            Instruction::register_span(bspan(1, 8)),
             Instruction::return_span(bspan(9, 8)),
         ])
         .run();
@@ -741,9 +737,7 @@ fn params9_reg_index_local() {
         .expect_func_instrs([
             Instruction::call_indirect(RegSpan::new(Reg::from(10)), FuncType::from(0)),
             Instruction::call_indirect_params(Reg::from(0), Table::from(0)),
-            Instruction::register_list_ext(1, 2, 3),
-            Instruction::register_list_ext(4, 5, 6),
-            Instruction::register3_ext(7, 8, 9),
+            Instruction::register_span(bspan(1, 9)),
             Instruction::return_span(bspan(10, 9)),
         ])
         .run();
@@ -254,9 +254,7 @@ fn params7_reg() {
         .expect_func_instrs([Instruction::return_span(bspan(0, 7))])
         .expect_func_instrs([
             Instruction::call_internal(RegSpan::new(Reg::from(7)), EngineFunc::from_u32(0)),
-            Instruction::register_list_ext(0, 1, 2),
-            Instruction::register_list_ext(3, 4, 5),
-            Instruction::register(6),
+            Instruction::register_span(bspan(0, 7)),
             Instruction::return_span(bspan(7, 7)),
         ])
         .run();
@@ -376,9 +374,7 @@ fn params8_reg() {
         .expect_func_instrs([Instruction::return_span(bspan(0, 8))])
         .expect_func_instrs([
             Instruction::call_internal(RegSpan::new(Reg::from(8)), EngineFunc::from_u32(0)),
-            Instruction::register_list_ext(0, 1, 2),
-            Instruction::register_list_ext(3, 4, 5),
-            Instruction::register2_ext(6, 7),
+            Instruction::register_span(bspan(0, 8)),
             Instruction::return_span(bspan(8, 8)),
         ])
         .run();
@@ -504,9 +500,7 @@ fn params9_reg() {
         .expect_func_instrs([Instruction::return_span(bspan(0, 9))])
         .expect_func_instrs([
             Instruction::call_internal(RegSpan::new(Reg::from(9)), EngineFunc::from_u32(0)),
-            Instruction::register_list_ext(0, 1, 2),
-            Instruction::register_list_ext(3, 4, 5),
-            Instruction::register3_ext(6, 7, 8),
+            Instruction::register_span(bspan(0, 9)),
             Instruction::return_span(bspan(9, 9)),
         ])
         .run();
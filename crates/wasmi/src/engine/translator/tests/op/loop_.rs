@@ -1,5 +1,5 @@
 use super::*;
-use crate::ir::{BranchOffset, RegSpan};
+use crate::ir::{BranchOffset, BranchOffset16, RegSpan};
 
 #[test]
 #[cfg_attr(miri, ignore)]
@@ -216,3 +216,108 @@ fn identity_loop_4_mixed_2() {
         ])
         .run()
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn hoist_loop_invariant_address() {
+    let wasm = r"
+        (module
+            (memory 1)
+            (func (param $base i32) (param $n i32) (result i32)
+                (local $sum i32)
+                (loop $continue
+                    (local.set $sum
+                        (i32.add
+                            (local.get $sum)
+                            (i32.load (i32.add (local.get $base) (i32.const 8)))
+                        )
+                    )
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br_if $continue (local.get $n))
+                )
+                (local.get $sum)
+            )
+        )";
+    let mut test = TranslationTest::new(wasm);
+    test.config_mut().hoist_loop_invariants(true);
+    test.expect_func_instrs([
+        Instruction::i32_add_imm16(Reg::from(4), Reg::from(0), 8),
+        Instruction::load32_offset16(Reg::from(3), Reg::from(4), offset16(0)),
+        Instruction::i32_add(Reg::from(2), Reg::from(2), Reg::from(3)),
+        Instruction::i32_add_imm16(Reg::from(1), Reg::from(1), -1),
+        Instruction::branch_i32_ne_imm16(Reg::from(1), 0, BranchOffset16::from(-3)),
+        Instruction::return_reg(Reg::from(2)),
+    ])
+    .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn hoist_loop_invariant_i64_const() {
+    let wasm = r"
+        (module
+            (func (param $x i64) (param $n i32) (result i64)
+                (local $sum i64)
+                (loop $continue
+                    (local.set $sum
+                        (i64.xor
+                            (local.get $sum)
+                            (i64.add (local.get $x) (i64.const 100000))
+                        )
+                    )
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br_if $continue (local.get $n))
+                )
+                (local.get $sum)
+            )
+        )";
+    let mut test = TranslationTest::new(wasm);
+    test.config_mut().hoist_loop_invariants(true);
+    test.expect_func(
+        ExpectedFunc::new([
+            Instruction::i64_add(Reg::from(4), Reg::from(0), Reg::from(-1)),
+            Instruction::i64_xor(Reg::from(2), Reg::from(2), Reg::from(4)),
+            Instruction::i32_add_imm16(Reg::from(1), Reg::from(1), -1),
+            Instruction::branch_i32_ne_imm16(Reg::from(1), 0, BranchOffset16::from(-2)),
+            Instruction::return_reg(Reg::from(2)),
+        ])
+        .consts([100_000_i64]),
+    )
+    .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_hoist_loop_variant() {
+    let wasm = r"
+        (module
+            (memory 1)
+            (func (param $ptr i32) (param $n i32) (result i32)
+                (local $sum i32)
+                (loop $continue
+                    (local.set $sum
+                        (i32.add
+                            (local.get $sum)
+                            (i32.load (i32.add (local.get $ptr) (i32.const 8)))
+                        )
+                    )
+                    (local.set $ptr (i32.add (local.get $ptr) (i32.const 4)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br_if $continue (local.get $n))
+                )
+                (local.get $sum)
+            )
+        )";
+    let mut test = TranslationTest::new(wasm);
+    test.config_mut().hoist_loop_invariants(true);
+    test.expect_func_instrs([
+        Instruction::i32_add_imm16(Reg::from(3), Reg::from(0), 8),
+        Instruction::load32_offset16(Reg::from(3), Reg::from(3), offset16(0)),
+        Instruction::i32_add(Reg::from(2), Reg::from(2), Reg::from(3)),
+        Instruction::i32_add_imm16(Reg::from(0), Reg::from(0), 4),
+        Instruction::i32_add_imm16(Reg::from(1), Reg::from(1), -1),
+        Instruction::branch_i32_ne_imm16(Reg::from(1), 0, BranchOffset16::from(-5)),
+        Instruction::return_reg(Reg::from(2)),
+    ])
+    .run()
+}
@@ -30,6 +30,8 @@ mod ref_;
 mod return_;
 mod return_call;
 mod select;
+#[cfg(feature = "simd")]
+mod simd;
 mod store;
 mod table;
 mod unary;
@@ -200,9 +200,7 @@ fn params7_reg() {
     TranslationTest::new(wasm)
         .expect_func_instrs([
             Instruction::return_call_imported(Func::from(0)),
-            Instruction::register_list_ext(0, 1, 2),
-            Instruction::register_list_ext(3, 4, 5),
-            Instruction::register(6),
+            Instruction::register_span(bspan(0, 7)),
         ])
         .run();
 }
@@ -291,9 +289,7 @@ fn params8_reg() {
     TranslationTest::new(wasm)
         .expect_func_instrs([
             Instruction::return_call_imported(Func::from(0)),
-            Instruction::register_list_ext(0, 1, 2),
-            Instruction::register_list_ext(3, 4, 5),
-            Instruction::register2_ext(6, 7),
+            Instruction::register_span(bspan(0, 8)),
         ])
         .run();
 }
@@ -385,9 +381,7 @@ fn params9_reg() {
     TranslationTest::new(wasm)
         .expect_func_instrs([
             Instruction::return_call_imported(Func::from(0)),
-            Instruction::register_list_ext(0, 1, 2),
-            Instruction::register_list_ext(3, 4, 5),
-            Instruction::register3_ext(6, 7, 8),
+            Instruction::register_span(bspan(0, 9)),
         ])
         .run();
 }
@@ -562,9 +562,7 @@ fn params7_reg_index_local() {
         .expect_func_instrs([
             Instruction::return_call_indirect(FuncType::from(0)),
             Instruction::call_indirect_params(Reg::from(0), Table::from(0)),
-            Instruction::register_list_ext(1, 2, 3),
-            Instruction::register_list_ext(4, 5, 6),
-            Instruction::register(7),
+            Instruction::register_span(bspan(1, 7)),
         ])
         .run();
 }
@@ -628,9 +626,7 @@ fn params8_reg_index_local() {
         .expect_func_instrs([
             Instruction::return_call_indirect(FuncType::from(0)),
             Instruction::call_indirect_params(Reg::from(0), Table::from(0)),
-            Instruction::register_list_ext(1, 2, 3),
-            Instruction::register_list_ext(4, 5, 6),
-            Instruction::register2_ext(7, 8),
+            Instruction::register_span(bspan(1, 8)),
         ])
         .run();
 }
@@ -696,9 +692,7 @@ fn params9_reg_index_local() {
         .expect_func_instrs([
             Instruction::return_call_indirect(FuncType::from(0)),
             Instruction::call_indirect_params(Reg::from(0), Table::from(0)),
-            Instruction::register_list_ext(1, 2, 3),
-            Instruction::register_list_ext(4, 5, 6),
-            Instruction::register3_ext(7, 8, 9),
+            Instruction::register_span(bspan(1, 9)),
         ])
         .run();
 }
@@ -243,9 +243,7 @@ fn params7_reg() {
         .expect_func_instrs([Instruction::return_span(bspan(0, 7))])
         .expect_func_instrs([
             Instruction::return_call_internal(EngineFunc::from_u32(0)),
-            Instruction::register_list_ext(0, 1, 2),
-            Instruction::register_list_ext(3, 4, 5),
-            Instruction::register(6),
+            Instruction::register_span(bspan(0, 7)),
         ])
         .run();
 }
@@ -362,9 +360,7 @@ fn params8_reg() {
         .expect_func_instrs([Instruction::return_span(bspan(0, 8))])
         .expect_func_instrs([
             Instruction::return_call_internal(EngineFunc::from_u32(0)),
-            Instruction::register_list_ext(0, 1, 2),
-            Instruction::register_list_ext(3, 4, 5),
-            Instruction::register2_ext(6, 7),
+            Instruction::register_span(bspan(0, 8)),
         ])
         .run();
 }
@@ -487,9 +483,7 @@ fn params9_reg() {
         .expect_func_instrs([Instruction::return_span(bspan(0, 9))])
         .expect_func_instrs([
             Instruction::return_call_internal(EngineFunc::from_u32(0)),
-            Instruction::register_list_ext(0, 1, 2),
-            Instruction::register_list_ext(3, 4, 5),
-            Instruction::register3_ext(6, 7, 8),
+            Instruction::register_span(bspan(0, 9)),
         ])
         .run();
 }
@@ -0,0 +1,57 @@
+use super::*;
+use crate::core::{simd, V128};
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn v128_const_dedup() {
+    let wasm = r"
+        (module
+            (func (param v128) (result v128)
+                (i8x16.add
+                    (i8x16.add
+                        (i8x16.add (local.get 0) (v128.const i64x2 1 2))
+                        (v128.const i64x2 3 4)
+                    )
+                    (v128.const i64x2 1 2)
+                )
+            )
+        )";
+    TranslationTest::new(wasm)
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::i8x16_add(Reg::from(1), Reg::from(0), Reg::from(-1)),
+                Instruction::i8x16_add(Reg::from(1), Reg::from(1), Reg::from(-2)),
+                Instruction::i8x16_add(Reg::from(1), Reg::from(1), Reg::from(-1)),
+                Instruction::return_reg(Reg::from(1)),
+            ])
+            .consts([
+                V128::from(0x0000_0000_0000_0002_0000_0000_0000_0001),
+                V128::from(0x0000_0000_0000_0004_0000_0000_0000_0003),
+            ]),
+        )
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn splat_const_eval() {
+    let wasm = r"
+        (module
+            (func (param v128) (result v128)
+                (i8x16.add
+                    (i32x4.add (local.get 0) (i32x4.splat (i32.const 7)))
+                    (i8x16.splat (i32.const 0x1FF))
+                )
+            )
+        )";
+    TranslationTest::new(wasm)
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::i32x4_add(Reg::from(1), Reg::from(0), Reg::from(-1)),
+                Instruction::i8x16_add(Reg::from(1), Reg::from(1), Reg::from(-2)),
+                Instruction::return_reg(Reg::from(1)),
+            ])
+            .consts([simd::i32x4_splat(7), simd::i8x16_splat(-1)]),
+        )
+        .run()
+}
@@ -1,10 +1,13 @@
 use super::{stack::ValueStack, Provider, TypedProvider, TypedVal};
 use crate::{
-    ir::{BoundedRegSpan, Const16, Const32, Reg, RegSpan, Sign},
+    ir::{BoundedRegSpan, Const16, Const32, Reg, RegSpan},
     Error,
     IndexType,
 };
 
+#[cfg(not(feature = "no-float"))]
+use crate::ir::Sign;
+
 /// Bail out early in case the current code is unreachable.
 ///
 /// # Note
@@ -63,6 +66,7 @@ impl WasmInteger for u64 {
 /// # Note
 ///
 /// This trait provides some utility methods useful for translation.
+#[cfg(not(feature = "no-float"))]
 pub trait WasmFloat: Copy + Into<TypedVal> + From<TypedVal> {
     /// Returns `true` if `self` is any kind of NaN value.
     fn is_nan(self) -> bool;
@@ -71,6 +75,7 @@ pub trait WasmFloat: Copy + Into<TypedVal> + From<TypedVal> {
     fn sign(self) -> Sign<Self>;
 }
 
+#[cfg(not(feature = "no-float"))]
 impl WasmFloat for f32 {
     fn is_nan(self) -> bool {
         self.is_nan()
@@ -81,6 +86,7 @@ impl WasmFloat for f32 {
     }
 }
 
+#[cfg(not(feature = "no-float"))]
 impl WasmFloat for f64 {
     fn is_nan(self) -> bool {
         self.is_nan()
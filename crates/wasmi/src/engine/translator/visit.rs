@@ -14,12 +14,8 @@ use super::{
     TypedVal,
 };
 use crate::{
-    core::{wasm, TrapCode, ValType, F32, F64},
-    engine::{
-        translator::{AcquiredTarget, Provider},
-        BlockType,
-        FuelCosts,
-    },
+    core::{wasm, TrapCode, ValType},
+    engine::{translator::Provider, BlockType, FuelCosts},
     ir::{
         self,
         index::{self, FuncType},
@@ -37,6 +33,9 @@ use crate::{
 use core::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
 use wasmparser::VisitOperator;
 
+#[cfg(not(feature = "no-float"))]
+use crate::core::{F32, F64};
+
 /// Used to swap operands of binary [`Instruction`] constructor.
 macro_rules! swap_ops {
     ($fn_name:path) => {
@@ -80,6 +79,22 @@ macro_rules! impl_visit_operator {
     () => {};
 }
 
+/// Implements the visit methods of Wasm operators disabled via crate features.
+///
+/// # Note
+///
+/// Wasm validation rejects all disabled Wasm operators, thus these visit methods are never called.
+#[cfg(any(feature = "no-float", feature = "no-bulk-memory"))]
+macro_rules! impl_disabled_visit_operator {
+    ( $( fn $visit:ident($($argty:ty),*); )* ) => {
+        $(
+            fn $visit(&mut self $(, _: $argty)*) -> Self::Output {
+                self.translate_unsupported_operator(stringify!($visit))
+            }
+        )*
+    };
+}
+
 impl<'a> VisitOperator<'a> for FuncTranslator {
     type Output = Result<(), Error>;
 
@@ -92,6 +107,97 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     wasmparser::for_each_visit_operator!(impl_visit_operator);
 
+    #[cfg(feature = "no-float")]
+    impl_disabled_visit_operator! {
+        fn visit_f32_load(wasmparser::MemArg);
+        fn visit_f64_load(wasmparser::MemArg);
+        fn visit_f32_store(wasmparser::MemArg);
+        fn visit_f64_store(wasmparser::MemArg);
+        fn visit_f32_const(wasmparser::Ieee32);
+        fn visit_f64_const(wasmparser::Ieee64);
+        fn visit_f32_eq();
+        fn visit_f32_ne();
+        fn visit_f32_lt();
+        fn visit_f32_gt();
+        fn visit_f32_le();
+        fn visit_f32_ge();
+        fn visit_f64_eq();
+        fn visit_f64_ne();
+        fn visit_f64_lt();
+        fn visit_f64_gt();
+        fn visit_f64_le();
+        fn visit_f64_ge();
+        fn visit_f32_abs();
+        fn visit_f32_neg();
+        fn visit_f32_ceil();
+        fn visit_f32_floor();
+        fn visit_f32_trunc();
+        fn visit_f32_nearest();
+        fn visit_f32_sqrt();
+        fn visit_f32_add();
+        fn visit_f32_sub();
+        fn visit_f32_mul();
+        fn visit_f32_div();
+        fn visit_f32_min();
+        fn visit_f32_max();
+        fn visit_f32_copysign();
+        fn visit_f64_abs();
+        fn visit_f64_neg();
+        fn visit_f64_ceil();
+        fn visit_f64_floor();
+        fn visit_f64_trunc();
+        fn visit_f64_nearest();
+        fn visit_f64_sqrt();
+        fn visit_f64_add();
+        fn visit_f64_sub();
+        fn visit_f64_mul();
+        fn visit_f64_div();
+        fn visit_f64_min();
+        fn visit_f64_max();
+        fn visit_f64_copysign();
+        fn visit_i32_trunc_f32_s();
+        fn visit_i32_trunc_f32_u();
+        fn visit_i32_trunc_f64_s();
+        fn visit_i32_trunc_f64_u();
+        fn visit_i64_trunc_f32_s();
+        fn visit_i64_trunc_f32_u();
+        fn visit_i64_trunc_f64_s();
+        fn visit_i64_trunc_f64_u();
+        fn visit_f32_convert_i32_s();
+        fn visit_f32_convert_i32_u();
+        fn visit_f32_convert_i64_s();
+        fn visit_f32_convert_i64_u();
+        fn visit_f32_demote_f64();
+        fn visit_f64_convert_i32_s();
+        fn visit_f64_convert_i32_u();
+        fn visit_f64_convert_i64_s();
+        fn visit_f64_convert_i64_u();
+        fn visit_f64_promote_f32();
+        fn visit_i32_reinterpret_f32();
+        fn visit_i64_reinterpret_f64();
+        fn visit_f32_reinterpret_i32();
+        fn visit_f64_reinterpret_i64();
+        fn visit_i32_trunc_sat_f32_s();
+        fn visit_i32_trunc_sat_f32_u();
+        fn visit_i32_trunc_sat_f64_s();
+        fn visit_i32_trunc_sat_f64_u();
+        fn visit_i64_trunc_sat_f32_s();
+        fn visit_i64_trunc_sat_f32_u();
+        fn visit_i64_trunc_sat_f64_s();
+        fn visit_i64_trunc_sat_f64_u();
+    }
+
+    #[cfg(feature = "no-bulk-memory")]
+    impl_disabled_visit_operator! {
+        fn visit_memory_init(u32, u32);
+        fn visit_data_drop(u32);
+        fn visit_memory_copy(u32, u32);
+        fn visit_memory_fill(u32);
+        fn visit_table_init(u32, u32);
+        fn visit_elem_drop(u32);
+        fn visit_table_copy(u32, u32);
+    }
+
     fn visit_unreachable(&mut self) -> Self::Output {
         bail_unreachable!(self);
         self.push_base_instr(Instruction::trap(TrapCode::UnreachableCodeReached))?;
@@ -171,10 +277,11 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             &self.alloc.buffer.providers[..],
             fuel_info,
         )?;
-        self.alloc.instr_encoder.reset_last_instr();
-        // Create loop header label and immediately pin it.
+        // Create loop header label and pin it after the hoisted loop-invariant computations.
         let stack_height = BlockHeight::new(self.engine(), self.alloc.stack.height(), block_type)?;
         let header = self.alloc.instr_encoder.new_label();
+        self.hoist_loop_invariants(header)?;
+        self.alloc.instr_encoder.reset_last_instr();
         self.alloc.instr_encoder.pin_label(header);
         // Optionally create the loop's [`Instruction::ConsumeFuel`].
         //
@@ -191,7 +298,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             branch_params,
             consume_fuel,
         ));
-        Ok(())
+        self.encode_coverage_probe()
     }
 
     fn visit_if(&mut self, block_type: wasmparser::BlockType) -> Self::Output {
@@ -267,6 +374,8 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                     condition,
                     else_label,
                 )?;
+                #[cfg(feature = "branch-profile")]
+                self.record_branch_site(true)?;
                 let reachability = IfReachability::both(else_label);
                 // Optionally create the [`Instruction::ConsumeFuel`] for the `then` branch.
                 //
@@ -286,7 +395,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             fuel_instr,
             reachability,
         ));
-        Ok(())
+        self.encode_coverage_probe()
     }
 
     fn visit_else(&mut self) -> Self::Output {
@@ -369,7 +478,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         }
         // At last we need to push the popped and adjusted [`IfControlFrame`] back.
         self.alloc.control_stack.push_frame(frame);
-        Ok(())
+        self.encode_coverage_probe()
     }
 
     fn visit_end(&mut self) -> Self::Output {
@@ -380,7 +489,11 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             ControlFrame::Unreachable(frame) => self.translate_end_unreachable(frame),
         }?;
         self.alloc.instr_encoder.reset_last_instr();
-        Ok(())
+        if self.alloc.control_stack.is_empty() {
+            // Note: there is no code following the `end` of the function body.
+            return Ok(());
+        }
+        self.encode_coverage_probe()
     }
 
     fn visit_br(&mut self, relative_depth: u32) -> Self::Output {
@@ -390,83 +503,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     fn visit_br_if(&mut self, relative_depth: u32) -> Self::Output {
         bail_unreachable!(self);
-        let engine = self.engine().clone();
-        let condition = match self.alloc.stack.pop() {
-            Provider::Const(condition) => {
-                if i32::from(condition) != 0 {
-                    // Case: `condition != 0` so the branch is always taken.
-                    // Therefore we can simplify the `br_if` to a `br` instruction.
-                    self.translate_br(relative_depth)?;
-                }
-                return Ok(());
-            }
-            Provider::Register(condition) => condition,
-        };
-        let fuel_info = self.fuel_info();
-        let frame = match self.alloc.control_stack.acquire_target(relative_depth) {
-            AcquiredTarget::Return(_frame) => return self.translate_return_if(condition),
-            AcquiredTarget::Branch(frame) => frame,
-        };
-        frame.bump_branches();
-        let branch_dst = frame.branch_destination();
-        let branch_params = frame.branch_params(&engine);
-        if branch_params.is_empty() {
-            // Case: no values need to be copied so we can directly
-            //       encode the `br_if` as efficient `branch_nez`.
-            self.alloc.instr_encoder.encode_branch_nez(
-                &mut self.alloc.stack,
-                condition,
-                branch_dst,
-            )?;
-            return Ok(());
-        }
-        self.alloc.stack.peek_n(
-            usize::from(branch_params.len()),
-            &mut self.alloc.buffer.providers,
-        );
-        if self
-            .alloc
-            .buffer
-            .providers
-            .iter()
-            .copied()
-            .eq(branch_params.iter().map(TypedProvider::Register))
-        {
-            // Case: the providers on the stack are already as
-            //       expected by the branch params and therefore
-            //       no copies are required.
-            //
-            // This means we can encode the `br_if` as efficient `branch_nez`.
-            self.alloc.instr_encoder.encode_branch_nez(
-                &mut self.alloc.stack,
-                condition,
-                branch_dst,
-            )?;
-            return Ok(());
-        }
-        // Case: We need to copy the branch inputs to where the
-        //       control frame expects them before actually branching
-        //       to it.
-        //       We do this by performing a negated `br_eqz` and skip
-        //       the copy process with it in cases where no branch is
-        //       needed.
-        //       Otherwise we copy the values to their expected locations
-        //       and finally perform the actual branch to the target
-        //       control frame.
-        let skip_label = self.alloc.instr_encoder.new_label();
-        self.alloc
-            .instr_encoder
-            .encode_branch_eqz(&mut self.alloc.stack, condition, skip_label)?;
-        self.alloc.instr_encoder.encode_copies(
-            &mut self.alloc.stack,
-            branch_params,
-            &self.alloc.buffer.providers[..],
-            fuel_info,
-        )?;
-        let branch_offset = self.alloc.instr_encoder.try_resolve_label(branch_dst)?;
-        self.push_base_instr(Instruction::branch(branch_offset))?;
-        self.alloc.instr_encoder.pin_label(skip_label);
-        Ok(())
+        self.translate_br_if(relative_depth)?;
+        // Note: the code following a `br_if` is a new basic block.
+        self.encode_coverage_probe()
     }
 
     fn visit_br_table(&mut self, targets: wasmparser::BrTable<'a>) -> Self::Output {
@@ -509,7 +548,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.alloc.instr_encoder.push_instr(instr)?;
         self.alloc
             .instr_encoder
-            .encode_register_list(&mut self.alloc.stack, provider_params)?;
+            .encode_call_params(&mut self.alloc.stack, provider_params)?;
         Ok(())
     }
 
@@ -543,7 +582,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.alloc.instr_encoder.append_instr(indirect_params)?;
         self.alloc
             .instr_encoder
-            .encode_register_list(&mut self.alloc.stack, provider_params)?;
+            .encode_call_params(&mut self.alloc.stack, provider_params)?;
         Ok(())
     }
 
@@ -576,7 +615,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.alloc.instr_encoder.push_instr(instr)?;
         self.alloc
             .instr_encoder
-            .encode_register_list(&mut self.alloc.stack, provider_params)?;
+            .encode_call_params(&mut self.alloc.stack, provider_params)?;
         self.reachable = false;
         Ok(())
     }
@@ -610,7 +649,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.alloc.instr_encoder.append_instr(indirect_params)?;
         self.alloc
             .instr_encoder
-            .encode_register_list(&mut self.alloc.stack, provider_params)?;
+            .encode_call_params(&mut self.alloc.stack, provider_params)?;
         self.reachable = false;
         Ok(())
     }
@@ -766,6 +805,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_load(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
         self.translate_load(
             memarg,
@@ -775,6 +815,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_load(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
         self.translate_load(
             memarg,
@@ -898,6 +939,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_store(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
         self.translate_store(
             memarg,
@@ -907,6 +949,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_store(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
         self.translate_store(
             memarg,
@@ -1028,12 +1071,14 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_const(&mut self, value: wasmparser::Ieee32) -> Self::Output {
         bail_unreachable!(self);
         self.alloc.stack.push_const(F32::from_bits(value.bits()));
         Ok(())
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_const(&mut self, value: wasmparser::Ieee64) -> Self::Output {
         bail_unreachable!(self);
         self.alloc.stack.push_const(F64::from_bits(value.bits()));
@@ -1695,6 +1740,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_eq(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f32, bool>(
             Instruction::f32_eq,
@@ -1711,6 +1757,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_ne(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f32, bool>(
             Instruction::f32_ne,
@@ -1727,6 +1774,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_lt(&mut self) -> Self::Output {
         self.translate_fbinary::<f32, bool>(
             Instruction::f32_lt,
@@ -1768,6 +1816,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_gt(&mut self) -> Self::Output {
         self.translate_fbinary::<f32, bool>(
             swap_ops!(Instruction::f32_lt),
@@ -1809,6 +1858,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_le(&mut self) -> Self::Output {
         self.translate_fbinary::<f32, bool>(
             Instruction::f32_le,
@@ -1833,6 +1883,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_ge(&mut self) -> Self::Output {
         self.translate_fbinary::<f32, bool>(
             swap_ops!(Instruction::f32_le),
@@ -1857,6 +1908,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_eq(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f64, bool>(
             Instruction::f64_eq,
@@ -1873,6 +1925,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_ne(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f64, bool>(
             Instruction::f64_ne,
@@ -1889,6 +1942,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_lt(&mut self) -> Self::Output {
         self.translate_fbinary::<f64, bool>(
             Instruction::f64_lt,
@@ -1930,6 +1984,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_gt(&mut self) -> Self::Output {
         self.translate_fbinary::<f64, bool>(
             swap_ops!(Instruction::f64_lt),
@@ -1971,6 +2026,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_le(&mut self) -> Self::Output {
         self.translate_fbinary::<f64, bool>(
             Instruction::f64_le,
@@ -1995,6 +2051,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_ge(&mut self) -> Self::Output {
         self.translate_fbinary::<f64, bool>(
             swap_ops!(Instruction::f64_le),
@@ -2043,7 +2100,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                     this.alloc.stack.push_register(reg)?;
                     return Ok(true);
                 }
-                Ok(false)
+                this.try_push_loop_invariant(reg, TypedVal::from(value))
             },
         )
     }
@@ -2360,7 +2417,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                     this.alloc.stack.push_register(reg)?;
                     return Ok(true);
                 }
-                Ok(false)
+                this.try_push_loop_invariant(reg, TypedVal::from(value))
             },
         )
     }
@@ -2653,34 +2710,42 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_abs(&mut self) -> Self::Output {
         self.translate_unary::<f32, f32>(Instruction::f32_abs, wasm::f32_abs)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_neg(&mut self) -> Self::Output {
         self.translate_unary::<f32, f32>(Instruction::f32_neg, wasm::f32_neg)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_ceil(&mut self) -> Self::Output {
         self.translate_unary::<f32, f32>(Instruction::f32_ceil, wasm::f32_ceil)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_floor(&mut self) -> Self::Output {
         self.translate_unary::<f32, f32>(Instruction::f32_floor, wasm::f32_floor)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_trunc(&mut self) -> Self::Output {
         self.translate_unary::<f32, f32>(Instruction::f32_trunc, wasm::f32_trunc)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_nearest(&mut self) -> Self::Output {
         self.translate_unary::<f32, f32>(Instruction::f32_nearest, wasm::f32_nearest)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_sqrt(&mut self) -> Self::Output {
         self.translate_unary::<f32, f32>(Instruction::f32_sqrt, wasm::f32_sqrt)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_add(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f32, f32>(
             Instruction::f32_add,
@@ -2690,6 +2755,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_sub(&mut self) -> Self::Output {
         self.translate_fbinary::<f32, f32>(
             Instruction::f32_sub,
@@ -2703,6 +2769,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_mul(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f32, f32>(
             Instruction::f32_mul,
@@ -2715,6 +2782,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_div(&mut self) -> Self::Output {
         self.translate_fbinary::<f32, f32>(
             Instruction::f32_div,
@@ -2725,6 +2793,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_min(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f32, f32>(
             Instruction::f32_min,
@@ -2741,6 +2810,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_max(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f32, f32>(
             Instruction::f32_max,
@@ -2757,6 +2827,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_copysign(&mut self) -> Self::Output {
         self.translate_fcopysign::<f32>(
             Instruction::f32_copysign,
@@ -2765,34 +2836,42 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_abs(&mut self) -> Self::Output {
         self.translate_unary::<f64, f64>(Instruction::f64_abs, wasm::f64_abs)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_neg(&mut self) -> Self::Output {
         self.translate_unary::<f64, f64>(Instruction::f64_neg, wasm::f64_neg)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_ceil(&mut self) -> Self::Output {
         self.translate_unary::<f64, f64>(Instruction::f64_ceil, wasm::f64_ceil)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_floor(&mut self) -> Self::Output {
         self.translate_unary::<f64, f64>(Instruction::f64_floor, wasm::f64_floor)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_trunc(&mut self) -> Self::Output {
         self.translate_unary::<f64, f64>(Instruction::f64_trunc, wasm::f64_trunc)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_nearest(&mut self) -> Self::Output {
         self.translate_unary::<f64, f64>(Instruction::f64_nearest, wasm::f64_nearest)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_sqrt(&mut self) -> Self::Output {
         self.translate_unary::<f64, f64>(Instruction::f64_sqrt, wasm::f64_sqrt)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_add(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f64, f64>(
             Instruction::f64_add,
@@ -2802,6 +2881,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_sub(&mut self) -> Self::Output {
         self.translate_fbinary::<f64, f64>(
             Instruction::f64_sub,
@@ -2815,6 +2895,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_mul(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f64, f64>(
             Instruction::f64_mul,
@@ -2827,6 +2908,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_div(&mut self) -> Self::Output {
         self.translate_fbinary::<f64, f64>(
             Instruction::f64_div,
@@ -2837,6 +2919,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_min(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f64, f64>(
             Instruction::f64_min,
@@ -2853,6 +2936,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_max(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f64, f64>(
             Instruction::f64_max,
@@ -2869,6 +2953,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_copysign(&mut self) -> Self::Output {
         self.translate_fcopysign::<f64>(
             Instruction::f64_copysign,
@@ -2881,6 +2966,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.translate_unary::<i64, i32>(Instruction::i32_wrap_i64, wasm::i32_wrap_i64)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i32_trunc_f32_s(&mut self) -> Self::Output {
         self.translate_unary_fallible::<f32, i32>(
             Instruction::i32_trunc_f32_s,
@@ -2888,6 +2974,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i32_trunc_f32_u(&mut self) -> Self::Output {
         self.translate_unary_fallible::<f32, u32>(
             Instruction::i32_trunc_f32_u,
@@ -2895,6 +2982,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i32_trunc_f64_s(&mut self) -> Self::Output {
         self.translate_unary_fallible::<f64, i32>(
             Instruction::i32_trunc_f64_s,
@@ -2902,6 +2990,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i32_trunc_f64_u(&mut self) -> Self::Output {
         self.translate_unary_fallible::<f64, u32>(
             Instruction::i32_trunc_f64_u,
@@ -2917,6 +3006,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.translate_i64_extend_i32_u()
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i64_trunc_f32_s(&mut self) -> Self::Output {
         self.translate_unary_fallible::<f32, i64>(
             Instruction::i64_trunc_f32_s,
@@ -2924,6 +3014,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i64_trunc_f32_u(&mut self) -> Self::Output {
         self.translate_unary_fallible::<f32, u64>(
             Instruction::i64_trunc_f32_u,
@@ -2931,6 +3022,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i64_trunc_f64_s(&mut self) -> Self::Output {
         self.translate_unary_fallible::<f64, i64>(
             Instruction::i64_trunc_f64_s,
@@ -2938,6 +3030,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i64_trunc_f64_u(&mut self) -> Self::Output {
         self.translate_unary_fallible::<f64, u64>(
             Instruction::i64_trunc_f64_u,
@@ -2945,58 +3038,72 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_convert_i32_s(&mut self) -> Self::Output {
         self.translate_unary::<i32, f32>(Instruction::f32_convert_i32_s, wasm::f32_convert_i32_s)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_convert_i32_u(&mut self) -> Self::Output {
         self.translate_unary::<u32, f32>(Instruction::f32_convert_i32_u, wasm::f32_convert_i32_u)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_convert_i64_s(&mut self) -> Self::Output {
         self.translate_unary::<i64, f32>(Instruction::f32_convert_i64_s, wasm::f32_convert_i64_s)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_convert_i64_u(&mut self) -> Self::Output {
         self.translate_unary::<u64, f32>(Instruction::f32_convert_i64_u, wasm::f32_convert_i64_u)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_demote_f64(&mut self) -> Self::Output {
         self.translate_unary::<f64, f32>(Instruction::f32_demote_f64, wasm::f32_demote_f64)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_convert_i32_s(&mut self) -> Self::Output {
         self.translate_unary::<i32, f64>(Instruction::f64_convert_i32_s, wasm::f64_convert_i32_s)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_convert_i32_u(&mut self) -> Self::Output {
         self.translate_unary::<u32, f64>(Instruction::f64_convert_i32_u, wasm::f64_convert_i32_u)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_convert_i64_s(&mut self) -> Self::Output {
         self.translate_unary::<i64, f64>(Instruction::f64_convert_i64_s, wasm::f64_convert_i64_s)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_convert_i64_u(&mut self) -> Self::Output {
         self.translate_unary::<u64, f64>(Instruction::f64_convert_i64_u, wasm::f64_convert_i64_u)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_promote_f32(&mut self) -> Self::Output {
         self.translate_unary::<f32, f64>(Instruction::f64_promote_f32, wasm::f64_promote_f32)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i32_reinterpret_f32(&mut self) -> Self::Output {
         self.translate_reinterpret(ValType::I32)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i64_reinterpret_f64(&mut self) -> Self::Output {
         self.translate_reinterpret(ValType::I64)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f32_reinterpret_i32(&mut self) -> Self::Output {
         self.translate_reinterpret(ValType::F32)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_f64_reinterpret_i64(&mut self) -> Self::Output {
         self.translate_reinterpret(ValType::F64)
     }
@@ -3021,6 +3128,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.translate_unary::<i64, i64>(Instruction::i64_extend32_s, wasm::i64_extend32_s)
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i32_trunc_sat_f32_s(&mut self) -> Self::Output {
         self.translate_unary::<f32, i32>(
             Instruction::i32_trunc_sat_f32_s,
@@ -3028,6 +3136,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i32_trunc_sat_f32_u(&mut self) -> Self::Output {
         self.translate_unary::<f32, u32>(
             Instruction::i32_trunc_sat_f32_u,
@@ -3035,6 +3144,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i32_trunc_sat_f64_s(&mut self) -> Self::Output {
         self.translate_unary::<f64, i32>(
             Instruction::i32_trunc_sat_f64_s,
@@ -3042,6 +3152,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i32_trunc_sat_f64_u(&mut self) -> Self::Output {
         self.translate_unary::<f64, u32>(
             Instruction::i32_trunc_sat_f64_u,
@@ -3049,6 +3160,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i64_trunc_sat_f32_s(&mut self) -> Self::Output {
         self.translate_unary::<f32, i64>(
             Instruction::i64_trunc_sat_f32_s,
@@ -3056,6 +3168,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i64_trunc_sat_f32_u(&mut self) -> Self::Output {
         self.translate_unary::<f32, u64>(
             Instruction::i64_trunc_sat_f32_u,
@@ -3063,6 +3176,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i64_trunc_sat_f64_s(&mut self) -> Self::Output {
         self.translate_unary::<f64, i64>(
             Instruction::i64_trunc_sat_f64_s,
@@ -3070,6 +3184,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-float"))]
     fn visit_i64_trunc_sat_f64_u(&mut self) -> Self::Output {
         self.translate_unary::<f64, u64>(
             Instruction::i64_trunc_sat_f64_u,
@@ -3077,6 +3192,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn visit_memory_init(&mut self, data_index: u32, mem: u32) -> Self::Output {
         bail_unreachable!(self);
         let memory = index::Memory::from(mem);
@@ -3121,12 +3237,14 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn visit_data_drop(&mut self, data_index: u32) -> Self::Output {
         bail_unreachable!(self);
         self.push_fueled_instr(Instruction::data_drop(data_index), FuelCosts::entity)?;
         Ok(())
     }
 
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn visit_memory_copy(&mut self, dst_mem: u32, src_mem: u32) -> Self::Output {
         bail_unreachable!(self);
         let dst_memory = index::Memory::from(dst_mem);
@@ -3177,6 +3295,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn visit_memory_fill(&mut self, mem: u32) -> Self::Output {
         bail_unreachable!(self);
         let memory = index::Memory::from(mem);
@@ -3218,6 +3337,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn visit_table_init(&mut self, elem_index: u32, table: u32) -> Self::Output {
         bail_unreachable!(self);
         let (dst, src, len) = self.alloc.stack.pop3();
@@ -3261,12 +3381,14 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn visit_elem_drop(&mut self, elem_index: u32) -> Self::Output {
         bail_unreachable!(self);
         self.push_fueled_instr(Instruction::elem_drop(elem_index), FuelCosts::entity)?;
         Ok(())
     }
 
+    #[cfg(not(feature = "no-bulk-memory"))]
     fn visit_table_copy(&mut self, dst_table: u32, src_table: u32) -> Self::Output {
         bail_unreachable!(self);
         let (dst, src, len) = self.alloc.stack.pop3();
@@ -33,6 +33,7 @@ impl VisitInputRegisters for Instruction {
     fn visit_input_registers(&mut self, mut f: impl FnMut(&mut Reg)) {
         // Note: for copy instructions that copy register values we also need to visit
         //       their results because preserved registers might be populating them.
+        //       The same applies to `add` instructions of hoisted loop invariants.
         match self {
             | Self::Copy { result, .. } => f(result),
             | Self::Copy2 { results, .. } => f(results.span_mut().head_mut()),
@@ -40,6 +41,10 @@ impl VisitInputRegisters for Instruction {
             | Self::CopySpanNonOverlapping { results, .. }
             | Self::CopyMany { results, .. }
             | Self::CopyManyNonOverlapping { results, .. } => f(results.head_mut()),
+            | Self::I32Add { result, .. }
+            | Self::I32AddImm16 { result, .. }
+            | Self::I64Add { result, .. }
+            | Self::I64AddImm16 { result, .. } => f(result),
             _ => {}
         }
         self.visit_regs(&mut Visitor { f });
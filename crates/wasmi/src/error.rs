@@ -7,14 +7,16 @@ use super::errors::{
     IrError,
     LinkerError,
     MemoryError,
+    SerializedCodeError,
     TableError,
+    ValidationError,
 };
 use crate::{
     core::{HostError, TrapCode},
     engine::{ResumableHostError, TranslationError},
     module::ReadError,
 };
-use alloc::{boxed::Box, string::String};
+use alloc::{boxed::Box, collections::TryReserveError, string::String};
 use core::{fmt, fmt::Display};
 use wasmparser::BinaryReaderError as WasmError;
 
@@ -78,11 +80,66 @@ impl Error {
         &self.kind
     }
 
+    /// Returns the [`ErrorCategory`] of the [`Error`].
+    ///
+    /// # Note
+    ///
+    /// Unlike the [`ErrorKind`] the [`ErrorCategory`] is stable and
+    /// thus suitable for embedders to branch on the cause of an [`Error`].
+    pub fn category(&self) -> ErrorCategory {
+        self.kind().category()
+    }
+
+    /// Returns `true` if the [`Error`] occurred while parsing, validating or translating Wasm.
+    pub fn is_compile(&self) -> bool {
+        matches!(self.category(), ErrorCategory::Compile)
+    }
+
+    /// Returns `true` if the [`Error`] occurred while linking or instantiating a Wasm module.
+    pub fn is_link(&self) -> bool {
+        matches!(self.category(), ErrorCategory::Link)
+    }
+
+    /// Returns `true` if the [`Error`] is a Wasm trap.
+    pub fn is_trap(&self) -> bool {
+        matches!(self.category(), ErrorCategory::Trap)
+    }
+
+    /// Returns `true` if the [`Error`] is caused by an exhausted or denied resource.
+    pub fn is_resource(&self) -> bool {
+        matches!(self.category(), ErrorCategory::Resource)
+    }
+
+    /// Returns `true` if the [`Error`] originates from the host.
+    pub fn is_host(&self) -> bool {
+        matches!(self.category(), ErrorCategory::Host)
+    }
+
     /// Returns a reference to [`TrapCode`] if [`Error`] is a [`TrapCode`].
     pub fn as_trap_code(&self) -> Option<TrapCode> {
         self.kind().as_trap_code()
     }
 
+    /// Returns a reference to [`ValidationError`] if [`Error`] is a [`ValidationError`].
+    pub fn as_validation_error(&self) -> Option<&ValidationError> {
+        self.kind().as_validation_error()
+    }
+
+    /// Returns a reference to [`LinkerError`] if [`Error`] is a [`LinkerError`].
+    pub fn as_linker_error(&self) -> Option<&LinkerError> {
+        self.kind().as_linker_error()
+    }
+
+    /// Returns a reference to [`InstantiationError`] if [`Error`] is an [`InstantiationError`].
+    pub fn as_instantiation_error(&self) -> Option<&InstantiationError> {
+        self.kind().as_instantiation_error()
+    }
+
+    /// Returns the message of the [`Error`] if it was created via [`Error::new`].
+    pub fn as_message(&self) -> Option<&str> {
+        self.kind().as_message()
+    }
+
     /// Returns the classic `i32` exit program code of a `Trap` if any.
     ///
     /// Otherwise returns `None`.
@@ -130,6 +187,24 @@ impl Error {
             .map(|boxed| *boxed)
     }
 
+    /// Adds the `func_index` to `self` if it is a [`ValidationError`].
+    pub(crate) fn with_func_index(mut self, func_index: u32) -> Self {
+        if let ErrorKind::Wasm(error) = &mut *self.kind {
+            error.set_func_index(func_index);
+        }
+        self
+    }
+
+    /// Adds the failing Wasm operator to `self` if it is a [`ValidationError`].
+    ///
+    /// The `bytes` are required to start at the `base` offset in the Wasm binary.
+    pub(crate) fn with_operator_from(mut self, bytes: &[u8], base: usize) -> Self {
+        if let ErrorKind::Wasm(error) = &mut *self.kind {
+            error.set_operator_from(bytes, base);
+        }
+        self
+    }
+
     pub(crate) fn into_resumable(self) -> Result<ResumableHostError, Error> {
         if matches!(&*self.kind, ErrorKind::ResumableHost(_)) {
             let ErrorKind::ResumableHost(error) = *self.kind else {
@@ -188,19 +263,76 @@ pub enum ErrorKind {
     /// Encountered when there is a problem with the Wasm input stream.
     Read(ReadError),
     /// Encountered when there is a Wasm parsing or validation error.
-    Wasm(WasmError),
+    Wasm(ValidationError),
     /// Encountered when there is a Wasm to Wasmi translation error.
     Translation(TranslationError),
     /// Encountered when an enforced limit is exceeded.
     Limits(EnforcedLimitsError),
     /// Encountered for Wasmi bytecode related errors.
     Ir(IrError),
+    /// Encountered when loading serialized Wasmi bytecode.
+    SerializedCode(SerializedCodeError),
+    /// Encountered when the system ran out of memory.
+    ///
+    /// # Note
+    ///
+    /// Wasmi reports allocation failures of linear memories, tables,
+    /// the value and call stacks as well as translation buffers with
+    /// this error instead of aborting.
+    OutOfMemory,
     /// Encountered an error from the `wat` crate.
     #[cfg(feature = "wat")]
     Wat(WatError),
 }
 
 impl ErrorKind {
+    /// Returns the [`ErrorCategory`] of the [`ErrorKind`].
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::TrapCode(_) => ErrorCategory::Trap,
+            Self::Message(_) | Self::I32ExitStatus(_) | Self::Host(_) | Self::ResumableHost(_) => {
+                ErrorCategory::Host
+            }
+            Self::Linker(_) | Self::Instantiation(_) => ErrorCategory::Link,
+            Self::Read(_)
+            | Self::Wasm(_)
+            | Self::Translation(_)
+            | Self::Limits(_)
+            | Self::Ir(_)
+            | Self::SerializedCode(_) => ErrorCategory::Compile,
+            #[cfg(feature = "wat")]
+            Self::Wat(_) => ErrorCategory::Compile,
+            Self::OutOfMemory => ErrorCategory::Resource,
+            Self::Fuel(error) => match error {
+                FuelError::OutOfFuel => ErrorCategory::Resource,
+                FuelError::FuelMeteringDisabled | FuelError::ScheduleUnavailable => {
+                    ErrorCategory::Usage
+                }
+            },
+            Self::Memory(error) => match error {
+                MemoryError::InvalidSubtype { .. } => ErrorCategory::Link,
+                MemoryError::OutOfSystemMemory
+                | MemoryError::OutOfBoundsGrowth
+                | MemoryError::TooManyMemories
+                | MemoryError::ResourceLimiterDeniedAllocation
+                | MemoryError::MinimumSizeOverflow
+                | MemoryError::MaximumSizeOverflow => ErrorCategory::Resource,
+                _ => ErrorCategory::Usage,
+            },
+            Self::Table(error) => match error {
+                TableError::InvalidSubtype { .. } => ErrorCategory::Link,
+                TableError::OutOfSystemMemory
+                | TableError::GrowOutOfBounds { .. }
+                | TableError::TooManyTables
+                | TableError::ResourceLimiterDeniedAllocation
+                | TableError::MinimumSizeOverflow
+                | TableError::MaximumSizeOverflow => ErrorCategory::Resource,
+                _ => ErrorCategory::Usage,
+            },
+            Self::Global(_) | Self::Func(_) => ErrorCategory::Usage,
+        }
+    }
+
     /// Returns a reference to [`TrapCode`] if [`ErrorKind`] is a [`TrapCode`].
     pub fn as_trap_code(&self) -> Option<TrapCode> {
         match self {
@@ -217,6 +349,38 @@ impl ErrorKind {
         }
     }
 
+    /// Returns a reference to [`ValidationError`] if [`ErrorKind`] is a [`ValidationError`].
+    pub fn as_validation_error(&self) -> Option<&ValidationError> {
+        match self {
+            Self::Wasm(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to [`LinkerError`] if [`ErrorKind`] is a [`LinkerError`].
+    pub fn as_linker_error(&self) -> Option<&LinkerError> {
+        match self {
+            Self::Linker(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to [`InstantiationError`] if [`ErrorKind`] is an [`InstantiationError`].
+    pub fn as_instantiation_error(&self) -> Option<&InstantiationError> {
+        match self {
+            Self::Instantiation(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Returns the message if [`ErrorKind`] is an [`ErrorKind::Message`].
+    pub fn as_message(&self) -> Option<&str> {
+        match self {
+            Self::Message(message) => Some(message),
+            _ => None,
+        }
+    }
+
     /// Returns a dynamic reference to [`HostError`] if [`ErrorKind`] is a [`HostError`].
     pub fn as_host(&self) -> Option<&dyn HostError> {
         match self {
@@ -245,6 +409,41 @@ impl ErrorKind {
 #[cfg(feature = "std")]
 impl std::error::Error for ErrorKind {}
 
+/// The stable category of an [`Error`].
+///
+/// Embedders can use the [`ErrorCategory`] to branch on the cause of an [`Error`]
+/// without depending on the exact [`ErrorKind`] or the error message.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorCategory {
+    /// The Wasm input could not be parsed, validated or translated.
+    Compile,
+    /// The Wasm module could not be linked or instantiated.
+    Link,
+    /// The execution of Wasm trapped.
+    Trap,
+    /// A resource such as memory or fuel is exhausted or its allocation was denied.
+    Resource,
+    /// The error originates from the host, e.g. from a host function call.
+    Host,
+    /// The Wasmi API was used incorrectly, e.g. due to type mismatches or out of bounds accesses.
+    Usage,
+}
+
+impl Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Compile => "compile",
+            Self::Link => "link",
+            Self::Trap => "trap",
+            Self::Resource => "resource",
+            Self::Host => "host",
+            Self::Usage => "usage",
+        };
+        f.write_str(name)
+    }
+}
+
 impl Display for ErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -265,6 +464,8 @@ impl Display for ErrorKind {
             Self::Limits(error) => Display::fmt(error, f),
             Self::ResumableHost(error) => Display::fmt(error, f),
             Self::Ir(error) => Display::fmt(error, f),
+            Self::SerializedCode(error) => Display::fmt(error, f),
+            Self::OutOfMemory => write!(f, "out of system memory"),
             #[cfg(feature = "wat")]
             Self::Wat(error) => Display::fmt(error, f),
         }
@@ -287,18 +488,50 @@ macro_rules! impl_from {
 impl_from! {
     impl From<TrapCode> for Error::TrapCode;
     impl From<GlobalError> for Error::Global;
-    impl From<MemoryError> for Error::Memory;
-    impl From<TableError> for Error::Table;
     impl From<LinkerError> for Error::Linker;
     impl From<InstantiationError> for Error::Instantiation;
     impl From<TranslationError> for Error::Translation;
-    impl From<WasmError> for Error::Wasm;
     impl From<ReadError> for Error::Read;
     impl From<FuelError> for Error::Fuel;
     impl From<FuncError> for Error::Func;
     impl From<EnforcedLimitsError> for Error::Limits;
     impl From<ResumableHostError> for Error::ResumableHost;
     impl From<IrError> for Error::Ir;
+    impl From<SerializedCodeError> for Error::SerializedCode;
+}
+impl From<MemoryError> for Error {
+    #[inline]
+    #[cold]
+    fn from(error: MemoryError) -> Self {
+        match error {
+            MemoryError::OutOfSystemMemory => Self::from_kind(ErrorKind::OutOfMemory),
+            error => Self::from_kind(ErrorKind::Memory(error)),
+        }
+    }
+}
+impl From<TableError> for Error {
+    #[inline]
+    #[cold]
+    fn from(error: TableError) -> Self {
+        match error {
+            TableError::OutOfSystemMemory => Self::from_kind(ErrorKind::OutOfMemory),
+            error => Self::from_kind(ErrorKind::Table(error)),
+        }
+    }
+}
+impl From<TryReserveError> for Error {
+    #[inline]
+    #[cold]
+    fn from(_error: TryReserveError) -> Self {
+        Self::from_kind(ErrorKind::OutOfMemory)
+    }
+}
+impl From<WasmError> for Error {
+    #[inline]
+    #[cold]
+    fn from(error: WasmError) -> Self {
+        Self::from_kind(ErrorKind::Wasm(ValidationError::from(error)))
+    }
 }
 #[cfg(feature = "wat")]
 impl_from! {
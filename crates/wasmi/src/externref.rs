@@ -6,7 +6,7 @@ use crate::{
     StoreContext,
 };
 use alloc::boxed::Box;
-use core::{any::Any, mem, num::NonZeroU32};
+use core::{any::Any, fmt, marker::PhantomData, mem, num::NonZeroU32};
 
 /// A raw index to a function entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -172,6 +172,11 @@ impl ExternRef {
         Self { inner: None }
     }
 
+    /// Returns the referenced [`ExternObject`] if `self` is not `null`.
+    pub(crate) fn object(&self) -> Option<&ExternObject> {
+        self.inner.as_ref()
+    }
+
     /// Returns a shared reference to the underlying data for this [`ExternRef`].
     ///
     /// # Panics
@@ -180,12 +185,129 @@ impl ExternRef {
     pub fn data<'a, T: 'a>(&self, ctx: impl Into<StoreContext<'a, T>>) -> Option<&'a dyn Any> {
         self.inner.map(|object| object.data(ctx))
     }
+
+    /// Creates a new [`TypedExternRef`] wrapping the given value.
+    pub fn new_typed<T>(ctx: impl AsContextMut, object: impl Into<Option<T>>) -> TypedExternRef<T>
+    where
+        T: 'static + Any + Send + Sync,
+    {
+        TypedExternRef::from_untyped(Self::new(ctx, object))
+    }
+
+    /// Converts `self` into a [`TypedExternRef`] referring to data of type `T`.
+    ///
+    /// # Note
+    ///
+    /// The type of the underlying data is checked upon access via [`TypedExternRef::data`].
+    pub fn typed<T>(self) -> TypedExternRef<T>
+    where
+        T: 'static + Any + Send + Sync,
+    {
+        TypedExternRef::from_untyped(self)
+    }
+}
+
+/// A nullable [`ExternRef`] that statically tracks the Rust type `T` of its data.
+///
+/// This can be used as parameter or result type of host functions
+/// in order to access the data of an [`ExternRef`] without downcasting.
+#[repr(transparent)]
+pub struct TypedExternRef<T> {
+    inner: ExternRef,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> fmt::Debug for TypedExternRef<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedExternRef")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<T> Copy for TypedExternRef<T> {}
+
+impl<T> Clone for TypedExternRef<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Default for TypedExternRef<T> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<T> From<UntypedVal> for TypedExternRef<T> {
+    fn from(untyped: UntypedVal) -> Self {
+        Self::from_untyped(ExternRef::from(untyped))
+    }
+}
+
+impl<T> From<TypedExternRef<T>> for UntypedVal {
+    fn from(externref: TypedExternRef<T>) -> Self {
+        UntypedVal::from(externref.inner)
+    }
+}
+
+impl<T> From<TypedExternRef<T>> for ExternRef {
+    fn from(externref: TypedExternRef<T>) -> Self {
+        externref.inner
+    }
+}
+
+impl<T> TypedExternRef<T> {
+    /// Creates a new [`TypedExternRef`] from the untyped `externref`.
+    fn from_untyped(externref: ExternRef) -> Self {
+        Self {
+            inner: externref,
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates a new [`TypedExternRef`] which is `null`.
+    pub fn null() -> Self {
+        Self::from_untyped(ExternRef::null())
+    }
+
+    /// Returns `true` if [`TypedExternRef`] is `null`.
+    pub fn is_null(&self) -> bool {
+        self.inner.is_null()
+    }
+
+    /// Returns the underlying untyped [`ExternRef`].
+    pub fn as_untyped(&self) -> ExternRef {
+        self.inner
+    }
+}
+
+impl<T> TypedExternRef<T>
+where
+    T: 'static + Any + Send + Sync,
+{
+    /// Creates a new [`TypedExternRef`] wrapping the given value.
+    pub fn new(ctx: impl AsContextMut, object: impl Into<Option<T>>) -> Self {
+        ExternRef::new_typed(ctx, object)
+    }
+
+    /// Returns a shared reference to the underlying data for this [`TypedExternRef`].
+    ///
+    /// Returns `None` if `self` is `null` or if its data is not of type `T`.
+    /// The latter can only happen if Wasm passed an [`ExternRef`] of another type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`TypedExternRef`].
+    pub fn data<'a, U: 'a>(&self, ctx: impl Into<StoreContext<'a, U>>) -> Option<&'a T> {
+        self.inner.data(ctx)?.downcast_ref::<T>()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Engine, Store};
+    use crate::{Caller, Engine, Func, Store, Val};
 
     #[test]
     fn it_works() {
@@ -195,4 +317,34 @@ mod tests {
         let obj = ExternObject::new::<i32>(&mut store, value);
         assert_eq!(obj.data(&store).downcast_ref::<i32>(), Some(&value),);
     }
+
+    #[test]
+    fn typed_works() {
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let value = 42_i32;
+        let typed = ExternRef::new_typed::<i32>(&mut store, value);
+        assert_eq!(typed.data(&store), Some(&value));
+        assert_eq!(typed.as_untyped().typed::<i64>().data(&store), None);
+        assert_eq!(<TypedExternRef<i32>>::null().data(&store), None);
+    }
+
+    #[test]
+    fn typed_host_func_works() {
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let func = Func::wrap(
+            &mut store,
+            |caller: Caller<()>, value: TypedExternRef<i32>| -> i32 {
+                value.data(&caller).copied().unwrap_or(-1)
+            },
+        );
+        let mut result = [Val::I32(0)];
+        let value = ExternRef::new_typed::<i32>(&mut store, 42);
+        func.call(&mut store, &[value.into()], &mut result).unwrap();
+        assert_eq!(result[0].i32(), Some(42));
+        let other = ExternRef::new::<i64>(&mut store, 42);
+        func.call(&mut store, &[other.into()], &mut result).unwrap();
+        assert_eq!(result[0].i32(), Some(-1));
+    }
 }
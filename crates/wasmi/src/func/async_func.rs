@@ -0,0 +1,103 @@
+use crate::{core::HostError, Error, Val};
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, future::Future, pin::Pin};
+
+#[cfg(feature = "std")]
+use crate::AsContextMut;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+/// The boxed future returned by an async host function.
+pub(crate) type HostFuture = Pin<Box<dyn Future<Output = Result<Vec<Val>, Error>> + Send>>;
+
+/// The host error returned by async host functions to suspend the Wasm execution.
+///
+/// # Note
+///
+/// The future of the async host function is stored in the [`Store`](crate::Store)
+/// and awaited by [`Func::call_async`](crate::Func::call_async) before the
+/// suspended Wasm execution is resumed with its results.
+#[derive(Debug)]
+pub(crate) struct AsyncCallPending;
+
+impl fmt::Display for AsyncCallPending {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "async host functions must be called via `Func::call_async` or `TypedFunc::call_async`"
+        )
+    }
+}
+
+impl HostError for AsyncCallPending {}
+
+/// The [`HostFuture`] of an async host function call that is yet to be awaited.
+pub(crate) struct PendingHostFuture {
+    future: HostFuture,
+}
+
+impl fmt::Debug for PendingHostFuture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingHostFuture").finish()
+    }
+}
+
+impl PendingHostFuture {
+    /// Creates a new [`PendingHostFuture`] for `future`.
+    pub fn new(future: HostFuture) -> Self {
+        Self { future }
+    }
+
+    /// Returns the underlying [`HostFuture`].
+    pub fn into_future(self) -> HostFuture {
+        self.future
+    }
+}
+
+// Safety: A `PendingHostFuture` never hands out shared references to its `future`
+//         and is only ever accessed by value. Thus sharing `&PendingHostFuture`
+//         across threads cannot be used to concurrently access the `future`.
+unsafe impl Sync for PendingHostFuture {}
+
+/// Sets the deadline of the [`Store`](crate::Store) of a context for as long as it is alive.
+///
+/// The previous deadline is restored when the [`DeadlineGuard`] is dropped.
+/// This also happens if the future of a timed call is dropped before it finished.
+#[cfg(feature = "std")]
+pub(crate) struct DeadlineGuard<C: AsContextMut> {
+    /// The guarded context.
+    ctx: C,
+    /// The deadline before the [`DeadlineGuard`] was created.
+    previous: Option<Instant>,
+}
+
+#[cfg(feature = "std")]
+impl<C: AsContextMut> DeadlineGuard<C> {
+    /// Sets the deadline of `ctx` to `timeout` from now unless an earlier deadline is set.
+    pub fn new(mut ctx: C, timeout: Duration) -> Self {
+        let inner = &mut ctx.as_context_mut().store.inner;
+        let previous = inner.deadline();
+        let deadline = match (previous, Instant::now().checked_add(timeout)) {
+            (Some(previous), Some(deadline)) => Some(previous.min(deadline)),
+            (previous, deadline) => deadline.or(previous),
+        };
+        inner.set_deadline(deadline);
+        Self { ctx, previous }
+    }
+
+    /// Returns an exclusive reference to the guarded context.
+    pub fn ctx_mut(&mut self) -> &mut C {
+        &mut self.ctx
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: AsContextMut> Drop for DeadlineGuard<C> {
+    fn drop(&mut self) {
+        self.ctx
+            .as_context_mut()
+            .store
+            .inner
+            .set_deadline(self.previous);
+    }
+}
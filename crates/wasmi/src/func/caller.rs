@@ -1,5 +1,22 @@
 use super::super::{AsContext, AsContextMut, StoreContext, StoreContextMut};
-use crate::{Engine, Error, Extern, Instance};
+use crate::{
+    func::FuncError,
+    memory::MemoryError,
+    Engine,
+    Error,
+    Extern,
+    FuelSchedule,
+    Func,
+    FuncEntity,
+    Instance,
+    Memory,
+    TypedFunc,
+    Val,
+    WasmParams,
+    WasmResults,
+};
+use alloc::boxed::Box;
+use core::any::Any;
 
 /// Represents the caller’s context when creating a host function via [`Func::wrap`].
 ///
@@ -25,6 +42,14 @@ impl<'a, T> Caller<'a, T> {
         }
     }
 
+    /// Returns a new [`Caller`] that reborrows the store context of `self`.
+    pub(crate) fn reborrow(&mut self) -> Caller<'_, T> {
+        Caller {
+            ctx: self.ctx.as_context_mut(),
+            instance: self.instance,
+        }
+    }
+
     /// Queries the caller for an exported definition identifier by `name`.
     ///
     /// Returns `None` if there is no associated [`Instance`] of the caller
@@ -34,6 +59,73 @@ impl<'a, T> Caller<'a, T> {
             .and_then(|instance| instance.get_export(self, name))
     }
 
+    /// Looks up an exported [`Memory`] of the caller by `name`.
+    ///
+    /// Returns `None` if there is no associated [`Instance`] of the caller
+    /// or if the caller does not export a linear memory under the name `name`.
+    pub fn get_memory(&self, name: &str) -> Option<Memory> {
+        self.get_export(name)?.into_memory()
+    }
+
+    /// Looks up an exported [`TypedFunc`] of the caller by `name`.
+    ///
+    /// # Errors
+    ///
+    /// - If there is no associated [`Instance`] of the caller.
+    /// - If the caller does not export a function named `name`.
+    /// - If `Params` or `Results` do not match the exported function type.
+    pub fn get_typed_func<Params, Results>(
+        &self,
+        name: &str,
+    ) -> Result<TypedFunc<Params, Results>, Error>
+    where
+        Params: WasmParams,
+        Results: WasmResults,
+    {
+        let Some(instance) = self.instance else {
+            return Err(Error::from(FuncError::ExportedFuncNotFound));
+        };
+        instance.get_typed_func(self, name)
+    }
+
+    /// Returns the default linear memory of the caller if any.
+    ///
+    /// The default linear memory is the linear memory at index 0 of the
+    /// associated [`Instance`] of the caller, regardless whether it is exported.
+    pub fn default_memory(&self) -> Option<Memory> {
+        self.instance?.get_memory_by_index(self, 0)
+    }
+
+    /// Reads `n` bytes from `memory[offset..offset+n]` of the default linear memory
+    /// of the caller into `buffer` where `n` is the length of `buffer`.
+    ///
+    /// For more information see [`Caller::default_memory`].
+    ///
+    /// # Errors
+    ///
+    /// - If the caller has no default linear memory.
+    /// - If this operation accesses out of bounds linear memory.
+    pub fn read(&self, offset: usize, buffer: &mut [u8]) -> Result<(), MemoryError> {
+        self.default_memory()
+            .ok_or(MemoryError::MissingDefaultMemory)?
+            .read(self, offset, buffer)
+    }
+
+    /// Writes `n` bytes to `memory[offset..offset+n]` of the default linear memory
+    /// of the caller from `buffer` where `n` is the length of `buffer`.
+    ///
+    /// For more information see [`Caller::default_memory`].
+    ///
+    /// # Errors
+    ///
+    /// - If the caller has no default linear memory.
+    /// - If this operation accesses out of bounds linear memory.
+    pub fn write(&mut self, offset: usize, buffer: &[u8]) -> Result<(), MemoryError> {
+        self.default_memory()
+            .ok_or(MemoryError::MissingDefaultMemory)?
+            .write(self, offset, buffer)
+    }
+
     /// Returns a shared reference to the user provided host data.
     pub fn data(&self) -> &T {
         self.ctx.store.data()
@@ -44,6 +136,26 @@ impl<'a, T> Caller<'a, T> {
         self.ctx.store.data_mut()
     }
 
+    /// Returns a shared reference to the host provided context of type `C` if any.
+    ///
+    /// For more information see [`Store::insert_context`](crate::Store::insert_context).
+    pub fn context<C>(&self) -> Option<&C>
+    where
+        C: Any + Send + Sync,
+    {
+        self.ctx.store.context()
+    }
+
+    /// Returns an exclusive reference to the host provided context of type `C` if any.
+    ///
+    /// For more information see [`Store::insert_context`](crate::Store::insert_context).
+    pub fn context_mut<C>(&mut self) -> Option<&mut C>
+    where
+        C: Any + Send + Sync,
+    {
+        self.ctx.store.context_mut()
+    }
+
     /// Returns a shared reference to the used [`Engine`].
     pub fn engine(&self) -> &Engine {
         self.ctx.store.engine()
@@ -70,6 +182,66 @@ impl<'a, T> Caller<'a, T> {
     pub fn set_fuel(&mut self, fuel: u64) -> Result<(), Error> {
         self.ctx.store.set_fuel(fuel)
     }
+
+    /// Adds `amount` to the remaining fuel of the [`Store`](crate::Store) if fuel metering is enabled.
+    ///
+    /// For more information see [`Store::refund_fuel`](crate::Store::refund_fuel).
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled.
+    pub fn refund_fuel(&mut self, amount: u64) -> Result<(), Error> {
+        self.ctx.store.refund_fuel(amount)
+    }
+
+    /// Returns the [`FuelSchedule`] used to charge fuel by the current call if any.
+    ///
+    /// For more information see [`Store::fuel_schedule`](crate::Store::fuel_schedule).
+    pub fn fuel_schedule(&self) -> Option<FuelSchedule> {
+        self.ctx.store.fuel_schedule()
+    }
+
+    /// Tail calls the Wasm `func` with `params` once the host function returns.
+    ///
+    /// # Note
+    ///
+    /// - The results of `func` become the results of the host function and
+    ///   the results written by the host function itself are ignored.
+    /// - Unlike calling `func` from within the host function this executes `func` as
+    ///   continuation of the host function by the same execution and thus does not
+    ///   use any additional native stack space. This is how host functions call back
+    ///   into Wasm with [`Config::stackless`] enabled.
+    /// - The tail call is discarded if the host function returns an error.
+    ///
+    /// # Errors
+    ///
+    /// - If `func` is a host function.
+    /// - If `params` do not match the parameters of `func`.
+    /// - Upon execution if the results of `func` do not match the results of the host function.
+    ///
+    /// [`Config::stackless`]: crate::Config::stackless
+    pub fn return_call(&mut self, func: &Func, params: &[Val]) -> Result<(), Error> {
+        let store = &mut self.ctx.store.inner;
+        let FuncEntity::Wasm(wasm_func) = store.resolve_func(func) else {
+            return Err(Error::from(FuncError::ReturnCallToHost));
+        };
+        let func_type = *wasm_func.ty_dedup();
+        store.resolve_func_type_with(&func_type, |func_type| func_type.match_params(params))?;
+        store.set_return_call(HostReturnCall {
+            func: *func,
+            params: params.into(),
+        });
+        Ok(())
+    }
+}
+
+/// A Wasm function tail called by a host function via [`Caller::return_call`].
+#[derive(Debug)]
+pub struct HostReturnCall {
+    /// The tail called Wasm function.
+    pub func: Func,
+    /// The parameters of the tail called Wasm function.
+    pub params: Box<[Val]>,
 }
 
 impl<T> AsContext for Caller<'_, T> {
@@ -14,6 +14,16 @@ pub enum FuncError {
     MismatchingResultType,
     /// Specified an incorrect number of results.
     MismatchingResultLen,
+    /// Called a [`ThreadBound`](crate::ThreadBound) host function from a foreign thread.
+    CalledFromForeignThread,
+    /// Called a function from a host function with [`Config::stackless`] enabled.
+    ///
+    /// [`Config::stackless`]: crate::Config::stackless
+    NestedCall,
+    /// Tail called a host function from a host function via [`Caller::return_call`].
+    ///
+    /// [`Caller::return_call`]: crate::Caller::return_call
+    ReturnCallToHost,
 }
 
 impl From<CoreFuncTypeError> for FuncError {
@@ -49,6 +59,21 @@ impl Display for FuncError {
             FuncError::MismatchingResultLen => {
                 write!(f, "encountered an incorrect number of results")
             }
+            FuncError::CalledFromForeignThread => {
+                write!(
+                    f,
+                    "called a thread-bound host function from a foreign thread"
+                )
+            }
+            FuncError::NestedCall => {
+                write!(
+                    f,
+                    "cannot call functions from host functions with stackless execution"
+                )
+            }
+            FuncError::ReturnCallToHost => {
+                write!(f, "cannot return call a host function from a host function")
+            }
         }
     }
 }
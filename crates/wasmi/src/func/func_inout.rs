@@ -131,6 +131,13 @@ impl<'a> FuncInOut<'a> {
         Ok(results)
     }
 
+    /// Consumes `self` to return the underlying untyped parameters and results buffer.
+    ///
+    /// The buffer holds the parameters before and the results after the host function call.
+    pub fn into_untyped(self) -> &'a mut [UntypedVal] {
+        self.params_results
+    }
+
     /// Consumes `self` to return the [`FuncResults`] out of it.
     fn into_func_results(self) -> FuncResults<'a> {
         FuncResults::new(&mut self.params_results[..self.len_results])
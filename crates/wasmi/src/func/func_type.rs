@@ -16,6 +16,11 @@ impl DynamicallyTyped for Val {
 ///
 /// Can be cloned cheaply.
 #[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct FuncType {
     /// The inner function type internals.
     inner: CoreFuncType,
@@ -62,6 +67,11 @@ impl FuncType {
         self.inner.params_results()
     }
 
+    /// Returns the number of bytes the function type allocates on the heap.
+    pub(crate) fn heap_size(&self) -> usize {
+        self.inner.heap_size()
+    }
+
     /// Returns `Ok` if the number and types of items in `params` matches as expected by the [`FuncType`].
     ///
     /// # Errors
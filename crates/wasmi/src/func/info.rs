@@ -0,0 +1,83 @@
+use crate::Module;
+use alloc::boxed::Box;
+use wasmparser::{BinaryReader, Name, NameSectionReader};
+
+/// The name of the custom section that holds the debug names of a Wasm module.
+const NAME_SECTION_NAME: &str = "name";
+
+/// Metadata about a Wasm [`Func`] and where it has been defined.
+///
+/// Useful to identify a [`Func`] in host-side logging, metrics or error messages.
+///
+/// [`Func`]: crate::Func
+#[derive(Debug, Clone)]
+pub struct FuncInfo {
+    /// The [`Module`] that defines the [`Func`](crate::Func).
+    module: Module,
+    /// The function index of the [`Func`](crate::Func) within its defining [`Module`].
+    index: u32,
+    /// The name of the [`Func`](crate::Func) found in the `name` custom section if any.
+    name: Option<Box<str>>,
+}
+
+impl FuncInfo {
+    /// Creates a new [`FuncInfo`] for the function at `index` defined by `module`.
+    pub(crate) fn new(module: Module, index: u32) -> Self {
+        let name = func_name(&module, index);
+        Self {
+            module,
+            index,
+            name,
+        }
+    }
+
+    /// Returns the [`Module`] that defines the [`Func`](crate::Func).
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
+    /// Returns the function index of the [`Func`](crate::Func) within its defining [`Module`].
+    ///
+    /// # Note
+    ///
+    /// As defined by the Wasm specification the function index space
+    /// starts with the imported functions of the [`Module`].
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the name of the [`Func`](crate::Func) if any.
+    ///
+    /// # Note
+    ///
+    /// The name is read from the `name` custom section of the [`Module`].
+    /// This always returns `None` if [`Config::ignore_custom_sections`] is set to `true`.
+    ///
+    /// [`Config::ignore_custom_sections`]: crate::Config::ignore_custom_sections
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+/// Returns the name of the function at `index` from the `name` custom section of `module`.
+///
+/// # Note
+///
+/// Malformed `name` custom sections are ignored as mandated by the Wasm specification.
+fn func_name(module: &Module, index: u32) -> Option<Box<str>> {
+    let section = module
+        .custom_sections()
+        .find(|section| section.name() == NAME_SECTION_NAME)?;
+    for subsection in NameSectionReader::new(BinaryReader::new(section.data(), 0)) {
+        let Ok(Name::Function(names)) = subsection else {
+            continue;
+        };
+        for naming in names {
+            let naming = naming.ok()?;
+            if naming.index == index {
+                return Some(naming.name.into());
+            }
+        }
+    }
+    None
+}
@@ -9,8 +9,12 @@ use crate::{
     ExternRef,
     FuncRef,
     FuncType,
+    TypedExternRef,
 };
-use core::{array, iter::FusedIterator};
+use core::{any::Any, array, iter::FusedIterator};
+
+#[cfg(feature = "std")]
+use super::ThreadBound;
 
 #[cfg(feature = "simd")]
 use crate::core::V128;
@@ -87,6 +91,68 @@ macro_rules! impl_into_func {
                 (signature, trampoline)
             }
         }
+
+        #[cfg(feature = "std")]
+        impl<T, F, $($tuple,)* R> IntoFunc<T, ($($tuple,)*), R> for ThreadBound<F>
+        where
+            F: Fn($($tuple),*) -> R + 'static,
+            $(
+                $tuple: WasmTy,
+            )*
+            R: WasmRet,
+        {
+            type Params = ($($tuple,)*);
+            type Results = <R as WasmRet>::Ok;
+
+            #[allow(non_snake_case)]
+            fn into_func(self) -> (FuncType, TrampolineEntity<T>) {
+                let signature = FuncType::new(
+                    <Self::Params as WasmTyList>::types(),
+                    <Self::Results as WasmTyList>::types(),
+                );
+                let trampoline = TrampolineEntity::new(
+                    move |_: Caller<T>, params_results: FuncInOut| -> Result<FuncFinished, Error> {
+                        let func = self.get()?;
+                        let (($($tuple,)*), func_results): (Self::Params, FuncResults) = params_results.decode_params();
+                        let results: Self::Results =
+                            (func)($($tuple),*).into_fallible()?;
+                        Ok(func_results.encode_results(results))
+                    },
+                );
+                (signature, trampoline)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<T, F, $($tuple,)* R> IntoFunc<T, (Caller<'_, T>, $($tuple),*), R> for ThreadBound<F>
+        where
+            F: Fn(Caller<T>, $($tuple),*) -> R + 'static,
+            $(
+                $tuple: WasmTy,
+            )*
+            R: WasmRet,
+        {
+            type Params = ($($tuple,)*);
+            type Results = <R as WasmRet>::Ok;
+
+            #[allow(non_snake_case)]
+            fn into_func(self) -> (FuncType, TrampolineEntity<T>) {
+                let signature = FuncType::new(
+                    <Self::Params as WasmTyList>::types(),
+                    <Self::Results as WasmTyList>::types(),
+                );
+                let trampoline = TrampolineEntity::new(
+                    move |caller: Caller<T>, params_results: FuncInOut| -> Result<FuncFinished, Error> {
+                        let func = self.get()?;
+                        let (($($tuple,)*), func_results): (Self::Params, FuncResults) = params_results.decode_params();
+                        let results: Self::Results =
+                            (func)(caller, $($tuple),*).into_fallible()?;
+                        Ok(func_results.encode_results(results))
+                    },
+                );
+                (signature, trampoline)
+            }
+        }
     };
 }
 for_each_tuple!(impl_into_func);
@@ -195,6 +261,16 @@ impl_wasm_type! {
     type ExternRef = ExternRef;
 }
 
+impl<T> WasmTy for TypedExternRef<T>
+where
+    T: 'static + Any + Send + Sync,
+{
+    #[inline]
+    fn ty() -> ValType {
+        ValType::ExternRef
+    }
+}
+
 /// A list of [`WasmTy`] types.
 ///
 /// # Note
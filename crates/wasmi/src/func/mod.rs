@@ -1,19 +1,32 @@
+mod async_func;
 mod caller;
 mod error;
 mod func_inout;
 mod func_type;
 mod funcref;
+mod info;
 mod into_func;
+#[cfg(feature = "std")]
+mod thread_bound;
 mod typed_func;
 
+#[cfg(feature = "std")]
+use self::async_func::DeadlineGuard;
 use self::func_inout::FuncFinished;
-pub(crate) use self::typed_func::CallResultsTuple;
+#[cfg(feature = "std")]
+pub use self::thread_bound::ThreadBound;
+pub(crate) use self::{
+    async_func::{AsyncCallPending, HostFuture, PendingHostFuture},
+    caller::HostReturnCall,
+    typed_func::CallResultsTuple,
+};
 pub use self::{
     caller::Caller,
     error::FuncError,
     func_inout::FuncInOut,
     func_type::FuncType,
     funcref::FuncRef,
+    info::FuncInfo,
     into_func::{IntoFunc, WasmRet, WasmTy, WasmTyList},
     typed_func::{TypedFunc, WasmParams, WasmResults},
 };
@@ -25,9 +38,18 @@ use super::{
     StoreContext,
     Stored,
 };
-use crate::{collections::arena::ArenaIndex, engine::ResumableCall, Engine, Error, Val};
-use alloc::{boxed::Box, sync::Arc};
-use core::{fmt, fmt::Debug, num::NonZeroU32};
+use crate::{
+    collections::arena::ArenaIndex,
+    core::UntypedVal,
+    engine::ResumableCall,
+    value::WithType,
+    Engine,
+    Error,
+    FuncDisassembly,
+    Val,
+};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{fmt, fmt::Debug, future::Future, num::NonZeroU32};
 
 /// A raw index to a function entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -202,6 +224,8 @@ pub struct HostFuncTrampolineEntity<T> {
     ty: FuncType,
     /// The trampoline of the associated host function.
     trampoline: TrampolineEntity<T>,
+    /// Is `true` if the associated host function is async.
+    is_async: bool,
 }
 
 impl<T> Clone for HostFuncTrampolineEntity<T> {
@@ -209,6 +233,7 @@ impl<T> Clone for HostFuncTrampolineEntity<T> {
         Self {
             ty: self.ty.clone(),
             trampoline: self.trampoline.clone(),
+            is_async: self.is_async,
         }
     }
 }
@@ -245,14 +270,88 @@ impl<T> HostFuncTrampolineEntity<T> {
             func(caller, params, results)?;
             Ok(func_results.encode_results_from_slice(results).unwrap())
         });
-        Self { ty, trampoline }
+        Self {
+            ty,
+            trampoline,
+            is_async: false,
+        }
+    }
+
+    /// Creates a new host function trampoline from the given untyped closure.
+    ///
+    /// Unlike [`HostFuncTrampolineEntity::new`] this does not allocate per invocation.
+    pub fn new_untyped(
+        ty: FuncType,
+        func: impl Fn(Caller<'_, T>, &mut [UntypedVal]) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Self {
+        let trampoline = <TrampolineEntity<T>>::new(move |caller, args| {
+            func(caller, args.into_untyped())?;
+            Ok(FuncFinished {})
+        });
+        Self {
+            ty,
+            trampoline,
+            is_async: false,
+        }
     }
 
     /// Creates a new host function trampoline from the given statically typed closure.
     pub fn wrap<Params, Results>(func: impl IntoFunc<T, Params, Results>) -> Self {
         let (ty, trampoline) = func.into_func();
         // let ty = engine.alloc_func_type(signature);
-        Self { ty, trampoline }
+        Self {
+            ty,
+            trampoline,
+            is_async: false,
+        }
+    }
+
+    /// Creates a new async host function trampoline from the given statically typed closure.
+    ///
+    /// # Note
+    ///
+    /// Upon invocation the trampoline stores the future returned by `func` in the
+    /// [`Store`](crate::Store) and suspends the Wasm execution with an [`AsyncCallPending`]
+    /// host error which is then resolved by [`Func::call_async`].
+    pub fn wrap_async<Params, Results, F>(
+        func: impl Fn(Caller<'_, T>, Params) -> F + Send + Sync + 'static,
+    ) -> Self
+    where
+        Params: WasmTyList,
+        Results: WasmTyList,
+        F: Future<Output = Result<Results, Error>> + Send + 'static,
+    {
+        let ty = FuncType::new(Params::types(), Results::types());
+        let trampoline = <TrampolineEntity<T>>::new(move |mut caller, args| {
+            let (params, _): (Params, _) = args.decode_params();
+            let future = func(caller.reborrow(), params);
+            let future: HostFuture = Box::pin(async move {
+                let results = future.await?;
+                let values = results
+                    .values()
+                    .into_iter()
+                    .zip(Results::types())
+                    .map(|(value, ty)| value.with_type(ty))
+                    .collect::<Vec<Val>>();
+                Ok(values)
+            });
+            caller
+                .as_context_mut()
+                .store
+                .inner
+                .set_pending_future(future);
+            Err(Error::host(AsyncCallPending))
+        });
+        Self {
+            ty,
+            trampoline,
+            is_async: true,
+        }
+    }
+
+    /// Returns `true` if the host function is async.
+    pub fn is_async(&self) -> bool {
+        self.is_async
     }
 
     /// Returns the [`FuncType`] of the host function.
@@ -368,6 +467,42 @@ impl Func {
             .alloc_func(host_func.into())
     }
 
+    /// Creates a new [`Func`] with the given arguments operating on untyped values.
+    ///
+    /// This is an efficient alternative to [`Func::new`] for host functions whose signature
+    /// is only known at runtime, e.g. when bridging RPC or FFI layers, since it does not
+    /// allocate for every invocation.
+    ///
+    /// - `ty`:
+    ///   The signature that the given closure adheres to,
+    ///   used to indicate what the inputs and outputs are.
+    /// - `func`:
+    ///   The native code invoked whenever this Func will be called.
+    ///   The closure is provided a [`Caller`] as its first argument and a buffer
+    ///   holding the untyped parameters upon invocation as its second argument.
+    ///   The closure must write its untyped results into the same buffer.
+    ///   The length of the buffer is the maximum of the number of parameters and results of `ty`.
+    ///
+    /// # Note
+    ///
+    /// It is the responsibility of the caller of [`Func::new_untyped`] to guarantee that
+    /// the results written into the buffer match the result types of `ty`. Otherwise the
+    /// remaining computation may fail in unexpected ways.
+    pub fn new_untyped<T>(
+        mut ctx: impl AsContextMut<Data = T>,
+        ty: FuncType,
+        func: impl Fn(Caller<'_, T>, &mut [UntypedVal]) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Self {
+        let host_func = HostFuncTrampolineEntity::new_untyped(ty.clone(), func);
+        let trampoline = host_func.trampoline().clone();
+        let func = ctx.as_context_mut().store.alloc_trampoline(trampoline);
+        let host_func = HostFuncEntity::new(ctx.as_context().engine(), &ty, func);
+        ctx.as_context_mut()
+            .store
+            .inner
+            .alloc_func(host_func.into())
+    }
+
     /// Creates a new host function from the given closure.
     pub fn wrap<T, Params, Results>(
         mut ctx: impl AsContextMut<Data = T>,
@@ -384,6 +519,17 @@ impl Func {
             .alloc_func(host_func.into())
     }
 
+    /// Creates a new host function from the given closure that is neither `Send` nor `Sync`.
+    ///
+    /// The closure is bound to the current thread. For more information see [`ThreadBound`].
+    #[cfg(feature = "std")]
+    pub fn wrap_local<T, Params, Results, F>(ctx: impl AsContextMut<Data = T>, func: F) -> Self
+    where
+        ThreadBound<F>: IntoFunc<T, Params, Results>,
+    {
+        Self::wrap(ctx, ThreadBound::new(func))
+    }
+
     /// Returns the signature of the function.
     pub(crate) fn ty_dedup<'a, T: 'a>(
         &self,
@@ -392,6 +538,52 @@ impl Func {
         ctx.into().store.inner.resolve_func(self).ty_dedup()
     }
 
+    /// Returns the [`FuncInfo`] metadata of the [`Func`].
+    ///
+    /// Returns `None` if the [`Func`] is a host function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Func`].
+    pub fn info(&self, ctx: impl AsContext) -> Option<FuncInfo> {
+        let store = &ctx.as_context().store.inner;
+        let FuncEntity::Wasm(func) = store.resolve_func(self) else {
+            return None;
+        };
+        let instance = store.resolve_instance(func.instance());
+        let module = instance.module()?.clone();
+        let index = instance.func_index(self)?;
+        Some(FuncInfo::new(module, index))
+    }
+
+    /// Disassembles the Wasmi bytecode of the [`Func`].
+    ///
+    /// Returns `None` if the [`Func`] is a host function.
+    ///
+    /// # Note
+    ///
+    /// - Lazily compiled functions are compiled without charging fuel.
+    /// - This is intended for analysis tools and test assertions on the compiled Wasmi bytecode.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Func`] fails Wasm to Wasmi bytecode translation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Func`].
+    pub fn disassemble(&self, ctx: impl AsContext) -> Result<Option<FuncDisassembly>, Error> {
+        let store = &ctx.as_context().store.inner;
+        let FuncEntity::Wasm(func) = store.resolve_func(self) else {
+            return Ok(None);
+        };
+        let instance = store.resolve_instance(func.instance());
+        let (Some(module), Some(index)) = (instance.module(), instance.func_index(self)) else {
+            return Ok(None);
+        };
+        module.disassemble_func(index, func.func_body()).map(Some)
+    }
+
     /// Returns the function type of the [`Func`].
     pub fn ty(&self, ctx: impl AsContext) -> FuncType {
         ctx.as_context()
@@ -469,6 +661,114 @@ impl Func {
             .map(ResumableCall::new)
     }
 
+    /// Calls the Wasm or host function with the given inputs and awaits all async host functions.
+    ///
+    /// The result is written back into the `outputs` buffer.
+    ///
+    /// # Note
+    ///
+    /// Async host functions, e.g. defined via [`Linker::func_wrap_async`], suspend the
+    /// Wasm execution until their future has been resolved. Calling them via the synchronous
+    /// [`Func::call`] or [`Func::call_resumable`] APIs results in an error.
+    ///
+    /// # Errors
+    ///
+    /// - If the function returned a [`Error`].
+    /// - If the future of an async host function resolved to an [`Error`].
+    /// - If the types of the `inputs` do not match the expected types for the
+    ///   function signature of `self`.
+    /// - If the number of input values does not match the expected number of
+    ///   inputs required by the function signature of `self`.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the function signature of `self`.
+    ///
+    /// [`Linker::func_wrap_async`]: crate::Linker::func_wrap_async
+    pub async fn call_async<T>(
+        &self,
+        mut ctx: impl AsContextMut<Data = T>,
+        inputs: &[Val],
+        outputs: &mut [Val],
+    ) -> Result<(), Error> {
+        let mut invocation = match self.call_resumable(&mut ctx, inputs, outputs) {
+            Ok(ResumableCall::Finished) => return Ok(()),
+            Ok(ResumableCall::Resumable(invocation)) => invocation,
+            Err(error) if error.downcast_ref::<AsyncCallPending>().is_some() => {
+                // Note: `self` is an async host function that has been called from the host.
+                let results = Self::await_pending_future(&mut ctx).await?;
+                outputs.clone_from_slice(&results);
+                return Ok(());
+            }
+            Err(error) => return Err(error),
+        };
+        loop {
+            if invocation
+                .host_error()
+                .downcast_ref::<AsyncCallPending>()
+                .is_none()
+            {
+                return Err(invocation.into_host_error());
+            }
+            let results = Self::await_pending_future(&mut ctx).await?;
+            ctx.as_context().store.inner.check_deadline()?;
+            invocation = match invocation.resume(&mut ctx, &results, outputs)? {
+                ResumableCall::Finished => return Ok(()),
+                ResumableCall::Resumable(invocation) => invocation,
+            };
+        }
+    }
+
+    /// Calls the Wasm or host function via [`Func::call_async`] and cancels it once `timeout` elapsed.
+    ///
+    /// The result is written back into the `outputs` buffer.
+    ///
+    /// # Note
+    ///
+    /// - Exceeding `timeout` traps the Wasm execution with [`TrapCode::DeadlineExceeded`] at its
+    ///   next safepoint, such as a branch or call. The deadline is checked periodically by the
+    ///   execution itself and thus neither requires background tickers nor a specific async runtime.
+    /// - The execution may run slightly beyond `timeout` until it reaches its next deadline check.
+    /// - The deadline is also checked once the future of an async host function resolved.
+    ///   However, pending futures of async host functions are not interrupted. Use the timeout
+    ///   utilities of the async runtime, e.g. `tokio::time::timeout`, on the returned future to
+    ///   bound them as well.
+    /// - Nested timed calls, e.g. from within host functions, never extend the deadline of
+    ///   their enclosing timed call.
+    ///
+    /// # Errors
+    ///
+    /// - If `timeout` elapsed before the call finished: [`TrapCode::DeadlineExceeded`]
+    /// - Same as [`Func::call_async`].
+    ///
+    /// [`TrapCode::DeadlineExceeded`]: crate::core::TrapCode::DeadlineExceeded
+    #[cfg(feature = "std")]
+    pub async fn call_async_with_timeout<T>(
+        &self,
+        ctx: impl AsContextMut<Data = T>,
+        inputs: &[Val],
+        outputs: &mut [Val],
+        timeout: std::time::Duration,
+    ) -> Result<(), Error> {
+        let mut guard = DeadlineGuard::new(ctx, timeout);
+        self.call_async(guard.ctx_mut(), inputs, outputs).await
+    }
+
+    /// Awaits the future of the last suspended async host function call of the store.
+    ///
+    /// # Panics
+    ///
+    /// If there is no suspended async host function call.
+    async fn await_pending_future<T>(
+        mut ctx: impl AsContextMut<Data = T>,
+    ) -> Result<Vec<Val>, Error> {
+        let future = ctx
+            .as_context_mut()
+            .store
+            .inner
+            .take_pending_future()
+            .unwrap_or_else(|| panic!("missing future for suspended async host function call"));
+        future.await
+    }
+
     /// Verify that the `inputs` and `outputs` value types match the function signature.
     ///
     /// Since [`Func`] is a dynamically typed function instance there is
@@ -0,0 +1,91 @@
+use super::FuncError;
+use crate::Error;
+use core::{fmt, mem::ManuallyDrop};
+use std::thread::{self, ThreadId};
+
+/// A host function closure that is bound to the thread that created it.
+///
+/// This allows to use closures that are neither `Send` nor `Sync` as host functions,
+/// e.g. closures capturing an `Rc`, for embedders that never move the
+/// [`Store`](crate::Store) across threads.
+///
+/// # Note
+///
+/// - Calling the host function from any other thread than the one that created
+///   the [`ThreadBound`] fails with [`FuncError::CalledFromForeignThread`].
+/// - Dropping a [`ThreadBound`] on any other thread than the one that created
+///   it leaks the underlying closure instead of dropping it.
+///
+/// # Example
+///
+/// ```
+/// use std::{cell::Cell, rc::Rc};
+/// use wasmi::{Func, Store, ThreadBound};
+///
+/// let mut store = <Store<()>>::default();
+/// let counter = Rc::new(Cell::new(0));
+/// let func = Func::wrap(&mut store, ThreadBound::new({
+///     let counter = counter.clone();
+///     move || counter.set(counter.get() + 1)
+/// }));
+/// func.call(&mut store, &[], &mut []).unwrap();
+/// assert_eq!(counter.get(), 1);
+/// ```
+pub struct ThreadBound<F> {
+    /// The thread-bound closure.
+    func: ManuallyDrop<F>,
+    /// The identifier of the thread that created the [`ThreadBound`].
+    thread: ThreadId,
+}
+
+impl<F> fmt::Debug for ThreadBound<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThreadBound")
+            .field("thread", &self.thread)
+            .finish_non_exhaustive()
+    }
+}
+
+// Safety: The underlying closure is only ever accessed and dropped on the thread
+//         that created the `ThreadBound` which is checked by `ThreadBound::get`
+//         and `ThreadBound::drop`. On any other thread it is merely moved or leaked.
+unsafe impl<F> Send for ThreadBound<F> {}
+
+// Safety: See the `Send` implementation above.
+unsafe impl<F> Sync for ThreadBound<F> {}
+
+impl<F> ThreadBound<F> {
+    /// Binds `func` to the current thread.
+    pub fn new(func: F) -> Self {
+        Self {
+            func: ManuallyDrop::new(func),
+            thread: thread::current().id(),
+        }
+    }
+
+    /// Returns `true` if the current thread is the thread that created `self`.
+    fn is_owning_thread(&self) -> bool {
+        thread::current().id() == self.thread
+    }
+
+    /// Returns a shared reference to the underlying closure.
+    ///
+    /// # Errors
+    ///
+    /// If the current thread is not the thread that created `self`.
+    pub(crate) fn get(&self) -> Result<&F, Error> {
+        if !self.is_owning_thread() {
+            return Err(Error::from(FuncError::CalledFromForeignThread));
+        }
+        Ok(&self.func)
+    }
+}
+
+impl<F> Drop for ThreadBound<F> {
+    fn drop(&mut self) {
+        if self.is_owning_thread() {
+            // Safety: `self.func` is never accessed again after this point.
+            unsafe { ManuallyDrop::drop(&mut self.func) }
+        }
+    }
+}
@@ -1,12 +1,18 @@
+#[cfg(feature = "std")]
+use super::DeadlineGuard;
 use super::{into_func::WasmTyList, Func};
 use crate::{
     core::UntypedVal,
     engine::{CallParams, CallResults},
+    value::WithType,
     AsContext,
     AsContextMut,
     Error,
+    FuelSchedule,
     TypedResumableCall,
+    Val,
 };
+use alloc::vec::Vec;
 use core::{fmt, fmt::Debug, marker::PhantomData};
 
 /// A typed [`Func`] instance.
@@ -103,6 +109,39 @@ where
         )
     }
 
+    /// Calls this Wasm or host function with the specified parameters using the fuel `schedule`.
+    ///
+    /// Unlike [`TypedFunc::call`] this charges fuel according to `schedule` instead of
+    /// the [`FuelSchedule`] of the [`Engine`] for the entire call, including nested calls
+    /// from host functions.
+    ///
+    /// # Note
+    ///
+    /// Calling with other [`FuelSchedule`]s than the one of the [`Engine`] requires
+    /// [`Config::per_call_fuel_schedules`] to be enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`TypedFunc`].
+    ///
+    /// # Errors
+    ///
+    /// - If `schedule` is unavailable for the call.
+    /// - If the execution of the called Wasm function traps.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Config::per_call_fuel_schedules`]: crate::Config::per_call_fuel_schedules
+    pub fn call_with_schedule(
+        &self,
+        mut ctx: impl AsContextMut,
+        params: Params,
+        schedule: FuelSchedule,
+    ) -> Result<Results, Error> {
+        ctx.as_context_mut()
+            .store
+            .with_fuel_schedule(schedule, |store| self.call(store, params))
+    }
+
     /// Calls this Wasm or host function with the specified parameters.
     ///
     /// Returns a resumable handle to the function invocation upon
@@ -136,6 +175,63 @@ where
             )
             .map(TypedResumableCall::new)
     }
+
+    /// Calls this Wasm or host function with the specified parameters and awaits all
+    /// async host functions.
+    ///
+    /// For more information see [`Func::call_async`].
+    ///
+    /// # Errors
+    ///
+    /// - If the function returned a [`Error`].
+    /// - If the future of an async host function resolved to an [`Error`].
+    pub async fn call_async(
+        &self,
+        mut ctx: impl AsContextMut,
+        params: Params,
+    ) -> Result<Results, Error> {
+        let inputs = params
+            .values()
+            .into_iter()
+            .zip(Params::types())
+            .map(|(value, ty)| value.with_type(ty))
+            .collect::<Vec<Val>>();
+        let mut outputs = Results::types()
+            .into_iter()
+            .map(Val::default)
+            .collect::<Vec<Val>>();
+        self.func
+            .call_async(&mut ctx, &inputs, &mut outputs)
+            .await?;
+        let results = outputs
+            .into_iter()
+            .map(UntypedVal::from)
+            .collect::<Vec<UntypedVal>>();
+        Ok(Results::from_values(&results)
+            .unwrap_or_else(|| panic!("encountered invalid results for typed function call")))
+    }
+
+    /// Calls this Wasm or host function via [`TypedFunc::call_async`] and cancels it once
+    /// `timeout` elapsed.
+    ///
+    /// For more information see [`Func::call_async_with_timeout`].
+    ///
+    /// # Errors
+    ///
+    /// - If `timeout` elapsed before the call finished: [`TrapCode::DeadlineExceeded`]
+    /// - Same as [`TypedFunc::call_async`].
+    ///
+    /// [`TrapCode::DeadlineExceeded`]: crate::core::TrapCode::DeadlineExceeded
+    #[cfg(feature = "std")]
+    pub async fn call_async_with_timeout(
+        &self,
+        ctx: impl AsContextMut,
+        params: Params,
+        timeout: std::time::Duration,
+    ) -> Result<Results, Error> {
+        let mut guard = DeadlineGuard::new(ctx, timeout);
+        self.call_async(guard.ctx_mut(), params).await
+    }
 }
 
 impl<Params> CallParams for Params
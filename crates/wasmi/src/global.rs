@@ -150,6 +150,8 @@ pub struct GlobalEntity {
     value: UntypedVal,
     /// The type of the global variable.
     ty: GlobalType,
+    /// Whether writes of Wasm code to the global variable are observed.
+    watched: bool,
 }
 
 impl GlobalEntity {
@@ -158,6 +160,7 @@ impl GlobalEntity {
         Self {
             ty: GlobalType::new(initial_value.ty(), mutability),
             value: initial_value.into(),
+            watched: false,
         }
     }
 
@@ -208,6 +211,16 @@ impl GlobalEntity {
         self.value
     }
 
+    /// Returns `true` if writes of Wasm code to the global variable are observed.
+    pub(crate) fn is_watched(&self) -> bool {
+        self.watched
+    }
+
+    /// Sets whether writes of Wasm code to the global variable are observed.
+    pub(crate) fn set_watched(&mut self, watched: bool) {
+        self.watched = watched;
+    }
+
     /// Returns a pointer to the untyped value of the global variable.
     pub(crate) fn get_untyped_ptr(&mut self) -> NonNull<UntypedVal> {
         NonNull::from(&mut self.value)
@@ -275,4 +288,25 @@ impl Global {
     pub fn get(&self, ctx: impl AsContext) -> Val {
         ctx.as_context().store.inner.resolve_global(self).get()
     }
+
+    /// Enables or disables observing writes of Wasm code to the global variable.
+    ///
+    /// While enabled the [`Store::global_hook`] is invoked whenever Wasm code
+    /// writes to the global variable via `global.set`.
+    ///
+    /// # Note
+    ///
+    /// Writes by the host via [`Global::set`] are never observed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Global`].
+    ///
+    /// [`Store::global_hook`]: crate::Store::global_hook
+    pub fn watch(&self, mut ctx: impl AsContextMut, enable: bool) {
+        ctx.as_context_mut()
+            .store
+            .inner
+            .set_global_watched(self, enable)
+    }
 }
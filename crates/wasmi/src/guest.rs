@@ -0,0 +1,361 @@
+//! Helpers to allocate buffers within the linear memory of Wasm guests.
+//!
+//! Wasm guests conventionally export an allocator that hosts use to pass data into
+//! the guest. The [`GuestAllocator`] drives such an exported allocator and hands out
+//! [`GuestPtr`] buffers that free themselves once dropped.
+
+use crate::{
+    memory::MemoryError,
+    AsContext,
+    AsContextMut,
+    Error,
+    Instance,
+    Memory,
+    StoreContext,
+    StoreContextMut,
+    TypedFunc,
+};
+use core::ops::Range;
+
+/// The exported function that allocates guest buffers.
+#[derive(Debug, Copy, Clone)]
+enum AllocFunc {
+    /// A `malloc(size) -> ptr` function.
+    Malloc(TypedFunc<i32, i32>),
+    /// A `realloc(old_ptr, old_size, align, new_size) -> ptr` function of the canonical ABI.
+    Realloc(TypedFunc<(i32, i32, i32, i32), i32>),
+}
+
+/// The exported function that frees guest buffers.
+#[derive(Debug, Copy, Clone)]
+enum FreeFunc {
+    /// A `free(ptr)` function.
+    Free(TypedFunc<i32, ()>),
+    /// A `free(ptr, size, align)` function of the canonical ABI.
+    CanonicalFree(TypedFunc<(i32, i32, i32), ()>),
+}
+
+/// Allocates buffers within the default linear memory of a Wasm guest [`Instance`].
+///
+/// The [`GuestAllocator`] drives the allocator exported by the guest which must be one of:
+///
+/// - `malloc(size: i32) -> i32` and optionally `free(ptr: i32)`
+/// - `canonical_abi_realloc(old_ptr: i32, old_size: i32, align: i32, new_size: i32) -> i32`
+///   and optionally `canonical_abi_free(ptr: i32, size: i32, align: i32)`
+/// - `cabi_realloc(old_ptr: i32, old_size: i32, align: i32, new_size: i32) -> i32`
+///
+/// Buffers of guests without an exported free function are never freed.
+///
+/// # Note
+///
+/// Only 32-bit linear memories are supported.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{Engine, GuestAllocator, Linker, Module, Store};
+/// # let engine = Engine::default();
+/// # let wasm = r#"
+/// #     (module
+/// #         (memory (export "memory") 1)
+/// #         (global $next (mut i32) (i32.const 16))
+/// #         (func (export "malloc") (param i32) (result i32)
+/// #             (global.get $next)
+/// #             (global.set $next (i32.add (global.get $next) (local.get 0)))
+/// #         )
+/// #         (func (export "free") (param i32))
+/// #         (func (export "first") (param i32 i32) (result i32)
+/// #             (i32.load8_u (local.get 0))
+/// #         )
+/// #     )
+/// # "#;
+/// # let module = Module::new(&engine, wasm).unwrap();
+/// # let mut store = Store::new(&engine, ());
+/// # let instance = <Linker<()>>::new(&engine)
+/// #     .instantiate(&mut store, &module).unwrap()
+/// #     .start(&mut store).unwrap();
+/// let first = instance.get_typed_func::<(i32, i32), i32>(&store, "first").unwrap();
+/// let allocator = GuestAllocator::new(&store, instance).unwrap();
+/// let mut guest = allocator.alloc_str(&mut store, "wasmi").unwrap();
+/// let (ptr, len) = (guest.ptr() as i32, guest.len() as i32);
+/// assert_eq!(first.call(&mut guest, (ptr, len)).unwrap(), i32::from(b'w'));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct GuestAllocator {
+    /// The default linear memory of the guest.
+    memory: Memory,
+    /// The exported function that allocates guest buffers.
+    alloc: AllocFunc,
+    /// The exported function that frees guest buffers if any.
+    free: Option<FreeFunc>,
+}
+
+impl GuestAllocator {
+    /// Creates a new [`GuestAllocator`] for the allocator exported by `instance`.
+    ///
+    /// # Errors
+    ///
+    /// - If `instance` has no default linear memory.
+    /// - If `instance` exports none of the supported allocator functions.
+    /// - If the exported allocator functions have unexpected signatures.
+    ///
+    /// # Panics
+    ///
+    /// If `ctx` does not own `instance`.
+    pub fn new(ctx: impl AsContext, instance: Instance) -> Result<Self, Error> {
+        let ctx = &ctx;
+        let memory = instance
+            .get_memory_by_index(ctx, 0)
+            .ok_or(MemoryError::MissingDefaultMemory)?;
+        let has_func = |name: &str| instance.get_func(ctx, name).is_some();
+        let (alloc, free) = if has_func("malloc") {
+            let alloc = AllocFunc::Malloc(instance.get_typed_func(ctx, "malloc")?);
+            let free = match has_func("free") {
+                true => Some(FreeFunc::Free(instance.get_typed_func(ctx, "free")?)),
+                false => None,
+            };
+            (alloc, free)
+        } else if has_func("canonical_abi_realloc") {
+            let alloc = AllocFunc::Realloc(instance.get_typed_func(ctx, "canonical_abi_realloc")?);
+            let free = match has_func("canonical_abi_free") {
+                true => Some(FreeFunc::CanonicalFree(
+                    instance.get_typed_func(ctx, "canonical_abi_free")?,
+                )),
+                false => None,
+            };
+            (alloc, free)
+        } else {
+            (
+                AllocFunc::Realloc(instance.get_typed_func(ctx, "cabi_realloc")?),
+                None,
+            )
+        };
+        Ok(Self {
+            memory,
+            alloc,
+            free,
+        })
+    }
+
+    /// Returns the default linear memory of the guest.
+    pub fn memory(&self) -> Memory {
+        self.memory
+    }
+
+    /// Allocates a guest buffer of `len` bytes aligned to `align` bytes.
+    ///
+    /// # Note
+    ///
+    /// The `align` is ignored by guests that export `malloc`.
+    ///
+    /// # Errors
+    ///
+    /// - If the guest allocator traps.
+    /// - If the guest allocator fails to allocate the buffer.
+    pub fn alloc<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContextMut<'a, T>>,
+        len: u32,
+        align: u32,
+    ) -> Result<GuestPtr<'a, T>, Error> {
+        let mut ctx = ctx.into();
+        let ptr = match self.alloc {
+            AllocFunc::Malloc(malloc) => malloc.call(&mut ctx, len as i32)?,
+            AllocFunc::Realloc(realloc) => {
+                realloc.call(&mut ctx, (0, 0, align as i32, len as i32))?
+            }
+        } as u32;
+        let end = u64::from(ptr) + u64::from(len);
+        if (ptr == 0 && len != 0) || end > self.memory.size_in_bytes(&ctx) {
+            return Err(Error::from(MemoryError::GuestAllocationFailed));
+        }
+        Ok(GuestPtr {
+            ctx,
+            allocator: *self,
+            ptr,
+            len,
+            align,
+            owned: true,
+        })
+    }
+
+    /// Allocates a guest buffer holding a copy of `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// - If `bytes` does not fit into a 32-bit linear memory.
+    /// - If the guest allocator traps or fails to allocate the buffer.
+    pub fn alloc_bytes<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContextMut<'a, T>>,
+        bytes: &[u8],
+    ) -> Result<GuestPtr<'a, T>, Error> {
+        let len = u32::try_from(bytes.len()).map_err(|_| MemoryError::GuestAllocationFailed)?;
+        let mut guest = self.alloc(ctx, len, 1)?;
+        guest.as_bytes_mut().copy_from_slice(bytes);
+        Ok(guest)
+    }
+
+    /// Allocates a guest buffer holding a copy of the UTF-8 encoded `string`.
+    ///
+    /// The guest buffer has no nul terminator.
+    ///
+    /// # Errors
+    ///
+    /// - If `string` does not fit into a 32-bit linear memory.
+    /// - If the guest allocator traps or fails to allocate the buffer.
+    pub fn alloc_str<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContextMut<'a, T>>,
+        string: &str,
+    ) -> Result<GuestPtr<'a, T>, Error> {
+        self.alloc_bytes(ctx, string.as_bytes())
+    }
+}
+
+/// A buffer within the linear memory of a Wasm guest that is freed when dropped.
+///
+/// Created via [`GuestAllocator`].
+///
+/// # Note
+///
+/// - A [`GuestPtr`] provides access to the [`Store`] it borrows via [`AsContextMut`]
+///   so that guest functions can be called while the buffer is alive.
+/// - Errors of the guest free function are ignored when a [`GuestPtr`] is dropped.
+///   Use [`GuestPtr::free`] to handle them instead.
+///
+/// [`Store`]: crate::Store
+#[derive(Debug)]
+pub struct GuestPtr<'a, T> {
+    /// The store context of the guest.
+    ctx: StoreContextMut<'a, T>,
+    /// The allocator of the guest.
+    allocator: GuestAllocator,
+    /// The address of the buffer within the default linear memory of the guest.
+    ptr: u32,
+    /// The length of the buffer in bytes.
+    len: u32,
+    /// The alignment of the buffer in bytes.
+    align: u32,
+    /// Is `true` if the buffer is freed when dropped.
+    owned: bool,
+}
+
+impl<T> GuestPtr<'_, T> {
+    /// Returns the address of the buffer within the default linear memory of the guest.
+    pub fn ptr(&self) -> u32 {
+        self.ptr
+    }
+
+    /// Returns the length of the buffer in bytes.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the byte range of the buffer within the default linear memory of the guest.
+    fn range(&self) -> Range<usize> {
+        let start = self.ptr as usize;
+        start..(start + self.len as usize)
+    }
+
+    /// Returns a shared slice to the bytes of the buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.allocator.memory.data(&self.ctx)[self.range()]
+    }
+
+    /// Returns an exclusive slice to the bytes of the buffer.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        let range = self.range();
+        &mut self.allocator.memory.data_mut(&mut self.ctx)[range]
+    }
+
+    /// Reads `n` bytes from `buffer[offset..offset+n]` into `bytes`
+    /// where `n` is the length of `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds of the buffer.
+    pub fn read(&self, offset: u32, bytes: &mut [u8]) -> Result<(), MemoryError> {
+        let slice = (offset as usize)
+            .checked_add(bytes.len())
+            .and_then(|end| self.as_bytes().get(offset as usize..end))
+            .ok_or(MemoryError::OutOfBoundsAccess)?;
+        bytes.copy_from_slice(slice);
+        Ok(())
+    }
+
+    /// Writes `n` bytes to `buffer[offset..offset+n]` from `bytes`
+    /// where `n` is the length of `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds of the buffer.
+    pub fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), MemoryError> {
+        let end = (offset as usize)
+            .checked_add(bytes.len())
+            .ok_or(MemoryError::OutOfBoundsAccess)?;
+        self.as_bytes_mut()
+            .get_mut(offset as usize..end)
+            .ok_or(MemoryError::OutOfBoundsAccess)?
+            .copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Frees the buffer via the guest free function if any.
+    ///
+    /// # Errors
+    ///
+    /// If the guest free function traps.
+    pub fn free(mut self) -> Result<(), Error> {
+        self.owned = false;
+        self.free_buffer()
+    }
+
+    /// Transfers the ownership of the buffer to the guest and returns its address.
+    ///
+    /// The buffer is no longer freed by the host.
+    pub fn into_raw(mut self) -> u32 {
+        self.owned = false;
+        self.ptr
+    }
+
+    /// Calls the guest free function for the buffer if any.
+    fn free_buffer(&mut self) -> Result<(), Error> {
+        let (ptr, len, align) = (self.ptr as i32, self.len as i32, self.align as i32);
+        match self.allocator.free {
+            Some(FreeFunc::Free(free)) => free.call(&mut self.ctx, ptr),
+            Some(FreeFunc::CanonicalFree(free)) => free.call(&mut self.ctx, (ptr, len, align)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T> Drop for GuestPtr<'_, T> {
+    fn drop(&mut self) {
+        if self.owned {
+            // Note: errors cannot be propagated from `drop`, see `GuestPtr::free`.
+            let _ = self.free_buffer();
+        }
+    }
+}
+
+impl<T> AsContext for GuestPtr<'_, T> {
+    type Data = T;
+
+    #[inline]
+    fn as_context(&self) -> StoreContext<'_, Self::Data> {
+        self.ctx.as_context()
+    }
+}
+
+impl<T> AsContextMut for GuestPtr<'_, T> {
+    #[inline]
+    fn as_context_mut(&mut self) -> StoreContextMut<'_, Self::Data> {
+        self.ctx.as_context_mut()
+    }
+}
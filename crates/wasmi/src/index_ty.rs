@@ -24,6 +24,7 @@ impl IndexType {
     }
 
     /// Returns the minimum [`IndexType`] between `self` and `other`.
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub fn min(&self, other: &Self) -> Self {
         match (self, other) {
             (IndexType::I64, IndexType::I64) => IndexType::I64,
@@ -18,6 +18,7 @@ use alloc::{boxed::Box, sync::Arc, vec::Vec};
 /// A module instance entity builder.
 #[derive(Debug)]
 pub struct InstanceEntityBuilder {
+    module: Module,
     func_types: Arc<[DedupFuncType]>,
     tables: Vec<Table>,
     funcs: Vec<Func>,
@@ -58,6 +59,7 @@ impl InstanceEntityBuilder {
             }
         }
         Self {
+            module: module.clone(),
             func_types: module.func_types_cloned(),
             tables: vec_with_capacity_exact(len_tables),
             funcs: vec_with_capacity_exact(len_funcs),
@@ -137,6 +139,11 @@ impl InstanceEntityBuilder {
             .unwrap_or_else(|| panic!("missing `Func` at index: {index}"))
     }
 
+    /// Returns the functions of the [`InstanceEntity`] under construction.
+    pub fn funcs(&self) -> &[Func] {
+        &self.funcs[..]
+    }
+
     /// Pushes a new [`Memory`] to the [`InstanceEntity`] under construction.
     pub fn push_memory(&mut self, memory: Memory) {
         self.memories.push(memory);
@@ -186,6 +193,7 @@ impl InstanceEntityBuilder {
     pub fn finish(self) -> InstanceEntity {
         InstanceEntity {
             initialized: true,
+            module: Some(self.module),
             func_types: self.func_types,
             tables: self.tables.into(),
             funcs: self.funcs.into(),
@@ -51,6 +51,7 @@ impl ArenaIndex for InstanceIdx {
 #[derive(Debug)]
 pub struct InstanceEntity {
     initialized: bool,
+    module: Option<Module>,
     func_types: Arc<[DedupFuncType]>,
     tables: Box<[Table]>,
     funcs: Box<[Func]>,
@@ -66,6 +67,7 @@ impl InstanceEntity {
     pub fn uninitialized() -> InstanceEntity {
         Self {
             initialized: false,
+            module: None,
             func_types: Arc::new([]),
             tables: [].into(),
             funcs: [].into(),
@@ -87,6 +89,20 @@ impl InstanceEntity {
         self.initialized
     }
 
+    /// Returns the [`Module`] from which the [`InstanceEntity`] has been instantiated if any.
+    pub fn module(&self) -> Option<&Module> {
+        self.module.as_ref()
+    }
+
+    /// Returns the index of `func` within the function index space of the [`InstanceEntity`] if any.
+    pub fn func_index(&self, func: &Func) -> Option<u32> {
+        let index = self
+            .funcs
+            .iter()
+            .position(|f| f.as_inner() == func.as_inner())?;
+        u32::try_from(index).ok()
+    }
+
     /// Returns the linear memory at the `index` if any.
     pub fn get_memory(&self, index: u32) -> Option<Memory> {
         self.memories.get(index as usize).copied()
@@ -222,6 +238,20 @@ impl Instance {
             .get_func(index)
     }
 
+    /// Returns the linear memory at the `index` if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    pub(crate) fn get_memory_by_index(&self, store: impl AsContext, index: u32) -> Option<Memory> {
+        store
+            .as_context()
+            .store
+            .inner
+            .resolve_instance(self)
+            .get_memory(index)
+    }
+
     /// Returns the value exported to the given `name` if any.
     ///
     /// # Panics
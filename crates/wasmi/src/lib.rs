@@ -65,9 +65,12 @@
 //! |:-:|:--|:--|
 //! | `std` | `wasmi`<br>`wasmi_core`<br>`wasmi_ir`<br>`wasmi_collections` | Enables usage of Rust's standard library. This may have some performance advantages when enabled. Disabling this feature makes Wasmi compile on platforms that do not provide Rust's standard library such as many embedded platforms. <br><br> Enabled by default. |
 //! | `wat` | `wasmi` | Enables support to parse Wat encoded Wasm modules. <br><br> Enabled by default. |
+//! | `wat-macro` | `wasmi` | Enables the `wat!` macro which parses Wat encoded Wasm modules at compile time. This does not require the `wat` feature. <br><br> Disabled by default. |
+//! | `to-wat` | `wasmi` | Enables `Module::to_wat` to print Wasm modules in the WebAssembly text format. Note that this retains the original Wasm binary of each [`Module`] in memory. <br><br> Disabled by default. |
 //! | `simd` | `wasmi`<br>`wasmi_core`<br>`wasmi_ir`<br>`wasmi_cli` | Enables support for the Wasm `simd` and `relaxed-simd` proposals. Note that this may introduce execution overhead and increased memory consumption for Wasm executions that do not need Wasm `simd` functionality. <br><br> Disabled by default. |
 //! | `hash-collections` | `wasmi`<br>`wasmi_collections` | Enables use of hash-map based collections in Wasmi internals. This might yield performance improvements in some use cases. <br><br> Disabled by default. |
 //! | `prefer-btree-collections` | `wasmi`<br>`wasmi_collections` | Enforces use of btree-map based collections in Wasmi internals. This may yield performance improvements and memory consumption decreases in some use cases. Also it enables Wasmi to run on platforms that have no random source. <br><br> Disabled by default. |
+//! | `branch-profile` | `wasmi` | Records taken and not-taken counts of executed Wasm conditional branches which are exported via `Store::branch_profile`. This introduces execution overhead for conditional branches. <br><br> Disabled by default. |
 //! | `extra-checks` | `wasmi` | Enables extra runtime checks in the Wasmi executor. Expected execution overhead is ~20%. Enable this if your focus is on safety. Disable this for maximum execution performance. <br><br> Disabled by default. |
 
 #![no_std]
@@ -93,17 +96,20 @@ mod foreach_tuple;
 #[cfg(test)]
 pub mod tests;
 
+mod canonical_abi;
 mod engine;
 mod error;
 mod externref;
 mod func;
 mod global;
+mod guest;
 mod index_ty;
 mod instance;
 mod limits;
 mod linker;
 mod memory;
 mod module;
+mod pool;
 mod store;
 mod table;
 mod value;
@@ -116,44 +122,61 @@ pub use wasmi_core as core;
 #[doc(inline)]
 use wasmi_collections as collections;
 
-/// Definitions from the `wasmi_collections` crate.
+/// Definitions from the `wasmi_ir` crate.
 #[doc(inline)]
-use wasmi_ir as ir;
+pub use wasmi_ir as ir;
 
 /// Defines some errors that may occur upon interaction with Wasmi.
 pub mod errors {
     pub use super::{
-        engine::EnforcedLimitsError,
-        error::ErrorKind,
+        engine::{EnforcedLimitsError, SerializedCodeError},
+        error::{ErrorCategory, ErrorKind},
         func::FuncError,
         global::GlobalError,
         ir::Error as IrError,
         linker::LinkerError,
         memory::MemoryError,
-        module::{InstantiationError, ReadError},
+        module::{InstantiationError, ReadError, ValidationError},
         store::FuelError,
         table::TableError,
     };
 }
 
+#[cfg(feature = "branch-profile")]
+pub use self::engine::{BranchCounts, BranchProfile};
+#[cfg(feature = "std")]
+pub use self::func::ThreadBound;
 pub use self::{
+    canonical_abi::{align_to, CanonicalType, Lift, LiftContext, Lower, LowerContext},
     engine::{
+        BlockLocation,
+        CompilationMetrics,
         CompilationMode,
         Config,
+        Coverage,
         EnforcedLimits,
         Engine,
+        EngineMemoryUsage,
         EngineWeak,
+        FuelSchedule,
+        FuncCompilationMetrics,
+        FuncDisassembly,
+        IndexWidth,
+        Instr,
+        PrecompileProgress,
         ResumableCall,
         ResumableInvocation,
         StackLimits,
+        TraceHash,
         TypedResumableCall,
         TypedResumableInvocation,
     },
     error::Error,
-    externref::ExternRef,
+    externref::{ExternRef, TypedExternRef},
     func::{
         Caller,
         Func,
+        FuncInfo,
         FuncRef,
         FuncType,
         IntoFunc,
@@ -165,22 +188,43 @@ pub use self::{
         WasmTyList,
     },
     global::{Global, GlobalType, Mutability},
+    guest::{GuestAllocator, GuestPtr},
     instance::{Export, ExportsIter, Extern, ExternType, Instance},
     limits::{ResourceLimiter, StoreLimits, StoreLimitsBuilder},
     linker::{state, Linker, LinkerBuilder},
-    memory::{Memory, MemoryType, MemoryTypeBuilder},
+    memory::{
+        Memory,
+        MemoryProvider,
+        MemoryScalar,
+        MemoryType,
+        MemoryTypeBuilder,
+        MemoryView,
+        Pod,
+        Protection,
+    },
     module::{
         CustomSection,
         CustomSectionsIter,
+        DylinkInfo,
         ExportType,
         ImportType,
         InstancePre,
         Module,
         ModuleExportsIter,
         ModuleImportsIter,
+        ModuleTransform,
         Read,
     },
-    store::{AsContext, AsContextMut, CallHook, Store, StoreContext, StoreContextMut},
+    pool::{InstancePool, InstancePoolMetrics, PooledInstance},
+    store::{
+        AsContext,
+        AsContextMut,
+        CallHook,
+        InstantiationHook,
+        Store,
+        StoreContext,
+        StoreContextMut,
+    },
     table::{Table, TableType},
     value::Val,
 };
@@ -193,3 +237,5 @@ use self::{
     store::Stored,
     table::{ElementSegment, ElementSegmentEntity, ElementSegmentIdx, TableEntity, TableIdx},
 };
+#[cfg(feature = "wat-macro")]
+pub use wasmi_macros::wat;
@@ -157,6 +157,18 @@ impl StoreLimitsBuilder {
         self
     }
 
+    /// The maximum number of elements of all tables of a [`Store`](crate::Store) combined.
+    ///
+    /// Creating or growing a table will fail if the total number of table elements
+    /// of the [`Store`](crate::Store) would exceed this limit. In contrast to
+    /// [`StoreLimitsBuilder::table_elements`] this limit is shared by all tables.
+    ///
+    /// By default, the total number of table elements will not be limited.
+    pub fn total_table_elements(mut self, limit: usize) -> Self {
+        self.0.total_table_elements = Some(limit);
+        self
+    }
+
     /// The maximum number of instances that can be created for a [`Store`](crate::Store).
     ///
     /// Module instantiation will fail if this limit is exceeded.
@@ -225,10 +237,17 @@ impl Default for StoreLimitsBuilder {
 pub struct StoreLimits {
     memory_size: Option<usize>,
     table_elements: Option<usize>,
+    total_table_elements: Option<usize>,
     instances: usize,
     tables: usize,
     memories: usize,
     trap_on_grow_failure: bool,
+    /// The number of table elements of all tables created or grown via `self`.
+    used_table_elements: usize,
+    /// The number of table elements of the last approved table growth.
+    ///
+    /// This is subtracted from `used_table_elements` if the growth failed anyways.
+    pending_table_elements: usize,
 }
 
 impl Default for StoreLimits {
@@ -236,10 +255,13 @@ impl Default for StoreLimits {
         Self {
             memory_size: None,
             table_elements: None,
+            total_table_elements: None,
             instances: DEFAULT_INSTANCE_LIMIT,
             tables: DEFAULT_TABLE_LIMIT,
             memories: DEFAULT_MEMORY_LIMIT,
             trap_on_grow_failure: false,
+            used_table_elements: 0,
+            pending_table_elements: 0,
         }
     }
 }
@@ -266,12 +288,15 @@ impl ResourceLimiter for StoreLimits {
 
     fn table_growing(
         &mut self,
-        _current: usize,
+        current: usize,
         desired: usize,
         maximum: Option<usize>,
     ) -> Result<bool, TableError> {
-        let allow = match self.table_elements {
-            Some(limit) if desired > limit => false,
+        let delta = desired.saturating_sub(current);
+        let used = self.used_table_elements.saturating_add(delta);
+        let allow = match (self.table_elements, self.total_table_elements) {
+            (Some(limit), _) if desired > limit => false,
+            (_, Some(limit)) if used > limit => false,
             _ => match maximum {
                 Some(max) if desired > max => false,
                 Some(_) | None => true,
@@ -280,9 +305,18 @@ impl ResourceLimiter for StoreLimits {
         if !allow && self.trap_on_grow_failure {
             return Err(TableError::ResourceLimiterDeniedAllocation);
         }
+        if allow {
+            self.used_table_elements = used;
+            self.pending_table_elements = delta;
+        }
         Ok(allow)
     }
 
+    fn table_grow_failed(&mut self, _error: &TableError) {
+        self.used_table_elements -= self.pending_table_elements;
+        self.pending_table_elements = 0;
+    }
+
     fn instances(&self) -> usize {
         self.instances
     }
@@ -1,27 +1,42 @@
+#[cfg(feature = "std")]
+use crate::ThreadBound;
 use crate::{
     collections::{
         string_interner::{InternHint, Sym as Symbol},
         StringInterner,
     },
+    core::{UntypedVal, ValType},
     func::{FuncEntity, HostFuncEntity, HostFuncTrampolineEntity},
-    module::{ImportName, ImportType},
+    memory::MemoryError,
+    module::{ImportName, ImportType, InstantiationError, Preserved},
+    table::TableError,
     AsContext,
     AsContextMut,
     Caller,
+    DylinkInfo,
     Engine,
     Error,
+    ExportType,
     Extern,
     ExternType,
     Func,
+    FuncRef,
     FuncType,
+    Global,
     GlobalType,
     Instance,
     InstancePre,
     IntoFunc,
+    Memory,
     MemoryType,
     Module,
+    Mutability,
+    StoreContextMut,
+    Table,
     TableType,
     Val,
+    WasmParams,
+    WasmResults,
 };
 use alloc::{
     collections::{btree_map::Entry, BTreeMap},
@@ -30,6 +45,7 @@ use alloc::{
 };
 use core::{
     fmt::{self, Debug, Display},
+    future::Future,
     marker::PhantomData,
 };
 
@@ -41,6 +57,11 @@ pub enum LinkerError {
         /// The duplicate import name of the definition.
         import_name: ImportName,
     },
+    /// Encountered when no definition exists for a name.
+    UndefinedName {
+        /// The import name for which no definition exists.
+        import_name: ImportName,
+    },
     /// Encountered when no definition for an import is found.
     MissingDefinition {
         /// The name of the import for which no definition was found.
@@ -48,6 +69,11 @@ pub enum LinkerError {
         /// The type of the import for which no definition has been found.
         ty: ExternType,
     },
+    /// Encountered when no definitions for multiple imports are found.
+    MissingDefinitions {
+        /// The names and types of all imports for which no definition was found.
+        imports: Vec<(ImportName, ExternType)>,
+    },
     /// Encountered when a definition with invalid type is found.
     InvalidTypeDefinition {
         /// The name of the import for which no definition was found.
@@ -104,6 +130,22 @@ impl LinkerError {
         }
     }
 
+    /// Creates a new [`LinkerError`] for when the definitions of all `imports` were not found.
+    ///
+    /// # Panics
+    ///
+    /// If `imports` is empty.
+    fn missing_definitions(mut imports: Vec<(ImportName, ExternType)>) -> Self {
+        match imports.len() {
+            0 => panic!("missing imports must not be empty"),
+            1 => {
+                let (name, ty) = imports.remove(0);
+                Self::MissingDefinition { name, ty }
+            }
+            _ => Self::MissingDefinitions { imports },
+        }
+    }
+
     /// Creates a new [`LinkerError`] for when an imported definition has an invalid type.
     fn invalid_type_definition(import: &ImportType, found: &ExternType) -> Self {
         Self::InvalidTypeDefinition {
@@ -162,12 +204,22 @@ impl Display for LinkerError {
                     "encountered duplicate definition with name `{import_name}`",
                 )
             }
+            Self::UndefinedName { import_name } => {
+                write!(f, "cannot find definition with name `{import_name}`")
+            }
             Self::MissingDefinition { name, ty } => {
                 write!(
                     f,
                     "cannot find definition for import {name} with type {ty:?}",
                 )
             }
+            Self::MissingDefinitions { imports } => {
+                write!(f, "cannot find definitions for {} imports:", imports.len())?;
+                for (name, ty) in imports {
+                    write!(f, "\n    {name} with type {ty:?}")?;
+                }
+                Ok(())
+            }
             Self::InvalidTypeDefinition {
                 name,
                 expected,
@@ -253,6 +305,14 @@ impl ImportKey {
     }
 }
 
+/// Returns the `value` of an address or offset as `i64` if `is_64` is `true` and as `i32` otherwise.
+fn address_val(is_64: bool, value: u64) -> Val {
+    match is_64 {
+        true => Val::I64(value as i64),
+        false => Val::I32(value as i32),
+    }
+}
+
 /// A [`Linker`] definition.
 #[derive(Debug)]
 enum Definition<T> {
@@ -387,6 +447,40 @@ impl<T> Linker<T> {
         self
     }
 
+    /// Removes the definition of `name` in `module` from this [`Linker`].
+    ///
+    /// This allows to replace existing definitions, e.g. to hot-swap host functions,
+    /// without enabling [`Linker::allow_shadowing`].
+    ///
+    /// # Note
+    ///
+    /// Definitions of the [`LinkerBuilder`] this [`Linker`] was created from cannot be removed.
+    ///
+    /// # Errors
+    ///
+    /// If there is no definition for `name` in `module` in this [`Linker`].
+    pub fn remove(&mut self, module: &str, name: &str) -> Result<&mut Self, LinkerError> {
+        if !self.inner.remove(module, name) {
+            return Err(LinkerError::UndefinedName {
+                import_name: ImportName::new(module, name),
+            });
+        }
+        Ok(self)
+    }
+
+    /// Removes all definitions in `module` from this [`Linker`].
+    ///
+    /// This is useful to replace all definitions of a [`Module`] defined via [`Linker::module`]
+    /// or an [`Instance`] defined via [`Linker::instance`], e.g. upon reloading a plugin.
+    ///
+    /// # Note
+    ///
+    /// Definitions of the [`LinkerBuilder`] this [`Linker`] was created from cannot be removed.
+    pub fn remove_module(&mut self, module: &str) -> &mut Self {
+        self.inner.remove_module(module);
+        self
+    }
+
     /// Ensures that the `name` in `module` is undefined in the shared definitions.
     ///
     /// Returns `Ok` if no shared definition exists.
@@ -443,6 +537,27 @@ impl<T> Linker<T> {
         Ok(self)
     }
 
+    /// Creates a new named [`Func::new_untyped`]-style host [`Func`] for this [`Linker`].
+    ///
+    /// For more information see [`Func::new_untyped`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    pub fn func_new_untyped(
+        &mut self,
+        module: &str,
+        name: &str,
+        ty: FuncType,
+        func: impl Fn(Caller<'_, T>, &mut [UntypedVal]) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Result<&mut Self, LinkerError> {
+        self.ensure_undefined(module, name)?;
+        let func = HostFuncTrampolineEntity::new_untyped(ty, func);
+        let key = self.inner.new_import_key(module, name);
+        self.inner.insert(key, Definition::HostFunc(func))?;
+        Ok(self)
+    }
+
     /// Creates a new named [`Func::new`]-style host [`Func`] for this [`Linker`].
     ///
     /// For information how to use this API see [`Func::wrap`].
@@ -477,6 +592,63 @@ impl<T> Linker<T> {
         Ok(self)
     }
 
+    /// Creates a new named [`Func::wrap_local`]-style host [`Func`] for this [`Linker`].
+    ///
+    /// The closure `func` is neither required to be `Send` nor `Sync` and is bound to
+    /// the current thread. For more information see [`ThreadBound`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    #[cfg(feature = "std")]
+    pub fn func_wrap_local<Params, Args, F>(
+        &mut self,
+        module: &str,
+        name: &str,
+        func: F,
+    ) -> Result<&mut Self, LinkerError>
+    where
+        ThreadBound<F>: IntoFunc<T, Params, Args>,
+    {
+        self.func_wrap(module, name, ThreadBound::new(func))
+    }
+
+    /// Creates a new named async host [`Func`] for this [`Linker`].
+    ///
+    /// The host function `func` returns a [`Future`] that is awaited by the embedder
+    /// instead of blocking the executing thread until its results are available.
+    ///
+    /// # Note
+    ///
+    /// - Async host functions can only be executed via [`Func::call_async`] or
+    ///   [`TypedFunc::call_async`]. Calling them via any of the synchronous call APIs
+    ///   results in an error.
+    /// - Instances of Wasm modules that import an async host function of this [`Linker`]
+    ///   must run their `start` function via [`InstancePre::start_async`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    ///
+    /// [`TypedFunc::call_async`]: crate::TypedFunc::call_async
+    pub fn func_wrap_async<Params, Results, F>(
+        &mut self,
+        module: &str,
+        name: &str,
+        func: impl Fn(Caller<'_, T>, Params) -> F + Send + Sync + 'static,
+    ) -> Result<&mut Self, LinkerError>
+    where
+        Params: WasmParams,
+        Results: WasmResults,
+        F: Future<Output = Result<Results, Error>> + Send + 'static,
+    {
+        self.ensure_undefined(module, name)?;
+        let func = HostFuncTrampolineEntity::wrap_async(func);
+        let key = self.inner.new_import_key(module, name);
+        self.inner.insert(key, Definition::HostFunc(func))?;
+        Ok(self)
+    }
+
     /// Looks up a defined [`Extern`] by name in this [`Linker`].
     ///
     /// - Returns `None` if this name was not previously defined in this [`Linker`].
@@ -556,6 +728,260 @@ impl<T> Linker<T> {
         Ok(self)
     }
 
+    /// Instantiates the [`Module`] and defines the exports of its [`Instance`] in this [`Linker`].
+    ///
+    /// This is a convenience wrapper around [`Linker::instantiate`], [`InstancePre::start`] and
+    /// [`Linker::instance`] so that the exports of `module` are available under `module_name`
+    /// to satisfy the imports of subsequently instantiated [`Module`]s.
+    ///
+    /// # Errors
+    ///
+    /// - If the instantiation of `module` fails.
+    /// - If executing the `start` function of `module` traps.
+    /// - If any item is re-defined in `self` (for example the same `module_name` was already defined).
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of this [`Linker`] and the [`Engine`] of `store` are not the same.
+    pub fn module(
+        &mut self,
+        mut store: impl AsContextMut<Data = T>,
+        module_name: &str,
+        module: &Module,
+    ) -> Result<&mut Self, Error> {
+        let instance = self.instantiate(&mut store, module)?.start(&mut store)?;
+        self.instance(store, module_name, instance)
+    }
+
+    /// Instantiates the Wasm shared object `module` into the `memory` and `table` of a main module.
+    ///
+    /// This follows the [dynamic linking tool conventions] in order to load shared objects,
+    /// e.g. compiled by Emscripten or `wasi-sdk`, at runtime:
+    ///
+    /// - Reserves the memory and table regions required by the [`DylinkInfo`] of `module`
+    ///   by growing `memory` and `table` and defines their offsets as the `env.__memory_base`
+    ///   and `env.__table_base` imports.
+    /// - Defines `memory` and `table` as the `env.memory` and `env.__indirect_function_table` imports.
+    /// - Resolves the `GOT.mem` and `GOT.func` imports using the exports of `module` itself
+    ///   or the definitions in the `env` module of this [`Linker`].
+    /// - Runs the `__wasm_apply_data_relocs` and `__wasm_call_ctors` functions if exported.
+    ///
+    /// All other imports of `module` are resolved using the definitions of this [`Linker`].
+    ///
+    /// # Errors
+    ///
+    /// - If `module` has no `dylink.0` custom section.
+    /// - If `memory` or `table` cannot be grown to reserve the required regions.
+    /// - If a `GOT.mem` or `GOT.func` import cannot be resolved.
+    /// - If the instantiation of `module` or any of its initialization functions fails.
+    ///
+    /// [dynamic linking tool conventions]: https://github.com/WebAssembly/tool-conventions/blob/main/DynamicLinking.md
+    pub fn instantiate_dylib(
+        &self,
+        mut context: impl AsContextMut<Data = T>,
+        module: &Module,
+        memory: Memory,
+        table: Table,
+    ) -> Result<Instance, Error> {
+        let info = module
+            .dylink_info()?
+            .ok_or(InstantiationError::MissingDylinkInfo)?;
+        let memory_base = Self::reserve_memory(&mut context, &memory, &info)?;
+        let table_base = Self::reserve_table(&mut context, &table, &info)?;
+        let memory_base_val = address_val(memory.ty(&context).is_64(), memory_base);
+        let table_base_val = address_val(table.ty(&context).is_64(), table_base);
+        let mut linker = self.clone();
+        linker.allow_shadowing(true);
+        linker
+            .define("env", "memory", memory)?
+            .define("env", "__indirect_function_table", table)?
+            .define(
+                "env",
+                "__memory_base",
+                Global::new(&mut context, memory_base_val, Mutability::Const),
+            )?
+            .define(
+                "env",
+                "__table_base",
+                Global::new(&mut context, table_base_val, Mutability::Const),
+            )?;
+        // Note: GOT entries are resolved after instantiation since they
+        //       may refer to the exports of the shared object itself.
+        let mut got_entries = Vec::new();
+        for import in module.imports() {
+            let is_func = match import.module() {
+                "GOT.mem" => false,
+                "GOT.func" => true,
+                _ => continue,
+            };
+            let ExternType::Global(global_type) = import.ty() else {
+                continue;
+            };
+            let init = Val::default(global_type.content());
+            let entry = Global::new(&mut context, init, Mutability::Var);
+            linker.define(import.module(), import.name(), entry)?;
+            got_entries.push((import, is_func, entry));
+        }
+        let instance = linker
+            .instantiate(&mut context, module)?
+            .start(&mut context)?;
+        for (import, is_func, entry) in got_entries {
+            let is_64 = matches!(entry.ty(&context).content(), ValType::I64);
+            let address = match is_func {
+                true => self.resolve_got_func(&mut context, &instance, &table, import.name())?,
+                false => self.resolve_got_mem(&context, &instance, memory_base, import.name())?,
+            }
+            .ok_or_else(|| LinkerError::UndefinedName {
+                import_name: import.import_name().clone(),
+            })?;
+            entry.set(&mut context, address_val(is_64, address))?;
+        }
+        for name in ["__wasm_apply_data_relocs", "__wasm_call_ctors"] {
+            if let Some(func) = instance.get_func(&context, name) {
+                func.call(&mut context, &[], &mut [])?;
+            }
+        }
+        Ok(instance)
+    }
+
+    /// Grows `memory` to reserve the linear memory region of the shared object described by `info`.
+    ///
+    /// Returns the offset of the reserved region.
+    fn reserve_memory(
+        mut context: impl AsContextMut<Data = T>,
+        memory: &Memory,
+        info: &DylinkInfo,
+    ) -> Result<u64, MemoryError> {
+        let size = memory.size_in_bytes(&context);
+        let base = 1_u64
+            .checked_shl(info.memory_alignment_log2())
+            .and_then(|align| size.checked_next_multiple_of(align))
+            .ok_or(MemoryError::OutOfBoundsGrowth)?;
+        let end = base
+            .checked_add(u64::from(info.memory_size()))
+            .ok_or(MemoryError::OutOfBoundsGrowth)?;
+        if end > size {
+            let page_size = u64::from(memory.ty(&context).page_size());
+            memory.grow(&mut context, (end - size).div_ceil(page_size))?;
+        }
+        Ok(base)
+    }
+
+    /// Grows `table` to reserve the table region of the shared object described by `info`.
+    ///
+    /// Returns the offset of the reserved region.
+    fn reserve_table(
+        mut context: impl AsContextMut<Data = T>,
+        table: &Table,
+        info: &DylinkInfo,
+    ) -> Result<u64, TableError> {
+        let size = table.size(&context);
+        let Some(delta) = 1_u64
+            .checked_shl(info.table_alignment_log2())
+            .and_then(|align| size.checked_next_multiple_of(align))
+            .and_then(|base| base.checked_add(u64::from(info.table_size())))
+            .map(|end| end - size)
+        else {
+            return Err(TableError::GrowOutOfBounds {
+                maximum: table.ty(&context).maximum().unwrap_or(u64::MAX),
+                current: size,
+                delta: u64::MAX,
+            });
+        };
+        let base = size + delta - u64::from(info.table_size());
+        let init = Val::default(table.ty(&context).element());
+        table.grow(&mut context, delta, init)?;
+        Ok(base)
+    }
+
+    /// Resolves the address of the `GOT.mem` entry for the data symbol `name`.
+    ///
+    /// Returns `None` if `name` cannot be resolved.
+    fn resolve_got_mem(
+        &self,
+        context: impl AsContext<Data = T>,
+        instance: &Instance,
+        memory_base: u64,
+        name: &str,
+    ) -> Result<Option<u64>, Error> {
+        // Note: exported data symbols of the shared object are relative to its `__memory_base`.
+        let (global, offset) = match instance.get_global(&context, name) {
+            Some(global) => (global, memory_base),
+            None => match self
+                .get_definition(&context, "env", name)
+                .and_then(Definition::as_extern)
+                .copied()
+                .and_then(Extern::into_global)
+            {
+                Some(global) => (global, 0),
+                None => return Ok(None),
+            },
+        };
+        let address = match global.get(&context) {
+            Val::I32(address) => u64::from(address as u32),
+            Val::I64(address) => address as u64,
+            _ => return Ok(None),
+        };
+        Ok(Some(address.wrapping_add(offset)))
+    }
+
+    /// Resolves the table index of the `GOT.func` entry for the function symbol `name`.
+    ///
+    /// The resolved function is appended to the `table`.
+    /// Returns `None` if `name` cannot be resolved.
+    fn resolve_got_func(
+        &self,
+        mut context: impl AsContextMut<Data = T>,
+        instance: &Instance,
+        table: &Table,
+        name: &str,
+    ) -> Result<Option<u64>, Error> {
+        let func = match instance.get_func(&context, name) {
+            Some(func) => func,
+            None => match self
+                .get_definition(&context, "env", name)
+                .cloned()
+                .and_then(|definition| definition.as_func(&mut context))
+            {
+                Some(func) => func,
+                None => return Ok(None),
+            },
+        };
+        let index = table.grow(&mut context, 1, Val::FuncRef(FuncRef::new(func)))?;
+        Ok(Some(index))
+    }
+
+    /// Aliases the definition of `name` in `module` as `as_name` in `as_module`.
+    ///
+    /// This allows the same definition to satisfy imports of multiple namespaces
+    /// without defining it multiple times.
+    ///
+    /// # Errors
+    ///
+    /// - If there is no definition for `name` in `module`.
+    /// - If there already is a definition for `as_name` in `as_module`.
+    pub fn alias(
+        &mut self,
+        module: &str,
+        name: &str,
+        as_module: &str,
+        as_name: &str,
+    ) -> Result<&mut Self, LinkerError> {
+        self.ensure_undefined(as_module, as_name)?;
+        let item = self
+            .shared
+            .as_ref()
+            .and_then(|shared| shared.get_definition(module, name))
+            .or_else(|| self.inner.get_definition(module, name))
+            .cloned()
+            .ok_or_else(|| LinkerError::UndefinedName {
+                import_name: ImportName::new(module, name),
+            })?;
+        let key = self.inner.new_import_key(as_module, as_name);
+        self.inner.insert(key, item)?;
+        Ok(self)
+    }
+
     /// Aliases one module's name as another.
     ///
     /// This method will alias all currently defined under `module` to also be
@@ -578,20 +1004,129 @@ impl<T> Linker<T> {
     /// # Errors
     ///
     /// - If the linker does not define imports of the instantiated [`Module`].
+    ///   In this case the error lists all imports without definition.
     /// - If any imported item does not satisfy its type requirements.
     pub fn instantiate(
+        &self,
+        context: impl AsContextMut<Data = T>,
+        module: &Module,
+    ) -> Result<InstancePre, Error> {
+        self.instantiate_preserving(context, module, &Preserved::default())
+    }
+
+    /// Instantiates `module` as the new version of `instance` preserving its state.
+    ///
+    /// This is a convenience wrapper around [`Linker::reinstantiate_with`] that preserves
+    /// every linear memory and global variable of `instance` that is exported under the
+    /// same name and with a matching type by `module`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Linker::reinstantiate_with`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `context` are not the same.
+    pub fn reinstantiate(
+        &self,
+        context: impl AsContextMut<Data = T>,
+        module: &Module,
+        instance: &Instance,
+    ) -> Result<InstancePre, Error> {
+        self.reinstantiate_with(context, module, instance, |store, export, old| {
+            let matches = match (export.ty(), &old) {
+                (ExternType::Memory(ty), Extern::Memory(memory)) => {
+                    memory.dynamic_ty(&store).is_subtype_of(ty)
+                }
+                (ExternType::Global(ty), Extern::Global(global)) => global.ty(&store) == *ty,
+                _ => false,
+            };
+            Ok(matches.then_some(old))
+        })
+    }
+
+    /// Instantiates `module` as the new version of `instance` using `migrate` to preserve its state.
+    ///
+    /// This is useful to hot-reload a new version of a Wasm module without losing the
+    /// state of its running [`Instance`].
+    ///
+    /// # Note
+    ///
+    /// - `migrate` is called for every linear memory and global variable defined and exported
+    ///   by `module` for which `instance` has an export with the same name. It is given the
+    ///   export of `module` as well as the [`Extern`] exported by `instance`.
+    /// - If `migrate` returns an [`Extern`] it is used as the definition of the export
+    ///   instead of creating a new one. Otherwise a new definition is created as usual.
+    ///   This allows `migrate` to convert or copy the old state as needed.
+    /// - Preserved definitions are treated as if they were imported by `module`.
+    ///   Therefore the active data segments of `module` are written to preserved linear
+    ///   memories and global variable initializers are skipped for preserved global variables.
+    /// - All imports of `module` are resolved using the definitions of this [`Linker`].
+    /// - The `start` function of `module` is run via the returned [`InstancePre`].
+    ///
+    /// # Errors
+    ///
+    /// - If `migrate` returns an error.
+    /// - If an [`Extern`] returned by `migrate` does not satisfy the type of its export.
+    /// - If the instantiation of `module` fails.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `context` are not the same.
+    pub fn reinstantiate_with(
         &self,
         mut context: impl AsContextMut<Data = T>,
         module: &Module,
+        instance: &Instance,
+        migrate: impl FnMut(StoreContextMut<T>, &ExportType, Extern) -> Result<Option<Extern>, Error>,
+    ) -> Result<InstancePre, Error> {
+        let preserved = module.preserve_exports(&mut context, instance, migrate)?;
+        self.instantiate_preserving(context, module, &preserved)
+    }
+
+    /// Instantiates the given [`Module`] using the definitions in the [`Linker`] and `preserved`.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `context` are not the same.
+    ///
+    /// # Errors
+    ///
+    /// - If the linker does not define imports of the instantiated [`Module`].
+    /// - If any imported or preserved item does not satisfy its type requirements.
+    fn instantiate_preserving(
+        &self,
+        mut context: impl AsContextMut<Data = T>,
+        module: &Module,
+        preserved: &Preserved,
     ) -> Result<InstancePre, Error> {
         assert!(Engine::same(self.engine(), context.as_context().engine()));
         // TODO: possibly add further resource limtation here on number of externals.
         // Not clear that user can't import the same external lots of times to inflate this.
+        let missing = module
+            .imports()
+            .filter(|import| {
+                self.get_definition(&context, import.module(), import.name())
+                    .is_none()
+            })
+            .map(|import| (import.import_name().clone(), import.ty().clone()))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            return Err(Error::from(LinkerError::missing_definitions(missing)));
+        }
         let externals = module
             .imports()
             .map(|import| self.process_import(&mut context, import))
             .collect::<Result<Vec<Extern>, Error>>()?;
-        module.instantiate(context, externals)
+        let has_async_imports = module.imports().any(|import| {
+            matches!(
+                self.get_definition(&context, import.module(), import.name()),
+                Some(Definition::HostFunc(func)) if func.is_async()
+            )
+        });
+        module
+            .instantiate_preserving(context, externals, preserved)
+            .map(|pre| pre.with_async_imports(has_async_imports))
     }
 
     /// Processes a single [`Module`] import.
@@ -991,6 +1526,22 @@ impl<T> LinkerInner<T> {
         };
         self.definitions.contains_key(&key)
     }
+
+    /// Removes the [`Definition`] for `name` in `module` and returns `true` if it existed.
+    fn remove(&mut self, module: &str, name: &str) -> bool {
+        let Some(key) = self.get_import_key(module, name) else {
+            return false;
+        };
+        self.definitions.remove(&key).is_some()
+    }
+
+    /// Removes all [`Definition`]s in `module`.
+    fn remove_module(&mut self, module: &str) {
+        let Some(module) = self.strings.get(module) else {
+            return;
+        };
+        self.definitions.retain(|key, _def| key.module() != module);
+    }
 }
 
 #[cfg(test)]
@@ -1099,6 +1650,153 @@ mod tests {
         assert_eq!(wasm_get_b.call(&mut store, ()).unwrap(), 200);
     }
 
+    #[test]
+    fn linker_module_namespacing() {
+        use crate::{Engine, Linker, Module, Store};
+        let wasm_lib = r#"
+            (module
+                (global $counter (export "counter") (mut i32) (i32.const 0))
+                (func (export "inc") (result i32)
+                    (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+                    (global.get $counter)
+                )
+            )"#;
+        let wasm_app = r#"
+            (module
+                (import "lib" "inc" (func $inc (result i32)))
+                (func (export "run") (result i32)
+                    (drop (call $inc))
+                    (call $inc)
+                )
+            )"#;
+        let engine = Engine::default();
+        let mut store = Store::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        let lib = Module::new(&engine, wasm_lib).unwrap();
+        let app = Module::new(&engine, wasm_app).unwrap();
+        linker.module(&mut store, "lib", &lib).unwrap();
+        let counter = linker
+            .get(&store, "lib", "counter")
+            .and_then(Extern::into_global)
+            .unwrap();
+        let instance = linker
+            .instantiate(&mut store, &app)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let run = instance.get_typed_func::<(), i32>(&store, "run").unwrap();
+        assert_eq!(run.call(&mut store, ()).unwrap(), 2);
+        assert_eq!(counter.get(&store).i32(), Some(2));
+        // Defining the same namespace twice is an error.
+        linker.module(&mut store, "lib", &lib).unwrap_err();
+    }
+
+    #[test]
+    fn linker_alias() {
+        let engine = Engine::default();
+        let mut builder = <Linker<()>>::build();
+        builder
+            .func_wrap("wasi_snapshot_preview1", "sched_yield", || 0_i32)
+            .unwrap();
+        let mut linker = builder.finish().create(&engine);
+        linker
+            .func_wrap("wasi_snapshot_preview1", "proc_exit", |_code: i32| ())
+            .unwrap();
+        linker
+            .alias(
+                "wasi_snapshot_preview1",
+                "sched_yield",
+                "wasi_unstable",
+                "sched_yield",
+            )
+            .unwrap()
+            .alias(
+                "wasi_snapshot_preview1",
+                "proc_exit",
+                "wasi_unstable",
+                "proc_exit",
+            )
+            .unwrap();
+        let store = Store::new(&engine, ());
+        for name in ["sched_yield", "proc_exit"] {
+            let def = linker
+                .get_definition(&store, "wasi_unstable", name)
+                .unwrap();
+            assert!(matches!(def, Definition::HostFunc(_)));
+        }
+        assert!(matches!(
+            linker.alias("wasi_unstable", "args_get", "env", "args_get"),
+            Err(LinkerError::UndefinedName { .. }),
+        ));
+        assert!(matches!(
+            linker.alias(
+                "wasi_snapshot_preview1",
+                "proc_exit",
+                "wasi_unstable",
+                "proc_exit"
+            ),
+            Err(LinkerError::DuplicateDefinition { .. }),
+        ));
+    }
+
+    #[test]
+    fn linker_remove() {
+        let engine = Engine::default();
+        let mut builder = <Linker<()>>::build();
+        builder.func_wrap("host", "shared", || ()).unwrap();
+        let mut linker = builder.finish().create(&engine);
+        linker.func_wrap("host", "a", || 1_i32).unwrap();
+        linker.func_wrap("host", "b", || 2_i32).unwrap();
+        linker.func_wrap("env", "c", || 3_i32).unwrap();
+        // Replace a definition without shadowing.
+        linker.func_wrap("host", "a", || 10_i32).unwrap_err();
+        linker.remove("host", "a").unwrap();
+        linker.func_wrap("host", "a", || 10_i32).unwrap();
+        // Removing undefined or shared definitions is an error.
+        assert!(matches!(
+            linker.remove("host", "missing"),
+            Err(LinkerError::UndefinedName { .. }),
+        ));
+        assert!(matches!(
+            linker.remove("host", "shared"),
+            Err(LinkerError::UndefinedName { .. }),
+        ));
+        // Remove all definitions of a module.
+        linker.remove_module("host");
+        let store = Store::new(&engine, ());
+        assert!(linker.get_definition(&store, "host", "a").is_none());
+        assert!(linker.get_definition(&store, "host", "b").is_none());
+        assert!(linker.get_definition(&store, "host", "shared").is_some());
+        assert!(linker.get_definition(&store, "env", "c").is_some());
+    }
+
+    #[test]
+    fn report_all_missing_imports() {
+        use crate::{errors::ErrorKind, Module, Store};
+        let wasm = r#"
+            (module
+                (import "host" "a" (func (param i32)))
+                (import "host" "b" (func (result i64)))
+                (import "host" "c" (func))
+                (import "env" "memory" (memory 1))
+            )"#;
+        let engine = Engine::default();
+        let mut store = Store::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        linker.func_wrap("host", "b", || 0_i64).unwrap();
+        let module = Module::new(&engine, wasm).unwrap();
+        let error = linker.instantiate(&mut store, &module).unwrap_err();
+        let ErrorKind::Linker(LinkerError::MissingDefinitions { imports }) = error.kind() else {
+            panic!("expected missing definitions error but found: {error}")
+        };
+        let names = imports
+            .iter()
+            .map(|(name, _ty)| std::format!("{name}"))
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["host::a", "host::c", "env::memory"]);
+        assert!(matches!(imports[2].1, ExternType::Memory(_)));
+    }
+
     #[test]
     fn build_linker() {
         let mut builder = <Linker<()>>::build();
@@ -1,6 +1,6 @@
-use crate::memory::MemoryError;
-use alloc::{slice, vec::Vec};
-use core::{iter, mem::ManuallyDrop};
+use crate::memory::{MemoryError, MemoryProvider};
+use alloc::{boxed::Box, slice, vec::Vec};
+use core::{fmt, iter, mem::ManuallyDrop};
 
 /// A byte buffer implementation.
 ///
@@ -19,6 +19,7 @@ pub struct ByteBuffer {
     ///
     /// - **Vec:** `vec.len()`
     /// - **Static:** The accessible subslice of the entire underlying static byte buffer.
+    /// - **Provider:** The length of the provided byte slice.
     pub(super) len: usize,
     /// The capacity of the current allocation.
     ///
@@ -26,21 +27,42 @@ pub struct ByteBuffer {
     ///
     /// - **Vec**: `vec.capacity()`
     /// - **Static:** The total length of the underlying static byte buffer.
+    /// - **Provider:** The length of the provided byte slice.
     capacity: usize,
-    /// Whether the [`ByteBuffer`] was initialized from a `&'static [u8]` or a `Vec<u8>`.
-    is_static: bool,
+    /// The kind of the underlying byte buffer.
+    kind: ByteBufferKind,
+}
+
+/// The kind of a [`ByteBuffer`].
+enum ByteBufferKind {
+    /// The [`ByteBuffer`] was initialized from a `Vec<u8>`.
+    Vec,
+    /// The [`ByteBuffer`] was initialized from a `&'static [u8]`.
+    Static,
+    /// The [`ByteBuffer`] is backed by a user provided [`MemoryProvider`].
+    Provider(Box<dyn MemoryProvider>),
+}
+
+impl fmt::Debug for ByteBufferKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Vec => write!(f, "Vec"),
+            Self::Static => write!(f, "Static"),
+            Self::Provider(_) => write!(f, "Provider"),
+        }
+    }
 }
 
 // # Safety
 //
-// `ByteBuffer` is essentially an `enum`` of `Vec<u8>` or `&'static mut [u8]`.
-// Both of them are `Send` so this is sound.
+// `ByteBuffer` is essentially an `enum`` of `Vec<u8>`, `&'static mut [u8]` or
+// `Box<dyn MemoryProvider>`. All of them are `Send` so this is sound.
 unsafe impl Send for ByteBuffer {}
 
 // # Safety
 //
-// `ByteBuffer` is essentially an `enum`` of `Vec<u8>` or `&'static mut [u8]`.
-// Both of them are `Sync` so this is sound.
+// `ByteBuffer` is essentially an `enum`` of `Vec<u8>`, `&'static mut [u8]` or
+// `Box<dyn MemoryProvider>`. All of them are `Sync` so this is sound.
 unsafe impl Sync for ByteBuffer {}
 
 /// Decomposes the `Vec<u8>` into its raw components.
@@ -84,7 +106,7 @@ impl ByteBuffer {
             ptr,
             len,
             capacity,
-            is_static: false,
+            kind: ByteBufferKind::Vec,
         })
     }
 
@@ -104,7 +126,38 @@ impl ByteBuffer {
             ptr: buffer.as_mut_ptr(),
             len: size,
             capacity: buffer.len(),
-            is_static: true,
+            kind: ByteBufferKind::Static,
+        })
+    }
+
+    /// Creates a new byte buffer with the given `size` in bytes backed by `provider`.
+    ///
+    /// This will zero all the bytes in `buffer[0..size]`.
+    ///
+    /// # Errors
+    ///
+    /// - If the bytes of `provider` are already larger than `size`.
+    /// - If `provider` failed to grow its bytes to `size`.
+    pub fn new_provider(
+        mut provider: Box<dyn MemoryProvider>,
+        size: usize,
+    ) -> Result<Self, MemoryError> {
+        if provider.as_slice().len() > size {
+            return Err(MemoryError::InvalidProviderSize);
+        }
+        provider.grow(size)?;
+        let bytes = provider.as_slice_mut();
+        assert_eq!(
+            bytes.len(),
+            size,
+            "memory provider returned an invalid slice"
+        );
+        bytes.fill(0x00_u8);
+        Ok(Self {
+            ptr: bytes.as_mut_ptr(),
+            len: size,
+            capacity: size,
+            kind: ByteBufferKind::Provider(provider),
         })
     }
 
@@ -123,6 +176,9 @@ impl ByteBuffer {
     ///     - `static`: If `new_size` is larger than it's the static buffer capacity.
     pub fn grow(&mut self, new_size: usize) -> Result<(), MemoryError> {
         assert!(self.len() <= new_size);
+        if let ByteBufferKind::Provider(provider) = &mut self.kind {
+            return Self::grow_provider(&mut **provider, &mut self.ptr, &mut self.len, new_size);
+        }
         match self.get_vec() {
             Some(vec) => self.grow_vec(vec, new_size),
             None => self.grow_static(new_size),
@@ -152,6 +208,26 @@ impl ByteBuffer {
         Ok(())
     }
 
+    /// Grow the byte buffer to the given `new_size` when backed by a [`MemoryProvider`].
+    fn grow_provider(
+        provider: &mut dyn MemoryProvider,
+        ptr: &mut *mut u8,
+        len: &mut usize,
+        new_size: usize,
+    ) -> Result<(), MemoryError> {
+        provider.grow(new_size)?;
+        let bytes = provider.as_slice_mut();
+        assert_eq!(
+            bytes.len(),
+            new_size,
+            "memory provider returned an invalid slice"
+        );
+        bytes[*len..].fill(0x00_u8);
+        *ptr = bytes.as_mut_ptr();
+        *len = new_size;
+        Ok(())
+    }
+
     /// Returns the length of the byte buffer in bytes.
     pub fn len(&self) -> usize {
         self.len
@@ -161,8 +237,8 @@ impl ByteBuffer {
     pub fn data(&self) -> &[u8] {
         // # Safety
         //
-        // The byte buffer is either backed by a `Vec<u8>` or a &'static [u8]`
-        // which are both valid byte slices in the range `self.ptr[0..self.len]`.
+        // The byte buffer is either backed by a `Vec<u8>`, a &'static [u8]` or a
+        // `MemoryProvider` which are all valid byte slices in the range `self.ptr[0..self.len]`.
         unsafe { slice::from_raw_parts(self.ptr, self.len) }
     }
 
@@ -170,12 +246,12 @@ impl ByteBuffer {
     pub fn data_mut(&mut self) -> &mut [u8] {
         // # Safety
         //
-        // The byte buffer is either backed by a `Vec<u8>` or a &'static [u8]`
-        // which are both valid byte slices in the range `self.ptr[0..self.len]`.
+        // The byte buffer is either backed by a `Vec<u8>`, a &'static [u8]` or a
+        // `MemoryProvider` which are all valid byte slices in the range `self.ptr[0..self.len]`.
         unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
     }
 
-    /// Returns the underlying `Vec<u8>` if the byte buffer is not backed by a static buffer.
+    /// Returns the underlying `Vec<u8>` if the byte buffer is backed by a `Vec<u8>`.
     ///
     /// Otherwise returns `None`.
     ///
@@ -183,7 +259,7 @@ impl ByteBuffer {
     ///
     /// The returned `Vec` will free its memory and thus the memory of the [`ByteBuffer`] if dropped.
     fn get_vec(&mut self) -> Option<Vec<u8>> {
-        if self.is_static {
+        if !matches!(self.kind, ByteBufferKind::Vec) {
             return None;
         }
         // Safety
@@ -260,6 +336,36 @@ mod test {
         assert_eq!(buffer.data(), &[0; 10]);
     }
 
+    #[test]
+    fn test_provider_buffer() {
+        #[derive(Default)]
+        struct Provider(Vec<u8>);
+        unsafe impl MemoryProvider for Provider {
+            fn grow(&mut self, new_size: usize) -> Result<(), MemoryError> {
+                if new_size > 8 {
+                    return Err(MemoryError::OutOfSystemMemory);
+                }
+                self.0.resize(new_size, 7);
+                Ok(())
+            }
+            fn as_slice(&self) -> &[u8] {
+                &self.0
+            }
+            fn as_slice_mut(&mut self) -> &mut [u8] {
+                &mut self.0
+            }
+        }
+        let provider = Box::new(Provider(Vec::with_capacity(8)));
+        let mut buffer = ByteBuffer::new_provider(provider, 4).unwrap();
+        assert_eq!(buffer.data(), &[0; 4]);
+        buffer.data_mut()[0] = 1;
+        buffer.grow(8).unwrap();
+        assert_eq!(buffer.data(), &[1, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(buffer.grow(10).is_err());
+        let provider = Box::new(Provider(Vec::from([0; 8])));
+        assert!(ByteBuffer::new_provider(provider, 4).is_err());
+    }
+
     #[test]
     fn test_static_buffer_overflow() {
         static mut BUF: [u8; 5] = [7; 5];
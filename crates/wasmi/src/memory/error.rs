@@ -1,5 +1,5 @@
 use super::MemoryType;
-use core::{fmt, fmt::Display};
+use core::{fmt, fmt::Display, str::Utf8Error};
 
 /// An error that may occur upon operating with virtual or linear memory.
 #[derive(Debug)]
@@ -24,12 +24,28 @@ pub enum MemoryError {
     TooManyMemories,
     /// Tried to create memory with invalid static buffer size
     InvalidStaticBufferSize,
+    /// Tried to create memory with a memory provider that provides too many bytes.
+    InvalidProviderSize,
     /// If a resource limiter denied allocation or growth of a linear memory.
     ResourceLimiterDeniedAllocation,
     // The minimum size of the memory type overflows the system index type.
     MinimumSizeOverflow,
     // The maximum size of the memory type overflows the system index type.
     MaximumSizeOverflow,
+    /// Tried to access the default linear memory of a caller that has none.
+    MissingDefaultMemory,
+    /// Tried to view linear memory at an address not properly aligned for the viewed type.
+    UnalignedAccess,
+    /// Tried to read a string from linear memory that is not valid UTF-8.
+    InvalidUtf8(Utf8Error),
+    /// Tried to write a nul-terminated string that contains a nul byte.
+    InteriorNul,
+    /// The allocator of a Wasm guest failed to allocate a buffer.
+    GuestAllocationFailed,
+    /// Tried to lift a `char` from linear memory that is not a Unicode scalar value.
+    InvalidChar(u32),
+    /// Tried to lift a variant from linear memory with an out of bounds discriminant.
+    InvalidDiscriminant(u8),
 }
 
 #[cfg(feature = "std")]
@@ -62,6 +78,12 @@ impl Display for MemoryError {
             Self::InvalidStaticBufferSize => {
                 write!(f, "tried to use too small static buffer")
             }
+            Self::InvalidProviderSize => {
+                write!(
+                    f,
+                    "tried to use memory provider with too many initial bytes"
+                )
+            }
             Self::ResourceLimiterDeniedAllocation => {
                 write!(
                     f,
@@ -80,6 +102,30 @@ impl Display for MemoryError {
                     "the maximum size of the memory type overflows the system index type"
                 )
             }
+            Self::MissingDefaultMemory => {
+                write!(f, "missing default linear memory")
+            }
+            Self::UnalignedAccess => {
+                write!(f, "unaligned typed memory access")
+            }
+            Self::InvalidUtf8(error) => {
+                write!(f, "invalid UTF-8 string in linear memory: {error}")
+            }
+            Self::InteriorNul => {
+                write!(f, "nul-terminated string contains a nul byte")
+            }
+            Self::GuestAllocationFailed => {
+                write!(f, "the guest allocator failed to allocate a buffer")
+            }
+            Self::InvalidChar(value) => {
+                write!(f, "invalid char in linear memory: {value:#x}")
+            }
+            Self::InvalidDiscriminant(discriminant) => {
+                write!(
+                    f,
+                    "invalid variant discriminant in linear memory: {discriminant}"
+                )
+            }
         }
     }
 }
@@ -1,14 +1,23 @@
 mod buffer;
 mod data;
 mod error;
+mod protection;
+mod provider;
+mod string;
+mod typed;
+mod view;
 
 #[cfg(test)]
 mod tests;
 
-use self::buffer::ByteBuffer;
+use self::{buffer::ByteBuffer, protection::ReadOnlyRanges};
 pub use self::{
     data::{DataSegment, DataSegmentEntity, DataSegmentIdx},
     error::MemoryError,
+    protection::Protection,
+    provider::MemoryProvider,
+    typed::{MemoryScalar, Pod},
+    view::MemoryView,
 };
 use super::{AsContext, AsContextMut, StoreContext, StoreContextMut, Stored};
 use crate::{
@@ -19,6 +28,8 @@ use crate::{
     Error,
     IndexType,
 };
+use alloc::boxed::Box;
+use core::ops::Range;
 
 /// A raw index to a linear memory entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -379,6 +390,8 @@ pub struct MemoryEntity {
     /// The size of `bytes` will always be a multiple of a page size.
     bytes: ByteBuffer,
     memory_type: MemoryType,
+    /// The byte ranges that Wasm code must not write to.
+    read_only: ReadOnlyRanges,
 }
 
 impl MemoryEntity {
@@ -402,6 +415,18 @@ impl MemoryEntity {
         .map_err(Error::from)
     }
 
+    /// Creates a new memory entity with the given memory type backed by `provider`.
+    pub fn new_with_provider(
+        memory_type: MemoryType,
+        limiter: &mut ResourceLimiterRef<'_>,
+        provider: Box<dyn MemoryProvider>,
+    ) -> Result<Self, Error> {
+        Self::new_impl(memory_type, limiter, |initial_size| {
+            ByteBuffer::new_provider(provider, initial_size)
+        })
+        .map_err(Error::from)
+    }
+
     fn new_impl(
         memory_type: MemoryType,
         limiter: &mut ResourceLimiterRef<'_>,
@@ -446,7 +471,11 @@ impl MemoryEntity {
                 return Err(error);
             }
         };
-        Ok(Self { bytes, memory_type })
+        Ok(Self {
+            bytes,
+            memory_type,
+            read_only: ReadOnlyRanges::default(),
+        })
     }
 
     /// Returns the memory type of the linear memory.
@@ -480,7 +509,7 @@ impl MemoryEntity {
     }
 
     /// Returns the size of this Wasm linear memory in bytes.
-    fn size_in_bytes(&self) -> u64 {
+    pub fn size_in_bytes(&self) -> u64 {
         let pages = self.size();
         let bytes_per_page = u64::from(self.memory_type.page_size());
         let Some(bytes) = pages.checked_mul(bytes_per_page) else {
@@ -573,7 +602,11 @@ impl MemoryEntity {
                 .checked_mul(bytes_per_page)
                 .expect("additional size is within [min, max) page bounds");
             if fuel
-                .consume_fuel_if(|costs| costs.fuel_for_bytes(additional_bytes))
+                .consume_fuel_if(|costs| {
+                    costs
+                        .fuel_for_bytes(additional_bytes)
+                        .saturating_add(costs.fuel_for_memory_pages(additional))
+                })
                 .is_err()
             {
                 return notify_limiter(limiter, EntityGrowError::TrapCode(TrapCode::OutOfFuel));
@@ -592,6 +625,43 @@ impl MemoryEntity {
         Ok(current_size)
     }
 
+    /// Sets the `protection` of the bytes within `range`.
+    ///
+    /// # Errors
+    ///
+    /// If `range` is out of bounds of the linear memory.
+    pub fn protect(
+        &mut self,
+        range: Range<u64>,
+        protection: Protection,
+    ) -> Result<(), MemoryError> {
+        if range.start > range.end || range.end > self.size_in_bytes() {
+            return Err(MemoryError::OutOfBoundsAccess);
+        }
+        // Note: the range is within the bounds of the byte buffer and thus fits into `usize`.
+        self.read_only
+            .protect(range.start as usize..range.end as usize, protection);
+        Ok(())
+    }
+
+    /// Returns `true` if any byte of the linear memory is read-only.
+    pub fn is_protected(&self) -> bool {
+        !self.read_only.is_empty()
+    }
+
+    /// Checks if Wasm code may write the `len` bytes starting at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If any of the bytes is read-only.
+    #[inline]
+    pub fn check_writable(&self, offset: usize, len: usize) -> Result<(), TrapCode> {
+        if self.read_only.overlaps(offset, len) {
+            return Err(TrapCode::MemoryReadOnly);
+        }
+        Ok(())
+    }
+
     /// Returns a shared slice to the bytes underlying to the byte buffer.
     pub fn data(&self) -> &[u8] {
         self.bytes.data()
@@ -645,6 +715,42 @@ impl MemoryEntity {
         slice.copy_from_slice(buffer);
         Ok(())
     }
+
+    /// Returns the range of `len` bytes starting at `offset` in the linear memory.
+    ///
+    /// # Errors
+    ///
+    /// If the range is out of bounds of the linear memory.
+    fn range64(&self, offset: u64, len: u64) -> Result<Range<usize>, MemoryError> {
+        let end = offset
+            .checked_add(len)
+            .filter(|&end| end <= self.size_in_bytes())
+            .ok_or(MemoryError::OutOfBoundsAccess)?;
+        // Note: both conversions cannot fail since `end` is within bounds of the linear memory.
+        let start = usize::try_from(offset).map_err(|_| MemoryError::OutOfBoundsAccess)?;
+        let end = usize::try_from(end).map_err(|_| MemoryError::OutOfBoundsAccess)?;
+        Ok(start..end)
+    }
+
+    /// Returns a shared slice to `memory[offset..offset+len]`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn data64(&self, offset: u64, len: u64) -> Result<&[u8], MemoryError> {
+        let range = self.range64(offset, len)?;
+        Ok(&self.data()[range])
+    }
+
+    /// Returns an exclusive slice to `memory[offset..offset+len]`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn data64_mut(&mut self, offset: u64, len: u64) -> Result<&mut [u8], MemoryError> {
+        let range = self.range64(offset, len)?;
+        Ok(&mut self.data_mut()[range])
+    }
 }
 
 /// A Wasm linear memory reference.
@@ -700,6 +806,29 @@ impl Memory {
         Ok(memory)
     }
 
+    /// Creates a new linear memory to the store backed by the [`MemoryProvider`].
+    ///
+    /// # Errors
+    ///
+    /// - If more than [`u32::MAX`] much linear memory is allocated.
+    /// - If `provider` already provides more bytes than the minimum size of `ty`.
+    /// - If `provider` failed to provide the minimum size of `ty`.
+    pub fn new_with_provider(
+        mut ctx: impl AsContextMut,
+        ty: MemoryType,
+        provider: impl MemoryProvider,
+    ) -> Result<Self, Error> {
+        let (inner, mut resource_limiter) = ctx
+            .as_context_mut()
+            .store
+            .store_inner_and_resource_limiter_ref();
+
+        let entity =
+            MemoryEntity::new_with_provider(ty, &mut resource_limiter, Box::new(provider))?;
+        let memory = inner.alloc_memory(entity);
+        Ok(memory)
+    }
+
     /// Returns the memory type of the linear memory.
     ///
     /// # Panics
@@ -768,6 +897,18 @@ impl Memory {
         ctx.into().store.inner.resolve_memory(self).data()
     }
 
+    /// Returns a shared [`MemoryView`] to the bytes underlying the [`Memory`].
+    ///
+    /// The returned [`MemoryView`] can be copied cheaply to hand out the bytes
+    /// to multiple host readers without copying them while execution is paused.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn data_shared<'a, T: 'a>(&self, ctx: impl Into<StoreContext<'a, T>>) -> MemoryView<'a> {
+        MemoryView::new(self.data(ctx))
+    }
+
     /// Returns an exclusive slice to the bytes underlying the [`Memory`].
     ///
     /// # Panics
@@ -860,4 +1001,360 @@ impl Memory {
             .resolve_memory_mut(self)
             .write(offset, buffer)
     }
+
+    /// Sets the `protection` of the bytes of this [`Memory`] within `range`.
+    ///
+    /// Writes of Wasm code to [`Protection::ReadOnly`] bytes trap with
+    /// [`TrapCode::MemoryReadOnly`] without modifying the [`Memory`].
+    /// This includes stores, `memory.fill`, `memory.copy`, `memory.init`
+    /// and the active data segments of instantiated [`Module`]s.
+    ///
+    /// # Note
+    ///
+    /// - The host may still write to [`Protection::ReadOnly`] bytes,
+    ///   for example via [`Memory::write`] or [`Memory::data_mut`].
+    /// - The protection of bytes added via [`Memory::grow`] is [`Protection::ReadWrite`].
+    ///
+    /// # Errors
+    ///
+    /// If `range` is out of bounds of the [`Memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    ///
+    /// [`Module`]: crate::Module
+    pub fn protect(
+        &self,
+        mut ctx: impl AsContextMut,
+        range: Range<u64>,
+        protection: Protection,
+    ) -> Result<(), MemoryError> {
+        ctx.as_context_mut()
+            .store
+            .inner
+            .set_memory_protection(self, range, protection)
+    }
+
+    /// Returns the byte length of this [`Memory`] as `u64`.
+    ///
+    /// # Note
+    ///
+    /// In contrast to [`Memory::data_size`] this is lossless for `memory64`
+    /// linear memories on all platforms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn size_in_bytes(&self, ctx: impl AsContext) -> u64 {
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_memory(self)
+            .size_in_bytes()
+    }
+
+    /// Returns a shared slice to `memory[offset..offset+len]`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn data64<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContext<'a, T>>,
+        offset: u64,
+        len: u64,
+    ) -> Result<&'a [u8], MemoryError> {
+        ctx.into()
+            .store
+            .inner
+            .resolve_memory(self)
+            .data64(offset, len)
+    }
+
+    /// Returns an exclusive slice to `memory[offset..offset+len]`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn data64_mut<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContextMut<'a, T>>,
+        offset: u64,
+        len: u64,
+    ) -> Result<&'a mut [u8], MemoryError> {
+        ctx.into()
+            .store
+            .inner
+            .resolve_memory_mut(self)
+            .data64_mut(offset, len)
+    }
+
+    /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
+    /// where `n` is the length of `buffer`.
+    ///
+    /// # Note
+    ///
+    /// This is the same as [`Memory::read`] but takes a 64-bit `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn read64(
+        &self,
+        ctx: impl AsContext,
+        offset: u64,
+        buffer: &mut [u8],
+    ) -> Result<(), MemoryError> {
+        let len = u64::try_from(buffer.len()).map_err(|_| MemoryError::OutOfBoundsAccess)?;
+        let bytes = ctx
+            .as_context()
+            .store
+            .inner
+            .resolve_memory(self)
+            .data64(offset, len)?;
+        buffer.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Writes `n` bytes to `memory[offset..offset+n]` from `buffer`
+    /// where `n` if the length of `buffer`.
+    ///
+    /// # Note
+    ///
+    /// This is the same as [`Memory::write`] but takes a 64-bit `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn write64(
+        &self,
+        mut ctx: impl AsContextMut,
+        offset: u64,
+        buffer: &[u8],
+    ) -> Result<(), MemoryError> {
+        let len = u64::try_from(buffer.len()).map_err(|_| MemoryError::OutOfBoundsAccess)?;
+        ctx.as_context_mut()
+            .store
+            .inner
+            .resolve_memory_mut(self)
+            .data64_mut(offset, len)?
+            .copy_from_slice(buffer);
+        Ok(())
+    }
+    /// Reads the little-endian scalar `T` from `memory[offset..]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmi::{Memory, MemoryType, Store};
+    /// # let mut store = <Store<()>>::default();
+    /// let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    /// memory.write(&mut store, 8, &[0x78, 0x56, 0x34, 0x12]).unwrap();
+    /// assert_eq!(memory.read_scalar::<u32>(&store, 8).unwrap(), 0x1234_5678);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn read_scalar<T: MemoryScalar>(
+        &self,
+        ctx: impl AsContext,
+        offset: u64,
+    ) -> Result<T, MemoryError> {
+        typed::read_scalar(self.data(ctx.as_context()), offset)
+    }
+
+    /// Writes the scalar `value` to `memory[offset..]` in little-endian byte order.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn write_scalar<T: MemoryScalar>(
+        &self,
+        mut ctx: impl AsContextMut,
+        offset: u64,
+        value: T,
+    ) -> Result<(), MemoryError> {
+        typed::write_scalar(self.data_mut(ctx.as_context_mut()), offset, value)
+    }
+
+    /// Returns a shared slice of `len` values of type `T` at `memory[offset..]`.
+    ///
+    /// # Note
+    ///
+    /// The values are viewed in place and thus in the byte order of the host,
+    /// see [`Pod`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmi::{Memory, MemoryType, Store};
+    /// # let mut store = <Store<()>>::default();
+    /// let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    /// memory.typed_slice_mut::<u16, _>(&mut store, 4, 3).unwrap().copy_from_slice(&[1, 2, 3]);
+    /// assert_eq!(memory.typed_slice::<u16, _>(&store, 4, 3).unwrap(), [1, 2, 3]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - If this operation accesses out of bounds linear memory.
+    /// - If `offset` is not properly aligned for `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn typed_slice<'a, T: Pod, U: 'a>(
+        &self,
+        ctx: impl Into<StoreContext<'a, U>>,
+        offset: u64,
+        len: usize,
+    ) -> Result<&'a [T], MemoryError> {
+        typed::typed_slice(self.data(ctx), offset, len)
+    }
+
+    /// Returns an exclusive slice of `len` values of type `T` at `memory[offset..]`.
+    ///
+    /// # Note
+    ///
+    /// The values are viewed in place and thus in the byte order of the host,
+    /// see [`Pod`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// - If this operation accesses out of bounds linear memory.
+    /// - If `offset` is not properly aligned for `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn typed_slice_mut<'a, T: Pod, U: 'a>(
+        &self,
+        ctx: impl Into<StoreContextMut<'a, U>>,
+        offset: u64,
+        len: usize,
+    ) -> Result<&'a mut [T], MemoryError> {
+        typed::typed_slice_mut(self.data_mut(ctx), offset, len)
+    }
+    /// Reads the UTF-8 encoded string of `len` bytes at `memory[ptr..]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmi::{Memory, MemoryType, Store};
+    /// # let mut store = <Store<()>>::default();
+    /// let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    /// memory.write_utf8(&mut store, 16, "hello").unwrap();
+    /// assert_eq!(memory.read_utf8(&store, 16, 5).unwrap(), "hello");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - If this operation accesses out of bounds linear memory.
+    /// - If the bytes are not valid UTF-8.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn read_utf8<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContext<'a, T>>,
+        ptr: u64,
+        len: u64,
+    ) -> Result<&'a str, MemoryError> {
+        string::read_utf8(self.data(ctx), ptr, len)
+    }
+
+    /// Reads the nul-terminated UTF-8 encoded string at `memory[ptr..]`.
+    ///
+    /// The returned string excludes the nul terminator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmi::{Memory, MemoryType, Store};
+    /// # let mut store = <Store<()>>::default();
+    /// let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    /// memory.write_cstr(&mut store, 16, "hello").unwrap();
+    /// assert_eq!(memory.read_cstr(&store, 16).unwrap(), "hello");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - If `ptr` is out of bounds or there is no nul terminator within bounds.
+    /// - If the bytes are not valid UTF-8.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn read_cstr<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContext<'a, T>>,
+        ptr: u64,
+    ) -> Result<&'a str, MemoryError> {
+        string::read_cstr(self.data(ctx), ptr)
+    }
+
+    /// Writes the UTF-8 encoded `string` to `memory[ptr..]` without a nul terminator.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn write_utf8(
+        &self,
+        mut ctx: impl AsContextMut,
+        ptr: u64,
+        string: &str,
+    ) -> Result<(), MemoryError> {
+        string::write_utf8(self.data_mut(ctx.as_context_mut()), ptr, string)
+    }
+
+    /// Writes the UTF-8 encoded `string` to `memory[ptr..]` followed by a nul terminator.
+    ///
+    /// Writes nothing if this operation fails.
+    ///
+    /// # Errors
+    ///
+    /// - If `string` contains a nul byte.
+    /// - If this operation accesses out of bounds linear memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn write_cstr(
+        &self,
+        mut ctx: impl AsContextMut,
+        ptr: u64,
+        string: &str,
+    ) -> Result<(), MemoryError> {
+        string::write_cstr(self.data_mut(ctx.as_context_mut()), ptr, string)
+    }
 }
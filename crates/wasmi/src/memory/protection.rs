@@ -0,0 +1,76 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The access protection of a range of bytes of a linear [`Memory`].
+///
+/// [`Memory`]: crate::Memory
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Protection {
+    /// Wasm code may read and write the bytes.
+    ///
+    /// This is the default for all bytes of a linear [`Memory`].
+    ///
+    /// [`Memory`]: crate::Memory
+    ReadWrite,
+    /// Wasm code may read but not write the bytes.
+    ///
+    /// Writes of Wasm code to the bytes trap with [`TrapCode::MemoryReadOnly`].
+    ///
+    /// [`TrapCode::MemoryReadOnly`]: crate::core::TrapCode::MemoryReadOnly
+    ReadOnly,
+}
+
+/// The read-only byte ranges of a linear memory.
+#[derive(Debug, Default)]
+pub struct ReadOnlyRanges {
+    /// The sorted, disjoint and non-adjacent read-only byte ranges.
+    ranges: Vec<Range<usize>>,
+}
+
+impl ReadOnlyRanges {
+    /// Returns `true` if no byte is read-only.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Sets the `protection` of all bytes within `range`.
+    pub fn protect(&mut self, range: Range<usize>, protection: Protection) {
+        if range.is_empty() {
+            return;
+        }
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 2);
+        for r in self.ranges.drain(..) {
+            if r.end <= range.start || range.end <= r.start {
+                ranges.push(r);
+                continue;
+            }
+            if r.start < range.start {
+                ranges.push(r.start..range.start);
+            }
+            if range.end < r.end {
+                ranges.push(range.end..r.end);
+            }
+        }
+        if let Protection::ReadOnly = protection {
+            let index = ranges.partition_point(|r| r.start < range.start);
+            ranges.insert(index, range);
+        }
+        // Note: merge adjacent ranges so that lookups stay cheap.
+        for r in ranges {
+            match self.ranges.last_mut() {
+                Some(last) if last.end == r.start => last.end = r.end,
+                _ => self.ranges.push(r),
+            }
+        }
+    }
+
+    /// Returns `true` if any of the `len` bytes starting at `offset` is read-only.
+    pub fn overlaps(&self, offset: usize, len: usize) -> bool {
+        if len == 0 {
+            return false;
+        }
+        let end = offset.saturating_add(len);
+        let index = self.ranges.partition_point(|r| r.end <= offset);
+        self.ranges.get(index).is_some_and(|r| r.start < end)
+    }
+}
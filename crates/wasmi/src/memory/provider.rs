@@ -0,0 +1,40 @@
+use super::MemoryError;
+
+/// Provides the backing storage of a linear [`Memory`](crate::Memory).
+///
+/// This allows embedders to decide where the bytes of a linear memory live,
+/// e.g. in shared memory segments, in static buffers on embedded targets or
+/// in instrumented allocators.
+///
+/// Use [`Memory::new_with_provider`](crate::Memory::new_with_provider) to create a
+/// linear memory that is backed by a [`MemoryProvider`].
+///
+/// # Safety
+///
+/// Wasmi caches the address and length of the provided byte slice while executing
+/// Wasm code. Therefore implementers must make sure that:
+///
+/// - [`MemoryProvider::as_slice`] and [`MemoryProvider::as_slice_mut`] always return
+///   slices with the same address and length in between calls to [`MemoryProvider::grow`].
+/// - After a successful call to [`MemoryProvider::grow`] with `new_size` the provided
+///   slices have a length of exactly `new_size` bytes and the first bytes are unchanged.
+pub unsafe trait MemoryProvider: Send + Sync + 'static {
+    /// Grows the provided bytes to `new_size` bytes.
+    ///
+    /// Wasmi zero initializes all newly added bytes after a successful call.
+    ///
+    /// # Note
+    ///
+    /// Wasmi guarantees that `new_size` is never smaller than the current length.
+    ///
+    /// # Errors
+    ///
+    /// If the provided bytes cannot be grown to `new_size` bytes.
+    fn grow(&mut self, new_size: usize) -> Result<(), MemoryError>;
+
+    /// Returns a shared slice to the provided bytes.
+    fn as_slice(&self) -> &[u8];
+
+    /// Returns an exclusive slice to the provided bytes.
+    fn as_slice_mut(&mut self) -> &mut [u8];
+}
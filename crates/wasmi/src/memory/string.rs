@@ -0,0 +1,90 @@
+use super::MemoryError;
+use core::str;
+
+/// Returns the bytes of `bytes` within `offset..offset+len`.
+///
+/// # Errors
+///
+/// If the range is out of bounds of `bytes`.
+pub(super) fn get(bytes: &[u8], offset: u64, len: u64) -> Result<&[u8], MemoryError> {
+    let start = usize::try_from(offset).map_err(|_| MemoryError::OutOfBoundsAccess)?;
+    let len = usize::try_from(len).map_err(|_| MemoryError::OutOfBoundsAccess)?;
+    start
+        .checked_add(len)
+        .and_then(|end| bytes.get(start..end))
+        .ok_or(MemoryError::OutOfBoundsAccess)
+}
+
+/// Returns the exclusive bytes of `bytes` within `offset..offset+len`.
+///
+/// # Errors
+///
+/// If the range is out of bounds of `bytes`.
+fn get_mut(bytes: &mut [u8], offset: u64, len: usize) -> Result<&mut [u8], MemoryError> {
+    let start = usize::try_from(offset).map_err(|_| MemoryError::OutOfBoundsAccess)?;
+    start
+        .checked_add(len)
+        .and_then(|end| bytes.get_mut(start..end))
+        .ok_or(MemoryError::OutOfBoundsAccess)
+}
+
+/// Reads the UTF-8 encoded string of `len` bytes from `bytes` at `ptr`.
+///
+/// # Errors
+///
+/// - If the access is out of bounds of `bytes`.
+/// - If the bytes are not valid UTF-8.
+pub(super) fn read_utf8(bytes: &[u8], ptr: u64, len: u64) -> Result<&str, MemoryError> {
+    let bytes = get(bytes, ptr, len)?;
+    str::from_utf8(bytes).map_err(MemoryError::InvalidUtf8)
+}
+
+/// Reads the nul-terminated UTF-8 encoded string from `bytes` at `ptr`.
+///
+/// The returned string excludes the nul terminator.
+///
+/// # Errors
+///
+/// - If `ptr` is out of bounds of `bytes` or there is no nul terminator within bounds.
+/// - If the bytes are not valid UTF-8.
+pub(super) fn read_cstr(bytes: &[u8], ptr: u64) -> Result<&str, MemoryError> {
+    let start = usize::try_from(ptr).map_err(|_| MemoryError::OutOfBoundsAccess)?;
+    let tail = bytes.get(start..).ok_or(MemoryError::OutOfBoundsAccess)?;
+    let len = tail
+        .iter()
+        .position(|&byte| byte == 0)
+        .ok_or(MemoryError::OutOfBoundsAccess)?;
+    str::from_utf8(&tail[..len]).map_err(MemoryError::InvalidUtf8)
+}
+
+/// Writes the UTF-8 encoded `string` to `bytes` at `ptr` without a nul terminator.
+///
+/// # Errors
+///
+/// If the access is out of bounds of `bytes`.
+pub(super) fn write_utf8(bytes: &mut [u8], ptr: u64, string: &str) -> Result<(), MemoryError> {
+    get_mut(bytes, ptr, string.len())?.copy_from_slice(string.as_bytes());
+    Ok(())
+}
+
+/// Writes the UTF-8 encoded `string` to `bytes` at `ptr` followed by a nul terminator.
+///
+/// # Errors
+///
+/// - If `string` contains a nul byte.
+/// - If the access is out of bounds of `bytes`.
+pub(super) fn write_cstr(bytes: &mut [u8], ptr: u64, string: &str) -> Result<(), MemoryError> {
+    if string.as_bytes().contains(&0) {
+        return Err(MemoryError::InteriorNul);
+    }
+    let len = string
+        .len()
+        .checked_add(1)
+        .ok_or(MemoryError::OutOfBoundsAccess)?;
+    let (nul, text) = get_mut(bytes, ptr, len)?
+        .split_last_mut()
+        .unwrap_or_else(|| unreachable!("the written range has at least one byte"));
+    text.copy_from_slice(string.as_bytes());
+    *nul = 0;
+    Ok(())
+}
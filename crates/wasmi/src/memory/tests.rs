@@ -14,3 +14,127 @@ fn subtyping_works() {
     assert!(memory_type(0, 1).is_subtype_of(&memory_type(0, None)));
     assert!(!memory_type(0, None).is_subtype_of(&memory_type(0, 1)));
 }
+
+#[test]
+fn memory_view_read_works() {
+    let bytes = [0x00, 0x01, 0x02, 0x03];
+    let view = MemoryView::new(&bytes);
+    let mut buffer = [0x00; 2];
+    view.read(1, &mut buffer).unwrap();
+    assert_eq!(buffer, [0x01, 0x02]);
+    assert!(view.read(3, &mut buffer).is_err());
+    assert!(view.read(u64::MAX, &mut buffer).is_err());
+    assert_eq!(view.slice(2, 2).unwrap(), &bytes[2..]);
+    assert!(view.slice(2, 3).is_err());
+    assert!(view.slice(u64::MAX, 1).is_err());
+    assert_eq!(view.as_slice().len(), 4);
+}
+
+#[test]
+fn read_only_ranges_protect_works() {
+    let mut ranges = ReadOnlyRanges::default();
+    assert!(ranges.is_empty());
+    ranges.protect(4..8, Protection::ReadOnly);
+    ranges.protect(8..12, Protection::ReadOnly);
+    ranges.protect(0..0, Protection::ReadOnly);
+    assert!(!ranges.overlaps(0, 4));
+    assert!(ranges.overlaps(3, 2));
+    assert!(ranges.overlaps(11, 1));
+    assert!(!ranges.overlaps(12, usize::MAX));
+    assert!(!ranges.overlaps(6, 0));
+    ranges.protect(6..10, Protection::ReadWrite);
+    assert!(ranges.overlaps(5, 1));
+    assert!(!ranges.overlaps(6, 4));
+    assert!(ranges.overlaps(9, 2));
+    ranges.protect(0..16, Protection::ReadWrite);
+    assert!(ranges.is_empty());
+}
+
+#[test]
+fn typed_scalar_accesses_are_little_endian() {
+    let mut bytes = [0x00_u8; 8];
+    typed::write_scalar(&mut bytes, 2, 0x1234_i16).unwrap();
+    assert_eq!(bytes[2..4], [0x34, 0x12]);
+    assert_eq!(typed::read_scalar::<u16>(&bytes, 2).unwrap(), 0x1234);
+    typed::write_scalar(&mut bytes, 0, -1.5_f64).unwrap();
+    assert_eq!(typed::read_scalar::<f64>(&bytes, 0).unwrap(), -1.5);
+    assert!(typed::read_scalar::<u32>(&bytes, 5).is_err());
+    assert!(typed::read_scalar::<u8>(&bytes, u64::MAX).is_err());
+    assert!(typed::write_scalar(&mut bytes, 7, 0_u16).is_err());
+}
+
+#[test]
+fn typed_slices_check_bounds_and_alignment() {
+    let mut words = [0_u64; 2];
+    // Safety: the `u64` words are valid to view as properly aligned bytes.
+    let bytes = unsafe { core::slice::from_raw_parts_mut(words.as_mut_ptr().cast::<u8>(), 16) };
+    typed::typed_slice_mut::<u32>(bytes, 4, 3)
+        .unwrap()
+        .copy_from_slice(&[1, 2, 3]);
+    assert_eq!(typed::typed_slice::<u32>(bytes, 4, 3).unwrap(), [1, 2, 3]);
+    assert_eq!(typed::typed_slice::<[u8; 4]>(bytes, 1, 2).unwrap().len(), 2);
+    assert!(matches!(
+        typed::typed_slice::<u32>(bytes, 2, 1),
+        Err(MemoryError::UnalignedAccess)
+    ));
+    assert!(matches!(
+        typed::typed_slice::<u32>(bytes, 8, 3),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    assert!(matches!(
+        typed::typed_slice::<u64>(bytes, 0, usize::MAX),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    assert!(typed::typed_slice::<u64>(bytes, 16, 0).unwrap().is_empty());
+}
+
+#[test]
+fn string_reads_work() {
+    let bytes = b"hello\0w\xFFrld\0";
+    assert_eq!(string::read_utf8(bytes, 0, 5).unwrap(), "hello");
+    assert_eq!(string::read_utf8(bytes, 5, 0).unwrap(), "");
+    assert_eq!(string::read_cstr(bytes, 0).unwrap(), "hello");
+    assert_eq!(string::read_cstr(bytes, 5).unwrap(), "");
+    assert!(matches!(
+        string::read_cstr(bytes, 6),
+        Err(MemoryError::InvalidUtf8(_))
+    ));
+    assert!(matches!(
+        string::read_utf8(bytes, 6, 3),
+        Err(MemoryError::InvalidUtf8(_))
+    ));
+    assert!(matches!(
+        string::read_utf8(bytes, 10, 10),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    assert!(matches!(
+        string::read_cstr(&bytes[..4], 0),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    assert!(matches!(
+        string::read_cstr(bytes, u64::MAX),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+}
+
+#[test]
+fn string_writes_work() {
+    let mut bytes = [0xFF_u8; 8];
+    string::write_cstr(&mut bytes, 1, "abc").unwrap();
+    assert_eq!(bytes, [0xFF, b'a', b'b', b'c', 0, 0xFF, 0xFF, 0xFF]);
+    string::write_utf8(&mut bytes, 5, "xyz").unwrap();
+    assert_eq!(bytes[5..], *b"xyz");
+    assert!(matches!(
+        string::write_cstr(&mut bytes, 0, "a\0b"),
+        Err(MemoryError::InteriorNul)
+    ));
+    assert!(matches!(
+        string::write_cstr(&mut bytes, 5, "xyz"),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    assert!(matches!(
+        string::write_utf8(&mut bytes, 6, "xyz"),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    assert_eq!(bytes[5..], *b"xyz");
+}
@@ -0,0 +1,184 @@
+use super::MemoryError;
+use core::{mem, ops::Range};
+
+/// Scalar values that are stored in linear memory in little-endian byte order.
+///
+/// Used by [`Memory::read_scalar`] and [`Memory::write_scalar`].
+///
+/// [`Memory::read_scalar`]: crate::Memory::read_scalar
+/// [`Memory::write_scalar`]: crate::Memory::write_scalar
+pub trait MemoryScalar: Copy + private::Sealed {
+    /// The little-endian byte representation of the scalar.
+    #[doc(hidden)]
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Default;
+
+    /// Creates the scalar from its little-endian byte representation.
+    #[doc(hidden)]
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+
+    /// Returns the little-endian byte representation of the scalar.
+    #[doc(hidden)]
+    fn to_le_bytes(self) -> Self::Bytes;
+}
+
+mod private {
+    /// Prevents implementations of [`MemoryScalar`](super::MemoryScalar) outside of Wasmi.
+    pub trait Sealed {}
+}
+
+macro_rules! impl_memory_scalar {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl private::Sealed for $ty {}
+
+            impl MemoryScalar for $ty {
+                type Bytes = [u8; mem::size_of::<$ty>()];
+
+                #[inline]
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    <$ty>::from_le_bytes(bytes)
+                }
+
+                #[inline]
+                fn to_le_bytes(self) -> Self::Bytes {
+                    <$ty>::to_le_bytes(self)
+                }
+            }
+        )*
+    };
+}
+impl_memory_scalar!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, f32, f64);
+
+/// Plain-old-data types that can be viewed in place within linear memory.
+///
+/// Used by [`Memory::typed_slice`] and [`Memory::typed_slice_mut`].
+///
+/// # Note
+///
+/// Values are viewed in the byte order of the host while Wasm uses little-endian
+/// byte order. Use [`MemoryScalar`] for portable accesses of multi-byte scalars.
+///
+/// # Safety
+///
+/// Implementers must make sure that the type:
+///
+/// - has no padding bytes,
+/// - is valid for any bit pattern,
+/// - contains no pointers, references or other types with interior invariants.
+///
+/// This usually means the type is a `#[repr(C)]` or `#[repr(transparent)]` struct
+/// with only [`Pod`] fields that are laid out without padding.
+///
+/// [`Memory::typed_slice`]: crate::Memory::typed_slice
+/// [`Memory::typed_slice_mut`]: crate::Memory::typed_slice_mut
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            // Safety: primitive integers and floats are valid for any bit pattern.
+            unsafe impl Pod for $ty {}
+        )*
+    };
+}
+impl_pod!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, f32, f64);
+
+// Safety: arrays have no padding and are valid for any bit pattern if their elements are.
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// Returns the range of `len` bytes starting at `offset` within `bytes`.
+///
+/// # Errors
+///
+/// If the range is out of bounds of `bytes`.
+fn range(bytes: &[u8], offset: u64, len: usize) -> Result<Range<usize>, MemoryError> {
+    let start = usize::try_from(offset).map_err(|_| MemoryError::OutOfBoundsAccess)?;
+    let end = start
+        .checked_add(len)
+        .filter(|&end| end <= bytes.len())
+        .ok_or(MemoryError::OutOfBoundsAccess)?;
+    Ok(start..end)
+}
+
+/// Reads the little-endian scalar `T` from `bytes` at `offset`.
+///
+/// # Errors
+///
+/// If the access is out of bounds of `bytes`.
+pub(super) fn read_scalar<T: MemoryScalar>(bytes: &[u8], offset: u64) -> Result<T, MemoryError> {
+    let mut buffer = T::Bytes::default();
+    let range = range(bytes, offset, buffer.as_ref().len())?;
+    buffer.as_mut().copy_from_slice(&bytes[range]);
+    Ok(T::from_le_bytes(buffer))
+}
+
+/// Writes the scalar `value` to `bytes` at `offset` in little-endian byte order.
+///
+/// # Errors
+///
+/// If the access is out of bounds of `bytes`.
+pub(super) fn write_scalar<T: MemoryScalar>(
+    bytes: &mut [u8],
+    offset: u64,
+    value: T,
+) -> Result<(), MemoryError> {
+    let buffer = value.to_le_bytes();
+    let range = range(bytes, offset, buffer.as_ref().len())?;
+    bytes[range].copy_from_slice(buffer.as_ref());
+    Ok(())
+}
+
+/// Returns the range of `len` values of type `T` starting at `offset` within `bytes`.
+///
+/// # Errors
+///
+/// - If the range is out of bounds of `bytes`.
+/// - If the range is not properly aligned for `T`.
+fn typed_range<T: Pod>(bytes: &[u8], offset: u64, len: usize) -> Result<Range<usize>, MemoryError> {
+    let len_bytes = len
+        .checked_mul(mem::size_of::<T>())
+        .ok_or(MemoryError::OutOfBoundsAccess)?;
+    let range = range(bytes, offset, len_bytes)?;
+    let address = bytes.as_ptr() as usize + range.start;
+    if address % mem::align_of::<T>() != 0 {
+        return Err(MemoryError::UnalignedAccess);
+    }
+    Ok(range)
+}
+
+/// Returns a shared slice of `len` values of type `T` within `bytes` at `offset`.
+///
+/// # Errors
+///
+/// - If the access is out of bounds of `bytes`.
+/// - If `offset` is not properly aligned for `T`.
+pub(super) fn typed_slice<T: Pod>(
+    bytes: &[u8],
+    offset: u64,
+    len: usize,
+) -> Result<&[T], MemoryError> {
+    let range = typed_range::<T>(bytes, offset, len)?;
+    let ptr = bytes[range].as_ptr().cast::<T>();
+    // Safety: the bytes are in bounds and properly aligned for `len` values of type `T`
+    //         which is valid for any bit pattern as guaranteed by the `Pod` trait.
+    Ok(unsafe { core::slice::from_raw_parts(ptr, len) })
+}
+
+/// Returns an exclusive slice of `len` values of type `T` within `bytes` at `offset`.
+///
+/// # Errors
+///
+/// - If the access is out of bounds of `bytes`.
+/// - If `offset` is not properly aligned for `T`.
+pub(super) fn typed_slice_mut<T: Pod>(
+    bytes: &mut [u8],
+    offset: u64,
+    len: usize,
+) -> Result<&mut [T], MemoryError> {
+    let range = typed_range::<T>(bytes, offset, len)?;
+    let ptr = bytes[range].as_mut_ptr().cast::<T>();
+    // Safety: the bytes are in bounds and properly aligned for `len` values of type `T`
+    //         which has no padding and is valid for any bit pattern as guaranteed by
+    //         the `Pod` trait.
+    Ok(unsafe { core::slice::from_raw_parts_mut(ptr, len) })
+}
@@ -0,0 +1,119 @@
+use super::{string, typed, MemoryError, MemoryScalar, Pod};
+
+/// A shared view into the bytes of a linear [`Memory`].
+///
+/// Created via [`Memory::data_shared`].
+///
+/// # Note
+///
+/// - A [`MemoryView`] borrows the [`Store`](crate::Store) that owns the [`Memory`].
+///   Thus it is impossible to execute Wasm code or to mutate the [`Memory`] for as long
+///   as the [`MemoryView`] or any of its slices are alive.
+/// - A [`MemoryView`] is cheap to copy which allows to hand out the bytes of the
+///   [`Memory`] to multiple host readers without copying them.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{Engine, Memory, MemoryType, Store};
+/// # let mut store = <Store<()>>::default();
+/// let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+/// memory.write(&mut store, 0, b"hello, world!").unwrap();
+/// let view = memory.data_shared(&store);
+/// let (hello, world) = (view.slice(0, 5).unwrap(), view.slice(7, 5).unwrap());
+/// assert_eq!(hello, b"hello");
+/// assert_eq!(world, b"world");
+/// ```
+///
+/// Mutating the [`Memory`] while a [`MemoryView`] is alive is rejected at compile time:
+///
+/// ```compile_fail
+/// # use wasmi::{Engine, Memory, MemoryType, Store};
+/// # let mut store = <Store<()>>::default();
+/// let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+/// let view = memory.data_shared(&store);
+/// memory.write(&mut store, 0, b"hello").unwrap();
+/// assert_eq!(view.slice(0, 5).unwrap(), b"hello");
+/// ```
+///
+/// [`Memory`]: crate::Memory
+/// [`Memory::data_shared`]: crate::Memory::data_shared
+#[derive(Debug, Copy, Clone)]
+pub struct MemoryView<'a> {
+    /// The bytes of the viewed linear memory.
+    bytes: &'a [u8],
+}
+
+impl<'a> MemoryView<'a> {
+    /// Creates a new [`MemoryView`] for the `bytes` of a linear memory.
+    pub(super) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the shared slice to all bytes of the viewed linear memory.
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the shared slice of `len` bytes at `memory[offset..]`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn slice(&self, offset: u64, len: u64) -> Result<&'a [u8], MemoryError> {
+        string::get(self.bytes, offset, len)
+    }
+
+    /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
+    /// where `n` is the length of `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn read(&self, offset: u64, buffer: &mut [u8]) -> Result<(), MemoryError> {
+        let len = u64::try_from(buffer.len()).map_err(|_| MemoryError::OutOfBoundsAccess)?;
+        buffer.copy_from_slice(self.slice(offset, len)?);
+        Ok(())
+    }
+
+    /// Reads the little-endian scalar `T` from `memory[offset..]`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn read_scalar<T: MemoryScalar>(&self, offset: u64) -> Result<T, MemoryError> {
+        typed::read_scalar(self.bytes, offset)
+    }
+
+    /// Returns a shared slice of `len` values of type `T` at `memory[offset..]`.
+    ///
+    /// # Errors
+    ///
+    /// - If this operation accesses out of bounds linear memory.
+    /// - If `offset` is not properly aligned for `T`.
+    pub fn typed_slice<T: Pod>(&self, offset: u64, len: usize) -> Result<&'a [T], MemoryError> {
+        typed::typed_slice(self.bytes, offset, len)
+    }
+
+    /// Reads the UTF-8 encoded string of `len` bytes at `memory[ptr..]`.
+    ///
+    /// # Errors
+    ///
+    /// - If this operation accesses out of bounds linear memory.
+    /// - If the bytes are not valid UTF-8.
+    pub fn read_utf8(&self, ptr: u64, len: u64) -> Result<&'a str, MemoryError> {
+        string::read_utf8(self.bytes, ptr, len)
+    }
+
+    /// Reads the nul-terminated UTF-8 encoded string at `memory[ptr..]`.
+    ///
+    /// The returned string excludes the nul terminator.
+    ///
+    /// # Errors
+    ///
+    /// - If `ptr` is out of bounds or there is no nul terminator within bounds.
+    /// - If the bytes are not valid UTF-8.
+    pub fn read_cstr(&self, ptr: u64) -> Result<&'a str, MemoryError> {
+        string::read_cstr(self.bytes, ptr)
+    }
+}
@@ -13,6 +13,7 @@ use super::{
     ImportName,
     Imported,
     Module,
+    ModuleHash,
     ModuleHeader,
     ModuleHeaderInner,
     ModuleImports,
@@ -36,6 +37,8 @@ pub struct ModuleBuilder {
     pub header: ModuleHeader,
     pub data_segments: DataSegmentsBuilder,
     pub custom_sections: CustomSectionsBuilder,
+    #[cfg(feature = "to-wat")]
+    pub wasm: Arc<[u8]>,
 }
 
 /// A builder for a WebAssembly [`Module`] header.
@@ -137,6 +140,8 @@ impl ModuleBuilder {
             header,
             data_segments: DataSegments::build(),
             custom_sections,
+            #[cfg(feature = "to-wat")]
+            wasm: Arc::from([]),
         }
     }
 }
@@ -398,8 +403,12 @@ impl ModuleHeaderBuilder {
 
 impl ModuleBuilder {
     /// Reserve space for at least `additional` new data segments.
-    pub fn reserve_data_segments(&mut self, additional: usize) {
-        self.data_segments.reserve(additional);
+    ///
+    /// # Errors
+    ///
+    /// If the system ran out of memory.
+    pub fn reserve_data_segments(&mut self, additional: usize) -> Result<(), Error> {
+        self.data_segments.reserve(additional)
     }
 
     /// Push another parsed data segment to the [`ModuleBuilder`].
@@ -407,14 +416,26 @@ impl ModuleBuilder {
         self.data_segments.push_data_segment(data)
     }
 
+    /// Sets the original Wasm binary of the [`Module`] under construction.
+    #[cfg(feature = "to-wat")]
+    pub fn with_wasm(mut self, wasm: &[u8]) -> Self {
+        self.wasm = wasm.into();
+        self
+    }
+
     /// Finishes construction of the WebAssembly [`Module`].
-    pub fn finish(self, engine: &Engine) -> Module {
+    ///
+    /// The `content_hash` is the hash of all non-custom sections of the [`Module`].
+    pub fn finish(self, engine: &Engine, content_hash: ModuleHash) -> Module {
         Module {
             inner: Arc::new(ModuleInner {
                 engine: engine.clone(),
                 header: self.header,
                 data_segments: self.data_segments.finish(),
                 custom_sections: self.custom_sections.finish(),
+                content_hash,
+                #[cfg(feature = "to-wat")]
+                wasm: Some(self.wasm),
             }),
         }
     }
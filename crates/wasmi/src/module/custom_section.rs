@@ -2,7 +2,7 @@ use alloc::vec::Vec;
 use core::{slice, str};
 
 /// Wasm custom sections.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct CustomSections {
     inner: CustomSectionsInner,
 }
@@ -13,6 +13,18 @@ impl CustomSections {
     pub fn iter(&self) -> CustomSectionsIter {
         self.inner.iter()
     }
+
+    /// Returns new [`CustomSections`] only containing the [`CustomSection`]s for which `f` returns `true`.
+    pub fn filter<F>(&self, mut f: F) -> Self
+    where
+        F: FnMut(&CustomSection) -> bool,
+    {
+        let mut builder = CustomSectionsBuilder::default();
+        for section in self.iter().filter(|section| f(section)) {
+            builder.push(section.name(), section.data());
+        }
+        builder.finish()
+    }
 }
 
 /// A builder for [`CustomSections`].
@@ -36,7 +48,7 @@ impl CustomSectionsBuilder {
 }
 
 /// Internal representation of [`CustomSections`].
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct CustomSectionsInner {
     /// The name and data lengths of each Wasm custom section.
     items: Vec<CustomSectionInner>,
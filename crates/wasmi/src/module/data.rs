@@ -6,13 +6,13 @@ use core::slice;
 /// A Wasm [`Module`] data segment.
 ///
 /// [`Module`]: [`super::Module`]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DataSegment {
     inner: DataSegmentInner,
 }
 
 /// The inner structure of a [`DataSegment`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum DataSegmentInner {
     /// An active data segment that is initialized upon Wasm module instantiation.
     Active(ActiveDataSegment),
@@ -24,7 +24,7 @@ pub enum DataSegmentInner {
 }
 
 /// An active data segment that is initialized upon Wasm module instantiation.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ActiveDataSegment {
     /// The linear memory that is to be initialized with this active segment.
     memory_index: MemoryIdx,
@@ -52,6 +52,14 @@ impl ActiveDataSegment {
 }
 
 /// The bytes of the passive data segment.
+///
+/// # Note
+///
+/// The bytes are reference counted and shared by the [`Module`] and all of its instances.
+/// Therefore instantiation does not copy passive data segments and `memory.init`
+/// copies directly from the bytes owned by the [`Module`].
+///
+/// [`Module`]: crate::Module
 #[derive(Debug, Clone)]
 pub struct PassiveDataSegmentBytes {
     bytes: Arc<[u8]>,
@@ -69,6 +77,27 @@ fn size_of_data_segment() {
     assert!(core::mem::size_of::<DataSegmentInner>() <= 32);
 }
 
+#[test]
+fn passive_data_segment_bytes_are_shared() {
+    let mut builder = DataSegments::build();
+    builder.reserve(1).unwrap();
+    builder
+        .push_data_segment(wasmparser::Data {
+            kind: wasmparser::DataKind::Passive,
+            data: &[1, 2, 3],
+            range: 0..0,
+        })
+        .unwrap();
+    let segments = builder.finish();
+    let segment = &segments.segments[0];
+    let Some(InitDataSegment::Passive { bytes }) = (&segments).into_iter().next() else {
+        panic!("expected a passive data segment")
+    };
+    let shared = segment.passive_data_segment_bytes().unwrap();
+    assert_eq!(bytes.as_ref(), &[1, 2, 3]);
+    assert!(Arc::ptr_eq(&bytes.bytes, &shared.bytes));
+}
+
 impl DataSegment {
     /// Returns the bytes of the [`DataSegment`] if passive, otherwise returns `None`.
     pub fn passive_data_segment_bytes(&self) -> Option<PassiveDataSegmentBytes> {
@@ -80,7 +109,7 @@ impl DataSegment {
 }
 
 /// Stores all data segments and their associated data.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DataSegments {
     /// All data segments.
     segments: Box<[DataSegment]>,
@@ -103,6 +132,58 @@ impl DataSegments {
             bytes: Vec::new(),
         }
     }
+
+    /// Returns new [`DataSegments`] where the bytes of each data segment are replaced via `f`.
+    ///
+    /// The closure `f` receives the index and the bytes of each data segment and returns
+    /// the replacement bytes or `None` if the data segment shall be kept as is.
+    ///
+    /// # Panics
+    ///
+    /// If a replaced active data segment has too many bytes.
+    pub fn map_bytes<F>(&self, mut f: F) -> Self
+    where
+        F: FnMut(u32, &[u8]) -> Option<Vec<u8>>,
+    {
+        let mut segments = Vec::with_capacity(self.segments.len());
+        let mut bytes = Vec::with_capacity(self.bytes.len());
+        for (index, segment) in self.into_iter().enumerate() {
+            let index = index as u32;
+            let inner = match segment {
+                InitDataSegment::Active {
+                    memory_index,
+                    offset,
+                    bytes: init,
+                } => {
+                    let replaced = f(index, init);
+                    let init = replaced.as_deref().unwrap_or(init);
+                    let len = u32::try_from(init.len()).unwrap_or_else(|_x| {
+                        panic!("data segment has too many bytes: {}", init.len())
+                    });
+                    bytes.extend_from_slice(init);
+                    DataSegmentInner::Active(ActiveDataSegment {
+                        memory_index,
+                        offset: offset.clone(),
+                        len,
+                    })
+                }
+                InitDataSegment::Passive { bytes: passive } => {
+                    let bytes = match f(index, passive.as_ref()) {
+                        Some(replaced) => PassiveDataSegmentBytes {
+                            bytes: replaced.into(),
+                        },
+                        None => passive,
+                    };
+                    DataSegmentInner::Passive { bytes }
+                }
+            };
+            segments.push(DataSegment { inner });
+        }
+        Self {
+            segments: segments.into(),
+            bytes,
+        }
+    }
 }
 
 /// Builds up a [`DataSegments`] instance.
@@ -116,12 +197,17 @@ pub struct DataSegmentsBuilder {
 
 impl DataSegmentsBuilder {
     /// Reserves space for at least `additional` new [`DataSegments`].
-    pub fn reserve(&mut self, count: usize) {
+    ///
+    /// # Errors
+    ///
+    /// If the system ran out of memory.
+    pub fn reserve(&mut self, count: usize) -> Result<(), Error> {
         assert!(
             self.segments.capacity() == 0,
             "must not reserve multiple times"
         );
-        self.segments.reserve(count);
+        self.segments.try_reserve(count)?;
+        Ok(())
     }
 
     /// Pushes another [`DataSegment`] to the [`DataSegmentsBuilder`].
@@ -149,6 +235,7 @@ impl DataSegmentsBuilder {
                 let len = u32::try_from(segment.data.len()).unwrap_or_else(|_x| {
                     panic!("data segment has too many bytes: {}", segment.data.len())
                 });
+                self.bytes.try_reserve(segment.data.len())?;
                 self.bytes.extend_from_slice(segment.data);
                 self.segments.push(DataSegment {
                     inner: DataSegmentInner::Active(ActiveDataSegment {
@@ -0,0 +1,79 @@
+use crate::Error;
+use alloc::{boxed::Box, vec::Vec};
+use wasmparser::{BinaryReader, Dylink0SectionReader, Dylink0Subsection};
+
+/// The name of the custom section that holds the dynamic linking information.
+pub(crate) const DYLINK_SECTION_NAME: &str = "dylink.0";
+
+/// The dynamic linking information of a Wasm shared object.
+///
+/// This is read from the `dylink.0` custom section as defined by the
+/// [dynamic linking tool conventions].
+///
+/// [dynamic linking tool conventions]: https://github.com/WebAssembly/tool-conventions/blob/main/DynamicLinking.md
+#[derive(Debug, Clone, Default)]
+pub struct DylinkInfo {
+    /// The number of bytes of linear memory reserved for the shared object.
+    memory_size: u32,
+    /// The required alignment of the reserved linear memory as power of 2.
+    memory_alignment_log2: u32,
+    /// The number of table elements reserved for the shared object.
+    table_size: u32,
+    /// The required alignment of the reserved table elements as power of 2.
+    table_alignment_log2: u32,
+    /// The names of the shared objects required by the shared object.
+    needed: Box<[Box<str>]>,
+}
+
+impl DylinkInfo {
+    /// Parses the [`DylinkInfo`] from the `data` of a `dylink.0` custom section.
+    ///
+    /// # Errors
+    ///
+    /// If `data` is not a valid `dylink.0` custom section payload.
+    pub(crate) fn parse(data: &[u8]) -> Result<Self, Error> {
+        let mut info = Self::default();
+        let mut needed = Vec::new();
+        for subsection in Dylink0SectionReader::new(BinaryReader::new(data, 0)) {
+            match subsection? {
+                Dylink0Subsection::MemInfo(mem_info) => {
+                    info.memory_size = mem_info.memory_size;
+                    info.memory_alignment_log2 = mem_info.memory_alignment;
+                    info.table_size = mem_info.table_size;
+                    info.table_alignment_log2 = mem_info.table_alignment;
+                }
+                Dylink0Subsection::Needed(names) => {
+                    needed.extend(names.into_iter().map(Box::from));
+                }
+                _ => {}
+            }
+        }
+        info.needed = needed.into();
+        Ok(info)
+    }
+
+    /// Returns the number of bytes of linear memory reserved for the shared object.
+    pub fn memory_size(&self) -> u32 {
+        self.memory_size
+    }
+
+    /// Returns the required alignment of the reserved linear memory as power of 2.
+    pub fn memory_alignment_log2(&self) -> u32 {
+        self.memory_alignment_log2
+    }
+
+    /// Returns the number of table elements reserved for the shared object.
+    pub fn table_size(&self) -> u32 {
+        self.table_size
+    }
+
+    /// Returns the required alignment of the reserved table elements as power of 2.
+    pub fn table_alignment_log2(&self) -> u32 {
+        self.table_alignment_log2
+    }
+
+    /// Returns the names of the shared objects required by the shared object.
+    pub fn needed(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.needed.iter().map(|name| &**name)
+    }
+}
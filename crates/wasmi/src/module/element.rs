@@ -1,6 +1,6 @@
 use super::{ConstExpr, TableIdx};
 use crate::core::ValType;
-use alloc::boxed::Box;
+use alloc::{boxed::Box, sync::Arc};
 
 /// A table element segment within a [`Module`].
 ///
@@ -12,7 +12,11 @@ pub struct ElementSegment {
     /// The type of elements of the [`ElementSegment`].
     ty: ValType,
     /// The items of the [`ElementSegment`].
-    items: Box<[ConstExpr]>,
+    items: Arc<[ConstExpr]>,
+    /// Whether all items are `ref.func` or constant expressions.
+    ///
+    /// Items of such an [`ElementSegment`] can be resolved without evaluating globals.
+    lazy_init: bool,
 }
 
 /// The kind of a Wasm [`ElementSegment`].
@@ -97,7 +101,15 @@ impl From<wasmparser::Element<'_>> for ElementSegment {
                 (items, ty)
             }
         };
-        Self { kind, ty, items }
+        let lazy_init = items
+            .iter()
+            .all(|item| item.funcref().is_some() || item.eval_const().is_some());
+        Self {
+            kind,
+            ty,
+            items: items.into(),
+            lazy_init,
+        }
     }
 }
 
@@ -116,4 +128,21 @@ impl ElementSegment {
     pub fn items(&self) -> &[ConstExpr] {
         &self.items[..]
     }
+
+    /// Returns the element items of the [`ElementSegment`].
+    ///
+    /// # Note
+    ///
+    /// The slice is stored in a `Arc` so that this operation is very cheap.
+    pub fn items_cloned(&self) -> Arc<[ConstExpr]> {
+        self.items.clone()
+    }
+
+    /// Returns `true` if the items of the [`ElementSegment`] support lazy initialization.
+    ///
+    /// This is the case if all items are `ref.func` or constant expressions
+    /// since those can be resolved without access to global variables.
+    pub fn supports_lazy_init(&self) -> bool {
+        self.lazy_init
+    }
 }
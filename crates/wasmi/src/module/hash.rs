@@ -0,0 +1,138 @@
+use core::{fmt, fmt::Display};
+
+/// A stable 128-bit content hash of a [`Module`].
+///
+/// Computed via [`Module::hash`].
+///
+/// # Note
+///
+/// - The hash is stable across platforms and program executions.
+/// - The hash is not cryptographically secure and thus must not be used
+///   to identify untrusted Wasm modules in adversarial settings.
+///
+/// [`Module`]: crate::Module
+/// [`Module::hash`]: crate::Module::hash
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ModuleHash([u8; 16]);
+
+impl ModuleHash {
+    /// Returns the little-endian bytes of the [`ModuleHash`].
+    pub fn to_bytes(self) -> [u8; 16] {
+        self.0
+    }
+
+    /// Creates a [`ModuleHash`] from its little-endian bytes.
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl Display for ModuleHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0.iter().rev() {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A stable, platform independent and non-cryptographic hasher.
+///
+/// # Note
+///
+/// This is a word-wise variant of the 128-bit FNV-1a hash function.
+/// Changing its algorithm invalidates all previously computed hashes.
+#[derive(Debug, Copy, Clone)]
+pub struct StableHasher {
+    state: u128,
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StableHasher {
+    /// The FNV-1a 128-bit offset basis.
+    const OFFSET_BASIS: u128 = 0x6C62_272E_07BB_0142_62B8_2175_6295_C58D;
+    /// The FNV-1a 128-bit prime.
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013B;
+
+    /// Creates a new [`StableHasher`].
+    pub fn new() -> Self {
+        Self {
+            state: Self::OFFSET_BASIS,
+        }
+    }
+
+    /// Hashes a single 64-bit `word`.
+    #[inline]
+    pub fn write_u64(&mut self, word: u64) {
+        self.state ^= u128::from(word);
+        self.state = self.state.wrapping_mul(Self::PRIME);
+    }
+
+    /// Hashes a single 32-bit `value`.
+    #[inline]
+    pub fn write_u32(&mut self, value: u32) {
+        self.write_u64(u64::from(value));
+    }
+
+    /// Hashes a single `bool` value.
+    #[inline]
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_u64(u64::from(value));
+    }
+
+    /// Hashes an optional 32-bit `value`.
+    #[inline]
+    pub fn write_opt_u32(&mut self, value: Option<u32>) {
+        match value {
+            Some(value) => self.write_u64(1 << 32 | u64::from(value)),
+            None => self.write_u64(0),
+        }
+    }
+
+    /// Hashes the `bytes` including their length.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_u64(bytes.len() as u64);
+        let mut words = bytes.chunks_exact(8);
+        for word in &mut words {
+            let word: [u8; 8] = word.try_into().unwrap_or_else(|_| unreachable!());
+            self.write_u64(u64::from_le_bytes(word));
+        }
+        for byte in words.remainder() {
+            self.write_u64(u64::from(*byte));
+        }
+    }
+
+    /// Returns the [`ModuleHash`] of all the values hashed so far.
+    pub fn finish(&self) -> ModuleHash {
+        ModuleHash(self.state.to_le_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn stable_hash_works() {
+        let mut hasher = StableHasher::new();
+        assert_eq!(
+            hasher.finish().to_bytes(),
+            StableHasher::OFFSET_BASIS.to_le_bytes()
+        );
+        hasher.write_bytes(b"hello, world!");
+        let hash = hasher.finish();
+        // Must never change since hashes are expected to be stable.
+        assert_eq!(hash.to_string(), "8c04f8c5f92f0a37486ed7f253e344a4");
+        // Different chunking must yield different hashes.
+        let mut hasher = StableHasher::new();
+        hasher.write_bytes(b"hello, ");
+        hasher.write_bytes(b"world!");
+        assert_ne!(hasher.finish(), hash);
+    }
+}
@@ -13,7 +13,7 @@ use crate::{
     FuncRef,
     Val,
 };
-use alloc::boxed::Box;
+use alloc::sync::Arc;
 use core::fmt;
 use smallvec::SmallVec;
 use wasmparser::AbstractHeapType;
@@ -51,7 +51,7 @@ impl EvalContext for EmptyEvalContext {
 }
 
 /// An input parameter to a [`ConstExpr`] operator.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Op {
     /// A constant value.
     Const(ConstOp),
@@ -72,7 +72,7 @@ pub enum Op {
 /// - `f32.const`
 /// - `f64.const`
 /// - `ref.null`
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ConstOp {
     /// The underlying precomputed untyped value.
     value: UntypedVal,
@@ -86,7 +86,7 @@ impl Eval for ConstOp {
 
 /// Represents a Wasm `global.get` operator.
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GlobalOp {
     /// The index of the global variable.
     global_index: u32,
@@ -100,7 +100,7 @@ impl Eval for GlobalOp {
 
 /// Represents a Wasm `func.ref` operator.
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FuncRefOp {
     /// The index of the function.
     function_index: u32,
@@ -122,10 +122,11 @@ impl Eval for FuncRefOp {
 /// - `i64.add`
 /// - `i64.sub`
 /// - `i64.mul`
+#[derive(Clone)]
 #[allow(clippy::type_complexity)]
 pub struct ExprOp {
     /// The underlying closure that implements the expression.
-    expr: Box<dyn Fn(&dyn EvalContext) -> Option<UntypedVal> + Send + Sync>,
+    expr: Arc<dyn Fn(&dyn EvalContext) -> Option<UntypedVal> + Send + Sync>,
 }
 
 impl fmt::Debug for ExprOp {
@@ -167,7 +168,7 @@ impl Op {
         T: Fn(&dyn EvalContext) -> Option<UntypedVal> + Send + Sync + 'static,
     {
         Self::Expr(ExprOp {
-            expr: Box::new(expr),
+            expr: Arc::new(expr),
         })
     }
 }
@@ -188,7 +189,7 @@ impl Eval for Op {
 /// These are used to determine the offsets of memory data
 /// and table element segments as well as the initial value
 /// of global variables.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ConstExpr {
     /// The root operator of the [`ConstExpr`].
     op: Op,
@@ -54,7 +54,14 @@ pub enum InstantiationError {
         /// The index of the found `start` function.
         index: u32,
     },
+    /// Caused when running the `start` function synchronously while it may call async host functions.
+    AsyncStartFn {
+        /// The index of the found `start` function.
+        index: u32,
+    },
     TooManyInstances,
+    /// Caused when instantiating a Wasm module as shared object without `dylink.0` custom section.
+    MissingDylinkInfo,
 }
 
 #[cfg(feature = "std")]
@@ -88,10 +95,19 @@ impl Display for InstantiationError {
             Self::FoundStartFn { index } => {
                 write!(f, "found an unexpected start function with index {index}")
             }
+            Self::AsyncStartFn { index } => {
+                write!(
+                    f,
+                    "the start function with index {index} may call async host functions and must be run via `InstancePre::start_async`"
+                )
+            }
             Self::Table(error) => Display::fmt(error, f),
             Self::Memory(error) => Display::fmt(error, f),
             Self::Global(error) => Display::fmt(error, f),
-            Self::TooManyInstances => write!(f, "too many instances")
+            Self::TooManyInstances => write!(f, "too many instances"),
+            Self::MissingDylinkInfo => {
+                write!(f, "missing `dylink.0` custom section for dynamic linking")
+            }
         }
     }
 }
@@ -1,9 +1,11 @@
 mod error;
 mod pre;
+mod preserved;
 
 #[cfg(test)]
 mod tests;
 
+pub(crate) use self::preserved::Preserved;
 pub use self::{error::InstantiationError, pre::InstancePre};
 use super::{element::ElementSegmentKind, export, ConstExpr, InitDataSegment, Module};
 use crate::{
@@ -17,15 +19,18 @@ use crate::{
     Error,
     Extern,
     ExternType,
+    Func,
     FuncRef,
     Global,
     Instance,
     InstanceEntity,
     InstanceEntityBuilder,
+    InstantiationHook,
     Memory,
     Table,
     Val,
 };
+use alloc::sync::Arc;
 
 impl Module {
     /// Instantiates a new [`Instance`] from the given compiled [`Module`].
@@ -46,9 +51,30 @@ impl Module {
     /// [`Linker`]: struct.Linker.html
     /// [`Func`]: [`crate::Func`]
     pub(crate) fn instantiate<I>(
+        &self,
+        context: impl AsContextMut,
+        externals: I,
+    ) -> Result<InstancePre, Error>
+    where
+        I: IntoIterator<Item = Extern, IntoIter: ExactSizeIterator>,
+    {
+        self.instantiate_preserving(context, externals, &Preserved::default())
+    }
+
+    /// Instantiates a new [`Instance`] from the given compiled [`Module`].
+    ///
+    /// Unlike [`Module::instantiate`] this uses the `preserved` linear memories and
+    /// global variables instead of creating new ones as if they were imported.
+    ///
+    /// # Errors
+    ///
+    /// - If the given `externals` do not satisfy the required imports.
+    /// - If the `preserved` definitions do not satisfy the types of their definitions.
+    pub(crate) fn instantiate_preserving<I>(
         &self,
         mut context: impl AsContextMut,
         externals: I,
+        preserved: &Preserved,
     ) -> Result<InstancePre, Error>
     where
         I: IntoIterator<Item = Extern, IntoIter: ExactSizeIterator>,
@@ -61,10 +87,20 @@ impl Module {
         let mut builder = InstanceEntity::build(self);
 
         self.extract_imports(&context, &mut builder, externals)?;
+        context.as_context_mut().store.invoke_instantiation_hook(
+            InstantiationHook::ImportsResolved {
+                len: self.imports().len(),
+            },
+        )?;
         self.extract_functions(&mut context, &mut builder, handle);
         self.extract_tables(&mut context, &mut builder)?;
-        self.extract_memories(&mut context, &mut builder)?;
-        self.extract_globals(&mut context, &mut builder);
+        self.extract_memories(&mut context, &mut builder, preserved)?;
+        context.as_context_mut().store.invoke_instantiation_hook(
+            InstantiationHook::MemoriesAllocated {
+                len: self.internal_memories().len(),
+            },
+        )?;
+        self.extract_globals(&mut context, &mut builder, preserved)?;
         self.extract_exports(&mut builder);
         self.extract_start_fn(&mut builder);
 
@@ -193,19 +229,31 @@ impl Module {
     /// Extracts the Wasm linear memories from the module and stores them into the [`Store`].
     ///
     /// This also stores [`Memory`] references into the [`Instance`] under construction.
+    /// Linear memories in `preserved` are used instead of creating new ones.
     ///
     /// [`Store`]: struct.Store.html
     fn extract_memories(
         &self,
         mut context: impl AsContextMut,
         builder: &mut InstanceEntityBuilder,
+        preserved: &Preserved,
     ) -> Result<(), Error> {
         context
             .as_context_mut()
             .store
-            .check_new_memories_limit(self.len_memories())?;
-        for memory_type in self.internal_memories().copied() {
-            let memory = Memory::new(context.as_context_mut(), memory_type)?;
+            .check_new_memories_limit(self.len_memories() - preserved.len_memories())?;
+        let len_imported = self.module_header().imports.len_memories as u32;
+        for (index, memory_type) in (len_imported..).zip(self.internal_memories().copied()) {
+            let memory = match preserved.get_memory(index) {
+                Some(memory) => {
+                    let preserved_type = memory.dynamic_ty(&context);
+                    preserved_type
+                        .is_subtype_or_err(&memory_type)
+                        .map_err(InstantiationError::from)?;
+                    memory
+                }
+                None => Memory::new(context.as_context_mut(), memory_type)?,
+            };
             builder.push_memory(memory);
         }
         Ok(())
@@ -214,10 +262,22 @@ impl Module {
     /// Extracts the Wasm global variables from the module and stores them into the [`Store`].
     ///
     /// This also stores [`Global`] references into the [`Instance`] under construction.
+    /// Global variables in `preserved` are used instead of creating new ones.
     ///
     /// [`Store`]: struct.Store.html
-    fn extract_globals(&self, mut context: impl AsContextMut, builder: &mut InstanceEntityBuilder) {
-        for (global_type, global_init) in self.internal_globals() {
+    fn extract_globals(
+        &self,
+        mut context: impl AsContextMut,
+        builder: &mut InstanceEntityBuilder,
+        preserved: &Preserved,
+    ) -> Result<(), InstantiationError> {
+        let len_imported = self.module_header().imports.len_globals as u32;
+        for (index, (global_type, global_init)) in (len_imported..).zip(self.internal_globals()) {
+            if let Some(global) = preserved.get_global(index) {
+                global.ty(&context).satisfies(global_type)?;
+                builder.push_global(global);
+                continue;
+            }
             let value_type = global_type.content();
             let init_value = Self::eval_init_expr(context.as_context_mut(), builder, global_init);
             let mutability = global_type.mutability();
@@ -228,6 +288,7 @@ impl Module {
             );
             builder.push_global(global);
         }
+        Ok(())
     }
 
     /// Evaluates the given initializer expression using the partially constructed [`Instance`].
@@ -286,40 +347,72 @@ impl Module {
         mut context: impl AsContextMut,
         builder: &mut InstanceEntityBuilder,
     ) -> Result<(), Error> {
+        // The functions of the instance shared by all lazily initialized element segments.
+        let mut lazy_funcs: Option<Arc<[Func]>> = None;
         for segment in &self.module_header().element_segments[..] {
-            let get_global = |index| builder.get_global(index);
-            let get_func = |index| builder.get_func(index);
-            let element =
-                ElementSegment::new(context.as_context_mut(), segment, get_func, get_global);
-            if let ElementSegmentKind::Active(active) = segment.kind() {
-                let dst_index = u64::from(Self::eval_init_expr(
-                    context.as_context(),
-                    builder,
-                    active.offset(),
-                ));
-                let table = builder.get_table(active.table_index().into_u32());
-                // Note: This checks not only that the elements in the element segments properly
-                //       fit into the table at the given offset but also that the element segment
-                //       consists of at least 1 element member.
-                let len_table = table.size(&context);
-                let len_items = element.size(&context);
-                dst_index
-                    .checked_add(u64::from(len_items))
-                    .filter(|&max_index| max_index <= len_table)
-                    .ok_or(InstantiationError::ElementSegmentDoesNotFit {
-                        table,
-                        table_index: dst_index,
-                        len: len_items,
-                    })?;
-                let (table, elem) = context
+            let ElementSegmentKind::Active(active) = segment.kind() else {
+                let get_global = |index| builder.get_global(index);
+                let get_func = |index| builder.get_func(index);
+                let element =
+                    ElementSegment::new(context.as_context_mut(), segment, get_func, get_global);
+                builder.push_element_segment(element);
+                continue;
+            };
+            let dst_index = u64::from(Self::eval_init_expr(
+                context.as_context(),
+                builder,
+                active.offset(),
+            ));
+            let table = builder.get_table(active.table_index().into_u32());
+            // Note: This checks not only that the elements in the element segments properly
+            //       fit into the table at the given offset but also that the element segment
+            //       consists of at least 1 element member.
+            let len_table = table.size(&context);
+            let len_items = segment.items().len() as u32;
+            dst_index
+                .checked_add(u64::from(len_items))
+                .filter(|&max_index| max_index <= len_table)
+                .ok_or(InstantiationError::ElementSegmentDoesNotFit {
+                    table,
+                    table_index: dst_index,
+                    len: len_items,
+                })?;
+            context.as_context_mut().store.invoke_instantiation_hook(
+                InstantiationHook::ElementSegment {
+                    table_index: active.table_index().into_u32(),
+                    offset: dst_index,
+                    len: len_items,
+                },
+            )?;
+            if segment.supports_lazy_init() {
+                // The table elements are resolved from the element segment items upon
+                // their first access. The active element segment is dropped right away
+                // as commanded by the Wasm spec.
+                let funcs = lazy_funcs
+                    .get_or_insert_with(|| builder.funcs().into())
+                    .clone();
+                let element = ElementSegment::new_dropped(context.as_context_mut(), segment.ty());
+                context
                     .as_context_mut()
                     .store
                     .inner
-                    .resolve_table_and_element_mut(&table, &element);
-                table.init(elem, dst_index, 0, len_items, None)?;
-                // Now drop the active element segment as commanded by the Wasm spec.
-                elem.drop_items();
+                    .resolve_table_mut(&table)
+                    .init_lazy(dst_index, segment.items_cloned(), funcs)?;
+                builder.push_element_segment(element);
+                continue;
             }
+            let get_global = |index| builder.get_global(index);
+            let get_func = |index| builder.get_func(index);
+            let element =
+                ElementSegment::new(context.as_context_mut(), segment, get_func, get_global);
+            let (table, elem) = context
+                .as_context_mut()
+                .store
+                .inner
+                .resolve_table_and_element_mut(&table, &element);
+            table.init(elem, dst_index, 0, len_items, None)?;
+            // Now drop the active element segment as commanded by the Wasm spec.
+            elem.drop_items();
             builder.push_element_segment(element);
         }
         Ok(())
@@ -342,6 +435,19 @@ impl Module {
                         u32::from(Self::eval_init_expr(context.as_context(), builder, offset))
                             as usize;
                     let memory = builder.get_memory(memory_index.into_u32());
+                    context
+                        .as_context()
+                        .store
+                        .inner
+                        .resolve_memory(&memory)
+                        .check_writable(offset, bytes.len())?;
+                    context.as_context_mut().store.invoke_instantiation_hook(
+                        InstantiationHook::DataSegment {
+                            memory_index: memory_index.into_u32(),
+                            offset: offset as u64,
+                            len: bytes.len(),
+                        },
+                    )?;
                     memory.write(context.as_context_mut(), offset, bytes)?;
                     DataSegment::new_active(context.as_context_mut())
                 }
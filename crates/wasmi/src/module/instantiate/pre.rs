@@ -1,5 +1,13 @@
 use super::InstantiationError;
-use crate::{module::FuncIdx, AsContextMut, Error, Instance, InstanceEntityBuilder};
+use crate::{
+    module::FuncIdx,
+    AsContextMut,
+    Error,
+    Func,
+    Instance,
+    InstanceEntityBuilder,
+    InstantiationHook,
+};
 
 /// A partially instantiated [`Instance`] where the `start` function has not yet been executed.
 ///
@@ -12,12 +20,24 @@ use crate::{module::FuncIdx, AsContextMut, Error, Instance, InstanceEntityBuilde
 pub struct InstancePre {
     handle: Instance,
     builder: InstanceEntityBuilder,
+    /// Is `true` if any import resolved to an async host function.
+    has_async_imports: bool,
 }
 
 impl InstancePre {
     /// Creates a new [`InstancePre`].
     pub(super) fn new(handle: Instance, builder: InstanceEntityBuilder) -> Self {
-        Self { handle, builder }
+        Self {
+            handle,
+            builder,
+            has_async_imports: false,
+        }
+    }
+
+    /// Marks whether any import of the [`InstancePre`] resolved to an async host function.
+    pub(crate) fn with_async_imports(mut self, has_async_imports: bool) -> Self {
+        self.has_async_imports = has_async_imports;
+        self
     }
 
     /// Returns the index of the `start` function if any.
@@ -35,28 +55,90 @@ impl InstancePre {
     ///
     /// # Errors
     ///
-    /// If executing the `start` function traps.
+    /// - If executing the `start` function traps.
+    /// - If a `start` function exists and any import resolved to an async host function.
+    ///   Use [`InstancePre::start_async`] in this case.
+    /// - If the [`Store::instantiation_hook`] rejects running the `start` function.
     ///
     /// # Panics
     ///
     /// If the `start` function is invalid albeit successful validation.
+    ///
+    /// [`Store::instantiation_hook`]: crate::Store::instantiation_hook
     pub fn start(self, mut context: impl AsContextMut) -> Result<Instance, Error> {
+        if let Some(index) = self.start_fn() {
+            if self.has_async_imports {
+                return Err(Error::from(InstantiationError::AsyncStartFn { index }));
+            }
+        }
+        let (handle, opt_start_func) = self.initialize(&mut context)?;
+        if let Some(start_func) = opt_start_func {
+            start_func.call(context.as_context_mut(), &[], &mut [])?
+        }
+        Ok(handle)
+    }
+
+    /// Runs the `start` function of the [`Instance`] and returns its handle.
+    ///
+    /// Unlike [`InstancePre::start`] this awaits all async host functions called
+    /// by the `start` function via [`Func::call_async`].
+    ///
+    /// # Note
+    ///
+    /// This finishes the instantiation procedure.
+    ///
+    /// # Errors
+    ///
+    /// - If executing the `start` function traps.
+    /// - If the [`Store::instantiation_hook`] rejects running the `start` function.
+    ///
+    /// # Panics
+    ///
+    /// If the `start` function is invalid albeit successful validation.
+    ///
+    /// [`Store::instantiation_hook`]: crate::Store::instantiation_hook
+    pub async fn start_async(self, mut context: impl AsContextMut) -> Result<Instance, Error> {
+        let (handle, opt_start_func) = self.initialize(&mut context)?;
+        if let Some(start_func) = opt_start_func {
+            start_func
+                .call_async(context.as_context_mut(), &[], &mut [])
+                .await?
+        }
+        Ok(handle)
+    }
+
+    /// Initializes the [`Instance`] and returns its handle and its `start` function if any.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Store::instantiation_hook`] rejects running the `start` function.
+    ///
+    /// # Panics
+    ///
+    /// If the `start` function is invalid albeit successful validation.
+    ///
+    /// [`Store::instantiation_hook`]: crate::Store::instantiation_hook
+    fn initialize(self, mut context: impl AsContextMut) -> Result<(Instance, Option<Func>), Error> {
         let opt_start_index = self.start_fn();
         context
             .as_context_mut()
             .store
             .inner
             .initialize_instance(self.handle, self.builder.finish());
-        if let Some(start_index) = opt_start_index {
-            let start_func = self
-                .handle
+        let opt_start_func = opt_start_index.map(|start_index| {
+            self.handle
                 .get_func_by_index(&mut context, start_index)
                 .unwrap_or_else(|| {
                     panic!("encountered invalid start function after validation: {start_index}")
-                });
-            start_func.call(context.as_context_mut(), &[], &mut [])?
+                })
+        });
+        if let Some(func_index) = opt_start_index {
+            context
+                .as_context_mut()
+                .store
+                .invoke_instantiation_hook(InstantiationHook::StartFunction { func_index })?;
         }
-        Ok(self.handle)
+        Ok((self.handle, opt_start_func))
     }
 
     /// Finishes instantiation ensuring that no `start` function exists.
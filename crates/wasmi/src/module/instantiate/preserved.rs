@@ -0,0 +1,119 @@
+use super::InstantiationError;
+use crate::{
+    module::export::ExternIdx,
+    AsContextMut,
+    Error,
+    ExportType,
+    Extern,
+    Global,
+    Instance,
+    Memory,
+    Module,
+    StoreContextMut,
+};
+use alloc::collections::BTreeMap;
+
+/// Linear memories and global variables of a [`Module`] preserved from another [`Instance`].
+///
+/// # Note
+///
+/// Upon instantiation preserved definitions are used instead of creating new ones
+/// as if the [`Module`] imported them. This allows to preserve the state of an
+/// [`Instance`] across instantiations of different versions of its [`Module`].
+#[derive(Debug, Default)]
+pub(crate) struct Preserved {
+    /// The preserved linear memories by their memory index within the [`Module`].
+    memories: BTreeMap<u32, Memory>,
+    /// The preserved global variables by their global index within the [`Module`].
+    globals: BTreeMap<u32, Global>,
+}
+
+impl Preserved {
+    /// Returns the preserved [`Memory`] at `index` if any.
+    pub fn get_memory(&self, index: u32) -> Option<Memory> {
+        self.memories.get(&index).copied()
+    }
+
+    /// Returns the preserved [`Global`] at `index` if any.
+    pub fn get_global(&self, index: u32) -> Option<Global> {
+        self.globals.get(&index).copied()
+    }
+
+    /// Returns the number of preserved linear memories.
+    pub fn len_memories(&self) -> usize {
+        self.memories.len()
+    }
+}
+
+impl Module {
+    /// Returns the definitions of `self` that are preserved from the exports of `instance`.
+    ///
+    /// Calls `migrate` for every linear memory and global variable that is defined and
+    /// exported by `self` and for which `instance` has an export with the same name.
+    /// The definition is preserved if `migrate` returns an [`Extern`] for it.
+    ///
+    /// # Errors
+    ///
+    /// - If `migrate` returns an error.
+    /// - If `migrate` returns an [`Extern`] of a different kind than the exported definition.
+    pub(crate) fn preserve_exports<T>(
+        &self,
+        mut context: impl AsContextMut<Data = T>,
+        instance: &Instance,
+        mut migrate: impl FnMut(
+            StoreContextMut<T>,
+            &ExportType,
+            Extern,
+        ) -> Result<Option<Extern>, Error>,
+    ) -> Result<Preserved, Error> {
+        let header = self.module_header();
+        let mut preserved = Preserved::default();
+        for export in self.exports() {
+            let (index, is_preserved) = match header.exports[export.name()] {
+                ExternIdx::Memory(index) => {
+                    let index = index.into_u32();
+                    if (index as usize) < header.imports.len_memories {
+                        // Case: imported linear memories are resolved by the linker.
+                        continue;
+                    }
+                    (index, preserved.memories.contains_key(&index))
+                }
+                ExternIdx::Global(index) => {
+                    let index = index.into_u32();
+                    if (index as usize) < header.imports.len_globals {
+                        // Case: imported global variables are resolved by the linker.
+                        continue;
+                    }
+                    (index, preserved.globals.contains_key(&index))
+                }
+                ExternIdx::Func(_) | ExternIdx::Table(_) => continue,
+            };
+            if is_preserved {
+                // Case: the definition is exported under multiple names
+                //       and has already been preserved under another name.
+                continue;
+            }
+            let Some(old) = instance.get_export(&context, export.name()) else {
+                continue;
+            };
+            let Some(new) = migrate(context.as_context_mut(), &export, old)? else {
+                continue;
+            };
+            match new {
+                Extern::Memory(memory) if export.ty().memory().is_some() => {
+                    preserved.memories.insert(index, memory);
+                }
+                Extern::Global(global) if export.ty().global().is_some() => {
+                    preserved.globals.insert(index, global);
+                }
+                actual => {
+                    return Err(Error::from(InstantiationError::ImportsExternalsMismatch {
+                        expected: export.ty().clone(),
+                        actual,
+                    }))
+                }
+            }
+        }
+        Ok(preserved)
+    }
+}
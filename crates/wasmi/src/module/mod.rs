@@ -1,19 +1,24 @@
 mod builder;
 mod custom_section;
 mod data;
+mod dylink;
 mod element;
 mod export;
 mod global;
+mod hash;
 mod import;
 mod init_expr;
 mod instantiate;
 mod parser;
 mod read;
+mod transform;
 pub(crate) mod utils;
+mod validation;
 
 use self::{
     builder::ModuleBuilder,
     custom_section::{CustomSections, CustomSectionsBuilder},
+    dylink::DYLINK_SECTION_NAME,
     export::ExternIdx,
     global::Global,
     import::{ExternTypeIdx, Import},
@@ -21,33 +26,60 @@ use self::{
 };
 pub use self::{
     custom_section::{CustomSection, CustomSectionsIter},
+    dylink::DylinkInfo,
     export::{ExportType, FuncIdx, MemoryIdx, ModuleExportsIter, TableIdx},
     global::GlobalIdx,
+    hash::ModuleHash,
     import::{FuncTypeIdx, ImportName},
     instantiate::{InstancePre, InstantiationError},
     read::{Read, ReadError},
+    transform::ModuleTransform,
+    validation::ValidationError,
 };
 pub(crate) use self::{
     data::{DataSegment, DataSegments, InitDataSegment, PassiveDataSegmentBytes},
     element::{ElementSegment, ElementSegmentKind},
+    hash::StableHasher,
     init_expr::ConstExpr,
+    instantiate::Preserved,
     utils::WasmiValueType,
 };
 use crate::{
     collections::Map,
-    engine::{DedupFuncType, EngineFunc, EngineFuncSpan, EngineFuncSpanIter, EngineWeak},
+    engine::{
+        CoverageBuilder,
+        CoverageRecorder,
+        DedupFuncType,
+        EngineFunc,
+        EngineFuncSpan,
+        EngineFuncSpanIter,
+        EngineWeak,
+        SerializedCode,
+        SerializedCodeError,
+    },
+    CompilationMetrics,
+    Coverage,
     Engine,
     Error,
     ExternType,
+    FuncDisassembly,
     FuncType,
     GlobalType,
     MemoryType,
     TableType,
 };
-use alloc::{boxed::Box, sync::Arc};
+#[cfg(feature = "to-wat")]
+use alloc::string::{String, ToString};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
 use core::{iter, slice::Iter as SliceIter};
 use wasmparser::{FuncValidatorAllocations, Parser, ValidPayload, Validator};
 
+#[cfg(feature = "branch-profile")]
+use crate::{
+    engine::{BranchCounter, BranchProfileBuilder},
+    BranchProfile,
+};
+
 /// A parsed and validated WebAssembly module.
 #[derive(Debug, Clone)]
 pub struct Module {
@@ -61,6 +93,14 @@ struct ModuleInner {
     header: ModuleHeader,
     data_segments: DataSegments,
     custom_sections: CustomSections,
+    /// The hash of all non-custom sections of the [`Module`].
+    content_hash: ModuleHash,
+    /// The original Wasm binary of the [`Module`].
+    ///
+    /// For a [`Module`] derived via [`Module::transform`] this is the re-encoded Wasm binary
+    /// or `None` if re-encoding failed.
+    #[cfg(feature = "to-wat")]
+    wasm: Option<Arc<[u8]>>,
 }
 
 /// A parsed and validated WebAssembly module header.
@@ -301,6 +341,58 @@ impl Module {
         unsafe { parser.parse_streaming_unchecked(stream) }
     }
 
+    /// Creates a new Wasm [`Module`] executing the serialized Wasmi bytecode in `code`.
+    ///
+    /// # Note
+    ///
+    /// - The `code` must have been produced by [`Module::serialize_code`] for the same `wasm`.
+    /// - The serialized Wasmi bytecode is decoded into the [`Engine`] and thus `code` is not
+    ///   borrowed by the returned [`Module`].
+    /// - Only the Wasm module header is parsed and validated. The function bodies of `wasm`
+    ///   are neither validated nor translated again.
+    /// - Code coverage and branch profiles are not recorded for serialized functions.
+    ///
+    /// # Safety
+    ///
+    /// - The `code` must have been produced by [`Module::serialize_code`] of a [`Module`]
+    ///   created from `wasm` by an [`Engine`] with the same [`Config`] using the exact same
+    ///   build of Wasmi, including its crate features.
+    /// - Wasmi checks the `code` header, the [`Module::hash`] and the number of functions.
+    ///   Decoding rejects unknown opcodes, invalid instruction fields and out of bounds
+    ///   called functions.
+    /// - Wasmi does not check whether the decoded Wasmi bytecode is valid, e.g. whether its
+    ///   registers, branch offsets and function local constants are in bounds. The only
+    ///   integrity check of `code` is the [`Module::hash`] in its header which is a 128-bit
+    ///   FNV-1a hash and thus neither covers the Wasmi bytecode nor protects against tampering.
+    /// - Violating the above rules is undefined behavior.
+    ///
+    /// # Errors
+    ///
+    /// - If the Wasm module header is malformed or fails to validate.
+    /// - If the `code` is malformed or fails to decode.
+    /// - If the `code` does not belong to `wasm` or to the [`Config`] of the `engine`.
+    ///
+    /// [`Config`]: crate::Config
+    pub unsafe fn new_with_serialized_code(
+        engine: &Engine,
+        wasm: impl AsRef<[u8]>,
+        code: &[u8],
+    ) -> Result<Self, Error> {
+        let wasm = wasm.as_ref();
+        #[cfg(feature = "wat")]
+        let wasm = &wat::parse_bytes(wasm)?[..];
+        let code = SerializedCode::new(code)?;
+        let module = ModuleParser::new(engine)
+            .with_serialized_code()
+            .parse_buffered(wasm)?;
+        if module.hash().to_bytes() != code.hash() {
+            return Err(Error::from(SerializedCodeError::ModuleMismatch));
+        }
+        let funcs = module.internal_funcs().map(|(_, func)| func);
+        unsafe { engine.init_serialized_funcs(code, funcs)? };
+        Ok(module)
+    }
+
     /// Returns the [`Engine`] used during creation of the [`Module`].
     pub fn engine(&self) -> &Engine {
         &self.inner.engine
@@ -336,9 +428,15 @@ impl Module {
         for payload in Parser::new(0).parse_all(wasm) {
             let payload = payload?;
             if let ValidPayload::Func(func_to_validate, func_body) = validator.payload(&payload)? {
+                let func_index = func_to_validate.index;
                 func_to_validate
                     .into_validator(FuncValidatorAllocations::default())
-                    .validate(&func_body)?;
+                    .validate(&func_body)
+                    .map_err(|error| {
+                        Error::from(error)
+                            .with_func_index(func_index)
+                            .with_operator_from(wasm, 0)
+                    })?;
             }
         }
         Ok(())
@@ -497,6 +595,232 @@ impl Module {
     pub fn custom_sections(&self) -> CustomSectionsIter {
         self.inner.custom_sections.iter()
     }
+
+    /// Returns the dynamic linking information of the [`Module`] if any.
+    ///
+    /// Returns `None` if the [`Module`] has no `dylink.0` custom section,
+    /// i.e. if it is not a Wasm shared object.
+    ///
+    /// # Note
+    ///
+    /// This always returns `None` if [`Config::ignore_custom_sections`] is set to `true`.
+    ///
+    /// # Errors
+    ///
+    /// If the `dylink.0` custom section of the [`Module`] is malformed.
+    ///
+    /// [`Config::ignore_custom_sections`]: crate::Config::ignore_custom_sections
+    pub fn dylink_info(&self) -> Result<Option<DylinkInfo>, Error> {
+        self.custom_sections()
+            .find(|section| section.name() == DYLINK_SECTION_NAME)
+            .map(|section| DylinkInfo::parse(section.data()))
+            .transpose()
+    }
+
+    /// Returns a stable content hash of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// - The hash covers all Wasm sections as well as the custom sections retained by the [`Module`].
+    /// - The hash also covers the parts of the [`Config`] of the [`Engine`] that affect
+    ///   Wasm validation and translation as well as the Wasmi version.
+    /// - This is intended to be used as key for caches of compiled Wasm modules.
+    ///
+    /// [`Config`]: crate::Config
+    pub fn hash(&self) -> ModuleHash {
+        let mut hasher = StableHasher::new();
+        self.engine().config().hash_into(&mut hasher);
+        hasher.write_bytes(&self.inner.content_hash.to_bytes());
+        for section in self.custom_sections() {
+            hasher.write_bytes(section.name().as_bytes());
+            hasher.write_bytes(section.data());
+        }
+        hasher.finish()
+    }
+
+    /// Serializes the compiled Wasmi bytecode of all internal functions of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// - The serialized code can be loaded via [`Module::new_with_serialized_code`].
+    /// - Lazily compiled functions are compiled without charging fuel.
+    /// - The serialized code is only valid for the exact same build of Wasmi,
+    ///   including its crate features, and for the same [`Config`].
+    ///
+    /// # Errors
+    ///
+    /// If a function fails Wasm to Wasmi bytecode translation.
+    ///
+    /// [`Config`]: crate::Config
+    pub fn serialize_code(&self) -> Result<Vec<u8>, Error> {
+        let funcs = self.internal_funcs().map(|(_, func)| func);
+        self.engine().serialize_funcs(self.hash(), funcs)
+    }
+
+    /// Returns the stable hash of the compiled Wasmi bytecode of all internal functions.
+    ///
+    /// # Note
+    ///
+    /// - In contrast to [`Module::hash`] this hashes the output of the translation and thus
+    ///   allows to verify that different hosts compiled a Wasm module into the same bytecode.
+    /// - The hash does not depend on the [`CompilationMode`], the target platform or the
+    ///   Wasm modules previously compiled by the same [`Engine`].
+    /// - The hash is only guaranteed to be stable across Wasmi builds with the same crate
+    ///   features and for [`Config`]s with [`Config::reproducible_translation`] enabled.
+    /// - Lazily compiled functions are compiled without charging fuel.
+    ///
+    /// # Errors
+    ///
+    /// If a function fails Wasm to Wasmi bytecode translation.
+    ///
+    /// [`CompilationMode`]: crate::CompilationMode
+    /// [`Config`]: crate::Config
+    /// [`Config::reproducible_translation`]: crate::Config::reproducible_translation
+    pub fn code_hash(&self) -> Result<ModuleHash, Error> {
+        let funcs = self.internal_funcs().map(|(_, func)| func);
+        self.engine().code_hash(funcs)
+    }
+
+    /// Returns an iterator over the disassembled Wasmi bytecode of all internal functions.
+    ///
+    /// # Note
+    ///
+    /// - Imported functions are skipped since they have no Wasmi bytecode.
+    /// - Lazily compiled functions are compiled without charging fuel.
+    /// - This is intended for debugging translation and code size issues.
+    ///
+    /// # Errors
+    ///
+    /// Yields an error for a function that fails Wasm to Wasmi bytecode translation.
+    pub fn disassemble(&self) -> impl Iterator<Item = Result<FuncDisassembly, Error>> + '_ {
+        let header = self.module_header();
+        let len_imported = header.imports.len_funcs as u32;
+        let names = header
+            .exports
+            .iter()
+            .filter_map(|(name, idx)| match idx {
+                ExternIdx::Func(func_idx) => Some((func_idx.into_u32(), &**name)),
+                _ => None,
+            })
+            .collect::<BTreeMap<u32, &str>>();
+        self.internal_funcs()
+            .zip(len_imported..)
+            .map(move |((_, engine_func), func_idx)| {
+                let name = names.get(&func_idx).copied();
+                self.engine()
+                    .disassemble_func(FuncIdx::from(func_idx), name, engine_func)
+            })
+    }
+
+    /// Returns the [`CompilationMetrics`] of the internal functions of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// - Functions that have not yet been compiled are skipped and are not compiled by this.
+    ///   With [`CompilationMode::Lazy`] this reports only the functions that have been called.
+    /// - Translation times are only measured with the `std` crate feature enabled.
+    ///
+    /// [`CompilationMode::Lazy`]: crate::CompilationMode::Lazy
+    pub fn compilation_metrics(&self) -> CompilationMetrics {
+        let len_imported = self.module_header().imports.len_funcs as u32;
+        let len_funcs = self.internal_funcs().len() as u32;
+        let funcs = self
+            .internal_funcs()
+            .zip(len_imported..)
+            .filter_map(|((_, engine_func), func_idx)| {
+                self.engine()
+                    .func_compilation_metrics(FuncIdx::from(func_idx), engine_func)
+            })
+            .collect();
+        CompilationMetrics::new(len_imported, len_funcs, funcs)
+    }
+
+    /// Disassembles the Wasmi bytecode of the internal function at `func_idx` with `engine_func`.
+    ///
+    /// # Errors
+    ///
+    /// If the function fails Wasm to Wasmi bytecode translation.
+    pub(crate) fn disassemble_func(
+        &self,
+        func_idx: u32,
+        engine_func: EngineFunc,
+    ) -> Result<FuncDisassembly, Error> {
+        let name = self
+            .module_header()
+            .exports
+            .iter()
+            .find_map(|(name, idx)| match idx {
+                ExternIdx::Func(idx) if idx.into_u32() == func_idx => Some(&**name),
+                _ => None,
+            });
+        self.engine()
+            .disassemble_func(FuncIdx::from(func_idx), name, engine_func)
+    }
+
+    /// Returns the [`Coverage`] of the internal functions of the [`Module`] recorded by `recorder`.
+    ///
+    /// # Note
+    ///
+    /// Lazily compiled functions are compiled without charging fuel.
+    ///
+    /// # Errors
+    ///
+    /// If a function fails Wasm to Wasmi bytecode translation.
+    pub(crate) fn coverage(&self, recorder: &CoverageRecorder) -> Result<Coverage, Error> {
+        let len_imported = self.module_header().imports.len_funcs as u32;
+        let mut builder = CoverageBuilder::new(recorder, len_imported);
+        for ((_, engine_func), func_idx) in self.internal_funcs().zip(len_imported..) {
+            self.engine()
+                .cover_func(&mut builder, FuncIdx::from(func_idx), engine_func)?;
+        }
+        Ok(builder.finish())
+    }
+
+    /// Returns the [`BranchProfile`] of the internal functions of the [`Module`] recorded by `counter`.
+    ///
+    /// # Note
+    ///
+    /// Lazily compiled functions are compiled without charging fuel.
+    ///
+    /// # Errors
+    ///
+    /// If a function fails Wasm to Wasmi bytecode translation.
+    #[cfg(feature = "branch-profile")]
+    pub(crate) fn branch_profile(&self, counter: &BranchCounter) -> Result<BranchProfile, Error> {
+        let len_imported = self.module_header().imports.len_funcs as u32;
+        let mut builder = BranchProfileBuilder::new(counter, len_imported);
+        for ((_, engine_func), func_idx) in self.internal_funcs().zip(len_imported..) {
+            self.engine()
+                .profile_func(&mut builder, FuncIdx::from(func_idx), engine_func)?;
+        }
+        Ok(builder.finish())
+    }
+
+    /// Prints the Wasm [`Module`] in the WebAssembly text format (`.wat`).
+    ///
+    /// # Note
+    ///
+    /// - This prints the original Wasm binary that the [`Module`] has been created from.
+    /// - A [`Module`] derived via [`Module::transform`] prints its re-encoded Wasm binary
+    ///   which reflects its stripped custom sections and replaced data segments.
+    /// - Custom sections are printed even if [`Config::ignore_custom_sections`] is set to `true`
+    ///   unless custom sections have been stripped via [`Module::transform`].
+    ///
+    /// # Errors
+    ///
+    /// - If the transformed Wasm binary could not be re-encoded.
+    /// - If the Wasm binary cannot be printed.
+    ///
+    /// [`Config::ignore_custom_sections`]: crate::Config::ignore_custom_sections
+    #[cfg(feature = "to-wat")]
+    pub fn to_wat(&self) -> Result<String, Error> {
+        let Some(wasm) = &self.inner.wasm else {
+            return Err(Error::new(
+                "failed to re-encode the transformed Wasm module",
+            ));
+        };
+        wasmprinter::print_bytes(&wasm[..]).map_err(|error| Error::new(error.to_string()))
+    }
 }
 
 /// An iterator over the imports of a [`Module`].
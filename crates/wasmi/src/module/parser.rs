@@ -8,6 +8,7 @@ use super::{
     FuncIdx,
     ModuleBuilder,
     ModuleHeader,
+    StableHasher,
 };
 use crate::{
     engine::{EnforcedLimitsError, EngineFunc},
@@ -55,6 +56,10 @@ pub struct ModuleParser {
     engine_funcs: u32,
     /// Flag, `true` when `stream` is at the end.
     eof: bool,
+    /// The hasher for all non-custom sections of the Wasm module.
+    hasher: StableHasher,
+    /// Flag, `true` if function bodies are initialized from serialized code instead of translated.
+    serialized_code: bool,
 }
 
 impl ModuleParser {
@@ -67,6 +72,33 @@ impl ModuleParser {
             parser,
             engine_funcs: 0,
             eof: false,
+            hasher: StableHasher::new(),
+            serialized_code: false,
+        }
+    }
+
+    /// Makes the [`ModuleParser`] skip translation of all function bodies.
+    ///
+    /// # Note
+    ///
+    /// The caller is responsible to initialize the functions of the parsed
+    /// [`Module`] from serialized code afterwards.
+    pub fn with_serialized_code(mut self) -> Self {
+        self.serialized_code = true;
+        self
+    }
+
+    /// Hashes the `bytes` of a processed Wasm payload unless it is a custom section.
+    ///
+    /// # Note
+    ///
+    /// Custom sections are excluded since [`Module::hash`] hashes them separately.
+    /// This way transformations and [`Config::ignore_custom_sections`] are accounted for.
+    ///
+    /// [`Config::ignore_custom_sections`]: crate::Config::ignore_custom_sections
+    fn hash_payload(&mut self, is_custom: bool, bytes: &[u8]) {
+        if !is_custom {
+            self.hasher.write_bytes(bytes);
         }
     }
 
@@ -410,7 +442,7 @@ impl ModuleParser {
             // Also benchmarks show that validation of the data section can be very costly.
             validator.data_section(&section)?;
         }
-        builder.reserve_data_segments(section.count() as usize);
+        builder.reserve_data_segments(section.count() as usize)?;
         for segment in section {
             builder.push_data_segment(segment?)?;
         }
@@ -489,6 +521,14 @@ impl ModuleParser {
         header: &ModuleHeader,
     ) -> Result<(), Error> {
         let (func, engine_func) = self.next_func(header);
+        if self.serialized_code {
+            // Note: the function bodies have already been validated and translated
+            //       upon serialization, so only the code section entries are counted.
+            if let Some(validator) = &mut self.validator {
+                validator.code_section_entry(&func_body)?;
+            }
+            return Ok(());
+        }
         let module = header.clone();
         let offset = func_body.get_binary_reader().original_position();
         let func_to_validate = match &mut self.validator {
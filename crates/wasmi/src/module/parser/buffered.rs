@@ -57,9 +57,13 @@ impl ModuleParser {
     ///
     /// If the Wasm bytecode stream fails to validate.
     unsafe fn parse_buffered_impl(mut self, mut buffer: &[u8]) -> Result<Module, Error> {
+        #[cfg(feature = "to-wat")]
+        let wasm = buffer;
         let mut custom_sections = CustomSectionsBuilder::default();
         let header = Self::parse_buffered_header(&mut self, &mut buffer, &mut custom_sections)?;
         let builder = Self::parse_buffered_code(&mut self, &mut buffer, header, custom_sections)?;
+        #[cfg(feature = "to-wat")]
+        let builder = builder.with_wasm(wasm);
         let module = Self::parse_buffered_data(&mut self, &mut buffer, builder)?;
         Ok(module)
     }
@@ -104,6 +108,7 @@ impl ModuleParser {
         let mut header = ModuleHeaderBuilder::new(&self.engine);
         loop {
             let (consumed, payload) = self.next_payload(buffer)?;
+            let is_custom = matches!(payload, Payload::CustomSection(_));
             match payload {
                 Payload::Version {
                     num,
@@ -124,7 +129,8 @@ impl ModuleParser {
                 Payload::DataCountSection { count, range } => self.process_data_count(count, range),
                 Payload::CodeSectionStart { count, range, size } => {
                     self.process_code_start(count, range, size)?;
-                    Self::consume_buffer(consumed, buffer);
+                    let bytes = Self::consume_buffer(consumed, buffer);
+                    self.hash_payload(is_custom, bytes);
                     break;
                 }
                 Payload::DataSection(_) => break,
@@ -134,7 +140,8 @@ impl ModuleParser {
                 }
                 unexpected => self.process_invalid_payload(unexpected),
             }?;
-            Self::consume_buffer(consumed, buffer);
+            let bytes = Self::consume_buffer(consumed, buffer);
+            self.hash_payload(is_custom, bytes);
         }
         Ok(header.finish())
     }
@@ -162,7 +169,8 @@ impl ModuleParser {
                     //       to return the byte slice for the respective code section
                     //       entry payload. Please remove this work around as soon as
                     //       such an API becomes available.
-                    Self::consume_buffer(consumed, buffer);
+                    let payload_bytes = Self::consume_buffer(consumed, buffer);
+                    self.hash_payload(false, payload_bytes);
                     let bytes = func_body.as_bytes();
                     self.process_code_entry(func_body, bytes, &header)?;
                 }
@@ -188,6 +196,7 @@ impl ModuleParser {
     ) -> Result<Module, Error> {
         loop {
             let (consumed, payload) = self.next_payload(buffer)?;
+            let is_custom = matches!(payload, Payload::CustomSection(_));
             match payload {
                 Payload::DataSection(section) => {
                     self.process_data(section, &mut builder)?;
@@ -201,8 +210,9 @@ impl ModuleParser {
                 }
                 invalid => self.process_invalid_payload(invalid)?,
             }
-            Self::consume_buffer(consumed, buffer);
+            let bytes = Self::consume_buffer(consumed, buffer);
+            self.hash_payload(is_custom, bytes);
         }
-        Ok(builder.finish(&self.engine))
+        Ok(builder.finish(&self.engine, self.hasher.finish()))
     }
 }
@@ -14,12 +14,17 @@ use wasmparser::{Chunk, Payload, Validator};
 #[derive(Debug, Default, Clone)]
 struct ParseBuffer {
     buffer: Vec<u8>,
+    /// All bytes that have been consumed so far.
+    #[cfg(feature = "to-wat")]
+    consumed: Vec<u8>,
 }
 
 impl ParseBuffer {
     /// Drops the first `amount` bytes from the [`ParseBuffer`] as they have been consumed.
     #[inline]
     fn consume(buffer: &mut Self, amount: usize) {
+        #[cfg(feature = "to-wat")]
+        buffer.consumed.extend_from_slice(&buffer.buffer[..amount]);
         buffer.drain(..amount);
     }
 
@@ -157,6 +162,7 @@ impl ModuleParser {
                     }
                 }
                 Chunk::Parsed { consumed, payload } => {
+                    let is_custom = matches!(payload, Payload::CustomSection(_));
                     match payload {
                         Payload::Version {
                             num,
@@ -191,6 +197,7 @@ impl ModuleParser {
                         }
                         Payload::CodeSectionStart { count, range, size } => {
                             self.process_code_start(count, range, size)?;
+                            self.hash_payload(is_custom, &buffer[..consumed]);
                             ParseBuffer::consume(buffer, consumed);
                             break;
                         }
@@ -201,6 +208,7 @@ impl ModuleParser {
                         }
                         unexpected => self.process_invalid_payload(unexpected),
                     }?;
+                    self.hash_payload(is_custom, &buffer[..consumed]);
                     // Cut away the parts from the intermediate buffer that have already been parsed.
                     ParseBuffer::consume(buffer, consumed);
                 }
@@ -231,6 +239,7 @@ impl ModuleParser {
                     self.eof = ParseBuffer::pull_bytes(buffer, hint, stream)?;
                 }
                 Chunk::Parsed { consumed, payload } => {
+                    let is_custom = matches!(payload, Payload::CustomSection(_));
                     match payload {
                         Payload::CodeSectionEntry(func_body) => {
                             // Note: Unfortunately the `wasmparser` crate is missing an API
@@ -242,6 +251,7 @@ impl ModuleParser {
                         }
                         _ => break,
                     }
+                    self.hash_payload(is_custom, &buffer[..consumed]);
                     // Cut away the parts from the intermediate buffer that have already been parsed.
                     ParseBuffer::consume(buffer, consumed);
                 }
@@ -271,6 +281,7 @@ impl ModuleParser {
                     self.eof = ParseBuffer::pull_bytes(buffer, hint, stream)?;
                 }
                 Chunk::Parsed { consumed, payload } => {
+                    let is_custom = matches!(payload, Payload::CustomSection(_));
                     match payload {
                         Payload::DataSection(section) => {
                             self.process_data(section, &mut builder)?;
@@ -285,11 +296,14 @@ impl ModuleParser {
                         }
                         invalid => self.process_invalid_payload(invalid)?,
                     }
+                    self.hash_payload(is_custom, &buffer[..consumed]);
                     // Cut away the parts from the intermediate buffer that have already been parsed.
                     ParseBuffer::consume(buffer, consumed);
                 }
             }
         }
-        Ok(builder.finish(&self.engine))
+        #[cfg(feature = "to-wat")]
+        let builder = builder.with_wasm(&buffer.consumed);
+        Ok(builder.finish(&self.engine, self.hasher.finish()))
     }
 }
@@ -0,0 +1,311 @@
+use super::{CustomSection, CustomSections, DataSegments, Module, ModuleInner, StableHasher};
+use alloc::{sync::Arc, vec::Vec};
+
+impl Module {
+    /// Returns a [`ModuleTransform`] to derive a new [`Module`] from `self`.
+    ///
+    /// # Note
+    ///
+    /// The derived [`Module`] shares its compiled functions with `self`.
+    /// Thus no Wasm parsing, validation or translation is required.
+    pub fn transform(&self) -> ModuleTransform<'_> {
+        ModuleTransform {
+            module: self,
+            custom_sections: None,
+            data_segments: None,
+            content_hash: None,
+        }
+    }
+}
+
+/// Derives a new [`Module`] from an existing [`Module`].
+///
+/// Created via [`Module::transform`].
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{Engine, Module};
+/// # fn main() -> Result<(), wasmi::Error> {
+/// # let engine = Engine::default();
+/// # let module = Module::new(&engine, "(module)")?;
+/// // Strip debug information and replace the first data segment.
+/// let stripped = module
+///     .transform()
+///     .strip_custom_sections(|section| section.name().starts_with(".debug"))
+///     .replace_data_segments(|index, _bytes| (index == 0).then(|| vec![0x00; 4]))
+///     .finish();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ModuleTransform<'a> {
+    /// The [`Module`] that is transformed.
+    module: &'a Module,
+    /// The transformed custom sections if any.
+    custom_sections: Option<CustomSections>,
+    /// The transformed data segments if any.
+    data_segments: Option<DataSegments>,
+    /// The hasher for the content hash of the transformed [`Module`] if any.
+    content_hash: Option<StableHasher>,
+}
+
+impl ModuleTransform<'_> {
+    /// Strips all custom sections for which `f` returns `true`.
+    pub fn strip_custom_sections<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&CustomSection) -> bool,
+    {
+        let custom_sections = self
+            .custom_sections
+            .as_ref()
+            .unwrap_or(&self.module.inner.custom_sections)
+            .filter(|section| !f(section));
+        self.custom_sections = Some(custom_sections);
+        self
+    }
+
+    /// Strips all custom sections.
+    pub fn strip_all_custom_sections(self) -> Self {
+        self.strip_custom_sections(|_| true)
+    }
+
+    /// Replaces the bytes of data segments via `f`.
+    ///
+    /// The closure `f` receives the index and the bytes of each data segment and returns
+    /// the replacement bytes or `None` if the data segment shall be kept as is.
+    ///
+    /// # Note
+    ///
+    /// Replaced active data segments are still initialized at their original offsets.
+    /// If they no longer fit into their linear memory, instantiation will fail.
+    ///
+    /// # Panics
+    ///
+    /// If a replaced active data segment has more than `u32::MAX` bytes.
+    pub fn replace_data_segments<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(u32, &[u8]) -> Option<Vec<u8>>,
+    {
+        let content_hash = self.module.inner.content_hash;
+        let hasher = &mut self.content_hash;
+        let data_segments = self
+            .data_segments
+            .as_ref()
+            .unwrap_or(&self.module.inner.data_segments)
+            .map_bytes(|index, bytes| {
+                let replaced = f(index, bytes)?;
+                let hasher = hasher.get_or_insert_with(|| {
+                    let mut hasher = StableHasher::new();
+                    hasher.write_bytes(&content_hash.to_bytes());
+                    hasher
+                });
+                hasher.write_u32(index);
+                hasher.write_bytes(&replaced);
+                Some(replaced)
+            });
+        self.data_segments = Some(data_segments);
+        self
+    }
+
+    /// Finishes the transformation and returns the derived [`Module`].
+    pub fn finish(self) -> Module {
+        let inner = &self.module.inner;
+        #[cfg(feature = "to-wat")]
+        let wasm = match (&inner.wasm, &self.custom_sections, &self.data_segments) {
+            (Some(wasm), custom_sections, data_segments)
+                if custom_sections.is_some() || data_segments.is_some() =>
+            {
+                let data_segments = data_segments.as_ref().unwrap_or(&inner.data_segments);
+                encode_transformed(wasm, custom_sections.as_ref(), data_segments)
+                    .ok()
+                    .map(Arc::from)
+            }
+            (wasm, _, _) => wasm.clone(),
+        };
+        Module {
+            inner: Arc::new(ModuleInner {
+                engine: inner.engine.clone(),
+                header: inner.header.clone(),
+                data_segments: self
+                    .data_segments
+                    .unwrap_or_else(|| inner.data_segments.clone()),
+                custom_sections: self
+                    .custom_sections
+                    .unwrap_or_else(|| inner.custom_sections.clone()),
+                content_hash: self
+                    .content_hash
+                    .map_or(inner.content_hash, |hasher| hasher.finish()),
+                #[cfg(feature = "to-wat")]
+                wasm,
+            }),
+        }
+    }
+}
+
+/// Re-encodes the original `wasm` binary of a transformed [`Module`].
+///
+/// - All non-custom sections except the data section are copied as is.
+/// - If `custom_sections` is `Some`, only the custom sections that are part of it are kept.
+/// - The data section is rebuilt with the bytes of the transformed `data_segments`.
+///
+/// # Errors
+///
+/// If `wasm` fails to parse.
+#[cfg(any(feature = "to-wat", test))]
+fn encode_transformed(
+    wasm: &[u8],
+    custom_sections: Option<&CustomSections>,
+    data_segments: &DataSegments,
+) -> Result<Vec<u8>, wasmparser::BinaryReaderError> {
+    use super::data::InitDataSegment;
+    use alloc::borrow::Cow;
+    use wasmparser::{DataKind, Parser, Payload};
+
+    let mut module = wasm_encoder::Module::new();
+    let mut kept_custom_sections = custom_sections.map(|sections| sections.iter().peekable());
+    for payload in Parser::new(0).parse_all(wasm) {
+        let payload = payload?;
+        match payload {
+            Payload::CustomSection(section) => {
+                if let Some(kept) = &mut kept_custom_sections {
+                    // The transformed custom sections retain the order of the original ones.
+                    let is_kept = kept.peek().is_some_and(|kept| {
+                        kept.name() == section.name() && kept.data() == section.data()
+                    });
+                    if !is_kept {
+                        continue;
+                    }
+                    kept.next();
+                }
+                module.section(&wasm_encoder::CustomSection {
+                    name: Cow::Borrowed(section.name()),
+                    data: Cow::Borrowed(section.data()),
+                });
+            }
+            Payload::DataSection(section) => {
+                let mut encoded = wasm_encoder::DataSection::new();
+                for (data, segment) in section.into_iter().zip(data_segments) {
+                    let data = data?;
+                    let bytes = match &segment {
+                        InitDataSegment::Active { bytes, .. } => bytes,
+                        InitDataSegment::Passive { bytes } => bytes.as_ref(),
+                    };
+                    match data.kind {
+                        DataKind::Active {
+                            memory_index,
+                            offset_expr,
+                        } => {
+                            let mut reader = offset_expr.get_binary_reader();
+                            let expr = reader.read_bytes(reader.bytes_remaining())?;
+                            // Note: `wasm_encoder::ConstExpr` appends the trailing `end` itself.
+                            let expr = &expr[..expr.len().saturating_sub(1)];
+                            let offset = wasm_encoder::ConstExpr::raw(expr.iter().copied());
+                            encoded.active(memory_index, &offset, bytes.iter().copied());
+                        }
+                        DataKind::Passive => {
+                            encoded.passive(bytes.iter().copied());
+                        }
+                    }
+                }
+                module.section(&encoded);
+            }
+            payload => {
+                if let Some((id, range)) = payload.as_section() {
+                    module.section(&wasm_encoder::RawSection {
+                        id,
+                        data: &wasm[range],
+                    });
+                }
+            }
+        }
+    }
+    Ok(module.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{module::data::InitDataSegment, Engine};
+    use alloc::string::ToString;
+
+    /// Returns the bytes of all data segments of `module`.
+    fn data_segment_bytes(module: &Module) -> Vec<Vec<u8>> {
+        module
+            .inner
+            .data_segments
+            .into_iter()
+            .map(|segment| match segment {
+                InitDataSegment::Active { bytes, .. } => bytes.to_vec(),
+                InitDataSegment::Passive { bytes } => bytes.as_ref().to_vec(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn encode_transformed_works() {
+        let wasm = wat::parse_str(
+            r#"
+            (module
+                (@custom ".debug_info" "debug")
+                (@custom "name-me" "keep")
+                (global $offset (import "env" "offset") i32)
+                (memory 1)
+                (data (i32.const 0) "hi")
+                (data "passive")
+                (data (global.get $offset) "there")
+            )
+            "#,
+        )
+        .unwrap();
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let transform = module
+            .transform()
+            .strip_custom_sections(|section| section.name().starts_with(".debug"))
+            .replace_data_segments(|index, _bytes| match index {
+                0 => Some(b"bye".to_vec()),
+                1 => Some(b"replaced".to_vec()),
+                _ => None,
+            });
+        let encoded = encode_transformed(
+            &wasm,
+            transform.custom_sections.as_ref(),
+            transform.data_segments.as_ref().unwrap(),
+        )
+        .unwrap();
+        let reencoded = Module::new(&engine, &encoded[..]).unwrap();
+        assert_eq!(
+            data_segment_bytes(&reencoded),
+            [&b"bye"[..], &b"replaced"[..], &b"there"[..]],
+        );
+        let names = |module: &Module| {
+            module
+                .inner
+                .custom_sections
+                .iter()
+                .map(|section| section.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&reencoded), ["name", "name-me"]);
+        assert_eq!(names(&reencoded), names(&transform.finish()));
+    }
+
+    #[test]
+    fn encode_transformed_keeps_unstripped_custom_sections() {
+        let wasm = wat::parse_str(
+            r#"
+            (module
+                (@custom ".debug_info" "debug")
+                (memory 1)
+                (data (i32.const 0) "hi")
+            )
+            "#,
+        )
+        .unwrap();
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let encoded = encode_transformed(&wasm, None, &module.inner.data_segments).unwrap();
+        assert_eq!(encoded, wasm);
+    }
+}
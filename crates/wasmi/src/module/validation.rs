@@ -0,0 +1,119 @@
+use core::{fmt, fmt::Display};
+use wasmparser::{BinaryReader, BinaryReaderError, Operator};
+
+/// An error that may occur upon parsing or validating a Wasm binary.
+///
+/// # Note
+///
+/// In contrast to a plain error message this provides structured information
+/// about the failure, such as its byte offset, the index of the function and
+/// the Wasm operator that failed to validate. This allows tools to highlight
+/// the exact location of the failure within user supplied Wasm modules.
+///
+/// The expected and found types of a type mismatch are not provided as structured
+/// information since the underlying Wasm validator only reports them as part of
+/// its [`message`](ValidationError::message).
+#[derive(Debug, Clone)]
+pub struct ValidationError {
+    /// The underlying error reported by the Wasm parser or validator.
+    error: BinaryReaderError,
+    /// The index of the function that failed to validate if any.
+    func_index: Option<u32>,
+    /// The name of the Wasm operator that failed to validate if any.
+    operator: Option<&'static str>,
+}
+
+impl From<BinaryReaderError> for ValidationError {
+    fn from(error: BinaryReaderError) -> Self {
+        Self {
+            error,
+            func_index: None,
+            operator: None,
+        }
+    }
+}
+
+impl ValidationError {
+    /// Returns the byte offset of the failure within the Wasm binary.
+    pub fn offset(&self) -> usize {
+        self.error.offset()
+    }
+
+    /// Returns the error message without any of the additional information.
+    pub fn message(&self) -> &str {
+        self.error.message()
+    }
+
+    /// Returns the index of the function that failed to validate if any.
+    ///
+    /// # Note
+    ///
+    /// The function index space includes imported functions.
+    pub fn func_index(&self) -> Option<u32> {
+        self.func_index
+    }
+
+    /// Returns the name of the Wasm operator that failed to validate if any.
+    ///
+    /// Operator names are given in `CamelCase`, e.g. `I32Add` or `LocalGet`.
+    pub fn operator(&self) -> Option<&str> {
+        self.operator
+    }
+
+    /// Returns a shared reference to the underlying [`BinaryReaderError`].
+    pub fn as_wasmparser(&self) -> &BinaryReaderError {
+        &self.error
+    }
+
+    /// Sets the index of the function that failed to validate if not already set.
+    pub(crate) fn set_func_index(&mut self, func_index: u32) {
+        self.func_index.get_or_insert(func_index);
+    }
+
+    /// Sets the failing Wasm operator by decoding it from `bytes` if not already set.
+    ///
+    /// The `bytes` are required to start at the `base` offset in the Wasm binary.
+    pub(crate) fn set_operator_from(&mut self, bytes: &[u8], base: usize) {
+        if self.operator.is_some() {
+            return;
+        }
+        let Some(start) = self.offset().checked_sub(base) else {
+            return;
+        };
+        let Some(bytes) = bytes.get(start..) else {
+            return;
+        };
+        let Ok(operator) = BinaryReader::new(bytes, self.offset()).read_operator() else {
+            return;
+        };
+        self.operator = operator_name(&operator);
+    }
+}
+
+/// Returns the name of the Wasm `operator` if known.
+fn operator_name(operator: &Operator) -> Option<&'static str> {
+    macro_rules! match_operator_name {
+        ($( @$proposal:ident $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident ($($ann:tt)*))*) => {
+            match operator {
+                $(
+                    Operator::$op $( { $($arg: _),* } )? => Some(stringify!($op)),
+                )*
+                _ => None,
+            }
+        };
+    }
+    wasmparser::for_each_operator!(match_operator_name)
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.error, f)?;
+        if let Some(func_index) = self.func_index {
+            write!(f, " in function {func_index}")?;
+        }
+        Ok(())
+    }
+}
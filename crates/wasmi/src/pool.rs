@@ -0,0 +1,270 @@
+use crate::{
+    AsContext,
+    AsContextMut,
+    Engine,
+    Error,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    StoreContext,
+    StoreContextMut,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+/// A pool of ready to use [`Instance`]s of a single [`Module`].
+///
+/// Every pooled [`Instance`] lives in its own [`Store`] so that [`Instance`]s handed out
+/// by the [`InstancePool`] never share any state with each other.
+///
+/// This is useful for embedders that handle many short-lived requests, e.g. FaaS platforms,
+/// since the [`Instance`]s are instantiated, including running their `start` function, ahead
+/// of time instead of on the latency-critical path of a request.
+///
+/// # Usage
+///
+/// - [`InstancePool::acquire`] hands out a ready [`PooledInstance`] for a request.
+/// - [`InstancePool::release`] recycles a [`PooledInstance`] after its request finished.
+/// - [`InstancePool::fill`] instantiates [`Instance`]s until the [`InstancePool`] is full.
+///
+/// # Note
+///
+/// The [`Linker`] of the [`InstancePool`] is used for many [`Store`]s and thus must only define
+/// host functions. Imports resolved to [`Store`] specific items, such as linear memories, fail
+/// to instantiate.
+pub struct InstancePool<T> {
+    /// The [`Linker`] used to instantiate the [`Module`].
+    linker: Linker<T>,
+    /// The [`Module`] of all pooled [`Instance`]s.
+    module: Module,
+    /// Creates the host state of the [`Store`] of every new [`PooledInstance`].
+    new_data: Box<dyn FnMut() -> T + Send>,
+    /// The pooled instances that are ready to be handed out.
+    ready: Vec<PooledInstance<T>>,
+    /// The maximum number of ready instances.
+    capacity: usize,
+    /// The metrics of the [`InstancePool`].
+    metrics: InstancePoolMetrics,
+}
+
+impl<T> fmt::Debug for InstancePool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstancePool")
+            .field("module", &self.module)
+            .field("ready", &self.ready.len())
+            .field("capacity", &self.capacity)
+            .field("metrics", &self.metrics)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: 'static> InstancePool<T> {
+    /// Creates a new [`InstancePool`] of `module` that keeps up to `capacity` [`Instance`]s ready.
+    ///
+    /// - All [`Instance`]s are instantiated using `linker`.
+    /// - The host state of the [`Store`] of every [`Instance`] is created by `new_data`.
+    ///
+    /// The [`InstancePool`] is filled with `capacity` [`Instance`]s before it is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the instantiation of `module` fails or traps.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`]s of `linker` and `module` are not the same.
+    pub fn new(
+        linker: Linker<T>,
+        module: &Module,
+        capacity: usize,
+        new_data: impl FnMut() -> T + Send + 'static,
+    ) -> Result<Self, Error> {
+        assert!(Engine::same(linker.engine(), module.engine()));
+        let mut pool = Self {
+            linker,
+            module: module.clone(),
+            new_data: Box::new(new_data),
+            ready: Vec::with_capacity(capacity),
+            capacity,
+            metrics: InstancePoolMetrics::default(),
+        };
+        pool.fill()?;
+        Ok(pool)
+    }
+
+    /// Returns the [`Module`] of the [`InstancePool`].
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
+    /// Returns the maximum number of ready [`Instance`]s of the [`InstancePool`].
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of [`Instance`]s that are ready to be handed out.
+    pub fn len_ready(&self) -> usize {
+        self.ready.len()
+    }
+
+    /// Returns the [`InstancePoolMetrics`] of the [`InstancePool`].
+    pub fn metrics(&self) -> InstancePoolMetrics {
+        self.metrics
+    }
+
+    /// Instantiates [`Instance`]s until the [`InstancePool`] is full.
+    ///
+    /// # Errors
+    ///
+    /// If the instantiation of the [`Module`] fails or traps.
+    pub fn fill(&mut self) -> Result<(), Error> {
+        while self.ready.len() < self.capacity {
+            let instance = self.instantiate()?;
+            self.ready.push(instance);
+        }
+        Ok(())
+    }
+
+    /// Hands out a [`PooledInstance`] of the [`InstancePool`].
+    ///
+    /// If the [`InstancePool`] is empty a new [`Instance`] is instantiated on the spot.
+    ///
+    /// # Errors
+    ///
+    /// If the [`InstancePool`] is empty and the instantiation of the [`Module`] fails or traps.
+    pub fn acquire(&mut self) -> Result<PooledInstance<T>, Error> {
+        if let Some(instance) = self.ready.pop() {
+            self.metrics.hits += 1;
+            return Ok(instance);
+        }
+        self.metrics.misses += 1;
+        self.instantiate()
+    }
+
+    /// Recycles the `instance` after it has been used.
+    ///
+    /// Since all state of `instance` may have been modified by its user its [`Store`] is
+    /// reset via [`Store::reset`] and the [`Module`] is instantiated into it again.
+    /// If the [`InstancePool`] is already full `instance` is dropped instead.
+    ///
+    /// # Errors
+    ///
+    /// If the instantiation of the [`Module`] fails or traps.
+    pub fn release(&mut self, instance: PooledInstance<T>) -> Result<(), Error> {
+        if self.ready.len() >= self.capacity {
+            return Ok(());
+        }
+        let mut store = instance.store;
+        store.reset((self.new_data)());
+        let instance = self.instantiate_into(store)?;
+        self.ready.push(instance);
+        self.metrics.recycled += 1;
+        Ok(())
+    }
+
+    /// Instantiates the [`Module`] in a new [`Store`] and runs its `start` function.
+    fn instantiate(&mut self) -> Result<PooledInstance<T>, Error> {
+        let store = Store::new(self.module.engine(), (self.new_data)());
+        self.instantiate_into(store)
+    }
+
+    /// Instantiates the [`Module`] in `store` and runs its `start` function.
+    fn instantiate_into(&self, mut store: Store<T>) -> Result<PooledInstance<T>, Error> {
+        let instance = self
+            .linker
+            .instantiate(&mut store, &self.module)?
+            .start(&mut store)?;
+        Ok(PooledInstance { store, instance })
+    }
+}
+
+/// An [`Instance`] handed out by an [`InstancePool`] together with its own [`Store`].
+///
+/// A [`PooledInstance`] can be used wherever a [`Store`] context is expected.
+#[derive(Debug)]
+pub struct PooledInstance<T> {
+    /// The [`Store`] owning the [`Instance`].
+    store: Store<T>,
+    /// The pooled [`Instance`].
+    instance: Instance,
+}
+
+impl<T> PooledInstance<T> {
+    /// Returns the pooled [`Instance`].
+    pub fn instance(&self) -> Instance {
+        self.instance
+    }
+
+    /// Returns a shared reference to the [`Store`] of the [`PooledInstance`].
+    pub fn store(&self) -> &Store<T> {
+        &self.store
+    }
+
+    /// Returns an exclusive reference to the [`Store`] of the [`PooledInstance`].
+    pub fn store_mut(&mut self) -> &mut Store<T> {
+        &mut self.store
+    }
+
+    /// Consumes the [`PooledInstance`] and returns its [`Store`] and [`Instance`].
+    pub fn into_parts(self) -> (Store<T>, Instance) {
+        (self.store, self.instance)
+    }
+}
+
+impl<T> AsContext for PooledInstance<T> {
+    type Data = T;
+
+    #[inline]
+    fn as_context(&self) -> StoreContext<'_, T> {
+        self.store.as_context()
+    }
+}
+
+impl<T> AsContextMut for PooledInstance<T> {
+    #[inline]
+    fn as_context_mut(&mut self) -> StoreContextMut<'_, T> {
+        self.store.as_context_mut()
+    }
+}
+
+/// Metrics of an [`InstancePool`].
+///
+/// Computed via [`InstancePool::metrics`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct InstancePoolMetrics {
+    /// The number of [`PooledInstance`]s handed out from the ready [`Instance`]s.
+    hits: u64,
+    /// The number of [`PooledInstance`]s instantiated on the spot due to an empty [`InstancePool`].
+    misses: u64,
+    /// The number of released [`PooledInstance`]s that have been reset and instantiated again.
+    recycled: u64,
+}
+
+impl InstancePoolMetrics {
+    /// Returns the number of [`PooledInstance`]s handed out from the ready [`Instance`]s.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the number of [`PooledInstance`]s instantiated on the spot due to an empty [`InstancePool`].
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Returns the number of released [`PooledInstance`]s that have been reset and instantiated again.
+    pub fn recycled(&self) -> u64 {
+        self.recycled
+    }
+
+    /// Returns the ratio of hits to all [`PooledInstance`]s handed out.
+    ///
+    /// Returns `1.0` if no [`PooledInstance`] has been handed out so far.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 1.0;
+        }
+        self.hits as f64 / total as f64
+    }
+}
@@ -1,13 +1,24 @@
 use crate::{
     collections::arena::{Arena, ArenaIndex, GuardedEntity},
-    core::{hint::unlikely, TrapCode},
-    engine::{DedupFuncType, FuelCosts},
+    core::{hint::unlikely, TrapCode, UntypedVal, ValType},
+    engine::{CoverageRecorder, DedupFuncType, ExecutionTrace, FuelCosts},
     externref::{ExternObject, ExternObjectEntity, ExternObjectIdx},
-    func::{FuncInOut, HostFuncEntity, Trampoline, TrampolineEntity, TrampolineIdx},
-    memory::{DataSegment, MemoryError},
+    func::{
+        FuncError,
+        FuncInOut,
+        HostFuncEntity,
+        HostFuture,
+        HostReturnCall,
+        PendingHostFuture,
+        Trampoline,
+        TrampolineEntity,
+        TrampolineIdx,
+    },
+    memory::{DataSegment, MemoryError, Protection},
     module::InstantiationError,
     table::TableError,
     Config,
+    Coverage,
     DataSegmentEntity,
     DataSegmentIdx,
     ElementSegment,
@@ -15,6 +26,8 @@ use crate::{
     ElementSegmentIdx,
     Engine,
     Error,
+    ExternRef,
+    FuelSchedule,
     Func,
     FuncEntity,
     FuncIdx,
@@ -28,19 +41,32 @@ use crate::{
     Memory,
     MemoryEntity,
     MemoryIdx,
+    Module,
     ResourceLimiter,
     Table,
     TableEntity,
     TableIdx,
+    TraceHash,
+    Val,
 };
-use alloc::{boxed::Box, sync::Arc};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc};
 use core::{
-    any::{type_name, TypeId},
+    any::{type_name, Any, TypeId},
     fmt::{self, Debug},
     mem,
+    ops::Range,
     sync::atomic::{AtomicU32, Ordering},
 };
 
+#[cfg(feature = "branch-profile")]
+use crate::{engine::BranchCounter, BranchProfile};
+
+/// The number of instructions executed between checks of the deadline of an execution.
+///
+/// Reading the clock is too expensive to be done at every safepoint.
+#[cfg(feature = "std")]
+const DEADLINE_CHECK_INTERVAL: u64 = 10_000;
+
 /// A unique store index.
 ///
 /// # Note
@@ -118,6 +144,60 @@ impl<T> Debug for CallHookWrapper<T> {
     }
 }
 
+/// A wrapper used to store hooks added with [`Store::global_hook`], containing a
+/// boxed `FnMut(&mut T, Global, Val) -> Result<(), Error>`.
+///
+/// This wrapper exists to provide a `Debug` impl so that `#[derive(Debug)]`
+/// works for [`Store`].
+#[allow(clippy::type_complexity)]
+struct GlobalHookWrapper<T>(Box<dyn FnMut(&mut T, Global, Val) -> Result<(), Error> + Send + Sync>);
+impl<T> Debug for GlobalHookWrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GlobalHook<{}>", type_name::<T>())
+    }
+}
+
+/// A wrapper used to store hooks added with [`Store::instantiation_hook`],
+/// containing a boxed `FnMut(&mut T, InstantiationHook) -> Result<(), Error>`.
+///
+/// This wrapper exists to provide a `Debug` impl so that `#[derive(Debug)]`
+/// works for [`Store`].
+#[allow(clippy::type_complexity)]
+struct InstantiationHookWrapper<T>(
+    Box<dyn FnMut(&mut T, InstantiationHook) -> Result<(), Error> + Send + Sync>,
+);
+impl<T> Debug for InstantiationHookWrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "InstantiationHook<{}>", type_name::<T>())
+    }
+}
+
+/// A wrapper used to store callbacks added with [`Store::instruction_callback`],
+/// containing a boxed `FnMut(&mut T) -> Result<(), Error>`.
+///
+/// This wrapper exists to provide a `Debug` impl so that `#[derive(Debug)]`
+/// works for [`Store`].
+#[allow(clippy::type_complexity)]
+struct InstrCallbackWrapper<T>(Box<dyn FnMut(&mut T) -> Result<(), Error> + Send + Sync>);
+impl<T> Debug for InstrCallbackWrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "InstrCallback<{}>", type_name::<T>())
+    }
+}
+
+/// A wrapper used to store hooks added with [`Store::fuel_consumed_hook`],
+/// containing a boxed `FnMut(&mut T, u64) -> Result<(), Error>`.
+///
+/// This wrapper exists to provide a `Debug` impl so that `#[derive(Debug)]`
+/// works for [`Store`].
+#[allow(clippy::type_complexity)]
+struct FuelHookWrapper<T>(Box<dyn FnMut(&mut T, u64) -> Result<(), Error> + Send + Sync>);
+impl<T> Debug for FuelHookWrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FuelHook<{}>", type_name::<T>())
+    }
+}
+
 /// A wrapper used to restore a [`PrunedStore`].
 ///
 /// This wrapper exists to provide a `Debug` impl so that `#[derive(Debug)]`
@@ -164,6 +244,20 @@ pub trait TypedStore {
 
     /// Returns an exclusive reference to [`StoreInner`] and a [`ResourceLimiterRef`].
     fn store_inner_and_resource_limiter_ref(&mut self) -> (&mut StoreInner, ResourceLimiterRef);
+
+    /// Executes the callback set by [`Store::global_hook`] for `global` if any has been set.
+    ///
+    /// # Errors
+    ///
+    /// If the global hook returned an error.
+    fn invoke_global_hook(&mut self, global: Global) -> Result<(), Error>;
+
+    /// Executes the callback set by [`Store::instruction_callback`] if any has been set.
+    ///
+    /// # Errors
+    ///
+    /// If the instruction callback returned an error.
+    fn invoke_instr_callback(&mut self) -> Result<(), Error>;
 }
 
 impl<T> TypedStore for Store<T> {
@@ -174,6 +268,7 @@ impl<T> TypedStore for Store<T> {
         params_results: FuncInOut,
         call_hooks: CallHooks,
     ) -> Result<(), Error> {
+        <Store<T>>::invoke_fuel_consumed_hook(self)?;
         if matches!(call_hooks, CallHooks::Call) {
             <Store<T>>::invoke_call_hook(self, CallHook::CallingHost)?;
         }
@@ -188,6 +283,14 @@ impl<T> TypedStore for Store<T> {
     fn store_inner_and_resource_limiter_ref(&mut self) -> (&mut StoreInner, ResourceLimiterRef) {
         <Store<T>>::store_inner_and_resource_limiter_ref(self)
     }
+
+    fn invoke_global_hook(&mut self, global: Global) -> Result<(), Error> {
+        <Store<T>>::invoke_global_hook(self, global)
+    }
+
+    fn invoke_instr_callback(&mut self) -> Result<(), Error> {
+        <Store<T>>::invoke_instr_callback(self)
+    }
 }
 
 /// The store that owns all data associated to Wasm modules.
@@ -281,6 +384,24 @@ impl PrunedStore {
         self.typed_store().store_inner_and_resource_limiter_ref()
     }
 
+    /// Executes the callback set by [`Store::global_hook`] for `global` if any has been set.
+    ///
+    /// # Errors
+    ///
+    /// If the global hook returned an error.
+    pub fn invoke_global_hook(&mut self, global: Global) -> Result<(), Error> {
+        self.typed_store().invoke_global_hook(global)
+    }
+
+    /// Executes the callback set by [`Store::instruction_callback`] if any has been set.
+    ///
+    /// # Errors
+    ///
+    /// If the instruction callback returned an error.
+    pub fn invoke_instr_callback(&mut self) -> Result<(), Error> {
+        self.typed_store().invoke_instr_callback()
+    }
+
     /// Returns the associated [`TypedStore`] of `self`.
     fn typed_store(&mut self) -> &mut dyn TypedStore {
         self.pruned.restore_pruned.clone().restore(self)
@@ -364,6 +485,14 @@ pub struct TypedStoreInner<T> {
     /// or a WebAssembly function calls a host function, or these functions
     /// return.
     call_hook: Option<CallHookWrapper<T>>,
+    /// User provided callback called when Wasm code writes to a watched global variable.
+    global_hook: Option<GlobalHookWrapper<T>>,
+    /// User provided callback called periodically while executing Wasm code.
+    instr_callback: Option<InstrCallbackWrapper<T>>,
+    /// User provided callback called with the fuel consumed by Wasm code.
+    fuel_hook: Option<FuelHookWrapper<T>>,
+    /// User provided callback called at the steps of module instantiations.
+    instantiation_hook: Option<InstantiationHookWrapper<T>>,
     /// User provided host data owned by the [`Store`].
     data: Box<T>,
 }
@@ -410,6 +539,110 @@ pub struct StoreInner {
     engine: Engine,
     /// The fuel of the [`Store`].
     fuel: Fuel,
+    /// The number of global variables watched via [`Global::watch`].
+    watched_globals: usize,
+    /// The number of linear memories with read-only bytes set via [`Memory::protect`].
+    protected_memories: usize,
+    /// The maximum number of instructions executed per call into Wasm if any.
+    instr_limit: Option<u64>,
+    /// The number of instructions executed between calls to the [`Store::instruction_callback`] if any.
+    instr_callback_interval: Option<u64>,
+    /// The point in time after which executions trap with [`TrapCode::DeadlineExceeded`] if any.
+    #[cfg(feature = "std")]
+    deadline: Option<std::time::Instant>,
+    /// The execution trace if trace hashing is enabled via [`Store::set_trace_hashing`].
+    trace: Option<ExecutionTrace>,
+    /// The recorded code coverage if enabled via [`Config::code_coverage`].
+    coverage: CoverageRecorder,
+    /// The recorded counts of the executed conditional branches.
+    #[cfg(feature = "branch-profile")]
+    branches: BranchCounter,
+    /// Host provided contexts owned by the [`Store`] keyed by their type.
+    contexts: StoreContexts,
+    /// The future of the last suspended async host function call if any.
+    pending_future: Option<PendingHostFuture>,
+    /// The Wasm function tail called by the last host function call if any.
+    return_call: Option<HostReturnCall>,
+    /// Is `true` while a stackless execution is active.
+    is_executing: bool,
+}
+
+/// A type-keyed map of host provided contexts owned by a [`Store`].
+///
+/// # Note
+///
+/// This allows independent host libraries to attach their own state
+/// to the same [`Store`] without competing for the user provided `T`.
+#[derive(Default)]
+pub struct StoreContexts {
+    /// The contexts keyed by the [`TypeId`] of their type.
+    contexts: BTreeMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Debug for StoreContexts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StoreContexts")
+            .field("len", &self.contexts.len())
+            .finish()
+    }
+}
+
+impl StoreContexts {
+    /// Removes all contexts.
+    pub fn clear(&mut self) {
+        self.contexts.clear();
+    }
+
+    /// Inserts the context `ctx` and returns the previous context of type `C` if any.
+    pub fn insert<C>(&mut self, ctx: C) -> Option<C>
+    where
+        C: Any + Send + Sync,
+    {
+        self.contexts
+            .insert(TypeId::of::<C>(), Box::new(ctx))
+            .map(Self::downcast)
+    }
+
+    /// Removes and returns the context of type `C` if any.
+    pub fn remove<C>(&mut self) -> Option<C>
+    where
+        C: Any + Send + Sync,
+    {
+        self.contexts.remove(&TypeId::of::<C>()).map(Self::downcast)
+    }
+
+    /// Returns a shared reference to the context of type `C` if any.
+    pub fn get<C>(&self) -> Option<&C>
+    where
+        C: Any + Send + Sync,
+    {
+        self.contexts.get(&TypeId::of::<C>())?.downcast_ref::<C>()
+    }
+
+    /// Returns an exclusive reference to the context of type `C` if any.
+    pub fn get_mut<C>(&mut self) -> Option<&mut C>
+    where
+        C: Any + Send + Sync,
+    {
+        self.contexts
+            .get_mut(&TypeId::of::<C>())?
+            .downcast_mut::<C>()
+    }
+
+    /// Downcasts the boxed `ctx` to `C`.
+    ///
+    /// # Panics
+    ///
+    /// If `ctx` is not of type `C`.
+    fn downcast<C>(ctx: Box<dyn Any + Send + Sync>) -> C
+    where
+        C: Any + Send + Sync,
+    {
+        match ctx.downcast::<C>() {
+            Ok(ctx) => *ctx,
+            Err(_) => panic!("invalid store context type: expected {}", type_name::<C>()),
+        }
+    }
 }
 
 #[test]
@@ -438,6 +671,50 @@ pub enum CallHook {
     ReturningFromHost,
 }
 
+/// Argument to the callback set by [`Store::instantiation_hook`] to indicate
+/// which step of a module instantiation is about to happen or has happened.
+///
+/// All indices refer to the index spaces of the instantiated [`Module`].
+///
+/// [`Module`]: crate::Module
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InstantiationHook {
+    /// Indicates that the imports of the module have been resolved.
+    ImportsResolved {
+        /// The number of resolved imports.
+        len: usize,
+    },
+    /// Indicates that the linear memories defined by the module have been allocated.
+    MemoriesAllocated {
+        /// The number of allocated linear memories.
+        len: usize,
+    },
+    /// Indicates that an active element segment is about to be applied to a table.
+    ElementSegment {
+        /// The index of the table the segment is applied to.
+        table_index: u32,
+        /// The offset of the first element within the table.
+        offset: u64,
+        /// The number of elements of the segment.
+        len: u32,
+    },
+    /// Indicates that an active data segment is about to be applied to a linear memory.
+    DataSegment {
+        /// The index of the linear memory the segment is applied to.
+        memory_index: u32,
+        /// The offset of the first byte within the linear memory.
+        offset: u64,
+        /// The number of bytes of the segment.
+        len: usize,
+    },
+    /// Indicates that the `start` function of the module is about to run.
+    StartFunction {
+        /// The index of the `start` function.
+        func_index: u32,
+    },
+}
+
 /// An error that may be encountered when operating on the [`Store`].
 #[derive(Debug, Clone)]
 pub enum FuelError {
@@ -445,6 +722,12 @@ pub enum FuelError {
     FuelMeteringDisabled,
     /// Raised when trying to consume more fuel than is available in the [`Store`].
     OutOfFuel,
+    /// Raised when calling a function with a [`FuelSchedule`] that is unavailable for it.
+    ///
+    /// Enable [`Config::per_call_fuel_schedules`] to call functions with any [`FuelSchedule`].
+    ///
+    /// [`Config::per_call_fuel_schedules`]: crate::Config::per_call_fuel_schedules
+    ScheduleUnavailable,
 }
 
 #[cfg(feature = "std")]
@@ -455,6 +738,7 @@ impl fmt::Display for FuelError {
         match self {
             Self::FuelMeteringDisabled => write!(f, "fuel metering is disabled"),
             Self::OutOfFuel => write!(f, "all fuel consumed"),
+            Self::ScheduleUnavailable => write!(f, "fuel schedule is unavailable for the call"),
         }
     }
 }
@@ -486,12 +770,19 @@ impl FuelError {
 pub struct Fuel {
     /// The remaining fuel.
     remaining: u64,
+    /// The remaining fuel upon the last report via [`Fuel::take_consumed`].
+    ///
+    /// This is adjusted along with `remaining` whenever fuel is set or refunded
+    /// so that only consumed fuel is reported.
+    reported: u64,
     /// This is `true` if fuel metering is enabled for the [`Engine`].
     enabled: bool,
-    /// The fuel costs provided by the [`Engine`]'s [`Config`].
+    /// The fuel costs provided by the [`Engine`]'s [`Config`] or the selected `schedule`.
     ///
     /// [`Config`]: crate::Config
     costs: FuelCosts,
+    /// The [`FuelSchedule`] selected for the current call if any.
+    schedule: Option<FuelSchedule>,
 }
 
 impl Fuel {
@@ -501,11 +792,34 @@ impl Fuel {
         let costs = *config.fuel_costs();
         Self {
             remaining: 0,
+            reported: 0,
             enabled,
             costs,
+            schedule: None,
         }
     }
 
+    /// Returns the [`FuelSchedule`] selected for the current call if any.
+    #[inline]
+    pub(crate) fn schedule(&self) -> Option<FuelSchedule> {
+        self.schedule
+    }
+
+    /// Selects the `schedule` for the current call and returns the previously selected one.
+    ///
+    /// Resets the fuel costs to the ones of `config` if `schedule` is `None`.
+    fn set_schedule(
+        &mut self,
+        schedule: Option<FuelSchedule>,
+        config: &Config,
+    ) -> Option<FuelSchedule> {
+        self.costs = match &schedule {
+            Some(schedule) => config.fuel_costs().with_schedule(schedule),
+            None => *config.fuel_costs(),
+        };
+        mem::replace(&mut self.schedule, schedule)
+    }
+
     /// Returns `true` if fuel metering is enabled.
     fn is_fuel_metering_enabled(&self) -> bool {
         self.enabled
@@ -532,10 +846,31 @@ impl Fuel {
     /// If fuel metering is disabled.
     pub fn set_fuel(&mut self, fuel: u64) -> Result<(), FuelError> {
         self.check_fuel_metering_enabled()?;
+        let consumed = self.reported.saturating_sub(self.remaining);
         self.remaining = fuel;
+        self.reported = fuel.saturating_add(consumed);
+        Ok(())
+    }
+
+    /// Adds `amount` to the remaining fuel.
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled.
+    pub fn refund_fuel(&mut self, amount: u64) -> Result<(), FuelError> {
+        self.check_fuel_metering_enabled()?;
+        self.remaining = self.remaining.saturating_add(amount);
+        self.reported = self.reported.saturating_add(amount);
         Ok(())
     }
 
+    /// Returns the fuel consumed since the last call to this method.
+    fn take_consumed(&mut self) -> u64 {
+        let consumed = self.reported.saturating_sub(self.remaining);
+        self.reported = self.remaining;
+        consumed
+    }
+
     /// Returns the remaining fuel.
     ///
     /// # Errors
@@ -601,7 +936,7 @@ impl Fuel {
     ) -> Result<(), TrapCode> {
         match self.consume_fuel(f) {
             Err(FuelError::OutOfFuel) => Err(TrapCode::OutOfFuel),
-            Err(FuelError::FuelMeteringDisabled) | Ok(_) => Ok(()),
+            Err(FuelError::FuelMeteringDisabled | FuelError::ScheduleUnavailable) | Ok(_) => Ok(()),
         }
     }
 }
@@ -622,9 +957,44 @@ impl StoreInner {
             elems: Arena::new(),
             extern_objects: Arena::new(),
             fuel,
+            watched_globals: 0,
+            protected_memories: 0,
+            instr_limit: None,
+            instr_callback_interval: None,
+            #[cfg(feature = "std")]
+            deadline: None,
+            trace: None,
+            coverage: CoverageRecorder::default(),
+            #[cfg(feature = "branch-profile")]
+            branches: BranchCounter::default(),
+            contexts: StoreContexts::default(),
+            pending_future: None,
+            return_call: None,
+            is_executing: false,
         }
     }
 
+    /// Drops all entities, host provided contexts and pending executions of the [`StoreInner`].
+    ///
+    /// The [`StoreInner`] is given a new [`StoreIdx`] so that entity handles
+    /// created before the reset can no longer be used with it.
+    pub fn reset(&mut self) {
+        self.store_idx = StoreIdx::new();
+        self.funcs.clear();
+        self.memories.clear();
+        self.tables.clear();
+        self.globals.clear();
+        self.instances.clear();
+        self.datas.clear();
+        self.elems.clear();
+        self.extern_objects.clear();
+        self.watched_globals = 0;
+        self.protected_memories = 0;
+        self.contexts.clear();
+        self.pending_future = None;
+        self.return_call = None;
+    }
+
     /// Returns the [`Engine`] that this store is associated with.
     pub fn engine(&self) -> &Engine {
         &self.engine
@@ -838,6 +1208,186 @@ impl StoreInner {
         self.resolve(table.as_inner(), &self.tables)
     }
 
+    /// Sets whether writes of Wasm code to the [`Global`] are observed.
+    ///
+    /// # Panics
+    ///
+    /// - If the [`Global`] does not originate from this [`Store`].
+    /// - If the [`Global`] cannot be resolved to its entity.
+    pub fn set_global_watched(&mut self, global: &Global, watched: bool) {
+        let entity = self.resolve_global_mut(global);
+        if entity.is_watched() == watched {
+            return;
+        }
+        entity.set_watched(watched);
+        match watched {
+            true => self.watched_globals += 1,
+            false => self.watched_globals -= 1,
+        }
+    }
+
+    /// Returns `true` if any global variable of the [`Store`] is watched.
+    #[inline]
+    pub fn has_watched_globals(&self) -> bool {
+        self.watched_globals != 0
+    }
+
+    /// Sets the `protection` of the bytes of the `memory` within `range`.
+    ///
+    /// # Errors
+    ///
+    /// If `range` is out of bounds of the `memory`.
+    ///
+    /// # Panics
+    ///
+    /// - If the [`Memory`] does not originate from this [`Store`].
+    /// - If the [`Memory`] cannot be resolved to its entity.
+    pub fn set_memory_protection(
+        &mut self,
+        memory: &Memory,
+        range: Range<u64>,
+        protection: Protection,
+    ) -> Result<(), MemoryError> {
+        let entity = self.resolve_memory_mut(memory);
+        let was_protected = entity.is_protected();
+        entity.protect(range, protection)?;
+        match (was_protected, entity.is_protected()) {
+            (false, true) => self.protected_memories += 1,
+            (true, false) => self.protected_memories -= 1,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if any linear memory of the [`Store`] has read-only bytes.
+    #[inline]
+    pub fn has_protected_memories(&self) -> bool {
+        self.protected_memories != 0
+    }
+
+    /// Returns the maximum number of instructions executed per call into Wasm if any.
+    #[inline]
+    pub fn instr_limit(&self) -> Option<u64> {
+        self.instr_limit
+    }
+
+    /// Returns the number of instructions executed between calls to the
+    /// [`Store::instruction_callback`] if any.
+    #[inline]
+    pub fn instr_callback_interval(&self) -> Option<u64> {
+        self.instr_callback_interval
+    }
+
+    /// Returns the number of instructions executed between checks of the deadline if any.
+    #[inline]
+    pub fn deadline_interval(&self) -> Option<u64> {
+        #[cfg(feature = "std")]
+        if self.deadline.is_some() {
+            return Some(DEADLINE_CHECK_INTERVAL);
+        }
+        None
+    }
+
+    /// Checks if the deadline of the executions of the [`StoreInner`] has passed.
+    ///
+    /// # Errors
+    ///
+    /// If the deadline has passed.
+    pub fn check_deadline(&self) -> Result<(), TrapCode> {
+        #[cfg(feature = "std")]
+        if let Some(deadline) = self.deadline {
+            if std::time::Instant::now() >= deadline {
+                return Err(TrapCode::DeadlineExceeded);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the point in time after which executions trap with [`TrapCode::DeadlineExceeded`] if any.
+    #[cfg(feature = "std")]
+    pub fn deadline(&self) -> Option<std::time::Instant> {
+        self.deadline
+    }
+
+    /// Sets the point in time after which executions trap with [`TrapCode::DeadlineExceeded`].
+    ///
+    /// Use `None` to remove the deadline.
+    #[cfg(feature = "std")]
+    pub fn set_deadline(&mut self, deadline: Option<std::time::Instant>) {
+        self.deadline = deadline;
+    }
+
+    /// Returns `true` if trace hashing is enabled via [`Store::set_trace_hashing`].
+    #[inline]
+    pub fn is_tracing(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    /// Returns an exclusive reference to the [`ExecutionTrace`] if trace hashing is enabled.
+    pub fn trace_mut(&mut self) -> Option<&mut ExecutionTrace> {
+        self.trace.as_mut()
+    }
+
+    /// Returns `true` if code coverage is enabled via [`Config::code_coverage`].
+    #[inline]
+    pub fn is_covering(&self) -> bool {
+        self.engine.config().get_code_coverage()
+    }
+
+    /// Returns an exclusive reference to the recorded code coverage.
+    pub fn coverage_mut(&mut self) -> &mut CoverageRecorder {
+        &mut self.coverage
+    }
+
+    /// Returns an exclusive reference to the recorded counts of the executed conditional branches.
+    #[cfg(feature = "branch-profile")]
+    pub fn branch_counter_mut(&mut self) -> &mut BranchCounter {
+        &mut self.branches
+    }
+
+    /// Stores the `future` of a suspended async host function call.
+    pub fn set_pending_future(&mut self, future: HostFuture) {
+        self.pending_future = Some(PendingHostFuture::new(future));
+    }
+
+    /// Takes the future of the last suspended async host function call if any.
+    pub fn take_pending_future(&mut self) -> Option<HostFuture> {
+        self.pending_future
+            .take()
+            .map(PendingHostFuture::into_future)
+    }
+
+    /// Stores the Wasm function tail called by a host function via [`Caller::return_call`].
+    ///
+    /// [`Caller::return_call`]: crate::Caller::return_call
+    pub fn set_return_call(&mut self, return_call: HostReturnCall) {
+        self.return_call = Some(return_call);
+    }
+
+    /// Takes the Wasm function tail called by the last host function call if any.
+    #[inline]
+    pub fn take_return_call(&mut self) -> Option<HostReturnCall> {
+        self.return_call.take()
+    }
+
+    /// Marks the start of a stackless execution.
+    ///
+    /// # Errors
+    ///
+    /// If a stackless execution is already active, i.e. upon nested calls from host functions.
+    pub fn enter_stackless(&mut self) -> Result<(), FuncError> {
+        if self.is_executing {
+            return Err(FuncError::NestedCall);
+        }
+        self.is_executing = true;
+        Ok(())
+    }
+
+    /// Marks the end of a stackless execution.
+    pub fn exit_stackless(&mut self) {
+        self.is_executing = false;
+    }
+
     /// Returns an exclusive reference to the [`TableEntity`] associated to the given [`Table`].
     ///
     /// # Panics
@@ -925,6 +1475,7 @@ impl StoreInner {
     /// - If the [`Table`] cannot be resolved to its entity.
     /// - If the [`ElementSegment`] does not originate from this [`Store`].
     /// - If the [`ElementSegment`] cannot be resolved to its entity.
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub(super) fn resolve_table_init_params(
         &mut self,
         table: &Table,
@@ -1016,6 +1567,7 @@ impl StoreInner {
     /// - If the [`Memory`] cannot be resolved to its entity.
     /// - If the [`DataSegment`] does not originate from this [`Store`].
     /// - If the [`DataSegment`] cannot be resolved to its entity.
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub(super) fn resolve_memory_init_params(
         &mut self,
         memory: &Memory,
@@ -1035,6 +1587,7 @@ impl StoreInner {
     ///
     /// - If the [`Memory`] does not originate from this [`Store`].
     /// - If the [`Memory`] cannot be resolved to its entity.
+    #[cfg(not(feature = "no-bulk-memory"))]
     pub(super) fn resolve_memory_pair_and_fuel(
         &mut self,
         fst: &Memory,
@@ -1080,6 +1633,52 @@ impl StoreInner {
         self.resolve(object.as_inner(), &self.extern_objects)
     }
 
+    /// Returns the [`ExternRef`] encoded by `value` if it refers to an [`ExternObject`] of this [`Store`].
+    ///
+    /// # Note
+    ///
+    /// This is used to conservatively scan untyped values, such as registers,
+    /// since any `value` may coincidentally look like a valid [`ExternRef`].
+    pub fn as_externref(&self, value: UntypedVal) -> Option<ExternRef> {
+        let externref = ExternRef::from(value);
+        let idx = externref
+            .object()?
+            .as_inner()
+            .entity_index(self.store_idx)?;
+        self.extern_objects.get(idx)?;
+        Some(externref)
+    }
+
+    /// Calls `f` for every non-null [`ExternRef`] held by a table, global variable or element segment.
+    pub fn for_each_externref(&self, mut f: impl FnMut(ExternRef)) {
+        let mut visit = |value: UntypedVal| {
+            let externref = ExternRef::from(value);
+            if !externref.is_null() {
+                f(externref)
+            }
+        };
+        for (_, table) in self.tables.iter() {
+            if table.ty().element() != ValType::ExternRef {
+                continue;
+            }
+            for index in 0..table.size() {
+                if let Some(value) = table.get_untyped(index) {
+                    visit(value)
+                }
+            }
+        }
+        for (_, global) in self.globals.iter() {
+            if global.ty().content() == ValType::ExternRef {
+                visit(global.get_untyped())
+            }
+        }
+        for (_, segment) in self.elems.iter() {
+            if segment.ty() == ValType::ExternRef {
+                segment.items().iter().copied().for_each(&mut visit)
+            }
+        }
+    }
+
     /// Allocates a new Wasm or host [`FuncEntity`] and returns a [`Func`] reference to it.
     pub fn alloc_func(&mut self, func: FuncEntity) -> Func {
         let idx = self.funcs.alloc(func);
@@ -1120,6 +1719,10 @@ impl<T: 'static> Store<T> {
                 data: Box::new(data),
                 limiter: None,
                 call_hook: None,
+                global_hook: None,
+                instr_callback: None,
+                fuel_hook: None,
+                instantiation_hook: None,
             },
             id: TypeId::of::<T>(),
             restore_pruned: RestorePrunedWrapper(Arc::new(|pruned| -> &mut dyn TypedStore {
@@ -1156,6 +1759,71 @@ impl<T> Store<T> {
         *self.typed.data
     }
 
+    /// Resets the [`Store`] to a fresh state with the user provided `data` and returns the old data.
+    ///
+    /// This drops all entities of the [`Store`], such as instances, functions, linear memories,
+    /// tables and global variables, as well as all host provided contexts. The internal entity
+    /// vectors of the [`Store`] keep their allocations for reuse. This gives pool-based hosts
+    /// a fresh [`Store`] per request without paying the allocator costs of a new one.
+    ///
+    /// # Note
+    ///
+    /// - All handles to entities created before the reset, such as [`Instance`], [`Func`] or
+    ///   [`Memory`], become invalid. Using them with the [`Store`] panics the same way as using
+    ///   them with another [`Store`].
+    /// - The configuration of the [`Store`] is kept. This includes the remaining fuel, the
+    ///   resource limiter, all hooks and callbacks as well as the instruction limit.
+    /// - Recorded diagnostics, such as the trace hash and code coverage, are kept as well.
+    /// - Execution stacks are cached by the [`Engine`] and thus reused regardless.
+    ///   Configure them via [`Config::set_cached_stacks`].
+    ///
+    /// [`Config::set_cached_stacks`]: crate::Config::set_cached_stacks
+    pub fn reset(&mut self, data: T) -> T {
+        self.inner.reset();
+        self.typed.trampolines.clear();
+        mem::replace(&mut *self.typed.data, data)
+    }
+
+    /// Inserts the host provided context `ctx` into this [`Store`].
+    ///
+    /// Returns the previously inserted context of type `C` if any.
+    ///
+    /// # Note
+    ///
+    /// In contrast to the single user provided `T` any number of contexts
+    /// may be attached to a [`Store`] as long as their types are distinct.
+    /// This allows independent host libraries to each attach their own state.
+    pub fn insert_context<C>(&mut self, ctx: C) -> Option<C>
+    where
+        C: Any + Send + Sync,
+    {
+        self.inner.contexts.insert(ctx)
+    }
+
+    /// Removes and returns the host provided context of type `C` if any.
+    pub fn remove_context<C>(&mut self) -> Option<C>
+    where
+        C: Any + Send + Sync,
+    {
+        self.inner.contexts.remove()
+    }
+
+    /// Returns a shared reference to the host provided context of type `C` if any.
+    pub fn context<C>(&self) -> Option<&C>
+    where
+        C: Any + Send + Sync,
+    {
+        self.inner.contexts.get()
+    }
+
+    /// Returns an exclusive reference to the host provided context of type `C` if any.
+    pub fn context_mut<C>(&mut self) -> Option<&mut C>
+    where
+        C: Any + Send + Sync,
+    {
+        self.inner.contexts.get_mut()
+    }
+
     /// Installs a function into the [`Store`] that will be called with the user
     /// data type `T` to retrieve a [`ResourceLimiter`] any time a limited,
     /// growable resource such as a linear memory or table is grown.
@@ -1257,6 +1925,179 @@ impl<T> Store<T> {
         self.inner.fuel.set_fuel(fuel).map_err(Into::into)
     }
 
+    /// Adds `amount` to the remaining fuel of the [`Store`] if fuel metering is enabled.
+    ///
+    /// # Note
+    ///
+    /// - This allows host functions to give back fuel via [`Caller::refund_fuel`],
+    ///   for example when Wasm code releases previously charged storage.
+    /// - Refunded fuel is not deducted from the fuel reported to the [`Store::fuel_consumed_hook`].
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled.
+    ///
+    /// [`Caller::refund_fuel`]: crate::Caller::refund_fuel
+    pub fn refund_fuel(&mut self, amount: u64) -> Result<(), Error> {
+        self.inner.fuel.refund_fuel(amount).map_err(Into::into)
+    }
+
+    /// Returns the [`FuelSchedule`] used to charge fuel by the [`Store`] if any.
+    ///
+    /// Returns `None` if Wasmi's unversioned default fuel costs are used.
+    ///
+    /// # Note
+    ///
+    /// Select a [`FuelSchedule`] via [`Config::fuel_schedule`](crate::Config::fuel_schedule).
+    /// The [`FuelSchedule`] is shared by all [`Store`]s of the same [`Engine`] unless another
+    /// [`FuelSchedule`] has been selected for the current call via [`TypedFunc::call_with_schedule`].
+    ///
+    /// [`TypedFunc::call_with_schedule`]: crate::TypedFunc::call_with_schedule
+    pub fn fuel_schedule(&self) -> Option<FuelSchedule> {
+        self.inner
+            .fuel
+            .schedule()
+            .or_else(|| self.engine().config().get_fuel_schedule())
+    }
+
+    /// Runs `f` with `schedule` selected as the [`FuelSchedule`] of its calls.
+    ///
+    /// # Errors
+    ///
+    /// - If `schedule` cannot be selected per call.
+    /// - If `f` fails.
+    pub(crate) fn with_fuel_schedule<R>(
+        &mut self,
+        schedule: FuelSchedule,
+        f: impl FnOnce(&mut Self) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let config = self.inner.engine.config();
+        let schedule = match config.get_fuel_schedule() {
+            Some(default) if default == schedule => None,
+            _ => Some(schedule),
+        };
+        if schedule.is_some() && !config.get_per_call_fuel_schedules() {
+            return Err(Error::from(FuelError::ScheduleUnavailable));
+        }
+        let inner = &mut self.inner;
+        let previous = inner.fuel.set_schedule(schedule, inner.engine.config());
+        let result = f(self);
+        let inner = &mut self.inner;
+        inner.fuel.set_schedule(previous, inner.engine.config());
+        result
+    }
+
+    /// Returns the maximum number of instructions executed per call into Wasm if any.
+    pub fn instruction_limit(&self) -> Option<u64> {
+        self.inner.instr_limit
+    }
+
+    /// Sets the maximum number of instructions executed per call into Wasm.
+    ///
+    /// Calls into Wasm that exceed the `limit` trap with
+    /// [`TrapCode::InstructionLimitExceeded`]. Use `None` to remove the limit.
+    ///
+    /// # Note
+    ///
+    /// - Unlike fuel metering the instruction limit does not require any configuration
+    ///   of the [`Engine`] and counts every executed Wasmi bytecode instruction alike.
+    /// - The limit applies to each call into Wasm individually, including
+    ///   calls from host functions back into Wasm and resumed calls.
+    /// - The limit is checked upon branches and calls and thus an execution
+    ///   may execute a few instructions beyond the `limit` before it traps.
+    ///
+    /// [`TrapCode::InstructionLimitExceeded`]: crate::core::TrapCode::InstructionLimitExceeded
+    pub fn set_instruction_limit(&mut self, limit: Option<u64>) {
+        self.inner.instr_limit = limit;
+    }
+
+    /// Enables or disables trace hashing for the [`Store`].
+    ///
+    /// While enabled, executions fold their control flow through the Wasmi bytecode
+    /// as well as their writes to linear memories and global variables into a
+    /// running hash that can be queried via [`Store::trace_hash`].
+    /// Two executions of the same call with the same state produce the same hash,
+    /// so comparing hashes detects diverging executions, e.g. amongst replaying nodes.
+    ///
+    /// # Note
+    ///
+    /// - Enabling trace hashing always restarts the running hash from scratch.
+    /// - Effects of host functions are not part of the hash.
+    /// - The hash depends on the Wasmi version and the [`Config`] of the [`Engine`]
+    ///   since both affect the translated Wasmi bytecode.
+    /// - Trace hashing slows down execution since every branch and call is traced.
+    ///
+    /// [`Config`]: crate::Config
+    pub fn set_trace_hashing(&mut self, enable: bool) {
+        self.inner.trace = enable.then(ExecutionTrace::default);
+    }
+
+    /// Returns the running hash of all executions since trace hashing has been enabled.
+    ///
+    /// Returns `None` if trace hashing is disabled.
+    ///
+    /// # Note
+    ///
+    /// Enable trace hashing via [`Store::set_trace_hashing`].
+    pub fn trace_hash(&self) -> Option<TraceHash> {
+        self.inner.trace.as_ref().map(ExecutionTrace::digest)
+    }
+
+    /// Returns the [`Coverage`] of the internal functions of `module` by all executions of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// - Code coverage is only recorded if enabled via [`Config::code_coverage`].
+    /// - Lazily compiled functions of `module` are compiled without charging fuel.
+    ///
+    /// # Errors
+    ///
+    /// If a function of `module` fails Wasm to Wasmi bytecode translation.
+    ///
+    /// # Panics
+    ///
+    /// If `module` and the [`Store`] do not originate from the same [`Engine`].
+    pub fn coverage(&self, module: &Module) -> Result<Coverage, Error> {
+        assert!(Engine::same(self.engine(), module.engine()));
+        module.coverage(&self.inner.coverage)
+    }
+
+    /// Calls `f` for every non-null [`ExternRef`] reachable by Wasm code of the [`Store`].
+    ///
+    /// This enumerates the [`ExternRef`]s held by tables, global variables and element segments.
+    /// Use [`ResumableInvocation::trace_externrefs`] to also enumerate the [`ExternRef`]s
+    /// held by the registers of suspended resumable calls.
+    ///
+    /// # Note
+    ///
+    /// Hosts with their own garbage collector can use this to keep the host objects
+    /// that are only referenced by Wasm code alive.
+    /// The same [`ExternRef`] may be reported multiple times.
+    ///
+    /// [`ResumableInvocation::trace_externrefs`]: crate::ResumableInvocation::trace_externrefs
+    pub fn trace_externrefs(&self, f: impl FnMut(ExternRef)) {
+        self.inner.for_each_externref(f)
+    }
+
+    /// Returns the [`BranchProfile`] of the internal functions of `module` by all executions of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// Lazily compiled functions of `module` are compiled without charging fuel.
+    ///
+    /// # Errors
+    ///
+    /// If a function of `module` fails Wasm to Wasmi bytecode translation.
+    ///
+    /// # Panics
+    ///
+    /// If `module` and the [`Store`] do not originate from the same [`Engine`].
+    #[cfg(feature = "branch-profile")]
+    pub fn branch_profile(&self, module: &Module) -> Result<BranchProfile, Error> {
+        assert!(Engine::same(self.engine(), module.engine()));
+        module.branch_profile(&self.inner.branches)
+    }
+
     /// Allocates a new [`TrampolineEntity`] and returns a [`Trampoline`] reference to it.
     pub(super) fn alloc_trampoline(&mut self, func: TrampolineEntity<T>) -> Trampoline {
         let idx = self.typed.trampolines.alloc(func);
@@ -1316,6 +2157,187 @@ impl<T> Store<T> {
         self.typed.call_hook = Some(CallHookWrapper(Box::new(hook)));
     }
 
+    /// Sets a callback function that is executed whenever Wasm code writes
+    /// to a global variable that is watched via [`Global::watch`].
+    ///
+    /// The function is passed a `&mut T` to the underlying store, the written
+    /// [`Global`] and its new value.
+    ///
+    /// The callback can either return `Ok(())` or an `Err` with an
+    /// [`Error`]. If an error is returned, execution of the Wasm code is
+    /// aborted and the error is returned to the host caller.
+    pub fn global_hook(
+        &mut self,
+        hook: impl FnMut(&mut T, Global, Val) -> Result<(), Error> + Send + Sync + 'static,
+    ) {
+        self.typed.global_hook = Some(GlobalHookWrapper(Box::new(hook)));
+    }
+
+    /// Sets a callback function that is executed at the steps of module instantiations.
+    ///
+    /// The function is passed a `&mut T` to the underlying store and an
+    /// [`InstantiationHook`] describing the step of the instantiation.
+    /// This allows to enforce custom policies, e.g. to forbid `start` functions
+    /// or to audit the sizes of data and element segments.
+    ///
+    /// The callback can either return `Ok(())` or an `Err` with an
+    /// [`Error`]. If an error is returned, the instantiation is aborted
+    /// and the error is returned to the host.
+    ///
+    /// # Note
+    ///
+    /// Steps that are not reached, e.g. because an earlier step failed
+    /// or a module has no `start` function, are not reported.
+    pub fn instantiation_hook(
+        &mut self,
+        hook: impl FnMut(&mut T, InstantiationHook) -> Result<(), Error> + Send + Sync + 'static,
+    ) {
+        self.typed.instantiation_hook = Some(InstantiationHookWrapper(Box::new(hook)));
+    }
+
+    /// Removes the callback set by [`Store::instantiation_hook`] if any.
+    pub fn remove_instantiation_hook(&mut self) {
+        self.typed.instantiation_hook = None;
+    }
+
+    /// Executes the callback set by [`Store::instantiation_hook`] if any has been set.
+    ///
+    /// # Note
+    ///
+    /// - Returns the value returned by the instantiation hook.
+    /// - Returns `Ok(())` if no instantiation hook exists.
+    pub(crate) fn invoke_instantiation_hook(
+        &mut self,
+        step: InstantiationHook,
+    ) -> Result<(), Error> {
+        let Some(hook) = self.typed.instantiation_hook.as_mut() else {
+            return Ok(());
+        };
+        hook.0(&mut self.typed.data, step)
+    }
+
+    /// Sets a callback function that is executed periodically, approximately
+    /// every `interval` instructions executed by a call into Wasm.
+    ///
+    /// The function is passed a `&mut T` to the underlying store.
+    /// This is useful for progress reporting, petting a watchdog or for
+    /// cooperative scheduling in single threaded environments.
+    ///
+    /// The callback can either return `Ok(())` or an `Err` with an
+    /// [`Error`]. If an error is returned, execution of the Wasm code is
+    /// aborted and the error is returned to the host caller.
+    ///
+    /// # Note
+    ///
+    /// - Executed instructions are counted per call into Wasm, the same
+    ///   way as for [`Store::set_instruction_limit`].
+    /// - The callback is invoked upon branches and calls and thus the actual
+    ///   number of instructions executed between invocations may slightly exceed `interval`.
+    ///
+    /// # Panics
+    ///
+    /// If `interval` is zero.
+    pub fn instruction_callback(
+        &mut self,
+        interval: u64,
+        callback: impl FnMut(&mut T) -> Result<(), Error> + Send + Sync + 'static,
+    ) {
+        assert!(
+            interval != 0,
+            "instruction callback interval must not be zero"
+        );
+        self.inner.instr_callback_interval = Some(interval);
+        self.typed.instr_callback = Some(InstrCallbackWrapper(Box::new(callback)));
+    }
+
+    /// Removes the callback set by [`Store::instruction_callback`] if any.
+    pub fn remove_instruction_callback(&mut self) {
+        self.inner.instr_callback_interval = None;
+        self.typed.instr_callback = None;
+    }
+
+    /// Sets a callback function that is executed with the amount of fuel
+    /// consumed since its last invocation.
+    ///
+    /// The function is passed a `&mut T` to the underlying store and the consumed fuel.
+    /// It is invoked whenever Wasm code calls a host function and whenever a call
+    /// into Wasm returns to the host, in both cases only if fuel has been consumed.
+    /// This is useful for live metering, e.g. to forward fuel consumption to a dashboard.
+    ///
+    /// The callback can either return `Ok(())` or an `Err` with an
+    /// [`Error`]. If an error is returned, execution of the Wasm code is
+    /// aborted and the error is returned to the host caller.
+    ///
+    /// # Note
+    ///
+    /// - Only fuel consumed after this call is reported.
+    /// - The callback is never invoked if fuel metering is disabled.
+    /// - Fuel set via [`Store::set_fuel`] or refunded via [`Store::refund_fuel`]
+    ///   is not deducted from the reported fuel.
+    pub fn fuel_consumed_hook(
+        &mut self,
+        hook: impl FnMut(&mut T, u64) -> Result<(), Error> + Send + Sync + 'static,
+    ) {
+        self.inner.fuel.take_consumed();
+        self.typed.fuel_hook = Some(FuelHookWrapper(Box::new(hook)));
+    }
+
+    /// Removes the callback set by [`Store::fuel_consumed_hook`] if any.
+    pub fn remove_fuel_consumed_hook(&mut self) {
+        self.typed.fuel_hook = None;
+    }
+
+    /// Executes the callback set by [`Store::fuel_consumed_hook`] if any fuel
+    /// has been consumed since its last invocation.
+    ///
+    /// # Note
+    ///
+    /// - Returns the value returned by the fuel hook.
+    /// - Returns `Ok(())` if no fuel hook exists or no fuel has been consumed.
+    #[inline]
+    pub(crate) fn invoke_fuel_consumed_hook(&mut self) -> Result<(), Error> {
+        let Some(fuel_hook) = self.typed.fuel_hook.as_mut() else {
+            return Ok(());
+        };
+        match self.inner.fuel.take_consumed() {
+            0 => Ok(()),
+            consumed => fuel_hook.0(&mut self.typed.data, consumed),
+        }
+    }
+
+    /// Executes the callback set by [`Store::instruction_callback`] if any has been set.
+    ///
+    /// # Note
+    ///
+    /// - Returns the value returned by the instruction callback.
+    /// - Returns `Ok(())` if no instruction callback exists.
+    fn invoke_instr_callback(&mut self) -> Result<(), Error> {
+        let Some(callback) = self.typed.instr_callback.as_mut() else {
+            return Ok(());
+        };
+        callback.0(&mut self.typed.data)
+    }
+
+    /// Executes the callback set by [`Store::global_hook`] for `global` if any has been set.
+    ///
+    /// # Note
+    ///
+    /// - Returns the value returned by the global hook.
+    /// - Returns `Ok(())` if no global hook exists.
+    fn invoke_global_hook(&mut self, global: Global) -> Result<(), Error> {
+        let Some(global_hook) = self.typed.global_hook.as_mut() else {
+            return Ok(());
+        };
+        let value = self.inner.resolve_global(&global).get();
+        global_hook.0(&mut self.typed.data, global, value)
+    }
+
+    /// Returns `true` if a callback has been set via [`Store::call_hook`].
+    #[inline]
+    pub(crate) fn has_call_hook(&self) -> bool {
+        self.typed.call_hook.is_some()
+    }
+
     /// Executes the callback set by [`Store::call_hook`] if any has been set.
     ///
     /// # Note
@@ -1469,6 +2491,17 @@ impl<T> StoreContextMut<'_, T> {
     pub fn set_fuel(&mut self, fuel: u64) -> Result<(), Error> {
         self.store.set_fuel(fuel)
     }
+
+    /// Adds `amount` to the remaining fuel of the [`Store`] if fuel metering is enabled.
+    ///
+    /// For more information see [`Store::refund_fuel`](crate::Store::refund_fuel).
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled.
+    pub fn refund_fuel(&mut self, amount: u64) -> Result<(), Error> {
+        self.store.refund_fuel(amount)
+    }
 }
 
 impl<T> AsContext for &'_ T
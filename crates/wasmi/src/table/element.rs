@@ -65,6 +65,20 @@ impl ElementSegment {
             .alloc_element_segment(entity)
     }
 
+    /// Allocates a new dropped [`ElementSegment`] with elements of type `ty` on the store.
+    ///
+    /// # Note
+    ///
+    /// This is used for active element segments that lazily initialize their table
+    /// and thus do not need to resolve their items at instantiation.
+    pub fn new_dropped(mut ctx: impl AsContextMut, ty: ValType) -> Self {
+        let entity = ElementSegmentEntity::empty(ty);
+        ctx.as_context_mut()
+            .store
+            .inner
+            .alloc_element_segment(entity)
+    }
+
     /// Returns the number of items in the [`ElementSegment`].
     pub fn size(&self, ctx: impl AsContext) -> u32 {
         ctx.as_context()
@@ -0,0 +1,204 @@
+use crate::{core::UntypedVal, module::ConstExpr, Func, FuncRef};
+use alloc::{sync::Arc, vec::Vec};
+use core::{cmp, ops::Range};
+
+/// The lazily initialized elements of a [`Table`].
+///
+/// # Note
+///
+/// Elements that are pending to be resolved from their [`LazyElements`] are tracked
+/// in a bitmap so that accessing any element requires only a single bit check.
+/// Elements resolved via [`LazyTableElements::take`] are materialized into the
+/// [`Table`] one by one and are accessed without any indirection afterwards.
+///
+/// [`Table`]: crate::Table
+#[derive(Debug, Default)]
+pub struct LazyTableElements {
+    /// Non-overlapping ranges of [`Table`] elements that are lazily initialized by element segments.
+    ///
+    /// [`Table`]: crate::Table
+    segments: Vec<LazyElements>,
+    /// Bitmap of the [`Table`] elements that have not yet been resolved from `segments`.
+    ///
+    /// The bit of the `n`-th element is stored at bit `n % 64` of word `n / 64`.
+    /// Elements beyond the end of the bitmap are never pending.
+    ///
+    /// [`Table`]: crate::Table
+    pending: Vec<u64>,
+}
+
+impl LazyTableElements {
+    /// Returns `true` if no [`Table`] element is lazily initialized.
+    ///
+    /// [`Table`]: crate::Table
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Pushes the `lazy` elements that must not overlap with any existing [`LazyElements`].
+    pub fn push(&mut self, lazy: LazyElements) {
+        let range = lazy.range();
+        let len_words = range.end.div_ceil(64);
+        if self.pending.len() < len_words {
+            self.pending.resize(len_words, 0);
+        }
+        fill_bits(&mut self.pending, range, true);
+        self.segments.push(lazy);
+    }
+
+    /// Returns `true` if the [`Table`] element at `index` has not yet been resolved.
+    ///
+    /// [`Table`]: crate::Table
+    #[inline]
+    fn is_pending(&self, index: usize) -> bool {
+        self.pending
+            .get(index / 64)
+            .is_some_and(|word| word & (1 << (index % 64)) != 0)
+    }
+
+    /// Returns the value of the pending [`Table`] element at `index` if any.
+    ///
+    /// [`Table`]: crate::Table
+    pub fn get(&self, index: usize) -> Option<UntypedVal> {
+        if !self.is_pending(index) {
+            return None;
+        }
+        self.segments.iter().find_map(|lazy| lazy.get(index))
+    }
+
+    /// Resolves the pending [`Table`] element at `index` if any.
+    ///
+    /// The returned value must be written into the [`Table`] since the element is no longer pending.
+    ///
+    /// [`Table`]: crate::Table
+    #[inline]
+    pub fn take(&mut self, index: usize) -> Option<UntypedVal> {
+        let value = self.get(index)?;
+        self.pending[index / 64] &= !(1 << (index % 64));
+        Some(value)
+    }
+
+    /// Writes all lazily initialized [`Table`] elements overlapping with `range` into `elements`.
+    ///
+    /// Afterwards no [`Table`] element within `range` is lazily initialized anymore.
+    ///
+    /// [`Table`]: crate::Table
+    pub fn materialize(&mut self, range: Range<usize>, elements: &mut [UntypedVal]) {
+        let pending = &mut self.pending;
+        self.segments.retain(|lazy| {
+            if !lazy.overlaps(&range) {
+                return true;
+            }
+            lazy.materialize(elements);
+            fill_bits(pending, lazy.range(), false);
+            false
+        });
+        if self.segments.is_empty() {
+            self.pending = Vec::new();
+        }
+    }
+}
+
+/// Sets all bits of `range` in `bitmap` to `value`.
+///
+/// # Panics
+///
+/// If `range` is out of bounds for `bitmap`.
+fn fill_bits(bitmap: &mut [u64], range: Range<usize>, value: bool) {
+    let mut index = range.start;
+    while index < range.end {
+        let shift = index % 64;
+        let len = cmp::min(64 - shift, range.end - index);
+        let mask = (u64::MAX >> (64 - len)) << shift;
+        let word = &mut bitmap[index / 64];
+        match value {
+            true => *word |= mask,
+            false => *word &= !mask,
+        }
+        index += len;
+    }
+}
+
+/// A range of [`Table`] elements that is lazily initialized by an active element segment.
+///
+/// # Note
+///
+/// Initializing large tables from their element segments eagerly at instantiation
+/// is costly and often unnecessary since most of the elements are never accessed.
+/// Instead the [`Table`] resolves untouched elements from the element segment upon access.
+///
+/// [`Table`]: crate::Table
+#[derive(Debug)]
+pub struct LazyElements {
+    /// The index of the first [`Table`] element initialized by the element segment.
+    ///
+    /// [`Table`]: crate::Table
+    dst_index: usize,
+    /// The items of the element segment.
+    ///
+    /// All items are either `ref.func` or constant expressions.
+    items: Arc<[ConstExpr]>,
+    /// The functions of the instance that owns the element segment.
+    funcs: Arc<[Func]>,
+}
+
+impl LazyElements {
+    /// Creates new [`LazyElements`] initializing elements starting at `dst_index`.
+    ///
+    /// # Note
+    ///
+    /// All `items` must either be `ref.func` or constant expressions.
+    /// Otherwise accessing the [`LazyElements`] panics.
+    pub fn new(dst_index: usize, items: Arc<[ConstExpr]>, funcs: Arc<[Func]>) -> Self {
+        Self {
+            dst_index,
+            items,
+            funcs,
+        }
+    }
+
+    /// Returns the range of [`Table`] elements initialized by the [`LazyElements`].
+    ///
+    /// [`Table`]: crate::Table
+    pub fn range(&self) -> Range<usize> {
+        self.dst_index..self.dst_index + self.items.len()
+    }
+
+    /// Returns `true` if the [`LazyElements`] initialize [`Table`] elements overlapping with `range`.
+    ///
+    /// [`Table`]: crate::Table
+    pub fn overlaps(&self, range: &Range<usize>) -> bool {
+        let this = self.range();
+        this.start < range.end && range.start < this.end
+    }
+
+    /// Returns the value of the [`Table`] element at `index` if initialized by the [`LazyElements`].
+    ///
+    /// Returns `None` if `index` is not within the range of the [`LazyElements`].
+    ///
+    /// [`Table`]: crate::Table
+    pub fn get(&self, index: usize) -> Option<UntypedVal> {
+        let item = self.items.get(index.checked_sub(self.dst_index)?)?;
+        Some(self.resolve(item))
+    }
+
+    /// Writes all values of the [`LazyElements`] into the `elements` of their [`Table`].
+    ///
+    /// [`Table`]: crate::Table
+    pub fn materialize(&self, elements: &mut [UntypedVal]) {
+        for (element, item) in elements[self.range()].iter_mut().zip(&self.items[..]) {
+            *element = self.resolve(item);
+        }
+    }
+
+    /// Resolves the value of the element segment `item`.
+    fn resolve(&self, item: &ConstExpr) -> UntypedVal {
+        if let Some(func_index) = item.funcref() {
+            let func = self.funcs[func_index.into_u32() as usize];
+            return FuncRef::new(func).into();
+        }
+        item.eval_const()
+            .unwrap_or_else(|| panic!("unexpected non-constant element segment item: {item:?}"))
+    }
+}
@@ -1,3 +1,4 @@
+use self::lazy::{LazyElements, LazyTableElements};
 pub use self::{
     element::{ElementSegment, ElementSegmentEntity, ElementSegmentIdx},
     error::TableError,
@@ -7,16 +8,19 @@ use crate::{
     collections::arena::ArenaIndex,
     core::{TrapCode, UntypedVal, ValType},
     error::EntityGrowError,
+    module::ConstExpr,
     store::{Fuel, FuelError, ResourceLimiterRef},
     value::WithType,
+    Func,
     IndexType,
     Val,
 };
-use alloc::vec::Vec;
-use core::{cmp::max, iter};
+use alloc::{sync::Arc, vec::Vec};
+use core::{cmp::max, iter, ops::Range};
 
 mod element;
 mod error;
+mod lazy;
 
 #[cfg(test)]
 mod tests;
@@ -192,6 +196,10 @@ impl TableType {
 pub struct TableEntity {
     ty: TableType,
     elements: Vec<UntypedVal>,
+    /// The `elements` that are lazily initialized by element segments.
+    ///
+    /// Pending elements are resolved from their element segments upon access.
+    lazy: LazyTableElements,
 }
 
 impl TableEntity {
@@ -226,7 +234,11 @@ impl TableEntity {
             return Err(error);
         };
         elements.extend(iter::repeat_n::<UntypedVal>(init.into(), min_size));
-        Ok(Self { ty, elements })
+        Ok(Self {
+            ty,
+            elements,
+            lazy: LazyTableElements::default(),
+        })
     }
 
     /// Returns the resizable limits of the table.
@@ -343,8 +355,13 @@ impl TableEntity {
             }
         }
         if let Some(fuel) = fuel {
-            match fuel.consume_fuel(|costs| costs.fuel_for_copies(delta)) {
-                Ok(_) | Err(FuelError::FuelMeteringDisabled) => {}
+            let consumed = fuel.consume_fuel(|costs| {
+                costs
+                    .fuel_for_copies(delta)
+                    .saturating_add(costs.fuel_for_table_elements(delta))
+            });
+            match consumed {
+                Ok(_) | Err(FuelError::FuelMeteringDisabled | FuelError::ScheduleUnavailable) => {}
                 Err(FuelError::OutOfFuel) => return notify_limiter(limiter),
             }
         }
@@ -379,7 +396,54 @@ impl TableEntity {
     /// internal use only.
     pub fn get_untyped(&self, index: u64) -> Option<UntypedVal> {
         let index = usize::try_from(index).ok()?;
-        self.elements.get(index).copied()
+        let value = self.elements.get(index).copied()?;
+        if self.lazy.is_empty() {
+            return Some(value);
+        }
+        Some(self.lazy.get(index).unwrap_or(value))
+    }
+
+    /// Returns the untyped [`Table`] element value at `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Note
+    ///
+    /// In contrast to [`TableEntity::get_untyped`] this materializes a lazily
+    /// initialized element so that repeated accesses, e.g. by indirect calls,
+    /// no longer need to resolve it from its element segment.
+    #[inline]
+    pub fn get_untyped_materialized(&mut self, index: u64) -> Option<UntypedVal> {
+        let index = usize::try_from(index).ok()?;
+        let element = self.elements.get_mut(index)?;
+        if self.lazy.is_empty() {
+            return Some(*element);
+        }
+        if let Some(value) = self.lazy.take(index) {
+            *element = value;
+        }
+        Some(*element)
+    }
+
+    /// Returns the untyped [`Table`] element value at `index`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn element(&self, index: usize) -> UntypedVal {
+        self.lazy.get(index).unwrap_or_else(|| self.elements[index])
+    }
+
+    /// Writes all lazily initialized [`Table`] elements overlapping with `range` into the [`Table`].
+    ///
+    /// # Note
+    ///
+    /// This must be called before mutating any [`Table`] elements within `range`.
+    fn materialize(&mut self, range: Range<usize>) {
+        if self.lazy.is_empty() {
+            return;
+        }
+        self.lazy.materialize(range, &mut self.elements[..]);
     }
 
     /// Sets the [`Val`] of this [`Table`] at `index`.
@@ -400,6 +464,8 @@ impl TableEntity {
     /// If `index` is out of bounds.
     pub fn set_untyped(&mut self, index: u64, value: UntypedVal) -> Result<(), TableError> {
         let current = self.size();
+        let index_size = index as usize;
+        self.materialize(index_size..index_size.saturating_add(1));
         let untyped = self
             .elements
             .get_mut(index as usize)
@@ -408,6 +474,60 @@ impl TableEntity {
         Ok(())
     }
 
+    /// Returns the range of `len` [`Table`] elements starting at `index`.
+    ///
+    /// # Errors
+    ///
+    /// If the range is out of bounds.
+    fn elements_range(&self, index: u64, len: usize) -> Result<Range<usize>, TableError> {
+        let current = self.size();
+        let out_of_bounds = || TableError::AccessOutOfBounds {
+            current,
+            index: max(index, current),
+        };
+        let start = usize::try_from(index).map_err(|_| out_of_bounds())?;
+        let end = start
+            .checked_add(len)
+            .filter(|&end| end <= self.elements.len())
+            .ok_or_else(out_of_bounds)?;
+        Ok(start..end)
+    }
+
+    /// Writes the [`Table`] element values starting at `index` into `values`.
+    ///
+    /// # Errors
+    ///
+    /// If the range of `values.len()` elements starting at `index` is out of bounds.
+    pub fn get_many(&self, index: u64, values: &mut [Val]) -> Result<(), TableError> {
+        let range = self.elements_range(index, values.len())?;
+        let element = self.ty().element();
+        for (value, index) in values.iter_mut().zip(range) {
+            *value = self.element(index).with_type(element);
+        }
+        Ok(())
+    }
+
+    /// Sets the [`Table`] element values starting at `index` to `values`.
+    ///
+    /// The [`Table`] remains unchanged if an error is returned.
+    ///
+    /// # Errors
+    ///
+    /// - If the range of `values.len()` elements starting at `index` is out of bounds.
+    /// - If any of the `values` does not match the [`Table`] element type.
+    pub fn set_many(&mut self, index: u64, values: &[Val]) -> Result<(), TableError> {
+        let ty = self.ty();
+        for value in values {
+            ty.matches_element_type(value.ty())?;
+        }
+        let range = self.elements_range(index, values.len())?;
+        self.materialize(range.clone());
+        for (untyped, value) in self.elements[range].iter_mut().zip(values) {
+            *untyped = value.clone().into();
+        }
+        Ok(())
+    }
+
     /// Initialize `len` elements from `src_element[src_index..]` into `self[dst_index..]`.
     ///
     /// # Errors
@@ -444,6 +564,7 @@ impl TableEntity {
         let Ok(len_size) = usize::try_from(len) else {
             return Err(TrapCode::TableOutOfBounds);
         };
+        self.materialize(dst_index..dst_index.saturating_add(len_size));
         // Perform bounds check before anything else.
         let dst_items = self
             .elements
@@ -469,6 +590,40 @@ impl TableEntity {
         Ok(())
     }
 
+    /// Lazily initialize `self[dst_index..]` with the `items` of an active element segment.
+    ///
+    /// The [`Table`] elements are resolved from the `items` upon their first access.
+    ///
+    /// # Note
+    ///
+    /// - All `items` must either be `ref.func` or constant expressions.
+    /// - `funcs` are the functions of the instance that owns the element segment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range is out of bounds of the table.
+    pub(crate) fn init_lazy(
+        &mut self,
+        dst_index: u64,
+        items: Arc<[ConstExpr]>,
+        funcs: Arc<[Func]>,
+    ) -> Result<(), TrapCode> {
+        let Ok(dst_index) = usize::try_from(dst_index) else {
+            return Err(TrapCode::TableOutOfBounds);
+        };
+        let range = dst_index
+            .checked_add(items.len())
+            .filter(|&end| end <= self.elements.len())
+            .map(|end| dst_index..end)
+            .ok_or(TrapCode::TableOutOfBounds)?;
+        if range.is_empty() {
+            return Ok(());
+        }
+        self.materialize(range);
+        self.lazy.push(LazyElements::new(dst_index, items, funcs));
+        Ok(())
+    }
+
     /// Copy `len` elements from `src_table[src_index..]` into
     /// `dst_table[dst_index..]`.
     ///
@@ -494,6 +649,7 @@ impl TableEntity {
         let Ok(len_size) = usize::try_from(len) else {
             return Err(TrapCode::TableOutOfBounds);
         };
+        dst_table.materialize(dst_index..dst_index.saturating_add(len_size));
         // Perform bounds check before anything else.
         let dst_items = dst_table
             .elements
@@ -508,6 +664,13 @@ impl TableEntity {
         if let Some(fuel) = fuel {
             fuel.consume_fuel_if(|costs| costs.fuel_for_copies(len))?;
         }
+        if !src_table.lazy.is_empty() {
+            // Lazily initialized source elements must be resolved one by one.
+            for (n, dst) in dst_items.iter_mut().enumerate() {
+                *dst = src_table.element(src_index + n);
+            }
+            return Ok(());
+        }
         // Finally, copy elements in-place for the table.
         dst_items.copy_from_slice(src_items);
         Ok(())
@@ -544,6 +707,8 @@ impl TableEntity {
         if let Some(fuel) = fuel {
             fuel.consume_fuel_if(|costs| costs.fuel_for_copies(len))?;
         }
+        self.materialize(src_index..src_index.wrapping_add(len_size));
+        self.materialize(dst_index..dst_index.wrapping_add(len_size));
         // Finally, copy elements in-place for the table.
         self.elements
             .copy_within(src_index..src_index.wrapping_add(len_size), dst_index);
@@ -604,6 +769,7 @@ impl TableEntity {
         let Ok(len_size) = usize::try_from(len) else {
             return Err(TrapCode::TableOutOfBounds);
         };
+        self.materialize(dst_index..dst_index.saturating_add(len_size));
         let dst = self
             .elements
             .get_mut(dst_index..)
@@ -756,6 +922,61 @@ impl Table {
             .set(index, value)
     }
 
+    /// Writes the [`Table`] element values starting at `index` into `values`.
+    ///
+    /// # Note
+    ///
+    /// This is more efficient than calling [`Table::get`] for each element.
+    ///
+    /// # Errors
+    ///
+    /// If the range of `values.len()` elements starting at `index` is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    pub fn get_many(
+        &self,
+        ctx: impl AsContext,
+        index: u64,
+        values: &mut [Val],
+    ) -> Result<(), TableError> {
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_table(self)
+            .get_many(index, values)
+    }
+
+    /// Sets the [`Table`] element values starting at `index` to `values`.
+    ///
+    /// The [`Table`] remains unchanged if an error is returned.
+    ///
+    /// # Note
+    ///
+    /// This is more efficient than calling [`Table::set`] for each element.
+    ///
+    /// # Errors
+    ///
+    /// - If the range of `values.len()` elements starting at `index` is out of bounds.
+    /// - If any of the `values` does not match the [`Table`] element type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    pub fn set_many(
+        &self,
+        mut ctx: impl AsContextMut,
+        index: u64,
+        values: &[Val],
+    ) -> Result<(), TableError> {
+        ctx.as_context_mut()
+            .store
+            .inner
+            .resolve_table_mut(self)
+            .set_many(index, values)
+    }
+
     /// Returns `true` if `lhs` and `rhs` [`Table`] refer to the same entity.
     ///
     /// # Note
@@ -3,6 +3,7 @@ use crate::{
     ExternRef,
     Func,
     FuncRef,
+    TypedExternRef,
 };
 
 /// Untyped instances that allow to be typed.
@@ -136,6 +137,14 @@ impl Val {
         }
     }
 
+    /// Returns the underlying `v128` if the type matches otherwise returns `None`.
+    pub fn v128(&self) -> Option<V128> {
+        match self {
+            Self::V128(value) => Some(*value),
+            _ => None,
+        }
+    }
+
     /// Returns the underlying `funcref` if the type matches otherwise returns `None`.
     pub fn funcref(&self) -> Option<&FuncRef> {
         match self {
@@ -202,9 +211,73 @@ impl From<ExternRef> for Val {
     }
 }
 
+impl<T> From<TypedExternRef<T>> for Val {
+    #[inline]
+    fn from(externref: TypedExternRef<T>) -> Self {
+        Self::ExternRef(externref.into())
+    }
+}
+
 impl From<V128> for Val {
     #[inline]
     fn from(value: V128) -> Self {
         Self::V128(value)
     }
 }
+
+/// The `serde` representation of a [`Val`].
+///
+/// Reference values have no meaning outside of their [`Store`] and thus only
+/// `null` references can be serialized. They are represented by unit variants.
+///
+/// [`Store`]: crate::Store
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Val")]
+enum ValRepr {
+    I32(i32),
+    I64(i64),
+    F32(F32),
+    F64(F64),
+    V128(V128),
+    FuncRef,
+    ExternRef,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Val {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::Error as _;
+        let repr = match self {
+            Self::I32(value) => ValRepr::I32(*value),
+            Self::I64(value) => ValRepr::I64(*value),
+            Self::F32(value) => ValRepr::F32(*value),
+            Self::F64(value) => ValRepr::F64(*value),
+            Self::V128(value) => ValRepr::V128(*value),
+            Self::FuncRef(value) if value.is_null() => ValRepr::FuncRef,
+            Self::ExternRef(value) if value.is_null() => ValRepr::ExternRef,
+            Self::FuncRef(_) | Self::ExternRef(_) => {
+                return Err(S::Error::custom(
+                    "cannot serialize non-null reference values",
+                ))
+            }
+        };
+        repr.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Val {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = match ValRepr::deserialize(deserializer)? {
+            ValRepr::I32(value) => Self::I32(value),
+            ValRepr::I64(value) => Self::I64(value),
+            ValRepr::F32(value) => Self::F32(value),
+            ValRepr::F64(value) => Self::F64(value),
+            ValRepr::V128(value) => Self::V128(value),
+            ValRepr::FuncRef => Self::FuncRef(FuncRef::null()),
+            ValRepr::ExternRef => Self::ExternRef(ExternRef::null()),
+        };
+        Ok(value)
+    }
+}
@@ -0,0 +1,267 @@
+//! Tests to check if the baseline tier interprets Wasm functions as intended.
+
+use wasmi::{core::TrapCode, CompilationMode, Config, Engine, Instance, Linker, Module, Store};
+
+/// A Wasm module exercising control flow, calls, memory, globals and tables.
+const WASM: &str = r#"
+    (module
+        (import "env" "double" (func $double (param i32) (result i32)))
+        (memory 1)
+        (global $counter (mut i32) (i32.const 0))
+        (table funcref (elem $inc $double))
+        (type $unop (func (param i32) (result i32)))
+        (func $inc (param i32) (result i32)
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func (export "sum") (param $n i32) (result i32)
+            (local $i i32)
+            (local $sum i32)
+            (block
+                (loop
+                    (br_if 1 (i32.ge_u (local.get $i) (local.get $n)))
+                    (local.set $sum (i32.add (local.get $sum) (local.get $i)))
+                    (local.set $i (call $inc (local.get $i)))
+                    (br 0)
+                )
+            )
+            (local.get $sum)
+        )
+        (func (export "classify") (param i32) (result i32)
+            (block
+                (block
+                    (block
+                        (br_table 0 1 2 (local.get 0))
+                    )
+                    (return (i32.const 10))
+                )
+                (return (i32.const 20))
+            )
+            (i32.const 30)
+        )
+        (func (export "select_if") (param i32) (result i64)
+            (if (result i64) (local.get 0)
+                (then (i64.const -1))
+                (else (i64.extend_i32_u (i32.const -1)))
+            )
+        )
+        (func (export "memory") (param $ptr i32) (param $value i64) (result i64 i32)
+            (i64.store offset=8 (local.get $ptr) (local.get $value))
+            (i64.load8_s offset=8 (local.get $ptr))
+            (memory.grow (i32.const 2))
+        )
+        (func (export "indirect") (param i32) (param i32) (result i32)
+            (call_indirect (type $unop) (local.get 0) (local.get 1))
+        )
+        (func (export "div") (param i32) (param i32) (result i32)
+            (i32.div_u (local.get 0) (local.get 1))
+        )
+        (func (export "counter") (result i32)
+            (global.get $counter)
+        )
+        (func (export "fill") (param i32) (result i32)
+            (memory.fill (i32.const 0) (local.get 0) (i32.const 4))
+            (i32.load (i32.const 0))
+        )
+    )
+"#;
+
+/// Returns a [`Config`] with the baseline tier enabled that tiers up at `threshold`.
+fn tier_up_config(threshold: u32) -> Config {
+    let mut config = Config::default();
+    config
+        .compilation_mode(CompilationMode::LazyTranslation)
+        .baseline_tier(true)
+        .tier_up_threshold(threshold);
+    config
+}
+
+/// Returns a [`Config`] with the baseline tier enabled that interprets only first calls.
+fn baseline_config() -> Config {
+    tier_up_config(1)
+}
+
+/// Instantiates [`WASM`] with `config`.
+fn setup(config: &Config) -> (Store<()>, Module, Instance) {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "double", |value: i32| value.wrapping_mul(2))
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, module, instance)
+}
+
+#[test]
+#[cfg(not(feature = "branch-profile"))]
+fn first_call_is_interpreted() {
+    let (mut store, module, instance) = setup(&baseline_config());
+    let sum = instance.get_typed_func::<i32, i32>(&store, "sum").unwrap();
+    assert_eq!(sum.call(&mut store, 10).unwrap(), 45);
+    // Note: only the first call of `$inc` has been interpreted, later calls translated it.
+    let metrics = module.compilation_metrics();
+    assert_eq!(metrics.len_compiled_funcs(), 1);
+    assert!(metrics.get(1).is_some());
+    assert!(metrics.get(2).is_none());
+    assert_eq!(sum.call(&mut store, 10).unwrap(), 45);
+    let metrics = module.compilation_metrics();
+    assert_eq!(metrics.len_compiled_funcs(), 2);
+    assert!(metrics.get(2).is_some());
+    let counter = instance
+        .get_typed_func::<(), i32>(&store, "counter")
+        .unwrap();
+    assert_eq!(counter.call(&mut store, ()).unwrap(), 20);
+}
+
+#[test]
+fn interpreted_and_compiled_results_match() {
+    let (mut store, _module, instance) = setup(&baseline_config());
+    let classify = instance
+        .get_typed_func::<i32, i32>(&store, "classify")
+        .unwrap();
+    let select_if = instance
+        .get_typed_func::<i32, i64>(&store, "select_if")
+        .unwrap();
+    let memory = instance
+        .get_typed_func::<(i32, i64), (i64, i32)>(&store, "memory")
+        .unwrap();
+    let indirect = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "indirect")
+        .unwrap();
+    for _ in 0..2 {
+        assert_eq!(classify.call(&mut store, 0).unwrap(), 10);
+        assert_eq!(select_if.call(&mut store, 1).unwrap(), -1);
+        assert_eq!(indirect.call(&mut store, (5, 0)).unwrap(), 6);
+    }
+    for (input, expected) in [(1, 20), (2, 30), (100, 30)] {
+        assert_eq!(classify.call(&mut store, input).unwrap(), expected);
+    }
+    assert_eq!(select_if.call(&mut store, 0).unwrap(), 0xFFFF_FFFF);
+    assert_eq!(indirect.call(&mut store, (5, 1)).unwrap(), 10);
+    assert_eq!(memory.call(&mut store, (16, 0x1FF)).unwrap(), (-1, 1));
+    assert_eq!(memory.call(&mut store, (16, 0x17F)).unwrap(), (127, 3));
+}
+
+#[test]
+fn interpreted_traps() {
+    let (mut store, _module, instance) = setup(&baseline_config());
+    let div = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "div")
+        .unwrap();
+    let indirect = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "indirect")
+        .unwrap();
+    let memory = instance
+        .get_typed_func::<(i32, i64), (i64, i32)>(&store, "memory")
+        .unwrap();
+    let trap_code = |error: wasmi::Error| error.as_trap_code();
+    assert_eq!(
+        trap_code(div.call(&mut store, (1, 0)).unwrap_err()),
+        Some(TrapCode::IntegerDivisionByZero)
+    );
+    assert_eq!(
+        trap_code(indirect.call(&mut store, (0, 2)).unwrap_err()),
+        Some(TrapCode::TableOutOfBounds)
+    );
+    assert_eq!(
+        trap_code(memory.call(&mut store, (-1, 0)).unwrap_err()),
+        Some(TrapCode::MemoryOutOfBounds)
+    );
+    // Note: the trapped calls were interpreted so the next calls are translated.
+    assert_eq!(div.call(&mut store, (7, 2)).unwrap(), 3);
+}
+
+#[test]
+fn unsupported_operators_are_translated() {
+    let (mut store, module, instance) = setup(&baseline_config());
+    let fill = instance.get_typed_func::<i32, i32>(&store, "fill").unwrap();
+    assert_eq!(fill.call(&mut store, 0x11).unwrap(), 0x1111_1111);
+    assert_eq!(module.compilation_metrics().len_compiled_funcs(), 1);
+}
+
+#[test]
+fn fuel_metering_bypasses_baseline_tier() {
+    let mut config = baseline_config();
+    config.consume_fuel(true);
+    let (mut store, module, instance) = setup(&config);
+    store.set_fuel(10_000).unwrap();
+    let sum = instance.get_typed_func::<i32, i32>(&store, "sum").unwrap();
+    assert_eq!(sum.call(&mut store, 10).unwrap(), 45);
+    assert_eq!(module.compilation_metrics().len_compiled_funcs(), 2);
+}
+
+#[test]
+#[cfg(feature = "branch-profile")]
+fn branch_profiles_bypass_baseline_tier() {
+    let (mut store, module, instance) = setup(&baseline_config());
+    let sum = instance.get_typed_func::<i32, i32>(&store, "sum").unwrap();
+    assert_eq!(sum.call(&mut store, 10).unwrap(), 45);
+    assert_eq!(module.compilation_metrics().len_compiled_funcs(), 2);
+}
+
+#[test]
+#[cfg(not(feature = "branch-profile"))]
+fn hot_functions_tier_up() {
+    let (mut store, module, instance) = setup(&tier_up_config(3));
+    let classify = instance
+        .get_typed_func::<i32, i32>(&store, "classify")
+        .unwrap();
+    for _ in 0..3 {
+        assert_eq!(classify.call(&mut store, 1).unwrap(), 20);
+        assert_eq!(module.compilation_metrics().len_compiled_funcs(), 0);
+    }
+    assert_eq!(classify.call(&mut store, 1).unwrap(), 20);
+    let metrics = module.compilation_metrics();
+    assert_eq!(metrics.len_compiled_funcs(), 1);
+    assert!(metrics.get(3).is_some());
+}
+
+#[test]
+#[cfg(not(feature = "branch-profile"))]
+fn interpreted_functions_are_decoded_once() {
+    let (mut store, module, instance) = setup(&tier_up_config(10));
+    let classify = instance
+        .get_typed_func::<i32, i32>(&store, "classify")
+        .unwrap();
+    let before = module.engine().memory_usage().uncompiled_code();
+    assert_eq!(classify.call(&mut store, 1).unwrap(), 20);
+    let decoded = module.engine().memory_usage().uncompiled_code();
+    assert!(decoded > before);
+    for _ in 0..3 {
+        assert_eq!(classify.call(&mut store, 2).unwrap(), 30);
+        assert_eq!(module.engine().memory_usage().uncompiled_code(), decoded);
+    }
+    assert_eq!(module.compilation_metrics().len_compiled_funcs(), 0);
+}
+
+#[test]
+#[cfg(not(feature = "branch-profile"))]
+fn loop_iterations_count_towards_hotness() {
+    let (mut store, module, instance) = setup(&tier_up_config(20));
+    let sum = instance.get_typed_func::<i32, i32>(&store, "sum").unwrap();
+    // Note: `sum` runs 10 loop iterations and calls `$inc` 10 times per call.
+    for _ in 0..2 {
+        assert_eq!(sum.call(&mut store, 10).unwrap(), 45);
+        assert_eq!(module.compilation_metrics().len_compiled_funcs(), 0);
+    }
+    assert_eq!(sum.call(&mut store, 10).unwrap(), 45);
+    assert_eq!(module.compilation_metrics().len_compiled_funcs(), 2);
+    let counter = instance
+        .get_typed_func::<(), i32>(&store, "counter")
+        .unwrap();
+    assert_eq!(counter.call(&mut store, ()).unwrap(), 30);
+}
+
+#[test]
+fn zero_tier_up_threshold_translates_first_calls() {
+    let (mut store, module, instance) = setup(&tier_up_config(0));
+    let sum = instance.get_typed_func::<i32, i32>(&store, "sum").unwrap();
+    assert_eq!(sum.call(&mut store, 10).unwrap(), 45);
+    assert_eq!(module.compilation_metrics().len_compiled_funcs(), 2);
+}
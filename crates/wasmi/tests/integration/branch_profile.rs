@@ -0,0 +1,131 @@
+//! Tests to check if branch profiles recorded by the `Store` work as intended.
+
+use wasmi::{BranchProfile, CompilationMode, Config, Engine, Instance, Linker, Module, Store};
+
+/// The Wasm module used for the branch profile tests.
+const WASM: &str = r#"
+    (module
+        (func (export "count") (param $n i32) (result i32)
+            (local $i i32)
+            (block
+                (loop
+                    (br_if 1 (i32.ge_u (local.get $i) (local.get $n)))
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br 0)
+                )
+            )
+            (if (result i32) (i32.and (local.get $i) (i32.const 1))
+                (then (i32.const 10))
+                (else (i32.const 20))
+            )
+        )
+        (func (export "select") (param $a i32) (param $b i32) (param $c i32) (result i32)
+            (block (result i32)
+                (br_if 0 (local.get $b) (local.get $c))
+                (drop)
+                (local.get $a)
+            )
+        )
+        (func (export "early") (param $c i32) (result i32)
+            (br_if 0 (i32.const 1) (local.get $c))
+            (drop)
+            (i32.const 2)
+        )
+    )
+"#;
+
+/// Setup [`Store`] and [`Instance`] for the branch profile tests with `config`.
+fn test_setup(config: &Config) -> (Store<()>, Module, Instance) {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, module, instance)
+}
+
+/// Returns the [`BranchProfile`] after calling `count` with `n` and `early` with `c`.
+fn profile_of(config: &Config, n: i32, c: i32) -> BranchProfile {
+    let (mut store, module, instance) = test_setup(config);
+    let count = instance
+        .get_typed_func::<i32, i32>(&store, "count")
+        .unwrap();
+    let early = instance
+        .get_typed_func::<i32, i32>(&store, "early")
+        .unwrap();
+    count.call(&mut store, n).unwrap();
+    early.call(&mut store, c).unwrap();
+    store.branch_profile(&module).unwrap()
+}
+
+/// Returns the `(func, offset, taken, not_taken)` tuples of all branches of `profile`.
+fn branch_counts(profile: &BranchProfile) -> Vec<(u32, u32, u64, u64)> {
+    profile
+        .branches()
+        .iter()
+        .map(|branch| {
+            let (func, offset) = (branch.func(), branch.offset());
+            (func, offset, branch.taken(), branch.not_taken())
+        })
+        .collect()
+}
+
+#[test]
+fn branch_profile_counts_taken_and_not_taken() {
+    let profile = profile_of(&Config::default(), 5, 0);
+    assert_eq!(profile.first_func(), 0);
+    assert_eq!(profile.len_funcs(), 3);
+    // Note:
+    // - The `br_if` of `count` at offset 12 exits its loop once after 5 iterations.
+    // - The `if` of `count` at offset 30 enters its `then` block since `5 & 1 != 0`.
+    // - The `br_if` of `early` at offset 5 returns early if its condition is non-zero.
+    assert_eq!(
+        branch_counts(&profile),
+        [(0, 12, 1, 5), (0, 30, 1, 0), (1, 7, 0, 0), (2, 5, 0, 1)],
+    );
+    let profile = profile_of(&Config::default(), 4, 1);
+    assert_eq!(
+        branch_counts(&profile),
+        [(0, 12, 1, 4), (0, 30, 0, 1), (1, 7, 0, 0), (2, 5, 1, 0)],
+    );
+    let branch = profile.get(0, 12).unwrap();
+    assert_eq!((branch.taken(), branch.not_taken()), (1, 4));
+    assert!(profile.get(0, 13).is_none());
+}
+
+#[test]
+fn branch_profile_of_br_if_with_copies() {
+    let (mut store, module, instance) = test_setup(&Config::default());
+    let select = instance
+        .get_typed_func::<(i32, i32, i32), i32>(&store, "select")
+        .unwrap();
+    assert_eq!(select.call(&mut store, (1, 2, 0)).unwrap(), 1);
+    assert_eq!(select.call(&mut store, (1, 2, 1)).unwrap(), 2);
+    assert_eq!(select.call(&mut store, (1, 2, 3)).unwrap(), 2);
+    let profile = store.branch_profile(&module).unwrap();
+    let branch = profile.get(1, 7).unwrap();
+    assert_eq!((branch.taken(), branch.not_taken()), (2, 1));
+}
+
+#[test]
+fn branch_profile_is_independent_of_compilation_mode() {
+    let eager = profile_of(&Config::default(), 7, 1);
+    for mode in [CompilationMode::LazyTranslation, CompilationMode::Lazy] {
+        let mut config = Config::default();
+        config.compilation_mode(mode);
+        let lazy = profile_of(&config, 7, 1);
+        assert_eq!(lazy.branches(), eager.branches());
+    }
+}
+
+#[test]
+fn branch_profile_display() {
+    let profile = profile_of(&Config::default(), 2, 0);
+    assert_eq!(
+        profile.to_string(),
+        "0 0xc 1 2\n0 0x1e 0 1\n1 0x7 0 0\n2 0x5 0 1\n",
+    );
+}
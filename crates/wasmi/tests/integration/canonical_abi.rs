@@ -0,0 +1,151 @@
+//! Tests to check if the canonical ABI lift and lower helpers work as intended.
+
+use wasmi::{
+    errors::{ErrorKind, MemoryError},
+    CanonicalType,
+    Engine,
+    GuestAllocator,
+    Instance,
+    LiftContext,
+    Linker,
+    LowerContext,
+    Memory,
+    Store,
+};
+
+/// A guest exporting an aligning bump allocator as `cabi_realloc`.
+const WASM: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (global $next (mut i32) (i32.const 16))
+        (func (export "cabi_realloc") (param i32 i32 i32 i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr
+                (i32.and
+                    (i32.add (global.get $next) (i32.sub (local.get 2) (i32.const 1)))
+                    (i32.sub (i32.const 0) (local.get 2))
+                )
+            )
+            (global.set $next (i32.add (local.get $ptr) (local.get 3)))
+            (local.get $ptr)
+        )
+        (func (export "sum") (param $ptr i32) (param $len i32) (result i32)
+            (local $sum i32)
+            (block
+                (loop
+                    (br_if 1 (i32.eqz (local.get $len)))
+                    (local.set $sum (i32.add (local.get $sum) (i32.load (local.get $ptr))))
+                    (local.set $ptr (i32.add (local.get $ptr) (i32.const 4)))
+                    (local.set $len (i32.sub (local.get $len) (i32.const 1)))
+                    (br 0)
+                )
+            )
+            (local.get $sum)
+        )
+    )
+"#;
+
+/// Instantiates [`WASM`] and returns its allocator and linear memory.
+fn setup() -> (Store<()>, Instance, GuestAllocator, Memory) {
+    let engine = Engine::default();
+    let module = wasmi::Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let allocator = GuestAllocator::new(&store, instance).unwrap();
+    let memory = allocator.memory();
+    (store, instance, allocator, memory)
+}
+
+/// Returns the `(size, align)` memory layout of `T`.
+fn layout<T: CanonicalType + ?Sized>() -> (u32, u32) {
+    (T::SIZE, T::ALIGN)
+}
+
+#[test]
+fn layouts_match_canonical_abi() {
+    assert_eq!(layout::<bool>(), (1, 1));
+    assert_eq!(layout::<u16>(), (2, 2));
+    assert_eq!(layout::<f64>(), (8, 8));
+    assert_eq!(layout::<char>(), (4, 4));
+    assert_eq!(layout::<String>(), (8, 4));
+    assert_eq!(layout::<Vec<u64>>(), (8, 4));
+    assert_eq!(layout::<()>(), (0, 1));
+    assert_eq!(layout::<(u8, u32)>(), (8, 4));
+    assert_eq!(layout::<(u8, u16, u8)>(), (6, 2));
+    assert_eq!(layout::<(u64, u8)>(), (16, 8));
+    assert_eq!(layout::<Option<u8>>(), (2, 1));
+    assert_eq!(layout::<Option<u32>>(), (8, 4));
+    assert_eq!(layout::<Result<(), ()>>(), (1, 1));
+    assert_eq!(layout::<Result<String, u8>>(), (12, 4));
+    assert_eq!(layout::<Result<u64, (u8, u8)>>(), (16, 8));
+}
+
+#[test]
+fn lowered_values_lift_back() {
+    type Record = (String, Vec<(char, Option<u16>)>, Result<bool, String>, f32);
+    let (mut store, _instance, allocator, memory) = setup();
+    let values: [Record; 2] = [
+        (
+            String::from("wasmi"),
+            vec![('🦀', Some(42)), ('a', None)],
+            Ok(true),
+            1.5,
+        ),
+        (String::new(), Vec::new(), Err(String::from("error")), -0.5),
+    ];
+    let mut cx = LowerContext::new(&mut store, allocator);
+    let ptr = cx.lower(&values[0]).unwrap();
+    let (list, len) = cx.lower_list(&values).unwrap();
+    let cx = LiftContext::new(&store, memory);
+    assert_eq!(cx.lift::<Record>(ptr).unwrap(), values[0]);
+    assert_eq!(cx.lift_list::<Record>(list, len).unwrap(), values);
+}
+
+#[test]
+fn lowered_lists_are_seen_by_guest() {
+    let (mut store, instance, allocator, _memory) = setup();
+    let sum = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "sum")
+        .unwrap();
+    let mut cx = LowerContext::new(&mut store, allocator);
+    let (ptr, len) = cx.lower_list(&[1_u32, 2, 3, 4]).unwrap();
+    assert_eq!(ptr % 4, 0);
+    assert_eq!(sum.call(&mut store, (ptr as i32, len as i32)).unwrap(), 10);
+}
+
+#[test]
+fn invalid_values_fail_to_lift() {
+    let (mut store, _instance, _allocator, memory) = setup();
+    memory
+        .write(&mut store, 0, &[0x00, 0xD8, 0x00, 0x00])
+        .unwrap();
+    memory.write(&mut store, 4, &[2, 0xFF, 0xFF]).unwrap();
+    memory
+        .write(&mut store, 8, &[1, 0, 0, 0, 2, 0, 0, 0])
+        .unwrap();
+    let cx = LiftContext::new(&store, memory);
+    assert!(matches!(
+        cx.lift::<char>(0).unwrap_err().kind(),
+        ErrorKind::Memory(MemoryError::InvalidChar(0xD800))
+    ));
+    assert!(matches!(
+        cx.lift::<Option<u8>>(4).unwrap_err().kind(),
+        ErrorKind::Memory(MemoryError::InvalidDiscriminant(2))
+    ));
+    assert!(matches!(
+        cx.lift::<u32>(2).unwrap_err().kind(),
+        ErrorKind::Memory(MemoryError::UnalignedAccess)
+    ));
+    assert!(matches!(
+        cx.lift::<String>(8).unwrap_err().kind(),
+        ErrorKind::Memory(MemoryError::InvalidUtf8(_))
+    ));
+    assert!(matches!(
+        cx.lift_list::<u64>(0, u32::MAX).unwrap_err().kind(),
+        ErrorKind::Memory(MemoryError::OutOfBoundsAccess)
+    ));
+}
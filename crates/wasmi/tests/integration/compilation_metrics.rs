@@ -0,0 +1,112 @@
+//! Tests to check if the compilation metrics of a `Module` work as intended.
+
+use wasmi::{CompilationMode, Config, Engine, Linker, Module, Store};
+
+/// A Wasm module with an imported function and two internal functions.
+const WASM: &str = r#"
+    (module
+        (import "env" "host" (func $host))
+        (func (export "sum") (param $n i32) (result i32)
+            (local $i i32)
+            (local $sum i32)
+            (block
+                (loop
+                    (br_if 1 (i32.ge_u (local.get $i) (local.get $n)))
+                    (local.set $sum (i32.add (local.get $sum) (local.get $i)))
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br 0)
+                )
+            )
+            (i32.add (local.get $sum) (i32.const 1_000_000))
+        )
+        (func (export "id") (param i32) (result i32)
+            (local.get 0)
+        )
+    )
+"#;
+
+/// Calls the `id` function of a new instance of `module`.
+fn call_id(engine: &Engine, module: &Module) {
+    let mut store = Store::new(engine, ());
+    let mut linker = <Linker<()>>::new(engine);
+    linker.func_wrap("env", "host", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let id = instance.get_typed_func::<i32, i32>(&store, "id").unwrap();
+    assert_eq!(id.call(&mut store, 42).unwrap(), 42);
+}
+
+#[test]
+fn compilation_metrics_of_eagerly_compiled_module() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let metrics = module.compilation_metrics();
+    assert_eq!(metrics.first_func(), 1);
+    assert_eq!(metrics.len_funcs(), 2);
+    assert_eq!(metrics.len_compiled_funcs(), 2);
+    let sum = metrics.get(1).unwrap();
+    let id = metrics.get(2).unwrap();
+    assert!(metrics.get(0).is_none());
+    assert_eq!(metrics.funcs(), [*sum, *id]);
+    assert_eq!(id.len_instrs(), 1);
+    assert_eq!(id.len_consts(), 0);
+    assert_eq!(id.len_registers(), 1);
+    assert!(sum.len_instrs() > id.len_instrs());
+    assert!(sum.len_consts() > 0);
+    assert_eq!(
+        metrics.len_instrs(),
+        u64::from(sum.len_instrs() + id.len_instrs())
+    );
+    assert_eq!(
+        metrics.len_consts(),
+        u64::from(sum.len_consts() + id.len_consts())
+    );
+    assert_eq!(
+        metrics.len_registers(),
+        u64::from(sum.len_registers() + id.len_registers())
+    );
+    assert_eq!(metrics.max_registers(), sum.len_registers());
+    assert_eq!(metrics.len_fuel_blocks(), 0);
+    assert!(sum.translation_time().is_some());
+    assert_eq!(
+        metrics.translation_time(),
+        Some(sum.translation_time().unwrap() + id.translation_time().unwrap())
+    );
+}
+
+#[test]
+fn compilation_metrics_count_fuel_blocks() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let metrics = module.compilation_metrics();
+    assert_eq!(metrics.get(2).unwrap().len_fuel_blocks(), 1);
+    // Note: the function entry and the loop body are separate basic blocks.
+    assert!(metrics.get(1).unwrap().len_fuel_blocks() >= 2);
+}
+
+#[test]
+fn compilation_metrics_skip_uncompiled_functions() {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let before = module.compilation_metrics();
+    assert_eq!(before.len_funcs(), 2);
+    assert_eq!(before.len_compiled_funcs(), 0);
+    assert_eq!(before.len_instrs(), 0);
+    assert_eq!(before.max_registers(), 0);
+    assert_eq!(before.translation_time(), Some(Default::default()));
+    // Note: querying the metrics does not compile functions.
+    assert_eq!(module.compilation_metrics(), before);
+    call_id(&engine, &module);
+    let after = module.compilation_metrics();
+    assert_eq!(after.len_compiled_funcs(), 1);
+    assert!(after.get(1).is_none());
+    assert_eq!(after.funcs()[0].index(), 2);
+    assert_eq!(after.len_instrs(), 1);
+}
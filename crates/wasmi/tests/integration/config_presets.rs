@@ -0,0 +1,91 @@
+//! Tests for the `Config` presets of common deployments.
+
+use wasmi::{core::TrapCode, Config, Engine, FuelSchedule, Linker, Module, Store, TypedFunc};
+
+/// A Wasm module with a function that recurses `n` times.
+const WASM: &str = r#"
+    (module
+        (func $depth (export "depth") (param $n i32) (result i32)
+            (if (result i32) (i32.eqz (local.get $n))
+                (then (i32.const 0))
+                (else
+                    (i32.add
+                        (call $depth (i32.sub (local.get $n) (i32.const 1)))
+                        (i32.const 1)
+                    )
+                )
+            )
+        )
+    )
+"#;
+
+/// Instantiates [`WASM`] with `config` and returns its `depth` function.
+fn setup(config: &Config) -> (Store<()>, TypedFunc<i32, i32>) {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let depth = instance
+        .get_typed_func::<i32, i32>(&store, "depth")
+        .unwrap();
+    (store, depth)
+}
+
+/// Asserts that `result` is a trap with `expected` [`TrapCode`].
+fn assert_trap<T>(result: Result<T, wasmi::Error>, expected: TrapCode) {
+    let Err(error) = result else {
+        panic!("expected trap: {expected:?}")
+    };
+    assert_eq!(error.as_trap_code(), Some(expected));
+}
+
+#[test]
+fn embedded_preset_limits_recursion() {
+    let (mut store, depth) = setup(&Config::default());
+    assert_eq!(depth.call(&mut store, 500).unwrap(), 500);
+    let (mut store, depth) = setup(&Config::embedded());
+    assert_eq!(depth.call(&mut store, 100).unwrap(), 100);
+    assert_trap(depth.call(&mut store, 500), TrapCode::StackOverflow);
+}
+
+#[test]
+fn server_preset_consumes_fuel() {
+    let (mut store, depth) = setup(&Config::server());
+    store.set_fuel(10_000).unwrap();
+    assert_eq!(depth.call(&mut store, 10).unwrap(), 10);
+    assert!(store.get_fuel().unwrap() < 10_000);
+    store.set_fuel(0).unwrap();
+    assert_trap(depth.call(&mut store, 10), TrapCode::OutOfFuel);
+}
+
+#[test]
+fn deterministic_preset_uses_fuel_schedule() {
+    let config = Config::deterministic();
+    assert_eq!(config.get_fuel_schedule(), Some(FuelSchedule::v1()));
+    let (mut store, depth) = setup(&config);
+    store.set_fuel(10_000).unwrap();
+    assert_eq!(depth.call(&mut store, 10).unwrap(), 10);
+    let consumed = 10_000 - store.get_fuel().unwrap();
+    let (mut store, depth) = setup(&config);
+    store.set_fuel(10_000).unwrap();
+    assert_eq!(depth.call(&mut store, 10).unwrap(), 10);
+    assert_eq!(10_000 - store.get_fuel().unwrap(), consumed);
+}
+
+#[test]
+#[cfg(feature = "simd")]
+fn deterministic_preset_rejects_relaxed_simd() {
+    let wasm = r#"
+        (module
+            (func (param v128 v128) (result v128)
+                (i32x4.relaxed_laneselect (local.get 0) (local.get 1) (local.get 0))
+            )
+        )
+    "#;
+    assert!(Module::new(&Engine::default(), wasm).is_ok());
+    assert!(Module::new(&Engine::new(&Config::deterministic()), wasm).is_err());
+}
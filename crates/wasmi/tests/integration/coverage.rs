@@ -0,0 +1,126 @@
+//! Tests to check if code coverage recorded by the `Store` works as intended.
+
+use wasmi::{CompilationMode, Config, Coverage, Engine, Instance, Linker, Module, Store};
+
+/// The Wasm module used for the code coverage tests.
+///
+/// The function body of `select` is encoded as follows:
+///
+/// | Offset | Wasm operator   |
+/// |-------:|:----------------|
+/// |      0 | locals          |
+/// |      1 | `local.get 0`   |
+/// |      3 | `if (result i32)` |
+/// |      5 | `i32.const 10`  |
+/// |      7 | `else`          |
+/// |      8 | `i32.const 20`  |
+/// |     10 | `end`           |
+/// |     11 | `end`           |
+const WASM: &str = r#"
+    (module
+        (import "env" "f" (func))
+        (func (export "select") (param i32) (result i32)
+            (if (result i32) (local.get 0)
+                (then (i32.const 10))
+                (else (i32.const 20))
+            )
+        )
+        (func (export "unused")
+            (call 0)
+        )
+    )
+"#;
+
+/// Setup [`Store`] and [`Instance`] for the code coverage tests with `config`.
+fn test_setup(config: &Config) -> (Store<()>, Module, Instance) {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "f", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, module, instance)
+}
+
+/// Returns the [`Coverage`] after calling `select` with each of the `inputs`.
+fn coverage_of(config: &Config, inputs: &[i32]) -> Coverage {
+    let (mut store, module, instance) = test_setup(config);
+    let select = instance
+        .get_typed_func::<i32, i32>(&store, "select")
+        .unwrap();
+    for input in inputs {
+        select.call(&mut store, *input).unwrap();
+    }
+    store.coverage(&module).unwrap()
+}
+
+/// Returns the offsets of the executed basic blocks of `select`.
+fn executed_offsets(coverage: &Coverage) -> Vec<u32> {
+    coverage
+        .executed_blocks()
+        .filter(|block| block.func() == 1)
+        .map(|block| block.offset())
+        .collect()
+}
+
+fn coverage_config() -> Config {
+    let mut config = Config::default();
+    config.code_coverage(true);
+    config
+}
+
+#[test]
+fn coverage_maps_blocks_to_wasm_offsets() {
+    let coverage = coverage_of(&coverage_config(), &[]);
+    assert_eq!(coverage.first_func(), 1);
+    assert_eq!(coverage.len_funcs(), 2);
+    let offsets = coverage
+        .blocks()
+        .iter()
+        .map(|block| (block.func(), block.offset()))
+        .collect::<Vec<_>>();
+    assert_eq!(offsets, [(1, 1), (1, 5), (1, 8), (1, 11), (2, 1)]);
+    assert!(coverage.funcs_bitmap().iter().all(|byte| *byte == 0));
+    assert!(coverage.blocks_bitmap().iter().all(|byte| *byte == 0));
+}
+
+#[test]
+fn coverage_records_executed_blocks() {
+    let config = coverage_config();
+    let coverage = coverage_of(&config, &[1]);
+    assert!(!coverage.is_func_executed(0));
+    assert!(coverage.is_func_executed(1));
+    assert!(!coverage.is_func_executed(2));
+    assert_eq!(coverage.funcs_bitmap(), [0b01]);
+    assert_eq!(coverage.blocks_bitmap(), [0b01011]);
+    assert_eq!(executed_offsets(&coverage), [1, 5, 11]);
+    let coverage = coverage_of(&config, &[0]);
+    assert_eq!(executed_offsets(&coverage), [1, 8, 11]);
+    let coverage = coverage_of(&config, &[0, 1]);
+    assert_eq!(executed_offsets(&coverage), [1, 5, 8, 11]);
+}
+
+#[test]
+fn coverage_is_independent_of_compilation_mode() {
+    let eager = coverage_of(&coverage_config(), &[1]);
+    for mode in [CompilationMode::LazyTranslation, CompilationMode::Lazy] {
+        let mut config = coverage_config();
+        config.compilation_mode(mode);
+        let lazy = coverage_of(&config, &[1]);
+        assert_eq!(lazy.blocks(), eager.blocks());
+        assert_eq!(lazy.blocks_bitmap(), eager.blocks_bitmap());
+        assert_eq!(lazy.funcs_bitmap(), eager.funcs_bitmap());
+    }
+}
+
+#[test]
+fn coverage_is_empty_if_disabled() {
+    let coverage = coverage_of(&Config::default(), &[0, 1]);
+    assert_eq!(coverage.len_funcs(), 2);
+    assert!(coverage.blocks().is_empty());
+    assert!(!coverage.is_func_executed(1));
+}
@@ -0,0 +1,42 @@
+//! Tests to check that Wasm validation follows the enabled crate features of Wasmi.
+
+use wasmi::{Engine, Module};
+
+/// The binary encoding of `(module (func (param f32)))`.
+const WASM_FLOAT: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x05, 0x01, 0x60, 0x01, 0x7D, 0x00, // types
+    0x03, 0x02, 0x01, 0x00, // funcs
+    0x0A, 0x04, 0x01, 0x02, 0x00, 0x0B, // code
+];
+
+/// The binary encoding of a Wasm module using `memory.fill`:
+///
+/// ```wat
+/// (module
+///     (memory 1)
+///     (func (memory.fill (i32.const 0) (i32.const 0) (i32.const 0)))
+/// )
+/// ```
+const WASM_BULK_MEMORY: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // types
+    0x03, 0x02, 0x01, 0x00, // funcs
+    0x05, 0x03, 0x01, 0x00, 0x01, // memories
+    0x0A, 0x0D, 0x01, 0x0B, 0x00, // code
+    0x41, 0x00, 0x41, 0x00, 0x41, 0x00, 0xFC, 0x0B, 0x00, 0x0B, // func 0
+];
+
+#[test]
+fn float_support_follows_crate_feature() {
+    let engine = Engine::default();
+    let result = Module::new(&engine, WASM_FLOAT);
+    assert_eq!(result.is_ok(), !cfg!(feature = "no-float"));
+}
+
+#[test]
+fn bulk_memory_support_follows_crate_feature() {
+    let engine = Engine::default();
+    let result = Module::new(&engine, WASM_BULK_MEMORY);
+    assert_eq!(result.is_ok(), !cfg!(feature = "no-bulk-memory"));
+}
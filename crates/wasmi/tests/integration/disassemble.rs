@@ -0,0 +1,116 @@
+//! Tests to check if `Module::disassemble` works as intended.
+
+use wasmi::{
+    core::UntypedVal,
+    ir::{index, Instruction, Reg, RegSpan},
+    Engine,
+    Func,
+    Instr,
+    Linker,
+    Module,
+    Store,
+};
+
+#[test]
+fn disassemble_works() {
+    let wasm = r#"
+        (module
+            (import "env" "f" (func))
+            (func (export "add") (param i32) (result i32)
+                (i32.add (local.get 0) (i32.const 100000))
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let funcs = module.disassemble().collect::<Result<Vec<_>, _>>().unwrap();
+    // Imported functions are not disassembled.
+    assert_eq!(funcs.len(), 1);
+    let func = &funcs[0];
+    assert_eq!(func.index(), 1);
+    assert_eq!(func.name(), Some("add"));
+    assert_eq!(func.len_consts(), 1);
+    assert!(func.len_instrs() >= 1);
+    assert!(usize::from(func.len_registers()) >= 2);
+    let text = func.to_string();
+    assert!(text.contains("Reg(-1): 0x00000000000186a0"));
+    assert!(text.contains("I32Add"));
+}
+
+#[test]
+fn disassembly_parses_back() {
+    let wasm = r#"
+        (module
+            (memory 1)
+            (func (export "f") (param i32 i64) (result i64)
+                (block
+                    (br_if 0 (i32.eqz (local.get 0)))
+                    (i64.store offset=8 (local.get 0) (local.get 1))
+                )
+                (i64.add
+                    (i64.rotl (local.get 1) (i64.const 7))
+                    (i64.load offset=8 (i32.const 0))
+                )
+            )
+            (func (export "g") (param f32) (result f32)
+                (f32.copysign (local.get 0) (f32.const -1.0))
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    for func in module.disassemble() {
+        let text = func.unwrap().to_string();
+        let instrs = wasmi_ir::text::parse_instrs(&text).unwrap();
+        // Note: the instructions are listed after the function local constants.
+        let listed = text.lines().skip_while(|line| *line != "instrs:").skip(1);
+        assert_eq!(instrs.len(), listed.clone().count());
+        for (n, (instr, line)) in instrs.iter().zip(listed).enumerate() {
+            assert_eq!(format!("  {n:04}: {instr:?}"), line);
+        }
+    }
+}
+
+#[test]
+fn func_disassemble_works() {
+    let wasm = r#"
+        (module
+            (import "env" "f" (func $f))
+            (func (export "add") (param i32) (result i32)
+                (call $f)
+                (i32.add (local.get 0) (i32.const 100000))
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "f", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let add = instance.get_func(&store, "add").unwrap();
+    let func = add.disassemble(&store).unwrap().unwrap();
+    assert_eq!(func.index(), 1);
+    assert_eq!(func.name(), Some("add"));
+    // Note: registers of function local constants are included.
+    assert_eq!(func.len_registers(), 3);
+    assert_eq!(func.consts(), [UntypedVal::from(100_000_i32)]);
+    let reg = Reg::from;
+    let expected = [
+        Instruction::call_imported_0(RegSpan::new(reg(1)), index::Func::from(0)),
+        Instruction::i32_add(reg(1), reg(0), reg(-1)),
+        Instruction::return_reg(reg(1)),
+    ];
+    let instrs = func.instrs().collect::<Vec<_>>();
+    assert_eq!(instrs.len(), func.len_instrs());
+    for (n, (instr, expected)) in instrs.into_iter().zip(expected).enumerate() {
+        assert_eq!(instr, (Instr::from_usize(n), expected));
+    }
+    // Host functions have no Wasmi bytecode.
+    let f = Func::wrap(&mut store, || {});
+    assert!(f.disassemble(&store).unwrap().is_none());
+}
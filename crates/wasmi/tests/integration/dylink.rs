@@ -0,0 +1,84 @@
+//! Tests to check if loading Wasm shared objects via `Linker::instantiate_dylib` works as intended.
+
+use wasmi::{Engine, Linker, Memory, MemoryType, Module, Store, Table, TableType, Val};
+use wasmi_core::ValType;
+
+/// A shared object reserving 8 bytes of linear memory aligned to 4 bytes and 1 table element.
+const WASM_DYLIB: &str = r#"
+    (module
+        (@custom "dylink.0" "\01\04\08\02\01\00")
+        (import "env" "memory" (memory 1))
+        (import "env" "__indirect_function_table" (table 0 funcref))
+        (import "env" "__memory_base" (global $memory_base i32))
+        (import "env" "__table_base" (global $table_base i32))
+        (import "GOT.mem" "counter" (global $counter_addr (mut i32)))
+        (import "GOT.func" "get" (global $get_index (mut i32)))
+        (global $counter (export "counter") i32 (i32.const 0))
+        (data (global.get $memory_base) "\2A\00\00\00")
+        (elem (global.get $table_base) func $get)
+        (func $get (export "get") (result i32)
+            (i32.load (global.get $counter_addr))
+        )
+        (func (export "get_indirect") (result i32)
+            (call_indirect (result i32) (global.get $get_index))
+        )
+    )
+"#;
+
+#[test]
+fn dylink_info_works() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM_DYLIB).unwrap();
+    let info = module.dylink_info().unwrap().unwrap();
+    assert_eq!(info.memory_size(), 8);
+    assert_eq!(info.memory_alignment_log2(), 2);
+    assert_eq!(info.table_size(), 1);
+    assert_eq!(info.table_alignment_log2(), 0);
+    assert_eq!(info.needed().len(), 0);
+}
+
+#[test]
+fn instantiate_dylib_works() {
+    let engine = Engine::default();
+    let mut store = <Store<()>>::new(&engine, ());
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let table = Table::new(
+        &mut store,
+        TableType::new(ValType::FuncRef, 3, None),
+        Val::default(ValType::FuncRef),
+    )
+    .unwrap();
+    let module = Module::new(&engine, WASM_DYLIB).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate_dylib(&mut store, &module, memory, table)
+        .unwrap();
+    // The data of the shared object is placed after the existing linear memory.
+    assert_eq!(memory.size(&store), 2);
+    let mut counter = [0x00_u8; 4];
+    memory.read(&store, 65536, &mut counter).unwrap();
+    assert_eq!(counter, [42, 0, 0, 0]);
+    // One table element is reserved and one is appended for the `GOT.func` entry.
+    assert_eq!(table.size(&store), 5);
+    for name in ["get", "get_indirect"] {
+        let func = instance.get_typed_func::<(), i32>(&store, name).unwrap();
+        assert_eq!(func.call(&mut store, ()).unwrap(), 42);
+    }
+}
+
+#[test]
+fn instantiate_dylib_requires_dylink_section() {
+    let engine = Engine::default();
+    let mut store = <Store<()>>::new(&engine, ());
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let table = Table::new(
+        &mut store,
+        TableType::new(ValType::FuncRef, 0, None),
+        Val::default(ValType::FuncRef),
+    )
+    .unwrap();
+    let module = Module::new(&engine, "(module)").unwrap();
+    assert!(module.dylink_info().unwrap().is_none());
+    <Linker<()>>::new(&engine)
+        .instantiate_dylib(&mut store, &module, memory, table)
+        .unwrap_err();
+}
@@ -0,0 +1,91 @@
+//! Tests for the stable [`ErrorCategory`] of Wasmi errors.
+//!
+//! [`ErrorCategory`]: wasmi::errors::ErrorCategory
+
+use wasmi::{
+    core::TrapCode,
+    errors::ErrorCategory,
+    Caller,
+    Engine,
+    Error,
+    Func,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Store,
+};
+
+#[test]
+fn compile_error() {
+    let engine = Engine::default();
+    let error = Module::new(&engine, &b"\0asm\x01\0\0\0\xFF"[..]).unwrap_err();
+    assert_eq!(error.category(), ErrorCategory::Compile);
+    assert!(error.is_compile());
+    assert!(!error.is_trap());
+}
+
+#[test]
+fn link_error() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, r#"(module (import "env" "f" (func)))"#).unwrap();
+    let mut store = <Store<()>>::new(&engine, ());
+    let error = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap_err();
+    assert_eq!(error.category(), ErrorCategory::Link);
+    assert!(error.is_link());
+    assert!(error.as_linker_error().is_some());
+}
+
+#[test]
+fn trap_error() {
+    let engine = Engine::default();
+    let wasm = r#"(module (func (export "f") (unreachable)))"#;
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = <Store<()>>::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let error = instance
+        .get_typed_func::<(), ()>(&store, "f")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err();
+    assert_eq!(error.category(), ErrorCategory::Trap);
+    assert!(error.is_trap());
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+}
+
+#[test]
+fn host_error() {
+    let mut store = <Store<()>>::default();
+    let func = Func::wrap(&mut store, |_caller: Caller<()>| -> Result<(), Error> {
+        Err(Error::new("custom host error"))
+    });
+    let error = func.call(&mut store, &[], &mut []).unwrap_err();
+    assert_eq!(error.category(), ErrorCategory::Host);
+    assert!(error.is_host());
+    assert_eq!(error.as_message(), Some("custom host error"));
+    assert_eq!(Error::i32_exit(1).category(), ErrorCategory::Host);
+}
+
+#[test]
+fn resource_error() {
+    let mut store = <Store<()>>::default();
+    let memory = Memory::new(&mut store, MemoryType::new(1, Some(1)).unwrap()).unwrap();
+    let error = Error::from(memory.grow(&mut store, 1).unwrap_err());
+    assert_eq!(error.category(), ErrorCategory::Resource);
+    assert!(error.is_resource());
+}
+
+#[test]
+fn usage_error() {
+    let mut store = <Store<()>>::default();
+    let func = Func::wrap(&mut store, |_caller: Caller<()>, _: i32| {});
+    let error = func.call(&mut store, &[], &mut []).unwrap_err();
+    assert_eq!(error.category(), ErrorCategory::Usage);
+    assert_eq!(error.category().to_string(), "usage");
+}
@@ -1,6 +1,19 @@
 //! Tests to check if wasmi's fuel metering works as intended.
 
-use wasmi::{Config, Engine, Error, Func, Linker, Module, Store};
+use wasmi::{
+    core::TrapCode,
+    errors::{ErrorKind, FuelError},
+    Caller,
+    Config,
+    Engine,
+    Error,
+    FuelSchedule,
+    Func,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+};
 
 /// Setup [`Engine`] and [`Store`] for fuel metering.
 fn test_setup() -> (Store<()>, Linker<()>) {
@@ -79,3 +92,206 @@ fn check_fuel_consumption(given_fuel: u64, consumed_fuel: u64) {
 fn fuel_consumption_01() {
     check_fuel_consumption(3, 3);
 }
+
+/// Returns the fuel consumed by calling the `"test"` function of `wasm` with `schedule`.
+fn fuel_consumed_with(schedule: FuelSchedule, wasm: &str) -> u64 {
+    let mut config = Config::default();
+    config.consume_fuel(true).fuel_schedule(schedule);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    assert_eq!(store.fuel_schedule(), Some(schedule));
+    let module = Module::new(&engine, wasm).unwrap();
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func::<(), i32>(&store, "test").unwrap();
+    let given_fuel = 1_000_000;
+    store.set_fuel(given_fuel).unwrap();
+    func.call(&mut store, ()).unwrap();
+    given_fuel - store.get_fuel().unwrap()
+}
+
+/// A reference program exercising all kinds of fuel costs.
+const FUEL_SCHEDULE_REFERENCE: &str = r#"
+    (module
+        (memory 1)
+        (func $swap (param i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+            (result i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+            (local.get 9) (local.get 8) (local.get 7) (local.get 6) (local.get 5)
+            (local.get 4) (local.get 3) (local.get 2) (local.get 1) (local.get 0)
+        )
+        (func (export "test") (result i32)
+            (local $i i32)
+            (loop $continue
+                (memory.fill (i32.const 0) (i32.const 1) (i32.const 1000))
+                (call $swap
+                    (i64.const 0) (i64.const 1) (i64.const 2) (i64.const 3) (i64.const 4)
+                    (i64.const 5) (i64.const 6) (i64.const 7) (i64.const 8) (i64.const 9)
+                )
+                (drop) (drop) (drop) (drop) (drop)
+                (drop) (drop) (drop) (drop) (drop)
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br_if $continue (i32.lt_u (local.get $i) (i32.const 10)))
+            )
+            (i32.load (i32.const 0))
+        )
+    )
+"#;
+
+#[test]
+fn fuel_schedule_v1() {
+    let schedule = FuelSchedule::v1();
+    assert_eq!(schedule.version(), 1);
+    // Note: released fuel schedules are frozen and this value must never change.
+    assert_eq!(fuel_consumed_with(schedule, FUEL_SCHEDULE_REFERENCE), 245);
+}
+
+/// A Wasm module growing its linear memory and table.
+const GROW: &str = r#"
+    (module
+        (memory (export "memory") 0)
+        (table 0 funcref)
+        (func (export "memory.grow") (param i32) (result i32)
+            (memory.grow (local.get 0))
+        )
+        (func (export "table.grow") (param i32) (result i32)
+            (table.grow (ref.null func) (local.get 0))
+        )
+    )
+"#;
+
+/// Calls the `name` function of [`GROW`] with `delta` and `fuel` using `config`.
+///
+/// Returns the result of the call, the consumed fuel and the size of the linear memory.
+fn grow_with(
+    config: &mut Config,
+    name: &str,
+    delta: i32,
+    fuel: u64,
+) -> (Result<i32, Error>, u64, u64) {
+    let engine = Engine::new(config.consume_fuel(true));
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, GROW).unwrap();
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func::<i32, i32>(&store, name).unwrap();
+    store.set_fuel(fuel).unwrap();
+    let result = func.call(&mut store, delta);
+    let consumed = fuel - store.get_fuel().unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    (result, consumed, memory.size(&store))
+}
+
+#[test]
+fn grow_fuel_is_proportional() {
+    let fuel = 1_000_000;
+    for name in ["memory.grow", "table.grow"] {
+        let (_, base, _) = grow_with(&mut Config::default(), name, 10, fuel);
+        let mut config = Config::default();
+        config.memory_page_fuel(1_000).table_element_fuel(100);
+        let (result, consumed, _) = grow_with(&mut config, name, 10, fuel);
+        assert_eq!(result.unwrap(), 0);
+        let expected = match name {
+            "memory.grow" => 10 * 1_000,
+            _ => 10 * 100,
+        };
+        assert_eq!(consumed - base, expected);
+    }
+}
+
+#[test]
+fn grow_fuel_is_kept_by_schedules() {
+    let mut config = Config::default();
+    config
+        .memory_page_fuel(1_000)
+        .fuel_schedule(FuelSchedule::v1());
+    let (result, consumed, _) = grow_with(&mut config, "memory.grow", 1, 1_000_000);
+    assert_eq!(result.unwrap(), 0);
+    assert!(consumed > 1_000);
+}
+
+#[test]
+fn grow_out_of_fuel_fails() {
+    let mut config = Config::default();
+    config.memory_page_fuel(1_000).table_element_fuel(1_000);
+    let (result, _, size) = grow_with(&mut config, "memory.grow", 10, 5_000);
+    assert_eq!(
+        result.unwrap_err().as_trap_code(),
+        Some(TrapCode::OutOfFuel)
+    );
+    assert_eq!(size, 0);
+    let (result, _, _) = grow_with(&mut config, "table.grow", 10, 5_000);
+    assert_eq!(result.unwrap(), -1);
+}
+
+/// Returns the store and `"test"` function of [`FUEL_SCHEDULE_REFERENCE`] using `config`.
+///
+fn schedule_test_setup(config: &mut Config) -> (Store<Option<FuelSchedule>>, TypedFunc<(), i32>) {
+    let engine = Engine::new(config.consume_fuel(true));
+    let mut store = Store::new(&engine, None);
+    let module = Module::new(&engine, FUEL_SCHEDULE_REFERENCE).unwrap();
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func::<(), i32>(&store, "test").unwrap();
+    (store, func)
+}
+
+#[test]
+fn per_call_fuel_schedule() {
+    let mut config = Config::default();
+    config.per_call_fuel_schedules(true);
+    let (mut store, func) = schedule_test_setup(&mut config);
+    let given_fuel = 1_000_000;
+    store.set_fuel(given_fuel).unwrap();
+    func.call_with_schedule(&mut store, (), FuelSchedule::v1())
+        .unwrap();
+    // Note: this equals the fuel consumed by an engine using `FuelSchedule::v1`.
+    assert_eq!(given_fuel - store.get_fuel().unwrap(), 245);
+    assert_eq!(store.fuel_schedule(), None);
+}
+
+#[test]
+fn per_call_fuel_schedule_is_reported() {
+    let mut config = Config::default();
+    config.per_call_fuel_schedules(true);
+    let (mut store, _) = schedule_test_setup(&mut config);
+    let report = Func::wrap(&mut store, |mut caller: Caller<Option<FuelSchedule>>| {
+        *caller.data_mut() = caller.fuel_schedule();
+    });
+    let report = report.typed::<(), ()>(&store).unwrap();
+    store.set_fuel(1_000).unwrap();
+    report
+        .call_with_schedule(&mut store, (), FuelSchedule::v1())
+        .unwrap();
+    assert_eq!(*store.data(), Some(FuelSchedule::v1()));
+    report.call(&mut store, ()).unwrap();
+    assert_eq!(*store.data(), None);
+}
+
+#[test]
+fn per_call_fuel_schedule_unavailable() {
+    let (mut store, func) = schedule_test_setup(&mut Config::default());
+    store.set_fuel(1_000_000).unwrap();
+    let error = func
+        .call_with_schedule(&mut store, (), FuelSchedule::v1())
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Fuel(FuelError::ScheduleUnavailable)
+    ));
+    // Note: the schedule of the engine is always available.
+    let mut config = Config::default();
+    config.fuel_schedule(FuelSchedule::v1());
+    let (mut store, func) = schedule_test_setup(&mut config);
+    store.set_fuel(1_000_000).unwrap();
+    func.call_with_schedule(&mut store, (), FuelSchedule::v1())
+        .unwrap();
+}
@@ -0,0 +1,141 @@
+//! Tests for the fuel consumed hook and fuel refunds of the `Store`.
+
+use wasmi::{Caller, Config, Engine, Error, Instance, Linker, Module, Store, TypedFunc};
+
+/// A Wasm module that calls the host function `refund` in between some work.
+const WASM: &str = r#"
+    (module
+        (import "env" "refund" (func $refund (param i64)))
+        (func (export "run") (param $refund i64) (result i32)
+            (local $i i32)
+            (loop $continue
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br_if $continue (i32.lt_u (local.get $i) (i32.const 10)))
+            )
+            (call $refund (local.get $refund))
+            (i32.add (local.get $i) (i32.const 1))
+        )
+        (func (export "spin") (param $n i32)
+            (loop $continue
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br_if $continue (i32.ne (local.get $n) (i32.const 0)))
+            )
+        )
+    )
+"#;
+
+/// The events recorded by the host during the execution.
+#[derive(Debug, Default)]
+struct Events {
+    /// The fuel reported by each invocation of the fuel consumed hook.
+    reported: Vec<u64>,
+    /// The number of reports before the `refund` host function was called.
+    reports_before_refund: Option<usize>,
+}
+
+/// Instantiates [`WASM`] with fuel metering.
+fn instantiate() -> (Store<Events>, Instance) {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, Events::default());
+    let mut linker = <Linker<Events>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "refund",
+            |mut caller: Caller<Events>, amount: i64| -> Result<(), Error> {
+                let reports = caller.data().reported.len();
+                caller.data_mut().reports_before_refund = Some(reports);
+                caller.refund_fuel(amount as u64)
+            },
+        )
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Instantiates [`WASM`] with fuel metering and returns its `run` function.
+fn setup() -> (Store<Events>, TypedFunc<i64, i32>) {
+    let (store, instance) = instantiate();
+    let run = instance.get_typed_func::<i64, i32>(&store, "run").unwrap();
+    (store, run)
+}
+
+#[test]
+fn fuel_consumed_hook_reports_consumed_fuel() {
+    let (mut store, run) = setup();
+    store.set_fuel(1_000).unwrap();
+    store.fuel_consumed_hook(|events, consumed| {
+        events.reported.push(consumed);
+        Ok(())
+    });
+    assert_eq!(run.call(&mut store, 0).unwrap(), 11);
+    let consumed = 1_000 - store.get_fuel().unwrap();
+    let events = store.data();
+    // Note: the hook reports before the host call and, if more fuel has been
+    //       consumed afterwards, once more upon returning to the host.
+    assert_eq!(events.reports_before_refund, Some(1));
+    assert!(events.reported.iter().all(|&fuel| fuel != 0));
+    assert_eq!(events.reported.iter().sum::<u64>(), consumed);
+}
+
+#[test]
+fn fuel_consumed_hook_reports_upon_return() {
+    let (mut store, instance) = instantiate();
+    let spin = instance.get_typed_func::<i32, ()>(&store, "spin").unwrap();
+    store.set_fuel(1_000).unwrap();
+    store.fuel_consumed_hook(|events, consumed| {
+        events.reported.push(consumed);
+        Ok(())
+    });
+    spin.call(&mut store, 10).unwrap();
+    spin.call(&mut store, 20).unwrap();
+    let consumed = 1_000 - store.get_fuel().unwrap();
+    let reported = &store.data().reported;
+    assert_eq!(reported.len(), 2);
+    assert!(reported[0] < reported[1]);
+    assert_eq!(reported[0] + reported[1], consumed);
+}
+
+#[test]
+fn refund_fuel_works() {
+    let (mut store, run) = setup();
+    store.set_fuel(1_000).unwrap();
+    assert_eq!(run.call(&mut store, 0).unwrap(), 11);
+    let consumed = 1_000 - store.get_fuel().unwrap();
+    store.set_fuel(1_000).unwrap();
+    store.fuel_consumed_hook(|events, consumed| {
+        events.reported.push(consumed);
+        Ok(())
+    });
+    assert_eq!(run.call(&mut store, 50).unwrap(), 11);
+    assert_eq!(store.get_fuel().unwrap(), 1_000 - consumed + 50);
+    // Note: refunded fuel is not deducted from the reported fuel.
+    assert_eq!(store.data().reported.iter().sum::<u64>(), consumed);
+    store.refund_fuel(u64::MAX).unwrap();
+    assert_eq!(store.get_fuel().unwrap(), u64::MAX);
+}
+
+#[test]
+fn fuel_consumed_hook_error_aborts_execution() {
+    let (mut store, run) = setup();
+    store.set_fuel(1_000).unwrap();
+    store.fuel_consumed_hook(|_, _| Err(Error::new("fuel budget exceeded")));
+    let error = run.call(&mut store, 0).unwrap_err();
+    assert_eq!(error.to_string(), "fuel budget exceeded");
+    assert_eq!(store.data().reports_before_refund, None);
+    store.remove_fuel_consumed_hook();
+    assert_eq!(run.call(&mut store, 0).unwrap(), 11);
+}
+
+#[test]
+fn refund_fuel_requires_fuel_metering() {
+    let mut store = <Store<()>>::default();
+    assert!(store.refund_fuel(1).is_err());
+}
@@ -1,6 +1,7 @@
 //! Tests for the `Func` type in Wasmi.
 
 use core::slice;
+use std::{cell::Cell, rc::Rc, thread};
 
 use assert_matches::assert_matches;
 use wasmi::{
@@ -8,10 +9,14 @@ use wasmi::{
     Engine,
     Func,
     FuncType,
+    Linker,
+    Module,
     Store,
     Val,
 };
-use wasmi_core::{ValType, F32, F64};
+#[cfg(feature = "simd")]
+use wasmi_core::V128;
+use wasmi_core::{UntypedVal, ValType, F32, F64};
 
 fn test_setup() -> Store<()> {
     let engine = Engine::default();
@@ -21,7 +26,7 @@ fn test_setup() -> Store<()> {
 /// Asserts that `lhs` and `rhs` tuples are equal.
 ///
 /// We need to define the following macro for the comparison of tuples with
-/// 16 elements since `PartialEq` and `Debug` do not seem to be implemented
+/// 16 or more elements since `PartialEq` and `Debug` do not seem to be implemented
 /// for tuples of that size.
 macro_rules! assert_eq_tuple {
     ( $lhs:ident, $rhs:ident; $($n:tt),* $(,)? ) => {
@@ -204,7 +209,7 @@ fn static_duplicate_works() {
 
 fn setup_many_params() -> (Store<()>, Func) {
     let mut store = test_setup();
-    // Function taking 16 arguments (maximum) and doing nothing.
+    // Function taking 16 arguments and doing nothing.
     let func = Func::wrap(
         &mut store,
         |_0: i32,
@@ -292,7 +297,7 @@ fn static_many_params_works() {
 
 fn setup_many_results() -> (Store<()>, Func) {
     let mut store = test_setup();
-    // Function taking 16 arguments (maximum) and doing nothing.
+    // Function taking 16 arguments and doing nothing.
     let func = Func::wrap(&mut store, ascending_tuple);
     (store, func)
 }
@@ -325,7 +330,7 @@ fn static_many_results_works() {
 
 fn setup_many_params_many_results() -> (Store<()>, Func) {
     let mut store = test_setup();
-    // Function taking 16 arguments (maximum) and doing nothing.
+    // Function taking 16 arguments and doing nothing.
     let func = Func::wrap(
         &mut store,
         |v0: i32,
@@ -373,10 +378,67 @@ fn static_many_params_many_results_works() {
     assert_eq_tuple!(result, inputs; 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
 }
 
+type I32x32 = (
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+    i32,
+);
+
+#[test]
+fn static_max_params_max_results_works() {
+    let mut store = test_setup();
+    // Function taking 32 arguments (maximum) and returning them as its 32 results.
+    let func = Func::new_untyped(
+        &mut store,
+        FuncType::new([ValType::I32; 32], [ValType::I32; 32]),
+        |_caller, _params_results: &mut [UntypedVal]| Ok(()),
+    );
+    let typed_func = func.typed::<I32x32, I32x32>(&mut store).unwrap();
+    let inputs: I32x32 = (
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
+        25, 26, 27, 28, 29, 30, 31,
+    );
+    let result = typed_func.call(&mut store, inputs).unwrap();
+    assert_eq_tuple!(
+        result, inputs;
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+        16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31,
+    );
+}
+
 #[test]
 fn dynamic_many_types_works() {
     let mut store = test_setup();
-    // Function taking no arguments and returning 16 results as tuple (maximum).
+    // Function taking no arguments and returning 16 results as tuple.
     let func = Func::wrap(
         &mut store,
         |v0: i32, v1: u32, v2: i64, v3: u64, v4: F32, v5: F64| (v0, v1, v2, v3, v4, v5),
@@ -402,7 +464,7 @@ fn dynamic_many_types_works() {
 #[test]
 fn static_many_types_works() {
     let mut store = test_setup();
-    // Function taking no arguments and returning 16 results as tuple (maximum).
+    // Function taking no arguments and returning 16 results as tuple.
     let func = Func::wrap(
         &mut store,
         |v0: i32, v1: u32, v2: i64, v3: u64, v4: F32, v5: F64| (v0, v1, v2, v3, v4, v5),
@@ -415,6 +477,87 @@ fn static_many_types_works() {
     assert_eq!(result, inputs);
 }
 
+#[test]
+fn untyped_many_params_many_results_works() {
+    let mut store = test_setup();
+    // Function reversing its 32 arguments which exceeds the `Func::wrap` limits.
+    let func = Func::new_untyped(
+        &mut store,
+        FuncType::new([ValType::I64; 32], [ValType::I64; 32]),
+        |_caller, params_results: &mut [UntypedVal]| {
+            assert_eq!(params_results.len(), 32);
+            params_results.reverse();
+            Ok(())
+        },
+    );
+    let inputs = core::array::from_fn::<_, 32, _>(|i| Val::I64(i as i64));
+    let mut results = [0; 32].map(Val::I64);
+    func.call(&mut store, &inputs, &mut results).unwrap();
+    for (i, result) in results.iter().enumerate() {
+        assert_eq!(result.i64(), Some(31 - i as i64));
+    }
+}
+
+#[test]
+fn untyped_more_params_than_results_works() {
+    let mut store = test_setup();
+    // Function summing its 3 arguments into a single result.
+    let func = Func::new_untyped(
+        &mut store,
+        FuncType::new([ValType::I32; 3], [ValType::I32]),
+        |_caller, params_results: &mut [UntypedVal]| {
+            assert_eq!(params_results.len(), 3);
+            let sum = params_results
+                .iter()
+                .map(|value| i32::from(*value))
+                .fold(0_i32, i32::wrapping_add);
+            params_results[0] = UntypedVal::from(sum);
+            Ok(())
+        },
+    );
+    let mut result = Val::I32(0);
+    func.call(
+        &mut store,
+        &[Val::I32(1), Val::I32(2), Val::I32(3)],
+        slice::from_mut(&mut result),
+    )
+    .unwrap();
+    assert_eq!(result.i32(), Some(6));
+}
+
+#[test]
+#[cfg(feature = "simd")]
+fn v128_params_and_results_work() {
+    let mut store = test_setup();
+    // Function swapping its two `v128` arguments and adding their lanes as `i64x2`.
+    let func = Func::wrap(&mut store, |lhs: V128, rhs: V128| {
+        let (lhs, rhs) = (lhs.as_u128(), rhs.as_u128());
+        let lo = (lhs as u64).wrapping_add(rhs as u64);
+        let hi = ((lhs >> 64) as u64).wrapping_add((rhs >> 64) as u64);
+        (
+            V128::from(rhs),
+            V128::from(lhs),
+            V128::from((hi as u128) << 64 | lo as u128),
+        )
+    });
+    let lhs = V128::from(0x0000_0000_0000_0001_FFFF_FFFF_FFFF_FFFF_u128);
+    let rhs = V128::from(0x0000_0000_0000_0002_0000_0000_0000_0001_u128);
+    let sum = V128::from(0x0000_0000_0000_0003_0000_0000_0000_0000_u128);
+    let mut results = [0_u128; 3].map(V128::from).map(Val::V128);
+    func.call(&mut store, &[Val::V128(lhs), Val::V128(rhs)], &mut results)
+        .unwrap();
+    assert_eq!(results[0].v128(), Some(rhs));
+    assert_eq!(results[1].v128(), Some(lhs));
+    assert_eq!(results[2].v128(), Some(sum));
+    let typed_func = func
+        .typed::<(V128, V128), (V128, V128, V128)>(&store)
+        .unwrap();
+    assert_eq!(
+        typed_func.call(&mut store, (lhs, rhs)).unwrap(),
+        (rhs, lhs, sum)
+    );
+}
+
 #[test]
 fn dynamic_type_check_works() {
     let mut store = test_setup();
@@ -520,3 +663,71 @@ fn static_type_check_works() {
         ErrorKind::Func(FuncError::MismatchingResultType)
     );
 }
+
+#[test]
+fn func_info_works() {
+    let wasm = r#"
+        (module
+            (import "env" "host" (func $host))
+            (func $named (export "named"))
+            (func (export "unnamed"))
+        )
+    "#;
+    let mut store = test_setup();
+    let module = Module::new(store.engine(), wasm).unwrap();
+    let mut linker = <Linker<()>>::new(store.engine());
+    linker.func_wrap("env", "host", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let named = instance
+        .get_func(&store, "named")
+        .unwrap()
+        .info(&store)
+        .unwrap();
+    assert_eq!(named.index(), 1);
+    assert_eq!(named.name(), Some("named"));
+    assert_eq!(named.module().exports().count(), 2);
+    let unnamed = instance
+        .get_func(&store, "unnamed")
+        .unwrap()
+        .info(&store)
+        .unwrap();
+    assert_eq!(unnamed.index(), 2);
+    assert_eq!(unnamed.name(), None);
+    let host = Func::wrap(&mut store, || {});
+    assert!(host.info(&store).is_none());
+}
+
+#[test]
+fn wrap_local_works() {
+    let mut store = test_setup();
+    let counter = Rc::new(Cell::new(0));
+    let func = Func::wrap_local(&mut store, {
+        let counter = counter.clone();
+        move |delta: i32| {
+            counter.set(counter.get() + delta);
+            counter.get()
+        }
+    });
+    let typed_func = func.typed::<i32, i32>(&store).unwrap();
+    assert_eq!(typed_func.call(&mut store, 5).unwrap(), 5);
+    assert_eq!(typed_func.call(&mut store, 2).unwrap(), 7);
+    assert_eq!(counter.get(), 7);
+}
+
+#[test]
+fn wrap_local_foreign_thread_fails() {
+    let mut store = test_setup();
+    let counter = Rc::new(Cell::new(0));
+    let func = Func::wrap_local(&mut store, move || counter.set(counter.get() + 1));
+    let error = thread::spawn(move || func.call(&mut store, &[], &mut []).unwrap_err())
+        .join()
+        .unwrap();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Func(FuncError::CalledFromForeignThread)
+    );
+}
@@ -0,0 +1,228 @@
+//! Tests to check if async host functions defined via `Linker::func_wrap_async` work as intended.
+
+use core::{
+    future::Future,
+    pin::pin,
+    ptr,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    time::Duration,
+};
+use wasmi::{core::TrapCode, Caller, Error, Instance, InstancePre, Linker, Module, Store, Val};
+
+/// Returns a [`Waker`] that does nothing when woken.
+fn noop_waker() -> Waker {
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+    // Safety: the vtable functions of the noop waker do nothing.
+    unsafe { Waker::from_raw(noop_raw_waker()) }
+}
+
+/// Polls `future` to completion on the current thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// A future that is pending exactly once before it resolves.
+#[derive(Default)]
+struct YieldOnce {
+    yielded: bool,
+}
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: core::pin::Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        Poll::Pending
+    }
+}
+
+/// Instantiates the test module with an async `host.double` import.
+///
+/// The instance is yet to run its `start` function if `with_start` is `true`.
+fn test_setup(with_start: bool) -> (Store<u32>, InstancePre) {
+    let start = if with_start { "(start $init)" } else { "" };
+    let wasm = format!(
+        r#"
+        (module
+            (import "host" "double" (func $double (param i32) (result i32)))
+            (import "host" "sleep" (func $sleep (param i32)))
+            (global $g (export "g") (mut i32) (i32.const 0))
+            (func $init
+                (global.set $g (call $double (i32.const 21)))
+            )
+            (func (export "run") (param i32) (result i32)
+                (i32.add (call $double (local.get 0)) (i32.const 1))
+            )
+            (func (export "sleep") (param i32) (result i32)
+                (call $sleep (local.get 0))
+                (i32.const 1)
+            )
+            (func (export "spin")
+                (loop $continue (br $continue))
+            )
+            (export "double" (func $double))
+            {start}
+        )
+    "#
+    );
+    let mut store = <Store<u32>>::default();
+    let module = Module::new(store.engine(), wasm).unwrap();
+    let mut linker = <Linker<u32>>::new(store.engine());
+    linker
+        .func_wrap_async("host", "double", |mut caller: Caller<u32>, input: i32| {
+            *caller.data_mut() += 1;
+            async move {
+                YieldOnce::default().await;
+                if input < 0 {
+                    return Err(Error::new("negative input"));
+                }
+                Ok(input * 2)
+            }
+        })
+        .unwrap();
+    linker
+        .func_wrap_async(
+            "host",
+            "sleep",
+            |_caller: Caller<u32>, millis: i32| async move {
+                std::thread::sleep(Duration::from_millis(millis as u64));
+                Ok(())
+            },
+        )
+        .unwrap();
+    let pre = linker.instantiate(&mut store, &module).unwrap();
+    (store, pre)
+}
+
+fn instantiate(with_start: bool) -> (Store<u32>, Instance) {
+    let (mut store, pre) = test_setup(with_start);
+    let instance = block_on(pre.start_async(&mut store)).unwrap();
+    (store, instance)
+}
+
+#[test]
+fn call_async_works() {
+    let (mut store, instance) = instantiate(false);
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    assert_eq!(block_on(run.call_async(&mut store, 5)).unwrap(), 11);
+    assert_eq!(block_on(run.call_async(&mut store, 10)).unwrap(), 21);
+    assert_eq!(*store.data(), 2);
+}
+
+#[test]
+fn call_async_root_host_func_works() {
+    let (mut store, instance) = instantiate(false);
+    let double = instance.get_func(&store, "double").unwrap();
+    let mut results = [Val::I32(0)];
+    block_on(double.call_async(&mut store, &[Val::I32(4)], &mut results)).unwrap();
+    assert_eq!(results[0].i32(), Some(8));
+}
+
+#[test]
+fn call_async_propagates_errors() {
+    let (mut store, instance) = instantiate(false);
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    let error = block_on(run.call_async(&mut store, -1)).unwrap_err();
+    assert_eq!(error.to_string(), "negative input");
+}
+
+#[test]
+fn sync_call_fails() {
+    let (mut store, instance) = instantiate(false);
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    assert!(run.call(&mut store, 5).is_err());
+}
+
+#[test]
+fn start_async_works() {
+    let (store, instance) = instantiate(true);
+    let g = instance.get_global(&store, "g").unwrap();
+    assert_eq!(g.get(&store).i32(), Some(42));
+    assert_eq!(*store.data(), 1);
+}
+
+#[test]
+fn sync_start_fails() {
+    let (mut store, pre) = test_setup(true);
+    assert!(pre.start(&mut store).is_err());
+    assert_eq!(*store.data(), 0);
+}
+
+#[test]
+fn sync_start_without_start_fn_works() {
+    let (mut store, pre) = test_setup(false);
+    assert!(pre.start(&mut store).is_ok());
+}
+
+/// Asserts that `error` is a [`TrapCode::DeadlineExceeded`] trap.
+fn assert_deadline_exceeded(error: Error) {
+    assert_eq!(error.as_trap_code(), Some(TrapCode::DeadlineExceeded));
+}
+
+#[test]
+fn call_async_with_timeout_works() {
+    let (mut store, instance) = instantiate(false);
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    let timeout = Duration::from_secs(60);
+    let result = block_on(run.call_async_with_timeout(&mut store, 5, timeout));
+    assert_eq!(result.unwrap(), 11);
+    let sleep = instance.get_func(&store, "sleep").unwrap();
+    let mut results = [Val::I32(0)];
+    block_on(sleep.call_async_with_timeout(&mut store, &[Val::I32(0)], &mut results, timeout))
+        .unwrap();
+    assert_eq!(results[0].i32(), Some(1));
+}
+
+#[test]
+fn call_async_with_timeout_cancels_wasm() {
+    let (mut store, instance) = instantiate(false);
+    let spin = instance.get_typed_func::<(), ()>(&store, "spin").unwrap();
+    let timeout = Duration::from_millis(10);
+    let error = block_on(spin.call_async_with_timeout(&mut store, (), timeout)).unwrap_err();
+    assert_deadline_exceeded(error);
+    // The deadline only applies to the timed call.
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    assert_eq!(block_on(run.call_async(&mut store, 5)).unwrap(), 11);
+}
+
+#[test]
+fn call_async_with_timeout_cancels_after_host_future() {
+    let (mut store, instance) = instantiate(false);
+    let sleep = instance
+        .get_typed_func::<i32, i32>(&store, "sleep")
+        .unwrap();
+    let timeout = Duration::from_millis(5);
+    let error = block_on(sleep.call_async_with_timeout(&mut store, 50, timeout)).unwrap_err();
+    assert_deadline_exceeded(error);
+}
+
+#[test]
+fn dropped_timed_call_removes_deadline() {
+    let (mut store, instance) = instantiate(false);
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(run.call_async_with_timeout(&mut store, 5, Duration::ZERO));
+        // Note: the call is pending on the async `host.double` function.
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+    }
+    assert_eq!(block_on(run.call_async(&mut store, 10)).unwrap(), 21);
+}
@@ -0,0 +1,78 @@
+//! Tests to check if `Store::global_hook` works as intended.
+
+use wasmi::{Error, Instance, Linker, Module, Store, Val};
+
+/// The values written to watched global variables observed by the global hook.
+type Writes = Vec<i32>;
+
+fn test_setup() -> (Store<Writes>, Instance) {
+    let wasm = r#"
+        (module
+            (global $g (export "g") (mut i32) (i32.const 0))
+            (global $h (export "h") (mut i32) (i32.const 0))
+            (func (export "run") (param i32)
+                (global.set $g (local.get 0))
+                (global.set $h (i32.add (local.get 0) (i32.const 100)))
+            )
+        )
+    "#;
+    let mut store = <Store<Writes>>::default();
+    let module = Module::new(store.engine(), wasm).unwrap();
+    let instance = <Linker<Writes>>::new(store.engine())
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    store.global_hook(|writes, _global, value| {
+        writes.push(value.i32().unwrap());
+        Ok(())
+    });
+    (store, instance)
+}
+
+fn run(store: &mut Store<Writes>, instance: &Instance, input: i32) -> Result<(), Error> {
+    instance
+        .get_typed_func::<i32, ()>(&mut *store, "run")
+        .unwrap()
+        .call(store, input)
+}
+
+#[test]
+fn unwatched_globals_are_not_observed() {
+    let (mut store, instance) = test_setup();
+    run(&mut store, &instance, 1).unwrap();
+    assert!(store.data().is_empty());
+}
+
+#[test]
+fn watched_globals_are_observed() {
+    let (mut store, instance) = test_setup();
+    let g = instance.get_global(&store, "g").unwrap();
+    let h = instance.get_global(&store, "h").unwrap();
+    g.watch(&mut store, true);
+    run(&mut store, &instance, 1).unwrap();
+    assert_eq!(store.data(), &[1]);
+    h.watch(&mut store, true);
+    run(&mut store, &instance, 2).unwrap();
+    assert_eq!(store.data(), &[1, 2, 102]);
+    // Writes by the host are never observed.
+    h.set(&mut store, Val::I32(3)).unwrap();
+    g.watch(&mut store, false);
+    run(&mut store, &instance, 4).unwrap();
+    assert_eq!(store.data(), &[1, 2, 102, 104]);
+}
+
+#[test]
+fn global_hook_error_aborts_execution() {
+    let (mut store, instance) = test_setup();
+    let g = instance.get_global(&store, "g").unwrap();
+    let h = instance.get_global(&store, "h").unwrap();
+    g.watch(&mut store, true);
+    h.watch(&mut store, true);
+    store.global_hook(|_, _, _| Err(Error::new("global hook failed")));
+    let error = run(&mut store, &instance, 1).unwrap_err();
+    assert_eq!(error.to_string(), "global hook failed");
+    // The global variable has been written before the global hook was invoked.
+    assert_eq!(g.get(&store).i32(), Some(1));
+    assert_eq!(h.get(&store).i32(), Some(0));
+}
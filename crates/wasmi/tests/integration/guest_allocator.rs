@@ -0,0 +1,144 @@
+//! Tests to check if the guest allocator helpers work as intended.
+
+use wasmi::{
+    errors::{ErrorKind, MemoryError},
+    Engine,
+    GuestAllocator,
+    Instance,
+    Linker,
+    Store,
+};
+
+/// A bump allocator guest exporting `malloc` and `free` that counts freed buffers.
+const MALLOC: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (global $next (mut i32) (i32.const 16))
+        (global $freed (export "freed") (mut i32) (i32.const 0))
+        (func (export "malloc") (param $size i32) (result i32)
+            (global.get $next)
+            (global.set $next (i32.add (global.get $next) (local.get $size)))
+        )
+        (func (export "free") (param i32)
+            (global.set $freed (i32.add (global.get $freed) (i32.const 1)))
+        )
+        (func (export "sum") (param $ptr i32) (param $len i32) (result i32)
+            (local $sum i32)
+            (block
+                (loop
+                    (br_if 1 (i32.eqz (local.get $len)))
+                    (local.set $sum (i32.add (local.get $sum) (i32.load8_u (local.get $ptr))))
+                    (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+                    (local.set $len (i32.sub (local.get $len) (i32.const 1)))
+                    (br 0)
+                )
+            )
+            (local.get $sum)
+        )
+    )
+"#;
+
+/// A guest exporting canonical ABI allocator functions that allocate at the requested
+/// alignment as address and record the size of freed buffers.
+const CANONICAL_ABI: &str = r#"
+    (module
+        (memory 1)
+        (global $freed (export "freed") (mut i32) (i32.const 0))
+        (func (export "canonical_abi_realloc") (param i32 i32 i32 i32) (result i32)
+            (local.get 2)
+        )
+        (func (export "canonical_abi_free") (param i32 i32 i32)
+            (global.set $freed (local.get 1))
+        )
+    )
+"#;
+
+/// Instantiates the `wasm` guest.
+fn setup(wasm: &str) -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let module = wasmi::Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Returns the number of buffers freed by the guest.
+fn freed(store: &Store<()>, instance: Instance) -> i32 {
+    instance
+        .get_global(store, "freed")
+        .unwrap()
+        .get(store)
+        .i32()
+        .unwrap()
+}
+
+#[test]
+fn guest_ptrs_are_freed_when_dropped() {
+    let (mut store, instance) = setup(MALLOC);
+    let sum = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "sum")
+        .unwrap();
+    let allocator = GuestAllocator::new(&store, instance).unwrap();
+    let mut guest = allocator.alloc_bytes(&mut store, &[1, 2, 3]).unwrap();
+    assert_eq!(guest.ptr(), 16);
+    assert_eq!(guest.as_bytes(), [1, 2, 3]);
+    guest.write(1, &[20, 30]).unwrap();
+    assert!(guest.write(2, &[0, 0]).is_err());
+    let mut buffer = [0; 2];
+    guest.read(1, &mut buffer).unwrap();
+    assert_eq!(buffer, [20, 30]);
+    let (ptr, len) = (guest.ptr() as i32, guest.len() as i32);
+    assert_eq!(sum.call(&mut guest, (ptr, len)).unwrap(), 51);
+    drop(guest);
+    assert_eq!(freed(&store, instance), 1);
+    allocator
+        .alloc_str(&mut store, "abc")
+        .unwrap()
+        .free()
+        .unwrap();
+    assert_eq!(freed(&store, instance), 2);
+    let ptr = allocator.alloc_str(&mut store, "abc").unwrap().into_raw();
+    assert_eq!(ptr, 22);
+    assert_eq!(freed(&store, instance), 2);
+}
+
+#[test]
+fn canonical_abi_allocator_works() {
+    let (mut store, instance) = setup(CANONICAL_ABI);
+    let allocator = GuestAllocator::new(&store, instance).unwrap();
+    let guest = allocator.alloc(&mut store, 12, 8).unwrap();
+    assert_eq!(guest.ptr(), 8);
+    assert_eq!(guest.len(), 12);
+    drop(guest);
+    assert_eq!(freed(&store, instance), 12);
+}
+
+#[test]
+fn failed_allocations_are_errors() {
+    let (mut store, instance) = setup(CANONICAL_ABI);
+    let allocator = GuestAllocator::new(&store, instance).unwrap();
+    let error = allocator.alloc(&mut store, 4, 0).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Memory(MemoryError::GuestAllocationFailed)
+    ));
+    assert!(allocator.alloc(&mut store, 0x1_0000, 8).is_err());
+    assert_eq!(freed(&store, instance), 0);
+}
+
+#[test]
+fn missing_allocator_is_an_error() {
+    let (store, instance) = setup("(module (memory 1))");
+    assert!(GuestAllocator::new(&store, instance).is_err());
+    let (store, instance) =
+        setup(r#"(module (func (export "malloc") (param i32) (result i32) (i32.const 0)))"#);
+    let error = GuestAllocator::new(&store, instance).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Memory(MemoryError::MissingDefaultMemory)
+    ));
+}
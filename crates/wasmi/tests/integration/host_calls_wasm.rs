@@ -1,7 +1,7 @@
 //! Test to assert that host functions that call back into
 //! Wasm works correctly.
 
-use wasmi::{Caller, Engine, Extern, Func, Linker, Module, Store};
+use wasmi::{errors::MemoryError, Caller, Engine, Extern, Func, Linker, Module, Store};
 
 fn test_setup() -> (Store<()>, Linker<()>) {
     let engine = Engine::default();
@@ -54,3 +54,65 @@ fn host_calls_wasm() {
     let result = wasm_fn.call(&mut store, input).unwrap();
     assert_eq!(result, expected);
 }
+
+#[test]
+fn host_uses_caller_accessors() {
+    let (mut store, mut linker) = test_setup();
+    let host_fn = Func::wrap(&mut store, |mut caller: Caller<()>, ptr: u32| -> i32 {
+        assert!(caller.get_memory("memory").is_some());
+        assert!(caller.get_memory("square").is_none());
+        let square = caller.get_typed_func::<i32, i32>("square").unwrap();
+        let mut input = [0x00_u8; 4];
+        caller.read(ptr as usize, &mut input).unwrap();
+        let output = square.call(&mut caller, i32::from_le_bytes(input)).unwrap();
+        caller
+            .write(ptr as usize + 4, &output.to_le_bytes())
+            .unwrap();
+        output
+    });
+    linker.define("env", "host_fn", host_fn).unwrap();
+    let wasm = r#"
+        (module
+            (import "env" "host_fn" (func $host_fn (param i32) (result i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 8) "\05\00\00\00")
+            (func (export "wasm_fn") (param i32) (result i32)
+                (call $host_fn (local.get 0))
+            )
+            (func (export "square") (param i32) (result i32)
+                (i32.mul
+                    (local.get 0)
+                    (local.get 0)
+                )
+            )
+        )
+        "#;
+    let module = Module::new(store.engine(), wasm).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let wasm_fn = instance
+        .get_typed_func::<u32, i32>(&store, "wasm_fn")
+        .unwrap();
+    assert_eq!(wasm_fn.call(&mut store, 8).unwrap(), 25);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let mut output = [0x00_u8; 4];
+    memory.read(&store, 12, &mut output).unwrap();
+    assert_eq!(i32::from_le_bytes(output), 25);
+}
+
+#[test]
+fn host_caller_without_instance() {
+    let (mut store, _linker) = test_setup();
+    let host_fn = Func::wrap(&mut store, |mut caller: Caller<()>| {
+        assert!(caller.default_memory().is_none());
+        assert!(caller.get_typed_func::<(), ()>("f").is_err());
+        assert!(matches!(
+            caller.write(0, &[0x00]),
+            Err(MemoryError::MissingDefaultMemory)
+        ));
+    });
+    host_fn.call(&mut store, &[], &mut []).unwrap();
+}
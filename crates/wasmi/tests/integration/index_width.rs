@@ -0,0 +1,76 @@
+//! Tests to check if narrow index widths cap compiled Wasm functions as intended.
+
+use wasmi::{Config, Engine, IndexWidth, Linker, Module, Store};
+
+/// Returns an [`Engine`] using `width` for compiled Wasm functions.
+fn engine(width: IndexWidth) -> Engine {
+    let mut config = Config::default();
+    config.index_width(width);
+    Engine::new(&config)
+}
+
+/// Returns a Wasm module with a function body repeating `body` `n` times.
+fn repeat(locals: usize, body: &str, n: usize) -> String {
+    let locals = "i32 ".repeat(locals);
+    let body = body.repeat(n);
+    format!(
+        r#"
+        (module
+            (global $g (mut i32) (i32.const 0))
+            (func (export "f") (param i32) (result i32)
+                (local {locals})
+                {body}
+                (local.get 0)
+            )
+        )
+        "#
+    )
+}
+
+#[test]
+fn small_functions_work() {
+    let engine = engine(IndexWidth::Narrow);
+    let wasm = repeat(
+        100,
+        "(global.set $g (i32.add (local.get 0) (i32.const 1)))",
+        8,
+    );
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let f = instance.get_typed_func::<i32, i32>(&store, "f").unwrap();
+    assert_eq!(f.call(&mut store, 41).unwrap(), 41);
+}
+
+#[test]
+fn too_many_registers_fail() {
+    let wasm = repeat(200, "", 0);
+    assert!(Module::new(&engine(IndexWidth::Narrow), &wasm).is_err());
+    assert!(Module::new(&engine(IndexWidth::Wide), &wasm).is_ok());
+}
+
+#[test]
+fn too_many_consts_fail() {
+    let wasm = (0..200)
+        .map(|n| {
+            format!(
+                "(global.set $g (i32.add (local.get 0) (i32.const {})))",
+                (n + 1) * 100_000
+            )
+        })
+        .collect::<String>();
+    let wasm = repeat(0, &wasm, 1);
+    assert!(Module::new(&engine(IndexWidth::Narrow), &wasm).is_err());
+    assert!(Module::new(&engine(IndexWidth::Wide), &wasm).is_ok());
+}
+
+#[test]
+fn too_many_instructions_fail() {
+    let wasm = repeat(0, "(global.set $g (local.get 0))", 40_000);
+    assert!(Module::new(&engine(IndexWidth::Narrow), &wasm).is_err());
+    assert!(Module::new(&engine(IndexWidth::Wide), &wasm).is_ok());
+}
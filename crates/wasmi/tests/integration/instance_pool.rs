@@ -0,0 +1,88 @@
+//! Tests to check if the `InstancePool` works as intended.
+
+use wasmi::{Caller, Engine, InstancePool, Linker, Module};
+
+/// A Wasm module with state that is initialized by its `start` function.
+const WASM: &str = r#"
+    (module
+        (import "host" "started" (func $started))
+        (global $counter (mut i32) (i32.const 0))
+        (func $start
+            (call $started)
+            (global.set $counter (i32.const 10))
+        )
+        (func (export "inc") (result i32)
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+            (global.get $counter)
+        )
+        (start $start)
+    )
+"#;
+
+/// Creates an [`InstancePool`] of [`WASM`] with `capacity`.
+///
+/// The host state counts how often the `start` function of an instance ran.
+fn pool(capacity: usize) -> InstancePool<u32> {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut linker = <Linker<u32>>::new(&engine);
+    linker
+        .func_wrap("host", "started", |mut caller: Caller<u32>| {
+            *caller.data_mut() += 1;
+        })
+        .unwrap();
+    InstancePool::new(linker, &module, capacity, || 0).unwrap()
+}
+
+#[test]
+fn pool_is_prewarmed() {
+    let mut pool = pool(3);
+    assert_eq!(pool.capacity(), 3);
+    assert_eq!(pool.len_ready(), 3);
+    let mut pooled = pool.acquire().unwrap();
+    assert_eq!(pool.len_ready(), 2);
+    assert_eq!(*pooled.store().data(), 1);
+    let inc = pooled
+        .instance()
+        .get_typed_func::<(), i32>(&pooled, "inc")
+        .unwrap();
+    assert_eq!(inc.call(&mut pooled, ()).unwrap(), 11);
+    assert_eq!(inc.call(&mut pooled, ()).unwrap(), 12);
+}
+
+#[test]
+fn released_instances_are_reset() {
+    let mut pool = pool(1);
+    for _ in 0..3 {
+        let mut pooled = pool.acquire().unwrap();
+        let inc = pooled
+            .instance()
+            .get_typed_func::<(), i32>(&pooled, "inc")
+            .unwrap();
+        assert_eq!(inc.call(&mut pooled, ()).unwrap(), 11);
+        pool.release(pooled).unwrap();
+        assert_eq!(pool.len_ready(), 1);
+    }
+    let metrics = pool.metrics();
+    assert_eq!(metrics.hits(), 3);
+    assert_eq!(metrics.misses(), 0);
+    assert_eq!(metrics.recycled(), 3);
+    assert_eq!(metrics.hit_rate(), 1.0);
+}
+
+#[test]
+fn empty_pool_instantiates_on_demand() {
+    let mut pool = pool(1);
+    let first = pool.acquire().unwrap();
+    let second = pool.acquire().unwrap();
+    assert_eq!(pool.metrics().hits(), 1);
+    assert_eq!(pool.metrics().misses(), 1);
+    assert_eq!(pool.metrics().hit_rate(), 0.5);
+    pool.release(first).unwrap();
+    // The pool is already full again and thus drops the second instance.
+    pool.release(second).unwrap();
+    assert_eq!(pool.len_ready(), 1);
+    assert_eq!(pool.metrics().recycled(), 1);
+    let (store, _instance) = pool.acquire().unwrap().into_parts();
+    assert_eq!(*store.data(), 1);
+}
@@ -0,0 +1,100 @@
+//! Tests to check if the instantiation hook works as intended.
+
+use wasmi::{Engine, Error, Global, InstantiationHook, Linker, Module, Mutability, Store, Val};
+
+/// A Wasm module with imports, a memory, segments and a `start` function.
+const WASM: &str = r#"
+    (module
+        (import "env" "f" (func $f))
+        (import "env" "g" (global i32))
+        (memory 1)
+        (table 4 funcref)
+        (elem (i32.const 1) $f $start)
+        (data (i32.const 8) "wasmi")
+        (data (i32.const 32) "hello, world")
+        (func $start)
+        (start $start)
+    )
+"#;
+
+/// Instantiates [`WASM`] with `hook` set as instantiation hook.
+///
+/// Returns the steps passed to the hook and the result of the instantiation.
+fn instantiate(
+    hook: impl Fn(InstantiationHook) -> Result<(), Error> + Send + Sync + 'static,
+) -> (Vec<InstantiationHook>, Result<(), Error>) {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, Vec::new());
+    store.instantiation_hook(move |steps, step| {
+        steps.push(step);
+        hook(step)
+    });
+    let mut linker = <Linker<Vec<InstantiationHook>>>::new(&engine);
+    linker.func_wrap("env", "f", || {}).unwrap();
+    let global = Global::new(&mut store, Val::I32(0), Mutability::Const);
+    linker.define("env", "g", global).unwrap();
+    let result = linker
+        .instantiate(&mut store, &module)
+        .and_then(|pre| pre.start(&mut store))
+        .map(|_| ());
+    (store.into_data(), result)
+}
+
+#[test]
+fn steps_are_reported_in_order() {
+    let (steps, result) = instantiate(|_| Ok(()));
+    result.unwrap();
+    assert_eq!(
+        steps,
+        [
+            InstantiationHook::ImportsResolved { len: 2 },
+            InstantiationHook::MemoriesAllocated { len: 1 },
+            InstantiationHook::ElementSegment {
+                table_index: 0,
+                offset: 1,
+                len: 2,
+            },
+            InstantiationHook::DataSegment {
+                memory_index: 0,
+                offset: 8,
+                len: 5,
+            },
+            InstantiationHook::DataSegment {
+                memory_index: 0,
+                offset: 32,
+                len: 12,
+            },
+            InstantiationHook::StartFunction { func_index: 1 },
+        ]
+    );
+}
+
+#[test]
+fn hook_can_forbid_start_functions() {
+    let (steps, result) = instantiate(|step| match step {
+        InstantiationHook::StartFunction { .. } => Err(Error::new("start functions are forbidden")),
+        _ => Ok(()),
+    });
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "start functions are forbidden"
+    );
+    assert_eq!(steps.len(), 6);
+}
+
+#[test]
+fn hook_can_audit_segment_sizes() {
+    let (steps, result) = instantiate(|step| match step {
+        InstantiationHook::DataSegment { len, .. } if len > 8 => {
+            Err(Error::new("data segment too large"))
+        }
+        _ => Ok(()),
+    });
+    assert_eq!(result.unwrap_err().to_string(), "data segment too large");
+    // Note: the start function step is never reached.
+    assert!(matches!(
+        steps.last(),
+        Some(InstantiationHook::DataSegment { len: 12, .. })
+    ));
+}
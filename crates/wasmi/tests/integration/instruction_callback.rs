@@ -0,0 +1,88 @@
+//! Tests to check if the instruction callback of the `Store` works as intended.
+
+use wasmi::{Engine, Error, Instance, Linker, Module, Store};
+
+/// Setup [`Store`] and [`Instance`] for the instruction callback tests.
+fn test_setup() -> (Store<u64>, Instance) {
+    let wasm = r#"
+        (module
+            (func (export "loop")
+                (loop (br 0))
+            )
+            (func (export "count") (param $n i32) (result i32)
+                (local $i i32)
+                (block
+                    (loop
+                        (br_if 1 (i32.ge_u (local.get $i) (local.get $n)))
+                        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                        (br 0)
+                    )
+                )
+                (local.get $i)
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, 0_u64);
+    let instance = <Linker<u64>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn callback_is_invoked_periodically() {
+    let (mut store, instance) = test_setup();
+    store.instruction_callback(100, |calls| {
+        *calls += 1;
+        Ok(())
+    });
+    let func = instance
+        .get_typed_func::<i32, i32>(&store, "count")
+        .unwrap();
+    // Each loop iteration executes multiple instructions.
+    assert_eq!(func.call(&mut store, 10_000).unwrap(), 10_000);
+    let calls = *store.data();
+    assert!(calls >= 100, "too few instruction callback calls: {calls}");
+    // Removing the callback stops its invocations.
+    store.remove_instruction_callback();
+    assert_eq!(func.call(&mut store, 10_000).unwrap(), 10_000);
+    assert_eq!(*store.data(), calls);
+}
+
+#[test]
+fn callback_error_aborts_execution() {
+    let (mut store, instance) = test_setup();
+    store.instruction_callback(1_000, |calls| {
+        *calls += 1;
+        if *calls == 10 {
+            return Err(Error::new("watchdog expired"));
+        }
+        Ok(())
+    });
+    let func = instance.get_typed_func::<(), ()>(&store, "loop").unwrap();
+    let error = func.call(&mut store, ()).unwrap_err();
+    assert_eq!(error.as_message(), Some("watchdog expired"));
+    assert_eq!(*store.data(), 10);
+}
+
+#[test]
+fn callback_with_instruction_limit() {
+    let (mut store, instance) = test_setup();
+    store.instruction_callback(100, |calls| {
+        *calls += 1;
+        Ok(())
+    });
+    store.set_instruction_limit(Some(1_000));
+    let func = instance.get_typed_func::<(), ()>(&store, "loop").unwrap();
+    let error = func.call(&mut store, ()).unwrap_err();
+    assert!(error.as_trap_code().is_some());
+    let calls = *store.data();
+    assert!(
+        (5..=10).contains(&calls),
+        "unexpected instruction callback calls: {calls}"
+    );
+}
@@ -0,0 +1,120 @@
+//! Tests to check if the instruction limit of the `Store` works as intended.
+
+use wasmi::{core::TrapCode, Engine, Error, Instance, Linker, Module, Store};
+
+/// Setup [`Store`] and [`Instance`] for the instruction limit tests.
+fn test_setup() -> (Store<()>, Instance) {
+    let wasm = r#"
+        (module
+            (func (export "loop")
+                (loop (br 0))
+            )
+            (func $recurse (export "recurse")
+                (call $recurse)
+            )
+            (func (export "count") (param $n i32) (result i32)
+                (local $i i32)
+                (block
+                    (loop
+                        (br_if 1 (i32.ge_u (local.get $i) (local.get $n)))
+                        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                        (br 0)
+                    )
+                )
+                (local.get $i)
+            )
+            (func (export "switch") (param $n i32) (result i32)
+                (local $acc i32)
+                (loop $dispatch
+                    (block $case2
+                        (block $case1
+                            (block $case0
+                                (br_table $case0 $case1 $case2 $dispatch (local.get $n))
+                            )
+                            (return (local.get $acc))
+                        )
+                        (local.set $acc (i32.add (local.get $acc) (i32.const 1)))
+                        (local.set $n (i32.const 0))
+                        (br $dispatch)
+                    )
+                    (local.set $acc (i32.add (local.get $acc) (i32.const 10)))
+                    (local.set $n (i32.const 1))
+                    (br $dispatch)
+                )
+                (unreachable)
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Asserts that the call trapped with [`TrapCode::InstructionLimitExceeded`].
+fn assert_limit_exceeded<T>(call_result: Result<T, Error>) {
+    let Err(error) = call_result else {
+        panic!("expected the call to exceed its instruction limit")
+    };
+    assert_eq!(
+        error.as_trap_code(),
+        Some(TrapCode::InstructionLimitExceeded)
+    );
+}
+
+#[test]
+fn loop_exceeds_limit() {
+    let (mut store, instance) = test_setup();
+    store.set_instruction_limit(Some(1_000));
+    let func = instance.get_typed_func::<(), ()>(&store, "loop").unwrap();
+    assert_limit_exceeded(func.call(&mut store, ()));
+}
+
+#[test]
+fn recursion_exceeds_limit() {
+    let (mut store, instance) = test_setup();
+    store.set_instruction_limit(Some(100));
+    let func = instance
+        .get_typed_func::<(), ()>(&store, "recurse")
+        .unwrap();
+    assert_limit_exceeded(func.call(&mut store, ()));
+}
+
+#[test]
+fn branch_table_loop_exceeds_limit() {
+    let (mut store, instance) = test_setup();
+    store.set_instruction_limit(Some(1_000));
+    let func = instance
+        .get_typed_func::<i32, i32>(&store, "switch")
+        .unwrap();
+    assert_eq!(func.call(&mut store, 0).unwrap(), 0);
+    assert_eq!(func.call(&mut store, 1).unwrap(), 1);
+    assert_eq!(func.call(&mut store, 2).unwrap(), 11);
+    // The default target of the `br_table` loops back to itself.
+    assert_limit_exceeded(func.call(&mut store, 3));
+    assert_limit_exceeded(func.call(&mut store, -1));
+}
+
+#[test]
+fn limit_applies_per_call() {
+    let (mut store, instance) = test_setup();
+    assert_eq!(store.instruction_limit(), None);
+    let func = instance
+        .get_typed_func::<i32, i32>(&store, "count")
+        .unwrap();
+    store.set_instruction_limit(Some(1_000));
+    assert_eq!(store.instruction_limit(), Some(1_000));
+    // Each call executes well below the limit of 1000 instructions.
+    for _ in 0..10 {
+        assert_eq!(func.call(&mut store, 100).unwrap(), 100);
+    }
+    assert_limit_exceeded(func.call(&mut store, 1_000));
+    // Removing the limit allows the execution to run to completion.
+    store.set_instruction_limit(None);
+    assert_eq!(func.call(&mut store, 1_000).unwrap(), 1_000);
+}
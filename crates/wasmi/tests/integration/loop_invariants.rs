@@ -0,0 +1,83 @@
+//! Tests to check if hoisting loop-invariant computations preserves Wasm semantics.
+
+use wasmi::{Config, Engine, Instance, Linker, Module, Store};
+
+/// The Wasm module used for the loop-invariant hoisting tests.
+const WASM: &str = r#"
+    (module
+        (memory (export "mem") 1)
+        (data (i32.const 0) "\01\00\00\00\02\00\00\00\03\00\00\00\04\00\00\00")
+        ;; Sums the 4 `i32` values at `base` for `n` times within nested loops.
+        (func (export "sum") (param $base i32) (param $n i32) (result i32)
+            (local $sum i32) (local $i i32)
+            (loop $outer
+                (local.set $i (i32.const 0))
+                (loop $inner
+                    (local.set $sum
+                        (i32.add
+                            (local.get $sum)
+                            (i32.load
+                                (i32.add
+                                    (i32.add (local.get $base) (i32.const 4))
+                                    (i32.mul (local.get $i) (i32.const 4))
+                                )
+                            )
+                        )
+                    )
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br_if $inner (i32.lt_u (local.get $i) (i32.const 3)))
+                )
+                (local.set $sum (i32.add (local.get $sum) (i32.load (i32.add (local.get $base) (i32.const 4)))))
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br_if $outer (local.get $n))
+            )
+            (local.get $sum)
+        )
+        ;; Returns the hoisted computation as the result of the loop.
+        (func (export "loop_result") (param $x i64) (param $n i32) (result i64)
+            (loop $continue (result i64)
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (i64.add (local.get $x) (i64.const -100000))
+                (br_if $continue (local.get $n))
+            )
+            (i64.add (local.get $x) (i64.const -100000))
+            (i64.add)
+        )
+    )
+"#;
+
+/// Setup [`Store`] and [`Instance`] for the loop-invariant hoisting tests.
+fn test_setup(hoist_loop_invariants: bool) -> (Store<()>, Instance) {
+    let mut config = Config::default();
+    config.hoist_loop_invariants(hoist_loop_invariants);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn hoisted_loop_invariants_work() {
+    for hoist_loop_invariants in [false, true] {
+        let (mut store, instance) = test_setup(hoist_loop_invariants);
+        let sum = instance
+            .get_typed_func::<(i32, i32), i32>(&store, "sum")
+            .unwrap();
+        assert_eq!(sum.call(&mut store, (0, 1)).unwrap(), 2 + 3 + 4 + 2);
+        assert_eq!(sum.call(&mut store, (0, 3)).unwrap(), 3 * (2 + 3 + 4 + 2));
+        assert_eq!(sum.call(&mut store, (4, 2)).unwrap(), 2 * (3 + 4 + 3));
+        let loop_result = instance
+            .get_typed_func::<(i64, i32), i64>(&store, "loop_result")
+            .unwrap();
+        assert_eq!(loop_result.call(&mut store, (200_000, 5)).unwrap(), 200_000);
+        assert_eq!(
+            loop_result.call(&mut store, (i64::MIN, 1)).unwrap(),
+            i64::MIN.wrapping_add(-100_000).wrapping_mul(2)
+        );
+    }
+}
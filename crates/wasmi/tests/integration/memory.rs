@@ -0,0 +1,61 @@
+//! Tests for the 64-bit host-side accessors of the `Memory` type.
+
+use wasmi::{
+    errors::{ErrorKind, MemoryError},
+    Memory,
+    MemoryType,
+    Store,
+};
+
+fn test_setup(ty: MemoryType) -> (Store<()>, Memory) {
+    let mut store = <Store<()>>::default();
+    let memory = Memory::new(&mut store, ty).unwrap();
+    (store, memory)
+}
+
+#[test]
+fn size_in_bytes_works() {
+    let (mut store, memory) = test_setup(MemoryType::new64(1, None).unwrap());
+    assert_eq!(memory.size_in_bytes(&store), 0x1_0000);
+    memory.grow(&mut store, 2).unwrap();
+    assert_eq!(memory.size_in_bytes(&store), 0x3_0000);
+}
+
+#[test]
+fn read64_write64_works() {
+    let (mut store, memory) = test_setup(MemoryType::new64(1, None).unwrap());
+    memory.write64(&mut store, 0xFFF0, b"hello").unwrap();
+    let mut buffer = [0x00; 5];
+    memory.read64(&store, 0xFFF0, &mut buffer).unwrap();
+    assert_eq!(&buffer, b"hello");
+    assert_eq!(memory.data64(&store, 0xFFF1, 4).unwrap(), b"ello");
+    memory.data64_mut(&mut store, 0xFFF0, 1).unwrap()[0] = b'j';
+    assert_eq!(memory.data64(&store, 0xFFF0, 5).unwrap(), b"jello");
+}
+
+#[test]
+fn out_of_bounds_access() {
+    let (mut store, memory) = test_setup(MemoryType::new(1, None).unwrap());
+    let mut buffer = [0x00; 2];
+    assert!(matches!(
+        memory.read64(&store, 0xFFFF, &mut buffer),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    assert!(matches!(
+        memory.write64(&mut store, u64::MAX, &buffer),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    assert!(matches!(
+        memory.data64(&store, 1 << 32, 0),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    assert!(memory.data64(&store, 0x1_0000, 0).unwrap().is_empty());
+}
+
+#[test]
+fn new_out_of_memory_fails() {
+    let mut store = <Store<()>>::default();
+    let ty = MemoryType::new64(1 << 47, None).unwrap();
+    let error = Memory::new(&mut store, ty).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::OutOfMemory));
+}
@@ -0,0 +1,194 @@
+//! Tests to check if read-only ranges of linear memories work as intended.
+
+use wasmi::{
+    core::TrapCode,
+    Engine,
+    Error,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Protection,
+    Store,
+};
+
+/// Setup [`Store`] and [`Instance`] for the memory protection tests.
+fn test_setup() -> (Store<()>, Instance, Memory) {
+    let wasm = r#"
+        (module
+            (memory (export "mem") 1)
+            (data $data "\01\02\03\04")
+            (func (export "store8") (param $addr i32)
+                (i32.store8 (local.get $addr) (i32.const 0xFF))
+            )
+            (func (export "store32") (param $addr i32)
+                (i32.store (local.get $addr) (i32.const -1))
+            )
+            (func (export "store32_offset") (param $addr i32)
+                (i32.store offset=4 (local.get $addr) (i32.const -1))
+            )
+            (func (export "store64_at")
+                (i64.store (i32.const 16) (i64.const -1))
+            )
+            (func (export "fill") (param $dst i32) (param $len i32)
+                (memory.fill (local.get $dst) (i32.const 0xFF) (local.get $len))
+            )
+            (func (export "copy") (param $dst i32) (param $src i32) (param $len i32)
+                (memory.copy (local.get $dst) (local.get $src) (local.get $len))
+            )
+            (func (export "init") (param $dst i32) (param $len i32)
+                (memory.init $data (local.get $dst) (i32.const 0) (local.get $len))
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    memory
+        .protect(&mut store, 16..24, Protection::ReadOnly)
+        .unwrap();
+    (store, instance, memory)
+}
+
+/// Calls the exported function `name` of the `instance` with `params`.
+fn call<Params>(
+    store: &mut Store<()>,
+    instance: Instance,
+    name: &str,
+    params: Params,
+) -> Result<(), Error>
+where
+    Params: wasmi::WasmParams,
+{
+    instance
+        .get_typed_func::<Params, ()>(&*store, name)
+        .unwrap()
+        .call(store, params)
+}
+
+/// Asserts that the call trapped with [`TrapCode::MemoryReadOnly`].
+fn assert_read_only(call_result: Result<(), Error>) {
+    let error = call_result.unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryReadOnly));
+}
+
+#[test]
+fn store_to_read_only_range_traps() {
+    let (mut store, instance, memory) = test_setup();
+    call(&mut store, instance, "store8", 15).unwrap();
+    call(&mut store, instance, "store8", 24).unwrap();
+    assert_read_only(call(&mut store, instance, "store8", 16));
+    assert_read_only(call(&mut store, instance, "store8", 23));
+    call(&mut store, instance, "store32", 12).unwrap();
+    assert_read_only(call(&mut store, instance, "store32", 13));
+    call(&mut store, instance, "store32_offset", 8).unwrap();
+    assert_read_only(call(&mut store, instance, "store32_offset", 12));
+    assert_read_only(call(&mut store, instance, "store64_at", ()));
+    // Note: the read-only range has not been modified by the trapping stores.
+    assert_eq!(&memory.data(&store)[16..24], &[0x00; 8]);
+    assert_eq!(memory.data(&store)[24], 0xFF);
+}
+
+#[test]
+fn bulk_memory_to_read_only_range_traps() {
+    let (mut store, instance, memory) = test_setup();
+    call(&mut store, instance, "fill", (0, 16)).unwrap();
+    call(&mut store, instance, "fill", (20, 0)).unwrap();
+    assert_read_only(call(&mut store, instance, "fill", (10, 7)));
+    call(&mut store, instance, "copy", (0, 16, 8)).unwrap();
+    assert_read_only(call(&mut store, instance, "copy", (16, 0, 1)));
+    call(&mut store, instance, "init", (14, 2)).unwrap();
+    assert_read_only(call(&mut store, instance, "init", (15, 2)));
+    assert_eq!(&memory.data(&store)[16..24], &[0x00; 8]);
+}
+
+#[test]
+fn read_write_lifts_protection() {
+    let (mut store, instance, memory) = test_setup();
+    memory
+        .protect(&mut store, 18..20, Protection::ReadWrite)
+        .unwrap();
+    call(&mut store, instance, "store8", 18).unwrap();
+    call(&mut store, instance, "store8", 19).unwrap();
+    assert_read_only(call(&mut store, instance, "store8", 17));
+    assert_read_only(call(&mut store, instance, "store8", 20));
+    let len = memory.data_size(&store) as u64;
+    memory
+        .protect(&mut store, 0..len, Protection::ReadWrite)
+        .unwrap();
+    call(&mut store, instance, "store8", 17).unwrap();
+    call(&mut store, instance, "store64_at", ()).unwrap();
+}
+
+#[test]
+fn host_writes_to_read_only_range() {
+    let (mut store, _instance, memory) = test_setup();
+    memory.write(&mut store, 16, &[0x01; 8]).unwrap();
+    assert_eq!(&memory.data(&store)[16..24], &[0x01; 8]);
+    let len = memory.data_size(&store) as u64;
+    assert!(memory
+        .protect(&mut store, 0..len + 1, Protection::ReadOnly)
+        .is_err());
+}
+
+#[test]
+fn active_data_segment_to_read_only_range_fails() {
+    let wasm = r#"
+        (module
+            (import "env" "mem" (memory 1))
+            (data (i32.const 20) "\01")
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    memory
+        .protect(&mut store, 16..24, Protection::ReadOnly)
+        .unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define("env", "mem", memory).unwrap();
+    let error = linker.instantiate(&mut store, &module).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryReadOnly));
+    assert_eq!(memory.data(&store)[20], 0x00);
+}
+
+#[test]
+#[cfg(feature = "simd")]
+fn simd_store_to_read_only_range_traps() {
+    let wasm = r#"
+        (module
+            (memory (export "mem") 1)
+            (func (export "store8_lane") (param $addr i32)
+                (v128.store8_lane 0 (local.get $addr) (v128.const i64x2 -1 -1))
+            )
+            (func (export "store128") (param $addr i32)
+                (v128.store (local.get $addr) (v128.const i64x2 -1 -1))
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    memory
+        .protect(&mut store, 16..24, Protection::ReadOnly)
+        .unwrap();
+    call(&mut store, instance, "store8_lane", 15).unwrap();
+    assert_read_only(call(&mut store, instance, "store8_lane", 16));
+    call(&mut store, instance, "store128", 0).unwrap();
+    assert_read_only(call(&mut store, instance, "store128", 1));
+    assert_eq!(&memory.data(&store)[16..24], &[0x00; 8]);
+}
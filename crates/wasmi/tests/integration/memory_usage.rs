@@ -0,0 +1,93 @@
+//! Tests to check if the memory usage reported by the `Engine` works as intended.
+
+use wasmi::{CompilationMode, Config, Engine, EngineMemoryUsage, Linker, Module, Store};
+
+/// A Wasm module with a function body that is too big to be stored inline.
+const WASM: &str = r#"
+    (module
+        (func (export "sum") (param $n i32) (result i32)
+            (local $i i32)
+            (local $sum i32)
+            (block
+                (loop
+                    (br_if 1 (i32.ge_u (local.get $i) (local.get $n)))
+                    (local.set $sum (i32.add (local.get $sum) (local.get $i)))
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br 0)
+                )
+            )
+            (i32.add (local.get $sum) (i32.const 1_000_000))
+        )
+    )
+"#;
+
+/// Calls the `sum` function of a new instance of `module`.
+fn call_sum(engine: &Engine, module: &Module) {
+    let mut store = Store::new(engine, ());
+    let instance = <Linker<()>>::new(engine)
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let sum = instance.get_typed_func::<i32, i32>(&store, "sum").unwrap();
+    assert_eq!(sum.call(&mut store, 10).unwrap(), 1_000_045);
+}
+
+/// Asserts that `usage` is consistent with its total.
+fn assert_total(usage: &EngineMemoryUsage) {
+    assert_eq!(
+        usage.total(),
+        usage.compiled_code()
+            + usage.uncompiled_code()
+            + usage.const_pools()
+            + usage.type_registry()
+            + usage.caches()
+    );
+}
+
+#[test]
+fn memory_usage_of_new_engine_is_empty() {
+    let usage = Engine::default().memory_usage();
+    assert_eq!(usage, EngineMemoryUsage::default());
+    assert_eq!(usage.total(), 0);
+}
+
+#[test]
+fn memory_usage_grows_with_compiled_modules() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let usage = engine.memory_usage();
+    assert_total(&usage);
+    assert!(usage.compiled_code() > 0);
+    assert!(usage.const_pools() > 0);
+    assert!(usage.type_registry() > 0);
+    assert_eq!(usage.uncompiled_code(), 0);
+    // Note: the function type is deduplicated so only the code grows.
+    let _module2 = Module::new(&engine, WASM).unwrap();
+    let usage2 = engine.memory_usage();
+    assert_total(&usage2);
+    assert_eq!(usage2.compiled_code(), 2 * usage.compiled_code());
+    assert_eq!(usage2.const_pools(), 2 * usage.const_pools());
+    assert_eq!(usage2.type_registry(), usage.type_registry());
+    assert_eq!(usage2.caches(), 0);
+    // Note: executions cache their stacks for reuse.
+    call_sum(&engine, &module);
+    assert!(engine.memory_usage().caches() > 0);
+}
+
+#[test]
+fn memory_usage_of_lazily_compiled_functions() {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let before = engine.memory_usage();
+    assert!(before.uncompiled_code() > 0);
+    assert_eq!(before.const_pools(), 0);
+    call_sum(&engine, &module);
+    let after = engine.memory_usage();
+    assert_total(&after);
+    assert_eq!(after.uncompiled_code(), 0);
+    assert!(after.compiled_code() > before.compiled_code());
+    assert!(after.const_pools() > 0);
+}
@@ -1,9 +1,51 @@
+mod baseline_tier;
+#[cfg(feature = "branch-profile")]
+mod branch_profile;
 mod call_hook;
+mod canonical_abi;
+mod compilation_metrics;
+mod config_presets;
+mod coverage;
+mod crate_features;
+mod disassemble;
+mod dylink;
+mod error;
 mod fuel_consumption;
+mod fuel_hook;
 mod fuel_metering;
 mod func;
+mod func_async;
+mod global_hook;
+mod guest_allocator;
 mod host_call_compilation;
 mod host_call_instantiation;
 mod host_calls_wasm;
+mod index_width;
+mod instance_pool;
+mod instantiation_hook;
+mod instruction_callback;
+mod instruction_limit;
+mod loop_invariants;
+mod memory;
+mod memory_protection;
+mod memory_usage;
+mod module_hash;
+mod module_transform;
+mod precompile;
+mod reinstantiate;
+mod reproducible_translation;
 mod resource_limiter;
 mod resumable_call;
+#[cfg(feature = "serde")]
+mod serde;
+mod serialized_code;
+mod stackless;
+mod store_context;
+mod store_reset;
+mod table;
+mod table_init;
+#[cfg(feature = "to-wat")]
+mod to_wat;
+mod trace_externrefs;
+mod trace_hash;
+mod validation_error;
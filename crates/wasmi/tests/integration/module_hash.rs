@@ -0,0 +1,62 @@
+//! Tests for the stable content hash computed via [`Module::hash`].
+
+use wasmi::{Config, Engine, Module};
+
+/// The binary encoding of:
+///
+/// ```wasm
+/// (module
+///     (@custom "foo" "bar")
+///     (memory 1)
+///     (data (i32.const 0) "abc")
+/// )
+/// ```
+const WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, // header
+    0x00, 0x07, 0x03, 0x66, 0x6F, 0x6F, 0x62, 0x61, 0x72, // custom
+    0x05, 0x03, 0x01, 0x00, 0x01, // memory
+    0x0B, 0x09, 0x01, 0x00, 0x41, 0x00, 0x0B, 0x03, 0x61, 0x62, 0x63, // data
+];
+
+#[test]
+fn same_bytes_same_hash() {
+    let engine = Engine::default();
+    let a = Module::new(&engine, WASM).unwrap();
+    let b = Module::new(&engine, WASM).unwrap();
+    let c = Module::new_streaming(&engine, WASM).unwrap();
+    assert_eq!(a.hash(), b.hash());
+    assert_eq!(a.hash(), c.hash());
+    // Hashes are stable across engines with equal configurations.
+    let d = Module::new(&Engine::default(), WASM).unwrap();
+    assert_eq!(a.hash(), d.hash());
+}
+
+#[test]
+fn different_config_different_hash() {
+    let a = Module::new(&Engine::default(), WASM).unwrap();
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let b = Module::new(&Engine::new(&config), WASM).unwrap();
+    assert_ne!(a.hash(), b.hash());
+}
+
+#[test]
+fn transform_changes_hash() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let unchanged = module.transform().finish();
+    assert_eq!(module.hash(), unchanged.hash());
+    let stripped = module.transform().strip_all_custom_sections().finish();
+    assert_ne!(module.hash(), stripped.hash());
+    let replaced = module
+        .transform()
+        .replace_data_segments(|_, _| Some(b"xyz".to_vec()))
+        .finish();
+    assert_ne!(module.hash(), replaced.hash());
+    assert_ne!(stripped.hash(), replaced.hash());
+    let kept = module
+        .transform()
+        .replace_data_segments(|_, _| None)
+        .finish();
+    assert_eq!(module.hash(), kept.hash());
+}
@@ -0,0 +1,57 @@
+//! Tests for deriving new Wasm modules via [`Module::transform`].
+
+use wasmi::{Engine, Linker, Module, Store};
+
+fn module(engine: &Engine) -> Module {
+    let wasm = r#"
+        (module
+            (@custom ".debug_info" "debug")
+            (@custom "name" "names")
+            (memory (export "memory") 1)
+            (data (i32.const 0) "abc")
+            (data "passive")
+        )
+    "#;
+    Module::new(engine, wasm).unwrap()
+}
+
+fn custom_section_names(module: &Module) -> Vec<&str> {
+    module
+        .custom_sections()
+        .map(|section| section.name())
+        .collect()
+}
+
+#[test]
+fn strip_custom_sections() {
+    let engine = Engine::default();
+    let module = module(&engine);
+    let stripped = module
+        .transform()
+        .strip_custom_sections(|section| section.name().starts_with(".debug"))
+        .finish();
+    assert_eq!(custom_section_names(&module), [".debug_info", "name"]);
+    assert_eq!(custom_section_names(&stripped), ["name"]);
+    let stripped = stripped.transform().strip_all_custom_sections().finish();
+    assert!(custom_section_names(&stripped).is_empty());
+}
+
+#[test]
+fn replace_data_segments() {
+    let engine = Engine::default();
+    let module = module(&engine)
+        .transform()
+        .replace_data_segments(|index, bytes| {
+            assert_eq!(bytes, [&b"abc"[..], &b"passive"[..]][index as usize]);
+            (index == 0).then(|| b"wxyz".to_vec())
+        })
+        .finish();
+    let mut store = <Store<()>>::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    assert_eq!(&memory.data(&store)[..5], b"wxyz\0");
+}
@@ -0,0 +1,71 @@
+//! Tests for batch precompilation of Wasm modules via the `Engine`.
+
+use core::num::NonZeroUsize;
+use wasmi::{Engine, Linker, Module, Store};
+
+/// Returns a Wasm module exporting a function `get` that returns `n`.
+fn wasm_returning(n: i32) -> String {
+    format!(r#"(module (func (export "get") (result i32) (i32.const {n})))"#)
+}
+
+/// Returns the result of calling `get` on a new instance of `module`.
+fn call_get(engine: &Engine, module: &Module) -> i32 {
+    let mut store = Store::new(engine, ());
+    let instance = <Linker<()>>::new(engine)
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let get = instance.get_typed_func::<(), i32>(&store, "get").unwrap();
+    get.call(&mut store, ()).unwrap()
+}
+
+#[test]
+fn precompile_modules_works() {
+    let engine = Engine::default();
+    let mut wasms: Vec<String> = (0..5).map(wasm_returning).collect();
+    wasms[3] = String::from("(module (func (result i32)))");
+    let mut results = Vec::new();
+    engine.precompile_modules(&wasms, |progress| {
+        assert_eq!(progress.index(), results.len());
+        assert_eq!(progress.completed(), results.len() + 1);
+        assert_eq!(progress.total(), 5);
+        results.push(progress.into_result());
+    });
+    for (n, result) in results.iter().enumerate() {
+        match n {
+            3 => assert!(result.is_err()),
+            _ => assert_eq!(call_get(&engine, result.as_ref().unwrap()), n as i32),
+        }
+    }
+}
+
+#[test]
+fn precompile_modules_parallel_works() {
+    let engine = Engine::default();
+    let wasms: Vec<String> = (0..50).map(wasm_returning).collect();
+    let threads = NonZeroUsize::new(4).unwrap();
+    let mut modules: Vec<Option<Module>> = vec![None; wasms.len()];
+    let mut completed = 0;
+    engine.precompile_modules_parallel(&wasms, threads, |progress| {
+        completed += 1;
+        assert_eq!(progress.completed(), completed);
+        assert_eq!(progress.total(), 50);
+        let index = progress.index();
+        assert!(modules[index].is_none());
+        modules[index] = Some(progress.into_result().unwrap());
+    });
+    assert_eq!(completed, 50);
+    for (n, module) in modules.iter().enumerate() {
+        assert_eq!(call_get(&engine, module.as_ref().unwrap()), n as i32);
+    }
+}
+
+#[test]
+fn precompile_modules_parallel_without_modules_works() {
+    let engine = Engine::default();
+    let threads = NonZeroUsize::new(4).unwrap();
+    engine.precompile_modules_parallel(<[&[u8]; 0]>::default(), threads, |_| {
+        panic!("unexpected progress without Wasm modules")
+    });
+}
@@ -0,0 +1,171 @@
+//! Tests to check if hot-reloading modules via `Linker::reinstantiate` works as intended.
+
+use wasmi::{Engine, Extern, Global, Instance, Linker, Module, Mutability, Store, Val};
+
+/// The first version of a Wasm module with some state.
+const WASM_V1: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (global $counter (export "counter") (mut i32) (i32.const 0))
+        (func (export "inc") (result i32)
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+            (i32.store (i32.const 0) (global.get $counter))
+            (global.get $counter)
+        )
+    )
+"#;
+
+/// The second version of the Wasm module of [`WASM_V1`].
+const WASM_V2: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (global $counter (export "counter") (mut i32) (i32.const 100))
+        (func (export "inc") (result i32)
+            (global.set $counter (i32.add (global.get $counter) (i32.const 10)))
+            (i32.store (i32.const 0) (global.get $counter))
+            (global.get $counter)
+        )
+        (func (export "load") (result i32)
+            (i32.load (i32.const 0))
+        )
+    )
+"#;
+
+/// Instantiates [`WASM_V1`] and calls its `inc` function `n` times.
+fn setup_v1(n: usize) -> (Store<()>, Linker<()>, Instance) {
+    let engine = Engine::default();
+    let mut store = <Store<()>>::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let module = Module::new(&engine, WASM_V1).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let inc = instance.get_typed_func::<(), i32>(&store, "inc").unwrap();
+    for _ in 0..n {
+        inc.call(&mut store, ()).unwrap();
+    }
+    (store, linker, instance)
+}
+
+#[test]
+fn reinstantiate_preserves_state() {
+    let (mut store, linker, v1) = setup_v1(3);
+    let module = Module::new(store.engine(), WASM_V2).unwrap();
+    let v2 = linker
+        .reinstantiate(&mut store, &module, &v1)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let load = v2.get_typed_func::<(), i32>(&store, "load").unwrap();
+    assert_eq!(load.call(&mut store, ()).unwrap(), 3);
+    let inc = v2.get_typed_func::<(), i32>(&store, "inc").unwrap();
+    assert_eq!(inc.call(&mut store, ()).unwrap(), 13);
+    assert_eq!(load.call(&mut store, ()).unwrap(), 13);
+    // The linear memory and global variable are shared with the old instance.
+    let inc_v1 = v1.get_typed_func::<(), i32>(&store, "inc").unwrap();
+    assert_eq!(inc_v1.call(&mut store, ()).unwrap(), 14);
+    assert_eq!(load.call(&mut store, ()).unwrap(), 14);
+}
+
+#[test]
+fn reinstantiate_skips_mismatching_types() {
+    let (mut store, linker, v1) = setup_v1(3);
+    let wasm = r#"
+        (module
+            (memory (export "memory") 2)
+            (global $counter (export "counter") (mut i64) (i64.const 100))
+        )
+    "#;
+    let module = Module::new(store.engine(), wasm).unwrap();
+    let v2 = linker
+        .reinstantiate(&mut store, &module, &v1)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    // Both definitions are created anew since their types do not match.
+    let memory = v2.get_memory(&store, "memory").unwrap();
+    assert_eq!(memory.size(&store), 2);
+    assert_eq!(memory.data(&store)[0], 0);
+    let counter = v2.get_global(&store, "counter").unwrap();
+    assert_eq!(counter.get(&store).i64(), Some(100));
+}
+
+#[test]
+fn reinstantiate_with_migrates_state() {
+    let (mut store, linker, v1) = setup_v1(3);
+    let wasm = r#"
+        (module
+            (memory (export "memory") 1)
+            (global $counter (export "counter") (mut i64) (i64.const 0))
+            (data (i32.const 8) "\2A")
+        )
+    "#;
+    let module = Module::new(store.engine(), wasm).unwrap();
+    let v2 = linker
+        .reinstantiate_with(&mut store, &module, &v1, |mut store, export, old| {
+            match (export.name(), old) {
+                ("counter", Extern::Global(counter)) => {
+                    let Val::I32(value) = counter.get(&store) else {
+                        panic!("unexpected type of global variable")
+                    };
+                    let value = Val::I64(i64::from(value) * 1000);
+                    let counter = Global::new(&mut store, value, Mutability::Var);
+                    Ok(Some(Extern::Global(counter)))
+                }
+                (_, old) => Ok(Some(old)),
+            }
+        })
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let counter = v2.get_global(&store, "counter").unwrap();
+    assert_eq!(counter.get(&store).i64(), Some(3000));
+    // Active data segments are written to preserved linear memories.
+    let memory = v2.get_memory(&store, "memory").unwrap();
+    assert_eq!(&memory.data(&store)[..9], &[3, 0, 0, 0, 0, 0, 0, 0, 42]);
+}
+
+#[test]
+fn reinstantiate_with_fails_for_mismatching_externs() {
+    let (mut store, linker, v1) = setup_v1(3);
+    let module = Module::new(store.engine(), WASM_V2).unwrap();
+    let memory = v1.get_memory(&store, "memory").unwrap();
+    // Error: a linear memory is returned for a global variable.
+    let result = linker.reinstantiate_with(&mut store, &module, &v1, |_, _, _| {
+        Ok(Some(Extern::Memory(memory)))
+    });
+    assert!(result.is_err());
+    // Error: the preserved global variable has a mismatching type.
+    let result =
+        linker.reinstantiate_with(
+            &mut store,
+            &module,
+            &v1,
+            |mut store, export, old| match export.name() {
+                "counter" => {
+                    let counter = Global::new(&mut store, Val::I64(0), Mutability::Var);
+                    Ok(Some(Extern::Global(counter)))
+                }
+                _ => Ok(Some(old)),
+            },
+        );
+    assert!(result.is_err());
+}
+
+#[test]
+fn reinstantiate_with_fails_for_too_small_memory() {
+    let (mut store, linker, v1) = setup_v1(3);
+    let wasm = r#"
+        (module
+            (memory (export "memory") 2)
+            (func (export "load") (result i32)
+                (i32.load (i32.const 65536))
+            )
+        )
+    "#;
+    let module = Module::new(store.engine(), wasm).unwrap();
+    let result = linker.reinstantiate_with(&mut store, &module, &v1, |_, _, old| Ok(Some(old)));
+    assert!(result.is_err());
+}
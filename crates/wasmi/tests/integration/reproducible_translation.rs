@@ -0,0 +1,103 @@
+//! Tests to check that Wasm translation produces reproducible Wasmi bytecode.
+
+use wasmi::{CompilationMode, Config, Engine, FuelSchedule, Module};
+
+/// A Wasm module with control flow, calls, large constants and a linear memory.
+const WASM: &str = r#"
+    (module
+        (memory 1)
+        (func $fib (export "fib") (param $n i64) (result i64)
+            (if (result i64) (i64.lt_u (local.get $n) (i64.const 2))
+                (then (local.get $n))
+                (else
+                    (i64.add
+                        (call $fib (i64.sub (local.get $n) (i64.const 1)))
+                        (call $fib (i64.sub (local.get $n) (i64.const 2)))
+                    )
+                )
+            )
+        )
+        (func (export "copy") (param $len i32)
+            (memory.copy (i32.const 0) (i32.const 1024) (local.get $len))
+            (i64.store (i32.const 8) (i64.const 0x0123_4567_89AB_CDEF))
+        )
+    )
+"#;
+
+/// Returns a [`Config`] with reproducible translation and fuel metering enabled.
+fn config(mode: CompilationMode) -> Config {
+    let mut config = Config::default();
+    config
+        .reproducible_translation(true)
+        .consume_fuel(true)
+        .compilation_mode(mode);
+    config
+}
+
+/// Returns the code hash of `wasm` compiled by `engine`.
+fn code_hash(engine: &Engine, wasm: &str) -> [u8; 16] {
+    Module::new(engine, wasm)
+        .unwrap()
+        .code_hash()
+        .unwrap()
+        .to_bytes()
+}
+
+/// Returns the code hash of [`WASM`] compiled with fuel metering and the `setup` [`Config`].
+fn config_hash(setup: impl FnOnce(&mut Config)) -> [u8; 16] {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    setup(&mut config);
+    code_hash(&Engine::new(&config), WASM)
+}
+
+#[test]
+fn code_hash_is_reproducible() {
+    let expected = code_hash(&Engine::new(&config(CompilationMode::Eager)), WASM);
+    for mode in [
+        CompilationMode::Eager,
+        CompilationMode::LazyTranslation,
+        CompilationMode::Lazy,
+    ] {
+        let engine = Engine::new(&config(mode));
+        assert_eq!(code_hash(&engine, WASM), expected);
+        // Compiling other Wasm modules first must not affect the Wasmi bytecode.
+        let engine = Engine::new(&config(mode));
+        code_hash(
+            &engine,
+            r#"(module (func (export "f") (result i32) (i32.const 42)))"#,
+        );
+        assert_eq!(code_hash(&engine, WASM), expected);
+        assert_eq!(code_hash(&engine, WASM), expected);
+    }
+}
+
+#[test]
+fn code_hash_depends_on_code() {
+    let engine = Engine::new(&config(CompilationMode::Eager));
+    let other = WASM.replace("0x0123_4567_89AB_CDEF", "0x0123_4567_89AB_CDEE");
+    assert_ne!(code_hash(&engine, WASM), code_hash(&engine, &other));
+    let mut config = Config::default();
+    config.reproducible_translation(true);
+    assert_ne!(
+        code_hash(&Engine::new(&config), WASM),
+        code_hash(&engine, WASM)
+    );
+}
+
+#[test]
+fn reproducible_translation_uses_frozen_fuel_costs() {
+    let mut config = Config::default();
+    config.consume_fuel(true).fuel_schedule(FuelSchedule::v1());
+    let expected = code_hash(&Engine::new(&config), WASM);
+    let reproducible = config_hash(|config| {
+        config.reproducible_translation(true);
+    });
+    assert_eq!(reproducible, expected);
+    let disabled = config_hash(|config| {
+        config
+            .reproducible_translation(true)
+            .reproducible_translation(false);
+    });
+    assert_eq!(disabled, config_hash(|_| {}));
+}
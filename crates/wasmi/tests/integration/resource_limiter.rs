@@ -9,8 +9,12 @@ use wasmi::{
     Store,
     StoreLimits,
     StoreLimitsBuilder,
+    Table,
+    TableType,
     TypedFunc,
+    Val,
 };
+use wasmi_core::ValType;
 
 /// Setup [`Engine`] and [`Store`] for resource limiting.
 fn test_setup(limits: StoreLimits) -> (Store<StoreLimits>, Linker<StoreLimits>) {
@@ -109,6 +113,35 @@ fn test_tables_count_limit() {
     assert!(Test::new(0x30, 100, limits).is_err());
 }
 
+#[test]
+fn test_total_table_elements_limit() -> Result<(), Error> {
+    let limits = StoreLimitsBuilder::new().total_table_elements(100).build();
+    assert!(Test::new(0x20, 101, limits.clone()).is_err());
+    let mut test = Test::new(0x20, 60, limits)?;
+    let funcref_table = |min| {
+        (
+            TableType::new(ValType::FuncRef, min, None),
+            Val::default(ValType::FuncRef),
+        )
+    };
+    // The limit is shared by all tables of the store.
+    let (ty, init) = funcref_table(41);
+    assert!(Table::new(&mut test.store, ty, init).is_err());
+    let (ty, init) = funcref_table(30);
+    let table = Table::new(&mut test.store, ty, init)?;
+    assert_eq!(test.table_grow.call(&mut test.store, (11,))?, -1);
+    assert_eq!(test.table_grow.call(&mut test.store, (10,))?, 60);
+    assert_eq!(test.table_size.call(&mut test.store, ())?, 70);
+    // All 100 table elements of the store are in use.
+    assert!(table
+        .grow(&mut test.store, 1, Val::default(ValType::FuncRef))
+        .is_err());
+    assert!(table
+        .grow(&mut test.store, 0, Val::default(ValType::FuncRef))
+        .is_ok());
+    Ok(())
+}
+
 #[test]
 fn test_memory_does_not_grow_on_limited_growth() -> Result<(), Error> {
     let limits = StoreLimitsBuilder::new().memory_size(0x30_0000).build();
@@ -0,0 +1,58 @@
+//! Tests to check if `serde` support for `Val`, `ValType` and `FuncType` works as intended.
+
+use wasmi::{Engine, Func, FuncRef, FuncType, Store, Val};
+use wasmi_core::{ValType, F32, F64};
+
+#[test]
+fn val_type_roundtrip_works() {
+    let json = serde_json::to_string(&ValType::ExternRef).unwrap();
+    assert_eq!(json, r#""ExternRef""#);
+    assert_eq!(
+        serde_json::from_str::<ValType>(&json).unwrap(),
+        ValType::ExternRef
+    );
+}
+
+#[test]
+fn func_type_roundtrip_works() {
+    let func_type = FuncType::new([ValType::I32, ValType::F64], [ValType::I64]);
+    let json = serde_json::to_string(&func_type).unwrap();
+    assert_eq!(json, r#"{"params":["I32","F64"],"results":["I64"]}"#);
+    assert_eq!(serde_json::from_str::<FuncType>(&json).unwrap(), func_type);
+}
+
+#[test]
+fn func_type_too_many_params_fails() {
+    let params = vec!["I32"; 1001];
+    let json = serde_json::json!({ "params": params, "results": [] }).to_string();
+    assert!(serde_json::from_str::<FuncType>(&json).is_err());
+}
+
+#[test]
+fn val_roundtrip_works() {
+    let values = [
+        Val::I32(-1),
+        Val::I64(i64::MAX),
+        Val::F32(F32::from(1.5)),
+        Val::F64(F64::from(-0.25)),
+        Val::FuncRef(FuncRef::null()),
+    ];
+    let json = serde_json::to_string(&values).unwrap();
+    assert_eq!(
+        json,
+        r#"[{"I32":-1},{"I64":9223372036854775807},{"F32":1.5},{"F64":-0.25},"FuncRef"]"#
+    );
+    let decoded = serde_json::from_str::<Vec<Val>>(&json).unwrap();
+    assert_eq!(decoded[0].i32(), Some(-1));
+    assert_eq!(decoded[1].i64(), Some(i64::MAX));
+    assert_eq!(decoded[2].f32(), Some(F32::from(1.5)));
+    assert_eq!(decoded[3].f64(), Some(F64::from(-0.25)));
+    assert!(decoded[4].funcref().unwrap().is_null());
+}
+
+#[test]
+fn non_null_ref_fails() {
+    let mut store = <Store<()>>::new(&Engine::default(), ());
+    let func = Func::wrap(&mut store, || {});
+    assert!(serde_json::to_string(&Val::from(func)).is_err());
+}
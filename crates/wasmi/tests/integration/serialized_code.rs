@@ -0,0 +1,154 @@
+//! Tests to check if executing serialized Wasmi bytecode works as intended.
+
+use wasmi::{
+    errors::{ErrorKind, SerializedCodeError},
+    CompilationMode,
+    Config,
+    Engine,
+    Error,
+    Linker,
+    Module,
+    Store,
+};
+
+/// A Wasm module with recursive calls, a large constant and a linear memory.
+const WASM: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (func $fib (export "fib") (param $n i64) (result i64)
+            (if (result i64) (i64.lt_u (local.get $n) (i64.const 2))
+                (then (local.get $n))
+                (else
+                    (i64.add
+                        (call $fib (i64.sub (local.get $n) (i64.const 1)))
+                        (call $fib (i64.sub (local.get $n) (i64.const 2)))
+                    )
+                )
+            )
+        )
+        (func (export "store") (param $ptr i32) (result i64)
+            (i64.store (local.get $ptr) (i64.const 0x0123_4567_89AB_CDEF))
+            (i64.load (local.get $ptr))
+        )
+    )
+"#;
+
+/// Returns the serialized code of `wasm` compiled by `engine`.
+fn serialize(engine: &Engine, wasm: &str) -> Vec<u8> {
+    let module = Module::new(engine, wasm).unwrap();
+    module.serialize_code().unwrap()
+}
+
+/// Loads `wasm` with the serialized `code` using `engine`.
+fn load(engine: &Engine, wasm: &str, code: &[u8]) -> Result<Module, Error> {
+    // SAFETY: all serialized code is produced by this test binary.
+    unsafe { Module::new_with_serialized_code(engine, wasm, code) }
+}
+
+/// Returns the [`SerializedCodeError`] of `error`.
+fn serialized_code_error(error: Error) -> SerializedCodeError {
+    match error.kind() {
+        ErrorKind::SerializedCode(error) => *error,
+        error => panic!("unexpected error: {error}"),
+    }
+}
+
+/// Asserts that the exports of [`WASM`] of `module` work as intended.
+fn assert_exports(engine: &Engine, module: &Module) {
+    let mut store = Store::new(engine, ());
+    let instance = <Linker<()>>::new(engine)
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let fib = instance.get_typed_func::<i64, i64>(&store, "fib").unwrap();
+    let store_fn = instance
+        .get_typed_func::<i32, i64>(&store, "store")
+        .unwrap();
+    assert_eq!(fib.call(&mut store, 20).unwrap(), 6765);
+    assert_eq!(
+        store_fn.call(&mut store, 64).unwrap(),
+        0x0123_4567_89AB_CDEF
+    );
+}
+
+#[test]
+fn serialized_code_executes() {
+    for mode in [CompilationMode::Eager, CompilationMode::Lazy] {
+        let mut config = Config::default();
+        config.compilation_mode(mode);
+        let engine = Engine::new(&config);
+        let code = serialize(&engine, WASM);
+        let module = load(&engine, WASM, &code).unwrap();
+        assert_exports(&engine, &module);
+        assert_eq!(module.serialize_code().unwrap(), code);
+    }
+}
+
+#[test]
+fn serialized_code_is_not_borrowed() {
+    let engine = Engine::default();
+    let code = serialize(&engine, WASM);
+    let module = load(&engine, WASM, &code).unwrap();
+    drop(code);
+    assert_exports(&engine, &module);
+}
+
+#[test]
+fn serialized_code_relocates_internal_calls() {
+    let code = serialize(&Engine::default(), WASM);
+    let engine = Engine::default();
+    let other = r#"
+        (module
+            (func $f (result i32) (call $g))
+            (func $g (result i32) (i32.const 42))
+        )
+    "#;
+    Module::new(&engine, other).unwrap();
+    let module = load(&engine, WASM, &code).unwrap();
+    assert_exports(&engine, &module);
+    assert_eq!(module.serialize_code().unwrap(), code);
+}
+
+#[test]
+fn mismatching_modules_fail() {
+    let engine = Engine::default();
+    let code = serialize(&engine, WASM);
+    let other = r#"(module (func (export "f")))"#;
+    let error = load(&engine, other, &code).unwrap_err();
+    assert_eq!(
+        serialized_code_error(error),
+        SerializedCodeError::ModuleMismatch
+    );
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let error = load(&Engine::new(&config), WASM, &code).unwrap_err();
+    assert_eq!(
+        serialized_code_error(error),
+        SerializedCodeError::ModuleMismatch
+    );
+}
+
+#[test]
+fn malformed_code_fails() {
+    let engine = Engine::default();
+    let code = serialize(&engine, WASM);
+    let truncated = &code[..code.len() - 8];
+    let error = load(&engine, WASM, truncated).unwrap_err();
+    assert_eq!(serialized_code_error(error), SerializedCodeError::Malformed);
+    let error = load(&engine, WASM, &code[1..]).unwrap_err();
+    assert_eq!(serialized_code_error(error), SerializedCodeError::Malformed);
+}
+
+#[test]
+fn unknown_opcodes_fail() {
+    let engine = Engine::default();
+    let mut code = serialize(&engine, WASM);
+    // The first function record starts after the 32 bytes of the header and its
+    // instruction words follow its 16 bytes header and function local constant values.
+    let len_consts = u32::from_le_bytes(code[36..40].try_into().unwrap()) as usize;
+    let opcode = 48 + len_consts * 16;
+    code[opcode..opcode + 2].copy_from_slice(&u16::MAX.to_le_bytes());
+    let error = load(&engine, WASM, &code).unwrap_err();
+    assert_eq!(serialized_code_error(error), SerializedCodeError::Malformed);
+}
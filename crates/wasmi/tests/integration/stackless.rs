@@ -0,0 +1,173 @@
+//! Tests to check if stackless execution works as intended.
+
+use wasmi::{
+    errors::{ErrorKind, FuncError},
+    Caller,
+    Config,
+    Engine,
+    Func,
+    Instance,
+    Linker,
+    Module,
+    StackLimits,
+    Store,
+    Val,
+};
+
+/// A Wasm module calling back and forth between Wasm and host functions.
+const WASM: &str = r#"
+    (module
+        (import "env" "ping" (func $ping (param i32) (result i32)))
+        (import "env" "nested" (func $nested (result i32)))
+        (import "env" "to_host" (func $to_host (result i32)))
+        (func $pong (export "pong") (param $n i32) (result i32)
+            (if (result i32) (i32.eqz (local.get $n))
+                (then (i32.const 0))
+                (else
+                    (i32.add
+                        (call $ping (i32.sub (local.get $n) (i32.const 1)))
+                        (i32.const 1)
+                    )
+                )
+            )
+        )
+        (func (export "tail_ping") (param $n i32) (result i32)
+            (return_call $ping (local.get $n))
+        )
+        (func $depth (export "depth") (param $n i32) (result i32)
+            (if (result i32) (i32.eqz (local.get $n))
+                (then (i32.const 0))
+                (else
+                    (i32.add
+                        (call $depth (i32.sub (local.get $n) (i32.const 1)))
+                        (i32.const 1)
+                    )
+                )
+            )
+        )
+        (func (export "call_nested") (result i32)
+            (call $nested)
+        )
+        (func (export "call_to_host") (result i32)
+            (call $to_host)
+        )
+    )
+"#;
+
+/// Returns the exported [`Func`] `name` of the instance of the `caller`.
+fn export(caller: &Caller<()>, name: &str) -> Func {
+    caller.get_export(name).and_then(|e| e.into_func()).unwrap()
+}
+
+/// Instantiates [`WASM`] with stackless execution enabled.
+fn setup() -> (Store<()>, Instance) {
+    let mut config = Config::default();
+    config
+        .stackless(true)
+        .set_stack_limits(StackLimits::new(1024, 1 << 24, 1 << 20).unwrap());
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "ping", |mut caller: Caller<()>, n: i32| {
+            let pong = export(&caller, "pong");
+            caller.return_call(&pong, &[Val::I32(n)])?;
+            Ok(0_i32)
+        })
+        .unwrap()
+        .func_wrap("env", "nested", |mut caller: Caller<()>| {
+            let pong = export(&caller, "pong");
+            let mut results = [Val::I32(0)];
+            pong.call(&mut caller, &[Val::I32(1)], &mut results)?;
+            Ok(results[0].i32().unwrap())
+        })
+        .unwrap()
+        .func_wrap("env", "to_host", |mut caller: Caller<()>| {
+            let host = Func::wrap(&mut caller, || 0_i32);
+            caller.return_call(&host, &[])?;
+            Ok(0_i32)
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Runs `f` on a thread with a tiny native stack.
+fn with_tiny_stack(f: impl FnOnce() + Send + 'static) {
+    std::thread::Builder::new()
+        .stack_size(128 * 1024)
+        .spawn(f)
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn deep_recursion_works() {
+    with_tiny_stack(|| {
+        let (mut store, instance) = setup();
+        let depth = instance
+            .get_typed_func::<i32, i32>(&store, "depth")
+            .unwrap();
+        assert_eq!(depth.call(&mut store, 100_000).unwrap(), 100_000);
+    });
+}
+
+#[test]
+fn host_return_calls_work() {
+    with_tiny_stack(|| {
+        let (mut store, instance) = setup();
+        let pong = instance.get_typed_func::<i32, i32>(&store, "pong").unwrap();
+        assert_eq!(pong.call(&mut store, 100_000).unwrap(), 100_000);
+        let tail_ping = instance
+            .get_typed_func::<i32, i32>(&store, "tail_ping")
+            .unwrap();
+        assert_eq!(tail_ping.call(&mut store, 1_000).unwrap(), 1_000);
+    });
+}
+
+#[test]
+fn host_return_calls_from_root_work() {
+    let (mut store, instance) = setup();
+    let pong = instance.get_typed_func::<i32, i32>(&store, "pong").unwrap();
+    let ping = Func::wrap(&mut store, move |mut caller: Caller<()>, n: i32| {
+        caller.return_call(pong.func(), &[Val::I32(n)])?;
+        Ok(0_i32)
+    });
+    let ping = ping.typed::<i32, i32>(&store).unwrap();
+    assert_eq!(ping.call(&mut store, 10).unwrap(), 10);
+}
+
+#[test]
+fn nested_calls_fail() {
+    let (mut store, instance) = setup();
+    let call_nested = instance
+        .get_typed_func::<(), i32>(&store, "call_nested")
+        .unwrap();
+    let error = call_nested.call(&mut store, ()).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Func(FuncError::NestedCall)
+    ));
+    // Note: the store can still execute after a failed execution.
+    let pong = instance.get_typed_func::<i32, i32>(&store, "pong").unwrap();
+    assert_eq!(pong.call(&mut store, 10).unwrap(), 10);
+}
+
+#[test]
+fn return_calls_to_host_fail() {
+    let (mut store, instance) = setup();
+    let call_to_host = instance
+        .get_typed_func::<(), i32>(&store, "call_to_host")
+        .unwrap();
+    let error = call_to_host.call(&mut store, ()).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Func(FuncError::ReturnCallToHost)
+    ));
+}
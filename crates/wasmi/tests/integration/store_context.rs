@@ -0,0 +1,55 @@
+//! Tests for the type-keyed host contexts of the `Store`.
+
+use wasmi::{Caller, Engine, Func, Store};
+
+/// The state of an imaginary logging host library.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Logger {
+    lines: Vec<String>,
+}
+
+/// The state of an imaginary counting host library.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Counter(u32);
+
+#[test]
+fn insert_and_remove_context() {
+    let mut store = <Store<()>>::default();
+    assert_eq!(store.context::<Counter>(), None);
+    assert_eq!(store.insert_context(Counter(1)), None);
+    assert_eq!(store.insert_context(Counter(2)), Some(Counter(1)));
+    assert_eq!(store.insert_context(Logger::default()), None);
+    store.context_mut::<Counter>().unwrap().0 += 1;
+    assert_eq!(store.context::<Counter>(), Some(&Counter(3)));
+    assert_eq!(store.context::<Logger>(), Some(&Logger::default()));
+    assert_eq!(store.remove_context::<Counter>(), Some(Counter(3)));
+    assert_eq!(store.remove_context::<Counter>(), None);
+    assert_eq!(store.context::<Logger>(), Some(&Logger::default()));
+}
+
+#[test]
+fn caller_context_works() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, 42_i32);
+    store.insert_context(Counter(0));
+    store.insert_context(Logger::default());
+    let func = Func::wrap(&mut store, |mut caller: Caller<i32>| -> i32 {
+        let data = *caller.data();
+        caller.context_mut::<Counter>().unwrap().0 += 1;
+        let count = caller.context::<Counter>().unwrap().0;
+        caller
+            .context_mut::<Logger>()
+            .unwrap()
+            .lines
+            .push(format!("call {count}"));
+        data
+    });
+    let func = func.typed::<(), i32>(&store).unwrap();
+    assert_eq!(func.call(&mut store, ()).unwrap(), 42);
+    assert_eq!(func.call(&mut store, ()).unwrap(), 42);
+    assert_eq!(store.context::<Counter>(), Some(&Counter(2)));
+    assert_eq!(
+        store.context::<Logger>().unwrap().lines,
+        ["call 1", "call 2"]
+    );
+}
@@ -0,0 +1,75 @@
+//! Tests to check if `Store::reset` works as intended.
+
+use wasmi::{Engine, Instance, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// A Wasm module with a linear memory and a global variable.
+const WASM: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (global $counter (mut i32) (i32.const 0))
+        (func (export "inc") (result i32)
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+            (i32.store (i32.const 0) (global.get $counter))
+            (global.get $counter)
+        )
+    )
+"#;
+
+/// Instantiates [`WASM`] within `store`.
+fn instantiate<T: 'static>(store: &mut Store<T>) -> Instance {
+    let module = Module::new(store.engine(), WASM).unwrap();
+    <Linker<T>>::new(store.engine())
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Calls the `inc` function of `instance`.
+fn inc<T>(store: &mut Store<T>, instance: Instance) -> i32 {
+    instance
+        .get_typed_func::<(), i32>(&*store, "inc")
+        .unwrap()
+        .call(store, ())
+        .unwrap()
+}
+
+#[test]
+fn reset_drops_all_state() {
+    let mut store = Store::new(&Engine::default(), 1_u32);
+    store.insert_context(String::from("context"));
+    let instance = instantiate(&mut store);
+    assert_eq!(inc(&mut store, instance), 1);
+    assert_eq!(inc(&mut store, instance), 2);
+    assert_eq!(store.reset(2), 1);
+    assert_eq!(*store.data(), 2);
+    assert!(store.context::<String>().is_none());
+    let instance = instantiate(&mut store);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    assert_eq!(memory.data(&store)[0], 0);
+    assert_eq!(inc(&mut store, instance), 1);
+    assert_eq!(memory.data(&store)[0], 1);
+}
+
+#[test]
+#[should_panic]
+fn reset_invalidates_handles() {
+    let mut store = Store::new(&Engine::default(), ());
+    let instance = instantiate(&mut store);
+    store.reset(());
+    instance.get_memory(&store, "memory");
+}
+
+#[test]
+fn reset_releases_resource_limits() {
+    let limits = StoreLimitsBuilder::new().instances(1).build();
+    let mut store = <Store<StoreLimits>>::new(&Engine::default(), limits.clone());
+    store.limiter(|limits| limits);
+    instantiate(&mut store);
+    let module = Module::new(store.engine(), WASM).unwrap();
+    let linker = <Linker<StoreLimits>>::new(store.engine());
+    assert!(linker.instantiate(&mut store, &module).is_err());
+    store.reset(limits);
+    let instance = instantiate(&mut store);
+    assert_eq!(inc(&mut store, instance), 1);
+}
@@ -0,0 +1,74 @@
+//! Tests for the host-side bulk operations of the `Table` type.
+
+use wasmi::{core::ValType, errors::TableError, ExternRef, Store, Table, TableType, Val};
+
+fn test_setup(len: u32) -> (Store<()>, Table) {
+    let mut store = <Store<()>>::default();
+    let ty = TableType::new(ValType::ExternRef, len, None);
+    let table = Table::new(&mut store, ty, Val::ExternRef(ExternRef::null())).unwrap();
+    (store, table)
+}
+
+fn externref(store: &mut Store<()>, value: i32) -> Val {
+    Val::ExternRef(ExternRef::new::<i32>(store, value))
+}
+
+fn as_i32(store: &Store<()>, value: &Val) -> Option<i32> {
+    value
+        .externref()?
+        .data(store)
+        .and_then(|data| data.downcast_ref::<i32>())
+        .copied()
+}
+
+#[test]
+fn set_many_get_many_works() {
+    let (mut store, table) = test_setup(10);
+    let values = [1, 2, 3].map(|value| externref(&mut store, value));
+    table.set_many(&mut store, 4, &values).unwrap();
+    let mut results = [(); 5].map(|_| Val::ExternRef(ExternRef::null()));
+    table.get_many(&store, 3, &mut results).unwrap();
+    let results = results.map(|value| as_i32(&store, &value));
+    assert_eq!(results, [None, Some(1), Some(2), Some(3), None]);
+}
+
+#[test]
+fn set_many_out_of_bounds() {
+    let (mut store, table) = test_setup(10);
+    let values = [1, 2, 3].map(|value| externref(&mut store, value));
+    assert!(matches!(
+        table.set_many(&mut store, 8, &values),
+        Err(TableError::AccessOutOfBounds {
+            current: 10,
+            index: 10
+        })
+    ));
+    assert!(table.set_many(&mut store, u64::MAX, &values).is_err());
+    // The table remains unchanged upon failure.
+    assert!(as_i32(&store, &table.get(&store, 8).unwrap()).is_none());
+}
+
+#[test]
+fn set_many_type_mismatch() {
+    let (mut store, table) = test_setup(10);
+    let values = [externref(&mut store, 1), Val::I32(2)];
+    assert!(matches!(
+        table.set_many(&mut store, 0, &values),
+        Err(TableError::ElementTypeMismatch { .. })
+    ));
+    // The table remains unchanged upon failure.
+    assert!(as_i32(&store, &table.get(&store, 0).unwrap()).is_none());
+}
+
+#[test]
+fn get_many_out_of_bounds() {
+    let (store, table) = test_setup(2);
+    let mut results = [(); 3].map(|_| Val::ExternRef(ExternRef::null()));
+    assert!(matches!(
+        table.get_many(&store, 0, &mut results),
+        Err(TableError::AccessOutOfBounds {
+            current: 2,
+            index: 2
+        })
+    ));
+}
@@ -0,0 +1,122 @@
+//! Tests for the lazy initialization of tables with active element segments.
+
+use wasmi::{core::TrapCode, Engine, Instance, Linker, Module, Store, Val};
+
+/// Instantiates a Wasm module with a large table initialized by overlapping element segments.
+fn setup() -> (Store<()>, Instance) {
+    let wasm = r#"
+        (module
+            (table (export "table") 100000 funcref)
+            (func $f0 (result i32) (i32.const 10))
+            (func $f1 (result i32) (i32.const 11))
+            (func $f2 (result i32) (i32.const 12))
+            (elem (i32.const 1000) $f0 $f1 $f2)
+            (elem (i32.const 1001) $f2)
+            (elem (i32.const 60) $f1 $f1 $f1 $f1 $f1 $f1 $f1 $f1)
+            (func (export "call") (param i32) (result i32)
+                (call_indirect (result i32) (local.get 0))
+            )
+            (func (export "set") (param $dst i32) (param $src i32)
+                (table.set (local.get $dst) (table.get (local.get $src)))
+            )
+            (func (export "copy") (param $dst i32) (param $src i32) (param $len i32)
+                (table.copy (local.get $dst) (local.get $src) (local.get $len))
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = <Store<()>>::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the function stored in the table at `index` and returns its result.
+fn call(store: &mut Store<()>, instance: Instance, index: i32) -> Result<i32, wasmi::Error> {
+    instance
+        .get_typed_func::<i32, i32>(&*store, "call")
+        .unwrap()
+        .call(store, index)
+}
+
+#[test]
+fn call_indirect_works() {
+    let (mut store, instance) = setup();
+    assert_eq!(call(&mut store, instance, 1000).unwrap(), 10);
+    assert_eq!(call(&mut store, instance, 1001).unwrap(), 12);
+    assert_eq!(call(&mut store, instance, 1002).unwrap(), 12);
+    let error = call(&mut store, instance, 999).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IndirectCallToNull));
+}
+
+#[test]
+fn host_get_works() {
+    let (mut store, instance) = setup();
+    let table = instance.get_table(&store, "table").unwrap();
+    let Some(Val::FuncRef(funcref)) = table.get(&store, 1002) else {
+        panic!("expected a funcref at index 1002")
+    };
+    let func = funcref.func().unwrap().typed::<(), i32>(&store).unwrap();
+    assert_eq!(func.call(&mut store, ()).unwrap(), 12);
+    let Some(Val::FuncRef(funcref)) = table.get(&store, 1003) else {
+        panic!("expected a funcref at index 1003")
+    };
+    assert!(funcref.is_null());
+}
+
+#[test]
+fn table_set_works() {
+    let (mut store, instance) = setup();
+    instance
+        .get_typed_func::<(i32, i32), ()>(&store, "set")
+        .unwrap()
+        .call(&mut store, (1002, 1000))
+        .unwrap();
+    assert_eq!(call(&mut store, instance, 1000).unwrap(), 10);
+    assert_eq!(call(&mut store, instance, 1001).unwrap(), 12);
+    assert_eq!(call(&mut store, instance, 1002).unwrap(), 10);
+}
+
+#[test]
+fn table_copy_works() {
+    let (mut store, instance) = setup();
+    let copy = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "copy")
+        .unwrap();
+    // Copy from lazily initialized elements to untouched elements.
+    copy.call(&mut store, (5, 1000, 3)).unwrap();
+    assert_eq!(call(&mut store, instance, 5).unwrap(), 10);
+    assert_eq!(call(&mut store, instance, 6).unwrap(), 12);
+    assert_eq!(call(&mut store, instance, 7).unwrap(), 12);
+    // Copy within lazily initialized elements.
+    copy.call(&mut store, (1001, 1000, 2)).unwrap();
+    assert_eq!(call(&mut store, instance, 1000).unwrap(), 10);
+    assert_eq!(call(&mut store, instance, 1001).unwrap(), 10);
+    assert_eq!(call(&mut store, instance, 1002).unwrap(), 12);
+}
+
+#[test]
+fn call_indirect_materializes_elements() {
+    let (mut store, instance) = setup();
+    for _ in 0..3 {
+        for index in 60..68 {
+            assert_eq!(call(&mut store, instance, index).unwrap(), 11);
+        }
+        let error = call(&mut store, instance, 68).unwrap_err();
+        assert_eq!(error.as_trap_code(), Some(TrapCode::IndirectCallToNull));
+    }
+    // Mutating elements next to already materialized elements keeps both intact.
+    assert_eq!(call(&mut store, instance, 1000).unwrap(), 10);
+    instance
+        .get_typed_func::<(i32, i32), ()>(&store, "set")
+        .unwrap()
+        .call(&mut store, (1001, 60))
+        .unwrap();
+    assert_eq!(call(&mut store, instance, 1000).unwrap(), 10);
+    assert_eq!(call(&mut store, instance, 1001).unwrap(), 11);
+    assert_eq!(call(&mut store, instance, 1002).unwrap(), 12);
+}
@@ -0,0 +1,63 @@
+//! Tests for printing Wasm modules in the WebAssembly text format.
+
+use wasmi::{Engine, Module};
+
+#[test]
+fn to_wat_works() {
+    let wasm = r#"
+        (module
+            (func (export "add") (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let wat = module.to_wat().unwrap();
+    assert!(wat.contains(r#"(export "add""#));
+    assert!(wat.contains("i32.add"));
+    // The printed Wasm text can be parsed again.
+    let module = Module::new(&engine, &wat).unwrap();
+    assert!(module.get_export("add").is_some());
+}
+
+#[test]
+fn to_wat_streaming_works() {
+    // The binary encoding of: `(module (memory 1) (data (i32.const 0) "hi"))`
+    let wasm = [
+        0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, // header
+        0x05, 0x03, 0x01, 0x00, 0x01, // memory
+        0x0B, 0x08, 0x01, 0x00, 0x41, 0x00, 0x0B, 0x02, 0x68, 0x69, // data
+    ];
+    let engine = Engine::default();
+    let module = Module::new_streaming(&engine, &wasm[..]).unwrap();
+    let wat = module.to_wat().unwrap();
+    assert!(wat.contains(r#""hi""#));
+}
+
+#[test]
+fn to_wat_of_transformed_module_works() {
+    let wasm = r#"
+        (module
+            (@custom ".debug_info" "debug")
+            (memory 1)
+            (data (i32.const 0) "hi")
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    // A derived module without any transformation still prints its original Wasm binary.
+    let derived = module.transform().finish();
+    assert_eq!(derived.to_wat().unwrap(), module.to_wat().unwrap());
+    let transformed = module
+        .transform()
+        .strip_all_custom_sections()
+        .replace_data_segments(|_, _| Some(b"bye".to_vec()))
+        .finish();
+    let wat = transformed.to_wat().unwrap();
+    assert!(wat.contains(r#""bye""#));
+    assert!(!wat.contains(r#""hi""#));
+    assert!(!wat.contains(".debug_info"));
+    // The printed Wasm text can be parsed again.
+    Module::new(&engine, &wat).unwrap();
+}
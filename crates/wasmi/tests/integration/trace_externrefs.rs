@@ -0,0 +1,104 @@
+//! Tests to check if tracing the `ExternRef`s of a `Store` works as intended.
+
+use wasmi::{
+    core::ValType,
+    Caller,
+    Engine,
+    Error,
+    ExternRef,
+    Global,
+    Linker,
+    Module,
+    Mutability,
+    Store,
+    Table,
+    TableType,
+    TypedResumableCall,
+    Val,
+};
+
+/// Returns the sorted `i32` data of all `externrefs`.
+fn sorted_data(store: &Store<()>, externrefs: &[ExternRef]) -> Vec<i32> {
+    let mut data: Vec<i32> = externrefs
+        .iter()
+        .map(|externref| {
+            *externref
+                .data(store)
+                .unwrap()
+                .downcast_ref::<i32>()
+                .unwrap()
+        })
+        .collect();
+    data.sort_unstable();
+    data
+}
+
+#[test]
+fn trace_externrefs_of_tables_and_globals() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let table = Table::new(
+        &mut store,
+        TableType::new(ValType::ExternRef, 3, None),
+        Val::default(ValType::ExternRef),
+    )
+    .unwrap();
+    let in_table = ExternRef::new(&mut store, 1_i32);
+    let in_global = ExternRef::new(&mut store, 2_i32);
+    let _unreferenced = ExternRef::new(&mut store, 3_i32);
+    table.set(&mut store, 1, Val::from(in_table)).unwrap();
+    let _global = Global::new(&mut store, Val::from(in_global), Mutability::Var);
+    let mut traced = Vec::new();
+    store.trace_externrefs(|externref| traced.push(externref));
+    assert_eq!(sorted_data(&store, &traced), [1, 2]);
+}
+
+#[test]
+fn trace_externrefs_of_suspended_call() {
+    let wasm = r#"
+        (module
+            (import "env" "suspend" (func $suspend))
+            (func (export "run") (param $ref externref) (result externref)
+                (call $suspend)
+                (local.get $ref)
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "suspend",
+            |_caller: Caller<'_, ()>| -> Result<(), Error> { Err(Error::i32_exit(0)) },
+        )
+        .unwrap();
+    let module = Module::new(&engine, wasm).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance
+        .get_typed_func::<ExternRef, ExternRef>(&store, "run")
+        .unwrap();
+    let in_register = ExternRef::new(&mut store, 42_i32);
+    let invocation = match run.call_resumable(&mut store, in_register).unwrap() {
+        TypedResumableCall::Resumable(invocation) => invocation,
+        TypedResumableCall::Finished(_) => panic!("expected a suspended call"),
+    };
+    let mut traced = Vec::new();
+    store.trace_externrefs(|externref| traced.push(externref));
+    assert!(traced.is_empty());
+    invocation.trace_externrefs(&store, |externref| traced.push(externref));
+    // Note: registers are scanned conservatively and may hold copies of the same `externref`.
+    let mut data = sorted_data(&store, &traced);
+    data.dedup();
+    assert_eq!(data, [42]);
+    let result = match invocation.resume(&mut store, &[]).unwrap() {
+        TypedResumableCall::Finished(result) => result,
+        TypedResumableCall::Resumable(_) => panic!("expected a finished call"),
+    };
+    assert_eq!(sorted_data(&store, &[result]), [42]);
+}
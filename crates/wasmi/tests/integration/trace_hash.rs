@@ -0,0 +1,117 @@
+//! Tests to check if execution trace hashing of the `Store` works as intended.
+
+use wasmi::{Engine, Instance, Linker, Module, Store, TraceHash, Val};
+
+/// Setup [`Store`] and [`Instance`] for the trace hashing tests.
+fn test_setup() -> (Store<u64>, Instance) {
+    let wasm = r#"
+        (module
+            (memory 1)
+            (global $g (mut i32) (i32.const 0))
+            (func $store (param $value i32)
+                (i32.store (i32.const 8) (local.get $value))
+            )
+            (func (export "count") (param $n i32) (result i32)
+                (local $i i32)
+                (block
+                    (loop
+                        (br_if 1 (i32.ge_u (local.get $i) (local.get $n)))
+                        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                        (br 0)
+                    )
+                )
+                (local.get $i)
+            )
+            (func (export "store") (param $value i32)
+                (call $store (local.get $value))
+            )
+            (func (export "set_global") (param $value i32)
+                (global.set $g (local.get $value))
+            )
+            (func (export "fill") (param $value i32)
+                (memory.fill (i32.const 0) (local.get $value) (i32.const 100))
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, 0_u64);
+    let instance = <Linker<u64>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Returns the [`TraceHash`] of calling the exported `func` of a fresh [`Instance`] with `input`.
+fn trace_hash_of(func: &str, input: i32) -> TraceHash {
+    let (mut store, instance) = test_setup();
+    let func = instance.get_func(&store, func).unwrap();
+    let mut results = vec![Val::I32(0); func.ty(&store).results().len()];
+    store.set_trace_hashing(true);
+    func.call(&mut store, &[Val::I32(input)], &mut results)
+        .unwrap();
+    store.trace_hash().unwrap()
+}
+
+#[test]
+fn trace_hashing_can_be_enabled_and_disabled() {
+    let (mut store, instance) = test_setup();
+    assert_eq!(store.trace_hash(), None);
+    store.set_trace_hashing(true);
+    let initial = store.trace_hash().unwrap();
+    let func = instance
+        .get_typed_func::<i32, i32>(&store, "count")
+        .unwrap();
+    func.call(&mut store, 10).unwrap();
+    assert_ne!(store.trace_hash().unwrap(), initial);
+    // Enabling trace hashing again restarts the running hash.
+    store.set_trace_hashing(true);
+    assert_eq!(store.trace_hash().unwrap(), initial);
+    store.set_trace_hashing(false);
+    assert_eq!(store.trace_hash(), None);
+}
+
+#[test]
+fn same_calls_produce_same_hash() {
+    for (func, input) in [
+        ("count", 10),
+        ("store", 42),
+        ("set_global", 42),
+        ("fill", 42),
+    ] {
+        assert_eq!(trace_hash_of(func, input), trace_hash_of(func, input));
+    }
+}
+
+#[test]
+fn diverging_control_flow_produces_different_hash() {
+    assert_ne!(trace_hash_of("count", 10), trace_hash_of("count", 11));
+}
+
+#[test]
+fn diverging_effects_produce_different_hash() {
+    // Note: the executed instructions are the same but their effects differ.
+    for func in ["store", "set_global", "fill"] {
+        assert_ne!(trace_hash_of(func, 1), trace_hash_of(func, 2));
+    }
+}
+
+#[test]
+fn trace_hashing_preserves_instruction_callback() {
+    let calls = |tracing: bool| {
+        let (mut store, instance) = test_setup();
+        store.set_trace_hashing(tracing);
+        store.instruction_callback(100, |calls| {
+            *calls += 1;
+            Ok(())
+        });
+        let func = instance
+            .get_typed_func::<i32, i32>(&store, "count")
+            .unwrap();
+        assert_eq!(func.call(&mut store, 10_000).unwrap(), 10_000);
+        *store.data()
+    };
+    assert_eq!(calls(true), calls(false));
+}
@@ -0,0 +1,77 @@
+//! Tests for the structured [`ValidationError`] information returned by Wasmi.
+//!
+//! [`ValidationError`]: wasmi::errors::ValidationError
+
+use wasmi::{CompilationMode, Config, Engine, Module};
+
+/// A Wasm module with a type mismatch at the `i32.add` of its second function.
+const WASM: &str = r#"
+    (module
+        (func)
+        (func (param i32) (result i32)
+            (i64.const 1)
+            (local.get 0)
+            (i32.add)
+        )
+    )
+"#;
+
+fn assert_type_mismatch(error: wasmi::Error) {
+    let error = error.as_validation_error().unwrap();
+    assert_eq!(error.func_index(), Some(1));
+    assert_eq!(error.operator(), Some("I32Add"));
+    assert!(error.message().starts_with("type mismatch"));
+}
+
+/// The binary encoding of [`WASM`].
+const WASM_BINARY: [u8; 38] = [
+    0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x09, 0x02, 0x60, 0x00, 0x00, 0x60, 0x01, 0x7F, 0x01, 0x7F, // types
+    0x03, 0x03, 0x02, 0x00, 0x01, // funcs
+    0x0A, 0x0C, 0x02, // code
+    0x02, 0x00, 0x0B, // func 0
+    0x07, 0x00, 0x42, 0x01, 0x20, 0x00, 0x6A, 0x0B, // func 1
+];
+
+/// Asserts that the offset of the `error` points at the `i32.add` of [`WASM_BINARY`].
+fn assert_offset(error: &wasmi::Error) {
+    let offset = error.as_validation_error().unwrap().offset();
+    assert_eq!(WASM_BINARY[offset], 0x6A); // `i32.add` opcode
+}
+
+#[test]
+fn module_new_reports_validation_error() {
+    let engine = Engine::default();
+    let error = Module::new(&engine, WASM).unwrap_err();
+    assert_type_mismatch(error);
+}
+
+#[test]
+fn module_validate_reports_validation_error() {
+    let engine = Engine::default();
+    // Note: `Module::validate` does not accept Wasm text.
+    let error = Module::validate(&engine, &WASM_BINARY).unwrap_err();
+    assert_offset(&error);
+    assert_type_mismatch(error);
+}
+
+#[test]
+fn lazy_translation_reports_validation_error() {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::LazyTranslation);
+    let engine = Engine::new(&config);
+    let error = Module::new(&engine, WASM).unwrap_err();
+    assert_type_mismatch(error);
+}
+
+#[test]
+fn lazy_compilation_reports_absolute_offset() {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &WASM_BINARY[..]).unwrap();
+    // Note: computing the code hash compiles all functions of the module.
+    let error = module.code_hash().unwrap_err();
+    assert_offset(&error);
+    assert_type_mismatch(error);
+}
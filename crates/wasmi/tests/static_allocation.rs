@@ -0,0 +1,139 @@
+//! Tests to check that executions do not heap allocate with [`Config::static_allocation`].
+//!
+//! # Note
+//!
+//! This is a separate test binary since it installs a global allocator.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+use wasmi::{
+    Caller,
+    Config,
+    Engine,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    StackLimits,
+    Store,
+    TypedFunc,
+};
+
+/// A global allocator counting the heap allocations of each thread.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Returns the number of heap allocations of the current thread while running `f`.
+fn count_allocations<R>(f: impl FnOnce() -> R) -> (usize, R) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let after = ALLOCATIONS.with(Cell::get);
+    (after - before, result)
+}
+
+/// A recursive Wasm function calling the host and writing to an imported linear memory.
+const WASM: &str = r#"
+    (module
+        (import "env" "memory" (memory 1 1))
+        (import "env" "log" (func $log (param i32)))
+        (func $fib (export "fib") (param $n i32) (result i32)
+            (call $log (local.get $n))
+            (i32.store (i32.mul (local.get $n) (i32.const 4)) (local.get $n))
+            (if (result i32) (i32.lt_u (local.get $n) (i32.const 2))
+                (then (local.get $n))
+                (else
+                    (i32.add
+                        (call $fib (i32.sub (local.get $n) (i32.const 1)))
+                        (call $fib (i32.sub (local.get $n) (i32.const 2)))
+                    )
+                )
+            )
+        )
+    )
+"#;
+
+/// Sets up [`WASM`] with `config` and returns its store and `fib` function.
+fn setup(config: &Config) -> (Store<u32>, TypedFunc<i32, i32>) {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, 0);
+    let buffer = Box::leak(vec![0_u8; 0x1_0000].into_boxed_slice());
+    let memory =
+        Memory::new_static(&mut store, MemoryType::new(1, Some(1)).unwrap(), buffer).unwrap();
+    let mut linker = <Linker<u32>>::new(&engine);
+    linker.define("env", "memory", memory).unwrap();
+    linker
+        .func_wrap("env", "log", |mut caller: Caller<u32>, _n: i32| {
+            *caller.data_mut() += 1;
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let fib = instance.get_typed_func::<i32, i32>(&store, "fib").unwrap();
+    (store, fib)
+}
+
+/// Returns a [`Config`] with small [`StackLimits`] and optional static allocation.
+fn config(static_allocation: bool) -> Config {
+    let mut config = Config::default();
+    config
+        .set_stack_limits(StackLimits::new(16, 4096, 64).unwrap())
+        .static_allocation(static_allocation);
+    config
+}
+
+#[test]
+fn executions_do_not_allocate() {
+    let (mut store, fib) = setup(&config(true));
+    for _ in 0..3 {
+        let (allocations, result) = count_allocations(|| fib.call(&mut store, 20));
+        assert_eq!(result.unwrap(), 6765);
+        assert_eq!(allocations, 0);
+    }
+    assert_eq!(*store.data(), 3 * 21891);
+}
+
+#[test]
+fn stack_overflow_does_not_allocate() {
+    let (mut store, fib) = setup(&config(true));
+    let (allocations, result) = count_allocations(|| fib.call(&mut store, 100));
+    assert!(result.is_err());
+    // Note: only the returned `Error` is heap allocated.
+    assert_eq!(allocations, 1);
+    assert_eq!(fib.call(&mut store, 10).unwrap(), 55);
+}
+
+#[test]
+fn executions_grow_stacks_by_default() {
+    let (mut store, fib) = setup(&config(false));
+    let (allocations, result) = count_allocations(|| fib.call(&mut store, 20));
+    assert_eq!(result.unwrap(), 6765);
+    assert_ne!(allocations, 0);
+}